@@ -152,6 +152,21 @@ class DealerSDK {
     };
   }
   
+  /**
+   * X-BlackBook-* headers for a role-gated route (signed by the dealer key)
+   */
+  createRoleHeaders(method, path, body) {
+    const timestamp = Math.floor(Date.now() / 1000);
+    const bodyHash = crypto.createHash('sha256').update(body).digest('hex');
+    const message = `${method} ${path}\n${timestamp}\n${bodyHash}`;
+    
+    return {
+      'X-BlackBook-Key': this.publicKey,
+      'X-BlackBook-Timestamp': timestamp.toString(),
+      'X-BlackBook-Signature': DealerCrypto.sign(this.privateKey, message),
+    };
+  }
+  
   /**
    * Create a signed generic request
    */
@@ -403,13 +418,12 @@ class DealerSDK {
    * @param {number} amountPerMarket - BB amount per market (0 = auto-calculate)
    */
   async fundAllMarkets(amountPerMarket = 0) {
-    const response = await fetch(`${this.l2Url}/dealer/fund-all-markets`, {
+    const path = '/dealer/fund-all-markets';
+    const body = JSON.stringify({ amount_per_market: amountPerMarket });
+    const response = await fetch(`${this.l2Url}${path}`, {
       method: 'POST',
-      headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify({
-        dealer_address: this.address,
-        amount_per_market: amountPerMarket,
-      }),
+      headers: { 'Content-Type': 'application/json', ...this.createRoleHeaders('POST', path, body) },
+      body,
     });
     
     if (!response.ok) throw new Error(`Fund markets failed: ${response.status}`);
@@ -0,0 +1,443 @@
+// ============================================================================
+// L1 Backend - Single abstraction for all L1 access
+// ============================================================================
+//
+// Everything the L2 asks of L1 goes through `L1Backend`. The backend is built
+// once at startup and injected through `AppState`, so handlers never build
+// their own HTTP clients or read L1 environment variables.
+//
+// Implementations:
+//   - L1BlackBookRpc: real HTTP (see l1_blackbook_rpc.rs)
+//   - MockL1Backend: in-process `MockL1` state (`l1.mock_mode = true`)
+//   - RecordingL1Backend: wraps another backend and records every call
+// ============================================================================
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::l1_blackbook_rpc::*;
+use super::l1_mock::{FaultAction, FaultConfig, MockL1};
+
+// ============================================================================
+// TRAIT
+// ============================================================================
+
+/// L1 operations used by the L2
+#[async_trait]
+pub trait L1Backend: Send + Sync {
+    /// Human-readable description for logs and status endpoints
+    fn describe(&self) -> String;
+
+    /// Whether this backend simulates L1 in-process
+    fn is_mock(&self) -> bool {
+        false
+    }
+
+    async fn health(&self) -> Result<L1HealthResponse, String>;
+    async fn poh_status(&self) -> Result<L1PoHStatus, String>;
+    async fn get_wallet_by_user_id(&self, user_id: &str) -> Result<L1WalletLookupResponse, String>;
+    async fn get_balance(&self, address: &str) -> Result<L1BalanceResponse, String>;
+    async fn get_nonce(&self, address: &str) -> Result<u64, String>;
+    async fn verify_signature(&self, request: &L1VerifyRequest) -> Result<L1VerifyResponse, String>;
+    async fn bridge_to_l1(&self, request: &L1BridgeRequest) -> Result<L1BridgeResponse, String>;
+    async fn withdraw_to_l1(&self, request: &L1WithdrawRequest) -> Result<L1WithdrawResponse, String>;
+    /// Look up a previously submitted withdrawal. `Ok(None)` if L1 has never seen it.
+    async fn get_withdrawal_status(&self, bridge_id: &str) -> Result<Option<L1WithdrawResponse>, String>;
+    /// Commit a withdrawal batch root; idempotent on batch_id
+    async fn commit_withdrawal_batch(&self, request: &L1WithdrawalBatchRequest) -> Result<L1WithdrawalBatchResponse, String>;
+    /// Look up a batch commitment. `Ok(None)` if L1 has never seen it.
+    async fn get_withdrawal_batch(&self, batch_id: u64) -> Result<Option<L1WithdrawalBatchResponse>, String>;
+    /// Post a signed L2 state root; idempotent on epoch
+    async fn submit_state_root(&self, request: &L1StateRootRequest) -> Result<L1StateRootResponse, String>;
+    async fn start_session(&self, request: &L1SessionStartRequest) -> Result<L1SessionStartResponse, String>;
+    async fn settle_session(&self, request: &L1SessionSettleRequest) -> Result<L1SessionSettleResponse, String>;
+    async fn get_session_status(&self, address: &str) -> Result<L1SessionStatusResponse, String>;
+    async fn submit_settlement(&self, request: &L1SettlementRequest) -> Result<L1SettlementResponse, String>;
+    /// Mint tokens to an address (liquidity seeding). Returns the L1 tx hash.
+    async fn mint(&self, to: &str, amount: f64) -> Result<String, String>;
+    /// Transfer tokens between L1 addresses. Returns the L1 tx hash.
+    async fn transfer(&self, from: &str, to: &str, amount: f64) -> Result<String, String>;
+}
+
+/// Build a backend from config: `mock` in-process, or HTTP
+pub fn l1_backend_from_config(config: L1RpcConfig, mock: MockL1) -> Arc<dyn L1Backend> {
+    if config.mock_mode {
+        Arc::new(MockL1Backend::new(mock))
+    } else {
+        Arc::new(L1BlackBookRpc::new(config))
+    }
+}
+
+// ============================================================================
+// MOCK BACKEND
+// ============================================================================
+
+/// In-process L1 backed by `MockL1`, with the mock server's fault injection
+#[derive(Debug, Default)]
+pub struct MockL1Backend {
+    state: Mutex<MockL1>,
+    faults: Mutex<FaultConfig>,
+}
+
+impl MockL1Backend {
+    pub fn new(l1: MockL1) -> Self {
+        Self { state: Mutex::new(l1), faults: Mutex::new(FaultConfig::default()) }
+    }
+
+    /// Direct access to the simulated chain (seeding, assertions)
+    pub fn state(&self) -> MutexGuard<'_, MockL1> {
+        self.state.lock().unwrap()
+    }
+
+    /// Fault injection settings, keyed by the mock server's route paths
+    pub fn faults(&self) -> MutexGuard<'_, FaultConfig> {
+        self.faults.lock().unwrap()
+    }
+
+    /// Run `f` against the chain as a request to `path` would: an injected
+    /// error leaves state untouched, a dropped response applies it first
+    fn call<T>(&self, path: &str, f: impl FnOnce(&mut MockL1) -> T) -> Result<T, String> {
+        let action = self.faults().decide(path);
+        match action {
+            FaultAction::Pass => Ok(f(&mut self.state())),
+            FaultAction::Error => Err(format!("injected fault on {}", path)),
+            FaultAction::Drop => {
+                let _ = f(&mut self.state());
+                Err(format!("dropped response on {}", path))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl L1Backend for MockL1Backend {
+    fn describe(&self) -> String {
+        "mock (in-process)".to_string()
+    }
+
+    fn is_mock(&self) -> bool {
+        true
+    }
+
+    async fn health(&self) -> Result<L1HealthResponse, String> {
+        self.call("/health", |l1| l1.health())
+    }
+
+    async fn poh_status(&self) -> Result<L1PoHStatus, String> {
+        self.call("/poh/status", |l1| l1.poh_status())
+    }
+
+    async fn get_wallet_by_user_id(&self, user_id: &str) -> Result<L1WalletLookupResponse, String> {
+        self.call(&format!("/auth/wallet/{}", user_id), |l1| l1.wallet(user_id).unwrap_or_else(|| L1WalletLookupResponse {
+            found: false,
+            user_id: user_id.to_string(),
+            wallet_address: None,
+            registered_at: None,
+        }))
+    }
+
+    async fn get_balance(&self, address: &str) -> Result<L1BalanceResponse, String> {
+        self.call(&format!("/balance/{}", address), |l1| l1.balance(address))
+    }
+
+    async fn get_nonce(&self, address: &str) -> Result<u64, String> {
+        self.call(&format!("/rpc/nonce/{}", address), |l1| l1.nonce(address))
+    }
+
+    async fn verify_signature(&self, request: &L1VerifyRequest) -> Result<L1VerifyResponse, String> {
+        self.call("/rpc/verify", |l1| l1.verify_signature(request))
+    }
+
+    async fn bridge_to_l1(&self, request: &L1BridgeRequest) -> Result<L1BridgeResponse, String> {
+        self.call("/rpc/bridge", |l1| l1.bridge(request))
+    }
+
+    async fn withdraw_to_l1(&self, request: &L1WithdrawRequest) -> Result<L1WithdrawResponse, String> {
+        self.call("/bridge/withdraw", |l1| l1.withdraw(request))
+    }
+
+    async fn get_withdrawal_status(&self, bridge_id: &str) -> Result<Option<L1WithdrawResponse>, String> {
+        self.call(&format!("/bridge/withdraw/{}", bridge_id), |l1| l1.withdrawal(bridge_id))
+    }
+
+    async fn commit_withdrawal_batch(&self, request: &L1WithdrawalBatchRequest) -> Result<L1WithdrawalBatchResponse, String> {
+        self.call("/bridge/batch", |l1| l1.commit_withdrawal_batch(request))
+    }
+
+    async fn get_withdrawal_batch(&self, batch_id: u64) -> Result<Option<L1WithdrawalBatchResponse>, String> {
+        self.call(&format!("/bridge/batch/{}", batch_id), |l1| l1.withdrawal_batch(batch_id))
+    }
+
+    async fn submit_state_root(&self, request: &L1StateRootRequest) -> Result<L1StateRootResponse, String> {
+        self.call("/l2/state-root", |l1| l1.submit_state_root(request))
+    }
+
+    async fn start_session(&self, request: &L1SessionStartRequest) -> Result<L1SessionStartResponse, String> {
+        self.call("/session/start", |l1| l1.start_session(request))
+    }
+
+    async fn settle_session(&self, request: &L1SessionSettleRequest) -> Result<L1SessionSettleResponse, String> {
+        self.call("/session/settle", |l1| l1.settle_session(request))
+    }
+
+    async fn get_session_status(&self, address: &str) -> Result<L1SessionStatusResponse, String> {
+        self.call(&format!("/session/status/{}", address), |l1| l1.session_status(address).unwrap_or_else(|| L1SessionStatusResponse {
+            success: true,
+            session_id: None,
+            wallet_address: address.to_string(),
+            l1_balance: l1.balance(address).balance,
+            l2_credit: 0.0,
+            status: "none".to_string(),
+            created_at: None,
+            expires_at: None,
+            error: None,
+        }))
+    }
+
+    async fn submit_settlement(&self, request: &L1SettlementRequest) -> Result<L1SettlementResponse, String> {
+        self.call("/rpc/settlement", |l1| l1.record_settlement(request))
+    }
+
+    async fn mint(&self, to: &str, amount: f64) -> Result<String, String> {
+        self.call("/admin/mint", |l1| l1.mint(to, amount))
+    }
+
+    async fn transfer(&self, from: &str, to: &str, amount: f64) -> Result<String, String> {
+        self.call("/transfer", |l1| l1.transfer(from, to, amount))?
+    }
+}
+
+// ============================================================================
+// RECORDING BACKEND
+// ============================================================================
+
+/// A single recorded L1 call
+#[derive(Debug, Clone, Serialize)]
+pub struct L1Call {
+    pub method: &'static str,
+    pub request: Value,
+    pub response: Result<Value, String>,
+    pub at: u64,
+}
+
+/// Wraps a backend and records every call and its result
+pub struct RecordingL1Backend {
+    inner: Arc<dyn L1Backend>,
+    calls: Mutex<Vec<L1Call>>,
+}
+
+impl RecordingL1Backend {
+    pub fn new(inner: Arc<dyn L1Backend>) -> Self {
+        Self { inner, calls: Mutex::new(Vec::new()) }
+    }
+
+    /// Calls recorded so far, oldest first
+    pub fn calls(&self) -> Vec<L1Call> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Recorded calls to one method
+    pub fn calls_to(&self, method: &str) -> Vec<L1Call> {
+        self.calls().into_iter().filter(|c| c.method == method).collect()
+    }
+
+    pub fn clear(&self) {
+        self.calls.lock().unwrap().clear();
+    }
+
+    fn record<T: Serialize>(&self, method: &'static str, request: Value, result: &Result<T, String>) {
+        let response = match result {
+            Ok(value) => Ok(serde_json::to_value(value).unwrap_or(Value::Null)),
+            Err(e) => Err(e.clone()),
+        };
+        let at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.calls.lock().unwrap().push(L1Call { method, request, response, at });
+    }
+}
+
+#[async_trait]
+impl L1Backend for RecordingL1Backend {
+    fn describe(&self) -> String {
+        format!("recording {}", self.inner.describe())
+    }
+
+    fn is_mock(&self) -> bool {
+        self.inner.is_mock()
+    }
+
+    async fn health(&self) -> Result<L1HealthResponse, String> {
+        let result = self.inner.health().await;
+        self.record("health", Value::Null, &result);
+        result
+    }
+
+    async fn poh_status(&self) -> Result<L1PoHStatus, String> {
+        let result = self.inner.poh_status().await;
+        self.record("poh_status", Value::Null, &result);
+        result
+    }
+
+    async fn get_wallet_by_user_id(&self, user_id: &str) -> Result<L1WalletLookupResponse, String> {
+        let result = self.inner.get_wallet_by_user_id(user_id).await;
+        self.record("get_wallet_by_user_id", json!({ "user_id": user_id }), &result);
+        result
+    }
+
+    async fn get_balance(&self, address: &str) -> Result<L1BalanceResponse, String> {
+        let result = self.inner.get_balance(address).await;
+        self.record("get_balance", json!({ "address": address }), &result);
+        result
+    }
+
+    async fn get_nonce(&self, address: &str) -> Result<u64, String> {
+        let result = self.inner.get_nonce(address).await;
+        self.record("get_nonce", json!({ "address": address }), &result);
+        result
+    }
+
+    async fn verify_signature(&self, request: &L1VerifyRequest) -> Result<L1VerifyResponse, String> {
+        let result = self.inner.verify_signature(request).await;
+        self.record("verify_signature", json!(request), &result);
+        result
+    }
+
+    async fn bridge_to_l1(&self, request: &L1BridgeRequest) -> Result<L1BridgeResponse, String> {
+        let result = self.inner.bridge_to_l1(request).await;
+        self.record("bridge_to_l1", json!(request), &result);
+        result
+    }
+
+    async fn withdraw_to_l1(&self, request: &L1WithdrawRequest) -> Result<L1WithdrawResponse, String> {
+        let result = self.inner.withdraw_to_l1(request).await;
+        self.record("withdraw_to_l1", json!(request), &result);
+        result
+    }
+
+    async fn get_withdrawal_status(&self, bridge_id: &str) -> Result<Option<L1WithdrawResponse>, String> {
+        let result = self.inner.get_withdrawal_status(bridge_id).await;
+        self.record("get_withdrawal_status", json!({ "bridge_id": bridge_id }), &result);
+        result
+    }
+
+    async fn commit_withdrawal_batch(&self, request: &L1WithdrawalBatchRequest) -> Result<L1WithdrawalBatchResponse, String> {
+        let result = self.inner.commit_withdrawal_batch(request).await;
+        self.record("commit_withdrawal_batch", json!(request), &result);
+        result
+    }
+
+    async fn get_withdrawal_batch(&self, batch_id: u64) -> Result<Option<L1WithdrawalBatchResponse>, String> {
+        let result = self.inner.get_withdrawal_batch(batch_id).await;
+        self.record("get_withdrawal_batch", json!({ "batch_id": batch_id }), &result);
+        result
+    }
+
+    async fn submit_state_root(&self, request: &L1StateRootRequest) -> Result<L1StateRootResponse, String> {
+        let result = self.inner.submit_state_root(request).await;
+        self.record("submit_state_root", json!(request), &result);
+        result
+    }
+
+    async fn start_session(&self, request: &L1SessionStartRequest) -> Result<L1SessionStartResponse, String> {
+        let result = self.inner.start_session(request).await;
+        self.record("start_session", json!(request), &result);
+        result
+    }
+
+    async fn settle_session(&self, request: &L1SessionSettleRequest) -> Result<L1SessionSettleResponse, String> {
+        let result = self.inner.settle_session(request).await;
+        self.record("settle_session", json!(request), &result);
+        result
+    }
+
+    async fn get_session_status(&self, address: &str) -> Result<L1SessionStatusResponse, String> {
+        let result = self.inner.get_session_status(address).await;
+        self.record("get_session_status", json!({ "address": address }), &result);
+        result
+    }
+
+    async fn submit_settlement(&self, request: &L1SettlementRequest) -> Result<L1SettlementResponse, String> {
+        let result = self.inner.submit_settlement(request).await;
+        self.record("submit_settlement", json!(request), &result);
+        result
+    }
+
+    async fn mint(&self, to: &str, amount: f64) -> Result<String, String> {
+        let result = self.inner.mint(to, amount).await;
+        self.record("mint", json!({ "to": to, "amount": amount }), &result);
+        result
+    }
+
+    async fn transfer(&self, from: &str, to: &str, amount: f64) -> Result<String, String> {
+        let result = self.inner.transfer(from, to, amount).await;
+        self.record("transfer", json!({ "from": from, "to": to, "amount": amount }), &result);
+        result
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_backend_withdraw_and_balance() {
+        let backend = MockL1Backend::new(MockL1::new(0.0));
+        let response = backend.withdraw_to_l1(&L1WithdrawRequest {
+            from_l2_address: "L2_ALICE".to_string(),
+            to_l1_address: "L1_ALICE".to_string(),
+            amount: 75.0,
+            bridge_id: "b1".to_string(),
+            signature: String::new(),
+            timestamp: 0,
+            nonce: "1".to_string(),
+        }).await.unwrap();
+        assert!(response.success);
+        assert_eq!(backend.get_balance("L1_ALICE").await.unwrap().balance, 75.0);
+        assert_eq!(backend.get_session_status("L1_ALICE").await.unwrap().status, "none");
+    }
+
+    #[tokio::test]
+    async fn test_mock_backend_injected_faults() {
+        let backend = MockL1Backend::new(MockL1::new(0.0));
+        {
+            let mut faults = backend.faults();
+            faults.fail_next = 1;
+            faults.drop_next = 1;
+            faults.paths = vec!["/admin/mint".to_string()];
+        }
+
+        // Errors leave L1 untouched, dropped responses still take effect
+        assert!(backend.health().await.is_ok());
+        assert!(backend.mint("L1_ALICE", 10.0).await.is_err());
+        assert_eq!(backend.state().balance("L1_ALICE").balance, 0.0);
+        assert!(backend.mint("L1_ALICE", 10.0).await.is_err());
+        assert_eq!(backend.state().balance("L1_ALICE").balance, 10.0);
+        assert!(backend.mint("L1_ALICE", 10.0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_recording_backend_records_calls() {
+        let inner: Arc<dyn L1Backend> = Arc::new(MockL1Backend::new(MockL1::new(100.0)));
+        let recorder = RecordingL1Backend::new(inner);
+
+        recorder.mint("escrow:m1", 500.0).await.unwrap();
+        assert!(recorder.transfer("L1_BOB", "escrow:m1", 1000.0).await.is_err());
+        recorder.get_balance("escrow:m1").await.unwrap();
+
+        let calls = recorder.calls();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].method, "mint");
+        assert!(calls[1].response.is_err());
+        assert_eq!(recorder.calls_to("get_balance")[0].response.as_ref().unwrap()["balance"], 600.0);
+        assert!(recorder.is_mock());
+    }
+}
@@ -2,8 +2,7 @@
 // L1 BlackBook RPC - Layer 1 Blockchain Communication
 // ============================================================================
 //
-// Wrapper for L1 blockchain RPC calls specific to BlackBook.
-// Provides high-level methods for wallet operations, settlements, and bridging.
+// Wire types for the L1 API and the HTTP implementation of `L1Backend`.
 //
 // L1 Endpoints:
 //   GET  /health              - L1 health check
@@ -11,14 +10,30 @@
 //   GET  /rpc/nonce/:address  - Get account nonce
 //   POST /rpc/verify          - Verify signature
 //   POST /rpc/settlement      - Record market settlement
+//   POST /rpc/bridge          - L2→L1 bridge
+//   POST /bridge/withdraw     - Release withdrawn funds on L1
+//   GET  /bridge/withdraw/:bridge_id - Withdrawal status (reconciliation)
+//   POST /bridge/batch        - Commit a withdrawal batch Merkle root
+//   GET  /bridge/batch/:batch_id - Withdrawal batch status
+//   POST /bridge/claim        - User claims a batched withdrawal (not called by L2)
+//   POST /l2/state-root       - Post a signed L2 state root
+//   POST /session/start       - Lock L1 funds for an L2 session
+//   POST /session/settle      - Write session PnL back to L1
+//   GET  /session/status/:address - Session status
+//   POST /admin/mint          - Mint (liquidity seeding)
+//   POST /transfer            - L1 transfer
 //   GET  /auth/wallet/:userId - Get wallet by Supabase user ID
 //   GET  /poh/status          - Proof of History status
 //
 // ============================================================================
 
-use serde::{Deserialize, Serialize};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::time::Duration;
 
+use super::l1_backend::L1Backend;
+use crate::withdrawal_batch::WithdrawalLeaf;
+
 // ============================================================================
 // CONSTANTS
 // ============================================================================
@@ -96,6 +111,39 @@ pub struct L1BridgeResponse {
     pub error: Option<String>,
 }
 
+/// Signature verification request (POST /rpc/verify)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1VerifyRequest {
+    pub address: String,
+    pub message: String,
+    pub signature: String,
+}
+
+/// Signature verification response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1VerifyResponse {
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// Market settlement record sent to L1 (POST /rpc/settlement)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1SettlementRequest {
+    pub market_id: String,
+    pub winning_outcome: usize,
+    pub total_payout: f64,
+    pub num_winners: usize,
+    pub resolved_at: u64,
+}
+
+/// Settlement response from L1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1SettlementResponse {
+    pub success: bool,
+    pub l1_tx_hash: Option<String>,
+    pub error: Option<String>,
+}
+
 // ============================================================================
 // L1 SESSION TYPES (Optimistic Execution)
 // ============================================================================
@@ -181,6 +229,76 @@ pub struct L1WithdrawResponse {
     pub error: Option<String>,
 }
 
+// ============================================================================
+// L1 WITHDRAWAL BATCH TYPES
+// ============================================================================
+
+/// Commit a batch of L2→L1 withdrawals as a single Merkle root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1WithdrawalBatchRequest {
+    pub batch_id: u64,
+    /// Merkle root over (address, amount, nonce) leaves (hex)
+    pub root: String,
+    pub leaf_count: usize,
+    pub total_amount: f64,
+    pub sealed_at: u64,
+}
+
+/// Batch commitment response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1WithdrawalBatchResponse {
+    pub success: bool,
+    pub batch_id: u64,
+    pub l1_tx_hash: Option<String>,
+    pub status: String,  // "committed", "failed"
+    pub error: Option<String>,
+}
+
+/// User claim of one batched withdrawal, proven against the batch root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1WithdrawalClaimRequest {
+    pub batch_id: u64,
+    pub leaf: WithdrawalLeaf,
+    pub proof: merkle::Proof<Vec<u8>>,
+}
+
+/// Claim response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1WithdrawalClaimResponse {
+    pub success: bool,
+    pub l1_tx_hash: Option<String>,
+    pub new_l1_balance: Option<f64>,
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// L1 STATE ROOT TYPES
+// ============================================================================
+
+/// Signed commitment to the full L2 state (balances, positions, markets)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1StateRootRequest {
+    pub epoch: u64,
+    /// Merkle root over the sorted state leaves (hex)
+    pub root: String,
+    pub leaf_count: usize,
+    pub l2_block: u64,
+    pub created_at: u64,
+    /// Sequencer Ed25519 public key (hex)
+    pub sequencer_pubkey: String,
+    /// Signature over `state_root_signing_bytes` (hex)
+    pub signature: String,
+}
+
+/// State root response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1StateRootResponse {
+    pub success: bool,
+    pub epoch: u64,
+    pub l1_tx_hash: Option<String>,
+    pub error: Option<String>,
+}
+
 // ============================================================================
 // L1 RPC CONFIG
 // ============================================================================
@@ -217,22 +335,6 @@ impl Default for L1RpcConfig {
 }
 
 impl L1RpcConfig {
-    /// Create config from environment variable
-    pub fn from_env() -> Self {
-        let endpoint = std::env::var("L1_RPC_URL")
-            .unwrap_or_else(|_| L1_DEFAULT_ENDPOINT.to_string());
-        
-        let mock_mode = std::env::var("L1_MOCK_MODE")
-            .map(|v| v == "true" || v == "1")
-            .unwrap_or(false);
-        
-        Self {
-            endpoint,
-            mock_mode,
-            ..Default::default()
-        }
-    }
-    
     /// Create mock mode config (for testing)
     pub fn mock() -> Self {
         Self {
@@ -246,37 +348,24 @@ impl L1RpcConfig {
 // L1 BLACKBOOK RPC CLIENT
 // ============================================================================
 
-/// High-level L1 RPC client for BlackBook operations
+/// HTTP implementation of `L1Backend`
 #[derive(Debug, Clone)]
 pub struct L1BlackBookRpc {
     /// Configuration
     pub config: L1RpcConfig,
     
-    /// Whether connected to L1
-    pub connected: bool,
-    
-    /// Last successful call timestamp
-    pub last_call: Option<u64>,
+    /// Shared HTTP client (connection pooling)
+    client: reqwest::Client,
 }
 
 impl L1BlackBookRpc {
     /// Create a new L1 BlackBook RPC client
     pub fn new(config: L1RpcConfig) -> Self {
-        Self {
-            config,
-            connected: false,
-            last_call: None,
-        }
-    }
-    
-    /// Create from environment
-    pub fn from_env() -> Self {
-        Self::new(L1RpcConfig::from_env())
-    }
-    
-    /// Create in mock mode
-    pub fn mock() -> Self {
-        Self::new(L1RpcConfig::mock())
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
     }
     
     /// Get the L1 endpoint URL
@@ -284,445 +373,202 @@ impl L1BlackBookRpc {
         &self.config.endpoint
     }
     
-    /// Check if in mock mode
-    pub fn is_mock(&self) -> bool {
-        self.config.mock_mode
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.endpoint.trim_end_matches('/'), path)
     }
     
-    /// Update last call timestamp
-    fn update_last_call(&mut self) {
-        self.last_call = Some(
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-        );
+    /// Parse a response body, turning non-2xx statuses into errors
+    async fn parse<T: DeserializeOwned>(response: reqwest::Response, what: &str) -> Result<T, String> {
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("L1 {} failed with status {}: {}", what, status, body));
+        }
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| format!("Failed to parse L1 {} response: {}", what, e))
     }
     
-    // ========================================================================
-    // HEALTH & STATUS
-    // ========================================================================
-    
-    /// Check L1 health
-    /// GET /health
-    pub async fn health(&mut self) -> Result<L1HealthResponse, String> {
-        if self.config.mock_mode {
-            return Ok(L1HealthResponse {
-                status: "healthy".to_string(),
-                version: Some("mock-1.0.0".to_string()),
-                slot: Some(12345),
-                epoch: Some(100),
-                uptime_secs: Some(3600),
-            });
-        }
-        
-        let url = format!("{}/health", self.config.endpoint);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .get(&url)
+    /// GET a JSON resource; `Ok(None)` on 404
+    async fn get_optional<T: DeserializeOwned>(&self, path: &str, what: &str) -> Result<Option<T>, String> {
+        let response = self.client
+            .get(self.url(path))
             .send()
             .await
-            .map_err(|e| format!("L1 health check failed: {}", e))?;
-        
-        if response.status().is_success() {
-            self.connected = true;
-            self.update_last_call();
-            
-            response
-                .json::<L1HealthResponse>()
-                .await
-                .map_err(|e| format!("Failed to parse L1 health response: {}", e))
-        } else {
-            self.connected = false;
-            Err(format!("L1 health check returned status: {}", response.status()))
+            .map_err(|e| format!("L1 {} failed: {}", what, e))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
         }
+        Self::parse(response, what).await.map(Some)
     }
     
-    /// Get PoH status
-    /// GET /poh/status
-    pub async fn poh_status(&mut self) -> Result<L1PoHStatus, String> {
-        if self.config.mock_mode {
-            return Ok(L1PoHStatus {
-                enabled: true,
-                current_slot: 12345,
-                current_hash: "0".repeat(64),
-                tick_rate_ms: 400,
-                entries_since_genesis: 1_000_000,
-            });
-        }
-        
-        let url = format!("{}/poh/status", self.config.endpoint);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .get(&url)
+    async fn get<T: DeserializeOwned>(&self, path: &str, what: &str) -> Result<T, String> {
+        let response = self.client
+            .get(self.url(path))
             .send()
             .await
-            .map_err(|e| format!("L1 PoH status failed: {}", e))?;
-        
-        self.update_last_call();
-        
-        response
-            .json::<L1PoHStatus>()
-            .await
-            .map_err(|e| format!("Failed to parse L1 PoH status: {}", e))
+            .map_err(|e| format!("L1 {} failed: {}", what, e))?;
+        Self::parse(response, what).await
     }
     
-    // ========================================================================
-    // WALLET OPERATIONS
-    // ========================================================================
+    async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(&self, path: &str, body: &B, what: &str) -> Result<T, String> {
+        self.send_post(self.client.post(self.url(path)), body, what).await
+    }
     
-    /// Get wallet by Supabase user ID
-    /// GET /auth/wallet/:userId
-    pub async fn get_wallet_by_user_id(&mut self, user_id: &str) -> Result<L1WalletLookupResponse, String> {
-        if self.config.mock_mode {
-            // In mock mode, return a deterministic wallet address
-            let mock_address = format!("{:0>64}", hex::encode(user_id.as_bytes()));
-            return Ok(L1WalletLookupResponse {
-                found: true,
-                user_id: user_id.to_string(),
-                wallet_address: Some(mock_address),
-                registered_at: Some(1700000000),
-            });
-        }
-        
-        let url = format!("{}/auth/wallet/{}", self.config.endpoint, user_id);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .get(&url)
+    /// POST with an `Idempotency-Key` header so L1 can dedupe retries
+    async fn post_idempotent<B: Serialize + ?Sized, T: DeserializeOwned>(&self, path: &str, body: &B, key: &str, what: &str) -> Result<T, String> {
+        let request = self.client
+            .post(self.url(path))
+            .header("Idempotency-Key", key);
+        self.send_post(request, body, what).await
+    }
+    
+    async fn send_post<B: Serialize + ?Sized, T: DeserializeOwned>(&self, request: reqwest::RequestBuilder, body: &B, what: &str) -> Result<T, String> {
+        let response = request
+            .json(body)
             .send()
             .await
-            .map_err(|e| format!("L1 wallet lookup failed: {}", e))?;
-        
-        self.update_last_call();
-        
-        if response.status().is_success() {
-            response
-                .json::<L1WalletLookupResponse>()
-                .await
-                .map_err(|e| format!("Failed to parse wallet lookup response: {}", e))
-        } else if response.status().as_u16() == 404 {
-            Ok(L1WalletLookupResponse {
-                found: false,
-                user_id: user_id.to_string(),
-                wallet_address: None,
-                registered_at: None,
-            })
-        } else {
-            Err(format!("L1 wallet lookup returned status: {}", response.status()))
-        }
+            .map_err(|e| format!("L1 {} failed: {}", what, e))?;
+        Self::parse(response, what).await
+    }
+}
+
+#[async_trait]
+impl L1Backend for L1BlackBookRpc {
+    fn describe(&self) -> String {
+        format!("http ({})", self.config.endpoint)
+    }
+    
+    /// GET /health
+    async fn health(&self) -> Result<L1HealthResponse, String> {
+        self.get("/health", "health check").await
+    }
+    
+    /// GET /poh/status
+    async fn poh_status(&self) -> Result<L1PoHStatus, String> {
+        self.get("/poh/status", "PoH status").await
+    }
+    
+    /// GET /auth/wallet/:userId
+    async fn get_wallet_by_user_id(&self, user_id: &str) -> Result<L1WalletLookupResponse, String> {
+        let found = self.get_optional(&format!("/auth/wallet/{}", user_id), "wallet lookup").await?;
+        Ok(found.unwrap_or_else(|| L1WalletLookupResponse {
+            found: false,
+            user_id: user_id.to_string(),
+            wallet_address: None,
+            registered_at: None,
+        }))
     }
     
-    /// Get balance for an address
     /// GET /balance/:address
-    pub async fn get_balance(&mut self, address: &str) -> Result<L1BalanceResponse, String> {
-        if self.config.mock_mode {
-            return Ok(L1BalanceResponse {
-                address: address.to_string(),
-                balance: 10000.0, // Mock balance
-                exists: true,
-            });
-        }
-        
-        let url = format!("{}/balance/{}", self.config.endpoint, address);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("L1 balance lookup failed: {}", e))?;
-        
-        self.update_last_call();
-        
-        response
-            .json::<L1BalanceResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse balance response: {}", e))
+    async fn get_balance(&self, address: &str) -> Result<L1BalanceResponse, String> {
+        self.get(&format!("/balance/{}", address), "balance lookup").await
     }
     
-    /// Get nonce for an address
     /// GET /rpc/nonce/:address
-    pub async fn get_nonce(&mut self, address: &str) -> Result<u64, String> {
-        if self.config.mock_mode {
-            return Ok(0); // Mock nonce starts at 0
-        }
-        
-        let url = format!("{}/rpc/nonce/{}", self.config.endpoint, address);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("L1 nonce lookup failed: {}", e))?;
-        
-        self.update_last_call();
-        
+    async fn get_nonce(&self, address: &str) -> Result<u64, String> {
         #[derive(Deserialize)]
         struct NonceResp {
             nonce: u64,
         }
         
-        let nonce_resp = response
-            .json::<NonceResp>()
-            .await
-            .map_err(|e| format!("Failed to parse nonce response: {}", e))?;
-        
-        Ok(nonce_resp.nonce)
+        let resp: NonceResp = self.get(&format!("/rpc/nonce/{}", address), "nonce lookup").await?;
+        Ok(resp.nonce)
     }
     
-    // ========================================================================
-    // BRIDGE OPERATIONS
-    // ========================================================================
+    /// POST /rpc/verify
+    async fn verify_signature(&self, request: &L1VerifyRequest) -> Result<L1VerifyResponse, String> {
+        self.post("/rpc/verify", request, "signature verification").await
+    }
     
-    /// Request bridge from L2 to L1
     /// POST /rpc/bridge
-    pub async fn bridge_to_l1(&mut self, request: L1BridgeRequest) -> Result<L1BridgeResponse, String> {
-        if self.config.mock_mode {
-            return Ok(L1BridgeResponse {
-                success: true,
-                bridge_id: Some(format!("bridge_{}", uuid::Uuid::new_v4())),
-                l1_tx_hash: Some("0".repeat(64)),
-                status: "pending".to_string(),
-                error: None,
-            });
-        }
-        
-        let url = format!("{}/rpc/bridge", self.config.endpoint);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("L1 bridge request failed: {}", e))?;
-        
-        self.update_last_call();
-        
-        response
-            .json::<L1BridgeResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse bridge response: {}", e))
-    }
-
-    /// Request L2→L1 withdrawal (unlock tokens on L1)
-    /// POST /bridge/withdraw
-    pub async fn withdraw_to_l1(&mut self, request: L1WithdrawRequest) -> Result<L1WithdrawResponse, String> {
-        if self.config.mock_mode {
-            return Ok(L1WithdrawResponse {
-                success: true,
-                bridge_id: Some(request.bridge_id.clone()),
-                l1_tx_hash: Some(format!("0x{}", "a".repeat(64))),
-                status: "pending".to_string(),
-                new_l1_balance: Some(request.amount),
-                error: None,
-            });
-        }
-        
-        let url = format!("{}/bridge/withdraw", self.config.endpoint);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("L1 withdraw request failed: {}", e))?;
-        
-        self.update_last_call();
-        
-        if response.status().is_success() {
-            response
-                .json::<L1WithdrawResponse>()
-                .await
-                .map_err(|e| format!("Failed to parse withdraw response: {}", e))
-        } else {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            Err(format!("L1 withdraw failed with status {}: {}", status, body))
-        }
+    async fn bridge_to_l1(&self, request: &L1BridgeRequest) -> Result<L1BridgeResponse, String> {
+        self.post("/rpc/bridge", request, "bridge").await
+    }
+    
+    /// POST /bridge/withdraw (bridge_id doubles as the idempotency key)
+    async fn withdraw_to_l1(&self, request: &L1WithdrawRequest) -> Result<L1WithdrawResponse, String> {
+        self.post_idempotent("/bridge/withdraw", request, &request.bridge_id, "withdraw").await
+    }
+    
+    /// GET /bridge/withdraw/:bridge_id
+    async fn get_withdrawal_status(&self, bridge_id: &str) -> Result<Option<L1WithdrawResponse>, String> {
+        self.get_optional(&format!("/bridge/withdraw/{}", bridge_id), "withdrawal status").await
+    }
+    
+    /// POST /bridge/batch
+    async fn commit_withdrawal_batch(&self, request: &L1WithdrawalBatchRequest) -> Result<L1WithdrawalBatchResponse, String> {
+        let key = format!("batch_{}", request.batch_id);
+        self.post_idempotent("/bridge/batch", request, &key, "withdrawal batch commit").await
+    }
+    
+    /// GET /bridge/batch/:batch_id
+    async fn get_withdrawal_batch(&self, batch_id: u64) -> Result<Option<L1WithdrawalBatchResponse>, String> {
+        self.get_optional(&format!("/bridge/batch/{}", batch_id), "withdrawal batch status").await
+    }
+    
+    /// POST /l2/state-root
+    async fn submit_state_root(&self, request: &L1StateRootRequest) -> Result<L1StateRootResponse, String> {
+        let key = format!("state_root_{}", request.epoch);
+        self.post_idempotent("/l2/state-root", request, &key, "state root").await
     }
-
-    // ========================================================================
-    // SESSION OPERATIONS (Optimistic Execution)
-    // ========================================================================
     
-    /// Start an L2 session on L1 (locks L1 balance for L2 use)
     /// POST /session/start
-    pub async fn start_session(&mut self, request: L1SessionStartRequest) -> Result<L1SessionStartResponse, String> {
-        if self.config.mock_mode {
-            return Ok(L1SessionStartResponse {
-                success: true,
-                session_id: Some(format!("session_{}", uuid::Uuid::new_v4())),
-                l1_balance: Some(10000.0),
-                l2_credit: Some(request.requested_amount),
-                expires_at: Some(
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs() + 3600  // 1 hour session
-                ),
-                error: None,
-            });
-        }
-        
-        let url = format!("{}/session/start", self.config.endpoint);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("L1 session start failed: {}", e))?;
-        
-        self.update_last_call();
-        self.connected = response.status().is_success();
-        
-        if response.status().is_success() {
-            response
-                .json::<L1SessionStartResponse>()
-                .await
-                .map_err(|e| format!("Failed to parse session start response: {}", e))
-        } else {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            Err(format!("L1 session start failed with status {}: {}", status, body))
-        }
+    async fn start_session(&self, request: &L1SessionStartRequest) -> Result<L1SessionStartResponse, String> {
+        self.post("/session/start", request, "session start").await
     }
     
-    /// Settle an L2 session on L1 (write PnL back to L1)
     /// POST /session/settle
-    pub async fn settle_session(&mut self, request: L1SessionSettleRequest) -> Result<L1SessionSettleResponse, String> {
-        if self.config.mock_mode {
-            return Ok(L1SessionSettleResponse {
-                success: true,
-                l1_tx_hash: Some(format!("0x{}", "b".repeat(64))),
-                new_l1_balance: Some(request.final_l2_balance),
-                settled_pnl: Some(request.pnl),
-                error: None,
-            });
-        }
-        
-        let url = format!("{}/session/settle", self.config.endpoint);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("L1 session settle failed: {}", e))?;
-        
-        self.update_last_call();
-        
-        if response.status().is_success() {
-            response
-                .json::<L1SessionSettleResponse>()
-                .await
-                .map_err(|e| format!("Failed to parse session settle response: {}", e))
-        } else {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            Err(format!("L1 session settle failed with status {}: {}", status, body))
-        }
+    async fn settle_session(&self, request: &L1SessionSettleRequest) -> Result<L1SessionSettleResponse, String> {
+        self.post("/session/settle", request, "session settle").await
     }
     
-    /// Get session status from L1
     /// GET /session/status/:address
-    pub async fn get_session_status(&mut self, address: &str) -> Result<L1SessionStatusResponse, String> {
-        if self.config.mock_mode {
-            return Ok(L1SessionStatusResponse {
-                success: true,
-                session_id: None,
-                wallet_address: address.to_string(),
-                l1_balance: 10000.0,
-                l2_credit: 0.0,
-                status: "none".to_string(),
-                created_at: None,
-                expires_at: None,
-                error: None,
-            });
-        }
-        
-        let url = format!("{}/session/status/{}", self.config.endpoint, address);
-        
-        let client = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .build()
-            .map_err(|e| format!("Failed to create client: {}", e))?;
-        
-        let response = client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("L1 session status failed: {}", e))?;
-        
-        self.update_last_call();
-        
-        if response.status().is_success() {
-            response
-                .json::<L1SessionStatusResponse>()
-                .await
-                .map_err(|e| format!("Failed to parse session status response: {}", e))
-        } else if response.status().as_u16() == 404 {
-            Ok(L1SessionStatusResponse {
-                success: true,
-                session_id: None,
-                wallet_address: address.to_string(),
-                l1_balance: 0.0,
-                l2_credit: 0.0,
-                status: "none".to_string(),
-                created_at: None,
-                expires_at: None,
-                error: None,
-            })
-        } else {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            Err(format!("L1 session status failed with status {}: {}", status, body))
-        }
+    async fn get_session_status(&self, address: &str) -> Result<L1SessionStatusResponse, String> {
+        let found = self.get_optional(&format!("/session/status/{}", address), "session status").await?;
+        Ok(found.unwrap_or_else(|| L1SessionStatusResponse {
+            success: true,
+            session_id: None,
+            wallet_address: address.to_string(),
+            l1_balance: 0.0,
+            l2_credit: 0.0,
+            status: "none".to_string(),
+            created_at: None,
+            expires_at: None,
+            error: None,
+        }))
+    }
+    
+    /// POST /rpc/settlement
+    async fn submit_settlement(&self, request: &L1SettlementRequest) -> Result<L1SettlementResponse, String> {
+        self.post("/rpc/settlement", request, "settlement").await
+    }
+    
+    /// POST /admin/mint
+    async fn mint(&self, to: &str, amount: f64) -> Result<String, String> {
+        let body: serde_json::Value = self
+            .post("/admin/mint", &serde_json::json!({ "to": to, "amount": amount }), "mint")
+            .await?;
+        Ok(tx_hash_from(&body))
     }
+    
+    /// POST /transfer
+    async fn transfer(&self, from: &str, to: &str, amount: f64) -> Result<String, String> {
+        let body: serde_json::Value = self
+            .post("/transfer", &serde_json::json!({ "from": from, "to": to, "amount": amount }), "transfer")
+            .await?;
+        Ok(tx_hash_from(&body))
+    }
+}
+
+/// Extract the tx hash from an L1 response ("tx_hash" or "hash")
+fn tx_hash_from(body: &serde_json::Value) -> String {
+    body.get("tx_hash")
+        .or_else(|| body.get("hash"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown")
+        .to_string()
 }
 
 // ============================================================================
@@ -732,6 +578,8 @@ impl L1BlackBookRpc {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::l1_backend::l1_backend_from_config;
+    use super::super::l1_mock::MockL1;
     
     #[test]
     fn test_config_default() {
@@ -747,9 +595,10 @@ mod tests {
     }
     
     #[test]
-    fn test_rpc_client_mock() {
-        let rpc = L1BlackBookRpc::mock();
-        assert!(rpc.is_mock());
-        assert!(!rpc.connected);
+    fn test_backend_from_config() {
+        assert!(l1_backend_from_config(L1RpcConfig::mock(), MockL1::default()).is_mock());
+        let http = l1_backend_from_config(L1RpcConfig::default(), MockL1::default());
+        assert!(!http.is_mock());
+        assert_eq!(http.describe(), format!("http ({})", L1_DEFAULT_ENDPOINT));
     }
 }
@@ -0,0 +1,788 @@
+// ============================================================================
+// L1 Mock - In-memory L1 chain for offline testing
+// ============================================================================
+//
+// Implements the L1 surface the L2 depends on (balances, nonces, bridge,
+// withdrawals, sessions, signature verification, settlements) against
+// in-memory state, plus configurable fault injection. Served over HTTP by
+// the `l1_mock` binary and used in-process by `MockL1Backend`.
+//
+// Bridge and withdraw calls are idempotent: replaying the same l2_tx_hash /
+// bridge_id returns the original response without moving funds twice.
+// ============================================================================
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::l1_blackbook_rpc::*;
+use crate::bridge_proof::verify_ed25519;
+use crate::state_root::state_root_signing_bytes;
+use crate::withdrawal_batch::verify_withdrawal_proof;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Length of a mock L1 session (1 hour)
+pub const MOCK_SESSION_SECS: u64 = 3600;
+
+/// Balance of unknown addresses unless L1_MOCK_DEFAULT_BALANCE is set
+pub const MOCK_DEFAULT_BALANCE: f64 = 10_000.0;
+
+/// Mock PoH tick rate
+const MOCK_TICK_RATE_MS: u64 = 400;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+// ============================================================================
+// FAULT INJECTION
+// ============================================================================
+
+/// What the mock server should do with a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultAction {
+    /// Handle normally
+    Pass,
+    /// Reject with an error status without touching state
+    Error,
+    /// Apply the request but never send the response
+    Drop,
+}
+
+/// Fault injection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FaultConfig {
+    /// Added latency before each request (ms)
+    pub latency_ms: u64,
+    /// Probability of an error response (0.0-1.0)
+    pub error_rate: f64,
+    /// Probability of a dropped response (0.0-1.0)
+    pub drop_rate: f64,
+    /// Fail the next N matching requests
+    pub fail_next: u32,
+    /// Drop the next N matching requests
+    pub drop_next: u32,
+    /// Status code used for injected errors
+    pub error_status: u16,
+    /// Path prefixes faults apply to (empty = all)
+    pub paths: Vec<String>,
+}
+
+impl Default for FaultConfig {
+    fn default() -> Self {
+        Self {
+            latency_ms: 0,
+            error_rate: 0.0,
+            drop_rate: 0.0,
+            fail_next: 0,
+            drop_next: 0,
+            error_status: 503,
+            paths: Vec::new(),
+        }
+    }
+}
+
+impl FaultConfig {
+    /// Load from L1_MOCK_LATENCY_MS, L1_MOCK_ERROR_RATE, L1_MOCK_DROP_RATE
+    /// and L1_MOCK_FAULT_PATHS (comma separated)
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+        let defaults = Self::default();
+        Self {
+            latency_ms: var("L1_MOCK_LATENCY_MS").unwrap_or(defaults.latency_ms),
+            error_rate: var("L1_MOCK_ERROR_RATE").unwrap_or(defaults.error_rate),
+            drop_rate: var("L1_MOCK_DROP_RATE").unwrap_or(defaults.drop_rate),
+            error_status: var("L1_MOCK_ERROR_STATUS").unwrap_or(defaults.error_status),
+            paths: std::env::var("L1_MOCK_FAULT_PATHS")
+                .map(|v| v.split(',').map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect())
+                .unwrap_or_default(),
+            ..defaults
+        }
+    }
+
+    pub fn applies_to(&self, path: &str) -> bool {
+        self.paths.is_empty() || self.paths.iter().any(|p| path.starts_with(p.as_str()))
+    }
+
+    /// Decide the fault for a request, consuming one-shot counters
+    pub fn decide(&mut self, path: &str) -> FaultAction {
+        if !self.applies_to(path) {
+            return FaultAction::Pass;
+        }
+        if self.fail_next > 0 {
+            self.fail_next -= 1;
+            return FaultAction::Error;
+        }
+        if self.drop_next > 0 {
+            self.drop_next -= 1;
+            return FaultAction::Drop;
+        }
+        let roll: f64 = rand::random();
+        if roll < self.error_rate {
+            FaultAction::Error
+        } else if roll < self.error_rate + self.drop_rate {
+            FaultAction::Drop
+        } else {
+            FaultAction::Pass
+        }
+    }
+}
+
+// ============================================================================
+// MOCK L1 STATE
+// ============================================================================
+
+/// Session locked on the mock L1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockL1Session {
+    pub session_id: String,
+    pub wallet_address: String,
+    pub l2_session_id: String,
+    pub l2_credit: f64,
+    pub status: String, // "active", "settled"
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// In-memory L1 chain state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockL1 {
+    pub balances: HashMap<String, f64>,
+    pub nonces: HashMap<String, u64>,
+    /// Supabase user ID → wallet address
+    pub wallets: HashMap<String, String>,
+    /// Sessions by wallet address
+    pub sessions: HashMap<String, MockL1Session>,
+    /// Bridge responses by l2_tx_hash
+    pub bridges: HashMap<String, L1BridgeResponse>,
+    /// Withdraw responses by bridge_id
+    pub withdrawals: HashMap<String, L1WithdrawResponse>,
+    /// Settlements by market ID
+    pub settlements: HashMap<String, (L1SettlementRequest, String)>,
+    /// Committed withdrawal batches by batch_id
+    pub withdrawal_batches: HashMap<u64, (L1WithdrawalBatchRequest, L1WithdrawalBatchResponse)>,
+    /// Claimed batched withdrawals by leaf nonce
+    pub claimed_withdrawals: HashSet<u64>,
+    /// Accepted L2 state roots by epoch
+    #[serde(default)]
+    pub state_roots: BTreeMap<u64, (L1StateRootRequest, L1StateRootResponse)>,
+    /// Sequencer key, pinned by the first accepted state root
+    #[serde(default)]
+    pub sequencer_pubkey: Option<String>,
+    /// Balance given to addresses seen for the first time
+    pub default_balance: f64,
+    pub started_at: u64,
+    pub tx_count: u64,
+}
+
+impl Default for MockL1 {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl MockL1 {
+    pub fn new(default_balance: f64) -> Self {
+        Self {
+            balances: HashMap::new(),
+            nonces: HashMap::new(),
+            wallets: HashMap::new(),
+            sessions: HashMap::new(),
+            bridges: HashMap::new(),
+            withdrawals: HashMap::new(),
+            settlements: HashMap::new(),
+            withdrawal_batches: HashMap::new(),
+            claimed_withdrawals: HashSet::new(),
+            state_roots: BTreeMap::new(),
+            sequencer_pubkey: None,
+            default_balance,
+            started_at: now(),
+            tx_count: 0,
+        }
+    }
+
+    /// Load from L1_MOCK_DEFAULT_BALANCE and L1_MOCK_ACCOUNTS ("addr=balance,...")
+    pub fn from_env() -> Self {
+        let default_balance = std::env::var("L1_MOCK_DEFAULT_BALANCE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(MOCK_DEFAULT_BALANCE);
+        let mut l1 = Self::new(default_balance);
+        if let Ok(accounts) = std::env::var("L1_MOCK_ACCOUNTS") {
+            for entry in accounts.split(',') {
+                if let Some((address, balance)) = entry.split_once('=') {
+                    if let Ok(balance) = balance.trim().parse() {
+                        l1.set_balance(address.trim(), balance);
+                    }
+                }
+            }
+        }
+        l1
+    }
+
+    pub fn set_balance(&mut self, address: &str, balance: f64) {
+        self.balances.insert(address.to_string(), balance);
+    }
+
+    pub fn register_wallet(&mut self, user_id: &str, address: &str) {
+        self.wallets.insert(user_id.to_string(), address.to_string());
+    }
+
+    fn balance_of(&self, address: &str) -> f64 {
+        self.balances.get(address).copied().unwrap_or(self.default_balance)
+    }
+
+    fn next_tx_hash(&mut self, kind: &str, data: &str) -> String {
+        self.tx_count += 1;
+        let mut hasher = Sha256::new();
+        hasher.update(kind.as_bytes());
+        hasher.update(self.tx_count.to_le_bytes());
+        hasher.update(data.as_bytes());
+        format!("0x{}", hex::encode(hasher.finalize()))
+    }
+
+    fn bump_nonce(&mut self, address: &str) {
+        *self.nonces.entry(address.to_string()).or_insert(0) += 1;
+    }
+
+    fn slot(&self) -> u64 {
+        (now() - self.started_at) * 1000 / MOCK_TICK_RATE_MS
+    }
+
+    // ========================================================================
+    // HEALTH & STATUS
+    // ========================================================================
+
+    pub fn health(&self) -> L1HealthResponse {
+        L1HealthResponse {
+            status: "healthy".to_string(),
+            version: Some(format!("mock-{}", env!("CARGO_PKG_VERSION"))),
+            slot: Some(self.slot()),
+            epoch: Some(self.slot() / 432_000),
+            uptime_secs: Some(now() - self.started_at),
+        }
+    }
+
+    pub fn poh_status(&self) -> L1PoHStatus {
+        let slot = self.slot();
+        L1PoHStatus {
+            enabled: true,
+            current_slot: slot,
+            current_hash: hex::encode(Sha256::digest(slot.to_le_bytes())),
+            tick_rate_ms: MOCK_TICK_RATE_MS,
+            entries_since_genesis: slot,
+        }
+    }
+
+    // ========================================================================
+    // ACCOUNTS
+    // ========================================================================
+
+    pub fn wallet(&self, user_id: &str) -> Option<L1WalletLookupResponse> {
+        self.wallets.get(user_id).map(|address| L1WalletLookupResponse {
+            found: true,
+            user_id: user_id.to_string(),
+            wallet_address: Some(address.clone()),
+            registered_at: Some(self.started_at),
+        })
+    }
+
+    pub fn balance(&self, address: &str) -> L1BalanceResponse {
+        L1BalanceResponse {
+            address: address.to_string(),
+            balance: self.balance_of(address),
+            exists: self.balances.contains_key(address),
+        }
+    }
+
+    pub fn nonce(&self, address: &str) -> u64 {
+        self.nonces.get(address).copied().unwrap_or(0)
+    }
+
+    /// Credit an address (POST /admin/mint)
+    pub fn mint(&mut self, to: &str, amount: f64) -> String {
+        let balance = self.balance_of(to) + amount;
+        self.set_balance(to, balance);
+        self.next_tx_hash("mint", to)
+    }
+
+    /// Move funds between addresses (POST /transfer)
+    pub fn transfer(&mut self, from: &str, to: &str, amount: f64) -> Result<String, String> {
+        let from_balance = self.balance_of(from);
+        if amount <= 0.0 || from_balance < amount {
+            return Err(format!("Insufficient balance: have {}, need {}", from_balance, amount));
+        }
+        let to_balance = self.balance_of(to);
+        self.set_balance(from, from_balance - amount);
+        self.set_balance(to, to_balance + amount);
+        self.bump_nonce(from);
+        Ok(self.next_tx_hash("transfer", &format!("{}:{}:{}", from, to, amount)))
+    }
+
+    /// Verify an Ed25519 signature. The address is the hex public key,
+    /// optionally prefixed with "L1_".
+    pub fn verify_signature(&self, request: &L1VerifyRequest) -> L1VerifyResponse {
+        let invalid = |e: &str| L1VerifyResponse { valid: false, error: Some(e.to_string()) };
+
+        let pubkey_hex = request.address.trim_start_matches("L1_").to_lowercase();
+        let pubkey: [u8; 32] = match hex::decode(&pubkey_hex).ok().and_then(|b| b.try_into().ok()) {
+            Some(bytes) => bytes,
+            None => return invalid("Address is not an Ed25519 public key"),
+        };
+        let signature: [u8; 64] = match hex::decode(&request.signature).ok().and_then(|b| b.try_into().ok()) {
+            Some(bytes) => bytes,
+            None => return invalid("Signature must be 64 hex-encoded bytes"),
+        };
+        let key = match VerifyingKey::from_bytes(&pubkey) {
+            Ok(key) => key,
+            Err(_) => return invalid("Invalid public key"),
+        };
+        match key.verify(request.message.as_bytes(), &Signature::from_bytes(&signature)) {
+            Ok(()) => L1VerifyResponse { valid: true, error: None },
+            Err(_) => invalid("Signature mismatch"),
+        }
+    }
+
+    // ========================================================================
+    // BRIDGE
+    // ========================================================================
+
+    /// L2→L1 bridge (POST /rpc/bridge), idempotent on l2_tx_hash
+    pub fn bridge(&mut self, request: &L1BridgeRequest) -> L1BridgeResponse {
+        if let Some(existing) = self.bridges.get(&request.l2_tx_hash) {
+            return existing.clone();
+        }
+        if request.amount <= 0.0 {
+            return L1BridgeResponse {
+                success: false,
+                bridge_id: None,
+                l1_tx_hash: None,
+                status: "failed".to_string(),
+                error: Some("Amount must be positive".to_string()),
+            };
+        }
+        let balance = self.balance_of(&request.to_l1_address) + request.amount;
+        self.set_balance(&request.to_l1_address, balance);
+        let response = L1BridgeResponse {
+            success: true,
+            bridge_id: Some(format!("bridge_{}", &request.l2_tx_hash)),
+            l1_tx_hash: Some(self.next_tx_hash("bridge", &request.l2_tx_hash)),
+            status: "completed".to_string(),
+            error: None,
+        };
+        self.bridges.insert(request.l2_tx_hash.clone(), response.clone());
+        response
+    }
+
+    /// L2→L1 withdrawal (POST /bridge/withdraw), idempotent on bridge_id
+    pub fn withdraw(&mut self, request: &L1WithdrawRequest) -> L1WithdrawResponse {
+        if let Some(existing) = self.withdrawals.get(&request.bridge_id) {
+            return existing.clone();
+        }
+        if request.amount <= 0.0 {
+            return L1WithdrawResponse {
+                success: false,
+                bridge_id: Some(request.bridge_id.clone()),
+                l1_tx_hash: None,
+                status: "failed".to_string(),
+                new_l1_balance: None,
+                error: Some("Amount must be positive".to_string()),
+            };
+        }
+        let balance = self.balance_of(&request.to_l1_address) + request.amount;
+        self.set_balance(&request.to_l1_address, balance);
+        let response = L1WithdrawResponse {
+            success: true,
+            bridge_id: Some(request.bridge_id.clone()),
+            l1_tx_hash: Some(self.next_tx_hash("withdraw", &request.bridge_id)),
+            status: "completed".to_string(),
+            new_l1_balance: Some(balance),
+            error: None,
+        };
+        self.withdrawals.insert(request.bridge_id.clone(), response.clone());
+        response
+    }
+
+    /// Previously processed withdrawal (GET /bridge/withdraw/:bridge_id)
+    pub fn withdrawal(&self, bridge_id: &str) -> Option<L1WithdrawResponse> {
+        self.withdrawals.get(bridge_id).cloned()
+    }
+
+    // ========================================================================
+    // WITHDRAWAL BATCHES
+    // ========================================================================
+
+    /// Commit a withdrawal batch root (POST /bridge/batch), idempotent on batch_id
+    pub fn commit_withdrawal_batch(&mut self, request: &L1WithdrawalBatchRequest) -> L1WithdrawalBatchResponse {
+        if let Some((_, existing)) = self.withdrawal_batches.get(&request.batch_id) {
+            return existing.clone();
+        }
+        let valid_root = hex::decode(&request.root).map(|r| r.len() == 32).unwrap_or(false);
+        if !valid_root || request.leaf_count == 0 {
+            return L1WithdrawalBatchResponse {
+                success: false,
+                batch_id: request.batch_id,
+                l1_tx_hash: None,
+                status: "failed".to_string(),
+                error: Some("Batch needs a 32-byte root and at least one leaf".to_string()),
+            };
+        }
+        let response = L1WithdrawalBatchResponse {
+            success: true,
+            batch_id: request.batch_id,
+            l1_tx_hash: Some(self.next_tx_hash("batch", &request.root)),
+            status: "committed".to_string(),
+            error: None,
+        };
+        self.withdrawal_batches.insert(request.batch_id, (request.clone(), response.clone()));
+        response
+    }
+
+    /// Batch commitment status (GET /bridge/batch/:batch_id)
+    pub fn withdrawal_batch(&self, batch_id: u64) -> Option<L1WithdrawalBatchResponse> {
+        self.withdrawal_batches.get(&batch_id).map(|(_, response)| response.clone())
+    }
+
+    /// Pay out one batched withdrawal against its inclusion proof (POST /bridge/claim)
+    pub fn claim_withdrawal(&mut self, request: &L1WithdrawalClaimRequest) -> L1WithdrawalClaimResponse {
+        let failed = |e: &str| L1WithdrawalClaimResponse {
+            success: false,
+            l1_tx_hash: None,
+            new_l1_balance: None,
+            error: Some(e.to_string()),
+        };
+        let root = match self.withdrawal_batches.get(&request.batch_id) {
+            Some((batch, _)) => hex::decode(&batch.root).unwrap_or_default(),
+            None => return failed("Unknown batch"),
+        };
+        if !verify_withdrawal_proof(&root, &request.leaf, &request.proof) {
+            return failed("Invalid inclusion proof");
+        }
+        if !self.claimed_withdrawals.insert(request.leaf.nonce) {
+            return failed("Withdrawal already claimed");
+        }
+        let balance = self.balance_of(&request.leaf.address) + request.leaf.amount;
+        self.set_balance(&request.leaf.address, balance);
+        L1WithdrawalClaimResponse {
+            success: true,
+            l1_tx_hash: Some(self.next_tx_hash("claim", &request.leaf.nonce.to_string())),
+            new_l1_balance: Some(balance),
+            error: None,
+        }
+    }
+
+    // ========================================================================
+    // L2 STATE ROOTS
+    // ========================================================================
+
+    /// Accept a signed L2 state root (POST /l2/state-root), idempotent on epoch.
+    /// Epochs must increase and every root must be signed by the same sequencer.
+    pub fn submit_state_root(&mut self, request: &L1StateRootRequest) -> L1StateRootResponse {
+        let rejected = |e: &str| L1StateRootResponse {
+            success: false,
+            epoch: request.epoch,
+            l1_tx_hash: None,
+            error: Some(e.to_string()),
+        };
+        if let Some((existing, response)) = self.state_roots.get(&request.epoch) {
+            if existing.root == request.root {
+                return response.clone();
+            }
+            return rejected("Epoch already committed with a different root");
+        }
+        if self.state_roots.keys().next_back().is_some_and(|latest| *latest > request.epoch) {
+            return rejected("Epoch is older than the latest state root");
+        }
+        if self.sequencer_pubkey.as_ref().is_some_and(|key| *key != request.sequencer_pubkey) {
+            return rejected("State root not signed by the sequencer");
+        }
+        let root = match hex::decode(&request.root) {
+            Ok(root) if root.len() == 32 => root,
+            _ => return rejected("State root must be 32 bytes"),
+        };
+        let message = state_root_signing_bytes(request.epoch, request.l2_block, request.leaf_count, request.created_at, &root);
+        if !verify_ed25519(&request.sequencer_pubkey, &request.signature, &message) {
+            return rejected("Invalid sequencer signature");
+        }
+
+        self.sequencer_pubkey.get_or_insert_with(|| request.sequencer_pubkey.clone());
+        let response = L1StateRootResponse {
+            success: true,
+            epoch: request.epoch,
+            l1_tx_hash: Some(self.next_tx_hash("state_root", &request.root)),
+            error: None,
+        };
+        self.state_roots.insert(request.epoch, (request.clone(), response.clone()));
+        response
+    }
+
+    /// Latest accepted state root
+    pub fn latest_state_root(&self) -> Option<&L1StateRootRequest> {
+        self.state_roots.values().next_back().map(|(request, _)| request)
+    }
+
+    // ========================================================================
+    // SESSIONS
+    // ========================================================================
+
+    /// Lock L1 funds for an L2 session (POST /session/start)
+    pub fn start_session(&mut self, request: &L1SessionStartRequest) -> L1SessionStartResponse {
+        let failed = |e: String| L1SessionStartResponse {
+            success: false,
+            session_id: None,
+            l1_balance: None,
+            l2_credit: None,
+            expires_at: None,
+            error: Some(e),
+        };
+
+        let now = now();
+        if let Some(existing) = self.sessions.get(&request.wallet_address) {
+            if existing.status == "active" && existing.expires_at > now {
+                return failed(format!("Active session {} already exists", existing.session_id));
+            }
+        }
+        let balance = self.balance_of(&request.wallet_address);
+        if request.requested_amount <= 0.0 || balance < request.requested_amount {
+            return failed(format!(
+                "Insufficient L1 balance: have {}, requested {}",
+                balance, request.requested_amount
+            ));
+        }
+
+        self.set_balance(&request.wallet_address, balance - request.requested_amount);
+        self.bump_nonce(&request.wallet_address);
+        self.tx_count += 1;
+        let session = MockL1Session {
+            session_id: format!("l1_session_{}", self.tx_count),
+            wallet_address: request.wallet_address.clone(),
+            l2_session_id: request.l2_session_id.clone(),
+            l2_credit: request.requested_amount,
+            status: "active".to_string(),
+            created_at: now,
+            expires_at: now + MOCK_SESSION_SECS,
+        };
+        let response = L1SessionStartResponse {
+            success: true,
+            session_id: Some(session.session_id.clone()),
+            l1_balance: Some(balance),
+            l2_credit: Some(session.l2_credit),
+            expires_at: Some(session.expires_at),
+            error: None,
+        };
+        self.sessions.insert(request.wallet_address.clone(), session);
+        response
+    }
+
+    /// Release a session's funds plus PnL back to L1 (POST /session/settle)
+    pub fn settle_session(&mut self, request: &L1SessionSettleRequest) -> L1SessionSettleResponse {
+        let failed = |e: String| L1SessionSettleResponse {
+            success: false,
+            l1_tx_hash: None,
+            new_l1_balance: None,
+            settled_pnl: None,
+            error: Some(e),
+        };
+
+        let credit = match self.sessions.get(&request.wallet_address) {
+            Some(session) if session.status == "active" => session.l2_credit,
+            Some(_) => return failed("Session already settled".to_string()),
+            None => return failed(format!("No session for {}", request.wallet_address)),
+        };
+        // A session can lose at most what it locked
+        let pnl = request.pnl.max(-credit);
+        let balance = self.balance_of(&request.wallet_address) + credit + pnl;
+        self.set_balance(&request.wallet_address, balance);
+        if let Some(session) = self.sessions.get_mut(&request.wallet_address) {
+            session.status = "settled".to_string();
+        }
+        L1SessionSettleResponse {
+            success: true,
+            l1_tx_hash: Some(self.next_tx_hash("settle", &request.session_id)),
+            new_l1_balance: Some(balance),
+            settled_pnl: Some(pnl),
+            error: None,
+        }
+    }
+
+    /// Session status (GET /session/status/:address)
+    pub fn session_status(&self, address: &str) -> Option<L1SessionStatusResponse> {
+        let session = self.sessions.get(address)?;
+        let status = if session.status == "active" && session.expires_at <= now() {
+            "expired"
+        } else {
+            session.status.as_str()
+        };
+        Some(L1SessionStatusResponse {
+            success: true,
+            session_id: Some(session.session_id.clone()),
+            wallet_address: address.to_string(),
+            l1_balance: self.balance_of(address),
+            l2_credit: if status == "active" { session.l2_credit } else { 0.0 },
+            status: status.to_string(),
+            created_at: Some(session.created_at),
+            expires_at: Some(session.expires_at),
+            error: None,
+        })
+    }
+
+    // ========================================================================
+    // SETTLEMENTS
+    // ========================================================================
+
+    /// Record a market settlement (POST /rpc/settlement), idempotent on market_id
+    pub fn record_settlement(&mut self, request: &L1SettlementRequest) -> L1SettlementResponse {
+        if let Some((_, tx_hash)) = self.settlements.get(&request.market_id) {
+            return L1SettlementResponse {
+                success: true,
+                l1_tx_hash: Some(tx_hash.clone()),
+                error: None,
+            };
+        }
+        let tx_hash = self.next_tx_hash("settlement", &request.market_id);
+        self.settlements.insert(request.market_id.clone(), (request.clone(), tx_hash.clone()));
+        L1SettlementResponse {
+            success: true,
+            l1_tx_hash: Some(tx_hash),
+            error: None,
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn withdraw_request(bridge_id: &str, amount: f64) -> L1WithdrawRequest {
+        L1WithdrawRequest {
+            from_l2_address: "L2_ALICE".to_string(),
+            to_l1_address: "L1_ALICE".to_string(),
+            amount,
+            bridge_id: bridge_id.to_string(),
+            signature: String::new(),
+            timestamp: 0,
+            nonce: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_withdraw_is_idempotent() {
+        let mut l1 = MockL1::new(0.0);
+        let first = l1.withdraw(&withdraw_request("b1", 50.0));
+        let replay = l1.withdraw(&withdraw_request("b1", 50.0));
+        assert!(first.success);
+        assert_eq!(first.l1_tx_hash, replay.l1_tx_hash);
+        assert_eq!(l1.balance("L1_ALICE").balance, 50.0);
+    }
+
+    #[test]
+    fn test_session_lock_and_settle() {
+        let mut l1 = MockL1::new(0.0);
+        l1.set_balance("L1_BOB", 1000.0);
+        let start = l1.start_session(&L1SessionStartRequest {
+            wallet_address: "L1_BOB".to_string(),
+            l2_session_id: "s1".to_string(),
+            requested_amount: 400.0,
+            signature: String::new(),
+            timestamp: 0,
+            nonce: "1".to_string(),
+        });
+        assert!(start.success);
+        assert_eq!(l1.balance("L1_BOB").balance, 600.0);
+
+        let settle = l1.settle_session(&L1SessionSettleRequest {
+            wallet_address: "L1_BOB".to_string(),
+            session_id: "s1".to_string(),
+            final_l2_balance: 450.0,
+            pnl: 50.0,
+            bet_count: 3,
+            signature: String::new(),
+            timestamp: 0,
+        });
+        assert!(settle.success);
+        assert_eq!(settle.new_l1_balance, Some(1050.0));
+        assert_eq!(l1.session_status("L1_BOB").unwrap().status, "settled");
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let l1 = MockL1::new(0.0);
+        let mut request = L1VerifyRequest {
+            address: format!("L1_{}", hex::encode(key.verifying_key().to_bytes()).to_uppercase()),
+            message: "hello".to_string(),
+            signature: hex::encode(key.sign(b"hello").to_bytes()),
+        };
+        assert!(l1.verify_signature(&request).valid);
+        request.message = "tampered".to_string();
+        assert!(!l1.verify_signature(&request).valid);
+    }
+
+    #[test]
+    fn test_fault_counters_and_paths() {
+        let mut faults = FaultConfig {
+            fail_next: 1,
+            drop_next: 1,
+            paths: vec!["/bridge".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(faults.decide("/health"), FaultAction::Pass);
+        assert_eq!(faults.decide("/bridge/withdraw"), FaultAction::Error);
+        assert_eq!(faults.decide("/bridge/withdraw"), FaultAction::Drop);
+        assert_eq!(faults.decide("/bridge/withdraw"), FaultAction::Pass);
+    }
+
+    #[test]
+    fn test_state_roots_signed_and_monotonic() {
+        use crate::state_root::{SequencerKey, StateCommitter, StateLeaf};
+        use ed25519_dalek::SigningKey;
+
+        let to_request = |c: &crate::state_root::StateCommitment| L1StateRootRequest {
+            epoch: c.epoch,
+            root: c.root.clone(),
+            leaf_count: c.leaf_count,
+            l2_block: c.l2_block,
+            created_at: c.created_at,
+            sequencer_pubkey: c.sequencer_pubkey.clone(),
+            signature: c.signature.clone(),
+        };
+        let leaf = |balance: f64| vec![StateLeaf::Account {
+            address: "L1_ALICE".into(), confirmed: balance, pending: 0.0, locked: 0.0,
+        }];
+        let sequencer = SequencerKey::new(SigningKey::from_bytes(&[1u8; 32]));
+        let mut committer = StateCommitter::new(60);
+        let first = committer.commit(&sequencer, leaf(10.0), 1, 100).unwrap();
+        let second = committer.commit(&sequencer, leaf(20.0), 2, 160).unwrap();
+
+        let mut l1 = MockL1::new(0.0);
+        assert!(l1.submit_state_root(&to_request(&second)).success);
+        assert!(l1.submit_state_root(&to_request(&second)).success); // idempotent
+        assert!(!l1.submit_state_root(&to_request(&first)).success); // older epoch
+
+        // Tampered payloads and other sequencers are refused
+        let mut tampered = to_request(&second);
+        tampered.epoch += 1;
+        tampered.l2_block += 1;
+        assert!(!l1.submit_state_root(&tampered).success);
+        let imposter = SequencerKey::new(SigningKey::from_bytes(&[2u8; 32]));
+        let forged = committer.commit(&imposter, leaf(30.0), 3, 220).unwrap();
+        assert!(!l1.submit_state_root(&to_request(&forged)).success);
+        assert_eq!(l1.latest_state_root().unwrap().epoch, second.epoch);
+    }
+}
@@ -7,12 +7,18 @@
 //
 // Components:
 //   - signed_transaction: Ed25519 signed transaction handling
-//   - l1_blackbook_rpc: L1 blockchain RPC client wrapper
+//   - l1_backend: L1Backend trait (HTTP, mock, recording) injected via AppState
+//   - l1_blackbook_rpc: L1 wire types and HTTP backend
+//   - l1_mock: In-memory L1 with fault injection (served by the l1_mock binary)
 //
 // ============================================================================
 
 pub mod signed_transaction;
+pub mod l1_backend;
 pub mod l1_blackbook_rpc;
+pub mod l1_mock;
 
 pub use signed_transaction::*;
+pub use l1_backend::*;
 pub use l1_blackbook_rpc::*;
+pub use l1_mock::*;
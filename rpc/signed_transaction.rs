@@ -4,6 +4,7 @@
 //! for secure cross-layer communication and verified bet placement.
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use sha2::{Sha256, Digest};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -11,7 +12,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use crate::easteregg::GodMode;
 
 /// Transaction type identifiers matching L1 protocol
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
 #[repr(u8)]
 pub enum SignedTxType {
     #[default]
@@ -22,6 +23,8 @@ pub enum SignedTxType {
     MarketLaunch = 9,
     AddLiquidity = 10,
     RemoveLiquidity = 11,
+    ForcedExit = 12,
+    ResolutionDispute = 13,
 }
 
 impl SignedTxType {
@@ -40,6 +43,8 @@ impl SignedTxType {
             9 => Some(SignedTxType::MarketLaunch),
             10 => Some(SignedTxType::AddLiquidity),
             11 => Some(SignedTxType::RemoveLiquidity),
+            12 => Some(SignedTxType::ForcedExit),
+            13 => Some(SignedTxType::ResolutionDispute),
             _ => None,
         }
     }
@@ -54,12 +59,14 @@ impl SignedTxType {
             SignedTxType::MarketLaunch => "MarketLaunch",
             SignedTxType::AddLiquidity => "AddLiquidity",
             SignedTxType::RemoveLiquidity => "RemoveLiquidity",
+            SignedTxType::ForcedExit => "ForcedExit",
+            SignedTxType::ResolutionDispute => "ResolutionDispute",
         }
     }
 }
 
 /// Transaction payload variants with typed fields
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TransactionPayload {
     Transfer {
@@ -92,6 +99,18 @@ pub enum TransactionPayload {
         market_id: String,
         shares: f64,
     },
+    /// Escape hatch: withdraw everything to `target_address` on L1
+    ForcedExit {
+        target_address: String,
+    },
+    /// Challenge a proposed resolution, bonding from the signer's wallet
+    ResolutionDispute {
+        market_id: String,
+        /// Defaults to the proposer's bond
+        bond: Option<f64>,
+        claimed_outcome: Option<usize>,
+        reason: Option<String>,
+    },
 }
 
 impl TransactionPayload {
@@ -105,6 +124,8 @@ impl TransactionPayload {
             TransactionPayload::MarketLaunch { .. } => SignedTxType::MarketLaunch,
             TransactionPayload::AddLiquidity { .. } => SignedTxType::AddLiquidity,
             TransactionPayload::RemoveLiquidity { .. } => SignedTxType::RemoveLiquidity,
+            TransactionPayload::ForcedExit { .. } => SignedTxType::ForcedExit,
+            TransactionPayload::ResolutionDispute { .. } => SignedTxType::ResolutionDispute,
         }
     }
 
@@ -114,12 +135,12 @@ impl TransactionPayload {
     }
 }
 
-/// Default transaction expiry window (24 hours for development)
-/// In production, reduce to 300 seconds (5 minutes)
-pub const TX_EXPIRY_SECS: u64 = 86400; // 24 hours
+/// Default transaction expiry window (5 minutes); raise
+/// `transactions.expiry_secs` for development against drifting clocks
+pub const TX_EXPIRY_SECS: u64 = 300; // 5 minutes
 
 /// A cryptographically signed transaction envelope
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SignedTransaction {
     /// Sender's Ed25519 public key (64 hex chars)
     pub sender_pubkey: String,
@@ -241,6 +262,13 @@ impl SignedTransaction {
         Ok(tx)
     }
 
+    /// Address of the signing key (L1_<PUBKEY>). Use this, not
+    /// `sender_address`, for the account a transaction acts for: only the
+    /// pubkey is covered by the signature.
+    pub fn signer_address(&self) -> String {
+        format!("L1_{}", self.sender_pubkey.to_uppercase())
+    }
+
     /// Generate the canonical bytes to sign
     /// 
     /// Format: SHA256(tx_type || nonce || timestamp || sender_pubkey || payload_json)
@@ -363,7 +391,12 @@ impl SignedTransaction {
 
     /// Validate the transaction completely (signature + expiry)
     pub fn validate(&self) -> Result<(), SignedTxError> {
-        if self.is_expired() {
+        self.validate_with_window(TX_EXPIRY_SECS)
+    }
+
+    /// Validate with a custom expiry window (in seconds)
+    pub fn validate_with_window(&self, window_secs: u64) -> Result<(), SignedTxError> {
+        if self.is_expired_with_window(window_secs) {
             return Err(SignedTxError::Expired);
         }
 
@@ -403,6 +436,7 @@ mod tests {
         assert_eq!(SignedTxType::MarketLaunch.as_u8(), 9);
         assert_eq!(SignedTxType::AddLiquidity.as_u8(), 10);
         assert_eq!(SignedTxType::RemoveLiquidity.as_u8(), 11);
+        assert_eq!(SignedTxType::ForcedExit.as_u8(), 12);
     }
 
     #[test]
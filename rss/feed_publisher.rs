@@ -0,0 +1,575 @@
+// ============================================================================
+// RSS Feed Publisher - Outbound RSS/Atom feed of exchange activity
+// ============================================================================
+//
+// Records what happens on the exchange as feed items and renders them as
+// RSS 2.0 or Atom for partners to subscribe to:
+//
+//   new_market  - a market was launched
+//   odds_move   - an outcome price moved past the configured threshold
+//   resolution  - a market resolved (winning outcome + payout totals)
+//
+// Items carry the market's `RssEvent` metadata (category, tags, image_url,
+// source_url, dates) so feeds can be filtered by category and tag.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+
+use super::market_rss::{escape_xml, RssEvent};
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Default number of items retained
+pub const DEFAULT_MAX_FEED_ITEMS: usize = 500;
+
+/// Default odds move that triggers an item (10 percentage points)
+pub const DEFAULT_ODDS_MOVE_THRESHOLD: f64 = 0.10;
+
+/// Default public site URL used for links
+pub const DEFAULT_SITE_URL: &str = "https://blackbook.market";
+
+// ============================================================================
+// FEED ITEMS
+// ============================================================================
+
+/// Kind of feed item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedItemKind {
+    NewMarket,
+    OddsMove,
+    Resolution,
+}
+
+impl FeedItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeedItemKind::NewMarket => "new_market",
+            FeedItemKind::OddsMove => "odds_move",
+            FeedItemKind::Resolution => "resolution",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "new_market" => Some(FeedItemKind::NewMarket),
+            "odds_move" => Some(FeedItemKind::OddsMove),
+            "resolution" => Some(FeedItemKind::Resolution),
+            _ => None,
+        }
+    }
+}
+
+/// Kind-specific details of a feed item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeedItemDetail {
+    NewMarket {
+        probabilities: Vec<f64>,
+    },
+    OddsMove {
+        previous: Vec<f64>,
+        current: Vec<f64>,
+        /// Outcome with the largest move
+        outcome: usize,
+    },
+    Resolution {
+        winning_outcome: usize,
+        winning_outcome_name: String,
+        total_payout: f64,
+        num_winners: usize,
+    },
+}
+
+/// Single published feed item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedItem {
+    pub id: String,
+    pub kind: FeedItemKind,
+    pub published_at: u64,
+    pub title: String,
+    pub summary: String,
+    pub event: RssEvent,
+    pub detail: FeedItemDetail,
+}
+
+impl FeedItem {
+    /// Link to the source article, or the market page if there is none
+    pub fn link(&self, site_url: &str) -> String {
+        if self.event.source_url.is_empty() {
+            format!("{}/markets/{}", site_url.trim_end_matches('/'), self.event.market_id)
+        } else {
+            self.event.source_url.clone()
+        }
+    }
+}
+
+/// Filter applied when listing or rendering items
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FeedFilter {
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub kind: Option<FeedItemKind>,
+    pub limit: Option<usize>,
+}
+
+impl FeedFilter {
+    pub fn matches(&self, item: &FeedItem) -> bool {
+        if let Some(category) = &self.category {
+            if !item.event.get_category().eq_ignore_ascii_case(category) {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if !item.event.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                return false;
+            }
+        }
+        if let Some(kind) = self.kind {
+            if item.kind != kind {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// ============================================================================
+// FEED PUBLISHER
+// ============================================================================
+
+/// Collects exchange activity into a bounded, newest-first feed
+#[derive(Debug, Clone)]
+pub struct FeedPublisher {
+    items: VecDeque<FeedItem>,
+    /// Odds at the last published item per market
+    last_odds: HashMap<String, Vec<f64>>,
+    next_seq: u64,
+    pub max_items: usize,
+    pub odds_move_threshold: f64,
+    pub site_url: String,
+}
+
+impl Default for FeedPublisher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeedPublisher {
+    pub fn new() -> Self {
+        Self {
+            items: VecDeque::new(),
+            last_odds: HashMap::new(),
+            next_seq: 1,
+            max_items: DEFAULT_MAX_FEED_ITEMS,
+            odds_move_threshold: DEFAULT_ODDS_MOVE_THRESHOLD,
+            site_url: DEFAULT_SITE_URL.to_string(),
+        }
+    }
+
+    fn push(&mut self, kind: FeedItemKind, title: String, summary: String, event: &RssEvent, detail: FeedItemDetail, now: u64) {
+        // The sequence restarts with the process, so ids also carry the time
+        // and a content hash to stay unique across restarts
+        let digest = Sha256::digest(format!("{}\n{}\n{}\n{}", self.next_seq, now, title, summary));
+        let id = format!("{}:{}:{}-{}", kind.as_str(), event.market_id, now, &hex::encode(digest)[..12]);
+        self.next_seq += 1;
+        self.items.push_front(FeedItem {
+            id,
+            kind,
+            published_at: now,
+            title,
+            summary,
+            event: event.clone(),
+            detail,
+        });
+        self.items.truncate(self.max_items);
+    }
+
+    /// Publish a newly launched market
+    pub fn record_new_market(&mut self, event: &RssEvent, now: u64) {
+        let probabilities = event.get_probabilities();
+        let summary = format!("{} — {}", event.description, format_odds(&event.outcomes, &probabilities));
+        self.last_odds.insert(event.market_id.clone(), probabilities.clone());
+        self.push(
+            FeedItemKind::NewMarket,
+            format!("New market: {}", event.title),
+            summary,
+            event,
+            FeedItemDetail::NewMarket { probabilities },
+            now,
+        );
+    }
+
+    /// Publish an odds move if any outcome moved past the threshold since the
+    /// last published odds. Returns true if an item was published.
+    pub fn observe_odds(&mut self, event: &RssEvent, prices: &[f64], now: u64) -> bool {
+        let previous = match self.last_odds.get(&event.market_id) {
+            Some(previous) if previous.len() == prices.len() => previous.clone(),
+            _ => {
+                self.last_odds.insert(event.market_id.clone(), prices.to_vec());
+                return false;
+            }
+        };
+
+        let (outcome, delta) = previous.iter().zip(prices)
+            .map(|(before, after)| after - before)
+            .enumerate()
+            .fold((0, 0.0_f64), |best, (i, d)| if d.abs() > best.1.abs() { (i, d) } else { best });
+        if delta.abs() < self.odds_move_threshold {
+            return false;
+        }
+
+        let name = event.outcomes.get(outcome).cloned().unwrap_or_else(|| outcome.to_string());
+        let title = format!(
+            "Odds move: {} {} {:.0}% → {:.0}%",
+            event.title, name, previous[outcome] * 100.0, prices[outcome] * 100.0
+        );
+        let summary = format!("{} — {}", event.title, format_odds(&event.outcomes, prices));
+        self.last_odds.insert(event.market_id.clone(), prices.to_vec());
+        self.push(
+            FeedItemKind::OddsMove,
+            title,
+            summary,
+            event,
+            FeedItemDetail::OddsMove { previous, current: prices.to_vec(), outcome },
+            now,
+        );
+        true
+    }
+
+    /// Publish a market resolution
+    pub fn record_resolution(
+        &mut self,
+        event: &RssEvent,
+        winning_outcome: usize,
+        total_payout: f64,
+        num_winners: usize,
+        now: u64,
+    ) {
+        let winning_outcome_name = event.outcomes.get(winning_outcome)
+            .cloned()
+            .unwrap_or_else(|| winning_outcome.to_string());
+        let summary = format!(
+            "{} resolved to {}. {:.2} BB paid out to {} winner(s).",
+            event.title, winning_outcome_name, total_payout, num_winners
+        );
+        self.last_odds.remove(&event.market_id);
+        self.push(
+            FeedItemKind::Resolution,
+            format!("Resolved: {} — {}", event.title, winning_outcome_name),
+            summary,
+            event,
+            FeedItemDetail::Resolution { winning_outcome, winning_outcome_name, total_payout, num_winners },
+            now,
+        );
+    }
+
+    /// Items matching a filter, newest first
+    pub fn items(&self, filter: &FeedFilter) -> Vec<&FeedItem> {
+        self.items.iter()
+            .filter(|item| filter.matches(item))
+            .take(filter.limit.unwrap_or(self.max_items))
+            .collect()
+    }
+
+    /// Render matching items as an RSS 2.0 document
+    pub fn render_rss(&self, filter: &FeedFilter) -> String {
+        let items: String = self.items(filter).iter()
+            .map(|item| self.rss_item(item))
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:blackbook="https://blackbook.market/rss">
+  <channel>
+    <title>{}</title>
+    <link>{}</link>
+    <description>New markets, odds moves and resolutions on BlackBook Layer 2</description>
+{}  </channel>
+</rss>
+"#,
+            escape_xml(&feed_title(filter)),
+            escape_xml(&self.site_url),
+            items
+        )
+    }
+
+    fn rss_item(&self, item: &FeedItem) -> String {
+        let event = &item.event;
+        let mut xml = format!(
+            r#"    <item>
+      <guid isPermaLink="false">{}</guid>
+      <title>{}</title>
+      <description>{}</description>
+      <link>{}</link>
+      <pubDate>{}</pubDate>
+      <category>{}</category>
+"#,
+            escape_xml(&item.id),
+            escape_xml(&item.title),
+            escape_xml(&item.summary),
+            escape_xml(&item.link(&self.site_url)),
+            rfc2822(item.published_at),
+            escape_xml(&event.get_category()),
+        );
+        for tag in &event.tags {
+            xml.push_str(&format!("      <category>{}</category>\n", escape_xml(tag)));
+        }
+        if let Some(image_url) = &event.image_url {
+            xml.push_str(&format!(
+                "      <enclosure url=\"{}\" type=\"image/jpeg\" length=\"0\"/>\n",
+                escape_xml(image_url)
+            ));
+        }
+        xml.push_str(&format!(
+            "      <blackbook:itemType>{}</blackbook:itemType>\n      <blackbook:marketId>{}</blackbook:marketId>\n      <blackbook:outcomes>{}</blackbook:outcomes>\n",
+            item.kind.as_str(),
+            escape_xml(&event.market_id),
+            escape_xml(&event.outcomes.join(",")),
+        ));
+        xml.push_str(&detail_xml(&item.detail));
+        xml.push_str(&format!(
+            "      <blackbook:publishedDate>{}</blackbook:publishedDate>\n      <blackbook:resolutionDate>{}</blackbook:resolutionDate>\n    </item>\n",
+            escape_xml(&event.dates.published),
+            escape_xml(event.dates.resolution.as_deref().unwrap_or("TBD")),
+        ));
+        xml
+    }
+
+    /// Render matching items as an Atom document
+    pub fn render_atom(&self, filter: &FeedFilter, self_url: &str) -> String {
+        let items = self.items(filter);
+        let updated = items.first().map(|i| i.published_at).unwrap_or(0);
+        let entries: String = items.iter()
+            .map(|item| self.atom_entry(item))
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:blackbook="https://blackbook.market/rss">
+  <id>{}</id>
+  <title>{}</title>
+  <link href="{}"/>
+  <link rel="self" href="{}"/>
+  <updated>{}</updated>
+{}</feed>
+"#,
+            escape_xml(self_url),
+            escape_xml(&feed_title(filter)),
+            escape_xml(&self.site_url),
+            escape_xml(self_url),
+            rfc3339(updated),
+            entries
+        )
+    }
+
+    fn atom_entry(&self, item: &FeedItem) -> String {
+        let event = &item.event;
+        let mut xml = format!(
+            r#"  <entry>
+    <id>urn:blackbook:{}</id>
+    <title>{}</title>
+    <summary>{}</summary>
+    <link href="{}"/>
+    <updated>{}</updated>
+    <category term="{}"/>
+"#,
+            escape_xml(&item.id),
+            escape_xml(&item.title),
+            escape_xml(&item.summary),
+            escape_xml(&item.link(&self.site_url)),
+            rfc3339(item.published_at),
+            escape_xml(&event.get_category()),
+        );
+        for tag in &event.tags {
+            xml.push_str(&format!("    <category term=\"{}\"/>\n", escape_xml(tag)));
+        }
+        if let Some(image_url) = &event.image_url {
+            xml.push_str(&format!("    <link rel=\"enclosure\" href=\"{}\"/>\n", escape_xml(image_url)));
+        }
+        xml.push_str(&format!(
+            "    <blackbook:itemType>{}</blackbook:itemType>\n    <blackbook:marketId>{}</blackbook:marketId>\n",
+            item.kind.as_str(),
+            escape_xml(&event.market_id),
+        ));
+        xml.push_str(&detail_xml(&item.detail));
+        xml.push_str("  </entry>\n");
+        xml
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+fn feed_title(filter: &FeedFilter) -> String {
+    let mut title = "BlackBook Prediction Market".to_string();
+    if let Some(category) = &filter.category {
+        title.push_str(&format!(" — {}", category));
+    }
+    if let Some(tag) = &filter.tag {
+        title.push_str(&format!(" #{}", tag));
+    }
+    title
+}
+
+fn format_odds(outcomes: &[String], probabilities: &[f64]) -> String {
+    outcomes.iter().zip(probabilities)
+        .map(|(o, p)| format!("{} {:.0}%", o, p * 100.0))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn join_probs(probabilities: &[f64]) -> String {
+    probabilities.iter()
+        .map(|p| format!("{:.4}", p))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn detail_xml(detail: &FeedItemDetail) -> String {
+    match detail {
+        FeedItemDetail::NewMarket { probabilities } => format!(
+            "      <blackbook:probabilities>{}</blackbook:probabilities>\n",
+            join_probs(probabilities)
+        ),
+        FeedItemDetail::OddsMove { previous, current, outcome } => format!(
+            "      <blackbook:previousProbabilities>{}</blackbook:previousProbabilities>\n      <blackbook:probabilities>{}</blackbook:probabilities>\n      <blackbook:movedOutcome>{}</blackbook:movedOutcome>\n",
+            join_probs(previous),
+            join_probs(current),
+            outcome
+        ),
+        FeedItemDetail::Resolution { winning_outcome, winning_outcome_name, total_payout, num_winners } => format!(
+            "      <blackbook:winningOutcome>{}</blackbook:winningOutcome>\n      <blackbook:winningOutcomeName>{}</blackbook:winningOutcomeName>\n      <blackbook:totalPayout>{:.2}</blackbook:totalPayout>\n      <blackbook:numWinners>{}</blackbook:numWinners>\n",
+            winning_outcome,
+            escape_xml(winning_outcome_name),
+            total_payout,
+            num_winners
+        ),
+    }
+}
+
+fn rfc2822(ts: u64) -> String {
+    chrono::DateTime::from_timestamp(ts as i64, 0)
+        .map(|dt| dt.to_rfc2822())
+        .unwrap_or_default()
+}
+
+fn rfc3339(ts: u64) -> String {
+    chrono::DateTime::from_timestamp(ts as i64, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, category: &str, tags: &[&str]) -> RssEvent {
+        let mut event = RssEvent::new(
+            id.to_string(),
+            format!("Market {}", id),
+            "Test market".to_string(),
+            vec!["Yes".to_string(), "No".to_string()],
+            vec![0.5, 0.5],
+            String::new(),
+            "2025-01-01T00:00:00Z".to_string(),
+            "2025-06-01T00:00:00Z".to_string(),
+            "2025-06-02T00:00:00Z".to_string(),
+        );
+        event.category = Some(category.to_string());
+        event.tags = tags.iter().map(|t| t.to_string()).collect();
+        event
+    }
+
+    #[test]
+    fn test_odds_move_threshold() {
+        let mut feed = FeedPublisher::new();
+        let e = event("m1", "crypto", &[]);
+        feed.record_new_market(&e, 100);
+
+        assert!(!feed.observe_odds(&e, &[0.55, 0.45], 110));
+        assert!(feed.observe_odds(&e, &[0.62, 0.38], 120));
+        // Baseline moves to the published odds
+        assert!(!feed.observe_odds(&e, &[0.65, 0.35], 130));
+
+        let moves = feed.items(&FeedFilter { kind: Some(FeedItemKind::OddsMove), ..Default::default() });
+        assert_eq!(moves.len(), 1);
+        match &moves[0].detail {
+            FeedItemDetail::OddsMove { outcome, .. } => assert_eq!(*outcome, 0),
+            other => panic!("unexpected detail {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_filter_by_category_and_tag() {
+        let mut feed = FeedPublisher::new();
+        feed.record_new_market(&event("m1", "crypto", &["btc"]), 100);
+        feed.record_new_market(&event("m2", "sports", &["nba"]), 110);
+        feed.record_resolution(&event("m1", "crypto", &["btc"]), 0, 1500.0, 3, 120);
+
+        let crypto = feed.items(&FeedFilter { category: Some("Crypto".into()), ..Default::default() });
+        assert_eq!(crypto.len(), 2);
+        assert_eq!(crypto[0].kind, FeedItemKind::Resolution);
+
+        let nba = feed.items(&FeedFilter { tag: Some("nba".into()), ..Default::default() });
+        assert_eq!(nba.len(), 1);
+        assert_eq!(nba[0].event.market_id, "m2");
+    }
+
+    #[test]
+    fn test_render_rss_and_atom_parse() {
+        let mut feed = FeedPublisher::new();
+        let mut e = event("m1", "crypto", &["btc"]);
+        e.image_url = Some("https://img.example/a.png".to_string());
+        feed.record_new_market(&e, 100);
+        feed.record_resolution(&e, 1, 250.0, 2, 200);
+
+        let rss = feed.render_rss(&FeedFilter::default());
+        let parsed = feed_rs::parser::parse(rss.as_bytes()).expect("valid rss");
+        assert_eq!(parsed.entries.len(), 2);
+        assert!(rss.contains("<blackbook:winningOutcomeName>No</blackbook:winningOutcomeName>"));
+
+        let atom = feed.render_atom(&FeedFilter::default(), "https://blackbook.market/feed/atom");
+        let parsed = feed_rs::parser::parse(atom.as_bytes()).expect("valid atom");
+        assert_eq!(parsed.entries.len(), 2);
+        assert!(parsed.entries[0].title.as_ref().unwrap().content.starts_with("Resolved:"));
+    }
+
+    #[test]
+    fn test_item_ids_unique_across_restarts() {
+        let e = event("m1", "crypto", &[]);
+        let mut before = FeedPublisher::new();
+        before.record_new_market(&e, 100);
+        before.observe_odds(&e, &[0.7, 0.3], 110);
+        let mut after = FeedPublisher::new();
+        after.record_new_market(&e, 200);
+        after.observe_odds(&e, &[0.7, 0.3], 210);
+
+        let ids: std::collections::HashSet<_> = before.items(&FeedFilter::default()).into_iter()
+            .chain(after.items(&FeedFilter::default()))
+            .map(|item| item.id.clone())
+            .collect();
+        assert_eq!(ids.len(), 4);
+        assert!(ids.iter().all(|id| id.contains(":m1:")));
+    }
+
+    #[test]
+    fn test_max_items_bounded() {
+        let mut feed = FeedPublisher::new();
+        feed.max_items = 2;
+        for i in 0..5 {
+            feed.record_new_market(&event(&format!("m{}", i), "general", &[]), i);
+        }
+        let items = feed.items(&FeedFilter::default());
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].event.market_id, "m4");
+    }
+}
@@ -0,0 +1,534 @@
+// ============================================================================
+// RSS Feed Worker - Live RSS/Atom ingestion into the pending-event inbox
+// ============================================================================
+//
+// Polls the feeds tracked by `RssFeedManager` and turns new items into
+// `PendingEvent`s:
+//
+//   fetch (HTTP or local file/dir) → feed-rs parse → RssEvent
+//   → validate → dedupe by content hash → PendingEvent
+//
+// Feeds that fail are retried with exponential backoff; the last error and
+// failure count are tracked per feed. Remote feeds share one HTTP client with
+// connect and request timeouts, so a hung host fails like any other error.
+// Content hashes of emitted items are persisted, so rejected or expired items
+// stay out of the inbox across restarts.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::market_rss::{parse_rss_xml, EventDates, RssEvent, RssFeedManager};
+use crate::market_resolve::cpmm::PendingEvent;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Default polling interval (5 minutes)
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 300;
+
+/// Maximum backoff after repeated failures (1 hour)
+pub const MAX_BACKOFF_SECS: u64 = 3600;
+
+/// Default limit on a whole feed request (connect + headers + body)
+pub const DEFAULT_FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// Limit on establishing the connection to a feed host
+const FETCH_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Confidence assigned to feed items (no AI scoring available)
+const FEED_ITEM_CONFIDENCE: f64 = 0.5;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+// ============================================================================
+// FEED SOURCE & STATUS
+// ============================================================================
+
+/// Where a feed is read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedSource {
+    /// http(s) URL
+    Remote(String),
+    /// Local feed file or directory of feed files
+    Local(PathBuf),
+}
+
+impl FeedSource {
+    pub fn parse(feed: &str) -> Self {
+        if feed.starts_with("http://") || feed.starts_with("https://") {
+            FeedSource::Remote(feed.to_string())
+        } else {
+            FeedSource::Local(PathBuf::from(feed.trim_start_matches("file://")))
+        }
+    }
+}
+
+/// Polling health for a single feed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedStatus {
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_attempt: Option<u64>,
+    pub last_success: Option<u64>,
+    /// Earliest time the feed should be polled again
+    pub next_poll_at: u64,
+    /// Total new events emitted from this feed
+    pub events_emitted: u64,
+}
+
+impl FeedStatus {
+    pub fn is_due(&self, now: u64) -> bool {
+        now >= self.next_poll_at
+    }
+
+    pub fn record_success(&mut self, now: u64, interval_secs: u64, emitted: usize) {
+        self.consecutive_failures = 0;
+        self.last_error = None;
+        self.last_attempt = Some(now);
+        self.last_success = Some(now);
+        self.next_poll_at = now + interval_secs;
+        self.events_emitted += emitted as u64;
+    }
+
+    /// Record a failure and schedule the next attempt with exponential backoff
+    pub fn record_failure(&mut self, now: u64, interval_secs: u64, error: String) {
+        self.consecutive_failures += 1;
+        self.last_error = Some(error);
+        self.last_attempt = Some(now);
+        self.next_poll_at = now + backoff_secs(interval_secs, self.consecutive_failures);
+    }
+}
+
+/// Backoff delay: interval * 2^(failures - 1), capped at MAX_BACKOFF_SECS
+pub fn backoff_secs(interval_secs: u64, failures: u32) -> u64 {
+    let exp = failures.saturating_sub(1).min(16);
+    interval_secs.saturating_mul(1u64 << exp).min(MAX_BACKOFF_SECS.max(interval_secs))
+}
+
+// ============================================================================
+// FETCH & PARSE
+// ============================================================================
+
+/// HTTP client for remote feeds; requests fail after `timeout_secs`
+pub fn feed_http_client(timeout_secs: u64) -> reqwest::Client {
+    let timeout = std::time::Duration::from_secs(timeout_secs.max(1));
+    reqwest::Client::builder()
+        .connect_timeout(timeout.min(std::time::Duration::from_secs(FETCH_CONNECT_TIMEOUT_SECS)))
+        .timeout(timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Fetch raw feed documents (one per file for local directories)
+pub async fn fetch_feed(client: &reqwest::Client, source: &FeedSource) -> Result<Vec<Vec<u8>>, String> {
+    match source {
+        FeedSource::Remote(url) => {
+            let resp = client.get(url)
+                .send()
+                .await
+                .map_err(|e| format!("Request failed: {}", e))?;
+            if !resp.status().is_success() {
+                return Err(format!("HTTP {}", resp.status()));
+            }
+            let bytes = resp.bytes()
+                .await
+                .map_err(|e| format!("Failed to read body: {}", e))?;
+            Ok(vec![bytes.to_vec()])
+        }
+        FeedSource::Local(path) => read_local_feed(path),
+    }
+}
+
+fn read_local_feed(path: &Path) -> Result<Vec<Vec<u8>>, String> {
+    if path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+            .map_err(|e| format!("Failed to read directory {}: {}", path.display(), e))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|p| matches!(
+                p.extension().and_then(|s| s.to_str()),
+                Some("rss") | Some("atom") | Some("xml")
+            ))
+            .collect();
+        files.sort();
+        files.iter()
+            .map(|p| std::fs::read(p).map_err(|e| format!("Failed to read {}: {}", p.display(), e)))
+            .collect()
+    } else {
+        std::fs::read(path)
+            .map(|bytes| vec![bytes])
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    }
+}
+
+/// Parse an RSS/Atom document into `RssEvent`s
+///
+/// Items get binary Yes/No outcomes unless the document carries BlackBook
+/// extension fields (as written by `write_rss_event_to_file`).
+pub fn parse_feed(bytes: &[u8]) -> Result<Vec<RssEvent>, String> {
+    let feed = feed_rs::parser::parse(bytes).map_err(|e| format!("Feed parse error: {}", e))?;
+    let feed_title = feed.title.as_ref().map(|t| t.content.trim().to_string());
+
+    // BlackBook single-item files carry outcomes/odds in extension elements
+    let raw = String::from_utf8_lossy(bytes);
+    let extension = if feed.entries.len() == 1 && raw.contains("blackbook:outcomes") {
+        parse_rss_xml(&raw)
+    } else {
+        None
+    };
+
+    let events = feed.entries.iter().map(|entry| {
+        let title = entry.title.as_ref().map(|t| t.content.trim().to_string()).unwrap_or_default();
+        let description = entry.summary.as_ref().map(|s| s.content.trim().to_string())
+            .or_else(|| entry.content.as_ref().and_then(|c| c.body.clone()))
+            .unwrap_or_else(|| title.clone());
+        let source_url = entry.links.first().map(|l| l.href.clone())
+            .or_else(|| Some(entry.id.clone()).filter(|id| id.starts_with("http")))
+            .unwrap_or_default();
+        let published = entry.published.or(entry.updated)
+            .map(|d| d.to_rfc3339())
+            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+        let tags: Vec<String> = entry.categories.iter().map(|c| c.term.clone()).collect();
+
+        let mut event = RssEvent {
+            title,
+            description,
+            source: feed_title.clone(),
+            category: tags.first().cloned(),
+            tags,
+            market_type: "binary".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            initial_probabilities: None,
+            source_url,
+            image_url: None,
+            dates: EventDates {
+                published,
+                freeze: None,
+                resolution: None,
+            },
+            resolution_rules: None,
+            market_id: entry.id.clone(),
+            added_to_ledger: false,
+        };
+
+        if let Some(ext) = &extension {
+            event.market_id = ext.market_id.clone();
+            event.outcomes = ext.outcomes.clone();
+            event.market_type = ext.market_type.clone();
+            event.initial_probabilities = ext.initial_probabilities.clone();
+            event.category = ext.category.clone().or(event.category);
+            if !ext.tags.is_empty() {
+                event.tags = ext.tags.clone();
+            }
+            event.dates.freeze = ext.dates.freeze.clone();
+            event.dates.resolution = ext.dates.resolution.clone();
+        }
+
+        event
+    }).collect();
+
+    Ok(events)
+}
+
+/// Convert a validated feed item into an inbox event
+pub fn to_pending_event(event: &RssEvent, content_hash: &str) -> PendingEvent {
+    let source_domain = event.source_url
+        .split("://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or_default()
+        .to_string();
+
+    let mut pending = PendingEvent::new(
+        format!("evt_{}", &content_hash[..16.min(content_hash.len())]),
+        event.title.clone(),
+        event.description.clone(),
+        event.get_category(),
+        event.outcomes.clone(),
+        FEED_ITEM_CONFIDENCE,
+        event.source_url.clone(),
+        source_domain,
+    );
+    pending.resolution_date = event.dates.resolution.clone();
+    pending
+}
+
+// ============================================================================
+// POLLING
+// ============================================================================
+
+/// Outcome of a polling pass
+#[derive(Debug, Clone, Default)]
+pub struct PollReport {
+    /// New, validated, deduplicated events
+    pub new_events: Vec<PendingEvent>,
+    /// Items skipped because their content hash was already seen
+    pub duplicates: usize,
+    /// (feed, reason) for items that failed validation
+    pub invalid: Vec<(String, String)>,
+    /// (feed, error) for feeds that failed to fetch/parse
+    pub errors: Vec<(String, String)>,
+}
+
+impl RssFeedManager {
+    /// Create a manager polling `feeds` (URLs, files or dirs)
+    pub fn with_feeds(feeds: &[String], poll_interval_secs: u64) -> Self {
+        let mut manager = Self::new();
+        for feed in feeds {
+            manager.add_feed(feed.clone());
+        }
+        manager.poll_interval_secs = poll_interval_secs;
+        manager
+    }
+
+    /// Load processed content hashes from `processed_path`.
+    /// Returns how many were restored (0 if there is no file yet).
+    pub fn load_processed(&mut self) -> Result<usize, String> {
+        let Some(path) = &self.processed_path else {
+            return Ok(0);
+        };
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let hashes: Vec<String> = serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        let count = hashes.len();
+        for hash in hashes {
+            self.mark_processed(hash);
+        }
+        Ok(count)
+    }
+
+    /// Write processed content hashes to `processed_path`, if set
+    pub fn save_processed(&self) -> Result<(), String> {
+        let Some(path) = &self.processed_path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).ok();
+        }
+        let json = serde_json::to_string(&self.processed_markets)
+            .map_err(|e| format!("Failed to serialize processed items: {}", e))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Validate and dedupe parsed items, marking new ones processed
+    pub fn ingest(&mut self, feed: &str, events: Vec<RssEvent>, report: &mut PollReport) -> usize {
+        let mut emitted = 0;
+        for mut event in events {
+            if let Err(e) = event.validate() {
+                report.invalid.push((feed.to_string(), format!("{}: {}", event.title, e)));
+                continue;
+            }
+            let hash = event.generate_content_hash();
+            if self.is_processed(&hash) {
+                report.duplicates += 1;
+                continue;
+            }
+            self.mark_processed(hash.clone());
+            event.market_id = hash.clone();
+            report.new_events.push(to_pending_event(&event, &hash));
+            emitted += 1;
+        }
+        emitted
+    }
+
+    /// Poll every feed that is due, honouring per-feed backoff
+    pub async fn poll_due_feeds(&mut self) -> PollReport {
+        let now = now();
+        let mut report = PollReport::default();
+        let interval = self.poll_interval_secs.max(1);
+
+        for feed in self.feed_urls.clone() {
+            if !self.feed_status.entry(feed.clone()).or_default().is_due(now) {
+                continue;
+            }
+
+            let result = fetch_feed(&self.http, &FeedSource::parse(&feed)).await.and_then(|docs| {
+                docs.iter()
+                    .map(|doc| parse_feed(doc))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|parsed| parsed.into_iter().flatten().collect::<Vec<_>>())
+            });
+
+            match result {
+                Ok(events) => {
+                    let emitted = self.ingest(&feed, events, &mut report);
+                    if let Some(status) = self.feed_status.get_mut(&feed) {
+                        status.record_success(now, interval, emitted);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("⚠️  RSS feed {} failed: {}", feed, e);
+                    if let Some(status) = self.feed_status.get_mut(&feed) {
+                        status.record_failure(now, interval, e.clone());
+                    }
+                    report.errors.push((feed.clone(), e));
+                }
+            }
+        }
+
+        if !report.new_events.is_empty() {
+            if let Err(e) = self.save_processed() {
+                eprintln!("⚠️  Failed to persist processed RSS items: {}", e);
+            }
+        }
+        self.update_poll_time();
+        report
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS_DOC: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Test Feed</title><link>https://example.com</link><description>d</description>
+<item><title>Will BTC hit $100k?</title><description>BTC price</description><link>https://example.com/btc</link><category>crypto</category><pubDate>Mon, 03 Nov 2025 01:32:51 +0000</pubDate></item>
+<item><title></title><description>missing title</description><link>https://example.com/empty</link></item>
+</channel></rss>"#;
+
+    const ATOM_DOC: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Atom Feed</title><id>urn:feed</id><updated>2025-11-03T00:00:00Z</updated>
+<entry><title>Will ETH flip BTC?</title><id>urn:e1</id><link href="https://example.com/eth"/><updated>2025-11-03T00:00:00Z</updated><summary>Flippening</summary></entry>
+</feed>"#;
+
+    #[test]
+    fn test_parse_rss_and_atom() {
+        let events = parse_feed(RSS_DOC.as_bytes()).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].title, "Will BTC hit $100k?");
+        assert_eq!(events[0].category.as_deref(), Some("crypto"));
+        assert_eq!(events[0].outcomes, vec!["Yes".to_string(), "No".to_string()]);
+
+        let events = parse_feed(ATOM_DOC.as_bytes()).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].source_url, "https://example.com/eth");
+        assert_eq!(events[0].description, "Flippening");
+
+        assert!(parse_feed(b"not a feed").is_err());
+    }
+
+    #[test]
+    fn test_parse_blackbook_market_file() {
+        let doc = include_str!("events/2475e0d72e79.rss");
+        let events = parse_feed(doc.as_bytes()).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].market_id, "2475e0d72e793adc23119d730c1c380d9194b5ba7d618cd4acf4baedebe74d50");
+        assert_eq!(events[0].outcomes, vec!["Yes", "No Change", "No"]);
+        assert!(events[0].title.starts_with("Will 'Proof of Engagement"));
+    }
+
+    #[test]
+    fn test_ingest_validates_and_dedupes() {
+        let mut manager = RssFeedManager::new();
+        let mut report = PollReport::default();
+
+        let emitted = manager.ingest("feed", parse_feed(RSS_DOC.as_bytes()).unwrap(), &mut report);
+        assert_eq!(emitted, 1);
+        assert_eq!(report.invalid.len(), 1);
+        assert!(report.new_events[0].id.starts_with("evt_"));
+        assert_eq!(report.new_events[0].source_domain, "example.com");
+
+        let emitted = manager.ingest("feed", parse_feed(RSS_DOC.as_bytes()).unwrap(), &mut report);
+        assert_eq!(emitted, 0);
+        assert_eq!(report.duplicates, 1);
+    }
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        assert_eq!(backoff_secs(60, 1), 60);
+        assert_eq!(backoff_secs(60, 2), 120);
+        assert_eq!(backoff_secs(60, 3), 240);
+        assert_eq!(backoff_secs(60, 30), MAX_BACKOFF_SECS);
+
+        let mut status = FeedStatus::default();
+        status.record_failure(1_000, 60, "boom".to_string());
+        status.record_failure(1_000, 60, "boom".to_string());
+        assert_eq!(status.consecutive_failures, 2);
+        assert_eq!(status.next_poll_at, 1_120);
+        assert!(!status.is_due(1_100));
+
+        status.record_success(1_200, 60, 3);
+        assert_eq!(status.consecutive_failures, 0);
+        assert!(status.last_error.is_none());
+        assert_eq!(status.events_emitted, 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_local_directory_with_error_tracking() {
+        let dir = std::env::temp_dir().join(format!("rss_worker_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.rss"), RSS_DOC).unwrap();
+        std::fs::write(dir.join("b.atom"), ATOM_DOC).unwrap();
+
+        let mut manager = RssFeedManager::new();
+        manager.add_feed(dir.display().to_string());
+        manager.add_feed(dir.join("missing.rss").display().to_string());
+
+        let report = manager.poll_due_feeds().await;
+        assert_eq!(report.new_events.len(), 2);
+        assert_eq!(report.errors.len(), 1);
+
+        let missing = dir.join("missing.rss").display().to_string();
+        let status = &manager.feed_status[&missing];
+        assert_eq!(status.consecutive_failures, 1);
+        assert!(status.last_error.is_some());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_processed_items_survive_a_restart() {
+        let dir = std::env::temp_dir().join(format!("rss_processed_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let feed = dir.join("feed.atom");
+        std::fs::write(&feed, ATOM_DOC).unwrap();
+
+        let manager = || {
+            let mut manager = RssFeedManager::with_feeds(&[feed.display().to_string()], 60);
+            manager.processed_path = Some(dir.join("processed.json"));
+            manager
+        };
+
+        let mut first = manager();
+        assert_eq!(first.load_processed().unwrap(), 0);
+        assert_eq!(first.poll_due_feeds().await.new_events.len(), 1);
+
+        let mut restarted = manager();
+        assert_eq!(restarted.load_processed().unwrap(), 1);
+        let report = restarted.poll_due_feeds().await;
+        assert!(report.new_events.is_empty());
+        assert_eq!(report.duplicates, 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_hung_feed_host_times_out() {
+        // Accepts the connection but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/feed.rss", listener.local_addr().unwrap());
+
+        let started = std::time::Instant::now();
+        let result = fetch_feed(&feed_http_client(1), &FeedSource::parse(&url)).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        drop(listener);
+    }
+}
@@ -13,7 +13,9 @@ use std::collections::HashMap;
 use sha2::{Sha256, Digest};
 use hex;
 use std::fs;
-use std::path::Path;
+use std::path::PathBuf;
+use crate::market_resolve::CompiledRules;
+use super::feed_worker::{feed_http_client, FeedStatus, DEFAULT_FETCH_TIMEOUT_SECS, DEFAULT_POLL_INTERVAL_SECS};
 
 // ============================================================================
 // EVENT DATES
@@ -53,6 +55,11 @@ pub struct ResolutionRules {
     /// Map of outcome name → resolution condition
     /// e.g., { "YES": "BTC price > $100k on Jan 1, 2025" }
     pub conditions: HashMap<String, String>,
+    
+    /// Map of outcome name → machine-checkable rule expression
+    /// e.g., { "YES": "BTC > 100000 AND date <= 2025-01-01", "NO": "otherwise" }
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome_rules: Option<HashMap<String, String>>,
 }
 
 impl ResolutionRules {
@@ -62,6 +69,7 @@ impl ResolutionRules {
             provider: None,
             data_source: None,
             conditions,
+            outcome_rules: None,
         }
     }
     
@@ -71,6 +79,7 @@ impl ResolutionRules {
             provider: None,
             data_source: None,
             conditions: HashMap::new(),
+            outcome_rules: None,
         }
     }
     
@@ -151,6 +160,47 @@ fn default_market_type() -> String {
 }
 
 impl RssEvent {
+    /// Create an event with explicit odds and lifecycle dates
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        market_id: String,
+        title: String,
+        description: String,
+        outcomes: Vec<String>,
+        initial_probabilities: Vec<f64>,
+        source_url: String,
+        published: String,
+        freeze: String,
+        resolution: String,
+    ) -> Self {
+        let market_type = match outcomes.len() {
+            2 => "binary",
+            3 => "three_choice",
+            _ => "multi",
+        }.to_string();
+        
+        Self {
+            title,
+            description,
+            source: None,
+            category: None,
+            tags: Vec::new(),
+            market_type,
+            outcomes,
+            initial_probabilities: Some(initial_probabilities),
+            source_url,
+            image_url: None,
+            dates: EventDates {
+                published,
+                freeze: Some(freeze),
+                resolution: Some(resolution),
+            },
+            resolution_rules: None,
+            market_id,
+            added_to_ledger: false,
+        }
+    }
+    
     /// Get probabilities with fallback to equal split
     pub fn get_probabilities(&self) -> Vec<f64> {
         if let Some(ref probs) = self.initial_probabilities {
@@ -226,6 +276,11 @@ impl RssEvent {
             }
         }
         
+        // If machine-checkable rules provided, they must parse
+        if let Some(rules) = self.resolution_rules.as_ref().and_then(|r| r.outcome_rules.as_ref()) {
+            CompiledRules::compile(rules, &self.outcomes).map_err(|e| e.to_string())?;
+        }
+        
         Ok(())
     }
     
@@ -272,7 +327,7 @@ impl RssEvent {
 // ============================================================================
 
 /// Manages RSS feed subscriptions and event processing
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct RssFeedManager {
     /// Processed market IDs (to avoid duplicates)
     pub processed_markets: Vec<String>,
@@ -282,6 +337,18 @@ pub struct RssFeedManager {
     
     /// Last poll timestamp
     pub last_poll: u64,
+    
+    /// Per-feed polling health (failures, backoff, last error)
+    pub feed_status: HashMap<String, FeedStatus>,
+    
+    /// Seconds between successful polls of a feed
+    pub poll_interval_secs: u64,
+    
+    /// Client for remote feeds (with connect and request timeouts)
+    pub http: reqwest::Client,
+    
+    /// Where processed content hashes are kept across restarts
+    pub processed_path: Option<PathBuf>,
 }
 
 impl RssFeedManager {
@@ -291,6 +358,10 @@ impl RssFeedManager {
             processed_markets: Vec::new(),
             feed_urls: Vec::new(),
             last_poll: 0,
+            feed_status: HashMap::new(),
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            http: feed_http_client(DEFAULT_FETCH_TIMEOUT_SECS),
+            processed_path: None,
         }
     }
     
@@ -322,6 +393,12 @@ impl RssFeedManager {
     }
 }
 
+impl Default for RssFeedManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ============================================================================
 // RSS FILE PERSISTENCE
 // ============================================================================
@@ -415,7 +492,7 @@ pub fn load_rss_events_from_folder(rss_dir: &str) -> Vec<RssEvent> {
 }
 
 /// Parse RSS XML content into RssEvent (simplified parser)
-fn parse_rss_xml(xml: &str) -> Option<RssEvent> {
+pub(crate) fn parse_rss_xml(xml: &str) -> Option<RssEvent> {
     // Extract values between XML tags
     let extract = |tag: &str| -> Option<String> {
         let start_tag = format!("<{}>", tag);
@@ -476,7 +553,7 @@ fn parse_rss_xml(xml: &str) -> Option<RssEvent> {
 }
 
 /// Escape XML special characters
-fn escape_xml(s: &str) -> String {
+pub(crate) fn escape_xml(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -6,8 +6,15 @@
 // into on-chain prediction markets.
 //
 // Flow: RSS Feed → RssEvent → PendingEvent → Active Market (via CPMM)
+//
+//   - market_rss: Event payload, feed manager, file persistence
+//   - feed_worker: Live RSS/Atom polling into the pending-event inbox
 // ============================================================================
 
 pub mod market_rss;
+pub mod feed_worker;
+pub mod feed_publisher;
 
 pub use market_rss::*;
+pub use feed_worker::*;
+pub use feed_publisher::*;
@@ -0,0 +1,537 @@
+//! API Errors
+//!
+//! Every HTTP handler fails with an [`ApiError`]. It renders as
+//!
+//! ```json
+//! { "success": false, "code": "INSUFFICIENT_BALANCE", "error": "Insufficient balance: ...", "available": 5.0 }
+//! ```
+//!
+//! with the matching HTTP status. `code` is stable and meant for clients to
+//! branch on; `error` is human readable and may change. Variant-specific
+//! fields (balances, deadlines, ids) sit next to them.
+//!
+//! Domain errors convert with `?`; each of their variants keeps its own code.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::{json, Value};
+use utoipa::openapi::schema::{AdditionalProperties, ObjectBuilder, Schema, SchemaType};
+use utoipa::openapi::RefOr;
+use utoipa::ToSchema;
+
+use crate::bridge::BridgeError;
+use crate::easteregg::GodModeError;
+use crate::idempotency::IdempotencyError;
+use crate::market_actor::TradeError;
+use crate::market_resolve::{DisputeError, RuleError};
+use crate::orderbook::OrderError;
+use crate::pagination::PageError;
+use crate::rate_limit::RateLimited;
+use crate::roles::RoleError;
+use crate::rpc::SignedTxError;
+
+// ============================================================================
+// ERROR
+// ============================================================================
+
+#[derive(Debug)]
+pub enum ApiError {
+    /// Malformed or invalid input (400)
+    BadRequest(String),
+    /// Missing or invalid credentials/signature (401)
+    Unauthorized(String),
+    /// Authenticated but not allowed (403)
+    Forbidden(String),
+    /// Generic missing resource (404)
+    NotFound(String),
+    /// Request conflicts with current state (409)
+    Conflict(String),
+    /// Resource existed but has expired (410)
+    Gone(String),
+    /// Nonce not above the last one used by the sender (400)
+    InvalidNonce { got: u64, last: u64 },
+    /// Wallet BB balance does not cover the request (400)
+    InsufficientBalance { available: f64, required: f64 },
+    /// No market with this id (404)
+    MarketNotFound(String),
+    /// No ledger account for this address (404)
+    AccountNotFound(String),
+    /// L2 is frozen; only forced exits are served (503)
+    Frozen { reason: String, frozen_at: u64 },
+    /// L1 could not be reached or rejected the call (502)
+    L1Unavailable(String),
+    /// Bug or broken invariant on our side (500)
+    Internal(String),
+    Order(OrderError),
+    Trade(TradeError),
+    Bridge(BridgeError),
+    SignedTx(SignedTxError),
+    Dispute(DisputeError),
+    Rule(RuleError),
+    GodMode(GodModeError),
+    Page(PageError),
+    Idempotency(IdempotencyError),
+    /// Too many requests from this IP or wallet (429)
+    RateLimited(RateLimited),
+    Role(RoleError),
+    /// Any of the above with extra fields in the response body
+    Detailed(Box<ApiError>, Value),
+}
+
+impl ApiError {
+    /// Attach extra fields (an object) to the response body
+    pub fn with(self, details: Value) -> Self {
+        ApiError::Detailed(Box::new(self), details)
+    }
+
+    /// Stable machine-readable code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::BadRequest(_) => "BAD_REQUEST",
+            ApiError::Unauthorized(_) => "UNAUTHORIZED",
+            ApiError::Forbidden(_) => "FORBIDDEN",
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::Conflict(_) => "CONFLICT",
+            ApiError::Gone(_) => "GONE",
+            ApiError::InvalidNonce { .. } => "INVALID_NONCE",
+            ApiError::InsufficientBalance { .. } => "INSUFFICIENT_BALANCE",
+            ApiError::MarketNotFound(_) => "MARKET_NOT_FOUND",
+            ApiError::AccountNotFound(_) => "ACCOUNT_NOT_FOUND",
+            ApiError::Frozen { .. } => "FROZEN",
+            ApiError::L1Unavailable(_) => "L1_UNAVAILABLE",
+            ApiError::Internal(_) => "INTERNAL_ERROR",
+            ApiError::Order(e) => match e {
+                OrderError::InvalidPrice(_) => "INVALID_PRICE",
+                OrderError::InvalidSize(_) => "INVALID_SIZE",
+                OrderError::InvalidOutcome(_) => "INVALID_OUTCOME",
+                OrderError::InsufficientBalance(_) => "INSUFFICIENT_BALANCE",
+                OrderError::InsufficientShares(_) => "INSUFFICIENT_SHARES",
+                OrderError::OrderNotFound(_) => "ORDER_NOT_FOUND",
+                OrderError::OrderNotActive(_) => "ORDER_NOT_ACTIVE",
+                OrderError::Unauthorized(_) => "NOT_ORDER_OWNER",
+                OrderError::MarketNotFound(_) => "MARKET_NOT_FOUND",
+                OrderError::MarketClosed(_) => "MARKET_CLOSED",
+                OrderError::InvalidSignature(_) => "INVALID_SIGNATURE",
+                OrderError::InvalidNonce(_) => "INVALID_NONCE",
+                OrderError::Expired(_) => "TX_EXPIRED",
+            },
+            ApiError::Trade(e) => match e {
+                TradeError::InsufficientShares { .. } => "INSUFFICIENT_SHARES",
+                TradeError::InsufficientBalance { .. } => "INSUFFICIENT_BALANCE",
+                TradeError::InsufficientPairs { .. } => "INSUFFICIENT_PAIRS",
+                TradeError::NoWinningShares { .. } => "NO_WINNING_SHARES",
+                TradeError::Rejected(_) => "ORDER_REJECTED",
+                TradeError::Settlement(_) => "SETTLEMENT_FAILED",
+                TradeError::Unavailable => "MARKET_UNAVAILABLE",
+            },
+            ApiError::Bridge(e) => match e {
+                BridgeError::InvalidAmount(_) => "INVALID_AMOUNT",
+                BridgeError::InvalidAddress(_) => "INVALID_ADDRESS",
+                BridgeError::InvalidDirection(_) => "INVALID_DIRECTION",
+                BridgeError::InsufficientBalance { .. } => "INSUFFICIENT_BALANCE",
+                BridgeError::BridgeNotFound(_) => "BRIDGE_NOT_FOUND",
+                BridgeError::BridgeAlreadyCompleted(_) => "BRIDGE_ALREADY_COMPLETED",
+                BridgeError::BridgeExpired(_) => "BRIDGE_EXPIRED",
+                BridgeError::SignatureVerificationFailed(_) => "INVALID_SIGNATURE",
+                BridgeError::InvalidProof(_) => "INVALID_PROOF",
+                BridgeError::L1CommunicationError(_) => "L1_UNAVAILABLE",
+                BridgeError::InternalError(_) => "INTERNAL_ERROR",
+            },
+            ApiError::SignedTx(e) => match e {
+                SignedTxError::InvalidPubkey(_) => "INVALID_PUBKEY",
+                SignedTxError::InvalidSignature(_) | SignedTxError::SignatureMismatch => "INVALID_SIGNATURE",
+                SignedTxError::Expired => "TX_EXPIRED",
+                SignedTxError::TypeMismatch => "TX_TYPE_MISMATCH",
+                SignedTxError::SerializationError(_) => "INVALID_PAYLOAD",
+                SignedTxError::L1VerificationFailed(_) => "L1_VERIFICATION_FAILED",
+            },
+            ApiError::Dispute(e) => match e {
+                DisputeError::ProposalNotFound(_) => "PROPOSAL_NOT_FOUND",
+                DisputeError::AlreadyProposed(_) => "ALREADY_PROPOSED",
+                DisputeError::AlreadyDisputed(_) => "ALREADY_DISPUTED",
+                DisputeError::AlreadyFinalized(_) => "ALREADY_FINALIZED",
+                DisputeError::WindowClosed { .. } => "CHALLENGE_WINDOW_CLOSED",
+                DisputeError::WindowOpen { .. } => "CHALLENGE_WINDOW_OPEN",
+                DisputeError::NotDisputed(_) => "NOT_DISPUTED",
+                DisputeError::InsufficientBond { .. } => "INSUFFICIENT_BOND",
+                DisputeError::InvalidOutcome { .. } => "INVALID_OUTCOME",
+                DisputeError::SelfDispute => "SELF_DISPUTE",
+                DisputeError::PartyArbiter(_) => "PARTY_ARBITER",
+            },
+            ApiError::Rule(e) => match e {
+                RuleError::Empty | RuleError::UnknownOutcome(_) | RuleError::Parse { .. } => "INVALID_RULES",
+                RuleError::MissingData(_) => "RULE_DATA_MISSING",
+                RuleError::NoOutcomeMatched => "NO_OUTCOME_MATCHED",
+            },
+            ApiError::Page(e) => match e {
+                PageError::InvalidCursor(_) => "INVALID_CURSOR",
+                PageError::InvalidFilter { .. } => "INVALID_FILTER",
+            },
+            ApiError::Idempotency(e) => match e {
+                IdempotencyError::InvalidKey(_) => "INVALID_IDEMPOTENCY_KEY",
+                IdempotencyError::KeyReused(_) => "IDEMPOTENCY_KEY_REUSED",
+                IdempotencyError::InProgress(_) => "IDEMPOTENCY_KEY_IN_USE",
+                IdempotencyError::BodyTooLarge => "PAYLOAD_TOO_LARGE",
+            },
+            ApiError::RateLimited(_) => "RATE_LIMITED",
+            ApiError::Role(e) => match e {
+                RoleError::Unsigned(_) => "SIGNATURE_REQUIRED",
+                RoleError::InvalidSignature => "INVALID_SIGNATURE",
+                RoleError::Expired { .. } => "SIGNATURE_EXPIRED",
+                RoleError::Replayed => "SIGNATURE_REPLAYED",
+                RoleError::Forbidden { .. } => "ROLE_REQUIRED",
+                RoleError::InvalidKey(_) => "INVALID_KEY",
+                RoleError::LastAdmin => "LAST_ADMIN",
+                RoleError::Unchanged { .. } => "ROLE_UNCHANGED",
+            },
+            ApiError::GodMode(e) => match e {
+                GodModeError::Disabled => "GOD_MODE_DISABLED",
+                GodModeError::InvalidAmount(_) => "INVALID_AMOUNT",
+                GodModeError::AccountNotFound(_) => "ACCOUNT_NOT_FOUND",
+                GodModeError::InvalidSignature => "INVALID_SIGNATURE",
+                GodModeError::Unauthorized => "FORBIDDEN",
+                GodModeError::AuditFailed(_) => "AUDIT_UNAVAILABLE",
+            },
+            ApiError::Detailed(inner, _) => inner.code(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) | ApiError::InvalidNonce { .. } | ApiError::InsufficientBalance { .. } => {
+                StatusCode::BAD_REQUEST
+            }
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) | ApiError::MarketNotFound(_) | ApiError::AccountNotFound(_) => {
+                StatusCode::NOT_FOUND
+            }
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Gone(_) => StatusCode::GONE,
+            ApiError::Frozen { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::L1Unavailable(_) => StatusCode::BAD_GATEWAY,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Order(e) => match e {
+                OrderError::OrderNotFound(_) | OrderError::MarketNotFound(_) => StatusCode::NOT_FOUND,
+                OrderError::Unauthorized(_) => StatusCode::FORBIDDEN,
+                OrderError::InvalidSignature(_) | OrderError::Expired(_) => StatusCode::UNAUTHORIZED,
+                OrderError::OrderNotActive(_) | OrderError::MarketClosed(_) => StatusCode::CONFLICT,
+                _ => StatusCode::BAD_REQUEST,
+            },
+            ApiError::Trade(e) => match e {
+                TradeError::Settlement(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                TradeError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+                _ => StatusCode::BAD_REQUEST,
+            },
+            ApiError::Bridge(e) => match e {
+                BridgeError::BridgeNotFound(_) => StatusCode::NOT_FOUND,
+                BridgeError::BridgeAlreadyCompleted(_) => StatusCode::CONFLICT,
+                BridgeError::BridgeExpired(_) => StatusCode::GONE,
+                BridgeError::SignatureVerificationFailed(_) => StatusCode::UNAUTHORIZED,
+                BridgeError::L1CommunicationError(_) => StatusCode::BAD_GATEWAY,
+                BridgeError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::BAD_REQUEST,
+            },
+            ApiError::SignedTx(e) => match e {
+                SignedTxError::InvalidPubkey(_)
+                | SignedTxError::TypeMismatch
+                | SignedTxError::SerializationError(_) => StatusCode::BAD_REQUEST,
+                _ => StatusCode::UNAUTHORIZED,
+            },
+            ApiError::Dispute(e) => match e {
+                DisputeError::ProposalNotFound(_) => StatusCode::NOT_FOUND,
+                DisputeError::SelfDispute | DisputeError::PartyArbiter(_) => StatusCode::FORBIDDEN,
+                DisputeError::InsufficientBond { .. } | DisputeError::InvalidOutcome { .. } => StatusCode::BAD_REQUEST,
+                _ => StatusCode::CONFLICT,
+            },
+            ApiError::Rule(e) => match e {
+                RuleError::MissingData(_) | RuleError::NoOutcomeMatched => StatusCode::CONFLICT,
+                _ => StatusCode::BAD_REQUEST,
+            },
+            ApiError::GodMode(e) => match e {
+                GodModeError::Disabled | GodModeError::Unauthorized => StatusCode::FORBIDDEN,
+                GodModeError::InvalidAmount(_) => StatusCode::BAD_REQUEST,
+                GodModeError::AccountNotFound(_) => StatusCode::NOT_FOUND,
+                GodModeError::InvalidSignature => StatusCode::UNAUTHORIZED,
+                GodModeError::AuditFailed(_) => StatusCode::SERVICE_UNAVAILABLE,
+            },
+            ApiError::Page(_) => StatusCode::BAD_REQUEST,
+            ApiError::Idempotency(e) => match e {
+                IdempotencyError::InvalidKey(_) => StatusCode::BAD_REQUEST,
+                IdempotencyError::KeyReused(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                IdempotencyError::InProgress(_) => StatusCode::CONFLICT,
+                IdempotencyError::BodyTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            },
+            ApiError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Role(e) => match e {
+                RoleError::Unsigned(_) | RoleError::InvalidSignature | RoleError::Expired { .. } | RoleError::Replayed => {
+                    StatusCode::UNAUTHORIZED
+                }
+                RoleError::Forbidden { .. } => StatusCode::FORBIDDEN,
+                RoleError::InvalidKey(_) => StatusCode::BAD_REQUEST,
+                RoleError::LastAdmin | RoleError::Unchanged { .. } => StatusCode::CONFLICT,
+            },
+            ApiError::Detailed(inner, _) => inner.status(),
+        }
+    }
+
+    /// Variant-specific fields for the response body
+    fn details(&self) -> Value {
+        match self {
+            ApiError::InvalidNonce { got, last } => json!({ "got": got, "last_nonce": last }),
+            ApiError::InsufficientBalance { available, required }
+            | ApiError::Trade(TradeError::InsufficientBalance { available, required }) => {
+                json!({ "available": available, "required": required })
+            }
+            ApiError::Frozen { frozen_at, .. } => json!({ "frozen_at": frozen_at }),
+            ApiError::Trade(TradeError::InsufficientShares { available, requested })
+            | ApiError::Bridge(BridgeError::InsufficientBalance { available, requested }) => {
+                json!({ "available": available, "requested": requested })
+            }
+            ApiError::Trade(TradeError::InsufficientPairs { yes_shares, no_shares, requested }) => json!({
+                "yes_shares": yes_shares,
+                "no_shares": no_shares,
+                "max_redeemable_pairs": yes_shares.min(*no_shares),
+                "requested": requested
+            }),
+            ApiError::Trade(TradeError::NoWinningShares { yes_shares, no_shares }) => json!({
+                "your_position": { "yes_shares": yes_shares, "no_shares": no_shares }
+            }),
+            ApiError::Dispute(DisputeError::WindowClosed { deadline }) => json!({ "deadline": deadline }),
+            ApiError::Dispute(DisputeError::WindowOpen { remaining_secs }) => {
+                json!({ "remaining_secs": remaining_secs })
+            }
+            ApiError::Dispute(DisputeError::InsufficientBond { required, provided }) => {
+                json!({ "required": required, "provided": provided })
+            }
+            ApiError::Page(PageError::InvalidFilter { field, value }) => json!({ "field": field, "value": value }),
+            ApiError::Role(RoleError::Forbidden { role, signer }) => json!({ "role": role.as_str(), "signer": signer }),
+            ApiError::Role(RoleError::Expired { timestamp, now }) => json!({ "timestamp": timestamp, "server_time": now }),
+            ApiError::RateLimited(e) => json!({
+                "class": e.class.as_str(),
+                "scope": e.scope.as_str(),
+                "retry_after": e.retry_after,
+            }),
+            ApiError::Detailed(inner, extra) => {
+                let mut details = inner.details();
+                if let (Value::Object(details), Value::Object(extra)) = (&mut details, extra) {
+                    details.extend(extra.clone());
+                }
+                details
+            }
+            _ => json!({}),
+        }
+    }
+
+    /// Response body: `success`, `code`, `error` plus details
+    pub fn body(&self) -> Value {
+        let mut body = self.details();
+        body["success"] = json!(false);
+        body["code"] = json!(self.code());
+        body["error"] = json!(self.to_string());
+        body
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::BadRequest(msg)
+            | ApiError::Unauthorized(msg)
+            | ApiError::Forbidden(msg)
+            | ApiError::NotFound(msg)
+            | ApiError::Conflict(msg)
+            | ApiError::Gone(msg)
+            | ApiError::Internal(msg) => write!(f, "{}", msg),
+            ApiError::InvalidNonce { got, last } => write!(f, "Invalid nonce: got {}, expected > {}", got, last),
+            ApiError::InsufficientBalance { available, required } => {
+                write!(f, "Insufficient balance: have {} BB, need {} BB", available, required)
+            }
+            ApiError::MarketNotFound(id) => write!(f, "Market {} not found", id),
+            ApiError::AccountNotFound(address) => write!(f, "Account {} not found", address),
+            ApiError::Frozen { reason, .. } => write!(f, "L2 is frozen ({}); only exits are processed", reason),
+            ApiError::L1Unavailable(msg) => write!(f, "L1 unavailable: {}", msg),
+            ApiError::Order(e) => write!(f, "{}", e),
+            ApiError::Trade(e) => write!(f, "{}", e),
+            ApiError::Bridge(e) => write!(f, "{}", e),
+            ApiError::SignedTx(e) => write!(f, "{}", e),
+            ApiError::Dispute(e) => write!(f, "{}", e),
+            ApiError::Rule(e) => write!(f, "{}", e),
+            ApiError::GodMode(e) => write!(f, "{}", e),
+            ApiError::Page(e) => write!(f, "{}", e),
+            ApiError::Idempotency(e) => write!(f, "{}", e),
+            ApiError::RateLimited(e) => write!(f, "{}", e),
+            ApiError::Role(e) => write!(f, "{}", e),
+            ApiError::Detailed(inner, _) => write!(f, "{}", inner),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self.body())).into_response()
+    }
+}
+
+// ============================================================================
+// SCHEMA
+// ============================================================================
+
+/// The error envelope as it appears in the OpenAPI document
+impl<'s> ToSchema<'s> for ApiError {
+    fn schema() -> (&'s str, RefOr<Schema>) {
+        let schema = ObjectBuilder::new()
+            .description(Some("Error envelope. Variant-specific fields (balances, deadlines, ids) sit next to `code`."))
+            .property("success", ObjectBuilder::new().schema_type(SchemaType::Boolean).example(Some(json!(false))))
+            .required("success")
+            .property(
+                "code",
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::String)
+                    .description(Some("Stable machine-readable code"))
+                    .example(Some(json!("MARKET_NOT_FOUND"))),
+            )
+            .required("code")
+            .property(
+                "error",
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::String)
+                    .description(Some("Human readable message")),
+            )
+            .required("error")
+            .additional_properties(Some(AdditionalProperties::FreeForm(true)))
+            .build();
+        ("ApiError", schema.into())
+    }
+}
+
+// ============================================================================
+// CONVERSIONS
+// ============================================================================
+
+impl From<OrderError> for ApiError {
+    fn from(e: OrderError) -> Self {
+        ApiError::Order(e)
+    }
+}
+
+impl From<TradeError> for ApiError {
+    fn from(e: TradeError) -> Self {
+        ApiError::Trade(e)
+    }
+}
+
+impl From<BridgeError> for ApiError {
+    fn from(e: BridgeError) -> Self {
+        ApiError::Bridge(e)
+    }
+}
+
+impl From<SignedTxError> for ApiError {
+    fn from(e: SignedTxError) -> Self {
+        ApiError::SignedTx(e)
+    }
+}
+
+impl From<DisputeError> for ApiError {
+    fn from(e: DisputeError) -> Self {
+        ApiError::Dispute(e)
+    }
+}
+
+impl From<RuleError> for ApiError {
+    fn from(e: RuleError) -> Self {
+        ApiError::Rule(e)
+    }
+}
+
+impl From<GodModeError> for ApiError {
+    fn from(e: GodModeError) -> Self {
+        ApiError::GodMode(e)
+    }
+}
+
+impl From<PageError> for ApiError {
+    fn from(e: PageError) -> Self {
+        ApiError::Page(e)
+    }
+}
+
+impl From<IdempotencyError> for ApiError {
+    fn from(e: IdempotencyError) -> Self {
+        ApiError::Idempotency(e)
+    }
+}
+
+impl From<RateLimited> for ApiError {
+    fn from(e: RateLimited) -> Self {
+        ApiError::RateLimited(e)
+    }
+}
+
+impl From<RoleError> for ApiError {
+    fn from(e: RoleError) -> Self {
+        ApiError::Role(e)
+    }
+}
+
+/// Plain `String` errors come from validation in the domain managers
+/// (ledger, pools, batches); L1 failures should use `L1Unavailable`.
+impl From<String> for ApiError {
+    fn from(e: String) -> Self {
+        ApiError::BadRequest(e)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roles::Role;
+
+    #[test]
+    fn test_body_has_code_error_and_details() {
+        let e = ApiError::Trade(TradeError::InsufficientBalance { available: 5.0, required: 10.0 });
+        assert_eq!(e.status(), StatusCode::BAD_REQUEST);
+        let body = e.body();
+        assert_eq!(body["success"], json!(false));
+        assert_eq!(body["code"], json!("INSUFFICIENT_BALANCE"));
+        assert_eq!(body["available"], json!(5.0));
+        assert_eq!(body["error"], json!("Insufficient balance: have 5 BB, need 10 BB"));
+    }
+
+    #[test]
+    fn test_domain_errors_keep_their_codes() {
+        let cases: Vec<(ApiError, &str, StatusCode)> = vec![
+            (OrderError::OrderNotFound("o1".into()).into(), "ORDER_NOT_FOUND", StatusCode::NOT_FOUND),
+            (OrderError::Unauthorized("w".into()).into(), "NOT_ORDER_OWNER", StatusCode::FORBIDDEN),
+            (BridgeError::BridgeExpired("b1".into()).into(), "BRIDGE_EXPIRED", StatusCode::GONE),
+            (SignedTxError::Expired.into(), "TX_EXPIRED", StatusCode::UNAUTHORIZED),
+            (DisputeError::SelfDispute.into(), "SELF_DISPUTE", StatusCode::FORBIDDEN),
+            (GodModeError::Disabled.into(), "GOD_MODE_DISABLED", StatusCode::FORBIDDEN),
+            (RuleError::NoOutcomeMatched.into(), "NO_OUTCOME_MATCHED", StatusCode::CONFLICT),
+            (RoleError::Forbidden { role: Role::Dealer, signer: "ab".into() }.into(), "ROLE_REQUIRED", StatusCode::FORBIDDEN),
+            (IdempotencyError::KeyReused("k".into()).into(), "IDEMPOTENCY_KEY_REUSED", StatusCode::UNPROCESSABLE_ENTITY),
+            ("bad amount".to_string().into(), "BAD_REQUEST", StatusCode::BAD_REQUEST),
+        ];
+        for (e, code, status) in cases {
+            assert_eq!((e.code(), e.status()), (code, status), "{}", e);
+        }
+    }
+
+    #[test]
+    fn test_detailed_keeps_code_and_merges_fields() {
+        let e = ApiError::InvalidNonce { got: 3, last: 7 }.with(json!({ "address": "L1_ALICE" }));
+        assert_eq!(e.code(), "INVALID_NONCE");
+        let body = e.body();
+        assert_eq!(body["last_nonce"], json!(7));
+        assert_eq!(body["address"], json!("L1_ALICE"));
+        assert_eq!(body["error"], json!("Invalid nonce: got 3, expected > 7"));
+    }
+}
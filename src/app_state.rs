@@ -2,17 +2,69 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, LockResult, Mutex, MutexGuard};
 use crate::models::PredictionMarket;
-use crate::market_resolve::{Ledger as MarketLedger, cpmm::PendingEvent};
+use crate::market_resolve::{Ledger as MarketLedger, cpmm::{PendingEvent, EventStatus}, DisputeManager, DisputeError, BondSettlement, ResolutionProposal};
+use crate::api_error::ApiError;
 use crate::auth::{SupabaseConfig, User};
 use crate::bridge::BridgeManager;
-use crate::ledger::Ledger;
-use crate::orderbook::OrderBookManager;
-use crate::shares::SharesManager;
-use crate::rpc::L1BlackBookRpc;
+use crate::bridge_proof::DepositVerifier;
+use crate::withdrawal_batch::WithdrawalBatcher;
+use crate::state_root::{SequencerKey, StateCommitter, StateLeaf, compute_state_root};
+use crate::blocks::{Block, BlockProducer};
+use crate::forced_exit::{ExitManager, ExitPosition};
+use crate::session_receipt::SettlementReceipt;
+use crate::withdrawal_batch::WithdrawalLeaf;
+use crate::ledger_service::LedgerService;
+use crate::market_actor::{MarketRegistry, MarketSnapshot};
+use crate::orderbook::OddsSource;
+use crate::market_search::MarketIndex;
+use crate::roles::{Role, RoleRegistry, SignedRequest};
+use crate::audit_log::{AuditEvent, AuditLog};
+use axum::extract::FromRef;
+use crate::rpc::{L1Backend, l1_backend_from_config};
+use crate::config::Config;
+use crate::rss::{parse_feed, FeedPublisher, FeedStatus};
+use crate::price_resolver::ResolverStatus;
 
-pub type SharedState = Arc<Mutex<AppState>>;
+/// Application state behind one lock, with the ledger and the market actors
+/// beside it so trading never waits on that lock
+///
+/// Markets, sessions, the search index and the outbound feed stay under the
+/// lock: a trade's bet record, session PnL, volume and odds are written with
+/// the market they belong to, and persisted with it. Handlers take it once
+/// before a trade and, when the fill matters, once after it.
+#[derive(Clone)]
+pub struct SharedState {
+    app: Arc<Mutex<AppState>>,
+    /// Same ledger as `AppState::ledger`
+    pub ledger: LedgerService,
+    /// Same actors as `AppState::books`
+    pub books: MarketRegistry,
+    /// Same registry as `AppState::roles`
+    pub roles: RoleRegistry,
+}
+
+impl SharedState {
+    pub fn new(app: AppState) -> Self {
+        let ledger = app.ledger.clone();
+        let books = app.books.clone();
+        let roles = app.roles.clone();
+        Self { app: Arc::new(Mutex::new(app)), ledger, books, roles }
+    }
+
+    /// Lock the application state (markets, sessions, bridges, ...)
+    pub fn lock(&self) -> LockResult<MutexGuard<'_, AppState>> {
+        self.app.lock()
+    }
+}
+
+/// Lets `Authorized` find the role registry
+impl FromRef<SharedState> for RoleRegistry {
+    fn from_ref(state: &SharedState) -> Self {
+        state.roles.clone()
+    }
+}
 
 // ============================================================================
 // L2 SESSION TRACKING (Optimistic Execution)
@@ -27,7 +79,10 @@ pub struct L2Session {
     pub wallet_address: String,
     /// L1 balance when session started
     pub l1_balance_snapshot: f64,
-    /// Current L2 balance (updated with bets)
+    /// Credit locked on L1 when the session started
+    #[serde(default)]
+    pub l2_credit: f64,
+    /// Current L2 balance (updated with every trade and claim)
     pub l2_balance: f64,
     /// Number of bets placed in this session
     pub bet_count: u32,
@@ -37,10 +92,22 @@ pub struct L2Session {
     pub started_at: u64,
     /// Unix timestamp when session expires (1 hour default)
     pub expires_at: u64,
-    /// Session status: "active", "settling", "settled", "expired"
+    /// Session status: "active", "expired" (awaiting settlement), "settled",
+    /// "settle_failed" (L1 rejected the automatic settlement)
     pub status: String,
     /// L1 settlement tx hash (if settled)
     pub l1_settlement_hash: Option<String>,
+    /// Sequencer-signed settlement receipt (if settled)
+    #[serde(default)]
+    pub settlement_receipt: Option<SettlementReceipt>,
+    /// Automatic settlement attempts after expiry
+    #[serde(default)]
+    pub settle_attempts: u32,
+    /// Earliest time of the next automatic settlement attempt
+    #[serde(default)]
+    pub next_settle_at: Option<u64>,
+    #[serde(default)]
+    pub last_settle_error: Option<String>,
 }
 
 impl L2Session {
@@ -54,6 +121,7 @@ impl L2Session {
             session_id,
             wallet_address,
             l1_balance_snapshot: l1_balance,
+            l2_credit,
             l2_balance: l2_credit,
             bet_count: 0,
             pnl: 0.0,
@@ -61,6 +129,10 @@ impl L2Session {
             expires_at: now + 3600, // 1 hour max session
             status: "active".to_string(),
             l1_settlement_hash: None,
+            settlement_receipt: None,
+            settle_attempts: 0,
+            next_settle_at: None,
+            last_settle_error: None,
         }
     }
     
@@ -72,6 +144,20 @@ impl L2Session {
         now > self.expires_at
     }
     
+    /// Active and not yet expired: trades count against the session
+    pub fn is_open(&self) -> bool {
+        self.status == "active" && !self.is_expired()
+    }
+    
+    /// Expired (or being settled) but not settled yet: trading is blocked
+    pub fn awaiting_settlement(&self) -> bool {
+        match self.status.as_str() {
+            "active" => self.is_expired(),
+            "expired" | "settle_failed" => true,
+            _ => false,
+        }
+    }
+    
     pub fn record_bet(&mut self, amount: f64, won: bool, payout: f64) {
         self.bet_count += 1;
         if won {
@@ -83,6 +169,19 @@ impl L2Session {
         }
     }
     
+    /// A trade that spent `spent` BB and returned `received` BB
+    pub fn record_trade(&mut self, spent: f64, received: f64) {
+        self.bet_count += 1;
+        self.pnl += received - spent;
+        self.l2_balance += received - spent;
+    }
+    
+    /// Winnings paid out for resolved shares
+    pub fn record_claim(&mut self, payout: f64) {
+        self.pnl += payout;
+        self.l2_balance += payout;
+    }
+    
     pub fn time_remaining_secs(&self) -> u64 {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -107,19 +206,23 @@ pub struct PendingWithdrawal {
     pub poll_count: u32,
     /// Last poll timestamp
     pub last_poll: Option<u64>,
+    /// Original signed request, kept so the relayer can resubmit it
+    #[serde(default)]
+    pub signature: String,
+    #[serde(default)]
+    pub nonce: u64,
+    #[serde(default)]
+    pub timestamp: u64,
 }
 
 // ============================================================================
-// ORACLE/ADMIN AUTHORIZATION SYSTEM
+// ORACLE RESOLUTION LIMITS
 // ============================================================================
 
-/// Oracle configuration for market resolution
+/// Limits on oracle resolution (who is an oracle or admin lives in
+/// `RoleRegistry`)
 #[derive(Debug, Clone)]
 pub struct OracleConfig {
-    /// Whitelisted oracle addresses that can resolve markets
-    pub oracle_whitelist: HashSet<String>,
-    /// Admin addresses with full control
-    pub admin_addresses: HashSet<String>,
     /// Minimum confirmations required for multi-sig (0 = single signature)
     pub multi_sig_threshold: u8,
     /// Markets requiring multi-sig (by market_id)
@@ -130,31 +233,7 @@ pub struct OracleConfig {
 
 impl Default for OracleConfig {
     fn default() -> Self {
-        let mut admin_addresses = HashSet::new();
-        let mut oracle_whitelist = HashSet::new();
-        
-        // Default admin is ORACLE account (L2-only admin for market resolution)
-        admin_addresses.insert("ORACLE".to_string());
-        // Also accept the ORACLE's derived public key
-        admin_addresses.insert("cdcc4c18855728e00efd5bc22cd594d91f4bf305fea61d8a07492b5f7099e95e".to_string());
-        
-        // Add environment-configured admin if present
-        if let Ok(admin) = std::env::var("BLACKBOOK_ADMIN_ADDRESS") {
-            admin_addresses.insert(admin);
-        }
-        
-        // Default oracle whitelist
-        oracle_whitelist.insert("ORACLE".to_string());
-        oracle_whitelist.insert("cdcc4c18855728e00efd5bc22cd594d91f4bf305fea61d8a07492b5f7099e95e".to_string());
-        
-        // Add environment-configured oracle if present
-        if let Ok(oracle) = std::env::var("BLACKBOOK_ORACLE_ADDRESS") {
-            oracle_whitelist.insert(oracle);
-        }
-        
         Self {
-            oracle_whitelist,
-            admin_addresses,
             multi_sig_threshold: 0, // Single sig by default
             high_value_markets: HashSet::new(),
             high_value_threshold: 100_000.0, // 100k BB = high value
@@ -163,49 +242,19 @@ impl Default for OracleConfig {
 }
 
 impl OracleConfig {
-    /// Check if address is an admin
-    pub fn is_admin(&self, address: &str) -> bool {
-        self.admin_addresses.contains(address) || 
-        self.admin_addresses.contains(&address.to_uppercase())
-    }
-    
-    /// Check if address is a whitelisted oracle
-    pub fn is_oracle(&self, address: &str) -> bool {
-        self.oracle_whitelist.contains(address) || 
-        self.oracle_whitelist.contains(&address.to_uppercase())
-    }
-    
-    /// Check if address can resolve a specific market
-    pub fn can_resolve(&self, address: &str, market_id: &str, market_volume: f64) -> bool {
+    /// Check if an oracle (`is_admin` false) or admin can resolve a specific market
+    pub fn can_resolve(&self, is_admin: bool, market_id: &str, market_volume: f64) -> bool {
         // Admins can always resolve
-        if self.is_admin(address) {
+        if is_admin {
             return true;
         }
         
         // Oracles can resolve non-high-value markets
-        if self.is_oracle(address) {
-            let is_high_value = self.high_value_markets.contains(market_id) || 
-                               market_volume >= self.high_value_threshold;
-            
-            // For high value, require multi-sig (not implemented yet - return false)
-            if is_high_value && self.multi_sig_threshold > 1 {
-                return false;
-            }
-            
-            return true;
-        }
+        let is_high_value = self.high_value_markets.contains(market_id) || 
+                           market_volume >= self.high_value_threshold;
         
-        false
-    }
-    
-    /// Add an oracle to the whitelist
-    pub fn add_oracle(&mut self, address: String) {
-        self.oracle_whitelist.insert(address);
-    }
-    
-    /// Remove an oracle from the whitelist
-    pub fn remove_oracle(&mut self, address: &str) {
-        self.oracle_whitelist.remove(address);
+        // For high value, require multi-sig (not implemented yet - return false)
+        !(is_high_value && self.multi_sig_threshold > 1)
     }
     
     /// Mark a market as high value (requires multi-sig)
@@ -235,27 +284,42 @@ pub struct MarketResolution {
 pub struct AppState {
     /// Core market ledger (for CPMM/pool logic)
     pub market_ledger: MarketLedger,
-    /// New unified ledger for L2 tracking
-    pub ledger: Ledger,
+    /// New unified ledger for L2 tracking, and the activity log (separately locked)
+    pub ledger: LedgerService,
     /// Active prediction markets
     pub markets: HashMap<String, PredictionMarket>,
+    /// Search index over `markets`; call `reindex_market` after changing one
+    pub market_index: MarketIndex,
     /// Nonces for replay protection
     pub nonces: HashMap<String, u64>,
-    /// Activity log
-    pub blockchain_activity: Vec<String>,
     /// Supabase config (optional)
     pub supabase_config: SupabaseConfig,
     pub supabase_users: HashMap<String, User>,
     /// Bridge manager
     pub bridge_manager: BridgeManager,
+    /// Trust anchors for L1→L2 deposits (relayer keys, L1 roots)
+    pub deposit_verifier: DepositVerifier,
+    /// L2→L1 withdrawal batches committed to L1 as Merkle roots
+    pub withdrawal_batches: WithdrawalBatcher,
+    /// Key the sequencer signs state roots with
+    pub sequencer: SequencerKey,
+    /// Signed L2 state roots posted to L1
+    pub state_commitments: StateCommitter,
+    /// Seals ledger transactions into L2 blocks
+    pub blocks: BlockProducer,
+    /// Forced exits (escape hatch) and freeze-and-exit mode
+    pub exits: ExitManager,
     /// Pending market events
     pub pending_events: Vec<PendingEvent>,
-    /// CLOB Order Book Manager (hybrid with CPMM fallback)
-    pub orderbook: OrderBookManager,
-    /// Outcome Shares Manager
-    pub shares: SharesManager,
-    /// Oracle/Admin configuration
+    /// Per-market actors owning each market's order book, CPMM pool and
+    /// outcome shares
+    pub books: MarketRegistry,
+    /// Oracle resolution limits
     pub oracle_config: OracleConfig,
+    /// Who holds admin, oracle, dealer, market-maker and moderator roles
+    pub roles: RoleRegistry,
+    /// Hash-chained record of privileged actions
+    pub audit_log: AuditLog,
     /// Market resolution history
     pub resolutions: HashMap<String, MarketResolution>,
     /// Active L2 sessions (optimistic execution)
@@ -264,50 +328,131 @@ pub struct AppState {
     pub pending_withdrawals: HashMap<String, PendingWithdrawal>,
     /// Completed L1 tx hashes (for idempotency)
     pub processed_l1_txs: HashSet<String>,
+    /// Resolution proposals & bonded disputes
+    pub disputes: DisputeManager,
+    /// Outbound RSS/Atom feed of exchange activity
+    pub feed: FeedPublisher,
+    /// Polling health of each inbound feed, refreshed after every worker pass
+    pub feed_sources: HashMap<String, FeedStatus>,
+    /// Funding and last pass of the automatic price resolver
+    pub resolver: ResolverStatus,
+    /// L1 access (HTTP, in-process mock, or recording wrapper)
+    pub l1: Arc<dyn L1Backend>,
+    /// Settings loaded at startup (file layers + env overrides)
+    pub config: Config,
 }
 
 impl AppState {
-    pub fn new() -> Self {
+    pub fn new(config: Config) -> Self {
         println!("🚀 Initializing BlackBook Layer 2 Prediction Market...");
+        println!("⚙️  Config: {}", config.sources.join(" + "));
         
         let oracle_config = OracleConfig::default();
-        println!("🔐 Oracle config: {} admins, {} oracles", 
-            oracle_config.admin_addresses.len(),
-            oracle_config.oracle_whitelist.len()
+        let roles = match RoleRegistry::open(&config.roles.store_path, &config.roles.bootstrap()) {
+            Ok((roles, dropped)) => {
+                if dropped > 0 {
+                    eprintln!("⚠️  Warning: Dropped {} role change(s) that no longer verify", dropped);
+                }
+                roles
+            }
+            Err(e) => {
+                eprintln!("⚠️  Warning: Failed to load roles: {}", e);
+                RoleRegistry::new(&config.roles.bootstrap())
+            }
+        };
+        println!("🔐 Roles: {} admins, {} oracles, {} dealers, {} moderators",
+            roles.holders(Role::Admin).len(),
+            roles.holders(Role::Oracle).len(),
+            roles.holders(Role::Dealer).len(),
+            roles.holders(Role::Moderator).len(),
         );
+        let audit_log = match AuditLog::open(&config.audit.store_path) {
+            Ok((log, report)) => {
+                match &report.first_break {
+                    None => println!("📜 Audit log: {} entries, chain verified", report.verified),
+                    Some(at) => eprintln!("⚠️  Warning: Audit log chain is broken at {} ({} entries verify)", at, report.verified),
+                }
+                log
+            }
+            Err(e) => {
+                eprintln!("⚠️  Warning: Failed to open audit log, keeping it in memory: {}", e);
+                AuditLog::default()
+            }
+        };
+        let mut market_ledger = MarketLedger::new_full_node();
+        market_ledger.audit_log = Some(audit_log.clone());
         
+        let ledger = LedgerService::default();
         let mut state = Self {
-            market_ledger: MarketLedger::new_full_node(),
-            ledger: Ledger::new(),
+            market_ledger,
+            books: MarketRegistry::with_config(ledger.clone(), Arc::new(config.clone())),
+            ledger,
             markets: HashMap::new(),
+            market_index: MarketIndex::new(),
             nonces: HashMap::new(),
-            blockchain_activity: Vec::new(),
-            supabase_config: SupabaseConfig {
-                url: std::env::var("SUPABASE_URL").unwrap_or_default(),
-                anon_key: std::env::var("SUPABASE_ANON_KEY").unwrap_or_default(),
-            },
+            supabase_config: config.auth.supabase(),
             supabase_users: HashMap::new(),
             bridge_manager: BridgeManager::new(),
+            deposit_verifier: config.bridge.deposit_verifier(),
+            withdrawal_batches: config.bridge.withdrawal_batcher(),
+            sequencer: config.sequencer.key(),
+            state_commitments: config.sequencer.state_committer(),
+            blocks: config.sequencer.block_producer(),
+            exits: config.exits.exit_manager(),
             pending_events: Vec::new(),
-            orderbook: OrderBookManager::new(),
-            shares: SharesManager::new(),
             oracle_config,
+            roles,
+            audit_log,
             resolutions: HashMap::new(),
             sessions: HashMap::new(),
             pending_withdrawals: HashMap::new(),
             processed_l1_txs: HashSet::new(),
+            disputes: config.resolution.dispute_manager(),
+            feed: config.feeds.publisher(),
+            feed_sources: HashMap::new(),
+            resolver: ResolverStatus::default(),
+            l1: l1_backend_from_config(config.l1.rpc_config(), config.l1.mock_l1()),
+            config,
         };
 
-        // Log L1 RPC config
-        let l1_url = std::env::var("L1_RPC_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
-        let l1_mock = std::env::var("L1_MOCK_MODE").unwrap_or_else(|_| "false".to_string());
-        println!("🔗 L1 RPC: {} (mock: {})", l1_url, l1_mock);
+        println!("🔗 L1 backend: {}", state.l1.describe());
+        if state.withdrawal_batches.enabled {
+            println!("📦 Withdrawal batching: up to {} per batch, sealed after {}s",
+                state.withdrawal_batches.max_size,
+                state.withdrawal_batches.max_age_secs
+            );
+        }
+        println!("🧱 Block interval: {}s", state.blocks.interval_secs);
+        println!("🚪 Forced exits: processed within {}s (checked every {}s)",
+            state.exits.deadline_secs,
+            state.exits.process_interval_secs
+        );
+        println!("🌳 State roots: every {}s, signed by sequencer {}",
+            state.state_commitments.interval_secs,
+            state.sequencer.pubkey_hex()
+        );
+        if state.deposit_verifier.is_configured() {
+            println!("🌉 Deposit proofs: {} relayer key(s), threshold {}, {} trusted L1 root(s)",
+                state.deposit_verifier.relayer_keys().len(),
+                state.deposit_verifier.threshold(),
+                state.deposit_verifier.trusted_roots().len()
+            );
+        } else {
+            println!("⚠️  No bridge.relayer_keys or bridge.trusted_l1_roots set - L1→L2 deposits will be rejected");
+        }
 
         println!("✅ BlackBook Prediction Market Initialized");
-        println!("🔗 Network: Layer 2 (L1 sync: {})", if state.ledger.mock_mode { "mock" } else { "live" });
+        println!("🔗 Network: Layer 2 (L1 sync: {})", if state.l1.is_mock() { "mock" } else { "live" });
         println!("💎 Token: BlackBook (BB)");
         println!("📊 CLOB: Hybrid mode (CPMM fallback for illiquid markets)");
-        println!("");
+        println!();
+
+        // In-flight bridges are persisted separately so the relayer can resume them
+        match state.load_bridge_state() {
+            Ok(count) if count > 0 => println!("🌉 Restored {} bridge(s) from disk", count),
+            Ok(_) => {}
+            Err(e) => eprintln!("⚠️  Warning: Failed to load bridge state: {}", e),
+        }
 
         // Try to load persisted state
         if let Ok(()) = state.load_from_disk() {
@@ -319,9 +464,59 @@ impl AppState {
             }
         }
 
+        for market in state.markets.values() {
+            state.books.open(&market.id, market.cpmm_pool.clone());
+        }
+        println!("🎭 Started {} market actor(s)", state.books.len());
+
+        let ids: Vec<String> = state.markets.keys().cloned().collect();
+        for id in &ids {
+            state.reindex_market(id);
+        }
+        for bet in state.markets.values().flat_map(|m| &m.bets) {
+            state.market_index.record_trade(&bet.market_id, bet.amount, bet.timestamp);
+        }
+        if !state.market_index.is_empty() {
+            println!("🔎 Indexed {} market(s) for search", state.market_index.len());
+        }
+
         state
     }
-    
+
+    /// Bring the search index in line with `markets[market_id]`, dropping
+    /// it if the market is gone
+    pub fn reindex_market(&mut self, market_id: &str) {
+        match self.markets.get(market_id) {
+            Some(market) => self.market_index.upsert(market.search_doc()),
+            None => self.market_index.remove(market_id),
+        }
+    }
+
+    /// Count a bet or fill toward the market's trending volume
+    pub fn record_market_trade(&mut self, market_id: &str, amount: f64, now: u64) {
+        self.market_index.record_trade(market_id, amount, now);
+        self.reindex_market(market_id);
+    }
+
+    /// Publish the market's odds to the outbound feed if they moved past the
+    /// threshold. After a trade pass the actor's snapshot (its pool is copied
+    /// onto the market); without one the odds come from the market's pool.
+    pub fn publish_odds(&mut self, market_id: &str, snapshot: Option<&MarketSnapshot>, now: u64) {
+        let Some(market) = self.markets.get_mut(market_id) else { return };
+        if let Some(pool) = snapshot.and_then(|s| s.pool.clone()) {
+            market.cpmm_pool = Some(pool);
+        }
+        let event = market.to_rss_event();
+        // Binary markets price off the book, falling back to the pool
+        let odds = match snapshot {
+            Some(s) if event.outcomes.len() == 2 && s.odds.source != OddsSource::None => {
+                vec![s.odds.yes_probability, s.odds.no_probability]
+            }
+            _ => event.get_probabilities(),
+        };
+        self.feed.observe_odds(&event, &odds, now);
+    }
+
     /// Get balance (from unified ledger)
     pub fn get_balance(&self, id: &str) -> f64 {
         self.ledger.balance(id)
@@ -331,21 +526,28 @@ impl AppState {
         use std::fs;
 
         #[derive(serde::Serialize)]
-        struct PersistedState {
-            markets: HashMap<String, PredictionMarket>,
-            nonces: HashMap<String, u64>,
+        struct PersistedState<'a> {
+            markets: &'a HashMap<String, PredictionMarket>,
+            nonces: &'a HashMap<String, u64>,
+            disputes: &'a HashMap<String, ResolutionProposal>,
         }
 
+        // Proposals hold bonds that were already debited and keep their
+        // markets closed, so they must survive a restart with the markets
         let state = PersistedState {
-            markets: self.markets.clone(),
-            nonces: self.nonces.clone(),
+            markets: &self.markets,
+            nonces: &self.nonces,
+            disputes: &self.disputes.proposals,
         };
 
         let json = serde_json::to_string_pretty(&state)
             .map_err(|e| format!("Failed to serialize state: {}", e))?;
         
-        fs::create_dir_all("data").ok();
-        fs::write("data/state.json", json)
+        let path = std::path::Path::new(&self.config.markets.state_path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).ok();
+        }
+        fs::write(path, json)
             .map_err(|e| format!("Failed to write state file: {}", e))?;
         
         println!("💾 State saved to disk");
@@ -359,9 +561,11 @@ impl AppState {
         struct PersistedState {
             markets: HashMap<String, PredictionMarket>,
             nonces: HashMap<String, u64>,
+            #[serde(default)]
+            disputes: HashMap<String, ResolutionProposal>,
         }
 
-        let json = fs::read_to_string("data/state.json")
+        let json = fs::read_to_string(&self.config.markets.state_path)
             .map_err(|_| "No state file found")?;
         
         let state: PersistedState = serde_json::from_str(&json)
@@ -369,27 +573,414 @@ impl AppState {
         
         self.markets = state.markets;
         self.nonces = state.nonces;
+        self.disputes.proposals = state.disputes;
         
         Ok(())
     }
 
-    pub fn log_activity(&mut self, emoji: &str, action: &str, details: &str) {
-        let timestamp = chrono::Local::now().format("%H:%M:%S");
-        let entry = format!("[{}] {} {} | {}", timestamp, emoji, action, details);
-        println!("{}", entry);
-        self.blockchain_activity.push(entry);
-        if self.blockchain_activity.len() > 1000 {
-            self.blockchain_activity.remove(0);
+    /// Persist bridges, pending withdrawals, withdrawal batches, processed L1
+    /// deposits, attested L1 roots, L2 state commitments and forced exits.
+    /// Called whenever bridge state changes so a crash never loses a
+    /// withdrawal that has already been debited on L2.
+    pub fn save_bridge_state(&self) -> Result<(), String> {
+        #[derive(serde::Serialize)]
+        struct PersistedBridges<'a> {
+            bridges: Vec<crate::bridge::PendingBridge>,
+            pending_withdrawals: &'a HashMap<String, PendingWithdrawal>,
+            processed_l1_txs: &'a HashSet<String>,
+            trusted_l1_roots: std::collections::BTreeMap<u64, String>,
+            withdrawal_batches: &'a WithdrawalBatcher,
+            state_commitments: &'a StateCommitter,
+            exits: &'a ExitManager,
+        }
+
+        let persisted = PersistedBridges {
+            bridges: self.bridge_manager.snapshot(),
+            pending_withdrawals: &self.pending_withdrawals,
+            processed_l1_txs: &self.processed_l1_txs,
+            trusted_l1_roots: self.deposit_verifier.trusted_roots().iter()
+                .map(|(slot, root)| (*slot, hex::encode(root)))
+                .collect(),
+            withdrawal_batches: &self.withdrawal_batches,
+            state_commitments: &self.state_commitments,
+            exits: &self.exits,
+        };
+        let json = serde_json::to_string_pretty(&persisted)
+            .map_err(|e| format!("Failed to serialize bridge state: {}", e))?;
+
+        let path = std::path::PathBuf::from(&self.config.bridge.state_path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).ok();
+        }
+        // Write-then-rename so a crash mid-write keeps the previous file
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .map_err(|e| format!("Failed to write bridge state: {}", e))?;
+        std::fs::rename(&tmp, &path)
+            .map_err(|e| format!("Failed to replace bridge state: {}", e))
+    }
+
+    /// Load persisted bridge state; returns the number of bridges restored
+    fn load_bridge_state(&mut self) -> Result<usize, String> {
+        #[derive(serde::Deserialize)]
+        struct PersistedBridges {
+            bridges: Vec<crate::bridge::PendingBridge>,
+            #[serde(default)]
+            pending_withdrawals: HashMap<String, PendingWithdrawal>,
+            #[serde(default)]
+            processed_l1_txs: HashSet<String>,
+            #[serde(default)]
+            trusted_l1_roots: std::collections::BTreeMap<u64, String>,
+            #[serde(default)]
+            withdrawal_batches: Option<WithdrawalBatcher>,
+            #[serde(default)]
+            state_commitments: Option<StateCommitter>,
+            #[serde(default)]
+            exits: Option<ExitManager>,
+        }
+
+        let json = match std::fs::read_to_string(&self.config.bridge.state_path) {
+            Ok(json) => json,
+            Err(_) => return Ok(0),
+        };
+        let persisted: PersistedBridges = serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to deserialize bridge state: {}", e))?;
+
+        let count = persisted.bridges.len();
+        self.bridge_manager.restore(persisted.bridges);
+        self.pending_withdrawals = persisted.pending_withdrawals;
+        self.processed_l1_txs = persisted.processed_l1_txs;
+        if let Some(batches) = persisted.withdrawal_batches {
+            self.withdrawal_batches.restore(batches);
+        }
+        if let Some(commitments) = persisted.state_commitments {
+            self.state_commitments.restore(commitments);
+        }
+        if let Some(exits) = persisted.exits {
+            self.exits.restore(exits);
+        }
+        for (slot, root) in persisted.trusted_l1_roots {
+            if let Ok(root) = hex::decode(&root) {
+                self.deposit_verifier.pin_root(slot, root);
+            }
+        }
+        Ok(count)
+    }
+
+    /// Every account balance, open share position and market, as leaves of
+    /// the L2 state commitment
+    pub fn state_leaves(&self) -> Vec<StateLeaf> {
+        let accounts: Vec<StateLeaf> = self.ledger.read().balances.iter().map(|(address, balance)| StateLeaf::Account {
+            address: address.clone(),
+            confirmed: balance.confirmed,
+            pending: balance.pending,
+            locked: balance.locked,
+        }).collect();
+        let positions = self.books.positions().into_iter()
+            .map(|position| StateLeaf::Position {
+                holder: position.holder,
+                market_id: position.market_id,
+                outcome: position.outcome.index(),
+                shares: position.shares,
+            });
+        let markets = self.markets.values().map(|market| StateLeaf::Market {
+            market_id: market.id.clone(),
+            status: format!("{:?}", market.market_status),
+            winning_option: market.winning_option,
+            reserves: market.cpmm_pool.as_ref().map(|pool| pool.reserves.clone()).unwrap_or_default(),
+            total_volume: market.total_volume,
+        });
+        accounts.into_iter().chain(positions).chain(markets).collect()
+    }
+
+    /// Key of the session tracking `id` (account name or address)
+    fn session_key(&self, id: &str) -> Option<String> {
+        if self.sessions.contains_key(id) {
+            return Some(id.to_string());
+        }
+        let address = self.ledger.resolve(id)?;
+        self.sessions.contains_key(&address).then_some(address)
+    }
+
+    /// Reject a trade spending `amount` if `id` has a session that has
+    /// expired or cannot cover it
+    pub fn check_session_spend(&self, id: &str, amount: f64) -> Result<(), String> {
+        let session = match self.session_key(id).and_then(|key| self.sessions.get(&key)) {
+            Some(session) => session,
+            None => return Ok(()),
+        };
+        if session.awaiting_settlement() {
+            return Err(format!(
+                "Session {} has expired and is being settled to L1; trading is paused until it settles",
+                session.session_id
+            ));
+        }
+        if session.is_open() && amount > session.l2_balance + 1e-9 {
+            return Err(format!(
+                "Exceeds session balance: have {} BB, need {} BB",
+                session.l2_balance, amount
+            ));
+        }
+        Ok(())
+    }
+
+    /// Funds held by `id`'s unsettled session (0 without one)
+    pub fn session_balance(&self, id: &str) -> f64 {
+        self.session_key(id)
+            .and_then(|key| self.sessions.get(&key))
+            .filter(|s| s.status == "active" || s.awaiting_settlement())
+            .map(|s| s.l2_balance.max(0.0))
+            .unwrap_or(0.0)
+    }
+
+    /// Count a trade against `id`'s open session, if any
+    pub fn record_session_trade(&mut self, id: &str, spent: f64, received: f64) {
+        if let Some(key) = self.session_key(id) {
+            if let Some(session) = self.sessions.get_mut(&key).filter(|s| s.is_open()) {
+                session.record_trade(spent, received);
+            }
+        }
+    }
+
+    /// Count a winnings payout towards `id`'s session PnL. Claims still count
+    /// after expiry, until the session has been settled.
+    pub fn record_session_claim(&mut self, id: &str, payout: f64) {
+        if let Some(key) = self.session_key(id) {
+            if let Some(session) = self.sessions.get_mut(&key)
+                .filter(|s| s.is_open() || s.awaiting_settlement())
+            {
+                session.record_claim(payout);
+            }
+        }
+    }
+
+    /// Debit `amount` from `wallet` and record an L2→L1 withdrawal to `target`.
+    /// With batching enabled it is queued into the open batch (returns the
+    /// batch id and leaf); otherwise it waits for the relayer. Callers check
+    /// balance, bounds and nonce, and persist bridge state afterwards.
+    #[allow(clippy::too_many_arguments)]
+    pub fn begin_withdrawal(
+        &mut self,
+        wallet: &str,
+        target: &str,
+        amount: f64,
+        signature: &str,
+        nonce: u64,
+        timestamp: u64,
+        now: u64,
+    ) -> (String, Option<(u64, WithdrawalLeaf)>) {
+        // Debit balance (lock on L2)
+        self.ledger.debit(wallet, amount);
+
+        let bridge = self.bridge_manager.store_pending_withdrawal(
+            wallet.to_string(),
+            target.to_string(),
+            amount,
+        );
+        let bridge_id = bridge.bridge_id.clone();
+
+        // Store pending withdrawal for tracking
+        self.pending_withdrawals.insert(bridge_id.clone(), PendingWithdrawal {
+            bridge_id: bridge_id.clone(),
+            wallet_address: wallet.to_string(),
+            amount,
+            l1_target: target.to_string(),
+            status: "pending".to_string(),
+            created_at: now,
+            l1_tx_hash: None,
+            error: None,
+            poll_count: 0,
+            last_poll: None,
+            signature: signature.to_string(),
+            nonce,
+            timestamp,
+        });
+
+        self.log_activity("🌉", "BRIDGE_WITHDRAW_INIT", &format!(
+            "{} initiated bridge of {} BB to L1 {}",
+            wallet, amount, target
+        ));
+
+        // Queue into the open batch instead of calling L1 per withdrawal
+        let batched = if self.withdrawal_batches.enabled {
+            let (batch_id, leaf) = self.withdrawal_batches.enqueue(&bridge_id, target, amount, now);
+            if let Some(pw) = self.pending_withdrawals.get_mut(&bridge_id) {
+                pw.status = "queued".to_string();
+            }
+            Some((batch_id, leaf))
+        } else {
+            None
+        };
+        (bridge_id, batched)
+    }
+
+    /// Open share positions of `wallet`, as recorded on an exit request
+    pub fn exit_positions(&self, wallet: &str) -> Vec<ExitPosition> {
+        self.books.positions_of(wallet).into_iter()
+            .map(|p| ExitPosition {
+                market_id: p.market_id,
+                outcome: p.outcome.index(),
+                shares: p.shares,
+                resolved_value: None,
+            })
+            .collect()
+    }
+
+    /// Seal every ledger transaction not yet in a block into the next block,
+    /// committing to the state after them. None if nothing is pending.
+    pub fn produce_block(&mut self, now: u64) -> Option<Block> {
+        let start = self.blocks.sealed_tx_count();
+        if start >= self.ledger.read().transactions.len() {
+            return None;
+        }
+        let state_root = compute_state_root(self.state_leaves());
+        let mut ledger = self.ledger.write();
+        let block = self.blocks.seal(&mut ledger.transactions[start..], &state_root, now)?;
+        ledger.block = block.height;
+        Some(block)
+    }
+
+    /// Apply a decided resolution: finalize the proposal, mark the market
+    /// resolved, record the winning-share payouts (already paid by the
+    /// market's actor, see [`resolve_market`]) and settle the
+    /// proposer/challenger bonds. Nothing changes if any step fails.
+    pub fn apply_resolution(
+        &mut self,
+        market_id: &str,
+        settlement: &BondSettlement,
+        resolved_by: &str,
+        now: u64,
+        share_payouts: &[(String, f64)],
+    ) -> Result<MarketResolution, ApiError> {
+        let winning_outcome = settlement.winning_outcome;
+        let winning_outcome_name = self.winning_option(market_id, winning_outcome)?;
+        self.disputes.finalize(market_id, settlement, now)?;
+
+        let market = self.markets.get_mut(market_id)
+            .ok_or_else(|| ApiError::MarketNotFound(market_id.to_string()))?;
+        let market_title = market.title.clone();
+        market.is_resolved = true;
+        market.winning_option = Some(winning_outcome);
+        market.market_status = EventStatus::Resolved;
+        self.reindex_market(market_id);
+
+        let total_payout: f64 = share_payouts.iter().map(|(_, amount)| amount).sum();
+        let num_winners = share_payouts.len();
+        for (wallet, payout_amount) in share_payouts {
+            self.record_session_claim(wallet, *payout_amount);
+        }
+
+        // Return / award bonds
+        for (wallet, amount) in &settlement.payouts {
+            self.ledger.credit(wallet, *amount);
+        }
+        for (wallet, amount) in &settlement.slashed {
+            self.log_activity("🔥", "BOND_SLASHED", &format!(
+                "{} lost {} BB bond on market {}", wallet, amount, market_id
+            ));
+        }
+
+        let resolution = MarketResolution {
+            market_id: market_id.to_string(),
+            winning_outcome,
+            winning_outcome_name: winning_outcome_name.clone(),
+            resolved_by: resolved_by.to_string(),
+            resolved_at: now,
+            total_payout,
+            num_winners,
+            l1_settlement_hash: None,
+            l1_settlement_status: "pending".to_string(),
+        };
+        self.resolutions.insert(market_id.to_string(), resolution.clone());
+
+        let event = self.markets[market_id].to_rss_event();
+        self.feed.record_resolution(&event, winning_outcome, total_payout, num_winners, now);
+
+        self.log_activity("⚖️", "RESOLVE", &format!(
+            "Market '{}' finalized: {} wins | {} winners | {} BB paid out | by {}",
+            market_title, winning_outcome_name, num_winners, total_payout, resolved_by
+        ));
+
+        Ok(resolution)
+    }
+
+    /// Name of `market_id`'s `outcome`, if the market exists, is unresolved
+    /// and has that outcome
+    fn winning_option(&self, market_id: &str, outcome: usize) -> Result<String, ApiError> {
+        let market = self.markets.get(market_id)
+            .ok_or_else(|| ApiError::MarketNotFound(market_id.to_string()))?;
+        if market.is_resolved {
+            return Err(DisputeError::AlreadyFinalized(market_id.to_string()).into());
+        }
+        market.options.get(outcome).cloned().ok_or_else(|| {
+            DisputeError::InvalidOutcome { outcome, num_outcomes: market.options.len() }.into()
+        })
+    }
+
+    /// Settle every undisputed proposal whose challenge window has closed.
+    /// Returns (market_id, settlement, proposer) for [`resolve_market`],
+    /// which finalizes each proposal once its market is resolved.
+    pub fn due_resolutions(&self, now: u64) -> Vec<(String, BondSettlement, String)> {
+        let mut due = Vec::new();
+        for market_id in self.disputes.due_for_finalization(now) {
+            let proposer = match self.disputes.get(&market_id) {
+                Some(p) => p.proposer.clone(),
+                None => continue,
+            };
+            if let Ok(settlement) = self.disputes.undisputed_settlement(&market_id, now) {
+                due.push((market_id, settlement, proposer));
+            }
+        }
+        due
+    }
+
+    /// Add feed-sourced events to the inbox, skipping IDs already known.
+    /// Returns the number of events added.
+    pub fn ingest_pending_events(&mut self, events: Vec<PendingEvent>) -> usize {
+        let mut added = 0;
+        for event in events {
+            let known = self.markets.contains_key(&event.id)
+                || self.pending_events.iter().any(|e| e.id == event.id);
+            if known {
+                continue;
+            }
+            self.log_activity("📡", "RSS_EVENT", &format!("{} | {}", event.id, event.title));
+            self.pending_events.push(event);
+            added += 1;
+        }
+        added
+    }
+
+    /// Append to the activity log kept beside the ledger
+    pub fn log_activity(&self, emoji: &str, action: &str, details: &str) {
+        self.ledger.log_activity(emoji, action, details);
+    }
+
+    /// Record a privileged action, with the signed request that authorized
+    /// it and the affected values before and after, in the audit log
+    pub fn audit(&mut self, request: &SignedRequest, action: &str, details: &str, before: serde_json::Value, after: serde_json::Value) {
+        self.log_activity("🔐", action, &format!(
+            "{} | by {} ({} {}, signed at {})",
+            details, request.address(), request.method, request.path, request.timestamp
+        ));
+        let event = AuditEvent {
+            actor: request.address(),
+            action: action.to_string(),
+            details: details.to_string(),
+            request: Some(request.clone()),
+            before,
+            after,
+        };
+        if let Err(e) = self.audit_log.append(event, chrono::Utc::now().timestamp() as u64) {
+            eprintln!("⚠️  Failed to write audit entry for {}: {}", action, e);
         }
     }
 
     fn load_events_from_rss(&mut self) -> Result<(), String> {
-        use quick_xml::Reader;
-        use quick_xml::events::Event;
         use std::fs;
 
-        let events_dir = "rss/events";
-        let entries = fs::read_dir(events_dir)
+        let events_dir = self.config.markets.events_dir.clone();
+        let entries = fs::read_dir(&events_dir)
             .map_err(|e| format!("Failed to read events directory: {}", e))?;
 
         let mut loaded_count = 0;
@@ -402,73 +993,112 @@ impl AppState {
                 continue;
             }
 
-            let xml_content = fs::read_to_string(&path)
+            let bytes = fs::read(&path)
                 .map_err(|e| format!("Failed to read RSS file {:?}: {}", path, e))?;
-
-            let mut reader = Reader::from_str(&xml_content);
-            reader.trim_text(true);
-
-            let mut buf = Vec::new();
-            let mut current_element = String::new();
-            
-            let mut title = String::new();
-            let mut description = String::new();
-            let mut guid = String::new();
-            let mut category = String::new();
-            let mut options: Vec<String> = Vec::new();
-            let mut in_outcomes = false;
-
-            loop {
-                match reader.read_event_into(&mut buf) {
-                    Ok(Event::Start(ref e)) => {
-                        current_element = String::from_utf8_lossy(e.name().as_ref()).to_string();
-                        if current_element == "outcomes" {
-                            in_outcomes = true;
-                        }
-                    }
-                    Ok(Event::Text(e)) => {
-                        let text = e.unescape().unwrap_or_default().trim().to_string();
-                        if !text.is_empty() {
-                            match current_element.as_str() {
-                                "title" => title = text,
-                                "description" => description = text,
-                                "guid" => guid = text,
-                                "category" => category = text,
-                                "outcome" if in_outcomes => options.push(text),
-                                _ => {}
-                            }
-                        }
-                    }
-                    Ok(Event::End(ref e)) => {
-                        let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
-                        if tag == "outcomes" {
-                            in_outcomes = false;
-                        }
-                    }
-                    Ok(Event::Eof) => break,
-                    Err(e) => return Err(format!("XML parse error: {}", e)),
-                    _ => {}
+            let events = match parse_feed(&bytes) {
+                Ok(events) => events,
+                Err(e) => {
+                    eprintln!("⚠️  Skipping {:?}: {}", path, e);
+                    continue;
                 }
-                buf.clear();
-            }
+            };
 
-            if !title.is_empty() && !options.is_empty() {
-                let market_id = guid.split('/').last().unwrap_or(&guid).to_string();
+            for event in events {
+                let market_id = event.market_id.rsplit('/').next().unwrap_or_default().to_string();
+                if market_id.is_empty() || event.title.is_empty() || event.outcomes.is_empty() {
+                    continue;
+                }
                 let market = PredictionMarket::new(
                     market_id.clone(),
-                    title.clone(),
-                    description,
-                    category,
-                    options,
+                    event.title.clone(),
+                    event.description.clone(),
+                    event.get_category(),
+                    event.outcomes.clone(),
                 );
                 
                 self.markets.insert(market_id, market);
                 loaded_count += 1;
-                println!("📈 Activated market: {}", title);
+                println!("📈 Activated market: {}", event.title);
             }
         }
 
-        println!("✅ Loaded {} markets from rss/events/", loaded_count);
+        println!("✅ Loaded {} markets from {}/", loaded_count, events_dir);
         Ok(())
     }
 }
+
+// ============================================================================
+// MARKET RESOLUTION
+// ============================================================================
+
+/// Resolve `market_id`: its actor pays out the winning shares, then the
+/// resolution is applied to the application state and the proposal is
+/// finalized. Fails, leaving the proposal open, if the market is missing or
+/// already resolved, has no such outcome, or its actor is unavailable.
+pub async fn resolve_market(
+    state: &SharedState,
+    market_id: &str,
+    settlement: &BondSettlement,
+    resolved_by: &str,
+    now: u64,
+) -> Result<(MarketResolution, Vec<(String, f64)>), ApiError> {
+    state.lock().unwrap().winning_option(market_id, settlement.winning_outcome)?;
+
+    let share_payouts = state.books.open(market_id, None)
+        .resolve(settlement.winning_outcome)
+        .await?;
+
+    let resolution = state.lock().unwrap()
+        .apply_resolution(market_id, settlement, resolved_by, now, &share_payouts)?;
+    Ok((resolution, share_payouts))
+}
+
+/// Finalize every undisputed proposal whose challenge window has closed.
+/// Returns the market IDs that were finalized.
+pub async fn finalize_expired_resolutions(state: &SharedState) -> Vec<String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let due = state.lock().unwrap().due_resolutions(now);
+    let mut finalized = Vec::new();
+    for (market_id, settlement, proposer) in due {
+        match resolve_market(state, &market_id, &settlement, &proposer, now).await {
+            Ok(_) => finalized.push(market_id),
+            Err(e) => state.ledger.log_activity("⚠️", "RESOLVE_FAILED", &format!(
+                "Market {} not finalized, will retry: {}", market_id, e
+            )),
+        }
+    }
+    finalized
+}
+
+// ============================================================================
+// TEST SUPPORT
+// ============================================================================
+
+/// Fresh state whose files live in a scratch directory, on the in-process
+/// mock L1 (swap `l1` to script it)
+#[cfg(test)]
+pub fn test_state(name: &str) -> SharedState {
+    test_state_with(name, |_| {})
+}
+
+/// [`test_state`] with `configure` applied to its config first
+#[cfg(test)]
+pub fn test_state_with(name: &str, configure: impl FnOnce(&mut Config)) -> SharedState {
+    let dir = std::env::temp_dir().join(format!("blackbook_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = |file: &str| dir.join(file).display().to_string();
+
+    let mut config = Config::default();
+    config.l1.mock_mode = true;
+    config.markets.state_path = path("state.json");
+    config.markets.events_dir = path("events");
+    config.bridge.state_path = path("bridges.json");
+    config.roles.store_path = path("roles.json");
+    config.audit.store_path = path("audit.ndjson");
+    configure(&mut config);
+    SharedState::new(AppState::new(config))
+}
@@ -0,0 +1,449 @@
+//! Audit Log
+//!
+//! Append-only record of privileged actions: resolutions, role and oracle
+//! changes, dealer funding, freezes and admin mints. Every entry holds the
+//! actor, the signed request that caused it (if it came over HTTP), the
+//! affected values before and after, and the hash of the previous entry:
+//!
+//! ```text
+//! hash = sha256(json(seq, at, actor, action, details, request, before, after, prev_hash))
+//! ```
+//!
+//! so editing, dropping or reordering any entry breaks every hash after it.
+//! Verification re-serializes parsed entries, which reproduces the written
+//! bytes only because serde_json is built with `float_roundtrip`.
+//! Entries are appended to an NDJSON file (one entry per line) and synced
+//! before the append returns; the file doubles as the compliance export.
+//!
+//! [`verify_file`] re-checks a log end to end: sequence numbers, hash links,
+//! entry hashes, and the signature of each embedded request. It is served at
+//! GET /admin/audit/verify and run offline by the `audit_verify` binary.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::roles::SignedRequest;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Default location of the log
+pub const DEFAULT_AUDIT_PATH: &str = "data/audit.ndjson";
+
+/// `prev_hash` of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// ============================================================================
+// ENTRIES
+// ============================================================================
+
+/// A privileged action, before it is chained into the log
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    /// Ledger address of the signer, or the internal account acting
+    pub actor: String,
+    pub action: String,
+    pub details: String,
+    /// The signed HTTP request that caused the action, if any
+    pub request: Option<SignedRequest>,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 1
+    pub seq: u64,
+    pub at: u64,
+    pub actor: String,
+    pub action: String,
+    pub details: String,
+    pub request: Option<SignedRequest>,
+    pub before: Value,
+    pub after: Value,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// The hashed fields of an entry (all but `hash`), in a fixed order
+#[derive(Serialize)]
+struct HashedFields<'a> {
+    seq: u64,
+    at: u64,
+    actor: &'a str,
+    action: &'a str,
+    details: &'a str,
+    request: &'a Option<SignedRequest>,
+    before: &'a Value,
+    after: &'a Value,
+    prev_hash: &'a str,
+}
+
+impl AuditEntry {
+    fn chain(event: AuditEvent, seq: u64, at: u64, prev_hash: String) -> Self {
+        let mut entry = AuditEntry {
+            seq,
+            at,
+            actor: event.actor,
+            action: event.action,
+            details: event.details,
+            request: event.request,
+            before: event.before,
+            after: event.after,
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        entry
+    }
+
+    /// SHA-256 (hex) over every field but `hash`
+    pub fn compute_hash(&self) -> String {
+        let fields = HashedFields {
+            seq: self.seq,
+            at: self.at,
+            actor: &self.actor,
+            action: &self.action,
+            details: &self.details,
+            request: &self.request,
+            before: &self.before,
+            after: &self.after,
+            prev_hash: &self.prev_hash,
+        };
+        let json = serde_json::to_vec(&fields).expect("audit entry serializes");
+        hex::encode(Sha256::digest(json))
+    }
+
+    /// Why this entry cannot follow one with sequence `prev_seq` and hash `prev_hash`
+    fn check(&self, prev_seq: u64, prev_hash: &str) -> Option<String> {
+        if self.seq != prev_seq + 1 {
+            return Some(format!("expected sequence {}, found {}", prev_seq + 1, self.seq));
+        }
+        if self.prev_hash != prev_hash {
+            return Some("prev_hash does not match the previous entry".to_string());
+        }
+        if self.hash != self.compute_hash() {
+            return Some("entry was modified after it was written".to_string());
+        }
+        if let Some(request) = &self.request {
+            if !request.verify() {
+                return Some("embedded request signature does not verify".to_string());
+            }
+            if request.address() != self.actor {
+                return Some(format!("actor {} did not sign the embedded request", self.actor));
+            }
+        }
+        None
+    }
+}
+
+// ============================================================================
+// VERIFICATION
+// ============================================================================
+
+/// The first point where a log stops verifying
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainBreak {
+    /// Line (= expected sequence number) of the offending entry
+    pub line: u64,
+    pub reason: String,
+}
+
+impl fmt::Display for ChainBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// Result of checking a log end to end
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditReport {
+    pub valid: bool,
+    /// Entries that verified before the first break (all of them if valid)
+    pub verified: u64,
+    /// Hash of the last verified entry
+    pub head: String,
+    pub first_break: Option<ChainBreak>,
+}
+
+/// Verify an NDJSON log line by line
+pub fn verify_ndjson(ndjson: &str) -> AuditReport {
+    let mut verified = 0;
+    let mut head = GENESIS_HASH.to_string();
+    for line in ndjson.lines().filter(|line| !line.trim().is_empty()) {
+        let reason = match serde_json::from_str::<AuditEntry>(line) {
+            Ok(entry) => match entry.check(verified, &head) {
+                None => {
+                    verified = entry.seq;
+                    head = entry.hash;
+                    continue;
+                }
+                Some(reason) => reason,
+            },
+            Err(e) => format!("unreadable entry: {}", e),
+        };
+        return AuditReport { valid: false, verified, head, first_break: Some(ChainBreak { line: verified + 1, reason }) };
+    }
+    AuditReport { valid: true, verified, head, first_break: None }
+}
+
+/// Verify the log at `path` (a missing file is an empty, valid log)
+pub fn verify_file(path: &Path) -> Result<AuditReport, String> {
+    match std::fs::read_to_string(path) {
+        Ok(ndjson) => Ok(verify_ndjson(&ndjson)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(verify_ndjson("")),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+// ============================================================================
+// LOG
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct Chain {
+    /// Entries appended or loaded, oldest first
+    entries: Vec<AuditEntry>,
+}
+
+impl Chain {
+    fn head(&self) -> (u64, String) {
+        self.entries.last()
+            .map(|entry| (entry.seq, entry.hash.clone()))
+            .unwrap_or((0, GENESIS_HASH.to_string()))
+    }
+}
+
+/// Handle to the audit log, shared by every request
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    inner: Arc<Mutex<Chain>>,
+    /// Where entries are appended; None keeps them in memory
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// Log appended to `path`, continuing the chain already there. Returns
+    /// the verification of the existing entries; a broken chain is kept as
+    /// it is (new entries link to its last line) so the break stays visible.
+    pub fn open(path: impl Into<PathBuf>) -> Result<(Self, AuditReport), String> {
+        let path = path.into();
+        let report = verify_file(&path)?;
+        let ndjson = std::fs::read_to_string(&path).unwrap_or_default();
+        let entries = ndjson.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| format!("Unreadable audit entry in {}: {}", path.display(), e)))
+            .collect::<Result<Vec<AuditEntry>, String>>()?;
+        let log = Self { inner: Arc::new(Mutex::new(Chain { entries })), path: Some(path) };
+        Ok((log, report))
+    }
+
+    /// Sequence number and hash of the newest entry written
+    pub fn head(&self) -> (u64, String) {
+        self.inner.lock().unwrap().head()
+    }
+
+    /// Chain `event` onto the log and write it durably before returning
+    pub fn append(&self, event: AuditEvent, now: u64) -> Result<AuditEntry, String> {
+        let mut chain = self.inner.lock().unwrap();
+        let (seq, prev_hash) = chain.head();
+        let entry = AuditEntry::chain(event, seq + 1, now, prev_hash);
+
+        if let Some(path) = &self.path {
+            let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+            append_line(path, &line).map_err(|e| format!("Failed to append to {}: {}", path.display(), e))?;
+        }
+        chain.entries.push(entry.clone());
+        Ok(entry)
+    }
+
+    /// Verify the stored log end to end. Also fails if the file no longer
+    /// ends where this process last appended (truncated or replaced).
+    pub fn verify(&self) -> Result<AuditReport, String> {
+        let mut report = match &self.path {
+            Some(path) => verify_file(path)?,
+            None => verify_ndjson(&self.export_ndjson()?),
+        };
+        let (seq, hash) = self.head();
+        if report.valid && (report.verified, &report.head) != (seq, &hash) {
+            report.valid = false;
+            report.first_break = Some(ChainBreak {
+                line: report.verified.min(seq) + 1,
+                reason: format!("log ends at entry {} but entry {} was written", report.verified, seq),
+            });
+        }
+        Ok(report)
+    }
+
+    /// The log as NDJSON, exactly as stored
+    pub fn export_ndjson(&self) -> Result<String, String> {
+        if let Some(path) = &self.path {
+            return match std::fs::read_to_string(path) {
+                Ok(ndjson) => Ok(ndjson),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+                Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+            };
+        }
+        let chain = self.inner.lock().unwrap();
+        let mut ndjson = String::new();
+        for entry in &chain.entries {
+            ndjson.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+            ndjson.push('\n');
+        }
+        Ok(ndjson)
+    }
+}
+
+/// Append one line and sync it to disk
+fn append_line(path: &Path, line: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(format!("{}\n", line).as_bytes())?;
+    file.sync_data()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use serde_json::json;
+
+    fn event(action: &str, before: Value, after: Value) -> AuditEvent {
+        AuditEvent {
+            actor: "ADMIN".to_string(),
+            action: action.to_string(),
+            details: format!("{} happened", action),
+            request: None,
+            before,
+            after,
+        }
+    }
+
+    fn signed_event(key: &SigningKey) -> AuditEvent {
+        let body = r#"{"reason":"incident"}"#;
+        let message = SignedRequest::signing_message("POST", "/admin/freeze", 1_000, body.as_bytes());
+        let request = SignedRequest {
+            signer: hex::encode(key.verifying_key().as_bytes()),
+            timestamp: 1_000,
+            signature: hex::encode(key.sign(message.as_bytes()).to_bytes()),
+            method: "POST".to_string(),
+            path: "/admin/freeze".to_string(),
+            body: body.to_string(),
+        };
+        AuditEvent { actor: request.address(), request: Some(request), ..event("FREEZE", json!(false), json!(true)) }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("blackbook_audit_{}_{}.ndjson", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_entries_chain_and_verify() {
+        let log = AuditLog::default();
+        let first = log.append(event("MINT", json!({ "balance": 0.0 }), json!({ "balance": 50.0 })), 10).unwrap();
+        let second = log.append(signed_event(&SigningKey::from_bytes(&[7; 32])), 11).unwrap();
+
+        assert_eq!(first.seq, 1);
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        let report = log.verify().unwrap();
+        assert!(report.valid, "{:?}", report.first_break);
+        assert_eq!(report.verified, 2);
+        assert_eq!(report.head, second.hash);
+    }
+
+    #[test]
+    fn test_floats_survive_reparsing() {
+        // Values whose shortest repr only parses back exactly with float_roundtrip
+        let balances = [0.45801233435819044, 2083.0 / 3.0, 0.1 + 0.2, 1e-7 * 3.3, 123456.789012345];
+        let path = temp_path("floats");
+        std::fs::remove_file(&path).ok();
+        let (stored, _) = AuditLog::open(&path).unwrap();
+        let memory = AuditLog::default();
+        for (i, balance) in balances.iter().enumerate() {
+            let event = || event("MINT", json!({ "balance": balance }), json!({ "balance": balance * 7.0, "i": i }));
+            stored.append(event(), 10).unwrap();
+            memory.append(event(), 10).unwrap();
+        }
+
+        for log in [&stored, &memory] {
+            let report = log.verify().unwrap();
+            assert!(report.valid, "{:?}", report.first_break);
+            let report = verify_ndjson(&log.export_ndjson().unwrap());
+            assert!(report.valid, "{:?}", report.first_break);
+            assert_eq!(report.verified, balances.len() as u64);
+        }
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let log = AuditLog::default();
+        log.append(event("MINT", json!(0.0), json!(50.0)), 10).unwrap();
+        log.append(event("SET_BALANCE", json!(50.0), json!(5.0)), 11).unwrap();
+        log.append(signed_event(&SigningKey::from_bytes(&[7; 32])), 12).unwrap();
+        let lines: Vec<String> = log.export_ndjson().unwrap().lines().map(String::from).collect();
+
+        // Edited value
+        let edited = lines.join("\n").replacen("50.0", "5000.0", 1);
+        let report = verify_ndjson(&edited);
+        assert!(!report.valid);
+        assert_eq!(report.first_break.unwrap().line, 1);
+
+        // Dropped entry
+        let dropped = format!("{}\n{}", lines[0], lines[2]);
+        let report = verify_ndjson(&dropped);
+        assert_eq!(report.verified, 1);
+        assert_eq!(report.first_break.unwrap().line, 2);
+
+        // Re-hashed entry whose embedded request no longer matches its signature
+        let mut forged: AuditEntry = serde_json::from_str(&lines[2]).unwrap();
+        forged.request.as_mut().unwrap().body = r#"{"reason":"routine"}"#.to_string();
+        forged.hash = forged.compute_hash();
+        let report = verify_ndjson(&format!("{}\n{}\n{}", lines[0], lines[1], serde_json::to_string(&forged).unwrap()));
+        assert!(report.first_break.unwrap().reason.contains("signature"));
+    }
+
+    #[test]
+    fn test_log_persists_and_detects_truncation() {
+        let path = temp_path("persist");
+        std::fs::remove_file(&path).ok();
+
+        let (log, report) = AuditLog::open(&path).unwrap();
+        assert!(report.valid);
+        assert_eq!(log.head(), (0, GENESIS_HASH.to_string()));
+        log.append(event("MINT", json!(0.0), json!(50.0)), 10).unwrap();
+        log.append(event("MINT", json!(50.0), json!(75.0)), 11).unwrap();
+
+        let (reopened, report) = AuditLog::open(&path).unwrap();
+        assert!(report.valid);
+        assert_eq!(reopened.head().0, 2);
+        let third = reopened.append(event("MINT", json!(75.0), json!(80.0)), 12).unwrap();
+        assert_eq!(third.seq, 3);
+        assert!(verify_file(&path).unwrap().valid);
+
+        // Dropping the newest line is invisible to the chain alone, not to the log
+        let ndjson = std::fs::read_to_string(&path).unwrap();
+        let truncated: Vec<&str> = ndjson.lines().take(2).collect();
+        std::fs::write(&path, truncated.join("\n") + "\n").unwrap();
+        assert!(verify_file(&path).unwrap().valid);
+        let report = reopened.verify().unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.first_break.unwrap().line, 3);
+        std::fs::remove_file(&path).ok();
+    }
+}
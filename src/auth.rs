@@ -146,7 +146,7 @@ impl UserRegistry {
     }
 }
 
-/// Supabase authentication helper
+/// Supabase authentication helper (built from `[auth]` in the config)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SupabaseConfig {
     pub url: String,
@@ -154,18 +154,6 @@ pub struct SupabaseConfig {
 }
 
 impl SupabaseConfig {
-    pub fn from_env() -> Result<Self, String> {
-        dotenv::dotenv().ok();
-        
-        let url = std::env::var("NEXT_PUBLIC_SUPABASE_URL")
-            .map_err(|_| "NEXT_PUBLIC_SUPABASE_URL not set".to_string())?;
-        
-        let anon_key = std::env::var("NEXT_PUBLIC_SUPABASE_ANON_KEY")
-            .map_err(|_| "NEXT_PUBLIC_SUPABASE_ANON_KEY not set".to_string())?;
-
-        Ok(Self { url, anon_key })
-    }
-
     /// Verify a Supabase JWT token
     pub async fn verify_token(&self, token: &str) -> Result<String, String> {
         let client = reqwest::Client::new();
@@ -0,0 +1,48 @@
+// BlackBook Audit Log Verifier
+//
+// Checks the hash-chained audit log of privileged actions offline: every
+// entry's hash, its link to the previous entry, and the signature of the
+// request embedded in it.
+//
+//   cargo run --bin audit_verify                        # [audit] store_path
+//   cargo run --bin audit_verify -- data/audit.ndjson
+//
+// Exits 0 if the whole log verifies, 1 at the first break, 2 if the log
+// cannot be read.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use blackbook_prediction_market::{verify_file, Config, DEFAULT_AUDIT_PATH};
+
+fn main() -> ExitCode {
+    let path = match std::env::args().nth(1) {
+        Some(path) => PathBuf::from(path),
+        None => Config::load()
+            .map(|config| config.audit.store_path)
+            .unwrap_or_else(|_| DEFAULT_AUDIT_PATH.to_string())
+            .into(),
+    };
+
+    let report = match verify_file(&path) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    println!("📜 {}", path.display());
+    println!("   verified entries: {}", report.verified);
+    println!("   head hash:        {}", report.head);
+    match report.first_break {
+        None => {
+            println!("✅ Chain intact");
+            ExitCode::SUCCESS
+        }
+        Some(at) => {
+            println!("❌ Chain broken at {}", at);
+            ExitCode::from(1)
+        }
+    }
+}
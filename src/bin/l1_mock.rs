@@ -0,0 +1,458 @@
+// BlackBook L1 Mock Server
+//
+// Serves the L1 endpoints used by the HTTP `L1Backend` (`L1BlackBookRpc`)
+// from in-memory state, with fault injection for testing bridge and
+// session failure paths offline.
+//
+//   cargo run --bin l1_mock -- --port 8080
+//   L1_RPC_URL=http://localhost:8080 cargo run
+//
+// Environment:
+//   L1_MOCK_PORT              - listen port (default 8080)
+//   L1_MOCK_ACCOUNTS          - seed balances, "addr=balance,addr=balance"
+//   L1_MOCK_DEFAULT_BALANCE   - balance of unknown addresses (default 10000)
+//   L1_MOCK_LATENCY_MS        - added latency per request
+//   L1_MOCK_ERROR_RATE        - probability of an injected error (0.0-1.0)
+//   L1_MOCK_DROP_RATE         - probability of a dropped response (0.0-1.0)
+//   L1_MOCK_ERROR_STATUS      - status code for injected errors (default 503)
+//   L1_MOCK_FAULT_PATHS       - path prefixes faults apply to (default all)
+//
+// Faults can be changed at runtime with POST /mock/faults.
+
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use blackbook_prediction_market::rpc::*;
+
+/// Shared mock server state
+struct MockServer {
+    l1: Mutex<MockL1>,
+    faults: Mutex<FaultConfig>,
+}
+
+type SharedMock = Arc<MockServer>;
+
+/// Build the mock L1 router
+fn router(state: SharedMock) -> Router {
+    Router::new()
+        // ===== L1 SURFACE =====
+        .route("/health", get(health))
+        .route("/poh/status", get(poh_status))
+        .route("/auth/wallet/:user_id", get(wallet_lookup))
+        .route("/balance/:address", get(balance))
+        .route("/rpc/nonce/:address", get(nonce))
+        .route("/rpc/accounts/:address/balance", get(account_balance))
+        .route("/rpc/accounts/:address/nonce", get(nonce))
+        .route("/rpc/verify", post(verify_signature))
+        .route("/rpc/verify_signature", post(verify_signature))
+        .route("/rpc/settlement", post(settlement))
+        .route("/rpc/bridge", post(bridge))
+        .route("/bridge/withdraw", post(withdraw))
+        .route("/bridge/withdraw/:bridge_id", get(withdrawal_status))
+        .route("/bridge/batch", post(commit_batch))
+        .route("/bridge/batch/:batch_id", get(batch_status))
+        .route("/bridge/claim", post(claim_withdrawal))
+        .route("/l2/state-root", post(submit_state_root))
+        .route("/l2/state-root/latest", get(latest_state_root))
+        .route("/session/start", post(session_start))
+        .route("/session/settle", post(session_settle))
+        .route("/session/status/:address", get(session_status))
+        .route("/admin/mint", post(mint))
+        .route("/transfer", post(transfer))
+        // ===== MOCK CONTROL =====
+        .route("/mock/state", get(dump_state))
+        .route("/mock/accounts", post(seed_account))
+        .route("/mock/wallets", post(register_wallet))
+        .route("/mock/faults", get(get_faults).post(set_faults))
+        .route("/mock/reset", post(reset))
+        .layer(middleware::from_fn_with_state(state.clone(), inject_faults))
+        .with_state(state)
+}
+
+// ============================================================================
+// FAULT INJECTION
+// ============================================================================
+
+/// Apply latency, injected errors and dropped responses (control routes are exempt)
+async fn inject_faults(State(state): State<SharedMock>, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    if path.starts_with("/mock/") {
+        return next.run(request).await;
+    }
+
+    let (action, latency_ms, error_status) = {
+        let mut faults = state.faults.lock().unwrap();
+        let latency = if faults.applies_to(&path) { faults.latency_ms } else { 0 };
+        (faults.decide(&path), latency, faults.error_status)
+    };
+
+    if latency_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(latency_ms)).await;
+    }
+
+    match action {
+        FaultAction::Pass => next.run(request).await,
+        FaultAction::Error => {
+            println!("💥 Injected {} on {}", error_status, path);
+            let status = StatusCode::from_u16(error_status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+            (status, Json(json!({ "success": false, "error": "injected fault" }))).into_response()
+        }
+        FaultAction::Drop => {
+            // The request takes effect, but the caller never hears back
+            let _ = next.run(request).await;
+            println!("🕳️  Dropped response on {}", path);
+            std::future::pending::<()>().await;
+            unreachable!()
+        }
+    }
+}
+
+// ============================================================================
+// L1 HANDLERS
+// ============================================================================
+
+async fn health(State(state): State<SharedMock>) -> Json<L1HealthResponse> {
+    Json(state.l1.lock().unwrap().health())
+}
+
+async fn poh_status(State(state): State<SharedMock>) -> Json<L1PoHStatus> {
+    Json(state.l1.lock().unwrap().poh_status())
+}
+
+async fn wallet_lookup(State(state): State<SharedMock>, Path(user_id): Path<String>) -> Response {
+    match state.l1.lock().unwrap().wallet(&user_id) {
+        Some(wallet) => Json(wallet).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "found": false, "user_id": user_id }))).into_response(),
+    }
+}
+
+/// GET /balance/:address - shape accepted by both the RPC client and the auth routes
+async fn balance(State(state): State<SharedMock>, Path(address): Path<String>) -> Json<Value> {
+    let balance = state.l1.lock().unwrap().balance(&address);
+    Json(json!({
+        "success": true,
+        "address": balance.address,
+        "balance": balance.balance,
+        "exists": balance.exists
+    }))
+}
+
+async fn account_balance(State(state): State<SharedMock>, Path(address): Path<String>) -> Json<Value> {
+    let balance = state.l1.lock().unwrap().balance(&address);
+    Json(json!({ "address": address, "balance": balance.balance }))
+}
+
+async fn nonce(State(state): State<SharedMock>, Path(address): Path<String>) -> Json<Value> {
+    let nonce = state.l1.lock().unwrap().nonce(&address);
+    Json(json!({ "address": address, "nonce": nonce }))
+}
+
+async fn verify_signature(State(state): State<SharedMock>, Json(req): Json<L1VerifyRequest>) -> Json<L1VerifyResponse> {
+    Json(state.l1.lock().unwrap().verify_signature(&req))
+}
+
+async fn settlement(State(state): State<SharedMock>, Json(req): Json<L1SettlementRequest>) -> Json<L1SettlementResponse> {
+    Json(state.l1.lock().unwrap().record_settlement(&req))
+}
+
+async fn bridge(State(state): State<SharedMock>, Json(req): Json<L1BridgeRequest>) -> Json<L1BridgeResponse> {
+    Json(state.l1.lock().unwrap().bridge(&req))
+}
+
+async fn withdraw(State(state): State<SharedMock>, Json(req): Json<L1WithdrawRequest>) -> Json<L1WithdrawResponse> {
+    Json(state.l1.lock().unwrap().withdraw(&req))
+}
+
+async fn withdrawal_status(State(state): State<SharedMock>, Path(bridge_id): Path<String>) -> Response {
+    match state.l1.lock().unwrap().withdrawal(&bridge_id) {
+        Some(withdrawal) => Json(withdrawal).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "success": false, "error": "Unknown withdrawal" }))).into_response(),
+    }
+}
+
+async fn commit_batch(State(state): State<SharedMock>, Json(req): Json<L1WithdrawalBatchRequest>) -> Json<L1WithdrawalBatchResponse> {
+    Json(state.l1.lock().unwrap().commit_withdrawal_batch(&req))
+}
+
+async fn batch_status(State(state): State<SharedMock>, Path(batch_id): Path<u64>) -> Response {
+    match state.l1.lock().unwrap().withdrawal_batch(batch_id) {
+        Some(batch) => Json(batch).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "success": false, "error": "Unknown batch" }))).into_response(),
+    }
+}
+
+async fn claim_withdrawal(State(state): State<SharedMock>, Json(req): Json<L1WithdrawalClaimRequest>) -> Json<L1WithdrawalClaimResponse> {
+    Json(state.l1.lock().unwrap().claim_withdrawal(&req))
+}
+
+async fn submit_state_root(State(state): State<SharedMock>, Json(req): Json<L1StateRootRequest>) -> Json<L1StateRootResponse> {
+    Json(state.l1.lock().unwrap().submit_state_root(&req))
+}
+
+async fn latest_state_root(State(state): State<SharedMock>) -> Response {
+    match state.l1.lock().unwrap().latest_state_root() {
+        Some(root) => Json(root.clone()).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "success": false, "error": "No state root posted" }))).into_response(),
+    }
+}
+
+async fn session_start(State(state): State<SharedMock>, Json(req): Json<L1SessionStartRequest>) -> Json<L1SessionStartResponse> {
+    Json(state.l1.lock().unwrap().start_session(&req))
+}
+
+async fn session_settle(State(state): State<SharedMock>, Json(req): Json<L1SessionSettleRequest>) -> Json<L1SessionSettleResponse> {
+    Json(state.l1.lock().unwrap().settle_session(&req))
+}
+
+async fn session_status(State(state): State<SharedMock>, Path(address): Path<String>) -> Response {
+    match state.l1.lock().unwrap().session_status(&address) {
+        Some(status) => Json(status).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "success": false, "error": "No session" }))).into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MintRequest {
+    to: String,
+    amount: f64,
+}
+
+async fn mint(State(state): State<SharedMock>, Json(req): Json<MintRequest>) -> Json<Value> {
+    let tx_hash = state.l1.lock().unwrap().mint(&req.to, req.amount);
+    Json(json!({ "success": true, "tx_hash": tx_hash }))
+}
+
+#[derive(Debug, Deserialize)]
+struct TransferRequest {
+    from: String,
+    to: String,
+    amount: f64,
+}
+
+async fn transfer(State(state): State<SharedMock>, Json(req): Json<TransferRequest>) -> Response {
+    match state.l1.lock().unwrap().transfer(&req.from, &req.to, req.amount) {
+        Ok(tx_hash) => Json(json!({ "success": true, "tx_hash": tx_hash })).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(json!({ "success": false, "error": e }))).into_response(),
+    }
+}
+
+// ============================================================================
+// MOCK CONTROL HANDLERS
+// ============================================================================
+
+async fn dump_state(State(state): State<SharedMock>) -> Json<Value> {
+    let l1 = state.l1.lock().unwrap();
+    Json(json!({ "l1": *l1, "faults": *state.faults.lock().unwrap() }))
+}
+
+#[derive(Debug, Deserialize)]
+struct SeedAccountRequest {
+    address: String,
+    balance: f64,
+}
+
+async fn seed_account(State(state): State<SharedMock>, Json(req): Json<SeedAccountRequest>) -> Json<Value> {
+    state.l1.lock().unwrap().set_balance(&req.address, req.balance);
+    Json(json!({ "success": true, "address": req.address, "balance": req.balance }))
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterWalletRequest {
+    user_id: String,
+    wallet_address: String,
+}
+
+async fn register_wallet(State(state): State<SharedMock>, Json(req): Json<RegisterWalletRequest>) -> Json<Value> {
+    state.l1.lock().unwrap().register_wallet(&req.user_id, &req.wallet_address);
+    Json(json!({ "success": true, "user_id": req.user_id, "wallet_address": req.wallet_address }))
+}
+
+async fn get_faults(State(state): State<SharedMock>) -> Json<FaultConfig> {
+    Json(state.faults.lock().unwrap().clone())
+}
+
+async fn set_faults(State(state): State<SharedMock>, Json(faults): Json<FaultConfig>) -> Json<FaultConfig> {
+    println!("⚙️  Faults: {:?}", faults);
+    *state.faults.lock().unwrap() = faults.clone();
+    Json(faults)
+}
+
+async fn reset(State(state): State<SharedMock>) -> Json<Value> {
+    *state.l1.lock().unwrap() = MockL1::from_env();
+    *state.faults.lock().unwrap() = FaultConfig::default();
+    Json(json!({ "success": true }))
+}
+
+// ============================================================================
+// MAIN
+// ============================================================================
+
+fn port_from_args() -> Option<u16> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|a| a == "--port")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|p| p.parse().ok())
+}
+
+#[tokio::main]
+async fn main() {
+    let port = port_from_args()
+        .or_else(|| std::env::var("L1_MOCK_PORT").ok().and_then(|p| p.parse().ok()))
+        .unwrap_or(8080);
+
+    let state = Arc::new(MockServer {
+        l1: Mutex::new(MockL1::from_env()),
+        faults: Mutex::new(FaultConfig::from_env()),
+    });
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let listener = tokio::net::TcpListener::bind(addr).await.expect("Failed to bind L1 mock port");
+
+    println!("🧪 BlackBook L1 mock listening on http://{}", addr);
+    println!("   Faults: {:?}", state.faults.lock().unwrap());
+    println!("   Control: GET /mock/state, POST /mock/accounts, /mock/wallets, /mock/faults, /mock/reset");
+
+    axum::serve(listener, router(state)).await.expect("L1 mock server failed");
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blackbook_prediction_market::withdrawal_batch::WithdrawalBatcher;
+
+    async fn spawn_mock(l1: MockL1, faults: FaultConfig) -> (String, SharedMock) {
+        let state = Arc::new(MockServer { l1: Mutex::new(l1), faults: Mutex::new(faults) });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = router(state.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, state)
+    }
+
+    fn client(url: &str) -> L1BlackBookRpc {
+        L1BlackBookRpc::new(L1RpcConfig {
+            endpoint: url.to_string(),
+            timeout: Duration::from_millis(500),
+            ..Default::default()
+        })
+    }
+
+    fn withdraw_request() -> L1WithdrawRequest {
+        L1WithdrawRequest {
+            from_l2_address: "L2_ALICE".to_string(),
+            to_l1_address: "L1_ALICE".to_string(),
+            amount: 25.0,
+            bridge_id: "bridge_1".to_string(),
+            signature: String::new(),
+            timestamp: 0,
+            nonce: "1".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rpc_client_against_mock() {
+        let mut l1 = MockL1::new(0.0);
+        l1.set_balance("L1_ALICE", 500.0);
+        let (url, _) = spawn_mock(l1, FaultConfig::default()).await;
+        let rpc = client(&url);
+
+        assert_eq!(rpc.health().await.unwrap().status, "healthy");
+        assert_eq!(rpc.get_balance("L1_ALICE").await.unwrap().balance, 500.0);
+
+        let start = rpc.start_session(&L1SessionStartRequest {
+            wallet_address: "L1_ALICE".to_string(),
+            l2_session_id: "s1".to_string(),
+            requested_amount: 200.0,
+            signature: String::new(),
+            timestamp: 0,
+            nonce: "1".to_string(),
+        }).await.unwrap();
+        assert!(start.success);
+        assert_eq!(rpc.get_nonce("L1_ALICE").await.unwrap(), 1);
+        assert_eq!(rpc.get_session_status("L1_ALICE").await.unwrap().status, "active");
+    }
+
+    #[tokio::test]
+    async fn test_injected_error_leaves_state_untouched() {
+        let faults = FaultConfig { fail_next: 1, ..Default::default() };
+        let (url, state) = spawn_mock(MockL1::new(0.0), faults).await;
+        let rpc = client(&url);
+
+        assert!(rpc.withdraw_to_l1(&withdraw_request()).await.is_err());
+        assert_eq!(state.l1.lock().unwrap().balance("L1_ALICE").balance, 0.0);
+
+        let retry = rpc.withdraw_to_l1(&withdraw_request()).await.unwrap();
+        assert!(retry.success);
+        assert_eq!(state.l1.lock().unwrap().balance("L1_ALICE").balance, 25.0);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_response_applies_once() {
+        let faults = FaultConfig { drop_next: 1, ..Default::default() };
+        let (url, state) = spawn_mock(MockL1::new(0.0), faults).await;
+        let rpc = client(&url);
+
+        // Caller times out, but L1 processed the withdrawal
+        assert!(rpc.withdraw_to_l1(&withdraw_request()).await.is_err());
+        assert_eq!(state.l1.lock().unwrap().balance("L1_ALICE").balance, 25.0);
+
+        // Retrying with the same bridge_id does not pay twice
+        assert!(rpc.withdraw_to_l1(&withdraw_request()).await.unwrap().success);
+        assert_eq!(state.l1.lock().unwrap().balance("L1_ALICE").balance, 25.0);
+
+        // Relayer reconciliation can see the outcome
+        let status = rpc.get_withdrawal_status("bridge_1").await.unwrap().unwrap();
+        assert_eq!(status.status, "completed");
+        assert!(rpc.get_withdrawal_status("bridge_unknown").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_commit_and_claim() {
+        let (url, state) = spawn_mock(MockL1::new(0.0), FaultConfig::default()).await;
+        let rpc = client(&url);
+
+        let mut batcher = WithdrawalBatcher::new(2, 30);
+        batcher.enqueue("w1", "L1_ALICE", 10.0, 0);
+        let (batch_id, _) = batcher.enqueue("w2", "L1_BOB", 5.0, 0);
+        let batch = batcher.batch(batch_id).unwrap().clone();
+
+        let committed = rpc.commit_withdrawal_batch(&L1WithdrawalBatchRequest {
+            batch_id,
+            root: batch.root.clone().unwrap(),
+            leaf_count: batch.leaves.len(),
+            total_amount: batch.total_amount(),
+            sealed_at: 0,
+        }).await.unwrap();
+        assert!(committed.success);
+        assert_eq!(rpc.get_withdrawal_batch(batch_id).await.unwrap().unwrap().status, "committed");
+
+        // Alice claims directly on L1 with her proof; a replay is refused
+        let claim = batcher.proof_for("w1").unwrap();
+        let request = L1WithdrawalClaimRequest { batch_id, leaf: claim.leaf, proof: claim.proof };
+        let http = reqwest::Client::new();
+        let first: L1WithdrawalClaimResponse = http.post(format!("{}/bridge/claim", url))
+            .json(&request).send().await.unwrap().json().await.unwrap();
+        assert!(first.success);
+        let replay: L1WithdrawalClaimResponse = http.post(format!("{}/bridge/claim", url))
+            .json(&request).send().await.unwrap().json().await.unwrap();
+        assert!(!replay.success);
+        assert_eq!(state.l1.lock().unwrap().balance("L1_ALICE").balance, 10.0);
+        assert_eq!(state.l1.lock().unwrap().balance("L1_BOB").balance, 0.0);
+    }
+}
@@ -0,0 +1,256 @@
+// BlackBook Order Throughput Benchmark
+//
+// Submits crossing limit orders from many concurrent tasks, each order
+// followed by a few order book / odds / position reads, and reports
+// orders/sec for two designs:
+//
+//   before - every order takes one global Mutex around the order books,
+//            share book and ledger, reads included (the old
+//            `Arc<Mutex<AppState>>` path)
+//   after  - every market runs as an actor, the ledger sits behind its own
+//            lock and reads use snapshots (`MarketRegistry` + `LedgerService`)
+//
+//   cargo run --release --bin order_bench -- --markets 16 --tasks 8 --orders 2000
+//
+// Environment (flags take precedence):
+//   BENCH_MARKETS  - number of markets (default 8)
+//   BENCH_TASKS    - concurrent tasks per market (default 4)
+//   BENCH_ORDERS   - orders submitted by each task (default 1000)
+//   BENCH_READS    - reads after each order (default 4)
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use blackbook_prediction_market::{
+    fill_amounts, fill_parties, Ledger, LedgerService, LimitOrder, MarketRegistry,
+    OrderBookManager, OrderType, Outcome, OutcomeIndex, SharesManager, Side, ORDERBOOK_ESCROW,
+};
+
+/// Starting BB per trader, minted 1:1 into YES + NO so asks can fill
+const STARTING_BALANCE: f64 = 1_000_000.0;
+const STARTING_SHARES: f64 = 100_000.0;
+
+#[derive(Debug, Clone, Copy)]
+struct BenchConfig {
+    markets: usize,
+    tasks: usize,
+    orders: usize,
+    reads: usize,
+}
+
+impl BenchConfig {
+    fn load() -> Self {
+        let setting = |flag: &str, var: &str, default: usize| {
+            let args: Vec<String> = std::env::args().collect();
+            args.iter()
+                .position(|a| a == flag)
+                .and_then(|i| args.get(i + 1))
+                .cloned()
+                .or_else(|| std::env::var(var).ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            markets: setting("--markets", "BENCH_MARKETS", 8).max(1),
+            tasks: setting("--tasks", "BENCH_TASKS", 4).max(1),
+            orders: setting("--orders", "BENCH_ORDERS", 1000).max(1),
+            reads: setting("--reads", "BENCH_READS", 4),
+        }
+    }
+
+    fn total_orders(&self) -> usize {
+        self.markets * self.tasks * self.orders
+    }
+}
+
+fn market_id(market: usize) -> String {
+    format!("bench_market_{:06}", market)
+}
+
+// Ledger transfers print a 16 char prefix, so keep ids at least that long
+fn trader(market: usize, task: usize) -> String {
+    format!("BENCH_TRADER_{:04}_{:04}", market, task)
+}
+
+/// Alternate asks and bids at the same price so every other order crosses
+fn bench_order(market: &str, wallet: &str, n: usize) -> LimitOrder {
+    let side = if n.is_multiple_of(2) { Side::Ask } else { Side::Bid };
+    LimitOrder::new(
+        market.to_string(),
+        Outcome::YES,
+        side,
+        50,
+        1.0,
+        OrderType::GTC,
+        wallet.to_string(),
+        String::new(),
+    )
+    .expect("valid bench order")
+}
+
+// ============================================================================
+// BEFORE: ONE GLOBAL MUTEX
+// ============================================================================
+
+struct GlobalState {
+    orderbook: OrderBookManager,
+    shares: SharesManager,
+    ledger: Ledger,
+}
+
+/// The check → match → settle sequence the handler ran under the app lock
+fn submit_locked(state: &Mutex<GlobalState>, order: LimitOrder) -> usize {
+    let mut guard = state.lock().unwrap();
+    let GlobalState { orderbook, shares, ledger } = &mut *guard;
+    let market_id = order.market_id.clone();
+    let outcome = OutcomeIndex::from_usize(order.outcome.index());
+
+    let funded = match order.side {
+        Side::Ask => shares.get_shares(&order.maker, &market_id, outcome) >= order.size,
+        Side::Bid => ledger.balance(&order.maker) >= (order.price_bps as f64 / 100.0) * order.size,
+    };
+    if !funded {
+        return 0;
+    }
+
+    let result = orderbook.submit_order(order);
+    for fill in &result.fills {
+        let (buyer, seller) = fill_parties(fill);
+        let (buyer_cost, seller_receive) = fill_amounts(fill);
+        let _ = ledger.transfer(buyer, ORDERBOOK_ESCROW, buyer_cost, &fill.id);
+        let _ = ledger.transfer(ORDERBOOK_ESCROW, seller, seller_receive, &fill.id);
+        shares.credit_shares_simple(buyer, &market_id, outcome, fill.size);
+        let _ = shares.debit_shares_simple(seller, &market_id, outcome, fill.size);
+    }
+    result.fills.len()
+}
+
+/// What a book, odds and position read cost under the app lock
+fn read_locked(state: &Mutex<GlobalState>, market_id: &str, wallet: &str) -> f64 {
+    let guard = state.lock().unwrap();
+    let book = guard.orderbook.get_orderbook(market_id, Outcome::YES, 10);
+    let odds = guard.orderbook.get_odds(market_id);
+    let position = guard.shares.get_position(wallet, market_id);
+    book.bids.len() as f64 + odds.yes_price + position.yes_shares
+}
+
+async fn run_global(config: BenchConfig) -> (Duration, usize) {
+    let mut state = GlobalState {
+        orderbook: OrderBookManager::new(),
+        shares: SharesManager::new(),
+        ledger: Ledger::new(),
+    };
+    state.ledger.register(ORDERBOOK_ESCROW, ORDERBOOK_ESCROW, 0.0);
+    for market in 0..config.markets {
+        let id = market_id(market);
+        state.orderbook.init_market(&id, None);
+        for task in 0..config.tasks {
+            let wallet = trader(market, task);
+            state.ledger.register(&wallet, &wallet, STARTING_BALANCE);
+            state.shares.credit_shares_simple(&wallet, &id, OutcomeIndex::YES, STARTING_SHARES);
+        }
+    }
+    let state = Arc::new(Mutex::new(state));
+
+    let started = Instant::now();
+    let mut workers = Vec::new();
+    for market in 0..config.markets {
+        for task in 0..config.tasks {
+            let state = state.clone();
+            workers.push(tokio::spawn(async move {
+                let (id, wallet) = (market_id(market), trader(market, task));
+                let mut fills = 0;
+                for n in 0..config.orders {
+                    fills += submit_locked(&state, bench_order(&id, &wallet, n + task));
+                    for _ in 0..config.reads {
+                        std::hint::black_box(read_locked(&state, &id, &wallet));
+                        tokio::task::yield_now().await;
+                    }
+                    tokio::task::yield_now().await;
+                }
+                fills
+            }));
+        }
+    }
+    let mut fills = 0;
+    for worker in workers {
+        fills += worker.await.unwrap();
+    }
+    (started.elapsed(), fills)
+}
+
+// ============================================================================
+// AFTER: PER-MARKET ACTORS
+// ============================================================================
+
+async fn run_actors(config: BenchConfig) -> (Duration, usize) {
+    let ledger = LedgerService::default();
+    let registry = MarketRegistry::new(ledger.clone());
+    for market in 0..config.markets {
+        let handle = registry.open(&market_id(market), None);
+        for task in 0..config.tasks {
+            let wallet = trader(market, task);
+            ledger.register(&wallet, &wallet, STARTING_BALANCE + STARTING_SHARES);
+            handle.mint(&wallet, STARTING_SHARES).await.expect("bench mint");
+        }
+    }
+
+    let started = Instant::now();
+    let mut workers = Vec::new();
+    for market in 0..config.markets {
+        for task in 0..config.tasks {
+            let handle = registry.open(&market_id(market), None);
+            workers.push(tokio::spawn(async move {
+                let (id, wallet) = (market_id(market), trader(market, task));
+                let mut fills = 0;
+                for n in 0..config.orders {
+                    if let Ok(result) = handle.submit_order(bench_order(&id, &wallet, n + task)).await {
+                        fills += result.fills.len();
+                    }
+                    for _ in 0..config.reads {
+                        let snapshot = handle.snapshot();
+                        let book = snapshot.book(Outcome::YES, 10);
+                        let read = book.bids.len() as f64
+                            + snapshot.odds.yes_price
+                            + snapshot.position(&wallet).yes_shares;
+                        std::hint::black_box(read);
+                        tokio::task::yield_now().await;
+                    }
+                }
+                fills
+            }));
+        }
+    }
+    let mut fills = 0;
+    for worker in workers {
+        fills += worker.await.unwrap();
+    }
+    (started.elapsed(), fills)
+}
+
+// ============================================================================
+// MAIN
+// ============================================================================
+
+fn report(label: &str, total: usize, (elapsed, fills): (Duration, usize)) -> f64 {
+    let rate = total as f64 / elapsed.as_secs_f64();
+    println!(
+        "{:<22} {:>8} orders {:>8} fills {:>10.1} ms {:>12.0} orders/sec",
+        label, total, fills, elapsed.as_secs_f64() * 1000.0, rate
+    );
+    rate
+}
+
+#[tokio::main]
+async fn main() {
+    let config = BenchConfig::load();
+    let total = config.total_orders();
+    println!(
+        "📈 Order throughput: {} market(s) × {} task(s) × {} order(s), {} read(s) per order",
+        config.markets, config.tasks, config.orders, config.reads
+    );
+
+    let before = report("before (global mutex)", total, run_global(config).await);
+    let after = report("after (market actors)", total, run_actors(config).await);
+    println!("speedup: {:.2}x", after / before);
+}
@@ -0,0 +1,88 @@
+// ============================================================================
+// Block Producer - Seals pending ledger transactions into L2 blocks
+// ============================================================================
+//
+// Every `sequencer.block_interval_secs` the producer takes the ledger
+// transactions that are not yet in a block and seals them into the next
+// block (height, parent hash, tx Merkle root, state root, timestamp). No block is produced when
+// nothing happened since the last one.
+// ============================================================================
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app_state::SharedState;
+use crate::blocks::Block;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Seal the next block, if there are pending transactions
+pub fn produce_once(state: &SharedState) -> Option<Block> {
+    let mut app = state.lock().unwrap();
+    let block = app.produce_block(now())?;
+    app.log_activity("🧱", "BLOCK", &format!(
+        "Block {} sealed: {} tx(s), hash {}", block.height, block.tx_count, &block.hash[..16]
+    ));
+    Some(block)
+}
+
+/// Spawn the background producer (every `sequencer.block_interval_secs`)
+pub fn spawn(state: SharedState) {
+    let interval_secs = state.lock().unwrap().blocks.interval_secs;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            produce_once(&state);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::test_state;
+    use crate::ledger::Transaction;
+
+    #[test]
+    fn test_tick_seals_pending_txs_and_advances_height() {
+        let state = test_state("block_producer_tick");
+        // Seal whatever startup recorded so the test starts from a clean tip
+        produce_once(&state);
+        let start = state.lock().unwrap().blocks.height();
+        assert!(produce_once(&state).is_none(), "nothing new to seal");
+
+        let first = state.ledger.record(Transaction::transfer("L1_ALICE", "L1_BOB", 5.0, "sig1"));
+        let second = state.ledger.record(Transaction::transfer("L1_BOB", "L1_ALICE", 2.0, "sig2"));
+        let block = produce_once(&state).unwrap();
+        assert_eq!(block.height, start + 1);
+        assert_eq!(block.tx_count, 2);
+        assert_eq!(block.tx_ids, vec![first.id.clone(), second.id.clone()]);
+        {
+            let app = state.lock().unwrap();
+            assert_eq!(app.blocks.height(), start + 1);
+            assert_eq!(app.blocks.latest().unwrap().hash, block.hash);
+            let ledger = app.ledger.read();
+            assert_eq!(ledger.block, block.height);
+            let sealed = ledger.transactions.iter().find(|tx| tx.id == second.id).unwrap();
+            assert_eq!((sealed.block_number, sealed.block_index), (block.height, Some(1)));
+        }
+
+        // Idle tick: no empty blocks
+        assert!(produce_once(&state).is_none());
+        assert_eq!(state.lock().unwrap().blocks.height(), start + 1);
+
+        // The next block only carries the new transaction and chains to the last
+        let third = state.ledger.record(Transaction::transfer("L1_ALICE", "L1_BOB", 1.0, "sig3"));
+        let next = produce_once(&state).unwrap();
+        assert_eq!(next.height, start + 2);
+        assert_eq!(next.parent_hash, block.hash);
+        assert_eq!(next.tx_ids, vec![third.id]);
+        assert_eq!(state.ledger.read().block, start + 2);
+    }
+}
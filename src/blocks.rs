@@ -0,0 +1,297 @@
+//! L2 Block Production
+//!
+//! Ledger transactions are appended as they happen; the block producer
+//! periodically seals every transaction not yet in a block into the next
+//! block. A block commits to its transactions (Merkle root over transaction
+//! hashes), to the L2 state after applying them (state root, see
+//! `state_root`) and to its parent, forming a hash chain from genesis.
+//!
+//! Blocks are only produced when there are transactions to seal. Sealing
+//! stamps each transaction with its block height and index before hashing,
+//! so a transaction's hash also commits to its position.
+
+use merkle::{MerkleTree, Proof};
+use ring::digest::SHA256;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use crate::ledger::Transaction;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Domain separator for transaction hashes
+pub const TX_HASH_DOMAIN: &[u8] = b"BLACKBOOK_L2_TX_V1";
+
+/// Domain separator for block hashes
+pub const BLOCK_HASH_DOMAIN: &[u8] = b"BLACKBOOK_L2_BLOCK_V1";
+
+/// Parent hash of the first block
+pub const GENESIS_PARENT_HASH: [u8; 32] = [0u8; 32];
+
+/// Default seconds between blocks
+pub const DEFAULT_BLOCK_INTERVAL_SECS: u64 = 2;
+
+// ============================================================================
+// HASHING
+// ============================================================================
+
+/// Transaction hash: SHA256(domain || JSON(tx))
+pub fn tx_hash(tx: &Transaction) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(TX_HASH_DOMAIN);
+    hasher.update(serde_json::to_vec(tx).expect("transaction serializes"));
+    hasher.finalize().to_vec()
+}
+
+/// Block hash: SHA256(domain || height || timestamp || tx_count (u64 BE) || parent || tx_root || state_root)
+pub fn block_hash(height: u64, timestamp: u64, tx_count: usize, parent_hash: &[u8], tx_root: &[u8], state_root: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(BLOCK_HASH_DOMAIN);
+    hasher.update(height.to_be_bytes());
+    hasher.update(timestamp.to_be_bytes());
+    hasher.update((tx_count as u64).to_be_bytes());
+    hasher.update(parent_hash);
+    hasher.update(tx_root);
+    hasher.update(state_root);
+    hasher.finalize().to_vec()
+}
+
+/// Check an inclusion proof for a transaction hash against a block's tx root
+pub fn verify_tx_proof(tx_root: &[u8], tx_hash: &[u8], proof: &Proof<Vec<u8>>) -> bool {
+    *proof.algorithm == SHA256 && proof.value == tx_hash && proof.validate(tx_root)
+}
+
+// ============================================================================
+// BLOCKS
+// ============================================================================
+
+/// A sealed L2 block (hashes are hex)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Block {
+    pub height: u64,
+    pub hash: String,
+    pub parent_hash: String,
+    /// Merkle root over the transaction hashes
+    pub tx_root: String,
+    /// L2 state root after applying this block
+    pub state_root: String,
+    pub timestamp: u64,
+    pub tx_count: usize,
+    pub tx_ids: Vec<String>,
+    /// Transaction hashes, in block order
+    pub tx_hashes: Vec<String>,
+}
+
+impl Block {
+    fn tree(&self) -> MerkleTree<Vec<u8>> {
+        let leaves = self.tx_hashes.iter()
+            .map(|h| hex::decode(h).unwrap_or_default())
+            .collect();
+        MerkleTree::from_vec(&SHA256, leaves)
+    }
+
+    /// Recompute the block hash from the header fields
+    pub fn compute_hash(&self) -> String {
+        let decode = |h: &str| hex::decode(h).unwrap_or_default();
+        hex::encode(block_hash(
+            self.height,
+            self.timestamp,
+            self.tx_count,
+            &decode(&self.parent_hash),
+            &decode(&self.tx_root),
+            &decode(&self.state_root),
+        ))
+    }
+}
+
+/// Proof that a transaction is part of a sealed block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxInclusionProof {
+    pub block_height: u64,
+    pub block_hash: String,
+    pub tx_root: String,
+    pub tx_index: usize,
+    pub tx_count: usize,
+    pub tx_hash: String,
+    pub proof: Proof<Vec<u8>>,
+}
+
+// ============================================================================
+// PRODUCER
+// ============================================================================
+
+/// Seals ledger transactions into blocks and indexes them
+#[derive(Debug, Clone)]
+pub struct BlockProducer {
+    pub interval_secs: u64,
+    blocks: Vec<Block>,
+    /// tx id → (height, index)
+    tx_index: HashMap<String, (u64, usize)>,
+    /// Number of ledger transactions already sealed
+    sealed_tx_count: usize,
+}
+
+impl Default for BlockProducer {
+    fn default() -> Self {
+        Self::new(DEFAULT_BLOCK_INTERVAL_SECS)
+    }
+}
+
+impl BlockProducer {
+    pub fn new(interval_secs: u64) -> Self {
+        BlockProducer {
+            interval_secs,
+            blocks: Vec::new(),
+            tx_index: HashMap::new(),
+            sealed_tx_count: 0,
+        }
+    }
+
+    /// Number of leading ledger transactions already in blocks
+    pub fn sealed_tx_count(&self) -> usize {
+        self.sealed_tx_count
+    }
+
+    /// Seal `pending` (the ledger transactions after `sealed_tx_count`) into
+    /// the next block. Stamps each transaction with its height and index.
+    /// Returns None if there is nothing to seal.
+    pub fn seal(&mut self, pending: &mut [Transaction], state_root: &[u8], now: u64) -> Option<Block> {
+        if pending.is_empty() {
+            return None;
+        }
+        let height = self.height() + 1;
+        let parent_hash = match self.blocks.last() {
+            Some(parent) => hex::decode(&parent.hash).unwrap_or_default(),
+            None => GENESIS_PARENT_HASH.to_vec(),
+        };
+
+        let mut hashes = Vec::with_capacity(pending.len());
+        for (index, tx) in pending.iter_mut().enumerate() {
+            tx.block_number = height;
+            tx.block_index = Some(index);
+            hashes.push(tx_hash(tx));
+        }
+        let tx_root = MerkleTree::from_vec(&SHA256, hashes.clone()).root_hash().clone();
+        let hash = block_hash(height, now, pending.len(), &parent_hash, &tx_root, state_root);
+
+        let block = Block {
+            height,
+            hash: hex::encode(hash),
+            parent_hash: hex::encode(parent_hash),
+            tx_root: hex::encode(tx_root),
+            state_root: hex::encode(state_root),
+            timestamp: now,
+            tx_count: pending.len(),
+            tx_ids: pending.iter().map(|tx| tx.id.clone()).collect(),
+            tx_hashes: hashes.iter().map(hex::encode).collect(),
+        };
+        for (index, id) in block.tx_ids.iter().enumerate() {
+            self.tx_index.insert(id.clone(), (height, index));
+        }
+        self.sealed_tx_count += pending.len();
+        self.blocks.push(block.clone());
+        Some(block)
+    }
+
+    /// Height of the latest block (0 before the first block)
+    pub fn height(&self) -> u64 {
+        self.blocks.len() as u64
+    }
+
+    pub fn latest(&self) -> Option<&Block> {
+        self.blocks.last()
+    }
+
+    pub fn block(&self, height: u64) -> Option<&Block> {
+        let index = usize::try_from(height).ok()?.checked_sub(1)?;
+        self.blocks.get(index)
+    }
+
+    /// Most recent blocks, newest first
+    pub fn recent(&self, limit: usize) -> Vec<&Block> {
+        self.blocks.iter().rev().take(limit).collect()
+    }
+
+    /// Block height and index of a sealed transaction
+    pub fn locate(&self, tx_id: &str) -> Option<(u64, usize)> {
+        self.tx_index.get(tx_id).copied()
+    }
+
+    /// Inclusion proof for a sealed transaction
+    pub fn proof_for(&self, tx_id: &str) -> Option<TxInclusionProof> {
+        let (height, index) = self.locate(tx_id)?;
+        let block = self.block(height)?;
+        Some(TxInclusionProof {
+            block_height: height,
+            block_hash: block.hash.clone(),
+            tx_root: block.tx_root.clone(),
+            tx_index: index,
+            tx_count: block.tx_count,
+            tx_hash: block.tx_hashes.get(index)?.clone(),
+            proof: block.tree().gen_nth_proof(index)?,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::TxType;
+
+    fn txs(count: usize) -> Vec<Transaction> {
+        (0..count)
+            .map(|i| Transaction::new(TxType::Transfer, &format!("L1_{}", i), 1.0, "sig"))
+            .collect()
+    }
+
+    #[test]
+    fn test_blocks_chain_and_stamp_transactions() {
+        let mut producer = BlockProducer::new(2);
+        let mut ledger_txs = txs(3);
+        assert!(producer.seal(&mut [], &[1u8; 32], 100).is_none());
+
+        let first = producer.seal(&mut ledger_txs[..], &[1u8; 32], 100).unwrap();
+        assert_eq!(first.height, 1);
+        assert_eq!(first.parent_hash, hex::encode(GENESIS_PARENT_HASH));
+        assert_eq!(ledger_txs[2].block_number, 1);
+        assert_eq!(ledger_txs[2].block_index, Some(2));
+
+        ledger_txs.extend(txs(2));
+        let start = producer.sealed_tx_count();
+        assert_eq!(start, 3);
+        let second = producer.seal(&mut ledger_txs[start..], &[2u8; 32], 102).unwrap();
+        assert_eq!(second.parent_hash, first.hash);
+        assert_eq!(second.compute_hash(), second.hash);
+        assert_eq!(producer.locate(&ledger_txs[4].id), Some((2, 1)));
+        assert_eq!(producer.latest().unwrap().height, 2);
+        assert!(producer.block(0).is_none());
+    }
+
+    #[test]
+    fn test_tx_inclusion_proofs() {
+        let mut producer = BlockProducer::new(2);
+        let mut ledger_txs = txs(5);
+        let block = producer.seal(&mut ledger_txs[..], &[7u8; 32], 100).unwrap();
+        let root = hex::decode(&block.tx_root).unwrap();
+
+        for tx in &ledger_txs {
+            let proof = producer.proof_for(&tx.id).unwrap();
+            assert_eq!(hex::decode(&proof.tx_hash).unwrap(), tx_hash(tx));
+            assert!(verify_tx_proof(&root, &tx_hash(tx), &proof.proof));
+        }
+
+        // A modified transaction no longer matches its proof
+        let mut forged = ledger_txs[0].clone();
+        forged.amount = 1000.0;
+        let proof = producer.proof_for(&forged.id).unwrap();
+        assert!(!verify_tx_proof(&root, &tx_hash(&forged), &proof.proof));
+        assert!(producer.proof_for("unknown").is_none());
+    }
+}
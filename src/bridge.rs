@@ -5,8 +5,14 @@
 //! Bridge Flow:
 //! 1. L2→L1: User initiates on L2, tokens locked, L1 confirms and releases
 //! 2. L1→L2: User initiates on L1, L1 confirms, L2 receives callback and mints
+//!
+//! L2→L1 withdrawals are driven by the bridge relayer, which retries
+//! submissions with exponential backoff using the relay fields on
+//! `PendingBridge`. Bridge state can be snapshotted and restored so in-flight
+//! withdrawals survive restarts.
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -27,12 +33,21 @@ pub const MIN_BRIDGE_AMOUNT: f64 = 0.01;
 /// Maximum bridge amount (per transaction)
 pub const MAX_BRIDGE_AMOUNT: f64 = 1_000_000.0;
 
+/// First relay retry delay; doubles with each failed attempt
+pub const RELAY_BASE_BACKOFF_SECS: u64 = 5;
+
+/// Maximum delay between relay retries
+pub const RELAY_MAX_BACKOFF_SECS: u64 = 300;
+
+/// Failed relay attempts after which a bridge is reported as stuck
+pub const STUCK_RELAY_ATTEMPTS: u32 = 5;
+
 // ============================================================================
 // BRIDGE STATUS
 // ============================================================================
 
 /// Status of a bridge operation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum BridgeStatus {
     /// Bridge initiated, waiting for confirmation
@@ -58,6 +73,17 @@ impl BridgeStatus {
         }
     }
 
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "pending" => Some(BridgeStatus::Pending),
+            "confirmed" => Some(BridgeStatus::Confirmed),
+            "completed" => Some(BridgeStatus::Completed),
+            "failed" => Some(BridgeStatus::Failed),
+            "cancelled" | "canceled" => Some(BridgeStatus::Cancelled),
+            _ => None,
+        }
+    }
+
     pub fn is_terminal(&self) -> bool {
         matches!(self, BridgeStatus::Completed | BridgeStatus::Failed | BridgeStatus::Cancelled)
     }
@@ -68,7 +94,7 @@ impl BridgeStatus {
 // ============================================================================
 
 /// Direction of the bridge
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum BridgeDirection {
     /// L1 → L2 (deposit to L2)
     L1ToL2,
@@ -105,7 +131,7 @@ impl BridgeDirection {
 // ============================================================================
 
 /// A pending bridge operation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PendingBridge {
     /// Unique bridge identifier
     pub bridge_id: String,
@@ -135,6 +161,21 @@ pub struct PendingBridge {
     pub error: Option<String>,
     /// Original signed transaction (for verification)
     pub signed_tx_signature: Option<String>,
+    /// Failed relay attempts (L1 unreachable or errored)
+    #[serde(default)]
+    pub relay_attempts: u32,
+    /// Unix timestamp of the last relay attempt
+    #[serde(default)]
+    pub last_relay_at: Option<u64>,
+    /// Last relay error (cleared once L1 answers)
+    #[serde(default)]
+    pub last_relay_error: Option<String>,
+    /// Earliest time the relayer should touch this bridge again
+    #[serde(default)]
+    pub next_attempt_at: u64,
+    /// How an L1→L2 deposit was proven (attestation / inclusion)
+    #[serde(default)]
+    pub verified_by: Option<String>,
 }
 
 impl PendingBridge {
@@ -165,6 +206,11 @@ impl PendingBridge {
             l1_slot: None,
             error: None,
             signed_tx_signature: None,
+            relay_attempts: 0,
+            last_relay_at: None,
+            last_relay_error: None,
+            next_attempt_at: 0,
+            verified_by: None,
         }
     }
 
@@ -201,6 +247,39 @@ impl PendingBridge {
     pub fn age_secs(&self) -> u64 {
         now_timestamp().saturating_sub(self.created_at)
     }
+
+    /// Whether the relayer should process this bridge at `now`
+    pub fn is_relay_due(&self, now: u64) -> bool {
+        !self.status.is_terminal() && self.next_attempt_at <= now
+    }
+
+    /// Record a relay attempt that L1 answered
+    pub fn record_relay_success(&mut self, now: u64, next_attempt_at: u64) {
+        self.last_relay_at = Some(now);
+        self.last_relay_error = None;
+        self.next_attempt_at = next_attempt_at;
+    }
+
+    /// Record a failed relay attempt and schedule the retry with backoff
+    pub fn record_relay_failure(&mut self, error: String, now: u64) {
+        self.relay_attempts += 1;
+        self.last_relay_at = Some(now);
+        self.last_relay_error = Some(error);
+        self.next_attempt_at = now + relay_backoff_secs(self.relay_attempts);
+    }
+
+    /// Non-terminal and either past the bridge timeout or repeatedly failing
+    pub fn is_stuck(&self, now: u64) -> bool {
+        !self.status.is_terminal()
+            && (now.saturating_sub(self.created_at) > BRIDGE_TIMEOUT_SECS
+                || self.relay_attempts >= STUCK_RELAY_ATTEMPTS)
+    }
+}
+
+/// Relay retry delay: base * 2^(attempts - 1), capped at RELAY_MAX_BACKOFF_SECS
+pub fn relay_backoff_secs(attempts: u32) -> u64 {
+    let exp = attempts.saturating_sub(1).min(16);
+    RELAY_BASE_BACKOFF_SECS.saturating_mul(1u64 << exp).min(RELAY_MAX_BACKOFF_SECS)
 }
 
 // ============================================================================
@@ -217,6 +296,7 @@ pub enum BridgeError {
     BridgeAlreadyCompleted(String),
     BridgeExpired(String),
     SignatureVerificationFailed(String),
+    InvalidProof(String),
     L1CommunicationError(String),
     InternalError(String),
 }
@@ -234,6 +314,7 @@ impl std::fmt::Display for BridgeError {
             BridgeError::BridgeAlreadyCompleted(id) => write!(f, "Bridge already completed: {}", id),
             BridgeError::BridgeExpired(id) => write!(f, "Bridge expired: {}", id),
             BridgeError::SignatureVerificationFailed(msg) => write!(f, "Signature verification failed: {}", msg),
+            BridgeError::InvalidProof(msg) => write!(f, "Invalid proof: {}", msg),
             BridgeError::L1CommunicationError(msg) => write!(f, "L1 communication error: {}", msg),
             BridgeError::InternalError(msg) => write!(f, "Internal error: {}", msg),
         }
@@ -372,10 +453,11 @@ impl BridgeManager {
             ));
         };
 
-        // Create pending bridge
+        // Create pending bridge, owned by the key that signed it
+        let from_address = signed_tx.signer_address();
         let mut bridge = PendingBridge::new(
             direction,
-            signed_tx.sender_address.clone(),
+            from_address.clone(),
             target_address,
             amount,
         );
@@ -390,7 +472,7 @@ impl BridgeManager {
         {
             let mut by_addr = self.bridges_by_address.lock().unwrap();
             by_addr
-                .entry(signed_tx.sender_address.clone())
+                .entry(from_address)
                 .or_insert_with(Vec::new)
                 .push(bridge_id);
         }
@@ -405,6 +487,15 @@ impl BridgeManager {
     pub fn complete_from_l1(
         &self,
         request: &BridgeCompleteRequest,
+    ) -> Result<PendingBridge, BridgeError> {
+        self.complete_verified_deposit(request, None)
+    }
+
+    /// Complete an L1→L2 deposit, recording how it was proven
+    pub fn complete_verified_deposit(
+        &self,
+        request: &BridgeCompleteRequest,
+        verified_by: Option<String>,
     ) -> Result<PendingBridge, BridgeError> {
         // Check if bridge already exists (for retries)
         {
@@ -424,6 +515,7 @@ impl BridgeManager {
             request.amount,
         );
         bridge.bridge_id = request.bridge_id.clone();
+        bridge.verified_by = verified_by;
         bridge.confirm(request.l1_tx_hash.clone(), request.l1_slot);
         bridge.complete();
 
@@ -534,10 +626,21 @@ impl BridgeManager {
     /// Get statistics
     pub fn stats(&self) -> BridgeStats {
         let bridges = self.bridges.lock().unwrap();
+        let now = now_timestamp();
         
         let mut stats = BridgeStats::default();
         for bridge in bridges.values() {
             stats.total += 1;
+            if !bridge.status.is_terminal() {
+                stats.oldest_pending_secs = stats.oldest_pending_secs
+                    .max(now.saturating_sub(bridge.created_at));
+                if bridge.relay_attempts > 0 {
+                    stats.retrying += 1;
+                }
+                if bridge.is_stuck(now) {
+                    stats.stuck += 1;
+                }
+            }
             match bridge.status {
                 BridgeStatus::Pending => stats.pending += 1,
                 BridgeStatus::Confirmed => stats.confirmed += 1,
@@ -654,6 +757,53 @@ impl BridgeManager {
         Ok(bridge.clone())
     }
     
+    /// L2→L1 withdrawals the relayer should process at `now`
+    pub fn due_withdrawals(&self, now: u64) -> Vec<PendingBridge> {
+        let bridges = self.bridges.lock().unwrap();
+        let mut due: Vec<PendingBridge> = bridges
+            .values()
+            .filter(|b| b.direction == BridgeDirection::L2ToL1 && b.is_relay_due(now))
+            .cloned()
+            .collect();
+        due.sort_by_key(|b| b.created_at);
+        due
+    }
+
+    /// Record that L1 answered a relay attempt; next look at `next_attempt_at`
+    pub fn record_relay_success(&self, bridge_id: &str, now: u64, next_attempt_at: u64) -> Result<(), BridgeError> {
+        let mut bridges = self.bridges.lock().unwrap();
+        let bridge = bridges.get_mut(bridge_id)
+            .ok_or_else(|| BridgeError::BridgeNotFound(bridge_id.to_string()))?;
+        bridge.record_relay_success(now, next_attempt_at);
+        Ok(())
+    }
+
+    /// Record a failed relay attempt; returns the bridge with its retry schedule
+    pub fn record_relay_failure(&self, bridge_id: &str, error: String, now: u64) -> Result<PendingBridge, BridgeError> {
+        let mut bridges = self.bridges.lock().unwrap();
+        let bridge = bridges.get_mut(bridge_id)
+            .ok_or_else(|| BridgeError::BridgeNotFound(bridge_id.to_string()))?;
+        
+        if bridge.status.is_terminal() {
+            return Err(BridgeError::BridgeAlreadyCompleted(bridge_id.to_string()));
+        }
+        
+        bridge.record_relay_failure(error, now);
+        Ok(bridge.clone())
+    }
+
+    /// Non-terminal bridges that are past their timeout or repeatedly failing
+    pub fn stuck(&self, now: u64) -> Vec<PendingBridge> {
+        let bridges = self.bridges.lock().unwrap();
+        let mut stuck: Vec<PendingBridge> = bridges
+            .values()
+            .filter(|b| b.is_stuck(now))
+            .cloned()
+            .collect();
+        stuck.sort_by_key(|b| b.created_at);
+        stuck
+    }
+
     /// Get all pending L2→L1 withdrawals that need L1 polling
     pub fn get_pending_l2_to_l1(&self) -> Vec<PendingBridge> {
         let bridges = self.bridges.lock().unwrap();
@@ -669,11 +819,42 @@ impl BridgeManager {
     }
 }
 
+// ============================================================================
+// PERSISTENCE
+// ============================================================================
+
+impl BridgeManager {
+    /// All bridges, oldest first (for persisting to disk)
+    pub fn snapshot(&self) -> Vec<PendingBridge> {
+        let bridges = self.bridges.lock().unwrap();
+        let mut all: Vec<PendingBridge> = bridges.values().cloned().collect();
+        all.sort_by_key(|b| b.created_at);
+        all
+    }
+
+    /// Replace all bridges with a persisted snapshot and rebuild the address index
+    pub fn restore(&self, snapshot: Vec<PendingBridge>) {
+        let mut bridges = self.bridges.lock().unwrap();
+        let mut by_addr = self.bridges_by_address.lock().unwrap();
+        bridges.clear();
+        by_addr.clear();
+        
+        for bridge in snapshot {
+            let owner = match bridge.direction {
+                BridgeDirection::L2ToL1 => bridge.from_address.clone(),
+                BridgeDirection::L1ToL2 => bridge.to_address.clone(),
+            };
+            by_addr.entry(owner).or_default().push(bridge.bridge_id.clone());
+            bridges.insert(bridge.bridge_id.clone(), bridge);
+        }
+    }
+}
+
 // ============================================================================
 // BRIDGE STATS
 // ============================================================================
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct BridgeStats {
     pub total: usize,
     pub pending: usize,
@@ -684,6 +865,12 @@ pub struct BridgeStats {
     pub l1_to_l2: usize,
     pub l2_to_l1: usize,
     pub total_volume: f64,
+    /// Non-terminal bridges with at least one failed relay attempt
+    pub retrying: usize,
+    /// Non-terminal bridges past their timeout or repeatedly failing
+    pub stuck: usize,
+    /// Age of the oldest non-terminal bridge (seconds)
+    pub oldest_pending_secs: u64,
 }
 
 // ============================================================================
@@ -791,7 +978,22 @@ mod tests {
         assert_eq!(bridge.status, BridgeStatus::Pending);
         assert_eq!(bridge.amount, 100.0);
         assert_eq!(bridge.direction, BridgeDirection::L2ToL1);
-        assert_eq!(bridge.from_address, tx.sender_address);
+        assert_eq!(bridge.from_address, tx.signer_address());
+    }
+
+    #[test]
+    fn test_bridge_manager_initiate_ignores_unsigned_sender() {
+        let godmode = GodMode::new();
+        let manager = BridgeManager::new();
+
+        // sender_address is not covered by the signature
+        let mut tx = create_bridge_tx(&godmode, "ALICE", "bb1_target", 100.0);
+        tx.sender_address = "L1_MALLORY".into();
+        let bridge = manager.initiate(&tx).unwrap();
+
+        assert_eq!(bridge.from_address, tx.signer_address());
+        assert!(manager.list_by_address("L1_MALLORY").is_empty());
+        assert_eq!(manager.list_by_address(&tx.signer_address()).len(), 1);
     }
 
     #[test]
@@ -886,7 +1088,7 @@ mod tests {
         let godmode = GodMode::new();
         let manager = BridgeManager::new();
         
-        let tx = create_bridge_tx(&godmode, "CHARLIE", "bb1_target", 75.0);
+        let tx = create_bridge_tx(&godmode, "BOB", "bb1_target", 75.0);
         let bridge = manager.initiate(&tx).unwrap();
 
         manager.fail_bridge(&bridge.bridge_id, "Insufficient L1 balance".into()).unwrap();
@@ -960,4 +1162,57 @@ mod tests {
         let result = manager.initiate(&tx);
         assert!(matches!(result, Err(BridgeError::InvalidDirection(_))));
     }
+
+    #[test]
+    fn test_relay_backoff_and_stuck() {
+        assert_eq!(relay_backoff_secs(1), RELAY_BASE_BACKOFF_SECS);
+        assert_eq!(relay_backoff_secs(2), RELAY_BASE_BACKOFF_SECS * 2);
+        assert_eq!(relay_backoff_secs(30), RELAY_MAX_BACKOFF_SECS);
+
+        let manager = BridgeManager::new();
+        let bridge = manager.store_pending_withdrawal("bb1_alice".into(), "L1_alice".into(), 10.0);
+        let now = bridge.created_at;
+        assert_eq!(manager.due_withdrawals(now).len(), 1);
+
+        let retried = manager.record_relay_failure(&bridge.bridge_id, "timeout".into(), now).unwrap();
+        assert_eq!(retried.next_attempt_at, now + RELAY_BASE_BACKOFF_SECS);
+        assert!(manager.due_withdrawals(now).is_empty());
+        assert_eq!(manager.due_withdrawals(retried.next_attempt_at).len(), 1);
+        assert_eq!(manager.stats().retrying, 1);
+
+        for _ in 1..STUCK_RELAY_ATTEMPTS {
+            manager.record_relay_failure(&bridge.bridge_id, "timeout".into(), now).unwrap();
+        }
+        assert_eq!(manager.stuck(now).len(), 1);
+
+        // Refunded bridges are no longer stuck or due
+        manager.refund_withdrawal(&bridge.bridge_id, "L1 rejected".into()).unwrap();
+        assert!(manager.stuck(now).is_empty());
+        assert!(manager.due_withdrawals(u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let manager = BridgeManager::new();
+        let withdrawal = manager.store_pending_withdrawal("bb1_alice".into(), "L1_alice".into(), 10.0);
+        manager.record_relay_failure(&withdrawal.bridge_id, "timeout".into(), withdrawal.created_at).unwrap();
+        manager.complete_from_l1(&BridgeCompleteRequest {
+            bridge_id: "bridge_l1_1".into(),
+            from_address: "L1_bob".into(),
+            to_address: "bb1_bob".into(),
+            amount: 5.0,
+            l1_tx_hash: "0x1".into(),
+            l1_slot: 1,
+        }).unwrap();
+
+        let json = serde_json::to_string(&manager.snapshot()).unwrap();
+        let restored = BridgeManager::new();
+        restored.restore(serde_json::from_str(&json).unwrap());
+
+        let bridge = restored.get_status(&withdrawal.bridge_id).unwrap();
+        assert_eq!(bridge.relay_attempts, 1);
+        assert_eq!(restored.list_by_address("bb1_alice").len(), 1);
+        assert_eq!(restored.list_by_address("bb1_bob").len(), 1);
+        assert_eq!(restored.stats().total, 2);
+    }
 }
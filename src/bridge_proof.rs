@@ -0,0 +1,359 @@
+//! Deposit Proofs for L1 → L2 Bridging
+//!
+//! An L1→L2 deposit mints BB on L2, so it is only accepted with proof that
+//! the L1 transaction happened. Two kinds of proof are supported:
+//!
+//! 1. Attestation: Ed25519 signatures over the deposit from a configured set
+//!    of L1 relayer keys, at least `threshold` of them distinct.
+//! 2. Inclusion: a Merkle inclusion proof of the deposit leaf against a
+//!    trusted L1 root for the deposit's slot. Trusted roots are configured at
+//!    startup or submitted later with relayer attestations.
+//!
+//! Both commit to the same canonical deposit leaf (see `deposit_leaf`).
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use merkle::Proof;
+use ring::digest::SHA256;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::bridge::{BridgeCompleteRequest, BridgeError};
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Domain separator for deposit attestations and leaves
+pub const DEPOSIT_DOMAIN: &[u8] = b"BLACKBOOK_L1_DEPOSIT_V1";
+
+/// Domain separator for trusted L1 root attestations
+pub const L1_ROOT_DOMAIN: &[u8] = b"BLACKBOOK_L1_ROOT_V1";
+
+/// Default number of relayer signatures required
+pub const DEFAULT_ATTESTATION_THRESHOLD: usize = 1;
+
+// ============================================================================
+// CANONICAL ENCODING
+// ============================================================================
+
+/// Canonical bytes of a deposit: the Merkle leaf value on L1
+///
+/// Format: domain || bridge_id || 0 || from || 0 || to || 0 || amount (f64 BE)
+///         || l1_tx_hash || 0 || l1_slot (u64 BE)
+pub fn deposit_leaf(request: &BridgeCompleteRequest) -> Vec<u8> {
+    let mut leaf = Vec::with_capacity(128);
+    leaf.extend_from_slice(DEPOSIT_DOMAIN);
+    for field in [&request.bridge_id, &request.from_address, &request.to_address] {
+        leaf.extend_from_slice(field.as_bytes());
+        leaf.push(0);
+    }
+    leaf.extend_from_slice(&request.amount.to_be_bytes());
+    leaf.extend_from_slice(request.l1_tx_hash.as_bytes());
+    leaf.push(0);
+    leaf.extend_from_slice(&request.l1_slot.to_be_bytes());
+    leaf
+}
+
+/// Bytes a relayer signs to attest a deposit: SHA256(deposit_leaf)
+pub fn deposit_signing_bytes(request: &BridgeCompleteRequest) -> Vec<u8> {
+    Sha256::digest(deposit_leaf(request)).to_vec()
+}
+
+/// Bytes a relayer signs to vouch for an L1 root: SHA256(domain || slot || root)
+pub fn root_signing_bytes(l1_slot: u64, root: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(L1_ROOT_DOMAIN);
+    hasher.update(l1_slot.to_be_bytes());
+    hasher.update(root);
+    hasher.finalize().to_vec()
+}
+
+// ============================================================================
+// PROOF TYPES
+// ============================================================================
+
+/// A single relayer signature
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Attestation {
+    /// Relayer Ed25519 public key (hex)
+    pub relayer_pubkey: String,
+    /// Signature over the relevant signing bytes (hex)
+    pub signature: String,
+}
+
+/// Proof accompanying an L1→L2 deposit
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DepositProof {
+    /// Signatures from the configured relayer key set
+    Attestation { attestations: Vec<Attestation> },
+    /// Merkle inclusion of `deposit_leaf` under a trusted L1 root (SHA256)
+    Inclusion {
+        #[schema(value_type = Object)]
+        proof: Proof<Vec<u8>>,
+    },
+}
+
+/// How an accepted deposit was proven
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum DepositVerification {
+    Attestation { signers: Vec<String> },
+    Inclusion { l1_slot: u64, root: String },
+}
+
+impl DepositVerification {
+    /// Short label recorded on the bridge
+    pub fn label(&self) -> String {
+        match self {
+            DepositVerification::Attestation { signers } => format!("attestation ({} signer(s))", signers.len()),
+            DepositVerification::Inclusion { l1_slot, root } => format!("inclusion (slot {}, root {})", l1_slot, root),
+        }
+    }
+}
+
+// ============================================================================
+// VERIFIER
+// ============================================================================
+
+/// Holds the trust anchors for deposits: relayer keys and L1 roots
+#[derive(Debug, Clone, Default)]
+pub struct DepositVerifier {
+    /// Trusted relayer public keys (lowercase hex)
+    relayer_keys: HashSet<String>,
+    /// Distinct relayer signatures required
+    threshold: usize,
+    /// Trusted L1 roots by slot
+    trusted_roots: BTreeMap<u64, Vec<u8>>,
+}
+
+impl DepositVerifier {
+    pub fn new(relayer_keys: impl IntoIterator<Item = String>, threshold: usize) -> Self {
+        DepositVerifier {
+            relayer_keys: relayer_keys.into_iter().map(|k| k.trim().to_lowercase()).collect(),
+            threshold: threshold.max(1),
+            trusted_roots: BTreeMap::new(),
+        }
+    }
+
+    /// Whether any trust anchor is configured (otherwise every deposit is rejected)
+    pub fn is_configured(&self) -> bool {
+        !self.relayer_keys.is_empty() || !self.trusted_roots.is_empty()
+    }
+
+    pub fn relayer_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.relayer_keys.iter().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn trusted_root(&self, l1_slot: u64) -> Option<&[u8]> {
+        self.trusted_roots.get(&l1_slot).map(|r| r.as_slice())
+    }
+
+    pub fn trusted_roots(&self) -> &BTreeMap<u64, Vec<u8>> {
+        &self.trusted_roots
+    }
+
+    /// Trust an L1 root without signatures (startup configuration)
+    pub fn pin_root(&mut self, l1_slot: u64, root: Vec<u8>) {
+        self.trusted_roots.insert(l1_slot, root);
+    }
+
+    /// Trust an L1 root vouched for by enough relayers
+    pub fn add_attested_root(
+        &mut self,
+        l1_slot: u64,
+        root: Vec<u8>,
+        attestations: &[Attestation],
+    ) -> Result<Vec<String>, BridgeError> {
+        if let Some(existing) = self.trusted_roots.get(&l1_slot) {
+            if *existing != root {
+                return Err(BridgeError::InvalidProof(format!(
+                    "Slot {} already has a different trusted root", l1_slot
+                )));
+            }
+        }
+        let signers = self.check_attestations(&root_signing_bytes(l1_slot, &root), attestations)?;
+        self.trusted_roots.insert(l1_slot, root);
+        Ok(signers)
+    }
+
+    /// Verify a deposit against its proof
+    pub fn verify(
+        &self,
+        request: &BridgeCompleteRequest,
+        proof: &DepositProof,
+    ) -> Result<DepositVerification, BridgeError> {
+        match proof {
+            DepositProof::Attestation { attestations } => {
+                let signers = self.check_attestations(&deposit_signing_bytes(request), attestations)?;
+                Ok(DepositVerification::Attestation { signers })
+            }
+            DepositProof::Inclusion { proof } => {
+                let root = self.trusted_root(request.l1_slot).ok_or_else(|| {
+                    BridgeError::InvalidProof(format!("No trusted L1 root for slot {}", request.l1_slot))
+                })?;
+                if *proof.algorithm != SHA256 {
+                    return Err(BridgeError::InvalidProof("Inclusion proofs must use SHA256".into()));
+                }
+                if proof.value != deposit_leaf(request) {
+                    return Err(BridgeError::InvalidProof("Proof leaf does not match the deposit".into()));
+                }
+                if !proof.validate(root) {
+                    return Err(BridgeError::InvalidProof(format!(
+                        "Inclusion proof does not match trusted root for slot {}", request.l1_slot
+                    )));
+                }
+                Ok(DepositVerification::Inclusion {
+                    l1_slot: request.l1_slot,
+                    root: hex::encode(root),
+                })
+            }
+        }
+    }
+
+    /// Count valid signatures from distinct trusted relayers over `message`
+    fn check_attestations(&self, message: &[u8], attestations: &[Attestation]) -> Result<Vec<String>, BridgeError> {
+        if self.relayer_keys.is_empty() {
+            return Err(BridgeError::InvalidProof("No L1 relayer keys configured".into()));
+        }
+
+        let mut signers: Vec<String> = Vec::new();
+        for attestation in attestations {
+            let pubkey = attestation.relayer_pubkey.trim().to_lowercase();
+            if !self.relayer_keys.contains(&pubkey) || signers.contains(&pubkey) {
+                continue;
+            }
+            if verify_ed25519(&pubkey, &attestation.signature, message) {
+                signers.push(pubkey);
+            }
+        }
+
+        if signers.len() < self.threshold {
+            return Err(BridgeError::SignatureVerificationFailed(format!(
+                "{} valid relayer attestation(s), {} required", signers.len(), self.threshold
+            )));
+        }
+        Ok(signers)
+    }
+}
+
+/// Verify a hex Ed25519 signature against a hex public key
+pub(crate) fn verify_ed25519(pubkey_hex: &str, signature_hex: &str, message: &[u8]) -> bool {
+    let pubkey: [u8; 32] = match hex::decode(pubkey_hex).ok().and_then(|b| b.try_into().ok()) {
+        Some(bytes) => bytes,
+        None => return false,
+    };
+    let signature: [u8; 64] = match hex::decode(signature_hex).ok().and_then(|b| b.try_into().ok()) {
+        Some(bytes) => bytes,
+        None => return false,
+    };
+    match VerifyingKey::from_bytes(&pubkey) {
+        Ok(key) => key.verify(message, &Signature::from_bytes(&signature)).is_ok(),
+        Err(_) => false,
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use merkle::MerkleTree;
+
+    fn relayer(seed: u8) -> (SigningKey, String) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let pubkey = hex::encode(key.verifying_key().to_bytes());
+        (key, pubkey)
+    }
+
+    fn attest(key: &SigningKey, message: &[u8]) -> Attestation {
+        Attestation {
+            relayer_pubkey: hex::encode(key.verifying_key().to_bytes()),
+            signature: hex::encode(key.sign(message).to_bytes()),
+        }
+    }
+
+    fn deposit(bridge_id: &str, amount: f64) -> BridgeCompleteRequest {
+        BridgeCompleteRequest {
+            bridge_id: bridge_id.into(),
+            from_address: "L1_alice".into(),
+            to_address: "bb1_alice".into(),
+            amount,
+            l1_tx_hash: format!("0x{}", bridge_id),
+            l1_slot: 42,
+        }
+    }
+
+    #[test]
+    fn test_attestation_threshold() {
+        let (k1, p1) = relayer(1);
+        let (k2, p2) = relayer(2);
+        let (outsider, _) = relayer(3);
+        let verifier = DepositVerifier::new(vec![p1, p2], 2);
+        let request = deposit("d1", 10.0);
+        let message = deposit_signing_bytes(&request);
+
+        // One signer (counted once even if repeated) plus an untrusted key is not enough
+        let weak = DepositProof::Attestation {
+            attestations: vec![attest(&k1, &message), attest(&k1, &message), attest(&outsider, &message)],
+        };
+        assert!(verifier.verify(&request, &weak).is_err());
+
+        let strong = DepositProof::Attestation {
+            attestations: vec![attest(&k1, &message), attest(&k2, &message)],
+        };
+        assert!(matches!(
+            verifier.verify(&request, &strong),
+            Ok(DepositVerification::Attestation { ref signers }) if signers.len() == 2
+        ));
+
+        // Signatures don't carry over to a tampered amount
+        assert!(verifier.verify(&deposit("d1", 1000.0), &strong).is_err());
+    }
+
+    #[test]
+    fn test_inclusion_proof_against_attested_root() {
+        let (k1, p1) = relayer(1);
+        let mut verifier = DepositVerifier::new(vec![p1], 1);
+
+        let requests = [deposit("d1", 10.0), deposit("d2", 20.0), deposit("d3", 30.0)];
+        let leaves: Vec<Vec<u8>> = requests.iter().map(deposit_leaf).collect();
+        let tree = MerkleTree::from_vec(&SHA256, leaves.clone());
+        let root = tree.root_hash().clone();
+        let proof = tree.gen_proof(leaves[1].clone()).unwrap();
+        let json = serde_json::to_value(DepositProof::Inclusion { proof }).unwrap();
+        let proof: DepositProof = serde_json::from_value(json).unwrap();
+
+        // Unknown root: rejected
+        assert!(verifier.verify(&requests[1], &proof).is_err());
+
+        let root_attestation = attest(&k1, &root_signing_bytes(42, &root));
+        verifier.add_attested_root(42, root.clone(), &[root_attestation]).unwrap();
+        assert!(verifier.verify(&requests[1], &proof).is_ok());
+
+        // Proof for d2 can't be replayed for d3
+        assert!(verifier.verify(&requests[2], &proof).is_err());
+
+        // A conflicting root for the same slot is refused
+        assert!(verifier.add_attested_root(42, vec![0u8; 32], &[]).is_err());
+    }
+
+    #[test]
+    fn test_unconfigured_verifier_rejects() {
+        let verifier = DepositVerifier::default();
+        assert!(!verifier.is_configured());
+        let proof = DepositProof::Attestation { attestations: vec![] };
+        assert!(verifier.verify(&deposit("d1", 10.0), &proof).is_err());
+    }
+}
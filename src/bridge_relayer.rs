@@ -0,0 +1,535 @@
+// ============================================================================
+// Bridge Relayer - Drives L2→L1 withdrawals to a final state
+// ============================================================================
+//
+// Background task that owns every L2→L1 withdrawal once it has been debited
+// on L2:
+//
+//   pending   → submit to L1 (bridge_id is the idempotency key)
+//   confirmed → query L1 until the release completes or fails
+//
+// With batching enabled, withdrawals are instead queued into batches; the
+// relayer seals due batches and commits each Merkle root to L1 (batch_id is
+// the idempotency key). A committed batch completes all of its withdrawals,
+// which users then claim on L1 with their inclusion proofs.
+//
+// Transport errors are retried with exponential backoff. A withdrawal is only
+// refunded when L1 definitively rejects it, or when it has expired and L1
+// reports it never saw it; refunding on a timeout alone could pay out twice.
+// Bridge state is saved to disk after every step.
+// ============================================================================
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app_state::{AppState, SharedState};
+use crate::bridge::{BridgeStatus, PendingBridge, BRIDGE_TIMEOUT_SECS};
+use crate::rpc::{L1WithdrawRequest, L1WithdrawResponse, L1WithdrawalBatchRequest};
+use crate::withdrawal_batch::WithdrawalBatch;
+
+/// Delay between status polls once L1 has accepted a withdrawal
+const CONFIRMATION_POLL_SECS: u64 = 10;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Result of pushing one withdrawal a step forward
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelayOutcome {
+    /// L1 released the funds
+    Completed { l1_tx_hash: Option<String> },
+    /// L1 accepted the withdrawal and has not released it yet
+    Submitted { l1_tx_hash: Option<String> },
+    /// L1 rejected the withdrawal (or it expired unseen); L2 balance refunded
+    Refunded { error: String },
+    /// L1 could not be reached; retry scheduled
+    Retrying { error: String, attempts: u32, next_attempt_at: u64 },
+    /// Unknown bridge, not relayer-managed, or already final
+    Skipped,
+}
+
+/// Rebuild the L1 request for a withdrawal from its tracking record
+fn withdraw_request(app: &AppState, bridge: &PendingBridge) -> Option<L1WithdrawRequest> {
+    let pw = app.pending_withdrawals.get(&bridge.bridge_id)?;
+    Some(L1WithdrawRequest {
+        from_l2_address: pw.wallet_address.clone(),
+        to_l1_address: pw.l1_target.clone(),
+        amount: pw.amount,
+        bridge_id: bridge.bridge_id.clone(),
+        signature: pw.signature.clone(),
+        timestamp: pw.timestamp,
+        nonce: pw.nonce.to_string(),
+    })
+}
+
+/// Relay a single withdrawal: submit it, or reconcile it against L1
+pub async fn relay_withdrawal(state: &SharedState, bridge_id: &str) -> RelayOutcome {
+    let (l1, bridge, request) = {
+        let app = state.lock().unwrap();
+        let bridge = match app.bridge_manager.get_status(bridge_id) {
+            Some(bridge) if !bridge.status.is_terminal() => bridge,
+            _ => return RelayOutcome::Skipped,
+        };
+        let request = match withdraw_request(&app, &bridge) {
+            Some(request) => request,
+            None => return RelayOutcome::Skipped,
+        };
+        (app.l1.clone(), bridge, request)
+    }; // Release lock before async L1 calls
+
+    let result = match (bridge.status, bridge.is_expired()) {
+        // Fresh withdrawal: submit (retries reuse the same bridge_id)
+        (BridgeStatus::Pending, false) => l1.withdraw_to_l1(&request).await.map(Some),
+        // Expired: never resubmit, only ask L1 what happened
+        (_, true) => l1.get_withdrawal_status(bridge_id).await,
+        // Accepted earlier: poll, resubmitting if L1 lost it
+        _ => match l1.get_withdrawal_status(bridge_id).await {
+            Ok(None) => l1.withdraw_to_l1(&request).await.map(Some),
+            other => other,
+        },
+    };
+
+    let now = now();
+    let mut app = state.lock().unwrap();
+
+    // The handler and the background task can race on the same bridge
+    match app.bridge_manager.get_status(bridge_id) {
+        Some(current) if !current.status.is_terminal() => {}
+        _ => return RelayOutcome::Skipped,
+    }
+    if let Some(pw) = app.pending_withdrawals.get_mut(bridge_id) {
+        pw.poll_count += 1;
+        pw.last_poll = Some(now);
+    }
+
+    let outcome = match result {
+        Ok(Some(response)) => apply_response(&mut app, &bridge, response, now),
+        Ok(None) => refund(&mut app, &bridge, "Expired before L1 accepted the withdrawal".to_string()),
+        Err(error) => retry(&mut app, &bridge, error, now),
+    };
+
+    if let Err(e) = app.save_bridge_state() {
+        eprintln!("⚠️  Failed to persist bridge state: {}", e);
+    }
+    outcome
+}
+
+/// Apply an L1 answer (submission or status query) to a withdrawal
+fn apply_response(
+    app: &mut AppState,
+    bridge: &PendingBridge,
+    response: L1WithdrawResponse,
+    now: u64,
+) -> RelayOutcome {
+    if !response.success || response.status == "failed" {
+        let error = response.error.unwrap_or_else(|| "L1 rejected withdrawal".to_string());
+        return refund(app, bridge, error);
+    }
+
+    let bridge_id = &bridge.bridge_id;
+    let _ = app.bridge_manager.record_relay_success(bridge_id, now, now + CONFIRMATION_POLL_SECS);
+
+    if response.status == "completed" {
+        let _ = app.bridge_manager.complete_withdrawal(
+            bridge_id,
+            response.l1_tx_hash.clone().unwrap_or_default(),
+            0,
+        );
+        if let Some(pw) = app.pending_withdrawals.get_mut(bridge_id) {
+            pw.status = "completed".to_string();
+            pw.l1_tx_hash = response.l1_tx_hash.clone();
+            pw.error = None;
+        }
+        app.log_activity("🌉", "BRIDGE_L1_COMPLETED", &format!(
+            "L1 released withdrawal {} for {} BB (tx: {:?})",
+            bridge_id, bridge.amount, response.l1_tx_hash
+        ));
+        RelayOutcome::Completed { l1_tx_hash: response.l1_tx_hash }
+    } else {
+        let _ = app.bridge_manager.update_withdrawal_l1_submitted(bridge_id, response.l1_tx_hash.clone());
+        if let Some(pw) = app.pending_withdrawals.get_mut(bridge_id) {
+            pw.status = "l1_submitted".to_string();
+            pw.l1_tx_hash = response.l1_tx_hash.clone();
+            pw.error = None;
+        }
+        if bridge.status == BridgeStatus::Pending {
+            app.log_activity("🌉", "BRIDGE_L1_ACCEPTED", &format!(
+                "L1 accepted withdrawal {} for {} BB (tx: {:?})",
+                bridge_id, bridge.amount, response.l1_tx_hash
+            ));
+        }
+        RelayOutcome::Submitted { l1_tx_hash: response.l1_tx_hash }
+    }
+}
+
+/// Definitive failure: give the L2 balance back
+fn refund(app: &mut AppState, bridge: &PendingBridge, error: String) -> RelayOutcome {
+    let bridge_id = &bridge.bridge_id;
+    if app.bridge_manager.refund_withdrawal(bridge_id, error.clone()).is_err() {
+        return RelayOutcome::Skipped;
+    }
+    app.ledger.credit(&bridge.from_address, bridge.amount);
+
+    if let Some(pw) = app.pending_withdrawals.get_mut(bridge_id) {
+        pw.status = "refunded".to_string();
+        pw.error = Some(error.clone());
+    }
+    app.log_activity("🌉", "BRIDGE_REFUNDED", &format!(
+        "Withdrawal {} failed ({}) - refunded {} BB to {}",
+        bridge_id, error, bridge.amount, bridge.from_address
+    ));
+    RelayOutcome::Refunded { error }
+}
+
+/// Transport failure: schedule another attempt with backoff
+fn retry(app: &mut AppState, bridge: &PendingBridge, error: String, now: u64) -> RelayOutcome {
+    let bridge_id = &bridge.bridge_id;
+    let updated = match app.bridge_manager.record_relay_failure(bridge_id, error.clone(), now) {
+        Ok(updated) => updated,
+        Err(_) => return RelayOutcome::Skipped,
+    };
+    if let Some(pw) = app.pending_withdrawals.get_mut(bridge_id) {
+        pw.error = Some(error.clone());
+    }
+    app.log_activity("🌉", "BRIDGE_RELAY_RETRY", &format!(
+        "L1 unreachable for {} (attempt {}): {} - retrying in {}s",
+        bridge_id, updated.relay_attempts, error, updated.next_attempt_at.saturating_sub(now)
+    ));
+    RelayOutcome::Retrying {
+        error,
+        attempts: updated.relay_attempts,
+        next_attempt_at: updated.next_attempt_at,
+    }
+}
+
+// ============================================================================
+// BATCHES
+// ============================================================================
+
+/// Commit a sealed batch root to L1, or reconcile it once expired
+pub async fn relay_batch(state: &SharedState, batch: &WithdrawalBatch) -> RelayOutcome {
+    let l1 = state.lock().unwrap().l1.clone();
+    let now = now();
+    let expired = batch.sealed_at
+        .map(|sealed_at| now > sealed_at + BRIDGE_TIMEOUT_SECS)
+        .unwrap_or(false);
+
+    let result = if expired {
+        l1.get_withdrawal_batch(batch.batch_id).await
+    } else {
+        let request = L1WithdrawalBatchRequest {
+            batch_id: batch.batch_id,
+            root: batch.root.clone().unwrap_or_default(),
+            leaf_count: batch.leaves.len(),
+            total_amount: batch.total_amount(),
+            sealed_at: batch.sealed_at.unwrap_or(now),
+        };
+        l1.commit_withdrawal_batch(&request).await.map(Some)
+    };
+
+    let mut app = state.lock().unwrap();
+    let outcome = match result {
+        Ok(Some(response)) if response.success && response.status != "failed" => {
+            match app.withdrawal_batches.mark_committed(batch.batch_id, response.l1_tx_hash.clone(), now) {
+                Some(committed) => {
+                    complete_batch(&mut app, &committed);
+                    RelayOutcome::Completed { l1_tx_hash: response.l1_tx_hash }
+                }
+                None => RelayOutcome::Skipped,
+            }
+        }
+        Ok(Some(response)) => {
+            let error = response.error.unwrap_or_else(|| "L1 rejected withdrawal batch".to_string());
+            fail_batch(&mut app, batch.batch_id, error)
+        }
+        Ok(None) => fail_batch(&mut app, batch.batch_id, "Batch expired before L1 accepted it".to_string()),
+        Err(error) => {
+            let updated = app.withdrawal_batches.record_relay_failure(batch.batch_id, error.clone(), now);
+            for bridge_id in &batch.bridge_ids {
+                let _ = app.bridge_manager.record_relay_failure(bridge_id, error.clone(), now);
+            }
+            match updated {
+                Some(updated) => {
+                    app.log_activity("📦", "BATCH_RELAY_RETRY", &format!(
+                        "L1 unreachable for batch {} (attempt {}): {}",
+                        batch.batch_id, updated.relay_attempts, error
+                    ));
+                    RelayOutcome::Retrying {
+                        error,
+                        attempts: updated.relay_attempts,
+                        next_attempt_at: updated.next_attempt_at,
+                    }
+                }
+                None => RelayOutcome::Skipped,
+            }
+        }
+    };
+
+    if let Err(e) = app.save_bridge_state() {
+        eprintln!("⚠️  Failed to persist bridge state: {}", e);
+    }
+    outcome
+}
+
+/// Root is on L1: every withdrawal in the batch is now claimable
+fn complete_batch(app: &mut AppState, batch: &WithdrawalBatch) {
+    let l1_tx_hash = batch.l1_tx_hash.clone().unwrap_or_default();
+    for bridge_id in &batch.bridge_ids {
+        let _ = app.bridge_manager.complete_withdrawal(bridge_id, l1_tx_hash.clone(), 0);
+        if let Some(pw) = app.pending_withdrawals.get_mut(bridge_id) {
+            pw.status = "completed".to_string();
+            pw.l1_tx_hash = batch.l1_tx_hash.clone();
+            pw.error = None;
+        }
+    }
+    app.log_activity("📦", "BATCH_COMMITTED", &format!(
+        "Committed withdrawal batch {} ({} withdrawals, {} BB, root {}) in L1 tx {}",
+        batch.batch_id,
+        batch.leaves.len(),
+        batch.total_amount(),
+        batch.root.as_deref().unwrap_or(""),
+        l1_tx_hash
+    ));
+}
+
+/// L1 rejected the batch (or it expired unseen): refund every withdrawal in it
+fn fail_batch(app: &mut AppState, batch_id: u64, error: String) -> RelayOutcome {
+    let batch = match app.withdrawal_batches.mark_failed(batch_id, error.clone()) {
+        Some(batch) => batch,
+        None => return RelayOutcome::Skipped,
+    };
+    for bridge_id in &batch.bridge_ids {
+        if let Some(bridge) = app.bridge_manager.get_status(bridge_id) {
+            refund(app, &bridge, error.clone());
+        }
+    }
+    RelayOutcome::Refunded { error }
+}
+
+/// Seal batches that are old enough and commit every sealed batch that is due
+pub async fn run_batches(state: &SharedState) -> Vec<(u64, RelayOutcome)> {
+    let due = {
+        let mut app = state.lock().unwrap();
+        let now = now();
+        if let Some(batch_id) = app.withdrawal_batches.seal_due(now) {
+            app.log_activity("📦", "BATCH_SEALED", &format!("Sealed withdrawal batch {}", batch_id));
+        }
+        app.withdrawal_batches.due_commits(now)
+    };
+
+    let mut outcomes = Vec::with_capacity(due.len());
+    for batch in due {
+        let outcome = relay_batch(state, &batch).await;
+        outcomes.push((batch.batch_id, outcome));
+    }
+    outcomes
+}
+
+// ============================================================================
+// RELAY LOOP
+// ============================================================================
+
+/// Process every withdrawal that is due (batched ones go through `run_batches`)
+pub async fn run_once(state: &SharedState) -> Vec<(String, RelayOutcome)> {
+    run_batches(state).await;
+
+    let due: Vec<String> = {
+        let app = state.lock().unwrap();
+        app.bridge_manager.due_withdrawals(now())
+            .into_iter()
+            .map(|b| b.bridge_id)
+            .filter(|id| app.pending_withdrawals.contains_key(id))
+            .filter(|id| app.withdrawal_batches.locate(id).is_none())
+            .collect()
+    };
+
+    let mut outcomes = Vec::with_capacity(due.len());
+    for bridge_id in due {
+        let outcome = relay_withdrawal(state, &bridge_id).await;
+        outcomes.push((bridge_id, outcome));
+    }
+    outcomes
+}
+
+/// Spawn the background relayer (every `bridge.relayer_interval_secs`)
+pub fn spawn(state: SharedState) {
+    let interval_secs = state.lock().unwrap().config.bridge.relayer_interval_secs;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            run_once(&state).await;
+        }
+    });
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::app_state::test_state;
+    use crate::bridge::STUCK_RELAY_ATTEMPTS;
+    use crate::rpc::{MockL1, MockL1Backend, RecordingL1Backend};
+
+    const ALICE: &str = "L2_ALICE";
+    const ALICE_L1: &str = "L1_ALICE";
+
+    /// Relayer-managed (unbatched) withdrawal of 40 BB out of a 100 BB balance,
+    /// relayed through a recorder wrapping the mock L1
+    fn setup(name: &str) -> (SharedState, Arc<MockL1Backend>, Arc<RecordingL1Backend>, String) {
+        let state = test_state(name);
+        let l1 = Arc::new(MockL1Backend::new(MockL1::new(0.0)));
+        let recorder = Arc::new(RecordingL1Backend::new(l1.clone()));
+        state.ledger.credit(ALICE, 100.0);
+        let bridge_id = {
+            let mut app = state.lock().unwrap();
+            app.l1 = recorder.clone();
+            app.withdrawal_batches.enabled = false;
+            let now = now();
+            app.begin_withdrawal(ALICE, ALICE_L1, 40.0, "sig", 1, now, now).0
+        };
+        (state, l1, recorder, bridge_id)
+    }
+
+    fn methods(recorder: &RecordingL1Backend) -> Vec<&'static str> {
+        recorder.calls().iter().map(|c| c.method).collect()
+    }
+
+    fn status(state: &SharedState, bridge_id: &str) -> (BridgeStatus, String) {
+        let app = state.lock().unwrap();
+        (
+            app.bridge_manager.get_status(bridge_id).unwrap().status,
+            app.pending_withdrawals[bridge_id].status.clone(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_transport_error_is_retried_with_backoff() {
+        let (state, l1, recorder, bridge_id) = setup("relay_retry");
+        l1.faults().fail_next = 1;
+
+        let outcomes = run_once(&state).await;
+        assert_eq!(outcomes.len(), 1);
+        match &outcomes[0].1 {
+            RelayOutcome::Retrying { attempts, next_attempt_at, .. } => {
+                assert_eq!(*attempts, 1);
+                assert!(*next_attempt_at > now());
+            }
+            other => panic!("expected a retry, got {:?}", other),
+        }
+        assert_eq!(status(&state, &bridge_id), (BridgeStatus::Pending, "pending".to_string()));
+        assert!(l1.state().withdrawal(&bridge_id).is_none());
+
+        // Backing off: the loop leaves it alone until the retry is due
+        assert!(run_once(&state).await.is_empty());
+
+        let outcome = relay_withdrawal(&state, &bridge_id).await;
+        assert!(matches!(outcome, RelayOutcome::Completed { .. }), "{:?}", outcome);
+        assert_eq!(status(&state, &bridge_id), (BridgeStatus::Completed, "completed".to_string()));
+        assert_eq!(l1.state().balance(ALICE_L1).balance, 40.0);
+        assert_eq!(state.ledger.balance(ALICE), 60.0);
+
+        // One failed submission, nothing while backing off, one resubmission
+        assert_eq!(methods(&recorder), vec!["withdraw_to_l1", "withdraw_to_l1"]);
+        let calls = recorder.calls();
+        assert!(calls[0].response.is_err());
+        assert!(calls[1].response.is_ok());
+        assert_eq!(calls[1].request["bridge_id"], bridge_id);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failures_mark_withdrawal_stuck() {
+        let (state, l1, recorder, bridge_id) = setup("relay_stuck");
+        l1.faults().fail_next = STUCK_RELAY_ATTEMPTS;
+
+        for attempt in 1..=STUCK_RELAY_ATTEMPTS {
+            assert!(state.lock().unwrap().bridge_manager.stuck(now()).is_empty());
+            match relay_withdrawal(&state, &bridge_id).await {
+                RelayOutcome::Retrying { attempts, .. } => assert_eq!(attempts, attempt),
+                other => panic!("expected a retry, got {:?}", other),
+            }
+        }
+
+        let stuck = state.lock().unwrap().bridge_manager.stuck(now());
+        assert_eq!(stuck.len(), 1);
+        assert_eq!(stuck[0].bridge_id, bridge_id);
+        assert_eq!(stuck[0].last_relay_error.as_deref(), Some("injected fault on /bridge/withdraw"));
+        // Stuck is not final: nothing is refunded while L1 may still act on it
+        assert_eq!(state.ledger.balance(ALICE), 60.0);
+
+        // L1 comes back and the withdrawal completes
+        let outcome = relay_withdrawal(&state, &bridge_id).await;
+        assert!(matches!(outcome, RelayOutcome::Completed { .. }), "{:?}", outcome);
+        assert!(state.lock().unwrap().bridge_manager.stuck(now()).is_empty());
+
+        let calls = recorder.calls_to("withdraw_to_l1");
+        assert_eq!(calls.len(), STUCK_RELAY_ATTEMPTS as usize + 1);
+        assert_eq!(calls.iter().filter(|c| c.response.is_err()).count(), STUCK_RELAY_ATTEMPTS as usize);
+        assert_eq!(recorder.calls().len(), calls.len());
+    }
+
+    #[tokio::test]
+    async fn test_lost_response_is_reconciled_without_paying_twice() {
+        let (state, l1, recorder, bridge_id) = setup("relay_reconcile");
+        l1.faults().drop_next = 1;
+
+        // L1 applied the withdrawal but the relayer never heard back
+        let outcome = relay_withdrawal(&state, &bridge_id).await;
+        assert!(matches!(outcome, RelayOutcome::Retrying { .. }), "{:?}", outcome);
+        assert!(l1.state().withdrawal(&bridge_id).is_some());
+        assert_eq!(l1.state().balance(ALICE_L1).balance, 40.0);
+
+        // The retry reuses bridge_id, so L1 answers with the original release
+        let outcome = relay_withdrawal(&state, &bridge_id).await;
+        let l1_tx_hash = l1.state().withdrawal(&bridge_id).unwrap().l1_tx_hash;
+        assert_eq!(outcome, RelayOutcome::Completed { l1_tx_hash: l1_tx_hash.clone() });
+        assert_eq!(l1.state().balance(ALICE_L1).balance, 40.0);
+        assert_eq!(state.ledger.balance(ALICE), 60.0);
+
+        // Both submissions carried the same bridge_id; only the second was heard
+        let calls = recorder.calls();
+        assert_eq!(methods(&recorder), vec!["withdraw_to_l1", "withdraw_to_l1"]);
+        assert_eq!(calls[0].request["bridge_id"], bridge_id);
+        assert_eq!(calls[1].request["bridge_id"], bridge_id);
+        assert_eq!(calls[0].response.as_ref().unwrap_err(), "dropped response on /bridge/withdraw");
+
+        let app = state.lock().unwrap();
+        assert_eq!(app.pending_withdrawals[&bridge_id].l1_tx_hash, l1_tx_hash);
+        assert_eq!(app.pending_withdrawals[&bridge_id].poll_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_l1_rejection_refunds_l2_balance() {
+        let (state, l1, recorder, bridge_id) = setup("relay_refund");
+        assert_eq!(state.ledger.balance(ALICE), 60.0);
+        l1.state().withdrawals.insert(bridge_id.clone(), L1WithdrawResponse {
+            success: false,
+            bridge_id: Some(bridge_id.clone()),
+            l1_tx_hash: None,
+            status: "failed".to_string(),
+            new_l1_balance: None,
+            error: Some("Bridge paused".to_string()),
+        });
+
+        let outcome = relay_withdrawal(&state, &bridge_id).await;
+        assert_eq!(outcome, RelayOutcome::Refunded { error: "Bridge paused".to_string() });
+        assert_eq!(state.ledger.balance(ALICE), 100.0);
+        assert_eq!(status(&state, &bridge_id), (BridgeStatus::Failed, "refunded".to_string()));
+        assert_eq!(l1.state().balance(ALICE_L1).balance, 0.0);
+
+        // Final: neither the loop nor a direct relay touches it again
+        assert!(run_once(&state).await.is_empty());
+        assert_eq!(relay_withdrawal(&state, &bridge_id).await, RelayOutcome::Skipped);
+        assert_eq!(state.ledger.balance(ALICE), 100.0);
+        assert_eq!(methods(&recorder), vec!["withdraw_to_l1"]);
+    }
+}
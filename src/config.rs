@@ -0,0 +1,1266 @@
+//! Configuration
+//!
+//! One typed config for the server, L1 connection and market economics,
+//! built from layers (later layers win):
+//!
+//! 1. Built-in defaults (the values that used to be compile-time constants)
+//! 2. `config/blackbook.toml`, or the file named by `BLACKBOOK_CONFIG`
+//! 3. `blackbook.<env>.toml` next to it, where `<env>` is `BLACKBOOK_ENV`
+//! 4. Legacy environment variables (`PORT`, `L1_RPC_URL`, `BRIDGE_RELAYER_KEYS`,
+//!    ... see `LEGACY_ENV`)
+//! 5. `BLACKBOOK__<SECTION>__<KEY>` environment variables,
+//!    e.g. `BLACKBOOK__FEES__TAKER_FEE_RATE=0.004`
+//!
+//! Unknown keys and out-of-range values fail startup instead of being ignored.
+//! Fees and order book limits can be overridden per market under
+//! `[markets.overrides."<market_id>"]`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::audit_log::DEFAULT_AUDIT_PATH;
+use crate::auth::SupabaseConfig;
+use crate::blocks::{BlockProducer, DEFAULT_BLOCK_INTERVAL_SECS};
+use crate::bridge_proof::{DepositVerifier, DEFAULT_ATTESTATION_THRESHOLD};
+use crate::easteregg::{DataFeedType, GodMode, LocalFeed, OracleManager};
+use crate::forced_exit::{ExitManager, DEFAULT_EXIT_DEADLINE_SECS, DEFAULT_EXIT_PROCESS_INTERVAL_SECS};
+use crate::idempotency::{DEFAULT_IDEMPOTENCY_STORE_PATH, DEFAULT_IDEMPOTENCY_TTL_SECS};
+use crate::market_resolve::cpmm::{LP_FEE_RATE, VIABILITY_THRESHOLD};
+use crate::market_resolve::dispute::{DisputeManager, DEFAULT_CHALLENGE_WINDOW_SECS, DEFAULT_RESOLUTION_BOND};
+use crate::roles::{normalize_key, Role, DEFAULT_ROLES_PATH};
+use crate::orderbook::{MAKER_FEE_RATE, MAX_SPREAD_BPS, MIN_CLOB_DEPTH, TAKER_FEE_RATE};
+use crate::rpc::{L1RpcConfig, MockL1, L1_DEFAULT_ENDPOINT, L1_RETRY_ATTEMPTS, L1_RETRY_DELAY_MS, L1_TIMEOUT_SECS, MOCK_DEFAULT_BALANCE, TX_EXPIRY_SECS};
+use crate::rss::{feed_http_client, FeedPublisher, RssFeedManager, DEFAULT_FETCH_TIMEOUT_SECS, DEFAULT_MAX_FEED_ITEMS, DEFAULT_ODDS_MOVE_THRESHOLD, DEFAULT_POLL_INTERVAL_SECS, DEFAULT_SITE_URL};
+use crate::state_root::{SequencerKey, StateCommitter, DEFAULT_STATE_COMMIT_INTERVAL_SECS};
+use crate::withdrawal_batch::{WithdrawalBatcher, DEFAULT_BATCH_MAX_AGE_SECS, DEFAULT_BATCH_MAX_SIZE};
+
+/// Config file read when `BLACKBOOK_CONFIG` is not set (optional)
+pub const DEFAULT_CONFIG_PATH: &str = "config/blackbook.toml";
+
+/// Prefix for per-key environment overrides
+pub const ENV_PREFIX: &str = "BLACKBOOK__";
+
+/// BB seeded into a CPMM pool when a bet hits a market without one
+pub const DEFAULT_AUTO_LIQUIDITY: f64 = 10_000.0;
+
+/// Where markets and nonces are persisted
+pub const DEFAULT_STATE_PATH: &str = "data/state.json";
+
+/// Folder of market `.rss` files, loaded when there is no saved state
+pub const DEFAULT_EVENTS_DIR: &str = "rss/events";
+
+/// Where in-flight bridges are persisted
+pub const DEFAULT_BRIDGE_STATE_PATH: &str = "data/bridges.json";
+
+/// Content hashes of feed items already sent to the inbox
+pub const DEFAULT_FEED_PROCESSED_PATH: &str = "data/feed_processed.json";
+
+/// Seconds between bridge relayer passes
+pub const DEFAULT_RELAYER_INTERVAL_SECS: u64 = 10;
+
+/// Seconds between price resolver scans
+pub const DEFAULT_RESOLVER_INTERVAL_SECS: u64 = 60;
+
+/// Account the price resolver bonds its proposals from
+pub const DEFAULT_RESOLVER_ADDRESS: &str = "ORACLE";
+
+/// Seconds between expired-session settlement passes
+pub const DEFAULT_SESSION_SETTLE_INTERVAL_SECS: u64 = 30;
+
+/// Older variables still honoured, mapped onto config keys
+const LEGACY_ENV: &[(&str, &str)] = &[
+    ("PORT", "server.port"),
+    ("L1_RPC_URL", "l1.rpc_url"),
+    ("L1_MOCK_MODE", "l1.mock_mode"),
+    ("BLACKBOOK_ADMIN_ADDRESS", "roles.admins"),
+    ("BLACKBOOK_ORACLE_ADDRESS", "roles.oracles"),
+    ("L1_MOCK_DEFAULT_BALANCE", "l1.mock_default_balance"),
+    ("L1_MOCK_ACCOUNTS", "l1.mock_accounts"),
+    ("BRIDGE_STATE_PATH", "bridge.state_path"),
+    ("BRIDGE_RELAYER_INTERVAL_SECS", "bridge.relayer_interval_secs"),
+    ("BRIDGE_RELAYER_KEYS", "bridge.relayer_keys"),
+    ("BRIDGE_ATTESTATION_THRESHOLD", "bridge.attestation_threshold"),
+    ("BRIDGE_TRUSTED_L1_ROOTS", "bridge.trusted_l1_roots"),
+    ("BRIDGE_BATCH_WITHDRAWALS", "bridge.batch_withdrawals"),
+    ("BRIDGE_BATCH_MAX_SIZE", "bridge.batch_max_size"),
+    ("BRIDGE_BATCH_MAX_AGE_SECS", "bridge.batch_max_age_secs"),
+    ("SEQUENCER_SIGNING_KEY", "sequencer.signing_key"),
+    ("BLOCK_INTERVAL_SECS", "sequencer.block_interval_secs"),
+    ("STATE_COMMIT_INTERVAL_SECS", "sequencer.state_commit_interval_secs"),
+    ("FORCED_EXIT_DEADLINE_SECS", "exits.deadline_secs"),
+    ("FORCED_EXIT_INTERVAL_SECS", "exits.process_interval_secs"),
+    ("RESOLUTION_CHALLENGE_WINDOW_SECS", "resolution.challenge_window_secs"),
+    ("RESOLUTION_BOND_BB", "resolution.bond"),
+    ("ORACLE_RESOLVER_INTERVAL_SECS", "resolution.resolver_interval_secs"),
+    ("ORACLE_LOCAL_FEED", "resolution.oracle_local_feed"),
+    ("SESSION_SETTLE_INTERVAL_SECS", "sessions.settle_interval_secs"),
+    ("FEED_MAX_ITEMS", "feeds.max_items"),
+    ("FEED_ODDS_MOVE_THRESHOLD", "feeds.odds_move_threshold"),
+    ("FEED_SITE_URL", "feeds.site_url"),
+    ("RSS_FEEDS", "feeds.sources"),
+    ("RSS_POLL_INTERVAL_SECS", "feeds.poll_interval_secs"),
+    ("NEXT_PUBLIC_SUPABASE_URL", "auth.supabase_url"),
+    ("NEXT_PUBLIC_SUPABASE_ANON_KEY", "auth.supabase_anon_key"),
+    ("SUPABASE_URL", "auth.supabase_url"),
+    ("SUPABASE_ANON_KEY", "auth.supabase_anon_key"),
+    ("GODMODE_ENABLED", "auth.godmode_enabled"),
+];
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// A config file could not be read
+    Io { path: String, error: String },
+    /// A layer is not valid TOML, or has the wrong shape/types
+    Parse { source: String, error: String },
+    /// An environment override names a key that does not exist
+    UnknownKey { var: String },
+    /// An environment override could not be parsed as the key's type
+    InvalidEnv { var: String, value: String },
+    /// Values parsed but failed validation
+    Invalid(Vec<String>),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => write!(f, "Failed to read config {}: {}", path, error),
+            ConfigError::Parse { source, error } => write!(f, "Invalid config in {}: {}", source, error),
+            ConfigError::UnknownKey { var } => write!(f, "{} does not match any config key", var),
+            ConfigError::InvalidEnv { var, value } => write!(f, "{}={} has the wrong type", var, value),
+            ConfigError::Invalid(problems) => write!(f, "Invalid config: {}", problems.join("; ")),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// ============================================================================
+// SECTIONS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self { host: "0.0.0.0".to_string(), port: 1234 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct L1Config {
+    pub rpc_url: String,
+    /// Use the in-process mock L1 instead of HTTP
+    pub mock_mode: bool,
+    pub timeout_secs: u64,
+    pub retry_attempts: u32,
+    pub retry_delay_ms: u64,
+    /// Balance of mock L1 accounts nobody has funded
+    pub mock_default_balance: f64,
+    /// Mock L1 balances as `address=balance`
+    pub mock_accounts: Vec<String>,
+}
+
+impl Default for L1Config {
+    fn default() -> Self {
+        Self {
+            rpc_url: L1_DEFAULT_ENDPOINT.to_string(),
+            mock_mode: false,
+            timeout_secs: L1_TIMEOUT_SECS,
+            retry_attempts: L1_RETRY_ATTEMPTS,
+            retry_delay_ms: L1_RETRY_DELAY_MS,
+            mock_default_balance: MOCK_DEFAULT_BALANCE,
+            mock_accounts: Vec::new(),
+        }
+    }
+}
+
+impl L1Config {
+    pub fn rpc_config(&self) -> L1RpcConfig {
+        L1RpcConfig {
+            endpoint: self.rpc_url.clone(),
+            timeout: Duration::from_secs(self.timeout_secs),
+            retry_attempts: self.retry_attempts,
+            retry_delay: Duration::from_millis(self.retry_delay_ms),
+            mock_mode: self.mock_mode,
+        }
+    }
+
+    /// The simulated chain used when `mock_mode` is set
+    pub fn mock_l1(&self) -> MockL1 {
+        let mut l1 = MockL1::new(self.mock_default_balance);
+        for (address, balance) in self.mock_accounts.iter().filter_map(|e| parse_mock_account(e)) {
+            l1.set_balance(&address, balance);
+        }
+        l1
+    }
+}
+
+/// Exchange-wide fee rates (fractions of trade value)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeeConfig {
+    pub lp_fee_rate: f64,
+    pub maker_fee_rate: f64,
+    pub taker_fee_rate: f64,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            lp_fee_rate: LP_FEE_RATE,
+            maker_fee_rate: MAKER_FEE_RATE,
+            taker_fee_rate: TAKER_FEE_RATE,
+        }
+    }
+}
+
+/// When the CLOB is liquid enough to quote instead of the CPMM
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OrderBookConfig {
+    pub min_clob_depth: f64,
+    pub max_spread_bps: u64,
+}
+
+impl Default for OrderBookConfig {
+    fn default() -> Self {
+        Self { min_clob_depth: MIN_CLOB_DEPTH, max_spread_bps: MAX_SPREAD_BPS }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionConfig {
+    /// How long a signed transaction stays valid
+    pub expiry_secs: u64,
+}
+
+impl Default for TransactionConfig {
+    fn default() -> Self {
+        Self { expiry_secs: TX_EXPIRY_SECS }
+    }
+}
+
+/// Replay of requests sent with an `Idempotency-Key` header
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdempotencyConfig {
+    /// How long a response is replayed for its key
+    pub ttl_secs: u64,
+    /// Where stored responses survive restarts
+    pub store_path: String,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self { ttl_secs: DEFAULT_IDEMPOTENCY_TTL_SECS, store_path: DEFAULT_IDEMPOTENCY_STORE_PATH.to_string() }
+    }
+}
+
+/// A token bucket: `burst` requests at once, refilled at `per_minute`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BucketLimit {
+    pub burst: u32,
+    pub per_minute: u32,
+}
+
+impl BucketLimit {
+    pub const fn new(burst: u32, per_minute: u32) -> Self {
+        Self { burst, per_minute }
+    }
+}
+
+impl Default for BucketLimit {
+    fn default() -> Self {
+        Self::new(60, 600)
+    }
+}
+
+/// Limits for one route class, applied separately to each IP and each wallet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClassLimits {
+    pub per_ip: BucketLimit,
+    pub per_wallet: BucketLimit,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Take the client IP from the last `X-Forwarded-For` hop (only behind a
+    /// proxy that appends it)
+    pub trust_forwarded_for: bool,
+    /// `X-Api-Key` values (market makers) that skip every limit
+    pub exempt_keys: Vec<String>,
+    pub read: ClassLimits,
+    pub trade: ClassLimits,
+    pub admin: ClassLimits,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trust_forwarded_for: false,
+            exempt_keys: Vec::new(),
+            read: ClassLimits { per_ip: BucketLimit::new(120, 1200), per_wallet: BucketLimit::new(120, 1200) },
+            trade: ClassLimits { per_ip: BucketLimit::new(30, 120), per_wallet: BucketLimit::new(20, 60) },
+            admin: ClassLimits { per_ip: BucketLimit::new(10, 30), per_wallet: BucketLimit::new(10, 30) },
+        }
+    }
+}
+
+/// Keys holding each role at startup (public key hex or `L1_` address).
+/// Grants and revocations made through `/admin/roles` apply on top.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RolesConfig {
+    pub admins: Vec<String>,
+    pub oracles: Vec<String>,
+    pub dealers: Vec<String>,
+    pub market_makers: Vec<String>,
+    pub moderators: Vec<String>,
+    /// Where grants and revocations survive restarts
+    pub store_path: String,
+}
+
+impl Default for RolesConfig {
+    fn default() -> Self {
+        Self {
+            admins: Vec::new(),
+            oracles: Vec::new(),
+            dealers: Vec::new(),
+            market_makers: Vec::new(),
+            moderators: Vec::new(),
+            store_path: DEFAULT_ROLES_PATH.to_string(),
+        }
+    }
+}
+
+impl RolesConfig {
+    /// (role, key) for every configured key, with its config key name
+    fn entries(&self) -> Vec<(&'static str, Role, &String)> {
+        let lists = [
+            ("admins", Role::Admin, &self.admins),
+            ("oracles", Role::Oracle, &self.oracles),
+            ("dealers", Role::Dealer, &self.dealers),
+            ("market_makers", Role::MarketMaker, &self.market_makers),
+            ("moderators", Role::Moderator, &self.moderators),
+        ];
+        lists.into_iter()
+            .flat_map(|(name, role, keys)| keys.iter().map(move |key| (name, role, key)))
+            .collect()
+    }
+
+    pub fn bootstrap(&self) -> Vec<(Role, String)> {
+        self.entries().into_iter().map(|(_, role, key)| (role, key.clone())).collect()
+    }
+}
+
+/// Hash-chained log of privileged actions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditConfig {
+    /// NDJSON file entries are appended to
+    pub store_path: String,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self { store_path: DEFAULT_AUDIT_PATH.to_string() }
+    }
+}
+
+/// L1 bridge: deposit trust anchors, withdrawal batching and the relayer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BridgeConfig {
+    /// Where in-flight bridges survive restarts
+    pub state_path: String,
+    /// Seconds between relayer retry/reconcile passes
+    pub relayer_interval_secs: u64,
+    /// Relayer Ed25519 public keys (hex) trusted to attest deposits
+    pub relayer_keys: Vec<String>,
+    /// Distinct relayer signatures a deposit needs
+    pub attestation_threshold: usize,
+    /// L1 roots trusted without signatures, as `slot=roothex`
+    pub trusted_l1_roots: Vec<String>,
+    pub batch_withdrawals: bool,
+    /// Withdrawals per batch
+    pub batch_max_size: usize,
+    /// Seconds before an open batch is sealed
+    pub batch_max_age_secs: u64,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            state_path: DEFAULT_BRIDGE_STATE_PATH.to_string(),
+            relayer_interval_secs: DEFAULT_RELAYER_INTERVAL_SECS,
+            relayer_keys: Vec::new(),
+            attestation_threshold: DEFAULT_ATTESTATION_THRESHOLD,
+            trusted_l1_roots: Vec::new(),
+            batch_withdrawals: true,
+            batch_max_size: DEFAULT_BATCH_MAX_SIZE,
+            batch_max_age_secs: DEFAULT_BATCH_MAX_AGE_SECS,
+        }
+    }
+}
+
+impl BridgeConfig {
+    pub fn deposit_verifier(&self) -> DepositVerifier {
+        let mut verifier = DepositVerifier::new(self.relayer_keys.clone(), self.attestation_threshold);
+        for (slot, root) in self.trusted_l1_roots.iter().filter_map(|e| parse_trusted_root(e)) {
+            verifier.pin_root(slot, root);
+        }
+        verifier
+    }
+
+    pub fn withdrawal_batcher(&self) -> WithdrawalBatcher {
+        let mut batcher = WithdrawalBatcher::new(self.batch_max_size, self.batch_max_age_secs);
+        batcher.enabled = self.batch_withdrawals;
+        batcher
+    }
+}
+
+/// L2 block production and signed state roots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SequencerConfig {
+    /// Hex Ed25519 seed state roots and session receipts are signed with.
+    /// Required unless `l1.mock_mode`; empty there means a random key per run
+    pub signing_key: String,
+    pub block_interval_secs: u64,
+    pub state_commit_interval_secs: u64,
+}
+
+impl Default for SequencerConfig {
+    fn default() -> Self {
+        Self {
+            signing_key: String::new(),
+            block_interval_secs: DEFAULT_BLOCK_INTERVAL_SECS,
+            state_commit_interval_secs: DEFAULT_STATE_COMMIT_INTERVAL_SECS,
+        }
+    }
+}
+
+impl SequencerConfig {
+    pub fn key(&self) -> SequencerKey {
+        SequencerKey::from_seed(parse_seed(&self.signing_key))
+    }
+
+    pub fn block_producer(&self) -> BlockProducer {
+        BlockProducer::new(self.block_interval_secs)
+    }
+
+    pub fn state_committer(&self) -> StateCommitter {
+        StateCommitter::new(self.state_commit_interval_secs)
+    }
+}
+
+/// Forced exits to L1
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExitConfig {
+    /// Seconds the L2 has to pay out an exit
+    pub deadline_secs: u64,
+    pub process_interval_secs: u64,
+}
+
+impl Default for ExitConfig {
+    fn default() -> Self {
+        Self { deadline_secs: DEFAULT_EXIT_DEADLINE_SECS, process_interval_secs: DEFAULT_EXIT_PROCESS_INTERVAL_SECS }
+    }
+}
+
+impl ExitConfig {
+    pub fn exit_manager(&self) -> ExitManager {
+        ExitManager::new(self.deadline_secs, self.process_interval_secs)
+    }
+}
+
+/// Bonded resolution proposals and the automatic price resolver
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResolutionConfig {
+    /// Seconds a proposal can be disputed
+    pub challenge_window_secs: u64,
+    /// Minimum BB bonded by a proposal or dispute
+    pub bond: f64,
+    /// Seconds between price resolver scans
+    pub resolver_interval_secs: u64,
+    /// Account the price resolver bonds its proposals from
+    pub resolver_address: String,
+    /// JSON price file the resolver reads instead of the public exchange
+    /// feeds (offline mode); empty uses the exchanges
+    pub oracle_local_feed: String,
+}
+
+impl Default for ResolutionConfig {
+    fn default() -> Self {
+        Self {
+            challenge_window_secs: DEFAULT_CHALLENGE_WINDOW_SECS,
+            bond: DEFAULT_RESOLUTION_BOND,
+            resolver_interval_secs: DEFAULT_RESOLVER_INTERVAL_SECS,
+            resolver_address: DEFAULT_RESOLVER_ADDRESS.to_string(),
+            oracle_local_feed: String::new(),
+        }
+    }
+}
+
+impl ResolutionConfig {
+    pub fn dispute_manager(&self) -> DisputeManager {
+        DisputeManager::new(self.challenge_window_secs, self.bond)
+    }
+
+    /// Price sources for the resolver: the local feed if one is set,
+    /// otherwise the public exchanges
+    pub fn oracle(&self) -> OracleManager {
+        if self.oracle_local_feed.is_empty() {
+            return OracleManager::new();
+        }
+        OracleManager::with_feeds(vec![DataFeedType::Local(LocalFeed::from_file(&self.oracle_local_feed))], 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionConfig {
+    /// Seconds between passes settling expired sessions to L1
+    pub settle_interval_secs: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self { settle_interval_secs: DEFAULT_SESSION_SETTLE_INTERVAL_SECS }
+    }
+}
+
+/// Inbound RSS/Atom sources and the outbound activity feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeedConfig {
+    /// Feeds polled for new events (URLs, files or directories)
+    pub sources: Vec<String>,
+    pub poll_interval_secs: u64,
+    /// Seconds before a remote feed request is abandoned
+    pub fetch_timeout_secs: u64,
+    /// Where seen feed items are remembered across restarts
+    pub processed_path: String,
+    /// Items kept in the outbound feed
+    pub max_items: usize,
+    /// Odds move (fraction) that publishes an item
+    pub odds_move_threshold: f64,
+    /// Public site URL used for links
+    pub site_url: String,
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            fetch_timeout_secs: DEFAULT_FETCH_TIMEOUT_SECS,
+            processed_path: DEFAULT_FEED_PROCESSED_PATH.to_string(),
+            max_items: DEFAULT_MAX_FEED_ITEMS,
+            odds_move_threshold: DEFAULT_ODDS_MOVE_THRESHOLD,
+            site_url: DEFAULT_SITE_URL.to_string(),
+        }
+    }
+}
+
+impl FeedConfig {
+    pub fn feed_manager(&self) -> RssFeedManager {
+        let mut manager = RssFeedManager::with_feeds(&self.sources, self.poll_interval_secs);
+        manager.http = feed_http_client(self.fetch_timeout_secs);
+        manager.processed_path = Some(PathBuf::from(&self.processed_path));
+        manager
+    }
+
+    pub fn publisher(&self) -> FeedPublisher {
+        let mut publisher = FeedPublisher::new();
+        publisher.max_items = self.max_items;
+        publisher.odds_move_threshold = self.odds_move_threshold;
+        publisher.site_url = self.site_url.clone();
+        publisher
+    }
+}
+
+/// Supabase sign-in and the development test accounts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Supabase project URL (empty disables Supabase sign-in)
+    pub supabase_url: String,
+    pub supabase_anon_key: String,
+    /// Whether the deterministic GodMode test accounts may sign
+    pub godmode_enabled: bool,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self { supabase_url: String::new(), supabase_anon_key: String::new(), godmode_enabled: true }
+    }
+}
+
+impl AuthConfig {
+    pub fn supabase(&self) -> SupabaseConfig {
+        SupabaseConfig { url: self.supabase_url.clone(), anon_key: self.supabase_anon_key.clone() }
+    }
+
+    pub fn godmode(&self) -> GodMode {
+        GodMode::with_enabled(self.godmode_enabled)
+    }
+}
+
+/// Per-market overrides; unset fields fall back to the exchange-wide values
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarketOverrides {
+    pub lp_fee_rate: Option<f64>,
+    pub maker_fee_rate: Option<f64>,
+    pub taker_fee_rate: Option<f64>,
+    pub min_clob_depth: Option<f64>,
+    pub max_spread_bps: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarketsConfig {
+    /// Liquidity a market needs to launch and stay viable
+    pub viability_threshold: f64,
+    /// BB seeded into a pool created on a market's first bet
+    pub auto_liquidity: f64,
+    /// Where markets, nonces and resolution proposals survive restarts
+    pub state_path: String,
+    /// Market `.rss` files: written on creation, loaded when there is no saved state
+    pub events_dir: String,
+    pub overrides: HashMap<String, MarketOverrides>,
+}
+
+impl Default for MarketsConfig {
+    fn default() -> Self {
+        Self {
+            viability_threshold: VIABILITY_THRESHOLD,
+            auto_liquidity: DEFAULT_AUTO_LIQUIDITY,
+            state_path: DEFAULT_STATE_PATH.to_string(),
+            events_dir: DEFAULT_EVENTS_DIR.to_string(),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+/// Fees and limits in effect for one market
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketParams {
+    pub lp_fee_rate: f64,
+    pub maker_fee_rate: f64,
+    pub taker_fee_rate: f64,
+    pub min_clob_depth: f64,
+    pub max_spread_bps: u64,
+}
+
+impl Default for MarketParams {
+    fn default() -> Self {
+        Config::default().market_params("")
+    }
+}
+
+// ============================================================================
+// CONFIG
+// ============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub l1: L1Config,
+    pub fees: FeeConfig,
+    pub orderbook: OrderBookConfig,
+    pub transactions: TransactionConfig,
+    pub markets: MarketsConfig,
+    pub idempotency: IdempotencyConfig,
+    pub rate_limit: RateLimitConfig,
+    pub roles: RolesConfig,
+    pub audit: AuditConfig,
+    pub bridge: BridgeConfig,
+    pub sequencer: SequencerConfig,
+    pub exits: ExitConfig,
+    pub resolution: ResolutionConfig,
+    pub sessions: SessionConfig,
+    pub feeds: FeedConfig,
+    pub auth: AuthConfig,
+    /// Layers that contributed, for the startup log
+    #[serde(skip)]
+    pub sources: Vec<String>,
+}
+
+impl Config {
+    /// Load from the config files and process environment, then validate
+    pub fn load() -> Result<Self, ConfigError> {
+        let explicit = std::env::var("BLACKBOOK_CONFIG").ok().map(PathBuf::from);
+        let base = explicit.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+
+        let mut layers = Vec::new();
+        if let Some(contents) = read_layer(&base, explicit.is_some())? {
+            layers.push((base.display().to_string(), contents));
+        }
+        if let Ok(profile) = std::env::var("BLACKBOOK_ENV") {
+            let stem = base.file_stem().and_then(|s| s.to_str()).unwrap_or("blackbook");
+            let path = base.with_file_name(format!("{}.{}.toml", stem, profile));
+            if let Some(contents) = read_layer(&path, false)? {
+                layers.push((path.display().to_string(), contents));
+            }
+        }
+        Self::from_layers(&layers, std::env::vars())
+    }
+
+    /// Build from TOML layers `(source name, contents)` and environment
+    /// variables, then validate
+    pub fn from_layers(
+        layers: &[(String, String)],
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let mut merged = serde_json::to_value(Config::default()).expect("default config serializes");
+        let mut sources = vec!["defaults".to_string()];
+
+        for (source, contents) in layers {
+            let doc: toml_edit::DocumentMut = contents.parse().map_err(|e: toml_edit::TomlError| ConfigError::Parse {
+                source: source.clone(),
+                error: e.to_string(),
+            })?;
+            let layer = table_to_json(doc.as_table()).map_err(|error| ConfigError::Parse {
+                source: source.clone(),
+                error,
+            })?;
+            merge(&mut merged, layer);
+            sources.push(source.clone());
+        }
+
+        let env: HashMap<String, String> = env.into_iter().collect();
+        let mut overridden = Vec::new();
+        for (var, key) in LEGACY_ENV {
+            if let Some(value) = env.get(*var) {
+                apply_env(&mut merged, var, key, value)?;
+                overridden.push(var.to_string());
+            }
+        }
+        let mut prefixed: Vec<_> = env.iter().filter(|(var, _)| var.starts_with(ENV_PREFIX)).collect();
+        prefixed.sort();
+        for (var, value) in prefixed {
+            let key = var[ENV_PREFIX.len()..].to_lowercase().replace("__", ".");
+            apply_env(&mut merged, var, &key, value)?;
+            overridden.push(var.clone());
+        }
+        if !overridden.is_empty() {
+            sources.push(format!("env ({})", overridden.join(", ")));
+        }
+
+        let mut config: Config = serde_json::from_value(merged).map_err(|e| ConfigError::Parse {
+            source: sources.join(" + "),
+            error: e.to_string(),
+        })?;
+        config.sources = sources;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check every value is usable, reporting all problems at once
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if self.server.port == 0 {
+            problems.push("server.port must be non-zero".to_string());
+        }
+        if self.server.host.parse::<std::net::IpAddr>().is_err() {
+            problems.push(format!("server.host {:?} is not an IP address", self.server.host));
+        }
+        let http = self.l1.rpc_url.starts_with("http://") || self.l1.rpc_url.starts_with("https://");
+        if !self.l1.mock_mode && !http {
+            problems.push(format!("l1.rpc_url {:?} must be an http(s) URL", self.l1.rpc_url));
+        }
+        if self.l1.timeout_secs == 0 {
+            problems.push("l1.timeout_secs must be positive".to_string());
+        }
+        if !(self.l1.mock_default_balance.is_finite() && self.l1.mock_default_balance >= 0.0) {
+            problems.push(format!("l1.mock_default_balance must be >= 0 (got {})", self.l1.mock_default_balance));
+        }
+        for entry in self.l1.mock_accounts.iter().filter(|e| parse_mock_account(e).is_none()) {
+            problems.push(format!("l1.mock_accounts entry {:?} is not address=balance", entry));
+        }
+        if self.transactions.expiry_secs == 0 {
+            problems.push("transactions.expiry_secs must be positive".to_string());
+        }
+        if self.idempotency.ttl_secs == 0 {
+            problems.push("idempotency.ttl_secs must be positive".to_string());
+        }
+        if self.idempotency.store_path.is_empty() {
+            problems.push("idempotency.store_path must not be empty".to_string());
+        }
+        if self.audit.store_path.is_empty() {
+            problems.push("audit.store_path must not be empty".to_string());
+        }
+        for (name, _, key) in self.roles.entries() {
+            if normalize_key(key).is_none() {
+                problems.push(format!("roles.{} entry {:?} is not an Ed25519 public key or L1_ address", name, key));
+            }
+        }
+        let classes = [("read", &self.rate_limit.read), ("trade", &self.rate_limit.trade), ("admin", &self.rate_limit.admin)];
+        for (class, limits) in classes {
+            for (scope, limit) in [("per_ip", limits.per_ip), ("per_wallet", limits.per_wallet)] {
+                if limit.burst == 0 || limit.per_minute == 0 {
+                    problems.push(format!("rate_limit.{}.{} burst and per_minute must be positive", class, scope));
+                }
+            }
+        }
+        if self.markets.state_path.is_empty() {
+            problems.push("markets.state_path must not be empty".to_string());
+        }
+        if self.markets.events_dir.is_empty() {
+            problems.push("markets.events_dir must not be empty".to_string());
+        }
+        if self.bridge.state_path.is_empty() {
+            problems.push("bridge.state_path must not be empty".to_string());
+        }
+        for entry in self.bridge.relayer_keys.iter().filter(|k| !is_pubkey_hex(k)) {
+            problems.push(format!("bridge.relayer_keys entry {:?} is not an Ed25519 public key", entry));
+        }
+        if self.bridge.attestation_threshold == 0 {
+            problems.push("bridge.attestation_threshold must be positive".to_string());
+        } else if !self.bridge.relayer_keys.is_empty() && self.bridge.attestation_threshold > self.bridge.relayer_keys.len() {
+            problems.push(format!(
+                "bridge.attestation_threshold {} exceeds the {} relayer key(s)",
+                self.bridge.attestation_threshold,
+                self.bridge.relayer_keys.len()
+            ));
+        }
+        for entry in self.bridge.trusted_l1_roots.iter().filter(|e| parse_trusted_root(e).is_none()) {
+            problems.push(format!("bridge.trusted_l1_roots entry {:?} is not slot=<32-byte root hex>", entry));
+        }
+        if self.bridge.batch_max_size == 0 {
+            problems.push("bridge.batch_max_size must be positive".to_string());
+        }
+        if self.sequencer.signing_key.is_empty() {
+            if !self.l1.mock_mode {
+                problems.push("sequencer.signing_key is required unless l1.mock_mode is set".to_string());
+            }
+        } else if parse_seed(&self.sequencer.signing_key).is_none() {
+            problems.push("sequencer.signing_key must be a 32-byte hex Ed25519 seed".to_string());
+        }
+        if self.resolution.challenge_window_secs == 0 {
+            problems.push("resolution.challenge_window_secs must be positive".to_string());
+        }
+        check_positive(&mut problems, "resolution.bond", self.resolution.bond);
+        if self.resolution.resolver_address.is_empty() {
+            problems.push("resolution.resolver_address must not be empty".to_string());
+        }
+        for (key, secs) in [
+            ("bridge.relayer_interval_secs", self.bridge.relayer_interval_secs),
+            ("bridge.batch_max_age_secs", self.bridge.batch_max_age_secs),
+            ("sequencer.block_interval_secs", self.sequencer.block_interval_secs),
+            ("sequencer.state_commit_interval_secs", self.sequencer.state_commit_interval_secs),
+            ("exits.deadline_secs", self.exits.deadline_secs),
+            ("exits.process_interval_secs", self.exits.process_interval_secs),
+            ("resolution.resolver_interval_secs", self.resolution.resolver_interval_secs),
+            ("sessions.settle_interval_secs", self.sessions.settle_interval_secs),
+            ("feeds.poll_interval_secs", self.feeds.poll_interval_secs),
+            ("feeds.fetch_timeout_secs", self.feeds.fetch_timeout_secs),
+        ] {
+            if secs == 0 {
+                problems.push(format!("{} must be positive", key));
+            }
+        }
+        if self.feeds.sources.iter().any(|s| s.trim().is_empty()) {
+            problems.push("feeds.sources entries must not be empty".to_string());
+        }
+        if self.feeds.processed_path.is_empty() {
+            problems.push("feeds.processed_path must not be empty".to_string());
+        }
+        if self.feeds.max_items == 0 {
+            problems.push("feeds.max_items must be positive".to_string());
+        }
+        let threshold = self.feeds.odds_move_threshold;
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            problems.push(format!("feeds.odds_move_threshold must be in (0, 1] (got {})", threshold));
+        }
+        if !(self.feeds.site_url.starts_with("http://") || self.feeds.site_url.starts_with("https://")) {
+            problems.push(format!("feeds.site_url {:?} must be an http(s) URL", self.feeds.site_url));
+        }
+        if !self.auth.supabase_url.is_empty() {
+            if !(self.auth.supabase_url.starts_with("http://") || self.auth.supabase_url.starts_with("https://")) {
+                problems.push(format!("auth.supabase_url {:?} must be an http(s) URL", self.auth.supabase_url));
+            }
+            if self.auth.supabase_anon_key.is_empty() {
+                problems.push("auth.supabase_anon_key is required when auth.supabase_url is set".to_string());
+            }
+        }
+        check_positive(&mut problems, "markets.viability_threshold", self.markets.viability_threshold);
+        check_positive(&mut problems, "markets.auto_liquidity", self.markets.auto_liquidity);
+
+        let exchange_wide = MarketOverrides {
+            lp_fee_rate: Some(self.fees.lp_fee_rate),
+            maker_fee_rate: Some(self.fees.maker_fee_rate),
+            taker_fee_rate: Some(self.fees.taker_fee_rate),
+            min_clob_depth: Some(self.orderbook.min_clob_depth),
+            max_spread_bps: Some(self.orderbook.max_spread_bps),
+        };
+        check_market_values(&mut problems, &exchange_wide, |key| {
+            let section = if key.ends_with("fee_rate") { "fees" } else { "orderbook" };
+            format!("{}.{}", section, key)
+        });
+        let mut market_ids: Vec<_> = self.markets.overrides.keys().collect();
+        market_ids.sort();
+        for market_id in market_ids {
+            check_market_values(&mut problems, &self.markets.overrides[market_id], |key| {
+                format!("markets.overrides.{}.{}", market_id, key)
+            });
+        }
+
+        if problems.is_empty() { Ok(()) } else { Err(ConfigError::Invalid(problems)) }
+    }
+
+    /// Fees and limits for `market_id`, with its overrides applied
+    pub fn market_params(&self, market_id: &str) -> MarketParams {
+        let overrides = self.markets.overrides.get(market_id).cloned().unwrap_or_default();
+        MarketParams {
+            lp_fee_rate: overrides.lp_fee_rate.unwrap_or(self.fees.lp_fee_rate),
+            maker_fee_rate: overrides.maker_fee_rate.unwrap_or(self.fees.maker_fee_rate),
+            taker_fee_rate: overrides.taker_fee_rate.unwrap_or(self.fees.taker_fee_rate),
+            min_clob_depth: overrides.min_clob_depth.unwrap_or(self.orderbook.min_clob_depth),
+            max_spread_bps: overrides.max_spread_bps.unwrap_or(self.orderbook.max_spread_bps),
+        }
+    }
+
+    pub fn listen_addr(&self) -> std::net::SocketAddr {
+        let host = self.server.host.parse().unwrap_or(std::net::IpAddr::from([0, 0, 0, 0]));
+        std::net::SocketAddr::new(host, self.server.port)
+    }
+}
+
+fn check_positive(problems: &mut Vec<String>, key: &str, value: f64) {
+    if !(value.is_finite() && value > 0.0) {
+        problems.push(format!("{} must be positive (got {})", key, value));
+    }
+}
+
+/// An `address=balance` mock L1 account
+fn parse_mock_account(entry: &str) -> Option<(String, f64)> {
+    let (address, balance) = entry.split_once('=')?;
+    let balance: f64 = balance.trim().parse().ok()?;
+    let address = address.trim();
+    (!address.is_empty() && balance.is_finite() && balance >= 0.0).then(|| (address.to_string(), balance))
+}
+
+/// A `slot=roothex` pinned L1 root
+fn parse_trusted_root(entry: &str) -> Option<(u64, Vec<u8>)> {
+    let (slot, root) = entry.split_once('=')?;
+    let root = hex::decode(root.trim()).ok().filter(|r| r.len() == 32)?;
+    Some((slot.trim().parse().ok()?, root))
+}
+
+/// A hex Ed25519 seed
+fn parse_seed(hex_seed: &str) -> Option<[u8; 32]> {
+    hex::decode(hex_seed.trim()).ok().and_then(|b| <[u8; 32]>::try_from(b).ok())
+}
+
+fn is_pubkey_hex(key: &str) -> bool {
+    parse_seed(key).is_some_and(|bytes| ed25519_dalek::VerifyingKey::from_bytes(&bytes).is_ok())
+}
+
+/// Check the fee and order book values that are set; `name` maps a field
+/// to its config key for the message
+fn check_market_values(problems: &mut Vec<String>, values: &MarketOverrides, name: impl Fn(&str) -> String) {
+    for (key, rate) in [
+        ("lp_fee_rate", values.lp_fee_rate),
+        ("maker_fee_rate", values.maker_fee_rate),
+        ("taker_fee_rate", values.taker_fee_rate),
+    ] {
+        if let Some(rate) = rate.filter(|r| !(0.0..1.0).contains(r)) {
+            problems.push(format!("{} must be in [0, 1) (got {})", name(key), rate));
+        }
+    }
+    if let Some(depth) = values.min_clob_depth.filter(|d| !(d.is_finite() && *d >= 0.0)) {
+        problems.push(format!("{} must be >= 0 (got {})", name("min_clob_depth"), depth));
+    }
+    if let Some(spread) = values.max_spread_bps.filter(|s| !(1..=99).contains(s)) {
+        problems.push(format!("{} must be 1-99 (got {})", name("max_spread_bps"), spread));
+    }
+}
+
+// ============================================================================
+// LAYERING
+// ============================================================================
+
+/// Contents of a config file; a missing optional file is skipped
+fn read_layer(path: &Path, required: bool) -> Result<Option<String>, ConfigError> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => Ok(None),
+        Err(e) => Err(ConfigError::Io { path: path.display().to_string(), error: e.to_string() }),
+    }
+}
+
+fn table_to_json(table: &toml_edit::Table) -> Result<Value, String> {
+    let mut object = Map::new();
+    for (key, item) in table.iter() {
+        object.insert(key.to_string(), item_to_json(item)?);
+    }
+    Ok(Value::Object(object))
+}
+
+fn item_to_json(item: &toml_edit::Item) -> Result<Value, String> {
+    match item {
+        toml_edit::Item::None => Ok(Value::Null),
+        toml_edit::Item::Value(value) => value_to_json(value),
+        toml_edit::Item::Table(table) => table_to_json(table),
+        toml_edit::Item::ArrayOfTables(tables) => tables.iter().map(table_to_json).collect::<Result<_, _>>().map(Value::Array),
+    }
+}
+
+fn value_to_json(value: &toml_edit::Value) -> Result<Value, String> {
+    use toml_edit::Value as Toml;
+    Ok(match value {
+        Toml::String(s) => Value::String(s.value().clone()),
+        Toml::Integer(i) => Value::from(*i.value()),
+        Toml::Float(f) => serde_json::Number::from_f64(*f.value())
+            .map(Value::Number)
+            .ok_or_else(|| format!("{} is not a finite number", f.value()))?,
+        Toml::Boolean(b) => Value::Bool(*b.value()),
+        Toml::Datetime(d) => Value::String(d.value().to_string()),
+        Toml::Array(array) => Value::Array(array.iter().map(value_to_json).collect::<Result<_, _>>()?),
+        Toml::InlineTable(table) => {
+            let mut object = Map::new();
+            for (key, value) in table.iter() {
+                object.insert(key.to_string(), value_to_json(value)?);
+            }
+            Value::Object(object)
+        }
+    })
+}
+
+/// Deep-merge `layer` into `base`; tables merge, everything else replaces
+fn merge(base: &mut Value, layer: Value) {
+    match (base, layer) {
+        (Value::Object(base), Value::Object(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+/// Set the scalar at dotted `key` from an env string, typed like the
+/// value already there
+fn apply_env(merged: &mut Value, var: &str, key: &str, raw: &str) -> Result<(), ConfigError> {
+    let slot = key.split('.')
+        .try_fold(merged, |value, part| value.get_mut(part))
+        .filter(|slot| !slot.is_object())
+        .ok_or_else(|| ConfigError::UnknownKey { var: var.to_string() })?;
+    let invalid = || ConfigError::InvalidEnv { var: var.to_string(), value: raw.to_string() };
+
+    *slot = match slot {
+        Value::Bool(_) => Value::Bool(match raw.to_lowercase().as_str() {
+            "1" | "true" | "yes" => true,
+            "0" | "false" | "no" => false,
+            _ => return Err(invalid()),
+        }),
+        Value::Number(_) => match raw.parse::<u64>() {
+            Ok(n) => Value::from(n),
+            Err(_) => raw.parse::<f64>().ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(invalid)?,
+        },
+        // Lists are comma separated
+        Value::Array(_) => Value::Array(
+            raw.split(',').map(str::trim).filter(|s| !s.is_empty()).map(|s| Value::String(s.to_string())).collect(),
+        ),
+        _ => Value::String(raw.to_string()),
+    };
+    Ok(())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(source: &str, contents: &str) -> (String, String) {
+        (source.to_string(), contents.to_string())
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    /// Against a real L1 a config only validates with a sequencer key
+    const SEQUENCER_KEY: (&str, &str) = ("SEQUENCER_SIGNING_KEY", "1111111111111111111111111111111111111111111111111111111111111111");
+
+    #[test]
+    fn test_defaults_match_constants() {
+        let config = Config::from_layers(&[], env(&[SEQUENCER_KEY])).unwrap();
+        assert_eq!(config.server.port, 1234);
+        assert_eq!(config.fees.taker_fee_rate, TAKER_FEE_RATE);
+        assert_eq!(config.transactions.expiry_secs, TX_EXPIRY_SECS);
+        assert_eq!(config.markets.viability_threshold, VIABILITY_THRESHOLD);
+        assert_eq!(config.market_params("any"), MarketParams::default());
+
+        // The shipped example documents the defaults
+        let example = layer(DEFAULT_CONFIG_PATH, include_str!("../config/blackbook.toml"));
+        let shipped = Config::from_layers(&[example], env(&[SEQUENCER_KEY])).unwrap();
+        assert_eq!(Config { sources: vec![], ..shipped }, Config { sources: vec![], ..config });
+    }
+
+    #[test]
+    fn test_layers_and_env_override_in_order() {
+        let base = layer("base.toml", r#"
+            [server]
+            port = 9000
+
+            [fees]
+            maker_fee_rate = 0.002
+            taker_fee_rate = 0.006
+
+            [markets.overrides."market_btc"]
+            taker_fee_rate = 0.001
+            max_spread_bps = 5
+        "#);
+        let production = layer("base.production.toml", "[fees]\ntaker_fee_rate = 0.004\n");
+        let config = Config::from_layers(&[base, production], env(&[
+            ("PORT", "10000"),
+            ("BLACKBOOK__SERVER__PORT", "8080"),
+            ("BLACKBOOK__L1__MOCK_MODE", "true"),
+            ("BLACKBOOK__RATE_LIMIT__EXEMPT_KEYS", "mm-one, mm-two"),
+            ("BLACKBOOK__RATE_LIMIT__TRADE__PER_WALLET__BURST", "5"),
+            ("BRIDGE_BATCH_MAX_SIZE", "25"),
+            ("RSS_FEEDS", "feeds/a.xml, https://example.com/rss"),
+            ("NEXT_PUBLIC_SUPABASE_URL", "https://old.supabase.co"),
+            ("SUPABASE_URL", "https://project.supabase.co"),
+            ("SUPABASE_ANON_KEY", "anon"),
+            ("GODMODE_ENABLED", "false"),
+            ("UNRELATED", "x"),
+        ])).unwrap();
+
+        assert_eq!(config.server.port, 8080);
+        assert!(config.l1.mock_mode);
+        assert_eq!(config.fees.maker_fee_rate, 0.002);
+        assert_eq!(config.fees.taker_fee_rate, 0.004);
+        assert_eq!(config.rate_limit.exempt_keys, vec!["mm-one", "mm-two"]);
+        assert_eq!(config.rate_limit.trade.per_wallet.burst, 5);
+        assert_eq!(config.bridge.withdrawal_batcher().max_size, 25);
+        assert_eq!(config.feeds.feed_manager().feed_urls, vec!["feeds/a.xml", "https://example.com/rss"]);
+        assert_eq!(config.auth.supabase().url, "https://project.supabase.co");
+        assert_eq!(config.auth.supabase().anon_key, "anon");
+        assert!(!config.auth.godmode().enabled);
+
+        let btc = config.market_params("market_btc");
+        assert_eq!(btc.taker_fee_rate, 0.001);
+        assert_eq!(btc.maker_fee_rate, 0.002);
+        assert_eq!(btc.max_spread_bps, 5);
+        assert_eq!(config.market_params("market_eth").taker_fee_rate, 0.004);
+    }
+
+    #[test]
+    fn test_rejects_unknown_keys_and_bad_env() {
+        let typo = Config::from_layers(&[layer("a.toml", "[fees]\ntaker_fee = 0.1\n")], env(&[]));
+        assert!(matches!(typo, Err(ConfigError::Parse { .. })));
+
+        let unknown = Config::from_layers(&[], env(&[("BLACKBOOK__FEES__NOPE", "1")]));
+        assert_eq!(unknown, Err(ConfigError::UnknownKey { var: "BLACKBOOK__FEES__NOPE".to_string() }));
+
+        let bad = Config::from_layers(&[], env(&[("PORT", "http")]));
+        assert!(matches!(bad, Err(ConfigError::InvalidEnv { .. })));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let err = Config::from_layers(&[layer("a.toml", r#"
+            [fees]
+            lp_fee_rate = 1.5
+
+            [markets.overrides."market_btc"]
+            max_spread_bps = 0
+        "#)], env(&[("BLACKBOOK__TRANSACTIONS__EXPIRY_SECS", "0"), SEQUENCER_KEY])).unwrap_err();
+
+        let ConfigError::Invalid(problems) = err else { panic!("expected validation error") };
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.starts_with("fees.lp_fee_rate")));
+        assert!(problems.iter().any(|p| p.starts_with("markets.overrides.market_btc.max_spread_bps")));
+        assert!(problems.iter().any(|p| p.starts_with("transactions.expiry_secs")));
+    }
+
+    #[test]
+    fn test_subsystem_settings_validate_at_startup() {
+        let root = "ab".repeat(32);
+        let pinned = format!("7={}", root);
+        let config = Config::from_layers(&[], env(&[
+            ("BRIDGE_TRUSTED_L1_ROOTS", pinned.as_str()),
+            ("L1_MOCK_MODE", "true"),
+            ("L1_MOCK_ACCOUNTS", "L1_ALICE=250"),
+            ("ORACLE_LOCAL_FEED", "data/prices.json"),
+        ])).unwrap();
+        assert_eq!(config.bridge.deposit_verifier().trusted_root(7), Some(hex::decode(&root).unwrap().as_slice()));
+        assert_eq!(config.l1.mock_l1().balances.get("L1_ALICE"), Some(&250.0));
+        assert_eq!(config.resolution.oracle_local_feed, "data/prices.json");
+        assert_eq!(config.resolution.resolver_address, DEFAULT_RESOLVER_ADDRESS);
+
+        let err = Config::from_layers(&[], env(&[
+            ("BRIDGE_RELAYER_KEYS", "not-a-key"),
+            ("BRIDGE_TRUSTED_L1_ROOTS", "7=zz"),
+            ("SEQUENCER_SIGNING_KEY", "1234"),
+            ("RESOLUTION_BOND_BB", "0"),
+            ("BLACKBOOK__RESOLUTION__RESOLVER_ADDRESS", ""),
+            ("SESSION_SETTLE_INTERVAL_SECS", "0"),
+            ("FEED_SITE_URL", "blackbook.market"),
+            ("SUPABASE_URL", "https://project.supabase.co"),
+        ])).unwrap_err();
+        let ConfigError::Invalid(problems) = err else { panic!("expected validation error") };
+        for key in [
+            "bridge.relayer_keys",
+            "bridge.trusted_l1_roots",
+            "sequencer.signing_key",
+            "resolution.bond",
+            "resolution.resolver_address",
+            "sessions.settle_interval_secs",
+            "feeds.site_url",
+            "auth.supabase_anon_key",
+        ] {
+            assert!(problems.iter().any(|p| p.starts_with(key)), "{} not reported in {:?}", key, problems);
+        }
+
+        // State roots and session receipts are never signed with a well-known key
+        let err = Config::from_layers(&[], env(&[])).unwrap_err();
+        assert_eq!(err, ConfigError::Invalid(vec!["sequencer.signing_key is required unless l1.mock_mode is set".to_string()]));
+        let config = Config::from_layers(&[], env(&[SEQUENCER_KEY])).unwrap();
+        assert_eq!(config.sequencer.key().pubkey_hex(), SequencerKey::from_seed(Some([0x11; 32])).pubkey_hex());
+
+        // Values that used to be silently replaced by defaults now refuse to load
+        let bad = Config::from_layers(&[], env(&[("BLOCK_INTERVAL_SECS", "fast")]));
+        assert!(matches!(bad, Err(ConfigError::InvalidEnv { .. })));
+    }
+}
@@ -11,6 +11,8 @@ use sha2::{Sha256, Digest};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::audit_log::{AuditEvent, AuditLog};
+
 // ============================================================================
 // CONSTANTS
 // ============================================================================
@@ -155,6 +157,9 @@ pub struct GodMode {
     
     /// Map of address -> account name for reverse lookup
     pub address_to_name: HashMap<String, String>,
+    
+    /// Where admin operations are recorded (None: not recorded)
+    pub audit_log: Option<AuditLog>,
 }
 
 impl GodMode {
@@ -179,23 +184,18 @@ impl GodMode {
         // Create admin account (separate from test accounts)
         let admin = TestAccount::from_seed(GODMODE_SEED, "GODMODE_ADMIN", 0.0);
         
-        // Check environment for enabled flag
-        let enabled = std::env::var("GODMODE_ENABLED")
-            .map(|v| v.to_lowercase() == "true" || v == "1")
-            .unwrap_or(true); // Default to enabled for development
-        
         GodMode {
-            enabled,
+            enabled: true, // Enabled for development; `auth.godmode_enabled` turns it off
             admin,
             test_accounts,
             address_to_name,
+            audit_log: None,
         }
     }
     
-    /// Create from environment (checks GODMODE_SEED env var)
-    pub fn from_env() -> Self {
-        // Could customize seed from env, but for now use default
-        Self::new()
+    /// Create with the test accounts enabled or disabled (`auth.godmode_enabled`)
+    pub fn with_enabled(enabled: bool) -> Self {
+        GodMode { enabled, ..Self::new() }
     }
     
     /// Get a test account by name (case-insensitive)
@@ -281,11 +281,13 @@ impl GodMode {
             return Err(GodModeError::InvalidAmount(amount));
         }
         
-        Ok(MintOperation {
+        let op = MintOperation {
             to_address: address.to_string(),
             amount,
             operation: "mint".to_string(),
-        })
+        };
+        self.audit("GODMODE_MINT", &format!("Mint {} BB to {}", amount, address), &op)?;
+        Ok(op)
     }
     
     /// Burn tokens from an address (god mode operation)
@@ -297,11 +299,13 @@ impl GodMode {
             return Err(GodModeError::InvalidAmount(amount));
         }
         
-        Ok(BurnOperation {
+        let op = BurnOperation {
             from_address: address.to_string(),
             amount,
             operation: "burn".to_string(),
-        })
+        };
+        self.audit("GODMODE_BURN", &format!("Burn {} BB from {}", amount, address), &op)?;
+        Ok(op)
     }
     
     /// Set exact balance for an address (god mode operation)
@@ -313,11 +317,13 @@ impl GodMode {
             return Err(GodModeError::InvalidAmount(balance));
         }
         
-        Ok(SetBalanceOperation {
+        let op = SetBalanceOperation {
             address: address.to_string(),
             new_balance: balance,
             operation: "set_balance".to_string(),
-        })
+        };
+        self.audit("GODMODE_SET_BALANCE", &format!("Set balance of {} to {} BB", address, balance), &op)?;
+        Ok(op)
     }
     
     /// Airdrop tokens to multiple addresses
@@ -329,12 +335,35 @@ impl GodMode {
             return Err(GodModeError::InvalidAmount(amount));
         }
         
-        Ok(AirdropOperation {
+        let op = AirdropOperation {
             addresses: addresses.iter().map(|s| s.to_string()).collect(),
             amount_each: amount,
             total_amount: amount * addresses.len() as f64,
             operation: "airdrop".to_string(),
-        })
+        };
+        self.audit("GODMODE_AIRDROP", &format!("Airdrop {} BB to {} addresses", amount, addresses.len()), &op)?;
+        Ok(op)
+    }
+    
+    /// Record an operation (as the admin account) before it is handed out
+    /// to be applied; fails if the audit log cannot be written
+    fn audit<T: Serialize>(&self, action: &str, details: &str, op: &T) -> Result<(), GodModeError> {
+        let Some(log) = &self.audit_log else {
+            return Ok(());
+        };
+        let event = AuditEvent {
+            actor: self.admin.address.clone(),
+            action: action.to_string(),
+            details: details.to_string(),
+            request: None,
+            before: serde_json::Value::Null,
+            after: serde_json::to_value(op).unwrap_or_default(),
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        log.append(event, now).map(|_| ()).map_err(GodModeError::AuditFailed)
     }
     
     /// Sign a message as admin (for testing/development)
@@ -403,6 +432,7 @@ pub enum GodModeError {
     AccountNotFound(String),
     InvalidSignature,
     Unauthorized,
+    AuditFailed(String),
 }
 
 impl std::fmt::Display for GodModeError {
@@ -413,6 +443,7 @@ impl std::fmt::Display for GodModeError {
             GodModeError::AccountNotFound(name) => write!(f, "Account not found: {}", name),
             GodModeError::InvalidSignature => write!(f, "Invalid signature"),
             GodModeError::Unauthorized => write!(f, "Unauthorized operation"),
+            GodModeError::AuditFailed(e) => write!(f, "Could not record operation in the audit log: {}", e),
         }
     }
 }
@@ -623,6 +654,27 @@ mod tests {
         assert!(gm.mint(&alice.address, 0.0).is_err());
     }
     
+    #[test]
+    fn test_operations_are_audited() {
+        let mut gm = GodMode::new();
+        let log = AuditLog::default();
+        gm.audit_log = Some(log.clone());
+        let alice = gm.get_account("ALICE").unwrap().address.clone();
+        
+        gm.mint(&alice, 500.0).unwrap();
+        gm.set_balance(&alice, 20.0).unwrap();
+        assert!(gm.mint(&alice, -1.0).is_err());
+        
+        // Rejected operations are not recorded; the rest chain and verify
+        assert_eq!(log.head().0, 2);
+        assert!(log.verify().unwrap().valid);
+        let ndjson = log.export_ndjson().unwrap();
+        let first: crate::audit_log::AuditEntry = serde_json::from_str(ndjson.lines().next().unwrap()).unwrap();
+        assert_eq!(first.action, "GODMODE_MINT");
+        assert_eq!(first.actor, gm.admin_address());
+        assert_eq!(first.after["amount"], 500.0);
+    }
+    
     #[test]
     fn test_airdrop_operation() {
         let gm = GodMode::new();
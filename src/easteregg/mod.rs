@@ -4,9 +4,12 @@
 //
 // This module contains special features and test utilities:
 //   - godmode: Test accounts and admin utilities for development
+//   - oracle: Price data feeds and automated price-market resolution
 //
 // ============================================================================
 
 pub mod godmode;
+pub mod oracle;
 
 pub use godmode::*;
+pub use oracle::*;
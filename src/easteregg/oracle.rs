@@ -4,6 +4,7 @@
 //
 // This module provides oracle infrastructure for prediction markets:
 //   - Multi-source price feeds (CoinGecko, Binance, Coinbase)
+//   - Local/file-backed price feed for offline testing
 //   - Data validation (require 2+ sources to agree)
 //   - Smart caching to reduce API calls
 //   - Automated market resolution from external data
@@ -85,6 +86,7 @@ pub enum DataFeedType {
     CoinGecko(CoinGeckoFeed),
     Binance(BinanceFeed),
     Coinbase(CoinbaseFeed),
+    Local(LocalFeed),
 }
 
 #[async_trait::async_trait]
@@ -94,6 +96,7 @@ impl DataFeed for DataFeedType {
             DataFeedType::CoinGecko(feed) => feed.fetch_price(asset).await,
             DataFeedType::Binance(feed) => feed.fetch_price(asset).await,
             DataFeedType::Coinbase(feed) => feed.fetch_price(asset).await,
+            DataFeedType::Local(feed) => feed.fetch_price(asset).await,
         }
     }
     
@@ -102,6 +105,7 @@ impl DataFeed for DataFeedType {
             DataFeedType::CoinGecko(feed) => feed.source_name(),
             DataFeedType::Binance(feed) => feed.source_name(),
             DataFeedType::Coinbase(feed) => feed.source_name(),
+            DataFeedType::Local(feed) => feed.source_name(),
         }
     }
     
@@ -110,6 +114,7 @@ impl DataFeed for DataFeedType {
             DataFeedType::CoinGecko(feed) => feed.reliability(),
             DataFeedType::Binance(feed) => feed.reliability(),
             DataFeedType::Coinbase(feed) => feed.reliability(),
+            DataFeedType::Local(feed) => feed.reliability(),
         }
     }
 }
@@ -146,6 +151,12 @@ impl CoinGeckoFeed {
     }
 }
 
+impl Default for CoinGeckoFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait::async_trait]
 impl DataFeed for CoinGeckoFeed {
     async fn fetch_price(&self, asset: &str) -> Result<PriceData, String> {
@@ -158,8 +169,11 @@ impl DataFeed for CoinGeckoFeed {
         );
         
         let client = reqwest::Client::new();
-        let resp = client
-            .get(&url)
+        let mut request = client.get(&url);
+        if let Some(key) = &self.api_key {
+            request = request.header("x-cg-demo-api-key", key);
+        }
+        let resp = request
             .send()
             .await
             .map_err(|e| format!("CoinGecko request failed: {}", e))?;
@@ -220,6 +234,12 @@ impl BinanceFeed {
     }
 }
 
+impl Default for BinanceFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait::async_trait]
 impl DataFeed for BinanceFeed {
     async fn fetch_price(&self, asset: &str) -> Result<PriceData, String> {
@@ -293,6 +313,12 @@ impl CoinbaseFeed {
     }
 }
 
+impl Default for CoinbaseFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait::async_trait]
 impl DataFeed for CoinbaseFeed {
     async fn fetch_price(&self, asset: &str) -> Result<PriceData, String> {
@@ -339,6 +365,79 @@ impl DataFeed for CoinbaseFeed {
     }
 }
 
+// ============================================================================
+// LOCAL / FILE-BACKED IMPLEMENTATION
+// ============================================================================
+
+/// Offline price feed backed by a JSON file (`{"BTC": 100000.0, ...}`)
+/// or an in-memory price table. The file is re-read on every fetch so
+/// prices can be updated while the server is running.
+pub struct LocalFeed {
+    path: Option<std::path::PathBuf>,
+    prices: HashMap<String, f64>,
+}
+
+impl LocalFeed {
+    /// Feed that reads prices from a JSON file on each request
+    pub fn from_file(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            prices: HashMap::new(),
+        }
+    }
+    
+    /// Feed with a fixed in-memory price table
+    pub fn with_prices(prices: HashMap<String, f64>) -> Self {
+        Self {
+            path: None,
+            prices: prices.into_iter().map(|(k, v)| (k.to_uppercase(), v)).collect(),
+        }
+    }
+    
+    fn load_prices(&self) -> Result<HashMap<String, f64>, String> {
+        let Some(path) = &self.path else {
+            return Ok(self.prices.clone());
+        };
+        
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Local feed read failed ({}): {}", path.display(), e))?;
+        let prices: HashMap<String, f64> = serde_json::from_str(&json)
+            .map_err(|e| format!("Local feed parse failed ({}): {}", path.display(), e))?;
+        
+        Ok(prices.into_iter().map(|(k, v)| (k.to_uppercase(), v)).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl DataFeed for LocalFeed {
+    async fn fetch_price(&self, asset: &str) -> Result<PriceData, String> {
+        let asset = asset.to_uppercase();
+        let price = self.load_prices()?
+            .get(&asset)
+            .copied()
+            .ok_or_else(|| format!("Unknown asset for local feed: {}", asset))?;
+        
+        Ok(PriceData {
+            source: "Local".to_string(),
+            asset,
+            price,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            confidence: 1.0,
+        })
+    }
+    
+    fn source_name(&self) -> &str {
+        "Local"
+    }
+    
+    fn reliability(&self) -> f64 {
+        1.0
+    }
+}
+
 // ============================================================================
 // ORACLE MANAGER
 // ============================================================================
@@ -355,12 +454,12 @@ pub struct OracleManager {
 impl OracleManager {
     /// Create new oracle manager with default settings
     pub fn new() -> Self {
-        let mut feeds: Vec<DataFeedType> = Vec::new();
-        
         // Add all available data feeds
-        feeds.push(DataFeedType::CoinGecko(CoinGeckoFeed::new()));
-        feeds.push(DataFeedType::Binance(BinanceFeed::new()));
-        feeds.push(DataFeedType::Coinbase(CoinbaseFeed::new()));
+        let feeds: Vec<DataFeedType> = vec![
+            DataFeedType::CoinGecko(CoinGeckoFeed::new()),
+            DataFeedType::Binance(BinanceFeed::new()),
+            DataFeedType::Coinbase(CoinbaseFeed::new()),
+        ];
         
         Self {
             feeds,
@@ -371,6 +470,17 @@ impl OracleManager {
         }
     }
     
+    /// Create oracle manager with a custom set of feeds
+    pub fn with_feeds(feeds: Vec<DataFeedType>, min_sources_required: usize) -> Self {
+        Self {
+            feeds,
+            cache: HashMap::new(),
+            cache_duration_secs: 60,
+            min_sources_required,
+            max_variance_percent: 5.0,
+        }
+    }
+    
     /// Get consensus price from multiple oracle sources
     /// 
     /// Returns error if sources disagree too much or insufficient sources available
@@ -544,6 +654,12 @@ impl OracleManager {
     }
 }
 
+impl Default for OracleManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ============================================================================
 // PRICE CONDITIONS
 // ============================================================================
@@ -557,6 +673,58 @@ pub enum PriceCondition {
     Between(f64, f64), // min, max
 }
 
+/// Price-threshold rule extracted from a market's resolution conditions
+/// 
+/// Expected keys: `asset`, `target_price`, `condition`
+/// (`>`, `>=`, `<`, `<=`, `between`), plus `min_price`/`max_price` for `between`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceRule {
+    pub asset: String,
+    pub target_price: f64,
+    pub condition: PriceCondition,
+}
+
+impl PriceRule {
+    /// Parse a price rule from `ResolutionRules.conditions`
+    pub fn from_conditions(conditions: &HashMap<String, String>) -> Result<Self, String> {
+        let get_f64 = |key: &str| -> Result<f64, String> {
+            conditions.get(key)
+                .ok_or_else(|| format!("Missing condition '{}'", key))?
+                .trim()
+                .trim_start_matches('$')
+                .replace(',', "")
+                .parse::<f64>()
+                .map_err(|e| format!("Invalid '{}': {}", key, e))
+        };
+        
+        let asset = conditions.get("asset")
+            .ok_or_else(|| "Missing condition 'asset'".to_string())?
+            .to_uppercase();
+        
+        let op = conditions.get("condition").map(|c| c.trim().to_lowercase())
+            .unwrap_or_else(|| ">=".to_string());
+        
+        let condition = match op.as_str() {
+            ">" | "gt" | "above" => PriceCondition::GreaterThan,
+            ">=" | "gte" => PriceCondition::GreaterThanOrEqual,
+            "<" | "lt" | "below" => PriceCondition::LessThan,
+            "<=" | "lte" => PriceCondition::LessThanOrEqual,
+            "between" => PriceCondition::Between(get_f64("min_price")?, get_f64("max_price")?),
+            other => return Err(format!("Unknown price condition '{}'", other)),
+        };
+        
+        let target_price = match condition {
+            PriceCondition::Between(min, _) => conditions.get("target_price")
+                .map(|_| get_f64("target_price"))
+                .transpose()?
+                .unwrap_or(min),
+            _ => get_f64("target_price")?,
+        };
+        
+        Ok(Self { asset, target_price, condition })
+    }
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -565,6 +733,59 @@ pub enum PriceCondition {
 mod tests {
     use super::*;
     
+    fn local_oracle(price: f64) -> OracleManager {
+        let mut prices = HashMap::new();
+        prices.insert("BTC".to_string(), price);
+        OracleManager::with_feeds(vec![DataFeedType::Local(LocalFeed::with_prices(prices))], 1)
+    }
+    
+    #[tokio::test]
+    async fn test_local_feed_resolves_price_market() {
+        let mut oracle = local_oracle(105_000.0);
+        let resolution = oracle
+            .resolve_price_market("btc_100k", "btc", 100_000.0, PriceCondition::GreaterThanOrEqual)
+            .await
+            .unwrap();
+        assert_eq!(resolution.winning_outcome, 0);
+        assert_eq!(resolution.data_sources, vec!["Local".to_string()]);
+        
+        let mut oracle = local_oracle(95_000.0);
+        let resolution = oracle
+            .resolve_price_market("btc_100k", "BTC", 100_000.0, PriceCondition::GreaterThanOrEqual)
+            .await
+            .unwrap();
+        assert_eq!(resolution.winning_outcome, 1);
+    }
+    
+    #[tokio::test]
+    async fn test_local_feed_reads_file() {
+        let path = std::env::temp_dir().join(format!("oracle_feed_{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"eth": 4200.5}"#).unwrap();
+        
+        let feed = LocalFeed::from_file(&path);
+        let price = feed.fetch_price("ETH").await.unwrap();
+        assert_eq!(price.price, 4200.5);
+        assert!(feed.fetch_price("SOL").await.is_err());
+        
+        std::fs::remove_file(&path).ok();
+    }
+    
+    #[test]
+    fn test_price_rule_from_conditions() {
+        let mut conditions = HashMap::new();
+        conditions.insert("asset".to_string(), "btc".to_string());
+        conditions.insert("target_price".to_string(), "$100,000".to_string());
+        conditions.insert("condition".to_string(), ">".to_string());
+        
+        let rule = PriceRule::from_conditions(&conditions).unwrap();
+        assert_eq!(rule.asset, "BTC");
+        assert_eq!(rule.target_price, 100_000.0);
+        assert!(matches!(rule.condition, PriceCondition::GreaterThan));
+        
+        conditions.insert("condition".to_string(), "sideways".to_string());
+        assert!(PriceRule::from_conditions(&conditions).is_err());
+    }
+    
     #[tokio::test]
     async fn test_oracle_consensus() {
         let mut oracle = OracleManager::new();
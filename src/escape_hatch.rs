@@ -0,0 +1,414 @@
+// ============================================================================
+// Escape Hatch - Pays out forced exits to L1
+// ============================================================================
+//
+// Every pass (`exits.process_interval_secs`) each open exit is pushed forward:
+//
+//   active session   → settle it to L1 (session settlement path)
+//   available funds  → withdraw them to the exit's L1 target via the bridge
+//   open positions   → wait; when a market resolves its payout is credited
+//                      to the ledger and swept by the next pass
+//
+// An exit completes once the account holds no balance, locked funds or
+// shares. Resting orders are cancelled first so their escrow is part of what
+// gets paid out. In freeze-and-exit mode every pass also queues an exit for
+// each user account that still holds anything; the order book and market
+// escrows and the resolver's house account are left alone.
+//
+// Passes may overlap (the background task, a new exit's first attempt, a
+// freeze), so each exit is claimed for the duration of a pass and skipped
+// by any other pass that finds it claimed.
+// ============================================================================
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app_state::{AppState, SharedState};
+use crate::bridge_relayer;
+use crate::forced_exit::{ExitPayoutKind, ExitRequest, MIN_EXIT_AMOUNT};
+use crate::market_actor::{market_escrow, ORDERBOOK_ESCROW};
+use crate::session_settlement;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// An exit claimed by one pass, released when the pass ends (or is dropped).
+/// Must not be dropped while the state lock is held.
+struct Claim<'a> {
+    state: &'a SharedState,
+    exit_id: &'a str,
+}
+
+impl Drop for Claim<'_> {
+    fn drop(&mut self) {
+        self.state.lock().unwrap().exits.end_processing(self.exit_id);
+    }
+}
+
+/// Ledger accounts that hold funds on users' behalf or for the house
+fn is_system_account(app: &AppState, address: &str) -> bool {
+    address == ORDERBOOK_ESCROW
+        || address.starts_with(&market_escrow(""))
+        || app.ledger.read().resolve(&app.config.resolution.resolver_address).as_deref() == Some(address)
+}
+
+/// Cancel resting orders (every wallet's, or just `wallet`'s) so their
+/// escrowed BB and shares go back to the owners. Returns how many were cancelled.
+pub async fn cancel_open_orders(state: &SharedState, wallet: Option<&str>) -> usize {
+    let mut cancelled = 0;
+    for snapshot in state.books.snapshots() {
+        let Some(market) = state.books.get(&snapshot.market_id) else {
+            continue;
+        };
+        for order in snapshot.open_orders.iter().filter(|o| wallet.is_none_or(|w| o.maker == w)) {
+            match market.cancel_order(&order.id, &order.maker).await {
+                Ok(_) => cancelled += 1,
+                Err(e) => eprintln!("⚠️  Failed to cancel order {} for exit: {}", order.id, e),
+            }
+        }
+    }
+    cancelled
+}
+
+/// Push one exit forward. Returns the exit after the attempt; an exit
+/// another pass is working on is returned as is.
+pub async fn process_exit(state: &SharedState, exit_id: &str) -> Option<ExitRequest> {
+    let now = now();
+    let (exit, claim) = {
+        let mut app = state.lock().unwrap();
+        let exit = app.exits.exit(exit_id)?.clone();
+        if !exit.is_open() || !app.exits.begin_processing(exit_id) {
+            return Some(exit);
+        }
+        (exit, Claim { state, exit_id })
+    };
+    let address = exit.address.clone();
+
+    // 0. Resting orders hold BB and shares in escrow: return them first
+    cancel_open_orders(state, Some(&address)).await;
+
+    // 1. An active session holds the balance on L1's behalf: settle it first
+    let session_active = state.lock().unwrap().sessions.get(&address)
+        .map(|s| s.status == "active")
+        .unwrap_or(false);
+    if session_active {
+        match session_settlement::settle(state, &address, &exit.signature, exit.timestamp, exit.forced).await {
+            Ok(settlement) => {
+                let mut app = state.lock().unwrap();
+                app.exits.record_payout(
+                    exit_id,
+                    ExitPayoutKind::SessionSettlement,
+                    &settlement.session_id,
+                    settlement.final_l2_balance,
+                    now,
+                );
+            }
+            Err(e) => {
+                let mut app = state.lock().unwrap();
+                app.log_activity("⚠️", "EXIT_RETRY", &format!(
+                    "Exit {} for {}: session not settled: {}", exit_id, address, e
+                ));
+                app.exits.record_error(exit_id, e.to_string());
+                if let Err(e) = app.save_bridge_state() {
+                    eprintln!("⚠️  Failed to persist exits: {}", e);
+                }
+                return app.exits.exit(exit_id).cloned();
+            }
+        }
+    }
+
+    // 2. Value resolved positions, sweep the available balance, update status
+    let relay = {
+        let mut app = state.lock().unwrap();
+
+        for position in exit.positions.iter().filter(|p| p.resolved_value.is_none()) {
+            let winner = app.markets.get(&position.market_id)
+                .filter(|m| m.is_resolved)
+                .and_then(|m| m.winning_option);
+            if let Some(winner) = winner {
+                let value = if winner == position.outcome { position.shares } else { 0.0 };
+                app.exits.value_position(exit_id, &position.market_id, position.outcome, value);
+            }
+        }
+
+        let available = app.ledger.balance(&address);
+        let relay = if available >= MIN_EXIT_AMOUNT {
+            let (bridge_id, batched) = app.begin_withdrawal(
+                &address,
+                &exit.target_address,
+                available,
+                &exit.signature,
+                exit.nonce,
+                exit.timestamp,
+                now,
+            );
+            app.exits.record_payout(exit_id, ExitPayoutKind::BridgeWithdrawal, &bridge_id, available, now);
+            app.log_activity("🚪", "EXIT_WITHDRAW", &format!(
+                "Exit {}: {} BB from {} to L1 {} ({})",
+                exit_id, available, address, exit.target_address, bridge_id
+            ));
+            batched.is_none().then_some(bridge_id)
+        } else {
+            None
+        };
+
+        let locked = app.ledger.locked(&address);
+        let holding = !app.exit_positions(&address).is_empty();
+        if holding || locked >= MIN_EXIT_AMOUNT {
+            app.exits.mark_awaiting_resolution(exit_id, now);
+        } else {
+            app.exits.complete(exit_id, now);
+            let total_paid = app.exits.exit(exit_id).map(|e| e.total_paid()).unwrap_or(0.0);
+            app.log_activity("🚪", "EXIT_COMPLETE", &format!(
+                "Exit {} for {} completed: {} BB paid to L1", exit_id, address, total_paid
+            ));
+        }
+
+        // Persist before calling L1 so a crash can't lose a debited withdrawal
+        if let Err(e) = app.save_bridge_state() {
+            eprintln!("⚠️  Failed to persist exits: {}", e);
+        }
+        relay
+    };
+
+    // 3. First relay attempt for unbatched withdrawals; the relayer retries
+    if let Some(bridge_id) = relay {
+        bridge_relayer::relay_withdrawal(state, &bridge_id).await;
+    }
+
+    let exit = state.lock().unwrap().exits.exit(exit_id).cloned();
+    drop(claim);
+    exit
+}
+
+/// Cancel every resting order, then queue an exit for every user account
+/// that still holds funds or shares (freeze-and-exit mode). Returns the new
+/// exit ids.
+pub async fn queue_exits_for_all(state: &SharedState) -> Vec<String> {
+    let cancelled = cancel_open_orders(state, None).await;
+
+    let now = now();
+    let mut app = state.lock().unwrap();
+    if cancelled > 0 {
+        app.log_activity("🧊", "FREEZE_ORDERS", &format!("Cancelled {} open order(s)", cancelled));
+    }
+    let mut accounts: Vec<String> = app.ledger.read().balances.keys()
+        .cloned()
+        .chain(state.books.positions().into_iter().map(|p| p.holder))
+        .collect();
+    accounts.sort();
+    accounts.dedup();
+
+    let mut queued = Vec::new();
+    for address in accounts {
+        if is_system_account(&app, &address) || app.exits.open_exit_for(&address).is_some() {
+            continue;
+        }
+        let balance = app.ledger.balance(&address);
+        let positions = app.exit_positions(&address);
+        if balance < MIN_EXIT_AMOUNT && positions.is_empty() {
+            continue;
+        }
+        if let Ok(exit) = app.exits.request(&address, &address, balance, positions, "", 0, now, true, now) {
+            queued.push(exit.exit_id);
+        }
+    }
+    if !queued.is_empty() {
+        app.log_activity("🧊", "FREEZE_EXITS", &format!("Queued {} forced exit(s)", queued.len()));
+        if let Err(e) = app.save_bridge_state() {
+            eprintln!("⚠️  Failed to persist exits: {}", e);
+        }
+    }
+    queued
+}
+
+/// One processing pass over every open exit
+pub async fn run_once(state: &SharedState) -> Vec<ExitRequest> {
+    let frozen = state.lock().unwrap().exits.is_frozen();
+    if frozen {
+        queue_exits_for_all(state).await;
+    }
+    let open = state.lock().unwrap().exits.open_exits();
+    let mut processed = Vec::new();
+    for exit_id in open {
+        if let Some(exit) = process_exit(state, &exit_id).await {
+            processed.push(exit);
+        }
+    }
+    processed
+}
+
+/// Spawn the background exit processor (every `exits.process_interval_secs`)
+pub fn spawn(state: SharedState) {
+    let interval_secs = state.lock().unwrap().exits.process_interval_secs;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            run_once(&state).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::app_state::{test_state, L2Session};
+    use crate::forced_exit::ExitStatus;
+    use crate::models::PredictionMarket;
+    use crate::orderbook::{LimitOrder, OrderType, Outcome, Side};
+    use crate::rpc::{L1SessionStartRequest, MockL1, MockL1Backend};
+
+    const ALICE: &str = "L1_ALICE_000000000000000";
+    const BOB: &str = "L1_BOB_00000000000000000";
+
+    fn bid(market_id: &str, wallet: &str, price_bps: u64, size: f64) -> LimitOrder {
+        LimitOrder::new(
+            market_id.to_string(),
+            Outcome::YES,
+            Side::Bid,
+            price_bps,
+            size,
+            OrderType::GTC,
+            wallet.to_string(),
+            String::new(),
+        ).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_freeze_cancels_orders_and_skips_system_accounts() {
+        let state = test_state("escape_hatch_freeze");
+        state.ledger.credit(ALICE, 100.0);
+        state.ledger.credit(BOB, 100.0);
+        let resolver = state.lock().unwrap().config.resolution.resolver_address.clone();
+        state.ledger.credit(&resolver, 1_000.0);
+
+        // BOB's mint parks BB in the market escrow, ALICE's bid in the book escrow
+        let market = state.books.open("m1", None);
+        market.mint(BOB, 40.0).await.unwrap();
+        market.submit_order(bid("m1", ALICE, 50, 100.0)).await.unwrap();
+        assert!(state.ledger.balance(ORDERBOOK_ESCROW) > 0.0);
+        assert!(state.ledger.balance(ALICE) < 100.0);
+
+        state.lock().unwrap().exits.freeze("admin", "operator outage", now());
+        let queued = queue_exits_for_all(&state).await;
+
+        // The bid was cancelled and its BB is part of ALICE's exit
+        assert!(market.snapshot().open_orders.is_empty());
+        assert_eq!(state.ledger.balance(ORDERBOOK_ESCROW), 0.0);
+        assert_eq!(state.ledger.balance(ALICE), 100.0);
+
+        let app = state.lock().unwrap();
+        let exited: Vec<&str> = queued.iter()
+            .map(|id| app.exits.exit(id).unwrap().address.as_str())
+            .collect();
+        assert!(exited.contains(&ALICE) && exited.contains(&BOB), "{:?}", exited);
+        assert_eq!(app.exits.open_exit_for(ALICE).unwrap().balance_at_request, 100.0);
+        assert_eq!(app.exits.open_exit_for(BOB).unwrap().positions.len(), 2);
+        for system in [ORDERBOOK_ESCROW.to_string(), market_escrow("m1"), resolver] {
+            let address = app.ledger.read().resolve(&system).unwrap();
+            assert!(app.exits.open_exit_for(&address).is_none(), "exit queued for {}", system);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exit_settles_session_withdraws_then_completes_at_resolution() {
+        let state = test_state("escape_hatch_sequence");
+        let l1 = Arc::new(MockL1Backend::new(MockL1::new(0.0)));
+        l1.state().mint(ALICE, 500.0);
+        let started = l1.state().start_session(&L1SessionStartRequest {
+            wallet_address: ALICE.to_string(),
+            l2_session_id: "session_exit".to_string(),
+            requested_amount: 100.0,
+            signature: String::new(),
+            timestamp: now(),
+            nonce: "1".to_string(),
+        });
+        let session = L2Session::new(ALICE.to_string(), 500.0, 100.0, started.session_id.unwrap());
+
+        // 100 BB in the session, 25 BB outside it, 10 YES + 10 NO in m1
+        state.ledger.credit(ALICE, 100.0 + 25.0 + 10.0);
+        state.books.open("m1", None).mint(ALICE, 10.0).await.unwrap();
+        let exit_id = {
+            let mut app = state.lock().unwrap();
+            app.l1 = l1.clone();
+            app.sessions.insert(ALICE.to_string(), session);
+            app.markets.insert("m1".to_string(), PredictionMarket::new(
+                "m1".to_string(),
+                "Will it rain?".to_string(),
+                String::new(),
+                "weather".to_string(),
+                vec!["Yes".to_string(), "No".to_string()],
+            ));
+            let positions = app.exit_positions(ALICE);
+            app.exits.request(ALICE, "L1_TARGET", 125.0, positions, "sig", 1, now(), false, now())
+                .unwrap()
+                .exit_id
+        };
+
+        // Session settled to L1, the rest withdrawn, shares still open
+        let exit = process_exit(&state, &exit_id).await.unwrap();
+        assert_eq!(exit.status, ExitStatus::AwaitingResolution);
+        assert_eq!(exit.payouts.len(), 2);
+        assert_eq!(exit.payouts[0].kind, ExitPayoutKind::SessionSettlement);
+        assert_eq!(exit.payouts[0].amount, 100.0);
+        assert_eq!(exit.payouts[1].kind, ExitPayoutKind::BridgeWithdrawal);
+        assert_eq!(exit.payouts[1].amount, 25.0);
+        assert_eq!(state.ledger.balance(ALICE), 0.0);
+        assert_eq!(l1.state().balance(ALICE).balance, 500.0);
+        assert_eq!(state.lock().unwrap().sessions[ALICE].status, "settled");
+
+        // Nothing to do until the market resolves
+        let waiting = run_once(&state).await;
+        assert_eq!(waiting[0].status, ExitStatus::AwaitingResolution);
+        assert_eq!(waiting[0].payouts.len(), 2);
+
+        // YES wins: the payout is swept and the exit completes
+        state.books.get("m1").unwrap().resolve(0).await.unwrap();
+        {
+            let mut app = state.lock().unwrap();
+            let market = app.markets.get_mut("m1").unwrap();
+            market.is_resolved = true;
+            market.winning_option = Some(0);
+        }
+        let done = run_once(&state).await;
+        assert_eq!(done.len(), 1);
+        let exit = &done[0];
+        assert_eq!(exit.status, ExitStatus::Completed);
+        assert_eq!(exit.payouts.len(), 3);
+        assert_eq!(exit.payouts[2].amount, 10.0);
+        assert_eq!(exit.total_paid(), 135.0);
+        assert_eq!(exit.positions.iter().map(|p| p.resolved_value).collect::<Vec<_>>(), vec![Some(10.0), Some(0.0)]);
+        assert!(run_once(&state).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_claimed_exit_is_left_to_the_pass_holding_it() {
+        let state = test_state("escape_hatch_claim");
+        state.ledger.credit(ALICE, 50.0);
+        let exit_id = state.lock().unwrap().exits
+            .request(ALICE, ALICE, 50.0, vec![], "sig", 1, now(), false, now())
+            .unwrap()
+            .exit_id;
+
+        // Another pass is mid-way through this exit
+        assert!(state.lock().unwrap().exits.begin_processing(&exit_id));
+        let exit = process_exit(&state, &exit_id).await.unwrap();
+        assert_eq!(exit.status, ExitStatus::Requested);
+        assert!(exit.payouts.is_empty());
+        assert_eq!(state.ledger.balance(ALICE), 50.0);
+
+        // Once released the exit is paid exactly once
+        state.lock().unwrap().exits.end_processing(&exit_id);
+        let exit = process_exit(&state, &exit_id).await.unwrap();
+        assert_eq!(exit.status, ExitStatus::Completed);
+        assert_eq!(exit.payouts.len(), 1);
+        assert!(state.lock().unwrap().exits.begin_processing(&exit_id), "claim released");
+    }
+}
@@ -0,0 +1,373 @@
+//! Forced Exits (escape hatch)
+//!
+//! If the operator stops processing or censors a user, funds on L2 would be
+//! stuck. A user can submit a signed exit request for everything they hold:
+//! their available balance is paid out to L1 (by settling an active session
+//! or through the bridge) and open share positions are valued at resolution,
+//! with the payouts swept to L1 as their markets resolve.
+//!
+//! The L2 must act on an exit before its deadline. An exit the L2 has not
+//! acted on by then is reported as overdue, which is public evidence that the
+//! operator is not honouring exits.
+//!
+//! Freeze-and-exit mode is one-way: once frozen, the L2 stops accepting
+//! trades and queues an exit for every account.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Default seconds the L2 has to act on an exit request (24h)
+pub const DEFAULT_EXIT_DEADLINE_SECS: u64 = 86_400;
+
+/// Default seconds between exit processing passes
+pub const DEFAULT_EXIT_PROCESS_INTERVAL_SECS: u64 = 30;
+
+/// Balances below this are dust and not withdrawn
+pub const MIN_EXIT_AMOUNT: f64 = 0.01;
+
+// ============================================================================
+// EXIT REQUESTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitStatus {
+    /// Waiting for the L2 to act
+    Requested,
+    /// Balance paid out; waiting for markets with open positions to resolve
+    AwaitingResolution,
+    /// Everything paid out to L1
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitPayoutKind {
+    /// Active session settled to L1 (reference is the session id)
+    SessionSettlement,
+    /// L2→L1 bridge withdrawal (reference is the bridge id)
+    BridgeWithdrawal,
+}
+
+/// One payout made against an exit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitPayout {
+    pub kind: ExitPayoutKind,
+    pub reference: String,
+    pub amount: f64,
+    pub paid_at: u64,
+}
+
+/// A share position held when the exit was requested
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitPosition {
+    pub market_id: String,
+    pub outcome: usize,
+    pub shares: f64,
+    /// BB the position paid out at resolution (None until the market resolves)
+    pub resolved_value: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitRequest {
+    pub exit_id: String,
+    pub address: String,
+    /// L1 address funds are withdrawn to
+    pub target_address: String,
+    pub requested_at: u64,
+    /// The L2 must act on the exit before this time
+    pub deadline: u64,
+    pub status: ExitStatus,
+    /// Available balance when the exit was requested
+    pub balance_at_request: f64,
+    pub positions: Vec<ExitPosition>,
+    pub payouts: Vec<ExitPayout>,
+    /// When the L2 first acted on the exit
+    pub processed_at: Option<u64>,
+    pub completed_at: Option<u64>,
+    /// Queued by freeze-and-exit rather than requested by the user
+    pub forced: bool,
+    /// Original signed request (empty for forced exits)
+    pub signature: String,
+    pub nonce: u64,
+    pub timestamp: u64,
+    pub last_error: Option<String>,
+}
+
+impl ExitRequest {
+    pub fn is_open(&self) -> bool {
+        self.status != ExitStatus::Completed
+    }
+
+    /// Not acted on by the L2 before its deadline
+    pub fn is_overdue(&self, now: u64) -> bool {
+        self.processed_at.is_none() && now > self.deadline
+    }
+
+    /// Total BB paid out to L1 so far
+    pub fn total_paid(&self) -> f64 {
+        self.payouts.iter().map(|p| p.amount).sum()
+    }
+
+    /// Markets of positions still waiting for resolution
+    pub fn unresolved_markets(&self) -> Vec<&str> {
+        self.positions.iter()
+            .filter(|p| p.resolved_value.is_none())
+            .map(|p| p.market_id.as_str())
+            .collect()
+    }
+}
+
+/// Freeze-and-exit mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreezeState {
+    pub frozen_at: u64,
+    pub frozen_by: String,
+    pub reason: String,
+}
+
+// ============================================================================
+// EXIT MANAGER
+// ============================================================================
+
+/// Tracks exit requests and the freeze flag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitManager {
+    #[serde(skip)]
+    pub deadline_secs: u64,
+    #[serde(skip)]
+    pub process_interval_secs: u64,
+    exits: BTreeMap<String, ExitRequest>,
+    next_id: u64,
+    frozen: Option<FreezeState>,
+    /// Exits a processing pass is currently pushing forward
+    #[serde(skip)]
+    in_progress: HashSet<String>,
+}
+
+impl Default for ExitManager {
+    fn default() -> Self {
+        Self::new(DEFAULT_EXIT_DEADLINE_SECS, DEFAULT_EXIT_PROCESS_INTERVAL_SECS)
+    }
+}
+
+impl ExitManager {
+    pub fn new(deadline_secs: u64, process_interval_secs: u64) -> Self {
+        ExitManager {
+            deadline_secs,
+            process_interval_secs,
+            exits: BTreeMap::new(),
+            next_id: 1,
+            frozen: None,
+            in_progress: HashSet::new(),
+        }
+    }
+
+    /// Replace exits and the freeze flag with persisted ones (keeps config)
+    pub fn restore(&mut self, persisted: ExitManager) {
+        self.exits = persisted.exits;
+        self.next_id = persisted.next_id.max(1);
+        self.frozen = persisted.frozen;
+    }
+
+    /// Record an exit request. Fails if the address already has an open exit.
+    #[allow(clippy::too_many_arguments)]
+    pub fn request(
+        &mut self,
+        address: &str,
+        target_address: &str,
+        balance: f64,
+        positions: Vec<ExitPosition>,
+        signature: &str,
+        nonce: u64,
+        timestamp: u64,
+        forced: bool,
+        now: u64,
+    ) -> Result<ExitRequest, String> {
+        if let Some(open) = self.open_exit_for(address) {
+            return Err(format!("{} already has an open exit: {}", address, open.exit_id));
+        }
+        let exit_id = format!("exit_{}", self.next_id);
+        self.next_id += 1;
+        let exit = ExitRequest {
+            exit_id: exit_id.clone(),
+            address: address.to_string(),
+            target_address: target_address.to_string(),
+            requested_at: now,
+            deadline: now + self.deadline_secs,
+            status: ExitStatus::Requested,
+            balance_at_request: balance,
+            positions,
+            payouts: Vec::new(),
+            processed_at: None,
+            completed_at: None,
+            forced,
+            signature: signature.to_string(),
+            nonce,
+            timestamp,
+            last_error: None,
+        };
+        self.exits.insert(exit_id, exit.clone());
+        Ok(exit)
+    }
+
+    pub fn exit(&self, exit_id: &str) -> Option<&ExitRequest> {
+        self.exits.get(exit_id)
+    }
+
+    pub fn open_exit_for(&self, address: &str) -> Option<&ExitRequest> {
+        self.exits.values().find(|e| e.address == address && e.is_open())
+    }
+
+    /// Ids of open exits, earliest deadline first
+    pub fn open_exits(&self) -> Vec<String> {
+        let mut open: Vec<&ExitRequest> = self.exits.values().filter(|e| e.is_open()).collect();
+        open.sort_by_key(|e| e.deadline);
+        open.into_iter().map(|e| e.exit_id.clone()).collect()
+    }
+
+    /// Exits the L2 failed to act on before their deadline
+    pub fn overdue(&self, now: u64) -> Vec<&ExitRequest> {
+        self.exits.values().filter(|e| e.is_overdue(now)).collect()
+    }
+
+    /// Every exit, newest first
+    pub fn exits(&self) -> Vec<&ExitRequest> {
+        self.exits.values().rev().collect()
+    }
+
+    pub fn record_payout(&mut self, exit_id: &str, kind: ExitPayoutKind, reference: &str, amount: f64, now: u64) {
+        if let Some(exit) = self.exits.get_mut(exit_id) {
+            exit.payouts.push(ExitPayout { kind, reference: reference.to_string(), amount, paid_at: now });
+            exit.processed_at.get_or_insert(now);
+            exit.last_error = None;
+        }
+    }
+
+    /// Record what a position paid out once its market resolved
+    pub fn value_position(&mut self, exit_id: &str, market_id: &str, outcome: usize, value: f64) {
+        if let Some(exit) = self.exits.get_mut(exit_id) {
+            for position in exit.positions.iter_mut()
+                .filter(|p| p.market_id == market_id && p.outcome == outcome)
+            {
+                position.resolved_value = Some(value);
+            }
+        }
+    }
+
+    /// Balance paid out; positions or locked funds remain
+    pub fn mark_awaiting_resolution(&mut self, exit_id: &str, now: u64) {
+        if let Some(exit) = self.exits.get_mut(exit_id) {
+            exit.status = ExitStatus::AwaitingResolution;
+            exit.processed_at.get_or_insert(now);
+        }
+    }
+
+    pub fn complete(&mut self, exit_id: &str, now: u64) {
+        if let Some(exit) = self.exits.get_mut(exit_id) {
+            exit.status = ExitStatus::Completed;
+            exit.processed_at.get_or_insert(now);
+            exit.completed_at = Some(now);
+        }
+    }
+
+    pub fn record_error(&mut self, exit_id: &str, error: String) {
+        if let Some(exit) = self.exits.get_mut(exit_id) {
+            exit.last_error = Some(error);
+        }
+    }
+
+    /// Enter freeze-and-exit mode. Returns false if already frozen.
+    pub fn freeze(&mut self, frozen_by: &str, reason: &str, now: u64) -> bool {
+        if self.frozen.is_some() {
+            return false;
+        }
+        self.frozen = Some(FreezeState {
+            frozen_at: now,
+            frozen_by: frozen_by.to_string(),
+            reason: reason.to_string(),
+        });
+        true
+    }
+
+    pub fn frozen(&self) -> Option<&FreezeState> {
+        self.frozen.as_ref()
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.is_some()
+    }
+
+    /// Claim an exit for one processing pass. False if another pass holds it.
+    pub fn begin_processing(&mut self, exit_id: &str) -> bool {
+        self.in_progress.insert(exit_id.to_string())
+    }
+
+    pub fn end_processing(&mut self, exit_id: &str) {
+        self.in_progress.remove(exit_id);
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(market_id: &str, outcome: usize, shares: f64) -> ExitPosition {
+        ExitPosition { market_id: market_id.to_string(), outcome, shares, resolved_value: None }
+    }
+
+    #[test]
+    fn test_exit_lifecycle_and_overdue() {
+        let mut exits = ExitManager::new(100, 30);
+        let exit = exits.request("L1_ALICE", "L1_ALICE", 50.0, vec![position("m1", 0, 10.0)], "sig", 7, 1000, false, 1000).unwrap();
+        assert_eq!(exit.deadline, 1100);
+        assert!(exits.request("L1_ALICE", "L1_ALICE", 50.0, vec![], "sig", 8, 1000, false, 1001).is_err());
+
+        // Not acted on before the deadline
+        assert!(exits.overdue(1100).is_empty());
+        assert_eq!(exits.overdue(1101).len(), 1);
+
+        exits.record_payout(&exit.exit_id, ExitPayoutKind::BridgeWithdrawal, "bridge_1", 50.0, 1102);
+        exits.mark_awaiting_resolution(&exit.exit_id, 1102);
+        assert!(exits.overdue(1200).is_empty());
+        assert_eq!(exits.exit(&exit.exit_id).unwrap().unresolved_markets(), vec!["m1"]);
+
+        // Market resolves; the winning shares are swept with a second payout
+        exits.value_position(&exit.exit_id, "m1", 0, 10.0);
+        exits.record_payout(&exit.exit_id, ExitPayoutKind::BridgeWithdrawal, "bridge_2", 10.0, 1300);
+        exits.complete(&exit.exit_id, 1300);
+        let done = exits.exit(&exit.exit_id).unwrap();
+        assert_eq!(done.status, ExitStatus::Completed);
+        assert_eq!(done.processed_at, Some(1102));
+        assert_eq!(done.total_paid(), 60.0);
+        assert!(done.unresolved_markets().is_empty());
+        assert!(exits.open_exits().is_empty());
+
+        // A new exit is allowed once the previous one completed
+        assert!(exits.request("L1_ALICE", "L1_ALICE", 0.0, vec![], "sig", 9, 1400, false, 1400).is_ok());
+    }
+
+    #[test]
+    fn test_freeze_is_one_way_and_persists() {
+        let mut exits = ExitManager::new(100, 30);
+        assert!(exits.freeze("ORACLE", "operator outage", 500));
+        assert!(!exits.freeze("ORACLE", "again", 600));
+        exits.request("L1_BOB", "L1_BOB", 5.0, vec![], "", 0, 500, true, 500).unwrap();
+
+        let json = serde_json::to_string(&exits).unwrap();
+        let mut restored = ExitManager::new(100, 30);
+        restored.restore(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.frozen().unwrap().frozen_at, 500);
+        assert!(restored.open_exit_for("L1_BOB").unwrap().forced);
+        let next = restored.request("L1_CAROL", "L1_CAROL", 1.0, vec![], "", 0, 501, true, 501).unwrap();
+        assert_eq!(next.exit_id, "exit_2");
+    }
+}
@@ -2,15 +2,22 @@
 
 use axum::{
     extract::{Path, State, Query},
-    http::StatusCode,
     response::Json,
 };
 use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 use serde_json::{json, Value};
+use std::sync::Arc;
+use crate::api_error::ApiError;
+use crate::pagination::{paginate, parse_filter, PageError, PageRequest, SortOrder, TimeRange};
 use crate::app_state::SharedState;
 use crate::models::*;
-use crate::market_resolve::cpmm::{CPMMPool, VIABILITY_THRESHOLD};
-use crate::rss::{RssEvent, EventDates, write_rss_event_to_file, ResolutionRules as RssResolutionRules};
+use crate::market_resolve::cpmm::{CPMMPool, EventStatus};
+use crate::market_resolve::CompiledRules;
+use crate::market_search::{SearchQuery, SearchSort};
+use crate::rss::write_rss_event_to_file;
+use crate::rpc::{L1Backend, SignedTxError};
+use crate::roles::{AdminRole, Authorized, DealerRole, ModeratorRole, OracleRole, Role, RoleAction, RoleChange};
 use crate::ledger::{TxType, Transaction, Layer, FundStatus, MarketData, BetData, reconstruct_transactions_from_market_data};
 
 /// Helper to convert app markets to ledger MarketData
@@ -38,7 +45,7 @@ fn markets_to_market_data(markets: &std::collections::HashMap<String, Prediction
 
 // ===== BET REQUEST =====
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BetRequest {
     pub signature: String,
     pub from_address: String,
@@ -50,11 +57,29 @@ pub struct BetRequest {
 }
 
 // ===== BETTING ENDPOINT =====
-
+/// POST /bet/signed - Place a signed bet against the market's CPMM pool
+///
+/// The application lock is taken once before the swap, for the nonce, session
+/// and balance checks and every record that doesn't depend on the fill (bet,
+/// session PnL, trending volume). The swap runs on the market's actor; after
+/// it the lock is only held to copy the actor's pool onto the market, which
+/// the market reads and the outbound feed are priced from.
+#[utoipa::path(
+    post,
+    path = "/bet/signed",
+    tag = "bets",
+    request_body = BetRequest,
+    responses(
+        (status = 200, description = "Bet placed", body = SignedBetResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Bad signature or expired request", body = ApiError),
+        (status = 404, description = "Market or account not found", body = ApiError),
+    )
+)]
 pub async fn place_signed_bet(
     State(state): State<SharedState>,
     Json(req): Json<BetRequest>,
-) -> Result<Json<SignedBetResponse>, (StatusCode, Json<SignedBetResponse>)> {
+) -> Result<Json<SignedBetResponse>, ApiError> {
     println!("📥 Bet: market={}, option={}, amount={}", req.market_id, req.option, req.amount);
     
     // Validate timestamp (24h window)
@@ -64,143 +89,160 @@ pub async fn place_signed_bet(
         .as_secs();
     
     if now.abs_diff(req.timestamp) > 86400 {
-        return Err((StatusCode::UNAUTHORIZED, Json(SignedBetResponse::error("Transaction expired"))));
+        return Err(SignedTxError::Expired.into());
     }
     
     let outcome = match req.option.as_str() {
         "0" | "YES" => 0,
         "1" | "NO" => 1,
-        _ => return Err((StatusCode::BAD_REQUEST, Json(SignedBetResponse::error("Invalid option")))),
+        _ => return Err(ApiError::BadRequest("Invalid option".to_string())),
     };
     
-    let mut app = state.lock().unwrap();
-    
-    // Validate nonce (replay protection)
-    let last_nonce = app.nonces.get(&req.from_address).copied().unwrap_or(0);
-    
-    // Nonce must be greater than last used nonce
-    // For first-time users (last_nonce 0), accept nonce 1+
-    if req.nonce <= last_nonce {
-        return Err((StatusCode::BAD_REQUEST, Json(SignedBetResponse {
-            success: false,
-            bet_id: None,
-            transaction_id: None,
-            market_id: None,
-            outcome: None,
-            amount: None,
-            new_balance: None,
-            nonce_used: None,
-            error: Some(format!("Invalid nonce: got {}, expected > {}", req.nonce, last_nonce)),
-            entry_price: None,
-            shares_purchased: None,
-            price_impact: None,
-            new_price: None,
-            fee_paid: None,
-        })));
-    }
-    
-    // Resolve address
-    let account = app.ledger.accounts.iter()
-        .find(|(_, addr)| **addr == req.from_address)
-        .map(|(name, _)| name.clone())
-        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(SignedBetResponse::error("Address not found"))))?;
-    
-    // Check market exists
-    if !app.markets.contains_key(&req.market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(SignedBetResponse::error("Market not found"))));
-    }
-    
-    // === CPMM DYNAMIC PRICING ===
-    // Execute swap on CPMM pool if it exists, otherwise use static pricing
-    let cpmm_result = {
-        let market = app.markets.get_mut(&req.market_id).unwrap();
+    let (account, tx, bet_id, balance, new_pool) = {
+        let mut app = state.lock().unwrap();
+        
+        // Validate nonce (replay protection)
+        let last_nonce = app.nonces.get(&req.from_address).copied().unwrap_or(0);
+        
+        // Nonce must be greater than last used nonce
+        // For first-time users (last_nonce 0), accept nonce 1+
+        if req.nonce <= last_nonce {
+            return Err(ApiError::InvalidNonce { got: req.nonce, last: last_nonce });
+        }
+        
+        // Resolve address
+        let account = app.ledger.read().accounts.iter()
+            .find(|(_, addr)| **addr == req.from_address)
+            .map(|(name, _)| name.clone())
+            .ok_or_else(|| ApiError::AccountNotFound(req.from_address.clone()))?;
         
-        // Auto-initialize CPMM pool if not present (with default 10k liquidity)
-        if market.cpmm_pool.is_none() {
-            let default_liquidity = 10000.0; // 10,000 BB default
+        let default_liquidity = app.config.markets.auto_liquidity;
+        let lp_fee_rate = app.config.market_params(&req.market_id).lp_fee_rate;
+        
+        // Check market exists and still takes bets
+        trading_open(&app, &req.market_id)?;
+        let market = app.markets.get_mut(&req.market_id)
+            .ok_or_else(|| ApiError::MarketNotFound(req.market_id.clone()))?;
+        
+        // Auto-initialize CPMM pool if not present (with the configured liquidity)
+        let new_pool = if market.cpmm_pool.is_none() {
             let pool = crate::market_resolve::cpmm::CPMMPool::new(
                 default_liquidity,
                 market.options.clone(),
                 &market.escrow_address,
-            );
-            market.cpmm_pool = Some(pool);
+            ).with_fee_rate(lp_fee_rate);
+            market.cpmm_pool = Some(pool.clone());
             println!("🔧 Auto-initialized CPMM pool for market {} with {} BB", req.market_id, default_liquidity);
-        }
-        
-        if let Some(ref mut pool) = market.cpmm_pool {
-            // Use buy_with_amount - this is the correct CPMM function
-            // User specifies how much BB to spend, pool calculates shares received
-            match pool.buy_with_amount(outcome, req.amount) {
-                Ok(buy_result) => {
-                    Some((
-                        buy_result.entry_price,
-                        buy_result.shares_received,
-                        buy_result.price_impact,
-                        buy_result.new_price,
-                        buy_result.fee_paid,
-                    ))
-                }
-                Err(e) => {
-                    println!("⚠️ CPMM buy failed: {} - falling back to static pricing", e);
-                    // Fallback: use 50% price, shares = amount
-                    let fallback_price = pool.calculate_prices().get(outcome).copied().unwrap_or(0.5);
-                    Some((fallback_price, req.amount, 0.0, fallback_price, 0.0))
-                }
-            }
+            Some(pool)
         } else {
             None
-        }
+        };
+        
+        // Session limits (expired sessions can't trade, open ones can't overspend)
+        app.check_session_spend(&account, req.amount)?;
+        
+        // Place bet on ledger (deduct balance); the nonce is spent in the same step
+        let tx = app.ledger.place_bet(&account, &req.market_id, outcome, req.amount, &req.signature)?;
+        app.nonces.insert(req.from_address.clone(), req.nonce);
+        app.record_session_trade(&account, req.amount, 0.0);
+        
+        let bet_id = match app.markets.get_mut(&req.market_id) {
+            Some(market) => market.record_bet(&account, req.amount, outcome),
+            None => tx.id.clone(),
+        };
+        app.record_market_trade(&req.market_id, req.amount, now);
+        
+        let balance = app.ledger.balance(&account);
+        (account, tx, bet_id, balance, new_pool)
     };
     
-    // Place bet on ledger (deduct balance)
-    match app.ledger.place_bet(&account, &req.market_id, outcome, req.amount, &req.signature) {
-        Ok(tx) => {
-            app.nonces.insert(req.from_address.clone(), req.nonce);
-            
-            let bet_id = if let Some(market) = app.markets.get_mut(&req.market_id) {
-                market.record_bet(&account, req.amount, outcome)
-            } else {
-                tx.id.clone()
-            };
-            
-            let balance = app.ledger.balance(&account);
-            
-            // Extract CPMM pricing info
-            let (entry_price, shares, price_impact, new_price, fee) = cpmm_result
-                .unwrap_or((0.5, req.amount, 0.0, 0.5, 0.0));
-            
-            app.log_activity("🎯", "BET", &format!(
-                "{} bet {} BB on {} @ {:.2}% → {:.2}% (impact: {:.2}%)",
-                account, req.amount, req.market_id,
-                entry_price * 100.0, new_price * 100.0, price_impact * 100.0
-            ));
-            
-            Ok(Json(SignedBetResponse {
-                success: true,
-                bet_id: Some(bet_id),
-                transaction_id: Some(tx.id),
-                market_id: Some(req.market_id),
-                outcome: Some(outcome),
-                amount: Some(req.amount),
-                new_balance: Some(balance),
-                nonce_used: Some(req.nonce),
-                error: None,
-                entry_price: Some(entry_price),
-                shares_purchased: Some(shares),
-                price_impact: Some(price_impact),
-                new_price: Some(new_price),
-                fee_paid: Some(fee),
-            }))
-        }
-        Err(e) => Err((StatusCode::BAD_REQUEST, Json(SignedBetResponse::error(&e)))),
-    }
+    // === CPMM DYNAMIC PRICING ===
+    // The market's actor swaps against its pool (static pricing if it has none)
+    let market_book = state.books.open(&req.market_id, new_pool);
+    let cpmm_result = market_book.buy_cpmm(outcome, req.amount).await.ok().flatten();
+    
+    // Keeps the market's pool in step with the actor's
+    state.lock().unwrap().publish_odds(&req.market_id, Some(&market_book.snapshot()), now);
+    
+    // Extract CPMM pricing info
+    let (entry_price, shares, price_impact, new_price, fee) = cpmm_result
+        .map(|fill| (fill.entry_price, fill.shares, fill.price_impact, fill.new_price, fill.fee))
+        .unwrap_or((0.5, req.amount, 0.0, 0.5, 0.0));
+    
+    state.ledger.log_activity("🎯", "BET", &format!(
+        "{} bet {} BB on {} @ {:.2}% → {:.2}% (impact: {:.2}%)",
+        account, req.amount, req.market_id,
+        entry_price * 100.0, new_price * 100.0, price_impact * 100.0
+    ));
+    
+    Ok(Json(SignedBetResponse {
+        success: true,
+        bet_id: Some(bet_id),
+        transaction_id: Some(tx.id),
+        market_id: Some(req.market_id),
+        outcome: Some(outcome),
+        amount: Some(req.amount),
+        new_balance: Some(balance),
+        nonce_used: Some(req.nonce),
+        error: None,
+        entry_price: Some(entry_price),
+        shares_purchased: Some(shares),
+        price_impact: Some(price_impact),
+        new_price: Some(new_price),
+        fee_paid: Some(fee),
+    }))
 }
 
 // ===== MARKET ENDPOINTS =====
 
-pub async fn get_markets(State(state): State<SharedState>) -> Json<Value> {
+/// Query params for the market list
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MarketsQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default: 100, max: 1000)
+    pub limit: Option<usize>,
+    /// Sort order by creation time: "asc", "desc" (default: "desc")
+    pub order: Option<String>,
+    /// Filter by lifecycle status: "pending", "provisional", "active", "closed", "resolved", "refunded"
+    pub status: Option<String>,
+    /// Filter by category (case-insensitive)
+    pub category: Option<String>,
+    /// Created at or after (unix seconds)
+    pub from_timestamp: Option<u64>,
+    /// Created at or before (unix seconds)
+    pub to_timestamp: Option<u64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/markets",
+    tag = "markets",
+    params(MarketsQuery),
+    responses(
+        (status = 200, description = "Page of markets, newest first", body = Object),
+        (status = 400, description = "Invalid cursor or filter", body = ApiError),
+    )
+)]
+pub async fn get_markets(
+    State(state): State<SharedState>,
+    Query(params): Query<MarketsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let status = parse_filter("status", params.status.as_deref(), EventStatus::parse)?;
+    let order = parse_filter("order", params.order.as_deref(), SortOrder::parse)?.unwrap_or_default();
+    let range = TimeRange::new(params.from_timestamp, params.to_timestamp);
+
     let app = state.lock().unwrap();
-    let markets: Vec<Value> = app.markets.values()
+    let markets: Vec<&PredictionMarket> = app.markets.values()
+        .filter(|m| status.is_none_or(|s| m.market_status == s))
+        .filter(|m| params.category.as_ref().is_none_or(|c| m.category.eq_ignore_ascii_case(c)))
+        .filter(|m| range.contains(m.created_at))
+        .collect();
+    let request = PageRequest::new(params.cursor.clone(), params.limit).with_order(order);
+    let page = paginate(markets, |m| (m.created_at, m.id.clone()), &request)?;
+
+    let markets: Vec<Value> = page.items.iter()
         .map(|m| json!({
             "id": m.id,
             "title": m.title,
@@ -210,17 +252,117 @@ pub async fn get_markets(State(state): State<SharedState>) -> Json<Value> {
             "is_resolved": m.is_resolved,
             "total_volume": m.total_volume,
             "odds": m.calculate_odds(),
+            "status": m.market_status.to_string(),
+            "created_at": m.created_at,
+        }))
+        .collect();
+    Ok(Json(json!({ "success": true, "markets": markets, "pagination": page.meta() })))
+}
+
+/// Query params for market search
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MarketSearchQuery {
+    /// Words to find in the title, description or tags (the last may be a prefix)
+    pub q: Option<String>,
+    /// Filter by category (case-insensitive)
+    pub category: Option<String>,
+    /// Comma-separated tags the market must all carry
+    pub tags: Option<String>,
+    /// Filter by lifecycle status: "pending", "provisional", "active", "closed", "resolved", "refunded"
+    pub status: Option<String>,
+    /// Betting closes at or after (unix seconds)
+    pub closes_after: Option<u64>,
+    /// Betting closes at or before (unix seconds)
+    pub closes_before: Option<u64>,
+    /// "relevance" (default), "trending", "liquidity", "newest" or "closing_soon"
+    pub sort: Option<String>,
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default: 100, max: 1000)
+    pub limit: Option<usize>,
+}
+
+/// GET /markets/search - Full-text market search with filters and ranking
+#[utoipa::path(
+    get,
+    path = "/markets/search",
+    tag = "markets",
+    params(MarketSearchQuery),
+    responses(
+        (status = 200, description = "Page of matching markets in the requested order", body = Object),
+        (status = 400, description = "Invalid cursor or filter", body = ApiError),
+    )
+)]
+pub async fn search_markets(
+    State(state): State<SharedState>,
+    Query(params): Query<MarketSearchQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let query = SearchQuery {
+        text: params.q.clone(),
+        category: params.category.clone(),
+        tags: params.tags.as_deref()
+            .map(|tags| tags.split(',').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect())
+            .unwrap_or_default(),
+        status: parse_filter("status", params.status.as_deref(), EventStatus::parse)?,
+        closes_after: params.closes_after,
+        closes_before: params.closes_before,
+        sort: parse_filter("sort", params.sort.as_deref(), SearchSort::parse)?.unwrap_or_default(),
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let app = state.lock().unwrap();
+    let hits = app.market_index.search(&query, now);
+    let request = PageRequest::new(params.cursor.clone(), params.limit).with_order(query.sort.order());
+    let page = paginate(hits, |h| (h.sort_value, h.market_id.clone()), &request)?;
+
+    let markets: Vec<Value> = page.items.iter()
+        .filter_map(|hit| app.markets.get(&hit.market_id).map(|m| (hit, m)))
+        .map(|(hit, m)| json!({
+            "id": m.id,
+            "title": m.title,
+            "description": m.description,
+            "category": m.category,
+            "tags": m.tags,
+            "options": m.options,
+            "status": m.market_status.to_string(),
+            "is_resolved": m.is_resolved,
+            "total_volume": m.total_volume,
+            "unique_bettors": m.unique_bettors.len(),
+            "on_leaderboard": m.on_leaderboard,
+            "odds": m.calculate_odds(),
+            "created_at": m.created_at,
+            "closes_at": hit.closes_at,
+            "liquidity": hit.liquidity,
+            "volume_24h": hit.volume_24h,
+            "volume_change_24h": hit.volume_change_24h,
+            "score": hit.score,
         }))
         .collect();
-    Json(json!({ "markets": markets }))
+    Ok(Json(json!({ "success": true, "markets": markets, "pagination": page.meta() })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/markets/{id}",
+    tag = "markets",
+    params(
+        ("id" = String, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 200, description = "Market details", body = Object),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_market(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     let app = state.lock().unwrap();
-    let m = app.markets.get(&id).ok_or(StatusCode::NOT_FOUND)?;
+    let m = app.markets.get(&id).ok_or_else(|| ApiError::MarketNotFound(id.clone()))?;
     Ok(Json(json!({
         "id": m.id,
         "title": m.title,
@@ -244,12 +386,24 @@ pub async fn get_market(
 }
 
 /// GET /markets/:id/prices - Get current CPMM prices and pool info for a market
+#[utoipa::path(
+    get,
+    path = "/markets/{id}/prices",
+    tag = "markets",
+    params(
+        ("id" = String, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 200, description = "CPMM prices", body = Object),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_market_prices(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     let app = state.lock().unwrap();
-    let market = app.markets.get(&id).ok_or(StatusCode::NOT_FOUND)?;
+    let market = app.markets.get(&id).ok_or_else(|| ApiError::MarketNotFound(id.clone()))?;
     
     // Calculate volume per side from option_stats
     let volume_per_side: Vec<Value> = market.options.iter()
@@ -303,7 +457,7 @@ pub async fn get_market_prices(
                 let entry_price = prices.get(0).copied().unwrap_or(0.5);
                 
                 // For constant product: shares_out = x - k/(y + amount_after_fee)
-                let fee = amount * crate::market_resolve::cpmm::LP_FEE_RATE;
+                let fee = amount * pool.fee_rate;
                 let amount_after_fee = amount - fee;
                 
                 if pool.reserves.len() == 2 {
@@ -350,7 +504,7 @@ pub async fn get_market_prices(
                 "lp_token_supply": pool.total_lp_tokens,
             },
             "price_impacts": price_impacts,
-            "fee_rate": crate::market_resolve::cpmm::LP_FEE_RATE,
+            "fee_rate": pool.fee_rate,
         })))
     } else {
         // Fallback to static odds
@@ -390,16 +544,31 @@ pub async fn get_market_prices(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/markets",
+    tag = "markets",
+    request_body = CreateMarketRequest,
+    responses(
+        (status = 200, description = "Market created", body = Object),
+        (status = 400, description = "Invalid market definition", body = ApiError),
+    )
+)]
 pub async fn create_market(
     State(state): State<SharedState>,
     Json(payload): Json<CreateMarketRequest>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     // Use source ID or generate new UUID
     let id = payload.source.clone().unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
     
     // Validate outcomes
     if payload.outcomes.is_empty() {
-        return Ok(Json(json!({ "success": false, "error": "At least one outcome required" })));
+        return Err(ApiError::BadRequest("At least one outcome required".to_string()));
+    }
+    
+    // Validate machine-checkable resolution rules
+    if let Some(rules) = payload.resolution_rules.as_ref().and_then(|r| r.outcome_rules.as_ref()) {
+        CompiledRules::compile(rules, &payload.outcomes)?;
     }
     
     // Category defaults to "general" if not provided
@@ -409,8 +578,11 @@ pub async fn create_market(
     let escrow_address = format!("escrow:{}", &id);
     
     // === MINT LIQUIDITY ON L1 (before acquiring lock) ===
-    let liquidity_amount = VIABILITY_THRESHOLD; // 10,000 BB tokens
-    let l1_mint_result = mint_liquidity_on_l1(&escrow_address, liquidity_amount).await;
+    let (liquidity_amount, lp_fee_rate, l1) = {
+        let app = state.lock().unwrap();
+        (app.config.markets.viability_threshold, app.config.market_params(&id).lp_fee_rate, app.l1.clone())
+    };
+    let l1_mint_result = l1.mint(&escrow_address, liquidity_amount).await;
     
     // Now acquire the lock after async call
     let mut app = state.lock().unwrap();
@@ -440,7 +612,7 @@ pub async fn create_market(
         liquidity_amount,
         payload.outcomes.clone(),
         &escrow_address, // Initial LP is the market escrow
-    );
+    ).with_fee_rate(lp_fee_rate);
     let initial_prices = cpmm_pool.calculate_prices();
     market.cpmm_pool = Some(cpmm_pool);
     
@@ -456,42 +628,23 @@ pub async fn create_market(
     // Use CPMM prices as initial probabilities (dynamic odds!)
     market.initial_probabilities = initial_prices.clone();
     
+    state.books.open(&id, market.cpmm_pool.clone());
     app.markets.insert(id.clone(), market);
+    app.reindex_market(&id);
     
     // === RECORD TO LEDGER ===
     let market_tx = Transaction::market_created(&id, &payload.title, liquidity_amount);
     app.ledger.record(market_tx);
     
     // === PERSIST TO RSS FILE ===
-    let rss_event = RssEvent {
-        title: payload.title.clone(),
-        description: payload.description.clone(),
-        source: payload.source.clone(),
-        category: payload.category.clone(),
-        tags: payload.tags.clone().unwrap_or_default(),
-        market_type: payload.market_type.clone().unwrap_or_else(|| "binary".to_string()),
-        outcomes: payload.outcomes.clone(),
-        initial_probabilities: Some(initial_prices.clone()),
-        source_url: payload.source_url.clone().unwrap_or_default(),
-        image_url: payload.image_url.clone(),
-        dates: EventDates {
-            published: payload.dates.as_ref()
-                .and_then(|d| d.published.clone())
-                .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
-            freeze: payload.dates.as_ref().and_then(|d| d.freeze.clone()),
-            resolution: payload.dates.as_ref().and_then(|d| d.resolution.clone()),
-        },
-        resolution_rules: payload.resolution_rules.as_ref().map(|r| RssResolutionRules {
-            provider: r.provider.clone(),
-            data_source: r.data_source.clone(),
-            conditions: r.conditions.clone().unwrap_or_default(),
-        }),
-        market_id: id.clone(),
-        added_to_ledger: true,
+    let (rss_event, created_at) = {
+        let market = &app.markets[&id];
+        (market.to_rss_event(), market.created_at)
     };
+    app.feed.record_new_market(&rss_event, created_at);
     
-    // Write to rss/events/ directory
-    let rss_result = write_rss_event_to_file(&rss_event, "rss/events");
+    // Write to the market events directory
+    let rss_result = write_rss_event_to_file(&rss_event, &app.config.markets.events_dir);
     let rss_file = match &rss_result {
         Ok(path) => {
             app.log_activity("💾", "RSS", &format!("Saved: {}", path));
@@ -521,51 +674,31 @@ pub async fn create_market(
     })))
 }
 
-/// Mint liquidity tokens on L1 blockchain
-async fn mint_liquidity_on_l1(to: &str, amount: f64) -> Result<String, String> {
-    let client = reqwest::Client::new();
-    
-    let mint_payload = json!({
-        "to": to,
-        "amount": amount
-    });
-    
-    let response = client
-        .post("http://localhost:8080/admin/mint")
-        .header("Content-Type", "application/json")
-        .json(&mint_payload)
-        .send()
-        .await
-        .map_err(|e| format!("L1 connection failed: {}", e))?;
-    
-    if response.status().is_success() {
-        let body: Value = response.json().await
-            .map_err(|e| format!("Failed to parse L1 response: {}", e))?;
-        
-        Ok(body.get("tx_hash")
-            .or_else(|| body.get("hash"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string())
-    } else {
-        Err(format!("L1 mint failed with status: {}", response.status()))
-    }
-}
-
 /// Initialize liquidity for all existing markets that don't have CPMM pools
 /// POST /markets/initial-liquidity
+#[utoipa::path(
+    post,
+    path = "/markets/initial-liquidity",
+    tag = "markets",
+    responses(
+        (status = 200, description = "Per-market initialization report", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not a dealer", body = ApiError),
+    )
+)]
 pub async fn initialize_all_market_liquidity(
     State(state): State<SharedState>,
+    auth: Authorized<DealerRole>,
 ) -> Json<Value> {
-    let liquidity_amount = VIABILITY_THRESHOLD; // 10,000 BB per market
+    let liquidity_amount = state.lock().unwrap().config.markets.viability_threshold;
     let mut initialized: Vec<Value> = Vec::new();
     let mut skipped: Vec<Value> = Vec::new();
     let mut failed: Vec<Value> = Vec::new();
     
     // Phase 1: Collect markets that need initialization (inside lock)
-    let markets_to_init: Vec<(String, String, Vec<String>)> = {
+    let (l1, markets_to_init) = {
         let app = state.lock().unwrap();
-        app.markets.iter()
+        let markets: Vec<(String, String, Vec<String>)> = app.markets.iter()
             .filter_map(|(id, market)| {
                 if market.cpmm_pool.is_some() {
                     None // Already has pool
@@ -573,7 +706,8 @@ pub async fn initialize_all_market_liquidity(
                     Some((id.clone(), market.title.clone(), market.options.clone()))
                 }
             })
-            .collect()
+            .collect();
+        (app.l1.clone(), markets)
     };
     
     // Collect skipped markets
@@ -595,10 +729,11 @@ pub async fn initialize_all_market_liquidity(
         let escrow_address = format!("escrow:{}", &market_id);
         
         // Mint on L1 (no lock held)
-        let l1_result = mint_liquidity_on_l1(&escrow_address, liquidity_amount).await;
+        let l1_result = l1.mint(&escrow_address, liquidity_amount).await;
         
         // Phase 3: Update market with CPMM pool (inside lock)
         let mut app = state.lock().unwrap();
+        let lp_fee_rate = app.config.market_params(&market_id).lp_fee_rate;
         
         if let Some(market) = app.markets.get_mut(&market_id) {
             // Initialize CPMM pool
@@ -606,10 +741,11 @@ pub async fn initialize_all_market_liquidity(
                 liquidity_amount,
                 options.clone(),
                 &escrow_address,
-            );
+            ).with_fee_rate(lp_fee_rate);
             let prices = cpmm_pool.calculate_prices();
             market.cpmm_pool = Some(cpmm_pool);
             market.initial_probabilities = prices.clone();
+            state.books.open(&market_id, market.cpmm_pool.clone());
             
             let l1_status = match &l1_result {
                 Ok(tx_hash) => {
@@ -632,6 +768,8 @@ pub async fn initialize_all_market_liquidity(
                 &format!("bulk_init_{}", market_id)
             );
             app.ledger.record(liquidity_tx);
+            app.reindex_market(&market_id);
+            app.publish_odds(&market_id, None, chrono::Utc::now().timestamp() as u64);
             
             initialized.push(json!({
                 "market_id": market_id,
@@ -651,8 +789,16 @@ pub async fn initialize_all_market_liquidity(
     }
     
     // Final summary
-    let app = state.lock().unwrap();
+    let mut app = state.lock().unwrap();
     let total_markets = app.markets.len();
+    let market_ids = |reports: &[Value]| reports.iter().map(|r| r["market_id"].clone()).collect::<Vec<_>>();
+    app.audit(
+        &auth.request,
+        "BULK_LIQUIDITY",
+        &format!("Minted {} BB into {} pools ({} failed)", liquidity_amount, initialized.len(), failed.len()),
+        json!({ "markets_without_pool": initialized.len() + failed.len() }),
+        json!({ "initialized": market_ids(&initialized), "failed": market_ids(&failed), "liquidity_per_market": liquidity_amount }),
+    );
     
     Json(json!({
         "success": true,
@@ -671,7 +817,7 @@ pub async fn initialize_all_market_liquidity(
 }
 
 /// Request body for initializing liquidity on a specific market
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct InitLiquidityRequest {
     /// Amount of BB tokens to add (minimum 10,000, no maximum)
     pub amount: Option<f64>,
@@ -693,59 +839,73 @@ pub struct InitLiquidityRequest {
 /// Body:
 ///   { "amount": 15000, "funder": "L1_xxx..." }  // User-funded
 ///   { "amount": 10000, "house_funded": true }   // Oracle-funded (admin mint)
+#[utoipa::path(
+    post,
+    path = "/markets/initial-liquidity/{market_id}",
+    tag = "markets",
+    params(
+        ("market_id" = String, Path, description = "Market ID"),
+    ),
+    request_body = InitLiquidityRequest,
+    responses(
+        (status = 200, description = "Pool initialized", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not a dealer", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+        (status = 409, description = "Pool already exists", body = ApiError),
+        (status = 500, description = "Internal error", body = ApiError),
+    )
+)]
 pub async fn initialize_market_liquidity(
     State(state): State<SharedState>,
     Path(market_id_raw): Path<String>,
-    Json(payload): Json<InitLiquidityRequest>,
-) -> Json<Value> {
+    auth: Authorized<DealerRole, InitLiquidityRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let payload = &auth.body;
     // Strip .rss extension if present
     let market_id = market_id_raw
         .strip_suffix(".rss")
         .unwrap_or(&market_id_raw)
         .to_string();
     
-    // Validate amount (minimum 10,000 BB, no maximum)
-    let amount = payload.amount.unwrap_or(VIABILITY_THRESHOLD);
-    if amount < VIABILITY_THRESHOLD {
-        return Json(json!({
-            "success": false,
-            "error": format!("Minimum liquidity is {} BB", VIABILITY_THRESHOLD),
-            "minimum": VIABILITY_THRESHOLD,
+    // Validate amount (minimum is the viability threshold, no maximum)
+    let minimum = state.lock().unwrap().config.markets.viability_threshold;
+    let amount = payload.amount.unwrap_or(minimum);
+    if amount < minimum {
+        return Err(ApiError::BadRequest(format!("Minimum liquidity is {} BB", minimum)).with(json!({
+            "minimum": minimum,
             "provided": amount
-        }));
+        })));
     }
     
     // Check if market exists and get info
-    let (market_exists, already_has_pool, title, options) = {
+    let (l1, market_exists, already_has_pool, title, options) = {
         let app = state.lock().unwrap();
         match app.markets.get(&market_id) {
             Some(market) => (
+                app.l1.clone(),
                 true,
                 market.cpmm_pool.is_some(),
                 market.title.clone(),
                 market.options.clone(),
             ),
-            None => (false, false, String::new(), Vec::new()),
+            None => (app.l1.clone(), false, false, String::new(), Vec::new()),
         }
     };
     
     if !market_exists {
-        return Json(json!({
-            "success": false,
-            "error": "Market not found",
-            "market_id": market_id,
+        return Err(ApiError::MarketNotFound(market_id).with(json!({
             "hint": "Check available markets at GET /markets"
-        }));
+        })));
     }
     
     if already_has_pool {
-        return Json(json!({
-            "success": false,
-            "error": "Market already has CPMM pool initialized",
+        return Err(ApiError::Conflict("Market already has CPMM pool initialized".to_string()).with(json!({
             "market_id": market_id,
             "title": title,
             "hint": "Future: Adding liquidity to existing pools coming soon"
-        }));
+        })));
     }
     
     let escrow_address = format!("escrow:{}", &market_id);
@@ -753,20 +913,21 @@ pub async fn initialize_market_liquidity(
     // Determine funding source and execute
     let (l1_result, funding_type, funder_display) = if payload.house_funded {
         // Oracle-funded: Admin mints tokens
-        let result = mint_liquidity_on_l1(&escrow_address, amount).await;
+        let result = l1.mint(&escrow_address, amount).await;
         (result, "oracle_mint", "ORACLE".to_string())
     } else if let Some(ref funder) = payload.funder {
         // User-funded: Transfer from user's L1 balance to escrow
-        let result = transfer_l1_to_escrow(funder, &escrow_address, amount).await;
+        let result = l1.transfer(funder, &escrow_address, amount).await;
         (result, "user_funded", funder.clone())
     } else {
         // Default to oracle-funded if no funder specified
-        let result = mint_liquidity_on_l1(&escrow_address, amount).await;
+        let result = l1.mint(&escrow_address, amount).await;
         (result, "oracle_mint", "ORACLE".to_string())
     };
     
     // Update market with CPMM pool
     let mut app = state.lock().unwrap();
+    let lp_fee_rate = app.config.market_params(&market_id).lp_fee_rate;
     
     if let Some(market) = app.markets.get_mut(&market_id) {
         // Initialize CPMM pool
@@ -774,10 +935,11 @@ pub async fn initialize_market_liquidity(
             amount,
             options.clone(),
             &escrow_address,
-        );
+        ).with_fee_rate(lp_fee_rate);
         let prices = cpmm_pool.calculate_prices();
         market.cpmm_pool = Some(cpmm_pool);
         market.initial_probabilities = prices.clone();
+        state.books.open(&market_id, market.cpmm_pool.clone());
         
         let l1_status = match &l1_result {
             Ok(tx_hash) => {
@@ -793,10 +955,13 @@ pub async fn initialize_market_liquidity(
             }
         };
         
-        app.log_activity("🎰", "CPMM", &format!(
-            "Initialized pool for: {} ({} BB from {})", 
-            title, amount, funder_display
-        ));
+        app.audit(
+            &auth.request,
+            "CPMM_INIT",
+            &format!("Initialized pool for: {} ({} BB from {})", title, amount, funder_display),
+            json!({ "market_id": market_id, "pool": null }),
+            json!({ "market_id": market_id, "liquidity": amount, "funder": funder_display, "odds": prices }),
+        );
         
         // === RECORD TO LEDGER ===
         let liquidity_tx = Transaction::liquidity_added(
@@ -806,8 +971,10 @@ pub async fn initialize_market_liquidity(
             &format!("cpmm_init_{}", market_id)
         );
         app.ledger.record(liquidity_tx);
+        app.reindex_market(&market_id);
+        app.publish_odds(&market_id, None, chrono::Utc::now().timestamp() as u64);
         
-        Json(json!({
+        Ok(Json(json!({
             "success": true,
             "market_id": market_id,
             "title": title,
@@ -821,46 +988,11 @@ pub async fn initialize_market_liquidity(
                 "amount": amount
             },
             "l1_transaction": l1_status
-        }))
+        })))
     } else {
-        Json(json!({
-            "success": false,
-            "error": "Market disappeared during initialization",
+        Err(ApiError::Internal("Market disappeared during initialization".to_string()).with(json!({
             "market_id": market_id
-        }))
-    }
-}
-
-/// Transfer tokens from user's L1 balance to market escrow
-async fn transfer_l1_to_escrow(from: &str, to_escrow: &str, amount: f64) -> Result<String, String> {
-    let client = reqwest::Client::new();
-    
-    let transfer_payload = json!({
-        "from": from,
-        "to": to_escrow,
-        "amount": amount
-    });
-    
-    // Try L1 transfer endpoint
-    let response = client
-        .post("http://localhost:8080/transfer")
-        .header("Content-Type", "application/json")
-        .json(&transfer_payload)
-        .send()
-        .await
-        .map_err(|e| format!("L1 connection failed: {}", e))?;
-    
-    if response.status().is_success() {
-        let body: Value = response.json().await
-            .map_err(|e| format!("Failed to parse L1 response: {}", e))?;
-        
-        Ok(body.get("tx_hash")
-            .or_else(|| body.get("hash"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("transfer_ok")
-            .to_string())
-    } else {
-        Err(format!("L1 transfer failed with status: {} - ensure funder has sufficient L1 balance", response.status()))
+        })))
     }
 }
 
@@ -868,11 +1000,9 @@ async fn transfer_l1_to_escrow(from: &str, to_escrow: &str, amount: f64) -> Resu
 // DEALER / MARKET MAKER ENDPOINTS
 // ============================================================================
 
-/// Request for dealer to fund all markets
-#[derive(Debug, Deserialize)]
+/// Request for dealer to fund all markets (the signing dealer is debited)
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct DealerFundAllRequest {
-    /// Dealer's wallet address
-    pub dealer_address: String,
     /// Amount of BB per market (default: 2083)
     pub amount_per_market: Option<f64>,
     /// Only fund markets without existing pools
@@ -885,16 +1015,30 @@ fn default_true() -> bool { true }
 /// POST /dealer/fund-all-markets
 /// Dealer funds ALL markets with CPMM liquidity from their L2 balance
 /// The dealer becomes the LP (market maker) and earns fees on trades
+#[utoipa::path(
+    post,
+    path = "/dealer/fund-all-markets",
+    tag = "dealer",
+    request_body = DealerFundAllRequest,
+    responses(
+        (status = 200, description = "Per-market funding report", body = Object),
+        (status = 400, description = "Insufficient balance", body = ApiError),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not a dealer", body = ApiError),
+    )
+)]
 pub async fn dealer_fund_all_markets(
     State(state): State<SharedState>,
-    Json(req): Json<DealerFundAllRequest>,
-) -> Json<Value> {
+    auth: Authorized<DealerRole, DealerFundAllRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let req = &auth.body;
+    let dealer = auth.address();
     let amount_per_market = req.amount_per_market.unwrap_or(2083.0);
     
     // Phase 1: Check dealer balance and collect markets (inside lock)
     let (dealer_balance, markets_to_fund): (f64, Vec<(String, String, Vec<String>, bool)>) = {
         let app = state.lock().unwrap();
-        let balance = app.ledger.balance(&req.dealer_address);
+        let balance = app.ledger.balance(&dealer);
         
         let markets: Vec<_> = app.markets.iter()
             .map(|(id, market)| {
@@ -915,15 +1059,11 @@ pub async fn dealer_fund_all_markets(
     
     // Validate dealer has enough balance
     if dealer_balance < total_required {
-        return Json(json!({
-            "success": false,
-            "error": "Insufficient dealer balance",
-            "dealer_address": req.dealer_address,
-            "dealer_balance": dealer_balance,
-            "required": total_required,
+        return Err(ApiError::InsufficientBalance { available: dealer_balance, required: total_required }.with(json!({
+            "dealer_address": dealer,
             "markets_to_fund": markets_to_process.len(),
             "amount_per_market": amount_per_market
-        }));
+        })));
     }
     
     // Phase 2: Fund each market
@@ -944,7 +1084,7 @@ pub async fn dealer_fund_all_markets(
         let mut app = state.lock().unwrap();
         
         // Check dealer still has balance
-        let current_balance = app.ledger.balance(&req.dealer_address);
+        let current_balance = app.ledger.balance(&dealer);
         if current_balance < amount_per_market {
             failed.push(json!({
                 "market_id": market_id,
@@ -956,30 +1096,27 @@ pub async fn dealer_fund_all_markets(
         }
         
         // Deduct from dealer balance
-        if let Some(addr) = app.ledger.resolve(&req.dealer_address) {
-            if let Some(bal) = app.ledger.balances.get_mut(&addr) {
-                bal.apply(-amount_per_market);
-            }
-        }
+        app.ledger.debit(&dealer, amount_per_market);
         
         // Initialize or add to CPMM pool with dealer as LP
+        let lp_fee_rate = app.config.market_params(market_id).lp_fee_rate;
         if let Some(market) = app.markets.get_mut(market_id) {
             if market.cpmm_pool.is_none() {
                 // Create new pool with dealer as LP
                 let pool = CPMMPool::new(
                     amount_per_market,
                     options.clone(),
-                    &req.dealer_address, // Dealer is the LP!
-                );
+                    &dealer, // Dealer is the LP!
+                ).with_fee_rate(lp_fee_rate);
                 let prices = pool.calculate_prices();
                 market.cpmm_pool = Some(pool);
                 market.initial_probabilities = prices.clone();
-                market.launched_by = Some(req.dealer_address.clone());
+                market.launched_by = Some(dealer.clone());
                 
                 // Record to ledger
                 let tx = Transaction::liquidity_added(
                     market_id,
-                    &req.dealer_address,
+                    &dealer,
                     amount_per_market,
                     &format!("dealer_lp_{}", market_id)
                 );
@@ -1000,13 +1137,13 @@ pub async fn dealer_fund_all_markets(
             } else {
                 // Add liquidity to existing pool
                 if let Some(ref mut pool) = market.cpmm_pool {
-                    match pool.add_liquidity(&req.dealer_address, amount_per_market) {
+                    match pool.add_liquidity(&dealer, amount_per_market) {
                         Ok(share) => {
                             let prices = pool.calculate_prices();
                             
                             let tx = Transaction::liquidity_added(
                                 market_id,
-                                &req.dealer_address,
+                                &dealer,
                                 amount_per_market,
                                 &format!("dealer_add_lp_{}", market_id)
                             );
@@ -1027,11 +1164,7 @@ pub async fn dealer_fund_all_markets(
                         }
                         Err(e) => {
                             // Refund dealer
-                            if let Some(addr) = app.ledger.resolve(&req.dealer_address) {
-                                if let Some(bal) = app.ledger.balances.get_mut(&addr) {
-                                    bal.apply(amount_per_market);
-                                }
-                            }
+                            app.ledger.credit(&dealer, amount_per_market);
                             failed.push(json!({
                                 "market_id": market_id,
                                 "title": title,
@@ -1042,17 +1175,38 @@ pub async fn dealer_fund_all_markets(
                 }
             }
         }
+        
+        // The market's actor trades against the funded pool
+        if let Some(pool) = app.markets.get(market_id).and_then(|m| m.cpmm_pool.clone()) {
+            state.books.open(market_id, Some(pool));
+        }
+        app.reindex_market(market_id);
+        app.publish_odds(market_id, None, chrono::Utc::now().timestamp() as u64);
     }
     
     // Final summary
-    let app = state.lock().unwrap();
-    let new_balance = app.ledger.balance(&req.dealer_address);
+    let mut app = state.lock().unwrap();
+    let new_balance = app.ledger.balance(&dealer);
     let total_funded = funded.len() as f64 * amount_per_market;
+    app.audit(
+        &auth.request,
+        "DEALER_FUND_ALL",
+        &format!(
+            "Funded {} markets with {} BB each ({} skipped, {} failed)",
+            funded.len(), amount_per_market, skipped.len(), failed.len()
+        ),
+        json!({ "dealer": dealer, "balance": dealer_balance }),
+        json!({
+            "dealer": dealer,
+            "balance": new_balance,
+            "funded": funded.iter().map(|m| m["market_id"].clone()).collect::<Vec<_>>()
+        }),
+    );
     
-    Json(json!({
+    Ok(Json(json!({
         "success": true,
         "dealer": {
-            "address": req.dealer_address,
+            "address": dealer,
             "initial_balance": dealer_balance,
             "new_balance": new_balance,
             "total_spent": total_funded
@@ -1072,11 +1226,22 @@ pub async fn dealer_fund_all_markets(
             "description": "Dealer earns LP fees on all trades in funded markets",
             "claim_on_resolution": "Remaining pool liquidity + collected fees"
         }
-    }))
+    })))
 }
 
 /// GET /dealer/positions/:address
 /// Get dealer's LP positions across all markets
+#[utoipa::path(
+    get,
+    path = "/dealer/positions/{address}",
+    tag = "dealer",
+    params(
+        ("address" = String, Path, description = "Dealer address"),
+    ),
+    responses(
+        (status = 200, description = "Dealer LP positions", body = Object),
+    )
+)]
 pub async fn get_dealer_positions(
     State(state): State<SharedState>,
     Path(dealer_address): Path<String>,
@@ -1138,30 +1303,52 @@ pub async fn get_dealer_positions(
 
 // ===== BALANCE ENDPOINTS =====
 
+#[utoipa::path(
+    get,
+    path = "/balance/{account}",
+    tag = "ledger",
+    params(
+        ("account" = String, Path, description = "Account name or address"),
+    ),
+    responses(
+        (status = 200, description = "Balance", body = Object),
+    )
+)]
 pub async fn get_balance(State(state): State<SharedState>, Path(account): Path<String>) -> Json<Value> {
-    let app = state.lock().unwrap();
-    let balance = app.ledger.balance(&account);
-    let confirmed = app.ledger.confirmed_balance(&account);
-    let pending = app.ledger.pending(&account);
-    let locked = app.ledger.locked(&account);
+    let ledger = state.ledger.read();
+    let balance = ledger.balance(&account);
+    let confirmed = ledger.confirmed_balance(&account);
+    let pending = ledger.pending(&account);
+    let locked = ledger.locked(&account);
     Json(json!({ 
         "account": account, 
         "balance": balance,
         "confirmed": confirmed,
         "pending": pending,
         "locked": locked,
-        "available": balance - locked
+        "available": balance
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/balance/details/{account}",
+    tag = "ledger",
+    params(
+        ("account" = String, Path, description = "Account name or address"),
+    ),
+    responses(
+        (status = 200, description = "Available, locked and L1 balances", body = Object),
+    )
+)]
 pub async fn get_balance_details(State(state): State<SharedState>, Path(account): Path<String>) -> Json<Value> {
-    let app = state.lock().unwrap();
-    let addr = app.ledger.resolve(&account).unwrap_or(account.clone());
-    let locked = app.ledger.locked(&account);
-    let available = app.ledger.balance(&account);
+    let ledger = state.ledger.read();
+    let addr = ledger.resolve(&account).unwrap_or(account.clone());
+    let locked = ledger.locked(&account);
+    let available = ledger.balance(&account);
     
     // Get breakdown if available
-    if let Some(breakdown) = app.ledger.balance_breakdown(&account) {
+    if let Some(breakdown) = ledger.balance_breakdown(&account) {
         return Json(json!({
             "success": true,
             "account": account,
@@ -1186,38 +1373,51 @@ pub async fn get_balance_details(State(state): State<SharedState>, Path(account)
         "success": true,
         "account": account,
         "address": addr,
-        "confirmed_balance": app.ledger.confirmed_balance(&account),
-        "pending_delta": app.ledger.pending(&account),
+        "confirmed_balance": ledger.confirmed_balance(&account),
+        "pending_delta": ledger.pending(&account),
         "available_balance": available,
         "locked_in_bets": locked,
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/transfer",
+    tag = "ledger",
+    request_body = TransferRequest,
+    responses(
+        (status = 200, description = "Transfer applied", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
 pub async fn transfer(
     State(state): State<SharedState>,
     Json(payload): Json<TransferRequest>,
-) -> Result<Json<Value>, StatusCode> {
-    let mut app = state.lock().unwrap();
-    match app.ledger.transfer(&payload.from, &payload.to, payload.amount, "") {
-        Ok(_) => {
-            app.log_activity("💸", "TRANSFER", &format!("{} → {} | {} BB", payload.from, payload.to, payload.amount));
-            Ok(Json(json!({ "success": true })))
-        }
-        Err(e) => Ok(Json(json!({ "success": false, "error": e }))),
-    }
+) -> Result<Json<Value>, ApiError> {
+    state.ledger.transfer(&payload.from, &payload.to, payload.amount, "")?;
+    state.ledger.log_activity("💸", "TRANSFER", &format!("{} → {} | {} BB", payload.from, payload.to, payload.amount));
+    Ok(Json(json!({ "success": true })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/ledger",
+    tag = "ledger",
+    responses(
+        (status = 200, description = "Recent activity log", body = Object),
+    )
+)]
 pub async fn get_ledger_activity(State(state): State<SharedState>) -> Json<Value> {
-    let app = state.lock().unwrap();
-    Json(json!({ "activity": app.blockchain_activity }))
+    Json(json!({ "activity": state.ledger.activity() }))
 }
 
 // ===== PUBLIC LEDGER TRANSACTIONS ENDPOINT =====
 
 /// Query params for ledger transactions
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LedgerQuery {
-    /// Filter by transaction type: "Bet", "Transfer", "Deposit", "Withdraw", "Payout", "AccountCreated"
+    /// Filter by transaction type, e.g. "Bet", "Transfer", "OrderFill" (case-insensitive, `_` optional)
     #[serde(rename = "type")]
     pub tx_type: Option<String>,
     /// Filter by market ID
@@ -1232,22 +1432,34 @@ pub struct LedgerQuery {
     pub sort_by: Option<String>,
     /// Sort order: "asc", "desc" (default: "desc")
     pub order: Option<String>,
-    /// Limit results (default: 100, max: 1000)
+    /// Page size (default: 100, max: 1000)
     pub limit: Option<usize>,
-    /// Offset for pagination (default: 0)
-    pub offset: Option<usize>,
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
     /// Filter by layer: "L1", "L2", "Bridge"
     pub layer: Option<String>,
     /// Filter by fund status: "Available", "Locked", "Pending", "Settled", "Bridging"
     pub fund_status: Option<String>,
+    /// Filter by outcome index
+    pub outcome: Option<usize>,
 }
 
 /// Public ledger transactions endpoint with filtering, sorting, and pagination
 /// Now aggregates from BOTH ledger.transactions AND market.bets for complete picture
+#[utoipa::path(
+    get,
+    path = "/ledger/transactions",
+    tag = "ledger",
+    params(LedgerQuery),
+    responses(
+        (status = 200, description = "Page of filtered ledger transactions", body = Object),
+        (status = 400, description = "Invalid cursor or filter", body = ApiError),
+    )
+)]
 pub async fn get_ledger_transactions(
     State(state): State<SharedState>,
     Query(params): Query<LedgerQuery>,
-) -> Json<Value> {
+) -> Result<Json<Value>, ApiError> {
     let app = state.lock().unwrap();
     
     // Reconstruct transactions from markets to get the real data
@@ -1258,114 +1470,48 @@ pub async fn get_ledger_transactions(
     let mut all_txs: Vec<Transaction> = market_txs;
     
     // Add non-bet transactions from ledger (transfers, deposits that weren't from markets)
-    for tx in &app.ledger.transactions {
+    for tx in &app.ledger.read().transactions {
         if tx.tx_type != TxType::Bet && tx.tx_type != TxType::MarketCreated {
             all_txs.push(tx.clone());
         }
     }
     
-    // Convert to references for filtering
-    let mut txs: Vec<&Transaction> = all_txs.iter().collect();
-    
-    // Filter by type
-    if let Some(ref type_filter) = params.tx_type {
-        let tx_type = match type_filter.to_lowercase().as_str() {
-            "bet" => Some(TxType::Bet),
-            "transfer" => Some(TxType::Transfer),
-            "deposit" => Some(TxType::Deposit),
-            "withdraw" => Some(TxType::Withdraw),
-            "payout" => Some(TxType::Payout),
-            "accountcreated" | "account_created" => Some(TxType::AccountCreated),
-            "marketcreated" | "market_created" => Some(TxType::MarketCreated),
-            "marketresolved" | "market_resolved" => Some(TxType::MarketResolved),
-            "bridgeinitiate" | "bridge_initiate" => Some(TxType::BridgeInitiate),
-            "bridgecomplete" | "bridge_complete" => Some(TxType::BridgeComplete),
-            _ => None,
-        };
-        if let Some(t) = tx_type {
-            txs.retain(|tx| tx.tx_type == t);
-        }
-    }
-    
-    // Filter by layer
-    if let Some(ref layer_filter) = params.layer {
-        let layer = match layer_filter.to_uppercase().as_str() {
-            "L1" => Some(Layer::L1),
-            "L2" => Some(Layer::L2),
-            "BRIDGE" => Some(Layer::Bridge),
-            _ => None,
-        };
-        if let Some(l) = layer {
-            txs.retain(|tx| tx.layer == l);
-        }
-    }
-    
-    // Filter by fund status
-    if let Some(ref status_filter) = params.fund_status {
-        let status = match status_filter.to_lowercase().as_str() {
-            "available" => Some(FundStatus::Available),
-            "locked" => Some(FundStatus::Locked),
-            "pending" => Some(FundStatus::Pending),
-            "settled" => Some(FundStatus::Settled),
-            "bridging" => Some(FundStatus::Bridging),
-            _ => None,
-        };
-        if let Some(s) = status {
-            txs.retain(|tx| tx.fund_status == s);
-        }
-    }
-    
-    // Filter by market_id
-    if let Some(ref market_id) = params.market_id {
-        txs.retain(|tx| tx.market_id.as_ref() == Some(market_id));
-    }
-    
-    // Filter by account (from or to)
-    if let Some(ref account) = params.account {
-        let resolved = app.ledger.resolve(account);
-        txs.retain(|tx| {
-            tx.from == *account || 
-            tx.to.as_ref() == Some(account) ||
-            resolved.as_ref().map(|r| &tx.from == r || tx.to.as_ref() == Some(r)).unwrap_or(false)
-        });
-    }
-    
-    // Filter by timestamp range
-    if let Some(from_ts) = params.from_timestamp {
-        txs.retain(|tx| tx.timestamp >= from_ts);
-    }
-    if let Some(to_ts) = params.to_timestamp {
-        txs.retain(|tx| tx.timestamp <= to_ts);
-    }
-    
-    // Sort
+    let tx_type = parse_filter("type", params.tx_type.as_deref(), TxType::parse)?;
+    let layer = parse_filter("layer", params.layer.as_deref(), Layer::parse)?;
+    let fund_status = parse_filter("fund_status", params.fund_status.as_deref(), FundStatus::parse)?;
+    let order = parse_filter("order", params.order.as_deref(), SortOrder::parse)?.unwrap_or_default();
     let sort_by = params.sort_by.as_deref().unwrap_or("timestamp");
-    let order_desc = params.order.as_deref().unwrap_or("desc") == "desc";
-    
-    match sort_by {
-        "amount" => {
-            if order_desc {
-                txs.sort_by(|a, b| b.amount.partial_cmp(&a.amount).unwrap_or(std::cmp::Ordering::Equal));
-            } else {
-                txs.sort_by(|a, b| a.amount.partial_cmp(&b.amount).unwrap_or(std::cmp::Ordering::Equal));
-            }
-        }
-        _ => { // timestamp
-            if order_desc {
-                txs.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-            } else {
-                txs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
-            }
-        }
+    if sort_by != "timestamp" && sort_by != "amount" {
+        return Err(PageError::InvalidFilter { field: "sort_by", value: sort_by.to_string() }.into());
     }
-    
-    let total = txs.len();
-    
-    // Pagination
-    let limit = params.limit.unwrap_or(100).min(1000);
-    let offset = params.offset.unwrap_or(0);
-    let txs: Vec<_> = txs.into_iter().skip(offset).take(limit).collect();
-    
+    let range = TimeRange::new(params.from_timestamp, params.to_timestamp);
+    let resolved = params.account.as_ref().and_then(|account| app.ledger.resolve(account));
+
+    let txs: Vec<&Transaction> = all_txs.iter()
+        .filter(|tx| tx_type.as_ref().is_none_or(|t| tx.tx_type == *t))
+        .filter(|tx| layer.is_none_or(|l| tx.layer == l))
+        .filter(|tx| fund_status.is_none_or(|s| tx.fund_status == s))
+        .filter(|tx| params.market_id.is_none() || tx.market_id == params.market_id)
+        .filter(|tx| params.outcome.is_none() || tx.outcome == params.outcome)
+        .filter(|tx| match params.account {
+            Some(ref account) => {
+                tx.from == *account ||
+                tx.to.as_ref() == Some(account) ||
+                resolved.as_ref().map(|r| &tx.from == r || tx.to.as_ref() == Some(r)).unwrap_or(false)
+            }
+            None => true,
+        })
+        .filter(|tx| range.contains(tx.timestamp))
+        .collect();
+
+    // Ties on the sort value are broken by id so cursors stay stable
+    let request = PageRequest::new(params.cursor.clone(), params.limit).with_order(order);
+    let page = if sort_by == "amount" {
+        paginate(txs, |tx| (tx.amount, tx.id.clone()), &request)?
+    } else {
+        paginate(txs, |tx| (tx.timestamp, tx.id.clone()), &request)?
+    };
+
     // Calculate comprehensive stats
     let total_bets: usize = all_txs.iter().filter(|t| t.tx_type == TxType::Bet).count();
     let bet_volume: f64 = all_txs.iter()
@@ -1381,7 +1527,7 @@ pub async fn get_ledger_transactions(
     let bridge_count = all_txs.iter().filter(|t| t.layer == Layer::Bridge).count();
     
     // Format transactions for response with full L1/L2 tracking
-    let transactions: Vec<Value> = txs.iter().map(|tx| {
+    let transactions: Vec<Value> = page.items.iter().map(|tx| {
         json!({
             "id": tx.id,
             "type": format!("{:?}", tx.tx_type),
@@ -1399,21 +1545,17 @@ pub async fn get_ledger_transactions(
             "l1_settled": tx.l1_settled,
             "l1_tx_hash": tx.l1_tx_hash,
             "block_number": tx.block_number,
+            "block_index": tx.block_index,
             "description": tx.description
         })
     }).collect();
     
-    Json(json!({
+    Ok(Json(json!({
         "success": true,
         "transactions": transactions,
-        "pagination": {
-            "total": total,
-            "limit": limit,
-            "offset": offset,
-            "has_more": offset + limit < total
-        },
+        "pagination": page.meta(),
         "stats": {
-            "total_accounts": app.ledger.balances.len(),
+            "total_accounts": app.ledger.read().balances.len(),
             "total_transactions": all_txs.len(),
             "current_block": all_txs.len() as u64,
             "total_bets": total_bets,
@@ -1423,10 +1565,18 @@ pub async fn get_ledger_transactions(
             "l2_transactions": l2_count,
             "bridge_transactions": bridge_count
         }
-    }))
+    })))
 }
 
 /// Unified ledger view - comprehensive overview of all L1/L2 activity
+#[utoipa::path(
+    get,
+    path = "/ledger/unified",
+    tag = "ledger",
+    responses(
+        (status = 200, description = "L1/L2 overview", body = Object),
+    )
+)]
 pub async fn get_unified_ledger(State(state): State<SharedState>) -> Json<Value> {
     let app = state.lock().unwrap();
     
@@ -1536,6 +1686,17 @@ pub async fn get_unified_ledger(State(state): State<SharedState>) -> Json<Value>
 
 // ===== RPC ENDPOINTS =====
 
+#[utoipa::path(
+    get,
+    path = "/rpc/nonce/{address}",
+    tag = "bets",
+    params(
+        ("address" = String, Path, description = "Wallet address"),
+    ),
+    responses(
+        (status = 200, description = "Last used nonce", body = Object),
+    )
+)]
 pub async fn get_nonce(State(state): State<SharedState>, Path(address): Path<String>) -> Json<Value> {
     let app = state.lock().unwrap();
     let last_nonce = app.nonces.get(&address).copied().unwrap_or(0);
@@ -1549,24 +1710,78 @@ pub async fn get_nonce(State(state): State<SharedState>, Path(address): Path<Str
 
 // ===== USER ENDPOINTS =====
 
-pub async fn get_user_bets(State(state): State<SharedState>, Path(account): Path<String>) -> Json<Value> {
-    let app = state.lock().unwrap();
-    let mut bets = Vec::new();
-    for market in app.markets.values() {
-        bets.extend(market.get_bets_for_account(&account));
-    }
-    Json(json!({ "account": account, "bets": bets }))
+/// Query params for an account's bets
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BetsQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default: 100, max: 1000)
+    pub limit: Option<usize>,
+    /// Sort order by time: "asc", "desc" (default: "desc")
+    pub order: Option<String>,
+    /// Filter by market ID
+    pub market_id: Option<String>,
+    /// Filter by outcome index
+    pub outcome: Option<usize>,
+    /// Filter by bet status, e.g. "PENDING" (case-insensitive)
+    pub status: Option<String>,
+    /// Placed at or after (unix seconds)
+    pub from_timestamp: Option<u64>,
+    /// Placed at or before (unix seconds)
+    pub to_timestamp: Option<u64>,
 }
 
-// ===== SETTLEMENT ENDPOINTS (Simplified) =====
+#[utoipa::path(
+    get,
+    path = "/bets/{account}",
+    tag = "bets",
+    params(
+        ("account" = String, Path, description = "Account name or address"),
+        BetsQuery,
+    ),
+    responses(
+        (status = 200, description = "Page of bets placed by the account, newest first", body = Object),
+        (status = 400, description = "Invalid cursor or filter", body = ApiError),
+    )
+)]
+pub async fn get_user_bets(
+    State(state): State<SharedState>,
+    Path(account): Path<String>,
+    Query(params): Query<BetsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let order = parse_filter("order", params.order.as_deref(), SortOrder::parse)?.unwrap_or_default();
+    let range = TimeRange::new(params.from_timestamp, params.to_timestamp);
 
-pub async fn settle_to_l1(State(_state): State<SharedState>) -> Json<Value> {
-    Json(json!({
+    let app = state.lock().unwrap();
+    let bets: Vec<MarketBet> = app.markets.values()
+        .filter(|m| params.market_id.as_ref().is_none_or(|id| m.id == *id))
+        .flat_map(|m| m.get_bets_for_account(&account))
+        .filter(|b| params.outcome.is_none_or(|o| b.outcome == o))
+        .filter(|b| params.status.as_ref().is_none_or(|s| b.status.eq_ignore_ascii_case(s)))
+        .filter(|b| range.contains(b.timestamp))
+        .collect();
+    let request = PageRequest::new(params.cursor.clone(), params.limit).with_order(order);
+    let page = paginate(bets, |b| (b.timestamp, b.id.clone()), &request)?;
+
+    Ok(Json(json!({
         "success": true,
-        "message": "Settlement queued (mock mode)"
-    }))
+        "account": account,
+        "bets": page.items,
+        "pagination": page.meta(),
+    })))
 }
 
+// ===== SETTLEMENT ENDPOINTS (Simplified) =====
+
+#[utoipa::path(
+    get,
+    path = "/settle/status",
+    tag = "settlement",
+    responses(
+        (status = 200, description = "Settlement status", body = Object),
+    )
+)]
 pub async fn get_settlement_status(State(state): State<SharedState>) -> Json<Value> {
     let app = state.lock().unwrap();
     let stats = app.ledger.stats();
@@ -1576,10 +1791,18 @@ pub async fn get_settlement_status(State(state): State<SharedState>) -> Json<Val
         "transactions": stats.transactions,
         "total_bets": stats.total_bets,
         "bet_volume": stats.bet_volume,
-        "l1_mock_mode": app.ledger.mock_mode
+        "l1_mock_mode": app.l1.is_mock()
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/sync",
+    tag = "settlement",
+    responses(
+        (status = 200, description = "Sync result", body = Object),
+    )
+)]
 pub async fn sync_from_l1(State(_state): State<SharedState>) -> Json<Value> {
     Json(json!({
         "success": true,
@@ -1591,15 +1814,36 @@ pub async fn sync_from_l1(State(_state): State<SharedState>) -> Json<Value> {
 // CLOB (Central Limit Order Book) HANDLERS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-use crate::orderbook::{
-    LimitOrder, OrderType, Side, OrderStatus, Fill, Outcome,
-    OrderBookManager, MarketOdds, OddsSource,
-};
-use crate::shares::{SharesManager, SharePosition, ShareBalance, OutcomeIndex};
+use std::collections::HashMap;
+use crate::orderbook::{Fill, LimitOrder, OrderError, OrderStatus, OrderType, Side, Outcome};
+use crate::market_actor::{fill_amounts, fill_parties, TradeError};
+
+/// Map a market actor error onto an API error. `context` prefixes ledger
+/// and share book failures.
+fn trade_error(e: TradeError, context: &str) -> ApiError {
+    match e {
+        TradeError::Settlement(error) => TradeError::Settlement(format!("{}: {}", context, error)).into(),
+        e => e.into(),
+    }
+}
+
+/// Bets, orders and mints are only taken while the market's trading is open:
+/// not once a resolution has been proposed, nor after it resolved or was
+/// refunded
+fn trading_open(app: &crate::app_state::AppState, market_id: &str) -> Result<(), ApiError> {
+    let market = app.markets.get(market_id)
+        .ok_or_else(|| ApiError::MarketNotFound(market_id.to_string()))?;
+    if market.is_resolved || !market.market_status.is_trading_open() {
+        return Err(OrderError::MarketClosed(format!(
+            "{} is {:?}; trading has ended", market_id, market.market_status
+        )).into());
+    }
+    Ok(())
+}
 
 // ===== ORDER REQUEST TYPES =====
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SubmitOrderRequest {
     pub wallet: String,
     pub market_id: String,
@@ -1610,33 +1854,46 @@ pub struct SubmitOrderRequest {
     pub order_type: Option<String>, // "gtc", "ioc", "fok", "market" (default: gtc)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CancelOrderRequest {
     pub wallet: String,
 }
 
 // ===== SUBMIT ORDER HANDLER =====
 /// POST /orders - Submit a limit order to the CLOB
+///
+/// Matching and settlement run on the market's actor. The application lock
+/// is taken for the session check before the order and, once the fills are
+/// known, for what depends on them: each party's session PnL, the market's
+/// trending volume, and its pool and odds. Sessions, the search index and the
+/// markets all live in the application state, so those records stay under
+/// its lock; the activity log is kept beside the ledger instead.
+#[utoipa::path(
+    post,
+    path = "/orders",
+    tag = "orders",
+    request_body = SubmitOrderRequest,
+    responses(
+        (status = 200, description = "Order accepted", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+        (status = 500, description = "Internal error", body = ApiError),
+    )
+)]
 pub async fn submit_order(
     State(state): State<SharedState>,
     Json(req): Json<SubmitOrderRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+) -> Result<Json<Value>, ApiError> {
     // Validate price bounds (1-99 bps = $0.01 - $0.99)
     if req.price_bps < 1 || req.price_bps > 99 {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "Price must be 1-99 (basis points representing $0.01-$0.99)"
-        }))));
+        return Err(ApiError::BadRequest("Price must be 1-99 (basis points representing $0.01-$0.99)".to_string()));
     }
     
     // Parse side
     let side = match req.side.to_lowercase().as_str() {
         "bid" | "buy" => Side::Bid,
         "ask" | "sell" => Side::Ask,
-        _ => return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "Side must be 'bid' or 'ask'"
-        })))),
+        _ => return Err(ApiError::BadRequest("Side must be 'bid' or 'ask'".to_string())),
     };
     
     // Parse order type
@@ -1648,44 +1905,6 @@ pub async fn submit_order(
         _ => OrderType::GTC,
     };
     
-    let mut app = state.lock().unwrap();
-    
-    // Check market exists
-    if !app.markets.contains_key(&req.market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))));
-    }
-    
-    // For asks (selling shares), check if user has shares to sell
-    if side == Side::Ask {
-        let position = app.shares.get_position(&req.wallet, &req.market_id);
-        let available = if req.outcome == 0 { position.yes_shares } else { position.no_shares };
-        if available < req.quantity {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": format!("Insufficient shares: have {}, need {}", available, req.quantity),
-                "available": available,
-                "requested": req.quantity
-            }))));
-        }
-    }
-    
-    // For bids (buying shares), check BB balance
-    if side == Side::Bid {
-        let cost = (req.price_bps as f64 / 100.0) * req.quantity;
-        let balance = app.ledger.balance(&req.wallet);
-        if balance < cost {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": format!("Insufficient balance: have {} BB, need {} BB", balance, cost),
-                "available": balance,
-                "required": cost
-            }))));
-        }
-    }
-    
     // Create the order
     let outcome = Outcome::new(req.outcome as usize);
     let order = match LimitOrder::new(
@@ -1699,37 +1918,51 @@ pub async fn submit_order(
         String::new(), // signature placeholder
     ) {
         Ok(o) => o,
-        Err(e) => return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": format!("{:?}", e)
-        })))),
+        Err(e) => return Err(e.into()),
     };
     let order_id = order.id.clone();
     
-    // Submit to order book
-    let result = app.orderbook.submit_order(order);
-    
-    // Process fills - update ledger and shares
-    for fill in &result.fills {
-        // Transfer BB from buyer to seller (minus fees)
-        let buyer_cost = fill.value * (1.0 + fill.taker_fee / fill.value);
-        let seller_receive = fill.value * (1.0 - fill.maker_fee / fill.value);
+    {
+        let app = state.lock().unwrap();
         
-        // Update ledger
-        let _ = app.ledger.transfer(&fill.taker, "orderbook_escrow", buyer_cost, &fill.id);
-        let _ = app.ledger.transfer("orderbook_escrow", &fill.maker, seller_receive, &fill.id);
+        // Check market exists and is still trading
+        trading_open(&app, &req.market_id)?;
         
-        // Update share positions
-        let outcome_idx = if req.outcome == 0 { OutcomeIndex::YES } else { OutcomeIndex::NO };
-        if side == Side::Bid {
-            // Buyer receives shares
-            app.shares.credit_shares_simple(&fill.taker, &req.market_id, outcome_idx, fill.size);
-            let _ = app.shares.debit_shares_simple(&fill.maker, &req.market_id, outcome_idx, fill.size);
-        } else {
-            // Seller gives up shares
-            let _ = app.shares.debit_shares_simple(&fill.taker, &req.market_id, outcome_idx, fill.size);
-            app.shares.credit_shares_simple(&fill.maker, &req.market_id, outcome_idx, fill.size);
+        // Session limits (expired sessions can't trade, open ones can't overspend)
+        let session_spend = if side == Side::Bid { (req.price_bps as f64 / 100.0) * req.quantity } else { 0.0 };
+        app.check_session_spend(&req.wallet, session_spend)?;
+    }
+    
+    // Escrow, match and settle on the market's actor (shares for asks, BB for bids)
+    let market_book = state.books.open(&req.market_id, None);
+    let result = market_book
+        .submit_order(order)
+        .await
+        .map_err(|e| trade_error(e, "Settlement failed"))?;
+    
+    // Session PnL: the submitter once per order, each counterparty per fill
+    {
+        let mut app = state.lock().unwrap();
+        let (mut spent, mut received) = (0.0, 0.0);
+        for fill in &result.fills {
+            let (buyer, seller) = fill_parties(fill);
+            let (buyer_cost, seller_receive) = fill_amounts(fill);
+            if buyer == req.wallet {
+                spent += buyer_cost;
+                app.record_session_trade(seller, 0.0, seller_receive);
+            } else {
+                received += seller_receive;
+                app.record_session_trade(buyer, buyer_cost, 0.0);
+            }
+        }
+        
+        if !result.fills.is_empty() {
+            app.record_session_trade(&req.wallet, spent, received);
         }
+        for fill in &result.fills {
+            app.market_index.record_trade(&fill.market_id, fill.value, fill.timestamp);
+        }
+        app.publish_odds(&req.market_id, Some(&market_book.snapshot()), chrono::Utc::now().timestamp() as u64);
     }
     
     let fill_status = if result.fills.is_empty() { 
@@ -1737,7 +1970,7 @@ pub async fn submit_order(
     } else { 
         format!("{} filled", result.fills.len()) 
     };
-    app.log_activity("📋", "ORDER", &format!(
+    state.ledger.log_activity("📋", "ORDER", &format!(
         "{} {} {} shares @ {} bps on {} ({})", 
         req.wallet, req.side, req.quantity, req.price_bps, req.market_id, fill_status
     ));
@@ -1762,42 +1995,109 @@ pub async fn submit_order(
 
 // ===== CANCEL ORDER HANDLER =====
 /// DELETE /orders/:order_id - Cancel an open order
+#[utoipa::path(
+    delete,
+    path = "/orders/{order_id}",
+    tag = "orders",
+    params(
+        ("order_id" = String, Path, description = "Order ID"),
+    ),
+    request_body = CancelOrderRequest,
+    responses(
+        (status = 200, description = "Order cancelled", body = Object),
+        (status = 403, description = "Not the order owner", body = ApiError),
+        (status = 404, description = "Order not found", body = ApiError),
+        (status = 409, description = "Order not active", body = ApiError),
+    )
+)]
 pub async fn cancel_order(
     State(state): State<SharedState>,
     Path(order_id): Path<String>,
     Json(req): Json<CancelOrderRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let mut app = state.lock().unwrap();
+) -> Result<Json<Value>, ApiError> {
+    let market = state.books.find_order(&order_id).ok_or_else(|| OrderError::OrderNotFound(order_id.clone()))?;
     
-    match app.orderbook.cancel_order(&order_id, &req.wallet) {
-        Ok(cancelled_order) => {
-            app.log_activity("❌", "CANCEL", &format!("{} cancelled order {}", req.wallet, order_id));
-            Ok(Json(json!({
-                "success": true,
-                "order_id": order_id,
-                "refunded_quantity": cancelled_order.remaining
-            })))
-        }
-        Err(e) => Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": format!("{:?}", e)
-        }))))
-    }
+    let cancelled_order = market.cancel_order(&order_id, &req.wallet).await
+        .map_err(|e| trade_error(e, "Cancel failed"))?;
+    
+    let mut app = state.lock().unwrap();
+    app.publish_odds(&cancelled_order.market_id, Some(&market.snapshot()), chrono::Utc::now().timestamp() as u64);
+    app.log_activity("❌", "CANCEL", &format!("{} cancelled order {}", req.wallet, order_id));
+    Ok(Json(json!({
+        "success": true,
+        "order_id": order_id,
+        "refunded_quantity": cancelled_order.remaining
+    })))
 }
 
 // ===== GET USER ORDERS HANDLER =====
-/// GET /orders/user/:wallet - Get user's open orders
+/// Query params for a wallet's orders
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserOrdersQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default: 100, max: 1000)
+    pub limit: Option<usize>,
+    /// Sort order by creation time: "asc", "desc" (default: "desc")
+    pub order: Option<String>,
+    /// "open", "partially_filled", "filled", "cancelled", "expired", "rejected" or "all"
+    /// (default: open and partially filled)
+    pub status: Option<String>,
+    /// Filter by market ID
+    pub market_id: Option<String>,
+    /// Filter by outcome index
+    pub outcome: Option<usize>,
+    /// Created at or after (unix seconds)
+    pub from_timestamp: Option<u64>,
+    /// Created at or before (unix seconds)
+    pub to_timestamp: Option<u64>,
+}
+
+/// GET /orders/user/:wallet - Get user's orders (open ones unless `status` says otherwise)
+#[utoipa::path(
+    get,
+    path = "/orders/user/{wallet}",
+    tag = "orders",
+    params(
+        ("wallet" = String, Path, description = "Wallet address"),
+        UserOrdersQuery,
+    ),
+    responses(
+        (status = 200, description = "Page of the wallet's orders, newest first", body = Object),
+        (status = 400, description = "Invalid cursor or filter", body = ApiError),
+    )
+)]
 pub async fn get_user_orders(
     State(state): State<SharedState>,
     Path(wallet): Path<String>,
-) -> Json<Value> {
-    let app = state.lock().unwrap();
-    let orders = app.orderbook.get_user_orders(&wallet);
-    
-    Json(json!({
+    Query(params): Query<UserOrdersQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let all_statuses = params.status.as_deref().is_some_and(|s| s.eq_ignore_ascii_case("all"));
+    let status = if all_statuses {
+        None
+    } else {
+        parse_filter("status", params.status.as_deref(), OrderStatus::parse)?
+    };
+    let order = parse_filter("order", params.order.as_deref(), SortOrder::parse)?.unwrap_or_default();
+    let range = TimeRange::new(params.from_timestamp, params.to_timestamp);
+
+    let orders: Vec<LimitOrder> = state.books.user_order_history(&wallet).into_iter()
+        .filter(|o| match status {
+            Some(s) => o.status == s,
+            None => all_statuses || o.status.is_active(),
+        })
+        .filter(|o| params.market_id.as_ref().is_none_or(|id| o.market_id == *id))
+        .filter(|o| params.outcome.is_none_or(|i| o.outcome.index() == i))
+        .filter(|o| range.contains(o.created_at))
+        .collect();
+    let request = PageRequest::new(params.cursor.clone(), params.limit).with_order(order);
+    let page = paginate(orders, |o| (o.created_at, o.id.clone()), &request)?;
+
+    Ok(Json(json!({
         "success": true,
         "wallet": wallet,
-        "orders": orders.iter().map(|o| json!({
+        "orders": page.items.iter().map(|o| json!({
             "order_id": o.id,
             "market_id": o.market_id,
             "outcome": o.outcome.index(),
@@ -1809,28 +2109,33 @@ pub async fn get_user_orders(
             "status": format!("{:?}", o.status),
             "created_at": o.created_at
         })).collect::<Vec<_>>(),
-        "total_orders": orders.len()
-    }))
+        "pagination": page.meta(),
+    })))
 }
 
 // ===== GET ORDER BOOK HANDLER =====
 /// GET /orderbook/:market_id - Get order book depth for a market
+#[utoipa::path(
+    get,
+    path = "/orderbook/{market_id}",
+    tag = "orders",
+    params(
+        ("market_id" = String, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 200, description = "Order book depth", body = Object),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_orderbook(
     State(state): State<SharedState>,
     Path(market_id): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let app = state.lock().unwrap();
-    
-    if !app.markets.contains_key(&market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))));
-    }
+) -> Result<Json<Value>, ApiError> {
+    let snapshot = state.books.get(&market_id).ok_or_else(|| ApiError::MarketNotFound(market_id.clone()))?.snapshot();
     
     // Get depth for both outcomes (YES=0, NO=1)
-    let yes_book = app.orderbook.get_orderbook(&market_id, Outcome::YES, 10);
-    let no_book = app.orderbook.get_orderbook(&market_id, Outcome::NO, 10);
+    let yes_book = snapshot.book(Outcome::YES, 10);
+    let no_book = snapshot.book(Outcome::NO, 10);
     
     Ok(Json(json!({
         "success": true,
@@ -1869,21 +2174,56 @@ pub async fn get_orderbook(
 }
 
 // ===== GET RECENT TRADES HANDLER =====
-/// GET /trades/:market_id - Get recent trades for a market
+/// Query params for a market's trades
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TradesQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default: 100, max: 1000)
+    pub limit: Option<usize>,
+    /// Sort order by time: "asc", "desc" (default: "desc")
+    pub order: Option<String>,
+    /// Filter by outcome index
+    pub outcome: Option<usize>,
+    /// Traded at or after (unix seconds)
+    pub from_timestamp: Option<u64>,
+    /// Traded at or before (unix seconds)
+    pub to_timestamp: Option<u64>,
+}
+
+/// GET /trades/:market_id - Get a market's trades, newest first
+#[utoipa::path(
+    get,
+    path = "/trades/{market_id}",
+    tag = "orders",
+    params(
+        ("market_id" = String, Path, description = "Market ID"),
+        TradesQuery,
+    ),
+    responses(
+        (status = 200, description = "Page of fills", body = Object),
+        (status = 400, description = "Invalid cursor or filter", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_recent_trades(
     State(state): State<SharedState>,
     Path(market_id): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let app = state.lock().unwrap();
-    
-    if !app.markets.contains_key(&market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))));
-    }
-    
-    let trades = app.orderbook.get_recent_trades(&market_id, 50);
+    Query(params): Query<TradesQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let order = parse_filter("order", params.order.as_deref(), SortOrder::parse)?.unwrap_or_default();
+    let range = TimeRange::new(params.from_timestamp, params.to_timestamp);
+
+    let market = state.books.get(&market_id).ok_or_else(|| ApiError::MarketNotFound(market_id.clone()))?;
+    let fills: Vec<Arc<Fill>> = market.history().fills.iter()
+        .filter(|f| params.outcome.is_none_or(|i| f.outcome.index() == i))
+        .filter(|f| range.contains(f.timestamp))
+        .cloned()
+        .collect();
+    let request = PageRequest::new(params.cursor.clone(), params.limit).with_order(order);
+    let page = paginate(fills, |f| (f.timestamp, f.id.clone()), &request)?;
+    let trades = &page.items;
     
     Ok(Json(json!({
         "success": true,
@@ -1898,31 +2238,40 @@ pub async fn get_recent_trades(
             "maker": &t.maker[..8.min(t.maker.len())],
             "taker": &t.taker[..8.min(t.taker.len())]
         })).collect::<Vec<_>>(),
-        "total_trades": trades.len()
+        "pagination": page.meta(),
     })))
 }
 
 // ===== GET MARKET ODDS (HYBRID CLOB/CPMM) =====
 /// GET /markets/:id/odds - Get current odds from CLOB or CPMM
+#[utoipa::path(
+    get,
+    path = "/markets/{id}/odds",
+    tag = "markets",
+    params(
+        ("id" = String, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 200, description = "Hybrid CLOB/CPMM odds", body = Object),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_market_odds(
     State(state): State<SharedState>,
     Path(market_id): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let app = state.lock().unwrap();
+) -> Result<Json<Value>, ApiError> {
+    let title = state.lock().unwrap().markets.get(&market_id)
+        .map(|m| m.title.clone())
+        .ok_or_else(|| ApiError::MarketNotFound(market_id.clone()))?;
     
-    let market = app.markets.get(&market_id)
-        .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))))?;
-    
-    // Get hybrid odds from OrderBookManager
-    let odds = app.orderbook.get_odds(&market_id);
+    // Hybrid odds from the market's latest snapshot
+    let snapshot = state.books.open(&market_id, None).snapshot();
+    let odds = &snapshot.odds;
     
     Ok(Json(json!({
         "success": true,
         "market_id": market_id,
-        "title": market.title,
+        "title": title,
         "odds": {
             "yes": odds.yes_price,
             "no": odds.no_price,
@@ -1940,29 +2289,33 @@ pub async fn get_market_odds(
 
 // ===== GET ORDERBOOK FOR SPECIFIC OUTCOME =====
 /// GET /orderbook/:market_id/:outcome - Get order book for a specific outcome
+#[utoipa::path(
+    get,
+    path = "/orderbook/{market_id}/{outcome}",
+    tag = "orders",
+    params(
+        ("market_id" = String, Path, description = "Market ID"),
+        ("outcome" = String, Path, description = "yes, no, 0 or 1"),
+    ),
+    responses(
+        (status = 200, description = "Order book depth for one outcome", body = Object),
+        (status = 400, description = "Unknown outcome", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_orderbook_outcome(
     State(state): State<SharedState>,
     Path((market_id, outcome_str)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let app = state.lock().unwrap();
-    
-    if !app.markets.contains_key(&market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))));
-    }
+) -> Result<Json<Value>, ApiError> {
+    let snapshot = state.books.get(&market_id).ok_or_else(|| ApiError::MarketNotFound(market_id.clone()))?.snapshot();
     
     let outcome = match outcome_str.to_lowercase().as_str() {
         "yes" | "0" => Outcome::YES,
         "no" | "1" => Outcome::NO,
-        _ => return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "Invalid outcome - use 'yes' or 'no' (or 0/1)"
-        })))),
+        _ => return Err(ApiError::BadRequest("Invalid outcome - use 'yes' or 'no' (or 0/1)".to_string())),
     };
     
-    let book = app.orderbook.get_orderbook(&market_id, outcome, 20);
+    let book = snapshot.book(outcome, 20);
     
     Ok(Json(json!({
         "success": true,
@@ -1990,14 +2343,14 @@ pub async fn get_orderbook_outcome(
 // OUTCOME SHARES HANDLERS  
 // ═══════════════════════════════════════════════════════════════════════════════
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MintSharesRequest {
     pub wallet: String,
     pub market_id: String,
     pub amount: f64,  // BB tokens to convert (1 BB = 1 YES + 1 NO)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RedeemSharesRequest {
     pub wallet: String,
     pub market_id: String,
@@ -2006,46 +2359,40 @@ pub struct RedeemSharesRequest {
 
 // ===== MINT SHARES HANDLER =====
 /// POST /shares/mint - Mint YES+NO shares from BB tokens (1 BB → 1 YES + 1 NO)
+#[utoipa::path(
+    post,
+    path = "/shares/mint",
+    tag = "shares",
+    request_body = MintSharesRequest,
+    responses(
+        (status = 200, description = "Shares minted", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn mint_shares(
     State(state): State<SharedState>,
     Json(req): Json<MintSharesRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let mut app = state.lock().unwrap();
-    
-    // Check market exists
-    if !app.markets.contains_key(&req.market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))));
-    }
-    
-    // Check BB balance
-    let balance = app.ledger.balance(&req.wallet);
-    if balance < req.amount {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": format!("Insufficient balance: have {} BB, need {} BB", balance, req.amount),
-            "available": balance,
-            "requested": req.amount
-        }))));
-    }
-    
-    // Debit BB from wallet to market escrow
-    let escrow = format!("escrow:{}", req.market_id);
-    if let Err(e) = app.ledger.transfer(&req.wallet, &escrow, req.amount, "mint_shares") {
-        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
-            "success": false,
-            "error": format!("Transfer failed: {}", e)
-        }))));
+) -> Result<Json<Value>, ApiError> {
+    {
+        let app = state.lock().unwrap();
+        
+        // Check market exists and is still trading
+        trading_open(&app, &req.market_id)?;
+        
+        app.check_session_spend(&req.wallet, req.amount)?;
     }
     
-    // Credit shares (1 BB = 1 YES + 1 NO)
-    app.shares.credit_shares_simple(&req.wallet, &req.market_id, OutcomeIndex::YES, req.amount);
-    app.shares.credit_shares_simple(&req.wallet, &req.market_id, OutcomeIndex::NO, req.amount);
-    
-    let position = app.shares.get_position(&req.wallet, &req.market_id);
+    // Move BB into the market escrow and credit 1 YES + 1 NO per BB
+    let market_book = state.books.open(&req.market_id, None);
+    let position = market_book
+        .mint(&req.wallet, req.amount)
+        .await
+        .map_err(|e| trade_error(e, "Transfer failed"))?;
     
+    let mut app = state.lock().unwrap();
+    app.publish_odds(&req.market_id, Some(&market_book.snapshot()), chrono::Utc::now().timestamp() as u64);
+    app.record_session_trade(&req.wallet, req.amount, 0.0);
     app.log_activity("🪙", "MINT", &format!(
         "{} minted {} YES + {} NO shares for {} on {}", 
         req.wallet, req.amount, req.amount, req.amount, req.market_id
@@ -2062,59 +2409,48 @@ pub async fn mint_shares(
             "yes_shares": position.yes_shares,
             "no_shares": position.no_shares
         },
-        "new_bb_balance": app.ledger.balance(&req.wallet)
+        "new_bb_balance": state.ledger.balance(&req.wallet)
     })))
 }
 
 // ===== REDEEM SHARES HANDLER =====
 /// POST /shares/redeem - Redeem share pairs back to BB (1 YES + 1 NO → 1 BB)
+#[utoipa::path(
+    post,
+    path = "/shares/redeem",
+    tag = "shares",
+    request_body = RedeemSharesRequest,
+    responses(
+        (status = 200, description = "Shares redeemed", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn redeem_shares(
     State(state): State<SharedState>,
     Json(req): Json<RedeemSharesRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let mut app = state.lock().unwrap();
-    
-    // Check market exists
-    if !app.markets.contains_key(&req.market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))));
-    }
-    
-    // Check share positions
-    let position = app.shares.get_position(&req.wallet, &req.market_id);
-    let max_pairs = position.yes_shares.min(position.no_shares);
-    
-    if req.amount > max_pairs {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": format!("Insufficient share pairs: can redeem max {} pairs", max_pairs),
-            "yes_shares": position.yes_shares,
-            "no_shares": position.no_shares,
-            "max_redeemable_pairs": max_pairs,
-            "requested": req.amount
-        }))));
-    }
-    
-    // Debit shares
-    let _ = app.shares.debit_shares_simple(&req.wallet, &req.market_id, OutcomeIndex::YES, req.amount);
-    let _ = app.shares.debit_shares_simple(&req.wallet, &req.market_id, OutcomeIndex::NO, req.amount);
-    
-    // Credit BB from market escrow to wallet
-    let escrow = format!("escrow:{}", req.market_id);
-    if let Err(e) = app.ledger.transfer(&escrow, &req.wallet, req.amount, "redeem_shares") {
-        // Rollback shares on failure
-        app.shares.credit_shares_simple(&req.wallet, &req.market_id, OutcomeIndex::YES, req.amount);
-        app.shares.credit_shares_simple(&req.wallet, &req.market_id, OutcomeIndex::NO, req.amount);
-        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
-            "success": false,
-            "error": format!("Redemption failed: {}", e)
-        }))));
+) -> Result<Json<Value>, ApiError> {
+    {
+        let app = state.lock().unwrap();
+        
+        // Check market exists
+        if !app.markets.contains_key(&req.market_id) {
+            return Err(ApiError::MarketNotFound(req.market_id.clone()));
+        }
+        
+        app.check_session_spend(&req.wallet, 0.0)?;
     }
     
-    let new_position = app.shares.get_position(&req.wallet, &req.market_id);
+    // Burn the pairs and pay BB out of the market escrow
+    let market_book = state.books.open(&req.market_id, None);
+    let new_position = market_book
+        .redeem(&req.wallet, req.amount)
+        .await
+        .map_err(|e| trade_error(e, "Redemption failed"))?;
     
+    let mut app = state.lock().unwrap();
+    app.publish_odds(&req.market_id, Some(&market_book.snapshot()), chrono::Utc::now().timestamp() as u64);
+    app.record_session_trade(&req.wallet, 0.0, req.amount);
     app.log_activity("💎", "REDEEM", &format!(
         "{} redeemed {} share pairs for {} BB on {}", 
         req.wallet, req.amount, req.amount, req.market_id
@@ -2131,30 +2467,48 @@ pub async fn redeem_shares(
             "yes_shares": new_position.yes_shares,
             "no_shares": new_position.no_shares
         },
-        "new_bb_balance": app.ledger.balance(&req.wallet)
+        "new_bb_balance": state.ledger.balance(&req.wallet)
     })))
 }
 
 // ===== GET POSITIONS HANDLER =====
 /// GET /positions/:wallet - Get all share positions for a wallet
+#[utoipa::path(
+    get,
+    path = "/positions/{wallet}",
+    tag = "shares",
+    params(
+        ("wallet" = String, Path, description = "Wallet address"),
+    ),
+    responses(
+        (status = 200, description = "All positions for a wallet", body = Object),
+    )
+)]
 pub async fn get_positions(
     State(state): State<SharedState>,
     Path(wallet): Path<String>,
 ) -> Json<Value> {
-    let app = state.lock().unwrap();
-    let positions = app.shares.get_all_positions(&wallet);
+    let snapshots: Vec<_> = state.books.snapshots().into_iter()
+        .filter(|s| s.positions.iter().any(|p| p.holder == wallet))
+        .collect();
+    let titles: HashMap<String, String> = {
+        let app = state.lock().unwrap();
+        snapshots.iter()
+            .filter_map(|s| app.markets.get(&s.market_id).map(|m| (s.market_id.clone(), m.title.clone())))
+            .collect()
+    };
     
     // Enrich with market titles and current odds
-    let enriched: Vec<Value> = positions.iter().map(|pos| {
-        let market = app.markets.get(&pos.market_id);
-        let odds = app.orderbook.get_odds(&pos.market_id);
+    let enriched: Vec<Value> = snapshots.iter().map(|snapshot| {
+        let pos = snapshot.position(&wallet);
+        let odds = &snapshot.odds;
         
         let yes_value = pos.yes_shares * odds.yes_price;
         let no_value = pos.no_shares * odds.no_price;
         
         json!({
             "market_id": pos.market_id,
-            "market_title": market.map(|m| m.title.clone()).unwrap_or_default(),
+            "market_title": titles.get(&pos.market_id).cloned().unwrap_or_default(),
             "yes_shares": pos.yes_shares,
             "no_shares": pos.no_shares,
             "current_odds": {
@@ -2179,31 +2533,39 @@ pub async fn get_positions(
         "wallet": wallet,
         "positions": enriched,
         "summary": {
-            "total_positions": positions.len(),
+            "total_positions": snapshots.len(),
             "total_estimated_value": total_value,
-            "bb_balance": app.ledger.balance(&wallet)
+            "bb_balance": state.ledger.balance(&wallet)
         }
     }))
 }
 
 // ===== GET MARKET POSITIONS HANDLER =====
 /// GET /positions/:wallet/:market_id - Get positions for a specific market
+#[utoipa::path(
+    get,
+    path = "/positions/{wallet}/{market_id}",
+    tag = "shares",
+    params(
+        ("wallet" = String, Path, description = "Wallet address"),
+        ("market_id" = String, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 200, description = "Position in one market", body = Object),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_market_positions(
     State(state): State<SharedState>,
     Path((wallet, market_id)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let app = state.lock().unwrap();
-    
-    if !app.markets.contains_key(&market_id) {
-        return Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))));
-    }
+) -> Result<Json<Value>, ApiError> {
+    let title = state.lock().unwrap().markets.get(&market_id)
+        .map(|m| m.title.clone())
+        .ok_or_else(|| ApiError::MarketNotFound(market_id.clone()))?;
     
-    let market = app.markets.get(&market_id).unwrap();
-    let position = app.shares.get_position(&wallet, &market_id);
-    let odds = app.orderbook.get_odds(&market_id);
+    let snapshot = state.books.open(&market_id, None).snapshot();
+    let position = snapshot.position(&wallet);
+    let odds = &snapshot.odds;
     
     let yes_value = position.yes_shares * odds.yes_price;
     let no_value = position.no_shares * odds.no_price;
@@ -2212,7 +2574,7 @@ pub async fn get_market_positions(
         "success": true,
         "wallet": wallet,
         "market_id": market_id,
-        "market_title": market.title,
+        "market_title": title,
         "position": {
             "yes_shares": position.yes_shares,
             "no_shares": position.no_shares,
@@ -2233,11 +2595,18 @@ pub async fn get_market_positions(
 
 // ===== ORDERBOOK STATS HANDLER =====
 /// GET /stats/orderbook - Get CLOB statistics
+#[utoipa::path(
+    get,
+    path = "/stats/orderbook",
+    tag = "stats",
+    responses(
+        (status = 200, description = "CLOB statistics", body = Object),
+    )
+)]
 pub async fn get_orderbook_stats(
     State(state): State<SharedState>,
 ) -> Json<Value> {
-    let app = state.lock().unwrap();
-    let stats = app.orderbook.get_stats();
+    let stats = state.books.orderbook_stats();
     
     Json(json!({
         "success": true,
@@ -2255,11 +2624,18 @@ pub async fn get_orderbook_stats(
 
 // ===== SHARES STATS HANDLER =====
 /// GET /stats/shares - Get shares system statistics
+#[utoipa::path(
+    get,
+    path = "/stats/shares",
+    tag = "stats",
+    responses(
+        (status = 200, description = "Share system statistics", body = Object),
+    )
+)]
 pub async fn get_shares_stats(
     State(state): State<SharedState>,
 ) -> Json<Value> {
-    let app = state.lock().unwrap();
-    let stats = app.shares.get_stats();
+    let stats = state.books.shares_stats();
     
     Json(json!({
         "success": true,
@@ -2278,286 +2654,574 @@ pub async fn get_shares_stats(
 // ═══════════════════════════════════════════════════════════════════════════════
 
 use crate::app_state::MarketResolution;
+use crate::market_resolve::{ProposalStatus, DisputeError};
 
-/// Request to resolve a market
-#[derive(Debug, Deserialize)]
+/// Request to resolve a market (signed by the resolving oracle or admin)
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ResolveMarketRequest {
-    /// The oracle/admin address resolving the market
-    pub resolver_address: String,
-    /// Signature proving ownership of resolver address
-    pub signature: String,
     /// The winning outcome index (0=YES, 1=NO for binary markets)
     pub winning_outcome: usize,
     /// Optional reason/evidence for resolution
     pub resolution_reason: Option<String>,
-    /// Nonce for replay protection
-    pub nonce: u64,
-    /// Timestamp
-    pub timestamp: u64,
+    /// Bond posted with the proposal (defaults to the minimum bond)
+    pub bond: Option<f64>,
+}
+
+/// Open a bonded resolution proposal for a market.
+///
+/// Debits the bond from the proposer, closes betting and starts the
+/// challenge window. Payouts happen only when the proposal finalizes.
+pub(crate) fn open_resolution_proposal(
+    app: &mut crate::app_state::AppState,
+    market_id: &str,
+    outcome: usize,
+    proposer: &str,
+    bond: Option<f64>,
+    reason: Option<String>,
+    now: u64,
+) -> Result<Value, ApiError> {
+    let (market_title, num_options) = {
+        let market = app.markets.get(market_id).ok_or_else(|| {
+            ApiError::MarketNotFound(market_id.to_string())
+        })?;
+        (market.title.clone(), market.options.len())
+    };
+
+    let bond = bond.unwrap_or(app.disputes.min_bond);
+    let balance = app.ledger.balance(proposer);
+    if balance < bond {
+        return Err(ApiError::InsufficientBalance { available: balance, required: bond });
+    }
+
+    let proposal = app.disputes
+        .propose(market_id, outcome, num_options, proposer, bond, reason, now)?;
+
+    app.ledger.debit(proposer, bond);
+
+    // Betting is closed while the outcome is being decided
+    if let Some(market) = app.markets.get_mut(market_id) {
+        market.market_status = EventStatus::Closed;
+    }
+    app.reindex_market(market_id);
+
+    let outcome_name = app.markets.get(market_id)
+        .and_then(|m| m.options.get(outcome).cloned())
+        .unwrap_or_default();
+
+    app.log_activity("📜", "PROPOSE_RESOLUTION", &format!(
+        "Market '{}' proposed: {} wins | bond {} BB | by {} | challenge window ends {}",
+        market_title, outcome_name, bond, proposer, proposal.challenge_deadline
+    ));
+
+    Ok(json!({
+        "success": true,
+        "market_id": market_id,
+        "status": proposal.status.as_str(),
+        "proposed_outcome": outcome,
+        "proposed_outcome_name": outcome_name,
+        "proposer": proposer,
+        "bond": bond,
+        "proposed_at": now,
+        "challenge_deadline": proposal.challenge_deadline,
+        "message": format!(
+            "Resolution proposed. Payouts finalize after the challenge window unless disputed with a {} BB bond.",
+            bond
+        )
+    }))
 }
 
-/// POST /markets/:id/resolve - Resolve a market with winning outcome
+/// POST /markets/:id/resolve - Propose a market's winning outcome
 /// 
-/// Authorization: Only whitelisted oracles or admins can resolve markets.
+/// Authorization: signed by a key with the oracle role (or an admin).
 /// High-value markets may require multi-sig (configurable).
+/// The resolver posts a bond and the outcome finalizes after the challenge window.
+#[utoipa::path(
+    post,
+    path = "/markets/{id}/resolve",
+    tag = "resolution",
+    params(
+        ("id" = String, Path, description = "Market ID"),
+    ),
+    request_body = ResolveMarketRequest,
+    responses(
+        (status = 200, description = "Resolution proposed", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an oracle or admin", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+        (status = 409, description = "Already resolved or proposed", body = ApiError),
+    )
+)]
 pub async fn resolve_market(
     State(state): State<SharedState>,
     Path(market_id): Path<String>,
-    Json(req): Json<ResolveMarketRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    auth: Authorized<OracleRole, ResolveMarketRequest>,
+) -> Result<Json<Value>, ApiError> {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_secs();
-    
-    // Validate timestamp (1 hour window for resolution)
-    if now.abs_diff(req.timestamp) > 3600 {
-        return Err((StatusCode::UNAUTHORIZED, Json(json!({
-            "success": false,
-            "error": "Resolution request expired (1 hour window)"
-        }))));
-    }
+    let req = &auth.body;
+    let resolver = auth.address();
+    let is_admin = state.roles.has_role(auth.signer(), Role::Admin);
     
     let mut app = state.lock().unwrap();
     
-    // Extract needed data from market (immutable borrow scope)
-    let (market_volume, market_title, winning_outcome_name, is_resolved, existing_winner, num_options) = {
+    let (market_volume, is_resolved, existing_winner) = {
         let market = app.markets.get(&market_id).ok_or_else(|| {
-            (StatusCode::NOT_FOUND, Json(json!({
-                "success": false,
-                "error": format!("Market {} not found", market_id)
-            })))
+            ApiError::MarketNotFound(market_id.clone())
         })?;
-        
-        (
-            market.total_volume,
-            market.title.clone(),
-            market.options.get(req.winning_outcome).cloned(),
-            market.is_resolved,
-            market.winning_option,
-            market.options.len(),
-        )
+        (market.total_volume, market.is_resolved, market.winning_option)
     };
     
     // Check if already resolved
     if is_resolved {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "Market already resolved",
+        return Err(ApiError::Conflict("Market already resolved".to_string()).with(json!({
             "winning_outcome": existing_winner
-        }))));
-    }
-    
-    // Validate outcome index
-    if req.winning_outcome >= num_options {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": format!("Invalid outcome index {}. Market has {} outcomes.", 
-                req.winning_outcome, num_options)
-        }))));
-    }
-    
-    let winning_outcome_name = winning_outcome_name.unwrap(); // Safe: checked above
-    
-    // Check nonce
-    let last_nonce = app.nonces.get(&req.resolver_address).copied().unwrap_or(0);
-    if req.nonce <= last_nonce {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": format!("Invalid nonce: got {}, expected > {}", req.nonce, last_nonce)
-        }))));
+        })));
     }
     
-    // Authorization check
-    if !app.oracle_config.can_resolve(&req.resolver_address, &market_id, market_volume) {
-        return Err((StatusCode::FORBIDDEN, Json(json!({
-            "success": false,
-            "error": "Not authorized to resolve this market",
-            "reason": "Address is not a whitelisted oracle or admin",
-            "resolver": req.resolver_address
-        }))));
+    // High-value markets need an admin while multi-sig is pending
+    if !app.oracle_config.can_resolve(is_admin, &market_id, market_volume) {
+        return Err(ApiError::Forbidden("Not authorized to resolve this market".to_string()).with(json!({
+            "reason": "High-value market requires multi-sig or an admin",
+            "resolver": resolver
+        })));
     }
     
-    // Update nonce
-    app.nonces.insert(req.resolver_address.clone(), req.nonce);
-    
-    // Resolve the market in PredictionMarket
-    let market = app.markets.get_mut(&market_id).unwrap();
-    market.is_resolved = true;
-    market.winning_option = Some(req.winning_outcome);
-    
-    // Calculate payouts from shares system
-    let share_payouts = app.shares.resolve_market(
+    let response = open_resolution_proposal(
+        &mut app,
         &market_id,
-        OutcomeIndex::from_usize(req.winning_outcome),
-        2, // Binary market
+        req.winning_outcome,
+        &resolver,
+        req.bond,
+        req.resolution_reason.clone(),
+        now,
+    )?;
+    app.audit(
+        &auth.request,
+        "RESOLVE_PROPOSED",
+        &format!("Market {} outcome {}", market_id, req.winning_outcome),
+        json!({ "market_id": market_id, "is_resolved": false, "proposal": null }),
+        response.clone(),
     );
     
-    // Calculate total payout
-    let total_payout: f64 = share_payouts.iter().map(|(_, amount)| amount).sum();
-    let num_winners = share_payouts.len();
-    
-    // Credit winners
-    for (wallet, payout_amount) in &share_payouts {
-        app.ledger.credit(wallet, *payout_amount);
-    }
-    
-    // Record resolution
-    let resolution = MarketResolution {
-        market_id: market_id.clone(),
-        winning_outcome: req.winning_outcome,
-        winning_outcome_name: winning_outcome_name.clone(),
-        resolved_by: req.resolver_address.clone(),
-        resolved_at: now,
-        total_payout,
-        num_winners,
-        l1_settlement_hash: None,
-        l1_settlement_status: "pending".to_string(),
-    };
-    app.resolutions.insert(market_id.clone(), resolution);
-    
-    // Log activity
-    app.log_activity("⚖️", "RESOLVE", &format!(
-        "Market '{}' resolved: {} wins | {} winners | {} BB paid out | by {}",
-        market_title, winning_outcome_name, num_winners, total_payout, req.resolver_address
-    ));
-    
-    Ok(Json(json!({
-        "success": true,
-        "market_id": market_id,
-        "winning_outcome": req.winning_outcome,
-        "winning_outcome_name": winning_outcome_name,
-        "resolved_by": req.resolver_address,
-        "resolved_at": now,
-        "payouts": {
-            "total_payout": total_payout,
-            "num_winners": num_winners,
-            "winners": share_payouts.iter().map(|(wallet, amount)| {
-                json!({ "wallet": wallet, "payout": amount })
-            }).collect::<Vec<_>>()
-        },
-        "l1_settlement_status": "pending",
-        "message": format!("Market resolved. {} will be settled to L1.", total_payout)
-    })))
+    Ok(Json(response))
 }
 
 /// POST /admin/resolve/:market_id/:outcome - Admin shortcut to resolve
 /// 
-/// Simplified endpoint for admin resolution without signature verification.
-/// Only works if caller is admin (checked by address in body or header).
+/// Signed by an admin; the outcome comes from the path.
+/// Still goes through the bonded challenge window.
+#[utoipa::path(
+    post,
+    path = "/admin/resolve/{market_id}/{outcome}",
+    tag = "resolution",
+    params(
+        ("market_id" = String, Path, description = "Market ID"),
+        ("outcome" = usize, Path, description = "Winning outcome index"),
+    ),
+    request_body = AdminResolveRequest,
+    responses(
+        (status = 200, description = "Resolution proposed", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+        (status = 409, description = "Already resolved or proposed", body = ApiError),
+    )
+)]
 pub async fn admin_resolve_market(
     State(state): State<SharedState>,
     Path((market_id, winning_outcome)): Path<(String, usize)>,
-    Json(req): Json<AdminResolveRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    auth: Authorized<AdminRole, AdminResolveRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let req = &auth.body;
     let mut app = state.lock().unwrap();
     
-    // Verify admin
-    if !app.oracle_config.is_admin(&req.admin_address) {
-        return Err((StatusCode::FORBIDDEN, Json(json!({
-            "success": false,
-            "error": "Not authorized. Admin access required.",
-            "address": req.admin_address
-        }))));
-    }
-    
     // Check if market exists
     let market = app.markets.get(&market_id).ok_or_else(|| {
-        (StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": format!("Market {} not found", market_id)
-        })))
+        ApiError::MarketNotFound(market_id.clone())
     })?;
     
     if market.is_resolved {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "Market already resolved"
-        }))));
+        return Err(ApiError::Conflict("Market already resolved".to_string()));
     }
     
-    if winning_outcome >= market.options.len() {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": format!("Invalid outcome {}. Market has {} outcomes.", 
-                winning_outcome, market.options.len())
-        }))));
-    }
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    
+    let response = open_resolution_proposal(
+        &mut app,
+        &market_id,
+        winning_outcome,
+        &auth.address(),
+        req.bond,
+        req.reason.clone(),
+        now,
+    )?;
+    app.audit(
+        &auth.request,
+        "ADMIN_RESOLVE",
+        &format!("Market {} outcome {}", market_id, winning_outcome),
+        json!({ "market_id": market_id, "is_resolved": false, "proposal": null }),
+        response.clone(),
+    );
+    
+    Ok(Json(response))
+}
+
+/// Admin resolution details (signed by an admin)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct AdminResolveRequest {
+    pub bond: Option<f64>,
+    pub reason: Option<String>,
+}
+
+/// POST /markets/:id/dispute - Challenge a proposed resolution
+/// 
+/// Body is a `SignedTransaction` with a `resolution_dispute` payload. Any
+/// wallet may dispute during the challenge window by posting a bond matching
+/// the proposer's; the bond comes from the signer's own wallet. The dispute
+/// escalates to admin/oracle arbitration.
+#[utoipa::path(
+    post,
+    path = "/markets/{id}/dispute",
+    tag = "resolution",
+    params(
+        ("id" = String, Path, description = "Market ID"),
+    ),
+    request_body = SignedTransaction,
+    responses(
+        (status = 200, description = "Dispute opened", body = Object),
+        (status = 400, description = "Invalid bond, outcome or nonce", body = ApiError),
+        (status = 401, description = "Bad signature or expired request", body = ApiError),
+        (status = 403, description = "Proposer cannot dispute", body = ApiError),
+        (status = 404, description = "No open proposal", body = ApiError),
+        (status = 409, description = "Challenge window closed", body = ApiError),
+    )
+)]
+pub async fn dispute_resolution(
+    State(state): State<SharedState>,
+    Path(market_id): Path<String>,
+    Json(tx): Json<SignedTransaction>,
+) -> Result<Json<Value>, ApiError> {
+    let (bond, claimed_outcome, reason) = match &tx.payload {
+        TransactionPayload::ResolutionDispute { market_id: signed_market, bond, claimed_outcome, reason } => {
+            if *signed_market != market_id {
+                return Err(ApiError::BadRequest(format!("Signed dispute is for market {}", signed_market)));
+            }
+            (*bond, *claimed_outcome, reason.clone())
+        }
+        _ => {
+            return Err(ApiError::BadRequest("Expected a resolution_dispute payload".to_string()));
+        }
+    };
+    
+    let expiry_secs = state.lock().unwrap().config.transactions.expiry_secs;
+    tx.validate_with_window(expiry_secs)?;
+    
+    // The bond always comes from the key that signed; sender_address is not signed
+    let wallet = tx.signer_address();
     
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_secs();
     
-    let winning_outcome_name = market.options[winning_outcome].clone();
-    let market_title = market.title.clone();
+    let mut app = state.lock().unwrap();
     
-    // Resolve
-    let market = app.markets.get_mut(&market_id).unwrap();
-    market.is_resolved = true;
-    market.winning_option = Some(winning_outcome);
+    // Replay protection
+    let last_nonce = app.nonces.get(&wallet).copied().unwrap_or(0);
+    if tx.nonce <= last_nonce {
+        return Err(ApiError::InvalidNonce { got: tx.nonce, last: last_nonce });
+    }
     
-    // Calculate payouts
-    let share_payouts = app.shares.resolve_market(
-        &market_id,
-        OutcomeIndex::from_usize(winning_outcome),
-        2,
-    );
+    let num_options = app.markets.get(&market_id).map(|m| m.options.len()).ok_or_else(|| {
+        ApiError::MarketNotFound(market_id.clone())
+    })?;
     
-    let total_payout: f64 = share_payouts.iter().map(|(_, amount)| amount).sum();
-    let num_winners = share_payouts.len();
+    let required_bond = app.disputes.get(&market_id)
+        .map(|p| p.proposer_bond)
+        .ok_or_else(|| DisputeError::ProposalNotFound(market_id.clone()))?;
+    let bond = bond.unwrap_or(required_bond);
     
-    // Credit winners
-    for (wallet, payout_amount) in &share_payouts {
-        app.ledger.credit(wallet, *payout_amount);
+    let balance = app.ledger.balance(&wallet);
+    if balance < bond {
+        return Err(ApiError::InsufficientBalance { available: balance, required: bond });
     }
     
-    // Record resolution
-    let resolution = MarketResolution {
-        market_id: market_id.clone(),
-        winning_outcome,
-        winning_outcome_name: winning_outcome_name.clone(),
-        resolved_by: req.admin_address.clone(),
-        resolved_at: now,
-        total_payout,
-        num_winners,
-        l1_settlement_hash: None,
-        l1_settlement_status: "pending".to_string(),
-    };
-    app.resolutions.insert(market_id.clone(), resolution);
+    let proposal = app.disputes
+        .dispute(&market_id, &wallet, bond, claimed_outcome, num_options, reason, now)?;
     
-    app.log_activity("⚖️", "ADMIN_RESOLVE", &format!(
-        "Market '{}' resolved by admin: {} wins", market_title, winning_outcome_name
+    app.ledger.debit(&wallet, bond);
+    app.nonces.insert(wallet.clone(), tx.nonce);
+    
+    app.log_activity("🚩", "DISPUTE", &format!(
+        "{} disputed resolution of market {} with {} BB bond (proposed outcome {})",
+        wallet, market_id, bond, proposal.proposed_outcome
     ));
     
     Ok(Json(json!({
         "success": true,
         "market_id": market_id,
-        "winning_outcome": winning_outcome,
-        "winning_outcome_name": winning_outcome_name,
-        "resolved_by": req.admin_address,
-        "total_payout": total_payout,
-        "num_winners": num_winners
+        "status": proposal.status.as_str(),
+        "proposal": proposal,
+        "message": "Resolution disputed. Escalated to admin/oracle arbitration."
     })))
 }
 
-#[derive(Debug, Deserialize)]
-pub struct AdminResolveRequest {
-    pub admin_address: String,
+/// POST /markets/:id/finalize - Finalize an undisputed proposal after its window
+#[utoipa::path(
+    post,
+    path = "/markets/{id}/finalize",
+    tag = "resolution",
+    params(
+        ("id" = String, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 200, description = "Resolution finalized", body = Object),
+        (status = 404, description = "No open proposal", body = ApiError),
+        (status = 409, description = "Still in challenge window or disputed", body = ApiError),
+    )
+)]
+pub async fn finalize_resolution(
+    State(state): State<SharedState>,
+    Path(market_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    
+    let (proposer, settlement) = {
+        let app = state.lock().unwrap();
+        
+        let proposer = app.disputes.get(&market_id)
+            .map(|p| p.proposer.clone())
+            .ok_or_else(|| DisputeError::ProposalNotFound(market_id.clone()))?;
+        
+        let settlement = app.disputes
+            .undisputed_settlement(&market_id, now)?;
+        (proposer, settlement)
+    };
+    
+    let (resolution, share_payouts) = crate::app_state::resolve_market(&state, &market_id, &settlement, &proposer, now)
+        .await?;
+    
+    Ok(Json(resolution_response(&resolution, &share_payouts, &settlement)))
+}
+
+/// Request to arbitrate a disputed resolution (signed by an admin or oracle)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ArbitrateDisputeRequest {
+    /// Final outcome according to the arbiter
+    pub outcome: usize,
+}
+
+/// POST /admin/disputes/:market_id/arbitrate - Decide a disputed resolution
+/// 
+/// Admins decide immediately. Oracles vote, and the dispute settles once
+/// `multi_sig_threshold` oracles agree on an outcome.
+#[utoipa::path(
+    post,
+    path = "/admin/disputes/{market_id}/arbitrate",
+    tag = "resolution",
+    params(
+        ("market_id" = String, Path, description = "Market ID"),
+    ),
+    request_body = ArbitrateDisputeRequest,
+    responses(
+        (status = 200, description = "Dispute arbitrated", body = Object),
+        (status = 400, description = "Invalid outcome", body = ApiError),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an oracle or admin, or is a party to the dispute", body = ApiError),
+        (status = 404, description = "No open proposal", body = ApiError),
+        (status = 409, description = "Not disputed", body = ApiError),
+    )
+)]
+pub async fn arbitrate_dispute(
+    State(state): State<SharedState>,
+    Path(market_id): Path<String>,
+    auth: Authorized<OracleRole, ArbitrateDisputeRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let arbiter = auth.address();
+    let outcome = auth.body.outcome;
+    let is_admin = state.roles.has_role(auth.signer(), Role::Admin);
+    
+    let (settlement, resolved_by) = {
+        let mut app = state.lock().unwrap();
+        
+        let num_options = app.markets.get(&market_id).map(|m| m.options.len()).ok_or_else(|| {
+            ApiError::MarketNotFound(market_id.clone())
+        })?;
+        let threshold = app.oracle_config.multi_sig_threshold;
+        let before = json!(app.disputes.get(&market_id));
+        
+        let settlement = app.disputes
+            .arbitrate(&market_id, &arbiter, outcome, num_options, is_admin, threshold)?;
+        let after = json!(app.disputes.get(&market_id));
+        app.audit(
+            &auth.request,
+            "ARBITRATION_VOTE",
+            &format!("Outcome {} on disputed market {}", outcome, market_id),
+            before,
+            after,
+        );
+        
+        let Some(settlement) = settlement else {
+            return Ok(Json(json!({
+                "success": true,
+                "market_id": market_id,
+                "status": ProposalStatus::Disputed.as_str(),
+                "vote_recorded": true,
+                "threshold": threshold.max(1),
+                "message": "Vote recorded. Waiting for more oracle votes."
+            })));
+        };
+        
+        let resolved_by = settlement.decided_by.clone();
+        (settlement, resolved_by)
+    };
+    
+    let (resolution, share_payouts) = crate::app_state::resolve_market(&state, &market_id, &settlement, &resolved_by, now)
+        .await?;
+    
+    Ok(Json(resolution_response(&resolution, &share_payouts, &settlement)))
+}
+
+fn resolution_response(
+    resolution: &MarketResolution,
+    share_payouts: &[(String, f64)],
+    settlement: &crate::market_resolve::BondSettlement,
+) -> Value {
+    json!({
+        "success": true,
+        "market_id": resolution.market_id,
+        "status": ProposalStatus::Finalized.as_str(),
+        "winning_outcome": resolution.winning_outcome,
+        "winning_outcome_name": resolution.winning_outcome_name,
+        "resolved_by": resolution.resolved_by,
+        "resolved_at": resolution.resolved_at,
+        "payouts": {
+            "total_payout": resolution.total_payout,
+            "num_winners": resolution.num_winners,
+            "winners": share_payouts.iter().map(|(wallet, amount)| {
+                json!({ "wallet": wallet, "payout": amount })
+            }).collect::<Vec<_>>()
+        },
+        "bonds": {
+            "proposal_upheld": settlement.proposal_upheld,
+            "returned": settlement.payouts.iter().map(|(wallet, amount)| {
+                json!({ "wallet": wallet, "amount": amount })
+            }).collect::<Vec<_>>(),
+            "slashed": settlement.slashed.iter().map(|(wallet, amount)| {
+                json!({ "wallet": wallet, "amount": amount })
+            }).collect::<Vec<_>>()
+        },
+        "l1_settlement_status": resolution.l1_settlement_status,
+        "message": format!("Market resolved. {} will be settled to L1.", resolution.total_payout)
+    })
+}
+
+/// GET /disputes - List open resolution proposals and disputes
+#[utoipa::path(
+    get,
+    path = "/disputes",
+    tag = "resolution",
+    responses(
+        (status = 200, description = "Open proposals and disputes", body = Object),
+    )
+)]
+pub async fn list_disputes(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let app = state.lock().unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    
+    let proposals: Vec<Value> = app.disputes.list_open().into_iter().map(|p| {
+        json!({
+            "proposal": p,
+            "time_remaining_secs": p.time_remaining_secs(now)
+        })
+    }).collect();
+    
+    Json(json!({
+        "success": true,
+        "count": proposals.len(),
+        "challenge_window_secs": app.disputes.challenge_window_secs,
+        "min_bond": app.disputes.min_bond,
+        "proposals": proposals
+    }))
+}
+
+/// GET /resolver/status - Price resolver funding and its last pass
+#[utoipa::path(
+    get,
+    path = "/resolver/status",
+    tag = "resolution",
+    responses(
+        (status = 200, description = "Resolver balance, required bond and the due markets it could not propose", body = Object),
+    )
+)]
+pub async fn get_resolver_status(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let mut app = state.lock().unwrap();
+    crate::price_resolver::check_funding(&mut app);
+    Json(json!({
+        "success": true,
+        "resolver": app.resolver
+    }))
 }
 
 /// GET /markets/:id/resolution - Get resolution details for a market
+#[utoipa::path(
+    get,
+    path = "/markets/{id}/resolution",
+    tag = "resolution",
+    params(
+        ("id" = String, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 200, description = "Resolution details", body = Object),
+        (status = 404, description = "Market not found", body = ApiError),
+    )
+)]
 pub async fn get_market_resolution(
     State(state): State<SharedState>,
     Path(market_id): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+) -> Result<Json<Value>, ApiError> {
     let app = state.lock().unwrap();
     
     if let Some(resolution) = app.resolutions.get(&market_id) {
         Ok(Json(json!({
             "success": true,
             "resolved": true,
-            "resolution": resolution
+            "resolution": resolution,
+            "proposal": app.disputes.get(&market_id)
+        })))
+    } else if let Some(proposal) = app.disputes.get(&market_id) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        Ok(Json(json!({
+            "success": true,
+            "resolved": false,
+            "status": proposal.status.as_str(),
+            "proposal": proposal,
+            "time_remaining_secs": proposal.time_remaining_secs(now),
+            "message": "Resolution proposed but not yet finalized"
         })))
     } else if app.markets.contains_key(&market_id) {
         Ok(Json(json!({
@@ -2566,10 +3230,7 @@ pub async fn get_market_resolution(
             "message": "Market exists but has not been resolved"
         })))
     } else {
-        Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        }))))
+        Err(ApiError::NotFound("Market not found".to_string()))
     }
 }
 
@@ -2577,82 +3238,69 @@ pub async fn get_market_resolution(
 // RESOLUTION REDEMPTION HANDLERS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ClaimWinningsRequest {
     pub wallet: String,
-    pub signature: Option<String>,
-    pub nonce: Option<u64>,
 }
 
 /// POST /shares/claim/:market_id - Claim winnings from a resolved market
 /// 
 /// After a market resolves, winners can claim their winnings.
-/// Winning shares are redeemed 1:1 for BB tokens.
+/// Winning shares are redeemed 1:1 for BB tokens. The payout only ever
+/// goes to `wallet`'s own balance, so the request is not signed.
+#[utoipa::path(
+    post,
+    path = "/shares/claim/{market_id}",
+    tag = "shares",
+    params(
+        ("market_id" = String, Path, description = "Market ID"),
+    ),
+    request_body = ClaimWinningsRequest,
+    responses(
+        (status = 200, description = "Winnings paid", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+        (status = 409, description = "Market not resolved", body = ApiError),
+    )
+)]
 pub async fn claim_market_winnings(
     State(state): State<SharedState>,
     Path(market_id): Path<String>,
     Json(req): Json<ClaimWinningsRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let mut app = state.lock().unwrap();
-    
-    // Check market exists and is resolved
-    let market = app.markets.get(&market_id).ok_or_else(|| {
-        (StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Market not found"
-        })))
-    })?;
-    
-    if !market.is_resolved {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "Market not yet resolved. Cannot claim winnings."
-        }))));
-    }
-    
-    let winning_outcome = market.winning_option.ok_or_else(|| {
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
-            "success": false,
-            "error": "Market marked resolved but no winning outcome set"
-        })))
-    })?;
-    
-    // Get user's position
-    let position = app.shares.get_position(&req.wallet, &market_id);
-    
-    // Calculate winnings based on winning outcome
-    let winning_shares = if winning_outcome == 0 {
-        position.yes_shares
-    } else {
-        position.no_shares
+) -> Result<Json<Value>, ApiError> {
+    let winning_outcome = {
+        let app = state.lock().unwrap();
+        
+        // Check market exists and is resolved
+        let market = app.markets.get(&market_id).ok_or_else(|| {
+            ApiError::NotFound("Market not found".to_string())
+        })?;
+        
+        if !market.is_resolved {
+            if app.disputes.is_pending(&market_id) {
+                let proposal = app.disputes.get(&market_id).unwrap();
+                return Err(ApiError::Conflict("Resolution pending finalization. Cannot claim winnings yet.".to_string()).with(json!({
+                    "status": proposal.status.as_str(),
+                    "challenge_deadline": proposal.challenge_deadline
+                })));
+            }
+            return Err(ApiError::Conflict("Market not yet resolved. Cannot claim winnings.".to_string()));
+        }
+        
+        market.winning_option.ok_or_else(|| {
+            ApiError::Internal("Market marked resolved but no winning outcome set".to_string())
+        })?
     };
     
-    if winning_shares <= 0.0 {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "No winning shares to claim",
-            "your_position": {
-                "yes_shares": position.yes_shares,
-                "no_shares": position.no_shares
-            },
-            "winning_outcome": winning_outcome
-        }))));
-    }
-    
-    // Calculate payout (1 winning share = 1 BB)
-    let payout = winning_shares;
-    
-    // Debit the winning shares
-    let outcome_idx = OutcomeIndex::from_usize(winning_outcome);
-    if let Err(e) = app.shares.debit_shares_simple(&req.wallet, &market_id, outcome_idx, winning_shares) {
-        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
-            "success": false,
-            "error": format!("Failed to debit shares: {}", e)
-        }))));
-    }
+    // Redeem the winning shares 1:1 for BB on the market's actor
+    let payout = state.books.open(&market_id, None)
+        .claim(&req.wallet, winning_outcome)
+        .await
+        .map_err(|e| trade_error(e, "Failed to debit shares").with(json!({ "winning_outcome": winning_outcome })))?;
+    let winning_shares = payout;
     
-    // Credit BB to wallet
-    app.ledger.credit(&req.wallet, payout);
+    let mut app = state.lock().unwrap();
+    app.record_session_claim(&req.wallet, payout);
     
     // Log activity
     app.log_activity("💰", "CLAIM", &format!(
@@ -2667,7 +3315,7 @@ pub async fn claim_market_winnings(
         "winning_outcome": winning_outcome,
         "shares_redeemed": winning_shares,
         "bb_received": payout,
-        "new_balance": app.ledger.balance(&req.wallet)
+        "new_balance": state.ledger.balance(&req.wallet)
     })))
 }
 
@@ -2675,67 +3323,98 @@ pub async fn claim_market_winnings(
 // ORACLE/ADMIN MANAGEMENT HANDLERS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-#[derive(Debug, Deserialize)]
+/// Oracle to whitelist (signed by an admin)
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AddOracleRequest {
-    pub admin_address: String,
+    /// Oracle's public key hex or L1_ address
     pub oracle_address: String,
 }
 
-/// POST /admin/oracles - Add a new oracle to the whitelist
+/// POST /admin/oracles - Grant the oracle role
+#[utoipa::path(
+    post,
+    path = "/admin/oracles",
+    tag = "oracles",
+    request_body = AddOracleRequest,
+    responses(
+        (status = 200, description = "Oracle added", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 409, description = "Already an oracle", body = ApiError),
+    )
+)]
 pub async fn add_oracle(
     State(state): State<SharedState>,
-    Json(req): Json<AddOracleRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let mut app = state.lock().unwrap();
-    
-    if !app.oracle_config.is_admin(&req.admin_address) {
-        return Err((StatusCode::FORBIDDEN, Json(json!({
-            "success": false,
-            "error": "Admin access required"
-        }))));
-    }
-    
-    app.oracle_config.add_oracle(req.oracle_address.clone());
+    auth: Authorized<AdminRole, AddOracleRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let now = chrono::Utc::now().timestamp() as u64;
+    let before = state.roles.holders(Role::Oracle);
+    let change = state.roles.change(RoleAction::Grant, Role::Oracle, &auth.body.oracle_address, auth.request.clone(), now)?;
     
-    app.log_activity("🔐", "ORACLE_ADD", &format!(
-        "Admin {} added oracle {}", req.admin_address, req.oracle_address
-    ));
+    let mut app = state.lock().unwrap();
+    app.audit(
+        &auth.request,
+        "ORACLE_ADD",
+        &format!("Added oracle {}", change.subject),
+        json!({ "oracles": before }),
+        json!({ "oracles": state.roles.holders(Role::Oracle) }),
+    );
     
     Ok(Json(json!({
         "success": true,
-        "message": format!("Oracle {} added to whitelist", req.oracle_address),
-        "total_oracles": app.oracle_config.oracle_whitelist.len()
+        "message": format!("Oracle {} added to whitelist", change.subject),
+        "total_oracles": state.roles.holders(Role::Oracle).len()
     })))
 }
 
-/// DELETE /admin/oracles/:address - Remove an oracle from whitelist
+/// DELETE /admin/oracles/:address - Revoke the oracle role
+#[utoipa::path(
+    delete,
+    path = "/admin/oracles/{address}",
+    tag = "oracles",
+    params(
+        ("address" = String, Path, description = "Oracle public key hex or L1_ address"),
+    ),
+    responses(
+        (status = 200, description = "Oracle removed", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 409, description = "Not an oracle", body = ApiError),
+    )
+)]
 pub async fn remove_oracle(
     State(state): State<SharedState>,
     Path(oracle_address): Path<String>,
-    Json(req): Json<AdminResolveRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let mut app = state.lock().unwrap();
-    
-    if !app.oracle_config.is_admin(&req.admin_address) {
-        return Err((StatusCode::FORBIDDEN, Json(json!({
-            "success": false,
-            "error": "Admin access required"
-        }))));
-    }
+    auth: Authorized<AdminRole>,
+) -> Result<Json<Value>, ApiError> {
+    let now = chrono::Utc::now().timestamp() as u64;
+    let before = state.roles.holders(Role::Oracle);
+    let change = state.roles.change(RoleAction::Revoke, Role::Oracle, &oracle_address, auth.request.clone(), now)?;
     
-    app.oracle_config.remove_oracle(&oracle_address);
-    
-    app.log_activity("🔐", "ORACLE_REMOVE", &format!(
-        "Admin {} removed oracle {}", req.admin_address, oracle_address
-    ));
+    let mut app = state.lock().unwrap();
+    app.audit(
+        &auth.request,
+        "ORACLE_REMOVE",
+        &format!("Removed oracle {}", change.subject),
+        json!({ "oracles": before }),
+        json!({ "oracles": state.roles.holders(Role::Oracle) }),
+    );
     
     Ok(Json(json!({
         "success": true,
-        "message": format!("Oracle {} removed from whitelist", oracle_address)
+        "message": format!("Oracle {} removed from whitelist", change.subject)
     })))
 }
 
-/// GET /admin/oracles - List all whitelisted oracles
+/// GET /admin/oracles - List oracles and admins
+#[utoipa::path(
+    get,
+    path = "/admin/oracles",
+    tag = "oracles",
+    responses(
+        (status = 200, description = "Admins and oracles", body = Object),
+    )
+)]
 pub async fn list_oracles(
     State(state): State<SharedState>,
 ) -> Json<Value> {
@@ -2743,106 +3422,288 @@ pub async fn list_oracles(
     
     Json(json!({
         "success": true,
-        "oracles": app.oracle_config.oracle_whitelist.iter().collect::<Vec<_>>(),
-        "admins": app.oracle_config.admin_addresses.iter().collect::<Vec<_>>(),
+        "oracles": state.roles.holders(Role::Oracle),
+        "admins": state.roles.holders(Role::Admin),
         "multi_sig_threshold": app.oracle_config.multi_sig_threshold,
         "high_value_threshold": app.oracle_config.high_value_threshold
     }))
 }
 
+/// Grant or revoke a role (signed by an admin)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RoleChangeRequest {
+    pub action: RoleAction,
+    pub role: Role,
+    /// Public key hex or L1_ address gaining or losing the role
+    pub subject: String,
+}
+
+/// POST /admin/roles - Grant or revoke a role
+#[utoipa::path(
+    post,
+    path = "/admin/roles",
+    tag = "oracles",
+    request_body = RoleChangeRequest,
+    responses(
+        (status = 200, description = "Role changed", body = RoleChange),
+        (status = 400, description = "Subject is not a public key", body = ApiError),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 409, description = "No change, or revoking the last admin", body = ApiError),
+    )
+)]
+pub async fn change_role(
+    State(state): State<SharedState>,
+    auth: Authorized<AdminRole, RoleChangeRequest>,
+) -> Result<Json<RoleChange>, ApiError> {
+    let now = chrono::Utc::now().timestamp() as u64;
+    let req = &auth.body;
+    let before = state.roles.holders(req.role);
+    let change = state.roles.change(req.action, req.role, &req.subject, auth.request.clone(), now)?;
+    
+    let mut app = state.lock().unwrap();
+    let action = match req.action {
+        RoleAction::Grant => "ROLE_GRANT",
+        RoleAction::Revoke => "ROLE_REVOKE",
+    };
+    app.audit(
+        &auth.request,
+        action,
+        &format!("{} role for {}", req.role.as_str(), change.subject),
+        json!({ "role": req.role, "holders": before }),
+        json!({ "role": req.role, "holders": state.roles.holders(req.role) }),
+    );
+    
+    Ok(Json(change))
+}
+
+/// GET /admin/roles - Role holders and the signed change history
+#[utoipa::path(
+    get,
+    path = "/admin/roles",
+    tag = "oracles",
+    responses(
+        (status = 200, description = "Holders per role, members by key and every grant/revocation", body = Object),
+    )
+)]
+pub async fn list_roles(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let holders: serde_json::Map<String, Value> = Role::ALL.iter()
+        .map(|role| (role.as_str().to_string(), json!(state.roles.holders(*role))))
+        .collect();
+    
+    Json(json!({
+        "success": true,
+        "holders": holders,
+        "members": state.roles.members(),
+        "changes": state.roles.changes(),
+    }))
+}
+
+/// GET /admin/audit/verify - Check the audit log's hash chain end to end
+///
+/// Re-hashes every stored entry, checks each links to the one before and
+/// that embedded requests still verify against their signers.
+#[utoipa::path(
+    get,
+    path = "/admin/audit/verify",
+    tag = "audit",
+    responses(
+        (status = 200, description = "Verification report; `valid` is false at the first break", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 500, description = "Log could not be read", body = ApiError),
+    )
+)]
+pub async fn verify_audit_log(
+    State(state): State<SharedState>,
+    _auth: Authorized<AdminRole>,
+) -> Result<Json<Value>, ApiError> {
+    let log = state.lock().unwrap().audit_log.clone();
+    let report = log.verify().map_err(ApiError::Internal)?;
+    let (entries, _) = log.head();
+    
+    Ok(Json(json!({
+        "success": true,
+        "valid": report.valid,
+        "entries": entries,
+        "verified": report.verified,
+        "head": report.head,
+        "first_break": report.first_break
+    })))
+}
+
+/// GET /admin/audit/export - The audit log as NDJSON, one entry per line
+#[utoipa::path(
+    get,
+    path = "/admin/audit/export",
+    tag = "audit",
+    responses(
+        (status = 200, description = "Stored audit entries, oldest first", body = String, content_type = "application/x-ndjson"),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 500, description = "Log could not be read", body = ApiError),
+    )
+)]
+pub async fn export_audit_log(
+    State(state): State<SharedState>,
+    _auth: Authorized<AdminRole>,
+) -> Result<Response, ApiError> {
+    let log = state.lock().unwrap().audit_log.clone();
+    let ndjson = log.export_ndjson().map_err(ApiError::Internal)?;
+    
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"audit.ndjson\""),
+        ],
+        ndjson,
+    ).into_response())
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // L1 SETTLEMENT HANDLERS (Real Implementation)
 // ═══════════════════════════════════════════════════════════════════════════════
 
-use crate::rpc::L1BlackBookRpc;
+use crate::rpc::L1SettlementRequest;
 
-#[derive(Debug, Deserialize)]
+/// Settlement to submit (signed by an admin)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(default)]
 pub struct SettlementRequest {
     /// Optional: settle specific market. If None, settles all pending.
     pub market_id: Option<String>,
-    /// Admin address initiating settlement
-    pub admin_address: Option<String>,
 }
 
 /// POST /settle - Submit market resolution to L1 for recording
 /// 
 /// This records the market outcome on L1 for audit trail and finality.
+#[utoipa::path(
+    post,
+    path = "/settle",
+    tag = "settlement",
+    request_body = SettlementRequest,
+    responses(
+        (status = 200, description = "Settlement report", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 502, description = "Nothing could be settled", body = ApiError),
+    )
+)]
 pub async fn settle_to_l1_real(
     State(state): State<SharedState>,
-    Json(req): Json<SettlementRequest>,
-) -> Json<Value> {
-    let mut app = state.lock().unwrap();
-    
-    // Collect markets to settle
-    let markets_to_settle: Vec<String> = if let Some(market_id) = req.market_id {
-        vec![market_id]
-    } else {
-        // Get all resolved but unsettled markets
-        app.resolutions.iter()
-            .filter(|(_, res)| res.l1_settlement_status == "pending")
-            .map(|(id, _)| id.clone())
-            .collect()
+    auth: Authorized<AdminRole, SettlementRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let req = &auth.body;
+    // Collect settlements to submit (inside lock)
+    let (l1, to_settle): (Arc<dyn L1Backend>, Vec<L1SettlementRequest>) = {
+        let app = state.lock().unwrap();
+        let to_settle = app.resolutions.iter()
+            .filter(|(id, res)| match &req.market_id {
+                Some(market_id) => *id == market_id,
+                // Get all resolved but unsettled markets
+                None => res.l1_settlement_status == "pending",
+            })
+            .map(|(id, res)| L1SettlementRequest {
+                market_id: id.clone(),
+                winning_outcome: res.winning_outcome,
+                total_payout: res.total_payout,
+                num_winners: res.num_winners,
+                resolved_at: res.resolved_at,
+            })
+            .collect();
+        (app.l1.clone(), to_settle)
     };
     
-    if markets_to_settle.is_empty() {
-        return Json(json!({
+    if to_settle.is_empty() {
+        return Ok(Json(json!({
             "success": true,
             "message": "No markets pending settlement",
             "settled": []
-        }));
+        })));
     }
     
+    let pending: Vec<String> = to_settle.iter().map(|s| s.market_id.clone()).collect();
     let mut settled = Vec::new();
-    let failed: Vec<serde_json::Value> = Vec::new();
+    let mut failed = Vec::new();
     
-    // Check if L1 is available
-    let l1_url = std::env::var("L1_RPC_URL").ok();
-    
-    for market_id in markets_to_settle {
-        if let Some(resolution) = app.resolutions.get_mut(&market_id) {
-            if l1_url.is_some() {
-                // Real L1 settlement (async would be better but keeping sync for simplicity)
-                // In production, this would call L1's /rpc/settlement endpoint
-                
-                // For now, mark as "submitted" and generate a mock tx hash
-                let tx_hash = format!("L1_TX_{}", uuid::Uuid::new_v4().simple());
-                resolution.l1_settlement_status = "submitted".to_string();
-                resolution.l1_settlement_hash = Some(tx_hash.clone());
-                
+    for settlement in to_settle {
+        // Submit to L1 (no lock held)
+        let result = l1.submit_settlement(&settlement).await;
+        
+        let mut app = state.lock().unwrap();
+        match result {
+            Ok(response) if response.success => {
+                let tx_hash = response.l1_tx_hash.unwrap_or_default();
+                if let Some(resolution) = app.resolutions.get_mut(&settlement.market_id) {
+                    resolution.l1_settlement_status = "submitted".to_string();
+                    resolution.l1_settlement_hash = Some(tx_hash.clone());
+                }
                 settled.push(json!({
-                    "market_id": market_id,
+                    "market_id": settlement.market_id,
                     "l1_tx_hash": tx_hash,
                     "status": "submitted",
-                    "winning_outcome": resolution.winning_outcome,
-                    "total_payout": resolution.total_payout
+                    "winning_outcome": settlement.winning_outcome,
+                    "total_payout": settlement.total_payout
                 }));
-                
                 app.log_activity("📤", "L1_SETTLE", &format!(
-                    "Market {} settlement submitted to L1: {}", market_id, tx_hash
+                    "Market {} settlement submitted to L1: {}", settlement.market_id, tx_hash
                 ));
-            } else {
-                // Mock mode - just mark as settled locally
-                resolution.l1_settlement_status = "mock_settled".to_string();
-                resolution.l1_settlement_hash = Some(format!("MOCK_{}", uuid::Uuid::new_v4().simple()));
-                
-                settled.push(json!({
-                    "market_id": market_id,
-                    "status": "mock_settled",
-                    "message": "L1_RPC_URL not configured - settlement recorded locally only"
+            }
+            Ok(response) => {
+                failed.push(json!({
+                    "market_id": settlement.market_id,
+                    "error": response.error.unwrap_or_else(|| "L1 rejected settlement".to_string())
                 }));
             }
+            Err(e) => {
+                app.log_activity("⚠️", "L1_SETTLE", &format!(
+                    "Market {} settlement failed: {}", settlement.market_id, e
+                ));
+                failed.push(json!({ "market_id": settlement.market_id, "error": e }));
+            }
         }
     }
+    state.lock().unwrap().audit(
+        &auth.request,
+        "L1_SETTLE_REQUEST",
+        &format!(
+            "Settle {}: {} submitted, {} failed",
+            req.market_id.as_deref().unwrap_or("all pending markets"), settled.len(), failed.len()
+        ),
+        json!({ "pending": pending }),
+        json!({
+            "submitted": settled.iter().map(|s| s["market_id"].clone()).collect::<Vec<_>>(),
+            "failed": failed
+        }),
+    );
     
-    Json(json!({
-        "success": true,
+    // Partial failures are reported per market; nothing settled is an error
+    if settled.is_empty() {
+        return Err(ApiError::L1Unavailable("No settlement was accepted by L1".to_string()).with(json!({
+            "failed": failed,
+            "l1_backend": l1.describe()
+        })));
+    }
+    
+    Ok(Json(json!({
+        "success": failed.is_empty(),
         "settled": settled,
         "failed": failed,
-        "l1_mode": if l1_url.is_some() { "live" } else { "mock" }
-    }))
+        "l1_backend": l1.describe()
+    })))
 }
 
 /// GET /settle/pending - Get markets pending L1 settlement
+#[utoipa::path(
+    get,
+    path = "/settle/pending",
+    tag = "settlement",
+    responses(
+        (status = 200, description = "Resolved markets awaiting settlement", body = Object),
+    )
+)]
 pub async fn get_pending_settlements(
     State(state): State<SharedState>,
 ) -> Json<Value> {
@@ -2869,220 +3730,413 @@ pub async fn get_pending_settlements(
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-// BRIDGE ENDPOINT HANDLERS
+// L2 BLOCK EXPLORER HANDLERS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-use crate::bridge::{BridgeCompleteRequest, BridgeDirection};
-use crate::rpc::{L1RpcConfig, L1WithdrawRequest};
-use crate::app_state::PendingWithdrawal;
+use crate::ledger::TransactionResponse;
 
-#[derive(Debug, Deserialize)]
-pub struct InitiateBridgeRequest {
-    pub wallet: String,
-    pub amount: f64,
-    pub target_address: String, // L1 address for L2→L1
-    pub signature: String,
-    pub nonce: u64,
-    pub timestamp: u64,
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BlocksQuery {
+    pub limit: Option<usize>,
+}
+
+/// GET /blocks - Most recent blocks (?limit, default 20)
+#[utoipa::path(
+    get,
+    path = "/blocks",
+    tag = "blocks",
+    params(BlocksQuery),
+    responses(
+        (status = 200, description = "Most recent blocks", body = Object),
+    )
+)]
+pub async fn list_blocks(
+    State(state): State<SharedState>,
+    Query(params): Query<BlocksQuery>,
+) -> Json<Value> {
+    let app = state.lock().unwrap();
+    let limit = params.limit.unwrap_or(20).min(100);
+    
+    Json(json!({
+        "success": true,
+        "height": app.blocks.height(),
+        "pending_transactions": app.ledger.read().transactions.len() - app.blocks.sealed_tx_count(),
+        "blocks": app.blocks.recent(limit)
+    }))
+}
+
+/// GET /blocks/latest - Latest sealed block
+#[utoipa::path(
+    get,
+    path = "/blocks/latest",
+    tag = "blocks",
+    responses(
+        (status = 200, description = "Latest block", body = Object),
+        (status = 404, description = "No blocks yet", body = ApiError),
+    )
+)]
+pub async fn get_latest_block(
+    State(state): State<SharedState>,
+) -> Result<Json<Value>, ApiError> {
+    let app = state.lock().unwrap();
+    let block = app.blocks.latest()
+        .ok_or_else(|| ApiError::NotFound("No blocks produced yet".to_string()))?;
+    
+    Ok(Json(json!({
+        "success": true,
+        "block": block
+    })))
+}
+
+/// GET /blocks/:height - Block by height
+#[utoipa::path(
+    get,
+    path = "/blocks/{height}",
+    tag = "blocks",
+    params(
+        ("height" = u64, Path, description = "Block height"),
+    ),
+    responses(
+        (status = 200, description = "Block", body = Object),
+        (status = 404, description = "Block not found", body = ApiError),
+    )
+)]
+pub async fn get_block(
+    State(state): State<SharedState>,
+    Path(height): Path<u64>,
+) -> Result<Json<Value>, ApiError> {
+    let app = state.lock().unwrap();
+    let block = app.blocks.block(height)
+        .ok_or_else(|| ApiError::NotFound(format!("Block {} not found", height)))?;
+    
+    Ok(Json(json!({
+        "success": true,
+        "block": block
+    })))
+}
+
+/// GET /tx/:id - Transaction with its block position and inclusion proof
+#[utoipa::path(
+    get,
+    path = "/tx/{id}",
+    tag = "blocks",
+    params(
+        ("id" = String, Path, description = "Transaction ID"),
+    ),
+    responses(
+        (status = 200, description = "Transaction with inclusion proof", body = Object),
+        (status = 404, description = "Transaction not found", body = ApiError),
+    )
+)]
+pub async fn get_transaction(
+    State(state): State<SharedState>,
+    Path(tx_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let app = state.lock().unwrap();
+    let tx = app.ledger.read().transactions.iter()
+        .find(|tx| tx.id == tx_id)
+        .cloned()
+        .ok_or_else(|| ApiError::NotFound(format!("Transaction {} not found", tx_id)))?;
+    
+    let inclusion = app.blocks.proof_for(&tx_id);
+    Ok(Json(json!({
+        "success": true,
+        "status": if inclusion.is_some() { "sealed" } else { "pending" },
+        "transaction": TransactionResponse::from(&tx),
+        "block_height": inclusion.as_ref().map(|p| p.block_height),
+        "block_index": inclusion.as_ref().map(|p| p.tx_index),
+        "inclusion": inclusion
+    })))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// L2 STATE COMMITMENT HANDLERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::state_publisher;
+
+/// GET /state/root - Newest state root and newest root accepted by L1
+#[utoipa::path(
+    get,
+    path = "/state/root",
+    tag = "state",
+    responses(
+        (status = 200, description = "Current state root", body = Object),
+    )
+)]
+pub async fn get_state_root(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let app = state.lock().unwrap();
+    
+    Json(json!({
+        "success": true,
+        "sequencer_pubkey": app.sequencer.pubkey_hex(),
+        "interval_secs": app.state_commitments.interval_secs,
+        "latest": app.state_commitments.latest(),
+        "latest_posted": app.state_commitments.latest_posted()
+    }))
+}
+
+/// GET /state/commitments - State root history (newest first)
+#[utoipa::path(
+    get,
+    path = "/state/commitments",
+    tag = "state",
+    responses(
+        (status = 200, description = "State root commitments", body = Object),
+    )
+)]
+pub async fn list_state_commitments(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let app = state.lock().unwrap();
+    let commitments = app.state_commitments.commitments();
+    
+    Json(json!({
+        "success": true,
+        "count": commitments.len(),
+        "commitments": commitments
+    }))
+}
+
+/// GET /state/proof/:account - Prove an account's balance and positions
+/// against the latest state root posted to L1
+#[utoipa::path(
+    get,
+    path = "/state/proof/{account}",
+    tag = "state",
+    params(
+        ("account" = String, Path, description = "Account name or address"),
+    ),
+    responses(
+        (status = 200, description = "Merkle proof of the account balance", body = Object),
+        (status = 404, description = "No committed root or unknown account", body = ApiError),
+    )
+)]
+pub async fn get_state_proof(
+    State(state): State<SharedState>,
+    Path(account): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let app = state.lock().unwrap();
+    let address = app.ledger.resolve(&account).unwrap_or(account);
+    
+    let (commitment, proofs) = app.state_commitments.proofs_for_account(&address)
+        .ok_or_else(|| ApiError::NotFound("No state root has been posted to L1 yet".to_string()))?;
+    if proofs.is_empty() {
+        return Err(ApiError::NotFound(format!("Account {} is not in state root epoch {}", address, commitment.epoch)));
+    }
+    let (balance, positions): (Vec<_>, Vec<_>) = proofs.into_iter()
+        .partition(|p| matches!(p.leaf, crate::state_root::StateLeaf::Account { .. }));
+    
+    Ok(Json(json!({
+        "success": true,
+        "address": address,
+        "commitment": commitment,
+        "balance": balance.into_iter().next(),
+        "positions": positions
+    })))
+}
+
+/// POST /state/commit - Commit and post a state root now (admin only)
+#[utoipa::path(
+    post,
+    path = "/state/commit",
+    tag = "state",
+    responses(
+        (status = 200, description = "State root committed", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+    )
+)]
+pub async fn commit_state_root(
+    State(state): State<SharedState>,
+    auth: Authorized<AdminRole>,
+) -> Result<Json<Value>, ApiError> {
+    let before = json!(state.lock().unwrap().state_commitments.latest());
+    
+    let latest = state_publisher::commit_and_post(&state).await;
+    state.lock().unwrap().audit(&auth.request, "STATE_COMMIT", "Forced a state root commitment", before, json!(latest));
+    
+    Ok(Json(json!({
+        "success": true,
+        "latest": latest
+    })))
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// BRIDGE ENDPOINT HANDLERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::bridge::{BridgeCompleteRequest, BridgeError, BridgeStatus};
+use crate::bridge_relayer::{self, RelayOutcome};
+use crate::bridge_proof::{Attestation, DepositProof};
+
 /// POST /bridge/withdraw - Initiate L2→L1 bridge (withdraw from L2)
 /// 
-/// Locks BB on L2, calls L1's /bridge/withdraw endpoint.
-/// Returns pending status - client can poll /bridge/status/:bridge_id
+/// Body is a `SignedTransaction` with a `bridge` payload targeting L1; the
+/// signing key's wallet is debited.
+/// Locks BB on L2. With batching enabled (default) the withdrawal is queued
+/// into the open batch; its Merkle proof appears at /bridge/status/:bridge_id
+/// once the batch is sealed and it is claimable on L1 once the root is committed.
+/// Otherwise makes the first relay attempt to L1's /bridge/withdraw, and the
+/// bridge relayer keeps retrying in the background if L1 is unreachable.
 /// If L1 rejects, automatically refunds L2 balance.
+#[utoipa::path(
+    post,
+    path = "/bridge/withdraw",
+    tag = "bridge",
+    request_body = SignedTransaction,
+    responses(
+        (status = 200, description = "Withdrawal queued", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Bad signature or expired request", body = ApiError),
+        (status = 502, description = "L1 unreachable or rejected the call", body = ApiError),
+    )
+)]
 pub async fn bridge_withdraw(
     State(state): State<SharedState>,
-    Json(req): Json<InitiateBridgeRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    Json(tx): Json<SignedTransaction>,
+) -> Result<Json<Value>, ApiError> {
+    let (target_address, amount) = match &tx.payload {
+        TransactionPayload::Bridge { target_layer, target_address, amount }
+            if target_layer.eq_ignore_ascii_case("L1") => (target_address.clone(), *amount),
+        _ => {
+            return Err(ApiError::BadRequest("Expected a bridge payload targeting L1".to_string()));
+        }
+    };
+
+    let expiry_secs = state.lock().unwrap().config.transactions.expiry_secs;
+    tx.validate_with_window(expiry_secs)?;
+
+    if target_address.is_empty() {
+        return Err(ApiError::BadRequest("target_address is required".to_string()));
+    }
+
+    // Withdraw from the key that signed; sender_address is not signed
+    let wallet = signer_account(&tx)?;
+
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_secs();
     
-    // Validate timestamp
-    if now.abs_diff(req.timestamp) > 3600 {
-        return Err((StatusCode::UNAUTHORIZED, Json(json!({
-            "success": false,
-            "error": "Bridge request expired"
-        }))));
-    }
-    
     // --- Phase 1: Validation & L2 Debit ---
-    let (bridge_id, bridge) = {
+    let (bridge_id, batched) = {
         let mut app = state.lock().unwrap();
         
         // Check nonce
-        let last_nonce = app.nonces.get(&req.wallet).copied().unwrap_or(0);
-        if req.nonce <= last_nonce {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": format!("Invalid nonce: got {}, expected > {}", req.nonce, last_nonce)
-            }))));
+        let last_nonce = app.nonces.get(&wallet).copied().unwrap_or(0);
+        if tx.nonce <= last_nonce {
+            return Err(ApiError::InvalidNonce { got: tx.nonce, last: last_nonce });
         }
         
-        // Check balance
-        let balance = app.ledger.balance(&req.wallet);
-        if balance < req.amount {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": format!("Insufficient balance: have {} BB, need {} BB", balance, req.amount)
-            }))));
+        // Check balance (session funds settle through /session/settle instead)
+        let balance = app.ledger.balance(&wallet) - app.session_balance(&wallet);
+        if balance < amount {
+            return Err(ApiError::BadRequest(format!("Insufficient balance: have {} BB outside sessions, need {} BB", balance, amount)));
         }
         
         // Validate amount bounds
-        if req.amount < 0.01 {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": "Minimum bridge amount is 0.01 BB"
-            }))));
-        }
-        if req.amount > 1_000_000.0 {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": "Maximum bridge amount is 1,000,000 BB"
-            }))));
+        if amount < 0.01 {
+            return Err(ApiError::BadRequest("Minimum bridge amount is 0.01 BB".to_string()));
+        }
+        if amount > 1_000_000.0 {
+            return Err(ApiError::BadRequest("Maximum bridge amount is 1,000,000 BB".to_string()));
         }
         
-        // Debit balance (lock on L2)
-        app.ledger.debit(&req.wallet, req.amount);
-        app.nonces.insert(req.wallet.clone(), req.nonce);
-        
-        // Create and store bridge record
-        let bridge = app.bridge_manager.store_pending_withdrawal(
-            req.wallet.clone(),
-            req.target_address.clone(),
-            req.amount,
+        app.nonces.insert(wallet.clone(), tx.nonce);
+        let (bridge_id, batched) = app.begin_withdrawal(
+            &wallet,
+            &target_address,
+            amount,
+            &tx.signature,
+            tx.nonce,
+            tx.timestamp,
+            now,
         );
-        let bridge_id = bridge.bridge_id.clone();
-        
-        // Store pending withdrawal for tracking
-        app.pending_withdrawals.insert(bridge_id.clone(), PendingWithdrawal {
-            bridge_id: bridge_id.clone(),
-            wallet_address: req.wallet.clone(),
-            amount: req.amount,
-            l1_target: req.target_address.clone(),
-            status: "pending".to_string(),
-            created_at: now,
-            l1_tx_hash: None,
-            error: None,
-            poll_count: 0,
-            last_poll: None,
-        });
         
-        app.log_activity("🌉", "BRIDGE_WITHDRAW_INIT", &format!(
-            "{} initiated bridge of {} BB to L1 {}",
-            req.wallet, req.amount, req.target_address
-        ));
+        // Persist before calling L1 so a crash can't lose a debited withdrawal
+        if let Err(e) = app.save_bridge_state() {
+            eprintln!("⚠️  Failed to persist bridge state: {}", e);
+        }
         
-        (bridge_id, bridge)
+        (bridge_id, batched)
     }; // Release lock before async L1 call
     
-    // --- Phase 2: Call L1 /bridge/withdraw (async) ---
-    let mut l1_rpc = L1BlackBookRpc::new(L1RpcConfig::from_env());
-    
-    let l1_request = L1WithdrawRequest {
-        from_l2_address: req.wallet.clone(),
-        to_l1_address: req.target_address.clone(),
-        amount: req.amount,
-        bridge_id: bridge_id.clone(),
-        signature: req.signature.clone(),
-        timestamp: req.timestamp,
-        nonce: req.nonce.to_string(),
-    };
+    if let Some((batch_id, leaf)) = batched {
+        return Ok(Json(json!({
+            "success": true,
+            "bridge_id": bridge_id,
+            "direction": "L2_TO_L1",
+            "from_address": wallet,
+            "to_address": target_address,
+            "amount": amount,
+            "status": "queued",
+            "batch_id": batch_id,
+            "leaf": leaf,
+            "message": "Queued for the next L1 batch. Poll /bridge/status/:bridge_id for the inclusion proof."
+        })));
+    }
     
-    match l1_rpc.withdraw_to_l1(l1_request).await {
-        Ok(l1_response) => {
-            let mut app = state.lock().unwrap();
-            
-            if l1_response.success {
-                // L1 accepted - update bridge status
-                let _ = app.bridge_manager.update_withdrawal_l1_submitted(
-                    &bridge_id,
-                    l1_response.l1_tx_hash.clone(),
-                );
-                
-                // Update pending withdrawal
-                if let Some(pw) = app.pending_withdrawals.get_mut(&bridge_id) {
-                    pw.status = "l1_submitted".to_string();
-                    pw.l1_tx_hash = l1_response.l1_tx_hash.clone();
-                }
-                
-                app.log_activity("🌉", "BRIDGE_L1_ACCEPTED", &format!(
-                    "L1 accepted withdrawal {} for {} BB (tx: {:?})",
-                    bridge_id, req.amount, l1_response.l1_tx_hash
-                ));
-                
-                Ok(Json(json!({
-                    "success": true,
-                    "bridge_id": bridge_id,
-                    "direction": "L2_TO_L1",
-                    "from_address": req.wallet,
-                    "to_address": req.target_address,
-                    "amount": req.amount,
-                    "status": l1_response.status,
-                    "l1_tx_hash": l1_response.l1_tx_hash,
-                    "message": "Bridge submitted to L1. Poll /bridge/status/:bridge_id for confirmation."
-                })))
-            } else {
-                // L1 rejected - refund L2 balance
-                let error_msg = l1_response.error.unwrap_or_else(|| "L1 rejected withdrawal".to_string());
-                
-                // Refund L2 balance
-                app.ledger.credit(&req.wallet, req.amount);
-                let _ = app.bridge_manager.refund_withdrawal(&bridge_id, error_msg.clone());
-                
-                // Update pending withdrawal
-                if let Some(pw) = app.pending_withdrawals.get_mut(&bridge_id) {
-                    pw.status = "refunded".to_string();
-                    pw.error = Some(error_msg.clone());
-                }
-                
-                app.log_activity("🌉", "BRIDGE_L1_REJECTED", &format!(
-                    "L1 rejected withdrawal {} - refunded {} BB to {}",
-                    bridge_id, req.amount, req.wallet
-                ));
-                
-                Err((StatusCode::BAD_REQUEST, Json(json!({
-                    "success": false,
-                    "bridge_id": bridge_id,
-                    "error": error_msg,
-                    "refunded": true,
-                    "message": "L1 rejected withdrawal. L2 balance has been refunded."
-                }))))
-            }
-        }
-        Err(l1_error) => {
-            // L1 communication error - refund L2 balance
-            let mut app = state.lock().unwrap();
-            
-            // Refund L2 balance
-            app.ledger.credit(&req.wallet, req.amount);
-            let _ = app.bridge_manager.refund_withdrawal(&bridge_id, l1_error.clone());
-            
-            // Update pending withdrawal
-            if let Some(pw) = app.pending_withdrawals.get_mut(&bridge_id) {
-                pw.status = "refunded".to_string();
-                pw.error = Some(l1_error.clone());
-            }
-            
-            app.log_activity("🌉", "BRIDGE_L1_ERROR", &format!(
-                "L1 communication failed for {} - refunded {} BB to {}",
-                bridge_id, req.amount, req.wallet
-            ));
-            
-            Err((StatusCode::SERVICE_UNAVAILABLE, Json(json!({
-                "success": false,
+    // --- Phase 2: First relay attempt (async) ---
+    match bridge_relayer::relay_withdrawal(&state, &bridge_id).await {
+        RelayOutcome::Completed { l1_tx_hash } | RelayOutcome::Submitted { l1_tx_hash } => {
+            let status = state.lock().unwrap().pending_withdrawals.get(&bridge_id)
+                .map(|pw| pw.status.clone())
+                .unwrap_or_default();
+            Ok(Json(json!({
+                "success": true,
                 "bridge_id": bridge_id,
-                "error": format!("L1 communication failed: {}", l1_error),
-                "refunded": true,
-                "message": "Could not reach L1. L2 balance has been refunded."
-            }))))
+                "direction": "L2_TO_L1",
+                "from_address": wallet,
+                "to_address": target_address,
+                "amount": amount,
+                "status": status,
+                "l1_tx_hash": l1_tx_hash,
+                "message": "Bridge submitted to L1. Poll /bridge/status/:bridge_id for confirmation."
+            })))
         }
-    }
+        RelayOutcome::Retrying { error, next_attempt_at, .. } => {
+            Ok(Json(json!({
+                "success": true,
+                "bridge_id": bridge_id,
+                "direction": "L2_TO_L1",
+                "from_address": wallet,
+                "to_address": target_address,
+                "amount": amount,
+                "status": "retrying",
+                "error": error,
+                "next_attempt_at": next_attempt_at,
+                "message": "Could not reach L1. The relayer will retry; poll /bridge/status/:bridge_id."
+            })))
+        }
+        RelayOutcome::Refunded { error } => {
+            Err(ApiError::L1Unavailable(error).with(json!({
+                "bridge_id": bridge_id,
+                "refunded": true,
+                "message": "L1 rejected withdrawal. L2 balance has been refunded."
+            })))
+        }
+        RelayOutcome::Skipped => {
+            // Background relayer got there first
+            let status = state.lock().unwrap().pending_withdrawals.get(&bridge_id)
+                .map(|pw| pw.status.clone())
+                .unwrap_or_default();
+            Ok(Json(json!({
+                "success": true,
+                "bridge_id": bridge_id,
+                "direction": "L2_TO_L1",
+                "amount": amount,
+                "status": status,
+                "message": "Poll /bridge/status/:bridge_id for confirmation."
+            })))
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BridgeDepositRequest {
     pub bridge_id: String,
     pub from_address: String,  // L1 address
@@ -3090,25 +4144,38 @@ pub struct BridgeDepositRequest {
     pub amount: f64,
     pub l1_tx_hash: String,
     pub l1_slot: u64,
+    /// Relayer attestations or Merkle inclusion proof of the L1 tx
+    pub proof: Option<DepositProof>,
 }
 
 /// POST /bridge/deposit - Complete L1→L2 bridge (deposit to L2)
 /// 
 /// Called by L1 (or relayer) when L1→L2 bridge is confirmed.
+/// Requires a proof: signatures from the configured relayer key set, or a
+/// Merkle inclusion proof against a trusted L1 root for `l1_slot`.
 /// Mints BB on L2 for the recipient.
 /// Idempotent - ignores duplicate l1_tx_hash.
+#[utoipa::path(
+    post,
+    path = "/bridge/deposit",
+    tag = "bridge",
+    request_body = BridgeDepositRequest,
+    responses(
+        (status = 200, description = "Deposit credited", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Deposit proof rejected", body = ApiError),
+        (status = 409, description = "Deposit already credited", body = ApiError),
+    )
+)]
 pub async fn bridge_deposit(
     State(state): State<SharedState>,
     Json(req): Json<BridgeDepositRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+) -> Result<Json<Value>, ApiError> {
     let mut app = state.lock().unwrap();
     
     // Validate amount
     if req.amount <= 0.0 {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({
-            "success": false,
-            "error": "Amount must be positive"
-        }))));
+        return Err(ApiError::BadRequest("Amount must be positive".to_string()));
     }
     
     // Idempotency check - prevent double-crediting same L1 tx
@@ -3132,13 +4199,29 @@ pub async fn bridge_deposit(
         l1_slot: req.l1_slot,
     };
     
-    match app.bridge_manager.complete_from_l1(&complete_request) {
+    // Only mint against proof from L1
+    let proof = req.proof.as_ref().ok_or_else(|| ApiError::Unauthorized("Deposit proof required (relayer attestations or L1 inclusion proof)".to_string()))?;
+    let verification = match app.deposit_verifier.verify(&complete_request, proof) {
+        Ok(verification) => verification,
+        Err(e) => {
+            app.log_activity("🚫", "BRIDGE_DEPOSIT_REJECTED", &format!(
+                "Rejected deposit {} of {} BB to {}: {}",
+                req.bridge_id, req.amount, req.to_address, e
+            ));
+            return Err(ApiError::from(e).with(json!({ "bridge_id": req.bridge_id })));
+        }
+    };
+    
+    match app.bridge_manager.complete_verified_deposit(&complete_request, Some(verification.label())) {
         Ok(_bridge) => {
             // Credit the L2 wallet
             app.ledger.credit(&req.to_address, req.amount);
             
             // Mark L1 tx as processed (idempotency)
             app.processed_l1_txs.insert(req.l1_tx_hash.clone());
+            if let Err(e) = app.save_bridge_state() {
+                eprintln!("⚠️  Failed to persist bridge state: {}", e);
+            }
             
             app.log_activity("🌉", "BRIDGE_DEPOSIT", &format!(
                 "L1→L2 bridge complete: {} BB to {} (L1 tx: {})",
@@ -3154,23 +4237,103 @@ pub async fn bridge_deposit(
                 "amount": req.amount,
                 "l1_tx_hash": req.l1_tx_hash,
                 "status": "completed",
+                "verification": verification,
                 "new_balance": app.ledger.balance(&req.to_address)
             })))
         }
-        Err(e) => {
-            Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": format!("Bridge completion failed: {:?}", e)
-            }))))
-        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TrustedRootRequest {
+    pub l1_slot: u64,
+    /// L1 block/state root (hex)
+    pub root: String,
+    pub attestations: Vec<Attestation>,
+}
+
+/// POST /bridge/l1-roots - Trust an L1 root for inclusion-proof deposits
+/// 
+/// The root must be signed by enough keys from the relayer key set.
+#[utoipa::path(
+    post,
+    path = "/bridge/l1-roots",
+    tag = "bridge",
+    request_body = TrustedRootRequest,
+    responses(
+        (status = 200, description = "Root trusted", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Attestations rejected", body = ApiError),
+    )
+)]
+pub async fn add_trusted_l1_root(
+    State(state): State<SharedState>,
+    Json(req): Json<TrustedRootRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let root = hex::decode(&req.root).map_err(|e| ApiError::BadRequest(format!("Invalid root hex: {}", e)))?;
+    
+    let mut app = state.lock().unwrap();
+    let signers = app.deposit_verifier.add_attested_root(req.l1_slot, root, &req.attestations)?;
+    
+    if let Err(e) = app.save_bridge_state() {
+        eprintln!("⚠️  Failed to persist bridge state: {}", e);
     }
+    app.log_activity("🌳", "L1_ROOT_TRUSTED", &format!(
+        "Trusted L1 root {} for slot {} ({} signer(s))",
+        req.root, req.l1_slot, signers.len()
+    ));
+    
+    Ok(Json(json!({
+        "success": true,
+        "l1_slot": req.l1_slot,
+        "root": req.root.to_lowercase(),
+        "signers": signers
+    })))
+}
+
+/// GET /bridge/trust - Deposit trust anchors (relayer keys, threshold, L1 roots)
+#[utoipa::path(
+    get,
+    path = "/bridge/trust",
+    tag = "bridge",
+    responses(
+        (status = 200, description = "Relayer set and trusted L1 roots", body = Object),
+    )
+)]
+pub async fn get_bridge_trust(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let app = state.lock().unwrap();
+    let verifier = &app.deposit_verifier;
+    
+    Json(json!({
+        "success": true,
+        "relayer_keys": verifier.relayer_keys(),
+        "threshold": verifier.threshold(),
+        "trusted_roots": verifier.trusted_roots().iter()
+            .map(|(slot, root)| json!({ "l1_slot": slot, "root": hex::encode(root) }))
+            .collect::<Vec<_>>()
+    }))
 }
 
 /// GET /bridge/status/:bridge_id - Get bridge status
+#[utoipa::path(
+    get,
+    path = "/bridge/status/{bridge_id}",
+    tag = "bridge",
+    params(
+        ("bridge_id" = String, Path, description = "Bridge ID"),
+    ),
+    responses(
+        (status = 200, description = "Bridge status", body = Object),
+        (status = 404, description = "Bridge not found", body = ApiError),
+    )
+)]
 pub async fn get_bridge_status(
     State(state): State<SharedState>,
     Path(bridge_id): Path<String>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+) -> Result<Json<Value>, ApiError> {
     let app = state.lock().unwrap();
     
     if let Some(bridge) = app.bridge_manager.get_status(&bridge_id) {
@@ -3185,48 +4348,214 @@ pub async fn get_bridge_status(
                 "status": format!("{:?}", bridge.status),
                 "created_at": bridge.created_at,
                 "l1_tx_hash": bridge.l1_tx_hash,
-                "l1_slot": bridge.l1_slot
-            }
+                "l1_slot": bridge.l1_slot,
+                "verified_by": bridge.verified_by,
+                "error": bridge.error,
+                "relay_attempts": bridge.relay_attempts,
+                "last_relay_error": bridge.last_relay_error,
+                "next_attempt_at": bridge.next_attempt_at
+            },
+            "batch": batch_claim_json(&app, &bridge_id)
         })))
     } else {
-        Err((StatusCode::NOT_FOUND, Json(json!({
-            "success": false,
-            "error": "Bridge not found"
-        }))))
+        Err(BridgeError::BridgeNotFound(bridge_id).into())
     }
 }
 
-/// GET /bridge/list/:wallet - List all bridges for a wallet
-pub async fn list_wallet_bridges(
+/// Batch membership and inclusion proof for a batched withdrawal
+fn batch_claim_json(app: &crate::app_state::AppState, bridge_id: &str) -> Value {
+    if let Some(claim) = app.withdrawal_batches.proof_for(bridge_id) {
+        let mut value = json!(claim);
+        value["leaf_hex"] = json!(hex::encode(claim.leaf.to_bytes()));
+        return value;
+    }
+    match app.withdrawal_batches.locate(bridge_id) {
+        // Batch still open: no root yet
+        Some((batch_id, leaf_index)) => json!({
+            "batch_id": batch_id,
+            "batch_status": "open",
+            "leaf_index": leaf_index
+        }),
+        None => Value::Null,
+    }
+}
+
+/// Summary of a withdrawal batch
+fn batch_summary_json(batch: &crate::withdrawal_batch::WithdrawalBatch) -> Value {
+    json!({
+        "batch_id": batch.batch_id,
+        "status": batch.status,
+        "root": batch.root,
+        "leaf_count": batch.leaves.len(),
+        "total_amount": batch.total_amount(),
+        "created_at": batch.created_at,
+        "sealed_at": batch.sealed_at,
+        "committed_at": batch.committed_at,
+        "l1_tx_hash": batch.l1_tx_hash,
+        "relay_attempts": batch.relay_attempts,
+        "last_relay_error": batch.last_relay_error,
+        "error": batch.error
+    })
+}
+
+/// GET /bridge/batches - List withdrawal batches (newest first)
+#[utoipa::path(
+    get,
+    path = "/bridge/batches",
+    tag = "bridge",
+    responses(
+        (status = 200, description = "Withdrawal batches", body = Object),
+    )
+)]
+pub async fn list_withdrawal_batches(
     State(state): State<SharedState>,
-    Path(wallet): Path<String>,
 ) -> Json<Value> {
     let app = state.lock().unwrap();
-    
-    let bridges = app.bridge_manager.list_by_address(&wallet);
+    let batcher = &app.withdrawal_batches;
     
     Json(json!({
+        "success": true,
+        "enabled": batcher.enabled,
+        "max_size": batcher.max_size,
+        "max_age_secs": batcher.max_age_secs,
+        "batches": batcher.batches().into_iter().map(batch_summary_json).collect::<Vec<_>>()
+    }))
+}
+
+/// GET /bridge/batches/:batch_id - Batch details with all leaves
+#[utoipa::path(
+    get,
+    path = "/bridge/batches/{batch_id}",
+    tag = "bridge",
+    params(
+        ("batch_id" = u64, Path, description = "Batch ID"),
+    ),
+    responses(
+        (status = 200, description = "Withdrawal batch", body = Object),
+        (status = 404, description = "Batch not found", body = ApiError),
+    )
+)]
+pub async fn get_withdrawal_batch(
+    State(state): State<SharedState>,
+    Path(batch_id): Path<u64>,
+) -> Result<Json<Value>, ApiError> {
+    let app = state.lock().unwrap();
+    let batch = app.withdrawal_batches.batch(batch_id).ok_or_else(|| ApiError::NotFound("Batch not found".to_string()))?;
+    
+    Ok(Json(json!({
+        "success": true,
+        "batch": batch_summary_json(batch),
+        "withdrawals": batch.leaves.iter().zip(&batch.bridge_ids).enumerate().map(|(index, (leaf, bridge_id))| json!({
+            "leaf_index": index,
+            "bridge_id": bridge_id,
+            "address": leaf.address,
+            "amount": leaf.amount,
+            "nonce": leaf.nonce
+        })).collect::<Vec<_>>()
+    })))
+}
+
+/// Query params for a wallet's bridges
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WalletBridgesQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default: 100, max: 1000)
+    pub limit: Option<usize>,
+    /// Sort order by creation time: "asc", "desc" (default: "desc")
+    pub order: Option<String>,
+    /// Filter by status: "pending", "confirmed", "completed", "failed", "cancelled"
+    pub status: Option<String>,
+    /// Created at or after (unix seconds)
+    pub from_timestamp: Option<u64>,
+    /// Created at or before (unix seconds)
+    pub to_timestamp: Option<u64>,
+}
+
+/// GET /bridge/list/:wallet - List a wallet's bridges, newest first
+#[utoipa::path(
+    get,
+    path = "/bridge/list/{wallet}",
+    tag = "bridge",
+    params(
+        ("wallet" = String, Path, description = "Wallet address"),
+        WalletBridgesQuery,
+    ),
+    responses(
+        (status = 200, description = "Page of the wallet's bridges", body = Object),
+        (status = 400, description = "Invalid cursor or filter", body = ApiError),
+    )
+)]
+pub async fn list_wallet_bridges(
+    State(state): State<SharedState>,
+    Path(wallet): Path<String>,
+    Query(params): Query<WalletBridgesQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let status = parse_filter("status", params.status.as_deref(), BridgeStatus::parse)?;
+    let order = parse_filter("order", params.order.as_deref(), SortOrder::parse)?.unwrap_or_default();
+    let range = TimeRange::new(params.from_timestamp, params.to_timestamp);
+
+    let app = state.lock().unwrap();
+    let bridges: Vec<_> = app.bridge_manager.list_by_address(&wallet).into_iter()
+        .filter(|b| status.is_none_or(|s| b.status == s))
+        .filter(|b| range.contains(b.created_at))
+        .collect();
+    let request = PageRequest::new(params.cursor.clone(), params.limit).with_order(order);
+    let page = paginate(bridges, |b| (b.created_at, b.bridge_id.clone()), &request)?;
+
+    Ok(Json(json!({
         "success": true,
         "wallet": wallet,
-        "bridges": bridges.iter().map(|b| json!({
+        "bridges": page.items.iter().map(|b| json!({
             "bridge_id": b.bridge_id,
             "direction": format!("{:?}", b.direction),
             "amount": b.amount,
             "status": format!("{:?}", b.status),
             "created_at": b.created_at
-        })).collect::<Vec<_>>()
-    }))
+        })).collect::<Vec<_>>(),
+        "pagination": page.meta(),
+    })))
 }
 
 /// GET /bridge/stats - Get overall bridge statistics
+#[utoipa::path(
+    get,
+    path = "/bridge/stats",
+    tag = "bridge",
+    responses(
+        (status = 200, description = "Bridge statistics", body = Object),
+    )
+)]
 pub async fn get_bridge_stats(
     State(state): State<SharedState>,
 ) -> Json<Value> {
     let app = state.lock().unwrap();
     let stats = app.bridge_manager.stats();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let stuck = app.bridge_manager.stuck(now);
     
     Json(json!({
         "success": true,
+        "relayer": {
+            "retrying": stats.retrying,
+            "stuck": stats.stuck,
+            "oldest_pending_secs": stats.oldest_pending_secs,
+            "stuck_bridges": stuck.iter().map(|b| json!({
+                "bridge_id": b.bridge_id,
+                "direction": format!("{:?}", b.direction),
+                "amount": b.amount,
+                "status": format!("{:?}", b.status),
+                "age_secs": now.saturating_sub(b.created_at),
+                "relay_attempts": b.relay_attempts,
+                "last_relay_at": b.last_relay_at,
+                "last_relay_error": b.last_relay_error,
+                "next_attempt_at": b.next_attempt_at
+            })).collect::<Vec<_>>()
+        },
         "stats": {
             "total_bridges": stats.total,
             "pending": stats.pending,
@@ -3246,9 +4575,10 @@ pub async fn get_bridge_stats(
 // ═══════════════════════════════════════════════════════════════════════════════
 
 use crate::app_state::L2Session;
-use crate::rpc::{L1SessionStartRequest, L1SessionSettleRequest};
+use crate::rpc::L1SessionStartRequest;
+use crate::session_settlement::{self, SettleError};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SessionStartRequest {
     pub wallet_address: String,
     pub requested_amount: f64,
@@ -3261,10 +4591,23 @@ pub struct SessionStartRequest {
 /// 
 /// Calls L1 to lock balance, creates L2 session for instant betting.
 /// Session expires after 1 hour (auto-settle required).
+#[utoipa::path(
+    post,
+    path = "/session/start",
+    tag = "session",
+    request_body = SessionStartRequest,
+    responses(
+        (status = 200, description = "Session started", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Bad signature or expired request", body = ApiError),
+        (status = 409, description = "Session already active", body = ApiError),
+        (status = 502, description = "L1 unreachable or rejected the call", body = ApiError),
+    )
+)]
 pub async fn session_start(
     State(state): State<SharedState>,
     Json(req): Json<SessionStartRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+) -> Result<Json<Value>, ApiError> {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
@@ -3272,23 +4615,24 @@ pub async fn session_start(
     
     // Validate timestamp
     if now.abs_diff(req.timestamp) > 300 {
-        return Err((StatusCode::UNAUTHORIZED, Json(json!({
-            "success": false,
-            "error": "Session request expired"
-        }))));
+        return Err(ApiError::from(SignedTxError::Expired).with(json!({ "window_secs": 300 })));
     }
     
     // Check if user already has active session
     {
         let app = state.lock().unwrap();
         if let Some(existing) = app.sessions.get(&req.wallet_address) {
+            if existing.awaiting_settlement() {
+                return Err(ApiError::Conflict("Previous session has expired and is still being settled to L1".to_string()).with(json!({
+                    "session_id": existing.session_id,
+                    "status": existing.status
+                })));
+            }
             if existing.status == "active" && !existing.is_expired() {
-                return Err((StatusCode::CONFLICT, Json(json!({
-                    "success": false,
-                    "error": "Active session already exists",
+                return Err(ApiError::Conflict("Active session already exists".to_string()).with(json!({
                     "session_id": existing.session_id,
                     "expires_in_secs": existing.time_remaining_secs()
-                }))));
+                })));
             }
         }
     }
@@ -3297,7 +4641,7 @@ pub async fn session_start(
     let session_id = format!("session_{}", uuid::Uuid::new_v4().simple());
     
     // Call L1 to start session (lock L1 balance)
-    let mut l1_rpc = L1BlackBookRpc::new(L1RpcConfig::from_env());
+    let l1 = state.lock().unwrap().l1.clone();
     
     let l1_request = L1SessionStartRequest {
         wallet_address: req.wallet_address.clone(),
@@ -3308,7 +4652,7 @@ pub async fn session_start(
         nonce: req.nonce.clone(),
     };
     
-    match l1_rpc.start_session(l1_request).await {
+    match l1.start_session(&l1_request).await {
         Ok(l1_response) => {
             if l1_response.success {
                 let mut app = state.lock().unwrap();
@@ -3348,22 +4692,16 @@ pub async fn session_start(
                 })))
             } else {
                 let error = l1_response.error.unwrap_or_else(|| "L1 rejected session start".to_string());
-                Err((StatusCode::BAD_REQUEST, Json(json!({
-                    "success": false,
-                    "error": error
-                }))))
+                Err(ApiError::BadRequest(error))
             }
         }
         Err(e) => {
-            Err((StatusCode::SERVICE_UNAVAILABLE, Json(json!({
-                "success": false,
-                "error": format!("L1 communication failed: {}", e)
-            }))))
+            Err(ApiError::L1Unavailable(e))
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SessionSettleRequest {
     pub wallet_address: String,
     pub signature: String,
@@ -3376,104 +4714,59 @@ pub struct SessionSettleRequest {
 /// - Session expires (1 hour max)
 /// - User wants to withdraw to L1
 /// - User wants to start a new session
+#[utoipa::path(
+    post,
+    path = "/session/settle",
+    tag = "session",
+    request_body = SessionSettleRequest,
+    responses(
+        (status = 200, description = "Session settled", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "No session", body = ApiError),
+        (status = 409, description = "Session not active", body = ApiError),
+        (status = 502, description = "L1 unreachable or rejected the call", body = ApiError),
+    )
+)]
 pub async fn session_settle(
     State(state): State<SharedState>,
     Json(req): Json<SessionSettleRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    
-    // Get session info
-    let (session, current_l2_balance) = {
-        let app = state.lock().unwrap();
-        
-        let session = app.sessions.get(&req.wallet_address)
-            .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({
-                "success": false,
-                "error": "No active session found"
-            }))))?
-            .clone();
-        
-        if session.status != "active" {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "success": false,
-                "error": format!("Session is not active (status: {})", session.status)
-            }))));
-        }
-        
-        let current_balance = app.ledger.balance(&req.wallet_address);
-        (session, current_balance)
-    };
-    
-    // Calculate final PnL
-    let initial_credit = session.l2_balance;
-    let pnl = current_l2_balance - initial_credit;
-    
-    // Call L1 to settle session
-    let mut l1_rpc = L1BlackBookRpc::new(L1RpcConfig::from_env());
-    
-    let l1_request = L1SessionSettleRequest {
-        wallet_address: req.wallet_address.clone(),
-        session_id: session.session_id.clone(),
-        final_l2_balance: current_l2_balance,
-        pnl,
-        bet_count: session.bet_count,
-        signature: req.signature.clone(),
-        timestamp: req.timestamp,
-    };
-    
-    match l1_rpc.settle_session(l1_request).await {
-        Ok(l1_response) => {
-            if l1_response.success {
-                let mut app = state.lock().unwrap();
-                
-                // Clear L2 balance (settled to L1)
-                app.ledger.debit(&req.wallet_address, current_l2_balance);
-                
-                // Update session status
-                if let Some(s) = app.sessions.get_mut(&req.wallet_address) {
-                    s.status = "settled".to_string();
-                    s.l1_settlement_hash = l1_response.l1_tx_hash.clone();
-                }
-                
-                app.log_activity("🎮", "SESSION_SETTLE", &format!(
-                    "{} settled session: {} bets, PnL: {:.2} BB, new L1 balance: {:?}",
-                    req.wallet_address, session.bet_count, pnl, l1_response.new_l1_balance
-                ));
-                
-                Ok(Json(json!({
-                    "success": true,
-                    "session_id": session.session_id,
-                    "wallet_address": req.wallet_address,
-                    "bet_count": session.bet_count,
-                    "final_l2_balance": current_l2_balance,
-                    "pnl": pnl,
-                    "l1_tx_hash": l1_response.l1_tx_hash,
-                    "new_l1_balance": l1_response.new_l1_balance,
-                    "status": "settled",
-                    "message": "Session settled. PnL written to L1."
-                })))
-            } else {
-                let error = l1_response.error.unwrap_or_else(|| "L1 rejected settlement".to_string());
-                Err((StatusCode::BAD_REQUEST, Json(json!({
-                    "success": false,
-                    "error": error
-                }))))
-            }
-        }
-        Err(e) => {
+) -> Result<Json<Value>, ApiError> {
+    match session_settlement::settle(&state, &req.wallet_address, &req.signature, req.timestamp, false).await {
+        Ok(settlement) => Ok(Json(json!({
+            "success": true,
+            "session_id": settlement.session_id,
+            "wallet_address": req.wallet_address,
+            "bet_count": settlement.bet_count,
+            "final_l2_balance": settlement.final_l2_balance,
+            "pnl": settlement.pnl,
+            "l1_tx_hash": settlement.l1_tx_hash,
+            "new_l1_balance": settlement.new_l1_balance,
+            "receipt": settlement.receipt,
+            "status": "settled",
+            "message": "Session settled. PnL written to L1."
+        }))),
+        Err(e @ SettleError::NoSession) => Err(ApiError::NotFound(e.to_string())),
+        Err(e @ SettleError::NotActive(_)) => Err(ApiError::Conflict(e.to_string())),
+        Err(SettleError::Rejected(error)) => Err(ApiError::BadRequest(error)),
+        Err(SettleError::L1Unavailable(error)) => {
             // L1 failed - session remains active, user can retry
-            Err((StatusCode::SERVICE_UNAVAILABLE, Json(json!({
-                "success": false,
-                "error": format!("L1 settlement failed: {}. Session remains active, please retry.", e)
-            }))))
+            Err(ApiError::L1Unavailable(format!("{}. Session remains active, please retry.", error)))
         }
     }
 }
 
 /// GET /session/status/:wallet - Get session status
+#[utoipa::path(
+    get,
+    path = "/session/status/{wallet}",
+    tag = "session",
+    params(
+        ("wallet" = String, Path, description = "Wallet address"),
+    ),
+    responses(
+        (status = 200, description = "Session status", body = Object),
+    )
+)]
 pub async fn session_status(
     State(state): State<SharedState>,
     Path(wallet): Path<String>,
@@ -3481,25 +4774,27 @@ pub async fn session_status(
     let app = state.lock().unwrap();
     
     if let Some(session) = app.sessions.get(&wallet) {
-        let current_balance = app.ledger.balance(&wallet);
-        let pnl = current_balance - session.l2_balance;
-        
         Json(json!({
             "success": true,
             "session": {
                 "session_id": session.session_id,
                 "wallet_address": session.wallet_address,
                 "l1_balance_snapshot": session.l1_balance_snapshot,
-                "initial_l2_credit": session.l2_balance,
-                "current_l2_balance": current_balance,
+                "initial_l2_credit": session.l2_credit,
+                "current_l2_balance": session.l2_balance,
                 "bet_count": session.bet_count,
-                "pnl": pnl,
+                "pnl": session.pnl,
                 "started_at": session.started_at,
                 "expires_at": session.expires_at,
                 "expires_in_secs": session.time_remaining_secs(),
                 "is_expired": session.is_expired(),
                 "status": session.status,
-                "l1_settlement_hash": session.l1_settlement_hash
+                "l1_settlement_hash": session.l1_settlement_hash,
+                "settlement_receipt": session.settlement_receipt,
+                "settlement_receipt_valid": session.settlement_receipt.as_ref().map(|r| r.verify()),
+                "settle_attempts": session.settle_attempts,
+                "next_settle_at": session.next_settle_at,
+                "last_settle_error": session.last_settle_error
             }
         }))
     } else {
@@ -3512,6 +4807,14 @@ pub async fn session_status(
 }
 
 /// GET /session/list - List all active sessions
+#[utoipa::path(
+    get,
+    path = "/session/list",
+    tag = "session",
+    responses(
+        (status = 200, description = "Active sessions", body = Object),
+    )
+)]
 pub async fn session_list(
     State(state): State<SharedState>,
 ) -> Json<Value> {
@@ -3520,11 +4823,11 @@ pub async fn session_list(
     let sessions: Vec<_> = app.sessions.values()
         .filter(|s| s.status == "active")
         .map(|s| {
-            let current_balance = app.ledger.balance(&s.wallet_address);
             json!({
                 "session_id": s.session_id,
                 "wallet_address": s.wallet_address,
-                "l2_balance": current_balance,
+                "l2_balance": s.l2_balance,
+                "pnl": s.pnl,
                 "bet_count": s.bet_count,
                 "expires_in_secs": s.time_remaining_secs(),
                 "is_expired": s.is_expired()
@@ -3537,4 +4840,1320 @@ pub async fn session_list(
         "active_sessions": sessions.len(),
         "sessions": sessions
     }))
-}
\ No newline at end of file
+}
+// ═══════════════════════════════════════════════════════════════════════════════
+// FORCED EXIT (ESCAPE HATCH) HANDLERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use axum::extract::Request;
+use axum::middleware::Next;
+use crate::escape_hatch;
+use crate::forced_exit::ExitStatus;
+
+/// Paths that keep accepting writes while frozen: exits, admin, inbound
+/// deposits (swept straight back out) and settlement to L1
+const FROZEN_ALLOWED_PATHS: &[&str] = &[
+    "/exits",
+    "/admin/",
+    "/bridge/deposit",
+    "/bridge/l1-roots",
+    "/session/settle",
+    "/state/commit",
+];
+
+/// Middleware: reject mutating requests once freeze-and-exit mode is on
+pub async fn freeze_guard(
+    State(state): State<SharedState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mutating = !matches!(request.method().as_str(), "GET" | "HEAD" | "OPTIONS");
+    let allowed = FROZEN_ALLOWED_PATHS.iter().any(|p| request.uri().path().starts_with(p));
+    if mutating && !allowed {
+        let frozen = state.lock().unwrap().exits.frozen().cloned();
+        if let Some(frozen) = frozen {
+            return ApiError::Frozen { reason: frozen.reason, frozen_at: frozen.frozen_at }.into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Account a signed request acts for: the signing key's address. A
+/// `sender_address` naming any other account is rejected.
+fn signer_account(tx: &SignedTransaction) -> Result<String, ApiError> {
+    let address = tx.signer_address();
+    if !tx.sender_address.is_empty() && !tx.sender_address.eq_ignore_ascii_case(&address) {
+        return Err(ApiError::Unauthorized(format!(
+            "sender_address {} is not the signing key's address {}",
+            tx.sender_address, address
+        )));
+    }
+    Ok(address)
+}
+
+/// POST /exits - Request a forced exit of everything the signer holds
+///
+/// Body is a `SignedTransaction` with a `forced_exit` payload. The available
+/// balance is paid out to `target_address` on L1 (settling an active session
+/// first); open share positions are valued at resolution and their payouts
+/// follow. The L2 must act before the deadline or the exit shows as overdue.
+#[utoipa::path(
+    post,
+    path = "/exits",
+    tag = "exits",
+    request_body = SignedTransaction,
+    responses(
+        (status = 200, description = "Exit requested", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Bad signature or expired request", body = ApiError),
+        (status = 409, description = "Exit already pending", body = ApiError),
+    )
+)]
+pub async fn submit_exit(
+    State(state): State<SharedState>,
+    Json(tx): Json<SignedTransaction>,
+) -> Result<Json<Value>, ApiError> {
+    let target_address = match &tx.payload {
+        TransactionPayload::ForcedExit { target_address } => target_address.clone(),
+        _ => {
+            return Err(ApiError::BadRequest("Expected a forced_exit payload".to_string()));
+        }
+    };
+
+    let expiry_secs = state.lock().unwrap().config.transactions.expiry_secs;
+    tx.validate_with_window(expiry_secs)?;
+
+    if target_address.is_empty() {
+        return Err(ApiError::BadRequest("target_address is required".to_string()));
+    }
+
+    // The exit always drains the key that signed; sender_address is not signed
+    let address = signer_account(&tx)?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let exit = {
+        let mut app = state.lock().unwrap();
+
+        // Replay protection
+        let last_nonce = app.nonces.get(&address).copied().unwrap_or(0);
+        if tx.nonce <= last_nonce {
+            return Err(ApiError::InvalidNonce { got: tx.nonce, last: last_nonce });
+        }
+
+        let balance = app.ledger.balance(&address);
+        let positions = app.exit_positions(&address);
+        let exit = app.exits.request(
+            &address,
+            &target_address,
+            balance,
+            positions,
+            &tx.signature,
+            tx.nonce,
+            tx.timestamp,
+            false,
+            now,
+        ).map_err(ApiError::Conflict)?;
+        app.nonces.insert(address.clone(), tx.nonce);
+
+        app.log_activity("🚪", "EXIT_REQUESTED", &format!(
+            "{} requested a forced exit to L1 {}: {} BB, {} position(s), deadline {}",
+            address, target_address, balance, exit.positions.len(), exit.deadline
+        ));
+        if let Err(e) = app.save_bridge_state() {
+            eprintln!("⚠️  Failed to persist exits: {}", e);
+        }
+        exit
+    };
+
+    // First processing attempt; the escape hatch task keeps going from here
+    let exit = escape_hatch::process_exit(&state, &exit.exit_id).await.unwrap_or(exit);
+
+    Ok(Json(json!({
+        "success": true,
+        "exit": exit,
+        "message": "Exit accepted. Poll /exits/:exit_id; it is reported overdue if not processed by the deadline."
+    })))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExitsQuery {
+    /// requested | awaiting_resolution | completed
+    pub status: Option<String>,
+    pub address: Option<String>,
+}
+
+/// GET /exits - Forced exits (newest first) and the freeze state
+#[utoipa::path(
+    get,
+    path = "/exits",
+    tag = "exits",
+    params(ExitsQuery),
+    responses(
+        (status = 200, description = "Exit requests", body = Object),
+        (status = 400, description = "Unknown status", body = ApiError),
+    )
+)]
+pub async fn list_exits(
+    State(state): State<SharedState>,
+    Query(params): Query<ExitsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let status = match params.status.as_deref() {
+        Some(s) => Some(serde_json::from_value::<ExitStatus>(json!(s)).map_err(|_| ApiError::BadRequest(format!("Unknown exit status '{}' (expected requested, awaiting_resolution or completed)", s)))?),
+        None => None,
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let app = state.lock().unwrap();
+    let exits: Vec<Value> = app.exits.exits().into_iter()
+        .filter(|e| status.is_none_or(|s| e.status == s))
+        .filter(|e| params.address.as_ref().is_none_or(|a| &e.address == a))
+        .map(|e| json!({
+            "exit": e,
+            "overdue": e.is_overdue(now)
+        }))
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "frozen": app.exits.frozen(),
+        "open": app.exits.open_exits().len(),
+        "overdue": app.exits.overdue(now).len(),
+        "count": exits.len(),
+        "exits": exits
+    })))
+}
+
+/// GET /exits/overdue - Exits the L2 did not act on before their deadline
+#[utoipa::path(
+    get,
+    path = "/exits/overdue",
+    tag = "exits",
+    responses(
+        (status = 200, description = "Exits past their deadline", body = Object),
+    )
+)]
+pub async fn list_overdue_exits(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let app = state.lock().unwrap();
+    let overdue = app.exits.overdue(now);
+    Json(json!({
+        "success": true,
+        "now": now,
+        "count": overdue.len(),
+        "exits": overdue
+    }))
+}
+
+/// GET /exits/:exit_id - Exit status, payouts and position valuations
+#[utoipa::path(
+    get,
+    path = "/exits/{exit_id}",
+    tag = "exits",
+    params(
+        ("exit_id" = String, Path, description = "Exit ID"),
+    ),
+    responses(
+        (status = 200, description = "Exit request", body = Object),
+        (status = 404, description = "Exit not found", body = ApiError),
+    )
+)]
+pub async fn get_exit(
+    State(state): State<SharedState>,
+    Path(exit_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let app = state.lock().unwrap();
+    let exit = app.exits.exit(&exit_id).ok_or_else(|| ApiError::NotFound(format!("Exit {} not found", exit_id)))?;
+
+    // Current state of each bridge withdrawal paid out for this exit
+    let withdrawals: Vec<Value> = exit.payouts.iter()
+        .filter_map(|p| app.pending_withdrawals.get(&p.reference))
+        .map(|pw| json!({
+            "bridge_id": pw.bridge_id,
+            "amount": pw.amount,
+            "status": pw.status,
+            "l1_tx_hash": pw.l1_tx_hash
+        }))
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "exit": exit,
+        "overdue": exit.is_overdue(now),
+        "total_paid": exit.total_paid(),
+        "unresolved_markets": exit.unresolved_markets(),
+        "withdrawals": withdrawals
+    })))
+}
+
+/// Freeze reason (signed by an admin)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FreezeRequest {
+    pub reason: String,
+}
+
+/// POST /admin/freeze - Freeze the L2 and exit every account to L1 (admin, one-way)
+///
+/// Mutating endpoints other than exits and settlement are rejected from now
+/// on, and every account holding funds or shares gets a forced exit.
+#[utoipa::path(
+    post,
+    path = "/admin/freeze",
+    tag = "exits",
+    request_body = FreezeRequest,
+    responses(
+        (status = 200, description = "Ledger frozen and exits queued", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not an admin", body = ApiError),
+        (status = 409, description = "Already frozen", body = ApiError),
+    )
+)]
+pub async fn freeze_and_exit(
+    State(state): State<SharedState>,
+    auth: Authorized<AdminRole, FreezeRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let req = &auth.body;
+    let admin = auth.address();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    {
+        let mut app = state.lock().unwrap();
+        if !app.exits.freeze(&admin, &req.reason, now) {
+            return Err(ApiError::Conflict("L2 is already frozen".to_string()).with(json!({
+                "frozen": app.exits.frozen()
+            })));
+        }
+        let frozen = json!(app.exits.frozen());
+        app.audit(&auth.request, "FREEZE", &format!("Froze the L2: {}", req.reason), json!({ "frozen": null }), json!({ "frozen": frozen }));
+        if let Err(e) = app.save_bridge_state() {
+            eprintln!("⚠️  Failed to persist exits: {}", e);
+        }
+    }
+
+    let queued = escape_hatch::queue_exits_for_all(&state).await;
+
+    // Pay out in the background; progress is visible at /exits. Exits the
+    // periodic processor is already working on are skipped by this pass.
+    let processor_state = state.clone();
+    tokio::spawn(async move {
+        escape_hatch::run_once(&processor_state).await;
+    });
+
+    let app = state.lock().unwrap();
+    Ok(Json(json!({
+        "success": true,
+        "frozen": app.exits.frozen(),
+        "exits_queued": queued.len(),
+        "exit_ids": queued,
+        "message": "L2 frozen. Every account is being settled to L1; track progress at /exits."
+    })))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PENDING EVENT INBOX HANDLERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::market_resolve::cpmm::{PendingEvent, MINIMUM_LAUNCH_LIQUIDITY, VIABILITY_PERIOD_SECONDS};
+use crate::rpc::{SignedTransaction, TransactionPayload};
+
+/// Query params for the pending event inbox
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PendingEventsQuery {
+    /// Filter by category
+    pub category: Option<String>,
+    /// Include expired events (default: false)
+    pub include_expired: Option<bool>,
+}
+
+/// GET /events/pending - Browse the pending event inbox
+#[utoipa::path(
+    get,
+    path = "/events/pending",
+    tag = "events",
+    params(PendingEventsQuery),
+    responses(
+        (status = 200, description = "Pending events", body = Object),
+    )
+)]
+pub async fn list_pending_events(
+    State(state): State<SharedState>,
+    Query(params): Query<PendingEventsQuery>,
+) -> Json<Value> {
+    let app = state.lock().unwrap();
+    let include_expired = params.include_expired.unwrap_or(false);
+    
+    let events: Vec<Value> = app.pending_events.iter()
+        .filter(|e| include_expired || !e.is_expired())
+        .filter(|e| params.category.as_ref().is_none_or(|c| e.category.eq_ignore_ascii_case(c)))
+        .map(|e| json!({
+            "event": e,
+            "is_expired": e.is_expired(),
+            "days_until_expiration": e.days_until_expiration()
+        }))
+        .collect();
+    
+    Json(json!({
+        "success": true,
+        "count": events.len(),
+        "minimum_launch_liquidity": MINIMUM_LAUNCH_LIQUIDITY,
+        "events": events
+    }))
+}
+
+/// POST /events/pending - Add an event to the inbox (moderators only)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubmitPendingEventRequest {
+    pub id: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub category: Option<String>,
+    pub options: Vec<String>,
+    pub confidence: Option<f64>,
+    pub source_url: Option<String>,
+    pub source_domain: Option<String>,
+    pub resolution_date: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/events/pending",
+    tag = "events",
+    request_body = SubmitPendingEventRequest,
+    responses(
+        (status = 200, description = "Event queued", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not a moderator", body = ApiError),
+        (status = 409, description = "Event already exists", body = ApiError),
+    )
+)]
+pub async fn submit_pending_event(
+    State(state): State<SharedState>,
+    auth: Authorized<ModeratorRole, SubmitPendingEventRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let req = &auth.body;
+    let mut app = state.lock().unwrap();
+    
+    if req.options.len() < 2 {
+        return Err(ApiError::BadRequest("At least 2 options required".to_string()));
+    }
+    
+    let id = req.id.clone().unwrap_or_else(|| format!("evt_{}", uuid::Uuid::new_v4().simple()));
+    if app.pending_events.iter().any(|e| e.id == id) || app.markets.contains_key(&id) {
+        return Err(ApiError::Conflict(format!("Event {} already exists", id)));
+    }
+    
+    let mut event = PendingEvent::new(
+        id.clone(),
+        req.title.clone(),
+        req.description.clone(),
+        req.category.clone().unwrap_or_else(|| "general".to_string()),
+        req.options.clone(),
+        req.confidence.unwrap_or(1.0),
+        req.source_url.clone().unwrap_or_default(),
+        req.source_domain.clone().unwrap_or_default(),
+    );
+    event.resolution_date = req.resolution_date.clone();
+    
+    app.audit(&auth.request, "EVENT_SUBMITTED", &format!("{} | {}", id, req.title), Value::Null, json!(event));
+    app.pending_events.push(event.clone());
+    
+    Ok(Json(json!({
+        "success": true,
+        "event": event
+    })))
+}
+
+/// Request to edit a pending event (moderators only)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EditPendingEventRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub options: Option<Vec<String>>,
+    pub resolution_date: Option<String>,
+    pub expires_at: Option<u64>,
+}
+
+/// PUT /events/pending/:id - Edit a pending event
+#[utoipa::path(
+    put,
+    path = "/events/pending/{id}",
+    tag = "events",
+    params(
+        ("id" = String, Path, description = "Pending event ID"),
+    ),
+    request_body = EditPendingEventRequest,
+    responses(
+        (status = 200, description = "Event updated", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not a moderator", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+    )
+)]
+pub async fn edit_pending_event(
+    State(state): State<SharedState>,
+    Path(event_id): Path<String>,
+    auth: Authorized<ModeratorRole, EditPendingEventRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let req = &auth.body;
+    let mut app = state.lock().unwrap();
+    
+    if let Some(options) = &req.options {
+        if options.len() < 2 {
+            return Err(ApiError::BadRequest("At least 2 options required".to_string()));
+        }
+    }
+    
+    let event = app.pending_events.iter_mut().find(|e| e.id == event_id).ok_or_else(|| {
+        ApiError::NotFound(format!("Pending event {} not found", event_id))
+    })?;
+    let before = json!(event);
+    
+    if let Some(title) = &req.title { event.title = title.clone(); }
+    if let Some(description) = &req.description { event.description = description.clone(); }
+    if let Some(category) = &req.category { event.category = category.clone(); }
+    if let Some(options) = &req.options { event.options = options.clone(); }
+    if let Some(date) = &req.resolution_date { event.resolution_date = Some(date.clone()); }
+    if let Some(expires_at) = req.expires_at { event.expires_at = Some(expires_at); }
+    let event = event.clone();
+    
+    app.audit(&auth.request, "EVENT_EDITED", &format!("Edited {}", event_id), before, json!(event));
+    
+    Ok(Json(json!({
+        "success": true,
+        "event": event
+    })))
+}
+
+/// Request to reject a pending event (moderators only)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct RejectPendingEventRequest {
+    pub reason: Option<String>,
+}
+
+/// POST /events/pending/:id/reject - Remove an event from the inbox
+#[utoipa::path(
+    post,
+    path = "/events/pending/{id}/reject",
+    tag = "events",
+    params(
+        ("id" = String, Path, description = "Pending event ID"),
+    ),
+    request_body = RejectPendingEventRequest,
+    responses(
+        (status = 200, description = "Event rejected", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not a moderator", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+    )
+)]
+pub async fn reject_pending_event(
+    State(state): State<SharedState>,
+    Path(event_id): Path<String>,
+    auth: Authorized<ModeratorRole, RejectPendingEventRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let req = &auth.body;
+    let mut app = state.lock().unwrap();
+    
+    let index = app.pending_events.iter().position(|e| e.id == event_id).ok_or_else(|| {
+        ApiError::NotFound(format!("Pending event {} not found", event_id))
+    })?;
+    let event = app.pending_events.remove(index);
+    
+    app.audit(
+        &auth.request,
+        "EVENT_REJECTED",
+        &format!("{} | {} | reason: {}", event.id, event.title, req.reason.as_deref().unwrap_or("none")),
+        json!(event),
+        Value::Null,
+    );
+    
+    Ok(Json(json!({
+        "success": true,
+        "rejected": event.id,
+        "reason": req.reason
+    })))
+}
+
+/// POST /events/pending/expire - Purge expired events from the inbox
+#[utoipa::path(
+    post,
+    path = "/events/pending/expire",
+    tag = "events",
+    responses(
+        (status = 200, description = "Expired events purged", body = Object),
+        (status = 401, description = "Unsigned, bad signature or expired request", body = ApiError),
+        (status = 403, description = "Signer is not a moderator", body = ApiError),
+    )
+)]
+pub async fn expire_pending_events(
+    State(state): State<SharedState>,
+    auth: Authorized<ModeratorRole>,
+) -> Result<Json<Value>, ApiError> {
+    let mut app = state.lock().unwrap();
+    
+    let (expired, remaining): (Vec<PendingEvent>, Vec<PendingEvent>) = app.pending_events
+        .drain(..)
+        .partition(|e| e.is_expired());
+    app.pending_events = remaining;
+    
+    let expired_ids: Vec<String> = expired.into_iter().map(|e| e.id).collect();
+    if !expired_ids.is_empty() {
+        app.audit(
+            &auth.request,
+            "EVENTS_EXPIRED",
+            &format!("Removed {} expired events", expired_ids.len()),
+            json!({ "expired": expired_ids }),
+            Value::Null,
+        );
+    }
+    
+    Ok(Json(json!({
+        "success": true,
+        "expired_count": expired_ids.len(),
+        "expired": expired_ids,
+        "remaining": app.pending_events.len()
+    })))
+}
+
+/// POST /events/launch - Launch a pending event as a market
+/// 
+/// Body is a `SignedTransaction` with a `market_launch` payload. The signer
+/// funds the CPMM pool (≥ MINIMUM_LAUNCH_LIQUIDITY), becomes the initial LP,
+/// and the market starts Provisional for the viability period.
+#[utoipa::path(
+    post,
+    path = "/events/launch",
+    tag = "events",
+    request_body = SignedTransaction,
+    responses(
+        (status = 200, description = "Market launched", body = Object),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 401, description = "Bad signature or expired request", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+        (status = 409, description = "Already launched", body = ApiError),
+        (status = 410, description = "Event expired", body = ApiError),
+    )
+)]
+pub async fn launch_pending_event(
+    State(state): State<SharedState>,
+    Json(tx): Json<SignedTransaction>,
+) -> Result<Json<Value>, ApiError> {
+    let (event_id, liquidity) = match &tx.payload {
+        TransactionPayload::MarketLaunch { event_id, liquidity } => (event_id.clone(), *liquidity),
+        _ => {
+            return Err(ApiError::BadRequest("Expected a market_launch payload".to_string()));
+        }
+    };
+    
+    let expiry_secs = state.lock().unwrap().config.transactions.expiry_secs;
+    tx.validate_with_window(expiry_secs)?;
+    
+    if liquidity < MINIMUM_LAUNCH_LIQUIDITY {
+        return Err(ApiError::BadRequest(format!("Launch liquidity must be at least {} BB (got {})", MINIMUM_LAUNCH_LIQUIDITY, liquidity)));
+    }
+    
+    // Liquidity always comes from the key that signed; sender_address is not signed
+    let launcher = signer_account(&tx)?;
+    
+    let mut app = state.lock().unwrap();
+    
+    // Replay protection
+    let last_nonce = app.nonces.get(&launcher).copied().unwrap_or(0);
+    if tx.nonce <= last_nonce {
+        return Err(ApiError::InvalidNonce { got: tx.nonce, last: last_nonce });
+    }
+    
+    let index = app.pending_events.iter().position(|e| e.id == event_id).ok_or_else(|| {
+        ApiError::NotFound(format!("Pending event {} not found", event_id))
+    })?;
+    
+    if app.pending_events[index].is_expired() {
+        return Err(ApiError::Gone(format!("Pending event {} has expired", event_id)));
+    }
+    
+    if app.markets.contains_key(&event_id) {
+        return Err(ApiError::Conflict(format!("Market {} already exists", event_id)));
+    }
+    
+    let balance = app.ledger.balance(&launcher);
+    if balance < liquidity {
+        return Err(ApiError::InsufficientBalance { available: balance, required: liquidity });
+    }
+    
+    let event = app.pending_events.remove(index);
+    app.ledger.debit(&launcher, liquidity);
+    app.nonces.insert(launcher.clone(), tx.nonce);
+    
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    
+    let mut market = PredictionMarket::new(
+        event.id.clone(),
+        event.title.clone(),
+        event.description.clone(),
+        event.category.clone(),
+        event.options.clone(),
+    );
+    
+    // Launcher is the initial LP
+    let pool = CPMMPool::new(liquidity, event.options.clone(), &launcher)
+        .with_fee_rate(app.config.market_params(&event.id).lp_fee_rate);
+    let prices = pool.calculate_prices();
+    market.cpmm_pool = Some(pool);
+    market.initial_probabilities = prices.clone();
+    market.market_status = EventStatus::Provisional;
+    market.provisional_deadline = Some(now + VIABILITY_PERIOD_SECONDS);
+    market.launched_by = Some(launcher.clone());
+    market.source_event_id = Some(event.id.clone());
+    market.source_url = Some(event.source_url.clone()).filter(|u| !u.is_empty());
+    market.dates = Some(MarketDates {
+        published: None,
+        freeze: None,
+        resolution: event.resolution_date.clone(),
+    });
+    let provisional_deadline = market.provisional_deadline;
+    let rss_event = market.to_rss_event();
+    state.books.open(&event.id, market.cpmm_pool.clone());
+    app.markets.insert(event.id.clone(), market);
+    app.reindex_market(&event.id);
+    app.feed.record_new_market(&rss_event, now);
+    
+    let ledger_tx = Transaction::liquidity_added(&event.id, &launcher, liquidity, &tx.signature);
+    app.ledger.record(ledger_tx);
+    
+    app.log_activity("🚀", "MARKET_LAUNCH", &format!(
+        "{} launched '{}' with {} BB | Odds: {:?}",
+        launcher, event.title, liquidity, prices
+    ));
+    
+    Ok(Json(json!({
+        "success": true,
+        "market_id": event.id,
+        "title": event.title,
+        "launched_by": launcher,
+        "liquidity": liquidity,
+        "status": EventStatus::Provisional.to_string(),
+        "provisional_deadline": provisional_deadline,
+        "viability_threshold": app.config.markets.viability_threshold,
+        "initial_odds": prices,
+        "new_balance": app.ledger.balance(&launcher)
+    })))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// OUTBOUND FEED HANDLERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use crate::rss::{FeedFilter, FeedItemKind};
+
+/// Query params for the outbound feeds
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeedQuery {
+    /// Filter by market category
+    pub category: Option<String>,
+    /// Filter by market tag
+    pub tag: Option<String>,
+    /// Item type: new_market, odds_move, resolution
+    #[serde(rename = "type")]
+    pub item_type: Option<String>,
+    /// Maximum items to return
+    pub limit: Option<usize>,
+}
+
+impl FeedQuery {
+    fn to_filter(&self) -> Result<FeedFilter, ApiError> {
+        let kind = match &self.item_type {
+            Some(t) => Some(FeedItemKind::parse(t).ok_or_else(|| ApiError::BadRequest(format!("Unknown feed item type '{}' (expected new_market, odds_move or resolution)", t)))?),
+            None => None,
+        };
+        Ok(FeedFilter {
+            category: self.category.clone(),
+            tag: self.tag.clone(),
+            kind,
+            limit: self.limit,
+        })
+    }
+}
+
+/// GET /feed/rss - RSS 2.0 feed of new markets, odds moves and resolutions
+#[utoipa::path(
+    get,
+    path = "/feed/rss",
+    tag = "feed",
+    params(FeedQuery),
+    responses(
+        (status = 200, description = "RSS 2.0 document", body = String, content_type = "application/rss+xml"),
+        (status = 400, description = "Unknown item type", body = ApiError),
+    )
+)]
+pub async fn get_rss_feed(
+    State(state): State<SharedState>,
+    Query(params): Query<FeedQuery>,
+) -> Result<Response, ApiError> {
+    let filter = params.to_filter()?;
+    let app = state.lock().unwrap();
+    let xml = app.feed.render_rss(&filter);
+    Ok(([(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")], xml).into_response())
+}
+
+/// GET /feed/atom - Atom feed of new markets, odds moves and resolutions
+#[utoipa::path(
+    get,
+    path = "/feed/atom",
+    tag = "feed",
+    params(FeedQuery),
+    responses(
+        (status = 200, description = "Atom document", body = String, content_type = "application/atom+xml"),
+        (status = 400, description = "Unknown item type", body = ApiError),
+    )
+)]
+pub async fn get_atom_feed(
+    State(state): State<SharedState>,
+    Query(params): Query<FeedQuery>,
+) -> Result<Response, ApiError> {
+    let filter = params.to_filter()?;
+    let app = state.lock().unwrap();
+    let self_url = format!("{}/feed/atom", app.feed.site_url.trim_end_matches('/'));
+    let xml = app.feed.render_atom(&filter, &self_url);
+    Ok(([(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")], xml).into_response())
+}
+
+/// GET /feed - Feed items as JSON
+#[utoipa::path(
+    get,
+    path = "/feed",
+    tag = "feed",
+    params(FeedQuery),
+    responses(
+        (status = 200, description = "Feed items", body = Object),
+        (status = 400, description = "Unknown item type", body = ApiError),
+    )
+)]
+pub async fn get_feed_items(
+    State(state): State<SharedState>,
+    Query(params): Query<FeedQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let filter = params.to_filter()?;
+    let app = state.lock().unwrap();
+    let items = app.feed.items(&filter);
+    Ok(Json(json!({
+        "success": true,
+        "count": items.len(),
+        "items": items
+    })))
+}
+
+/// GET /feeds/status - Polling health of each inbound RSS/Atom feed
+#[utoipa::path(
+    get,
+    path = "/feeds/status",
+    tag = "events",
+    responses(
+        (status = 200, description = "Failures, last error, backoff and events emitted per configured feed", body = Object),
+    )
+)]
+pub async fn get_feed_status(
+    State(state): State<SharedState>,
+) -> Json<Value> {
+    let app = state.lock().unwrap();
+    let now = chrono::Utc::now().timestamp() as u64;
+    let feeds: Vec<Value> = app.config.feeds.sources.iter().map(|feed| {
+        let status = app.feed_sources.get(feed).cloned().unwrap_or_default();
+        json!({
+            "feed": feed,
+            "healthy": status.consecutive_failures == 0,
+            "due": status.is_due(now),
+            "status": status
+        })
+    }).collect();
+    Json(json!({
+        "success": true,
+        "poll_interval_secs": app.config.feeds.poll_interval_secs,
+        "count": feeds.len(),
+        "feeds": feeds
+    }))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::{test_state, test_state_with};
+    use crate::easteregg::GodMode;
+    use crate::roles::{SignedRequest, SIGNATURE_HEADER, SIGNER_HEADER, TIMESTAMP_HEADER};
+    use crate::rpc::TransactionPayload;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use axum::routing::{get, post, put};
+    use axum::Router;
+    use ed25519_dalek::{Signer, SigningKey};
+    use tower::Service;
+
+    fn pending_event(id: &str) -> PendingEvent {
+        PendingEvent::new(
+            id.to_string(),
+            "Will it rain tomorrow?".to_string(),
+            String::new(),
+            "weather".to_string(),
+            vec!["Yes".to_string(), "No".to_string()],
+            0.9,
+            String::new(),
+            String::new(),
+        )
+    }
+
+    /// The pending-event inbox routes, with `moderator` holding the moderator role
+    fn inbox(name: &str, moderator: &SigningKey) -> (SharedState, Router) {
+        let key = hex::encode(moderator.verifying_key().as_bytes());
+        let state = test_state_with(name, |config| config.roles.moderators = vec![key]);
+        let router = Router::new()
+            .route("/events/pending", get(list_pending_events).post(submit_pending_event))
+            .route("/events/pending/expire", post(expire_pending_events))
+            .route("/events/pending/:id", put(edit_pending_event))
+            .route("/events/pending/:id/reject", post(reject_pending_event))
+            .with_state(state.clone());
+        (state, router)
+    }
+
+    /// A request signed by `signer` (unsigned without one)
+    fn signed(method: &str, path: &str, body: &str, signer: Option<&SigningKey>) -> Request<Body> {
+        let builder = Request::builder().method(method).uri(path).header("content-type", "application/json");
+        let builder = match signer {
+            Some(key) => {
+                let now = chrono::Utc::now().timestamp() as u64;
+                let message = SignedRequest::signing_message(method, path, now, body.as_bytes());
+                builder
+                    .header(SIGNER_HEADER, hex::encode(key.verifying_key().as_bytes()))
+                    .header(TIMESTAMP_HEADER, now)
+                    .header(SIGNATURE_HEADER, hex::encode(key.sign(message.as_bytes()).to_bytes()))
+            }
+            None => builder,
+        };
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    async fn call(router: &mut Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = router.call(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), 1 << 20).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn test_exit_rejects_sender_address_of_another_key() {
+        let state = test_state("exit_sender_address");
+        let godmode = GodMode::new();
+        let victim = godmode.get_account("bob").unwrap().address.clone();
+        state.ledger.credit(&victim, 500.0);
+
+        // Signed by alice, claiming to be bob: sender_address is not signed
+        let mut tx = SignedTransaction::new(&godmode, "alice", 1, TransactionPayload::ForcedExit {
+            target_address: "L1_ATTACKER".to_string(),
+        }).unwrap();
+        tx.sender_address = victim.clone();
+
+        let err = submit_exit(State(state.clone()), Json(tx)).await.unwrap_err();
+        assert!(matches!(err, ApiError::Unauthorized(_)), "{:?}", err);
+        assert_eq!(state.ledger.balance(&victim), 500.0);
+        assert!(state.lock().unwrap().exits.exits().is_empty());
+
+        // Restating the signer's own address is fine
+        let alice = godmode.get_account("alice").unwrap().address.clone();
+        let tx = SignedTransaction::new(&godmode, "alice", 1, TransactionPayload::ForcedExit {
+            target_address: alice.clone(),
+        }).unwrap();
+        assert_eq!(tx.sender_address, alice);
+        let _ = submit_exit(State(state.clone()), Json(tx)).await.unwrap();
+        let app = state.lock().unwrap();
+        let exits = app.exits.exits();
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].address, alice);
+    }
+
+    #[tokio::test]
+    async fn test_launch_rejects_sender_address_of_another_key() {
+        let state = test_state("launch_sender_address");
+        let godmode = GodMode::new();
+        let victim = godmode.get_account("bob").unwrap().address.clone();
+        state.ledger.credit(&victim, 5_000.0);
+        state.lock().unwrap().pending_events.push(pending_event("evt_test"));
+
+        let mut tx = SignedTransaction::new(&godmode, "alice", 1, TransactionPayload::MarketLaunch {
+            event_id: "evt_test".to_string(),
+            liquidity: MINIMUM_LAUNCH_LIQUIDITY,
+        }).unwrap();
+        tx.sender_address = victim.clone();
+
+        let err = launch_pending_event(State(state.clone()), Json(tx)).await.unwrap_err();
+        assert!(matches!(err, ApiError::Unauthorized(_)), "{:?}", err);
+        assert_eq!(state.ledger.balance(&victim), 5_000.0);
+        let app = state.lock().unwrap();
+        assert_eq!(app.pending_events.len(), 1);
+        assert!(!app.markets.contains_key("evt_test"));
+    }
+
+    #[tokio::test]
+    async fn test_bridge_withdraw_debits_the_signer() {
+        let state = test_state("bridge_withdraw_signer");
+        let godmode = GodMode::new();
+        let alice = godmode.get_account("alice").unwrap().address.clone();
+        let victim = godmode.get_account("bob").unwrap().address.clone();
+        state.ledger.credit(&alice, 500.0);
+        state.ledger.credit(&victim, 500.0);
+        let payload = TransactionPayload::Bridge {
+            target_layer: "L1".to_string(),
+            target_address: "L1_TARGET".to_string(),
+            amount: 100.0,
+        };
+
+        // Signed by alice, claiming to be bob
+        let mut tx = SignedTransaction::new(&godmode, "alice", 1, payload.clone()).unwrap();
+        tx.sender_address = victim.clone();
+        let err = bridge_withdraw(State(state.clone()), Json(tx)).await.unwrap_err();
+        assert!(matches!(err, ApiError::Unauthorized(_)), "{:?}", err);
+        assert_eq!(state.ledger.balance(&victim), 500.0);
+
+        // A tampered signature is rejected before anything is debited
+        let mut tx = SignedTransaction::new(&godmode, "alice", 1, payload.clone()).unwrap();
+        tx.signature = "00".repeat(64);
+        let err = bridge_withdraw(State(state.clone()), Json(tx)).await.unwrap_err();
+        assert!(matches!(err, ApiError::SignedTx(SignedTxError::SignatureMismatch)), "{:?}", err);
+        assert_eq!(state.ledger.balance(&alice), 500.0);
+
+        let tx = SignedTransaction::new(&godmode, "alice", 1, payload).unwrap();
+        let Json(body) = bridge_withdraw(State(state.clone()), Json(tx)).await.unwrap();
+        assert_eq!(body["from_address"], alice);
+        assert_eq!(state.ledger.balance(&alice), 400.0);
+        assert_eq!(state.ledger.balance(&victim), 500.0);
+    }
+
+    #[tokio::test]
+    async fn test_resolution_proposals_survive_a_restart() {
+        let state = test_state("dispute_persistence");
+        state.ledger.credit("L1_ORACLE", 500.0);
+        let config = {
+            let mut app = state.lock().unwrap();
+            app.markets.insert("m1".to_string(), PredictionMarket::new(
+                "m1".to_string(),
+                "Will it rain?".to_string(),
+                String::new(),
+                "weather".to_string(),
+                vec!["Yes".to_string(), "No".to_string()],
+            ));
+            open_resolution_proposal(&mut app, "m1", 0, "L1_ORACLE", None, None, 1_000).unwrap();
+            app.save_to_disk().unwrap();
+            app.config.clone()
+        };
+
+        let restarted = crate::app_state::AppState::new(config);
+        let proposal = restarted.disputes.get("m1").expect("proposal restored");
+        assert_eq!(proposal.proposer, "L1_ORACLE");
+        assert_eq!(proposal.status, ProposalStatus::Proposed);
+        assert_eq!(restarted.markets["m1"].market_status, EventStatus::Closed);
+        assert_eq!(restarted.disputes.due_for_finalization(proposal.challenge_deadline + 1), vec!["m1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_resolution_leaves_proposal_and_bond_open() {
+        let state = test_state("resolution_retry");
+        state.ledger.credit("L1_ORACLE", 500.0);
+        let (market, bond) = {
+            let mut app = state.lock().unwrap();
+            app.markets.insert("m1".to_string(), PredictionMarket::new(
+                "m1".to_string(),
+                "Will it rain?".to_string(),
+                String::new(),
+                "weather".to_string(),
+                vec!["Yes".to_string(), "No".to_string()],
+            ));
+            open_resolution_proposal(&mut app, "m1", 0, "L1_ORACLE", None, None, 1_000).unwrap();
+            let bond = app.disputes.get("m1").unwrap().proposer_bond;
+            (app.markets.remove("m1").unwrap(), bond)
+        };
+
+        // The market can't be resolved: the proposal and its bond stay open
+        assert!(crate::app_state::finalize_expired_resolutions(&state).await.is_empty());
+        assert!(state.lock().unwrap().disputes.is_pending("m1"));
+        assert_eq!(state.ledger.balance("L1_ORACLE"), 500.0 - bond);
+
+        // Retried on the next pass, and applied once
+        state.lock().unwrap().markets.insert("m1".to_string(), market);
+        assert_eq!(crate::app_state::finalize_expired_resolutions(&state).await, vec!["m1".to_string()]);
+        assert!(crate::app_state::finalize_expired_resolutions(&state).await.is_empty());
+        assert_eq!(state.ledger.balance("L1_ORACLE"), 500.0);
+        let app = state.lock().unwrap();
+        assert!(!app.disputes.is_pending("m1"));
+        assert!(app.markets["m1"].is_resolved);
+        assert_eq!(app.disputes.get("m1").unwrap().finalized_by.as_deref(), Some("challenge_window"));
+    }
+
+    #[tokio::test]
+    async fn test_proposed_market_rejects_bets_orders_and_mints() {
+        let state = test_state("proposed_market_closed");
+        state.ledger.register("alice", "L1_ALICE", 100.0);
+        state.ledger.credit("L1_ORACLE", 500.0);
+        {
+            let mut app = state.lock().unwrap();
+            app.markets.insert("m1".to_string(), PredictionMarket::new(
+                "m1".to_string(),
+                "Will it rain?".to_string(),
+                String::new(),
+                "weather".to_string(),
+                vec!["Yes".to_string(), "No".to_string()],
+            ));
+            open_resolution_proposal(&mut app, "m1", 0, "L1_ORACLE", None, None, 1_000).unwrap();
+        }
+        let closed = |err: ApiError| assert!(matches!(err, ApiError::Order(OrderError::MarketClosed(_))), "{:?}", err);
+
+        closed(place_signed_bet(State(state.clone()), Json(BetRequest {
+            signature: "sig".to_string(),
+            from_address: "L1_ALICE".to_string(),
+            market_id: "m1".to_string(),
+            option: "YES".to_string(),
+            amount: 10.0,
+            nonce: 1,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+        })).await.unwrap_err());
+        closed(submit_order(State(state.clone()), Json(SubmitOrderRequest {
+            wallet: "alice".to_string(),
+            market_id: "m1".to_string(),
+            outcome: 0,
+            side: "bid".to_string(),
+            price_bps: 90,
+            quantity: 10.0,
+            order_type: None,
+        })).await.unwrap_err());
+        closed(mint_shares(State(state.clone()), Json(MintSharesRequest {
+            wallet: "alice".to_string(),
+            market_id: "m1".to_string(),
+            amount: 10.0,
+        })).await.unwrap_err());
+
+        assert_eq!(state.ledger.balance("alice"), 100.0);
+        assert!(state.books.positions_of("alice").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_feed_status_reports_each_configured_feed() {
+        let state = test_state_with("feed_status", |config| {
+            config.feeds.sources = vec!["feeds/ok.xml".to_string(), "feeds/down.xml".to_string()];
+        });
+        {
+            let mut app = state.lock().unwrap();
+            let mut down = crate::rss::FeedStatus::default();
+            down.record_failure(1_000, 60, "timed out".to_string());
+            app.feed_sources.insert("feeds/down.xml".to_string(), down);
+        }
+
+        let Json(body) = get_feed_status(State(state)).await;
+        assert_eq!(body["count"], 2);
+        assert_eq!(body["feeds"][0]["feed"], "feeds/ok.xml");
+        assert_eq!(body["feeds"][0]["healthy"], true);
+        assert_eq!(body["feeds"][1]["healthy"], false);
+        assert_eq!(body["feeds"][1]["status"]["last_error"], "timed out");
+        assert_eq!(body["feeds"][1]["status"]["consecutive_failures"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_ledger_endpoints_skip_the_application_lock() {
+        let state = test_state("ledger_off_lock");
+        state.ledger.register("alice", "L1_ALICE", 100.0);
+        state.ledger.register("bob", "L1_BOB", 0.0);
+
+        // Served while another request holds the application lock
+        let held = state.lock().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ledger_state = state.clone();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            runtime.block_on(async {
+                let _ = transfer(State(ledger_state.clone()), Json(TransferRequest {
+                    from: "alice".to_string(),
+                    to: "bob".to_string(),
+                    amount: 40.0,
+                })).await.unwrap();
+                let Json(balance) = get_balance(State(ledger_state.clone()), Path("bob".to_string())).await;
+                let Json(details) = get_balance_details(State(ledger_state.clone()), Path("alice".to_string())).await;
+                let Json(activity) = get_ledger_activity(State(ledger_state)).await;
+                tx.send((balance, details, activity)).unwrap();
+            });
+        });
+        let (balance, details, activity) = rx.recv_timeout(std::time::Duration::from_secs(5))
+            .expect("ledger endpoints waited on the application lock");
+        drop(held);
+
+        assert_eq!(balance["balance"], 40.0);
+        assert_eq!(details["address"], "L1_ALICE");
+        assert!(activity["activity"].as_array().unwrap().iter()
+            .any(|entry| entry.as_str().unwrap().contains("TRANSFER | alice → bob | 40 BB")));
+    }
+
+    #[tokio::test]
+    async fn test_order_book_moves_publish_odds() {
+        let state = test_state("order_odds_feed");
+        state.ledger.credit("L1_SELLER", 100.0);
+        state.ledger.credit("L1_BUYER", 100.0);
+        {
+            let mut app = state.lock().unwrap();
+            let market = PredictionMarket::new(
+                "m1".to_string(),
+                "Will it rain?".to_string(),
+                String::new(),
+                "weather".to_string(),
+                vec!["Yes".to_string(), "No".to_string()],
+            );
+            let event = market.to_rss_event();
+            app.markets.insert("m1".to_string(), market);
+            app.feed.record_new_market(&event, 1_000);
+        }
+        let order = |wallet: &str, side: &str, price_bps: u64| Json(SubmitOrderRequest {
+            wallet: wallet.to_string(),
+            market_id: "m1".to_string(),
+            outcome: 0,
+            side: side.to_string(),
+            price_bps,
+            quantity: 10.0,
+            order_type: None,
+        });
+        let odds_moves = |state: &SharedState| state.lock().unwrap().feed
+            .items(&FeedFilter { kind: Some(FeedItemKind::OddsMove), ..Default::default() })
+            .into_iter()
+            .map(|item| item.detail.clone())
+            .collect::<Vec<_>>();
+
+        let _ = mint_shares(State(state.clone()), Json(MintSharesRequest {
+            wallet: "L1_SELLER".to_string(),
+            market_id: "m1".to_string(),
+            amount: 20.0,
+        })).await.unwrap();
+        let _ = submit_order(State(state.clone()), order("L1_SELLER", "ask", 90)).await.unwrap();
+        // One-sided book: no price yet
+        assert!(odds_moves(&state).is_empty());
+
+        let _ = submit_order(State(state.clone()), order("L1_BUYER", "bid", 80)).await.unwrap();
+        let moves = odds_moves(&state);
+        assert_eq!(moves.len(), 1);
+        match &moves[0] {
+            crate::rss::FeedItemDetail::OddsMove { current, outcome, .. } => {
+                assert_eq!(*outcome, 0);
+                assert!((current[0] - 0.85).abs() < 1e-9, "{:?}", current);
+            }
+            other => panic!("unexpected detail {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inbox_moderation_requires_moderator_role() {
+        let moderator = SigningKey::from_bytes(&[4; 32]);
+        let outsider = SigningKey::from_bytes(&[5; 32]);
+        let (state, mut router) = inbox("inbox_authorization", &moderator);
+        state.lock().unwrap().pending_events.push(pending_event("evt_rain"));
+
+        let body = r#"{"reason":"duplicate"}"#;
+        let path = "/events/pending/evt_rain/reject";
+        let (status, _) = call(&mut router, signed("POST", path, body, None)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = call(&mut router, signed("POST", path, body, Some(&outsider))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let edit = r#"{"title":"Will it snow?"}"#;
+        let (status, _) = call(&mut router, signed("PUT", "/events/pending/evt_rain", edit, Some(&outsider))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = call(&mut router, signed("POST", "/events/pending/expire", "", Some(&outsider))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        {
+            let app = state.lock().unwrap();
+            assert_eq!(app.pending_events.len(), 1);
+            assert_eq!(app.pending_events[0].title, "Will it rain tomorrow?");
+        }
+
+        // Edits are validated, then applied
+        let one_option = r#"{"options":["Yes"]}"#;
+        let (status, _) = call(&mut router, signed("PUT", "/events/pending/evt_rain", one_option, Some(&moderator))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, json) = call(&mut router, signed("PUT", "/events/pending/evt_rain", edit, Some(&moderator))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["event"]["title"], "Will it snow?");
+
+        let (status, json) = call(&mut router, signed("POST", path, body, Some(&moderator))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["rejected"], "evt_rain");
+        assert_eq!(json["reason"], "duplicate");
+        let (status, _) = call(&mut router, signed("POST", path, "{}", Some(&moderator))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let app = state.lock().unwrap();
+        assert!(app.pending_events.is_empty());
+        let audit = app.audit_log.export_ndjson().unwrap();
+        assert!(audit.contains("EVENT_EDITED") && audit.contains("EVENT_REJECTED"), "{}", audit);
+    }
+
+    #[tokio::test]
+    async fn test_expired_events_are_hidden_purged_and_not_launchable() {
+        let moderator = SigningKey::from_bytes(&[4; 32]);
+        let (state, mut router) = inbox("inbox_expiry", &moderator);
+        let mut stale = pending_event("evt_stale");
+        stale.expires_at = Some(1);
+        {
+            let mut app = state.lock().unwrap();
+            app.pending_events.push(stale);
+            app.pending_events.push(pending_event("evt_live"));
+        }
+
+        let (_, json) = call(&mut router, signed("GET", "/events/pending", "", None)).await;
+        assert_eq!(json["count"], 1);
+        assert_eq!(json["events"][0]["event"]["id"], "evt_live");
+        let (_, json) = call(&mut router, signed("GET", "/events/pending?include_expired=true", "", None)).await;
+        assert_eq!(json["count"], 2);
+
+        // An expired event cannot be launched, even by a funded signer
+        let godmode = GodMode::new();
+        let alice = godmode.get_account("alice").unwrap().address.clone();
+        state.ledger.credit(&alice, 5_000.0);
+        let tx = SignedTransaction::new(&godmode, "alice", 1, TransactionPayload::MarketLaunch {
+            event_id: "evt_stale".to_string(),
+            liquidity: MINIMUM_LAUNCH_LIQUIDITY,
+        }).unwrap();
+        let err = launch_pending_event(State(state.clone()), Json(tx)).await.unwrap_err();
+        assert!(matches!(err, ApiError::Gone(_)), "{:?}", err);
+        assert_eq!(state.ledger.balance(&alice), 5_000.0);
+
+        let (status, json) = call(&mut router, signed("POST", "/events/pending/expire", "", Some(&moderator))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["expired"], json!(["evt_stale"]));
+        assert_eq!(json["remaining"], 1);
+        let app = state.lock().unwrap();
+        assert_eq!(app.pending_events.len(), 1);
+        assert_eq!(app.pending_events[0].id, "evt_live");
+    }
+}
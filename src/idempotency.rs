@@ -0,0 +1,581 @@
+//! Idempotency Keys
+//!
+//! A mutating request (POST, PUT, PATCH, DELETE) carrying an
+//! `Idempotency-Key` header runs at most once per key. The first response is
+//! stored for the configured TTL and replayed, marked with
+//! `Idempotent-Replayed: true`, to every retry with the same key. Retries
+//! must be the same request: the key is bound to a hash of the method, path
+//! and body, and reusing it for anything else is rejected.
+//!
+//! - A retry that arrives while the first attempt is still running gets a
+//!   409 rather than a second execution.
+//! - 5xx responses are not stored, so a retry after a server-side failure
+//!   runs again.
+//! - Keys are scoped to their caller (the verified signer, else the client
+//!   IP) and route, so two clients picking the same key never collide.
+//! - Completed entries are appended to disk by a background task and
+//!   reloaded on startup, so a retry after a restart still replays.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::request::Parts;
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::api_error::ApiError;
+use crate::rate_limit::{client_ip, verified_wallet};
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on replayed responses
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// How long a stored response is replayed for
+pub const DEFAULT_IDEMPOTENCY_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Where stored responses are persisted
+pub const DEFAULT_IDEMPOTENCY_STORE_PATH: &str = "data/idempotency.ndjson";
+
+/// Longest key accepted
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Largest request or response body buffered for a keyed request
+pub const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Completions between prunes (and store file compactions)
+const COMPACT_EVERY: usize = 1000;
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyError {
+    /// Empty, too long or not printable ASCII
+    InvalidKey(String),
+    /// The key was first used for a different method, path or body
+    KeyReused(String),
+    /// The first request with this key has not finished yet
+    InProgress(String),
+    /// The request body is larger than `MAX_BODY_BYTES`
+    BodyTooLarge,
+}
+
+impl fmt::Display for IdempotencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdempotencyError::InvalidKey(key) => {
+                write!(f, "Invalid Idempotency-Key {:?}: use 1-{} printable ASCII characters", key, MAX_KEY_LEN)
+            }
+            IdempotencyError::KeyReused(key) => {
+                write!(f, "Idempotency-Key {} was already used for a different request", key)
+            }
+            IdempotencyError::InProgress(key) => {
+                write!(f, "A request with Idempotency-Key {} is still in progress", key)
+            }
+            IdempotencyError::BodyTooLarge => {
+                write!(f, "Request body too large for an idempotent request (max {} bytes)", MAX_BODY_BYTES)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IdempotencyError {}
+
+// ============================================================================
+// STORE
+// ============================================================================
+
+/// A response kept for replay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl StoredResponse {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        let mut response = (status, self.body).into_response();
+        let headers = response.headers_mut();
+        match self.content_type.and_then(|ct| HeaderValue::from_str(&ct).ok()) {
+            Some(content_type) => {
+                headers.insert(header::CONTENT_TYPE, content_type);
+            }
+            None => {
+                headers.remove(header::CONTENT_TYPE);
+            }
+        }
+        headers.insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// Hash of method, path and body
+    fingerprint: String,
+    created_at: u64,
+    /// None while the first request is running
+    response: Option<StoredResponse>,
+}
+
+/// One line of the store file
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    scope: String,
+    key: String,
+    #[serde(flatten)]
+    entry: Entry,
+}
+
+/// What to do with a keyed request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Begin {
+    /// First time: run it, then `complete` or `abandon` the key
+    Proceed,
+    /// Seen before: send this instead
+    Replay(StoredResponse),
+}
+
+/// (scope, key)
+type EntryId = (String, String);
+
+#[derive(Debug, Default)]
+struct StoreInner {
+    entries: HashMap<EntryId, Entry>,
+    /// Completions since the last prune
+    completed_since_prune: usize,
+}
+
+impl StoreInner {
+    fn prune(&mut self, ttl_secs: u64, now: u64) {
+        self.entries.retain(|_, e| e.response.is_none() || e.created_at + ttl_secs > now);
+        self.completed_since_prune = 0;
+    }
+
+    /// Completed entries as store file lines (running requests are not persisted)
+    fn lines(&self) -> String {
+        self.entries.iter()
+            .filter(|(_, entry)| entry.response.is_some())
+            .filter_map(|((scope, key), entry)| record_line(scope, key, entry))
+            .collect()
+    }
+}
+
+/// Work for the background writer
+#[derive(Debug)]
+enum Persist {
+    Append(String),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Keys and their stored responses, shared by every request
+#[derive(Debug, Clone)]
+pub struct IdempotencyStore {
+    inner: Arc<Mutex<StoreInner>>,
+    ttl_secs: u64,
+    /// Background writer of completed entries; None keeps them in memory
+    writer: Option<mpsc::UnboundedSender<Persist>>,
+    /// Scope unsigned callers by `X-Forwarded-For` (behind a proxy)
+    trust_forwarded_for: bool,
+}
+
+impl IdempotencyStore {
+    /// In-memory store (tests)
+    pub fn new(ttl_secs: u64) -> Self {
+        Self { inner: Arc::default(), ttl_secs, writer: None, trust_forwarded_for: false }
+    }
+
+    /// Store persisted at `path`, starting with the unexpired entries saved
+    /// there. Completed entries are appended by a background task, which
+    /// also compacts the file; call from within the Tokio runtime.
+    pub fn open(path: impl Into<PathBuf>, ttl_secs: u64, now: u64) -> Result<Self, String> {
+        let path = path.into();
+        let mut inner = StoreInner::default();
+        match std::fs::read_to_string(&path) {
+            Ok(ndjson) => {
+                for (i, line) in ndjson.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+                    let record: Record = serde_json::from_str(line)
+                        .map_err(|e| format!("Idempotency store line {}: {}", i + 1, e))?;
+                    inner.entries.insert((record.scope, record.key), record.entry);
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read idempotency store: {}", e)),
+        }
+        inner.prune(ttl_secs, now);
+
+        let inner = Arc::new(Mutex::new(inner));
+        let (writer, queue) = mpsc::unbounded_channel();
+        tokio::spawn(persist_loop(path, inner.clone(), ttl_secs, queue));
+        Ok(Self { inner, ttl_secs, writer: Some(writer), trust_forwarded_for: false })
+    }
+
+    /// Scope unsigned callers by their `X-Forwarded-For` address
+    pub fn with_forwarded_for(mut self, trust_forwarded_for: bool) -> Self {
+        self.trust_forwarded_for = trust_forwarded_for;
+        self
+    }
+
+    /// Keys currently held (running or stored)
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Claim `key` within `scope` for a request with `fingerprint`, or find
+    /// its stored response
+    pub fn begin(&self, scope: &str, key: &str, fingerprint: &str, now: u64) -> Result<Begin, IdempotencyError> {
+        let mut inner = self.inner.lock().unwrap();
+        let id = (scope.to_string(), key.to_string());
+        if let Some(entry) = inner.entries.get(&id) {
+            let expired = entry.response.is_some() && entry.created_at + self.ttl_secs <= now;
+            if !expired {
+                if entry.fingerprint != fingerprint {
+                    return Err(IdempotencyError::KeyReused(key.to_string()));
+                }
+                return match &entry.response {
+                    Some(response) => Ok(Begin::Replay(response.clone())),
+                    None => Err(IdempotencyError::InProgress(key.to_string())),
+                };
+            }
+        }
+        inner.entries.insert(id, Entry {
+            fingerprint: fingerprint.to_string(),
+            created_at: now,
+            response: None,
+        });
+        Ok(Begin::Proceed)
+    }
+
+    /// Store the response to a claimed key and queue it for persisting
+    pub fn complete(&self, scope: &str, key: &str, response: StoredResponse, now: u64) {
+        let line = {
+            let mut inner = self.inner.lock().unwrap();
+            let Some(entry) = inner.entries.get_mut(&(scope.to_string(), key.to_string())) else {
+                return;
+            };
+            entry.response = Some(response);
+            let line = self.writer.as_ref().and_then(|_| record_line(scope, key, entry));
+            // Persisted stores are pruned by their writer as it compacts
+            inner.completed_since_prune += 1;
+            if self.writer.is_none() && inner.completed_since_prune >= COMPACT_EVERY {
+                inner.prune(self.ttl_secs, now);
+            }
+            line
+        };
+        if let (Some(writer), Some(line)) = (&self.writer, line) {
+            writer.send(Persist::Append(line)).ok();
+        }
+    }
+
+    /// Release a claimed key without storing anything (so a retry runs again)
+    pub fn abandon(&self, scope: &str, key: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        let id = (scope.to_string(), key.to_string());
+        if inner.entries.get(&id).is_some_and(|e| e.response.is_none()) {
+            inner.entries.remove(&id);
+        }
+    }
+
+    /// Wait until every completed entry so far is on disk
+    pub async fn flush(&self) {
+        let Some(writer) = &self.writer else { return };
+        let (done, flushed) = oneshot::channel();
+        if writer.send(Persist::Flush(done)).is_ok() {
+            flushed.await.ok();
+        }
+    }
+}
+
+fn record_line(scope: &str, key: &str, entry: &Entry) -> Option<String> {
+    let record = Record { scope: scope.to_string(), key: key.to_string(), entry: entry.clone() };
+    serde_json::to_string(&record).ok().map(|json| json + "\n")
+}
+
+/// Background writer: appends each completed entry, and every
+/// `COMPACT_EVERY` appends (and once at startup) prunes expired entries and
+/// rewrites the file with what is left
+async fn persist_loop(
+    path: PathBuf,
+    inner: Arc<Mutex<StoreInner>>,
+    ttl_secs: u64,
+    mut queue: mpsc::UnboundedReceiver<Persist>,
+) {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await.ok();
+    }
+    let mut appended = COMPACT_EVERY;
+    loop {
+        if appended >= COMPACT_EVERY {
+            let lines = {
+                let mut inner = inner.lock().unwrap();
+                inner.prune(ttl_secs, now_secs());
+                inner.lines()
+            };
+            if let Err(e) = write_atomically(&path, &lines).await {
+                eprintln!("⚠️  Failed to compact idempotency store: {}", e);
+            }
+            appended = 0;
+        }
+        match queue.recv().await {
+            Some(Persist::Append(line)) => {
+                if let Err(e) = append(&path, &line).await {
+                    eprintln!("⚠️  Failed to persist idempotency key: {}", e);
+                }
+                appended += 1;
+            }
+            Some(Persist::Flush(done)) => {
+                done.send(()).ok();
+            }
+            None => return,
+        }
+    }
+}
+
+async fn append(path: &PathBuf, line: &str) -> std::io::Result<()> {
+    let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(path).await?;
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await
+}
+
+/// Write-then-rename so a crash mid-write keeps the previous file
+async fn write_atomically(path: &PathBuf, contents: &str) -> Result<(), String> {
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, contents).await.map_err(|e| e.to_string())?;
+    tokio::fs::rename(&tmp, path).await.map_err(|e| e.to_string())
+}
+
+/// Releases the key if the request never completes (handler error or the
+/// client going away mid-request)
+struct Claim<'a> {
+    store: &'a IdempotencyStore,
+    scope: &'a str,
+    key: &'a str,
+    done: bool,
+}
+
+impl Drop for Claim<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.store.abandon(self.scope, self.key);
+        }
+    }
+}
+
+/// Who a key belongs to: the verified signer of the request, else the
+/// client IP, on this method and route. Keys from different callers never
+/// collide, and nobody can replay another caller's response.
+pub fn scope(parts: &Parts, body: &[u8], trust_forwarded_for: bool, now: u64) -> String {
+    let caller = match verified_wallet(parts, body, now) {
+        Some(signer) => format!("signer:{}", signer),
+        None => format!("ip:{}", client_ip(parts, trust_forwarded_for)),
+    };
+    format!("{} {} {}", caller, parts.method, parts.uri.path())
+}
+
+/// Hash binding a key to one request
+pub fn fingerprint(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LEN && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// ============================================================================
+// MIDDLEWARE
+// ============================================================================
+
+/// Middleware: run each keyed mutating request once and replay its response
+pub async fn idempotency_guard(
+    State(store): State<IdempotencyStore>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mutating = matches!(*request.method(), Method::POST | Method::PUT | Method::PATCH | Method::DELETE);
+    let key = match request.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(key) if mutating => key.to_str().unwrap_or_default().to_string(),
+        _ => return next.run(request).await,
+    };
+    if !valid_key(&key) {
+        return ApiError::from(IdempotencyError::InvalidKey(key)).into_response();
+    }
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = axum::body::to_bytes(body, MAX_BODY_BYTES).await else {
+        return ApiError::from(IdempotencyError::BodyTooLarge).into_response();
+    };
+    let now = now_secs();
+    let scope = scope(&parts, &body, store.trust_forwarded_for, now);
+    let path = parts.uri.path_and_query().map(|p| p.as_str()).unwrap_or(parts.uri.path());
+    let fingerprint = fingerprint(&parts.method, path, &body);
+
+    match store.begin(&scope, &key, &fingerprint, now) {
+        Ok(Begin::Replay(stored)) => return stored.into_response(),
+        Ok(Begin::Proceed) => {}
+        Err(e) => return ApiError::from(e).into_response(),
+    }
+    let mut claim = Claim { store: &store, scope: &scope, key: &key, done: false };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if response.status().is_server_error() {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_BODY_BYTES).await else {
+        return ApiError::Internal("Response too large to store for replay".to_string()).into_response();
+    };
+    if let Ok(text) = std::str::from_utf8(&bytes) {
+        let stored = StoredResponse {
+            status: parts.status.as_u16(),
+            content_type: parts.headers.get(header::CONTENT_TYPE)
+                .and_then(|ct| ct.to_str().ok())
+                .map(String::from),
+            body: text.to_string(),
+        };
+        store.complete(&scope, &key, stored, now_secs());
+        claim.done = true;
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use axum::Router;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::Service;
+
+    fn response(body: &str) -> StoredResponse {
+        StoredResponse { status: 200, content_type: Some("application/json".into()), body: body.into() }
+    }
+
+    #[test]
+    fn test_replay_and_reuse() {
+        let store = IdempotencyStore::new(60);
+        assert_eq!(store.begin("a", "k1", "fp", 100), Ok(Begin::Proceed));
+        assert_eq!(store.begin("a", "k1", "fp", 101), Err(IdempotencyError::InProgress("k1".into())));
+
+        store.complete("a", "k1", response("{}"), 102);
+        assert_eq!(store.begin("a", "k1", "fp", 103), Ok(Begin::Replay(response("{}"))));
+        assert_eq!(store.begin("a", "k1", "other", 103), Err(IdempotencyError::KeyReused("k1".into())));
+
+        // Another caller's key of the same name is unrelated
+        assert_eq!(store.begin("b", "k1", "other", 103), Ok(Begin::Proceed));
+
+        // After the TTL the key is free again
+        assert_eq!(store.begin("a", "k1", "other", 160), Ok(Begin::Proceed));
+    }
+
+    #[test]
+    fn test_abandon_releases_key() {
+        let store = IdempotencyStore::new(60);
+        assert_eq!(store.begin("a", "k1", "fp", 100), Ok(Begin::Proceed));
+        store.abandon("a", "k1");
+        assert_eq!(store.begin("a", "k1", "fp", 101), Ok(Begin::Proceed));
+    }
+
+    #[tokio::test]
+    async fn test_persists_completed_entries() {
+        let path = std::env::temp_dir().join(format!("idempotency_{}.ndjson", uuid::Uuid::new_v4().simple()));
+        let store = IdempotencyStore::open(&path, 60, 100).unwrap();
+        store.begin("a", "done", "fp", 100).unwrap();
+        store.complete("a", "done", response("{\"ok\":true}"), 100);
+        store.begin("a", "running", "fp", 100).unwrap();
+        store.flush().await;
+
+        let reopened = IdempotencyStore::open(&path, 60, 110).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(reopened.begin("a", "done", "fp", 110), Ok(Begin::Replay(response("{\"ok\":true}"))));
+        assert_eq!(reopened.begin("b", "done", "fp", 110), Ok(Begin::Proceed));
+        assert!(IdempotencyStore::open(&path, 60, 200).unwrap().is_empty());
+        std::fs::remove_file(&path).ok();
+    }
+
+    async fn send(app: &Router, key: &str, body: &'static str) -> Response {
+        send_from(app, "10.0.0.1", key, body).await
+    }
+
+    async fn send_from(app: &Router, ip: &str, key: &str, body: &'static str) -> Response {
+        let request = Request::post("/transfer")
+            .header(IDEMPOTENCY_KEY_HEADER, key)
+            .header("x-forwarded-for", ip)
+            .body(Body::from(body))
+            .unwrap();
+        app.clone().call(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_handler_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new()
+            .route("/transfer", post(move || {
+                let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                async move { format!("transfer #{}", n) }
+            }))
+            .layer(axum::middleware::from_fn_with_state(
+                IdempotencyStore::new(60).with_forwarded_for(true),
+                idempotency_guard,
+            ));
+
+        let first = send(&app, "abc", "{\"amount\":5}").await;
+        assert!(first.headers().get(REPLAYED_HEADER).is_none());
+        let retry = send(&app, "abc", "{\"amount\":5}").await;
+        assert_eq!(retry.headers()[REPLAYED_HEADER], "true");
+        let body = axum::body::to_bytes(retry.into_body(), 1024).await.unwrap();
+        assert_eq!(&body[..], b"transfer #1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let reused = send(&app, "abc", "{\"amount\":6}").await;
+        assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let invalid = send(&app, "", "{}").await;
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Another client using the same key gets its own run, not a replay
+        let other = send_from(&app, "10.0.0.2", "abc", "{\"amount\":6}").await;
+        assert!(other.headers().get(REPLAYED_HEADER).is_none());
+        let body = axum::body::to_bytes(other.into_body(), 1024).await.unwrap();
+        assert_eq!(&body[..], b"transfer #2");
+    }
+}
@@ -30,6 +30,17 @@ impl Default for Layer {
     fn default() -> Self { Layer::L2 }
 }
 
+impl Layer {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "l1" => Some(Layer::L1),
+            "l2" => Some(Layer::L2),
+            "bridge" => Some(Layer::Bridge),
+            _ => None,
+        }
+    }
+}
+
 /// Status of funds in a transaction
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum FundStatus {
@@ -44,6 +55,19 @@ impl Default for FundStatus {
     fn default() -> Self { FundStatus::Available }
 }
 
+impl FundStatus {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "available" => Some(FundStatus::Available),
+            "locked" => Some(FundStatus::Locked),
+            "pending" => Some(FundStatus::Pending),
+            "settled" => Some(FundStatus::Settled),
+            "bridging" => Some(FundStatus::Bridging),
+            _ => None,
+        }
+    }
+}
+
 // ============================================================================
 // CORE TYPES
 // ============================================================================
@@ -72,8 +96,10 @@ impl Balance {
         Self { confirmed: amount, pending: 0.0, locked: 0.0, layer, last_sync: now() }
     }
     
+    /// Spendable funds; bet stakes already left `pending` when they were
+    /// locked, so `locked` is tracked alongside rather than subtracted again
     pub fn available(&self) -> f64 {
-        (self.confirmed + self.pending - self.locked).max(0.0)
+        (self.confirmed + self.pending).max(0.0)
     }
     
     pub fn total(&self) -> f64 {
@@ -132,6 +158,22 @@ pub enum TxType {
     OrderFill,
 }
 
+impl TxType {
+    pub const ALL: [TxType; 16] = [
+        TxType::Bet, TxType::Transfer, TxType::Deposit, TxType::Withdraw, TxType::Payout,
+        TxType::AccountCreated, TxType::MarketCreated, TxType::LiquidityAdded, TxType::MarketResolved,
+        TxType::BridgeInitiate, TxType::BridgeComplete, TxType::ShareMint, TxType::ShareRedeem,
+        TxType::OrderPlace, TxType::OrderCancel, TxType::OrderFill,
+    ];
+
+    /// Parse a type name case-insensitively, with or without underscores
+    /// (`OrderFill`, `order_fill` and `orderfill` all match)
+    pub fn parse(s: &str) -> Option<Self> {
+        let wanted = s.to_lowercase().replace('_', "");
+        Self::ALL.into_iter().find(|t| format!("{:?}", t).to_lowercase() == wanted)
+    }
+}
+
 /// A single transaction record with full L1/L2 tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
@@ -161,9 +203,12 @@ pub struct Transaction {
     /// L1 settlement tx hash (if settled)
     #[serde(default)]
     pub l1_tx_hash: Option<String>,
-    /// Block number on the relevant layer
+    /// Block number on the relevant layer (0 until sealed into an L2 block)
     #[serde(default)]
     pub block_number: u64,
+    /// Position within its L2 block (once sealed)
+    #[serde(default)]
+    pub block_index: Option<usize>,
     /// Human-readable description
     #[serde(default)]
     pub description: Option<String>,
@@ -187,6 +232,7 @@ impl Transaction {
             l1_settled: false,
             l1_tx_hash: None,
             block_number: 0,
+            block_index: None,
             description: None,
         }
     }
@@ -292,6 +338,7 @@ impl Transaction {
             l1_settled: false,
             l1_tx_hash: None,
             block_number: 0,
+            block_index: None,
             description: Some(format!("Bet {} BB on outcome {}", amount, outcome)),
         }
     }
@@ -310,28 +357,19 @@ pub struct Ledger {
     pub accounts: HashMap<String, String>,
     /// All transactions
     pub transactions: Vec<Transaction>,
-    /// Current L2 block
+    /// Height of the latest sealed L2 block
     pub block: u64,
-    /// L1 RPC URL
-    pub l1_url: String,
-    /// Use mock mode (no real L1 calls)
-    pub mock_mode: bool,
 }
 
 impl Ledger {
     pub fn new() -> Self {
-        let l1_url = std::env::var("L1_RPC_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
-        let mock_mode = std::env::var("L1_MOCK_MODE").map(|v| v == "true").unwrap_or(true);
-        
-        println!("📒 Ledger initialized (L1: {}, mock: {})", l1_url, mock_mode);
+        println!("📒 Ledger initialized");
         
         Self {
             balances: HashMap::new(),
             accounts: HashMap::new(),
             transactions: Vec::new(),
             block: 0,
-            l1_url,
-            mock_mode,
         }
     }
     
@@ -340,8 +378,7 @@ impl Ledger {
         self.accounts.insert(name.to_uppercase(), address.to_string());
         self.balances.insert(address.to_string(), Balance::new(initial));
         self.transactions.push(Transaction::new(TxType::AccountCreated, address, initial, ""));
-        let display_addr = if address.len() > 16 { &address[..16] } else { address };
-        println!("👤 Registered {} ({}) with {} BB", name, display_addr, initial);
+        println!("👤 Registered {} ({}) with {} BB", name, display_addr(address), initial);
     }
     
     /// Resolve name or address to address
@@ -391,13 +428,12 @@ impl Ledger {
         // Lock funds for the bet (deduct from available, add to locked)
         bal.apply(-amount);  // Reduce available balance
         bal.lock(amount);    // Track as locked in bet
-        self.block += 1;
         
         let mut tx = Transaction::bet(&addr, market_id, outcome, amount, sig);
         tx.fund_status = FundStatus::Locked; // Mark as locked
         self.transactions.push(tx.clone());
         
-        println!("🎯 Bet: {} wagered {} BB on {} (outcome {}) [🔒 locked]", &addr[..16], amount, market_id, outcome);
+        println!("🎯 Bet: {} wagered {} BB on {} (outcome {}) [🔒 locked]", addr.get(..16).unwrap_or(&addr), amount, market_id, outcome);
         Ok(tx)
     }
     
@@ -438,12 +474,11 @@ impl Ledger {
         
         self.balances.get_mut(&from_addr).unwrap().apply(-amount);
         self.balances.get_mut(&to_addr).unwrap().apply(amount);
-        self.block += 1;
         
         let tx = Transaction::transfer(&from_addr, &to_addr, amount, sig);
         self.transactions.push(tx.clone());
         
-        println!("💸 Transfer: {} -> {} ({} BB)", &from_addr[..16], &to_addr[..16], amount);
+        println!("💸 Transfer: {} -> {} ({} BB)", display_addr(&from_addr), display_addr(&to_addr), amount);
         Ok(tx)
     }
     
@@ -456,7 +491,7 @@ impl Ledger {
         let mut tx = Transaction::new(TxType::Deposit, &addr, amount, "");
         self.transactions.push(tx);
         
-        println!("📥 Deposit: {} received {} BB", &addr[..16], amount);
+        println!("📥 Deposit: {} received {} BB", addr.get(..16).unwrap_or(&addr), amount);
         Ok(bal.available())
     }
     
@@ -473,7 +508,7 @@ impl Ledger {
         tx.fund_status = FundStatus::Settled;
         self.transactions.push(tx);
         
-        println!("🏆 Payout: {} won {} BB from {}", &addr[..16], amount, market_id);
+        println!("🏆 Payout: {} won {} BB from {}", addr.get(..16).unwrap_or(&addr), amount, market_id);
         Ok(bal.available())
     }
     
@@ -485,7 +520,7 @@ impl Ledger {
         // Unlock the bet amount from tracking
         bal.unlock(bet_amount);
         
-        println!("🔓 Unlocked {} BB for {} from {} (bet resolved)", bet_amount, &addr[..16], market_id);
+        println!("🔓 Unlocked {} BB for {} from {} (bet resolved)", bet_amount, addr.get(..16).unwrap_or(&addr), market_id);
         Ok(())
     }
     
@@ -530,7 +565,6 @@ impl Ledger {
     
     /// Record any transaction (used for market events, liquidity, etc.)
     pub fn record(&mut self, tx: Transaction) -> Transaction {
-        self.block += 1;
         self.transactions.push(tx.clone());
         tx
     }
@@ -572,7 +606,6 @@ impl Ledger {
     /// Add a transaction from market reconstruction (doesn't affect balances)
     pub fn add_reconstructed_transaction(&mut self, tx: Transaction) {
         self.transactions.push(tx);
-        self.block += 1;
     }
     
     /// Get locked balance for an address (funds in active bets)
@@ -739,97 +772,6 @@ pub struct UnifiedLedgerView {
     pub accounts: Vec<AccountSummary>,
 }
 
-// ============================================================================
-// L1 RPC CLIENT
-// ============================================================================
-
-/// Simple L1 RPC client for blockchain communication
-pub struct L1Client {
-    pub url: String,
-    pub mock: bool,
-    client: reqwest::Client,
-}
-
-impl L1Client {
-    pub fn new() -> Self {
-        let url = std::env::var("L1_RPC_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
-        let mock = std::env::var("L1_MOCK_MODE").map(|v| v == "true").unwrap_or(true);
-        
-        Self {
-            url,
-            mock,
-            client: reqwest::Client::new(),
-        }
-    }
-    
-    /// Get account balance from L1
-    pub async fn get_balance(&self, address: &str) -> Result<f64, String> {
-        if self.mock {
-            return Ok(30000.0); // Mock balance
-        }
-        
-        let url = format!("{}/rpc/accounts/{}/balance", self.url, address);
-        let resp = self.client.get(&url).send().await
-            .map_err(|e| format!("L1 request failed: {}", e))?;
-        
-        #[derive(Deserialize)]
-        struct BalanceResp { balance: f64 }
-        
-        let data: BalanceResp = resp.json().await
-            .map_err(|e| format!("Failed to parse L1 response: {}", e))?;
-        
-        Ok(data.balance)
-    }
-    
-    /// Verify signature via L1
-    pub async fn verify_signature(&self, address: &str, message: &str, signature: &str) -> Result<bool, String> {
-        if self.mock {
-            return Ok(true); // Always valid in mock
-        }
-        
-        let url = format!("{}/rpc/verify_signature", self.url);
-        
-        #[derive(Serialize)]
-        struct VerifyReq<'a> {
-            address: &'a str,
-            message: &'a str,
-            signature: &'a str,
-        }
-        
-        let resp = self.client.post(&url)
-            .json(&VerifyReq { address, message, signature })
-            .send().await
-            .map_err(|e| format!("L1 verify failed: {}", e))?;
-        
-        #[derive(Deserialize)]
-        struct VerifyResp { valid: bool }
-        
-        let data: VerifyResp = resp.json().await
-            .map_err(|e| format!("Failed to parse verify response: {}", e))?;
-        
-        Ok(data.valid)
-    }
-    
-    /// Get current nonce for address
-    pub async fn get_nonce(&self, address: &str) -> Result<u64, String> {
-        if self.mock {
-            return Ok(0);
-        }
-        
-        let url = format!("{}/rpc/accounts/{}/nonce", self.url, address);
-        let resp = self.client.get(&url).send().await
-            .map_err(|e| format!("L1 nonce request failed: {}", e))?;
-        
-        #[derive(Deserialize)]
-        struct NonceResp { nonce: u64 }
-        
-        let data: NonceResp = resp.json().await
-            .map_err(|e| format!("Failed to parse nonce response: {}", e))?;
-        
-        Ok(data.nonce)
-    }
-}
-
 // ============================================================================
 // HELPERS
 // ============================================================================
@@ -841,6 +783,12 @@ fn now() -> u64 {
         .as_secs()
 }
 
+/// First 16 characters of an address for log lines; short names (system
+/// accounts such as `escrow:<market>`) are shown whole
+fn display_addr(address: &str) -> &str {
+    address.get(..16).unwrap_or(address)
+}
+
 /// Compute SHA256 hash
 pub fn hash(data: &str) -> String {
     hex::encode(Sha256::digest(data.as_bytes()))
@@ -878,6 +826,7 @@ pub struct TransactionResponse {
     pub l1_settled: bool,
     pub l1_tx_hash: Option<String>,
     pub block_number: u64,
+    pub block_index: Option<usize>,
     pub description: Option<String>,
 }
 
@@ -898,6 +847,7 @@ impl From<&Transaction> for TransactionResponse {
             l1_settled: tx.l1_settled,
             l1_tx_hash: tx.l1_tx_hash.clone(),
             block_number: tx.block_number,
+            block_index: tx.block_index,
             description: tx.description.clone(),
         }
     }
@@ -934,7 +884,10 @@ pub fn reconstruct_transactions_from_market_data(markets: &[MarketData]) -> Vec<
     
     for market in markets {
         // Add market creation transaction
+        // Ids are derived from the market so a rebuild yields the same ids
+        // (list cursors end with the transaction id)
         let mut market_tx = Transaction::market_created(&market.id, &market.title, market.total_volume);
+        market_tx.id = format!("market_created:{}", market.id);
         market_tx.timestamp = market.created_at;
         market_tx.block_number = transactions.len() as u64 + 1;
         transactions.push(market_tx);
@@ -957,6 +910,7 @@ pub fn reconstruct_transactions_from_market_data(markets: &[MarketData]) -> Vec<
         if market.is_resolved {
             if let Some(winning) = market.winning_option {
                 let mut resolve_tx = Transaction::market_resolved(&market.id, winning);
+                resolve_tx.id = format!("market_resolved:{}", market.id);
                 resolve_tx.timestamp = market.created_at + 1; // Approximate
                 transactions.push(resolve_tx);
             }
@@ -994,6 +948,16 @@ mod tests {
         assert_eq!(bal.pending, 0.0);
     }
     
+    #[test]
+    fn test_tx_type_parse() {
+        assert_eq!(TxType::parse("order_fill"), Some(TxType::OrderFill));
+        assert_eq!(TxType::parse("MarketCreated"), Some(TxType::MarketCreated));
+        assert_eq!(TxType::parse("bet"), Some(TxType::Bet));
+        assert_eq!(TxType::parse("bets"), None);
+        assert_eq!(Layer::parse("l1"), Some(Layer::L1));
+        assert_eq!(FundStatus::parse("LOCKED"), Some(FundStatus::Locked));
+    }
+
     #[test]
     fn test_ledger_bet() {
         let mut ledger = Ledger::new();
@@ -1003,4 +967,16 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(ledger.balance("ALICE"), 900.0);
     }
+
+    #[test]
+    fn test_transfer_to_short_system_account() {
+        let mut ledger = Ledger::new();
+        ledger.register("ALICE", "L1_ALICE_ADDR", 100.0);
+        ledger.credit("escrow:m1", 0.0);
+
+        ledger.transfer("ALICE", "escrow:m1", 40.0, "mint_shares").unwrap();
+        ledger.transfer("escrow:m1", "ALICE", 15.0, "redeem_shares").unwrap();
+        assert_eq!(ledger.balance("escrow:m1"), 25.0);
+        assert_eq!(ledger.balance("ALICE"), 75.0);
+    }
 }
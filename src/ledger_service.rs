@@ -0,0 +1,161 @@
+//! Ledger Service
+//!
+//! The unified ledger behind its own lock, shared by the application state
+//! and every market actor. Fills, mints and payouts settle against the ledger
+//! without taking the application lock, and a market actor never waits on
+//! another market.
+//!
+//! The common operations lock internally for a single call. `read()` and
+//! `write()` hand out the guard for anything that needs several steps under
+//! one lock; never hold a guard across an `.await`.
+//!
+//! The human-readable activity log (`GET /ledger`) sits beside the ledger
+//! under a lock of its own, so logging never waits on balances or on the
+//! application lock.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::ledger::{BalanceBreakdown, Ledger, LedgerStats, Transaction};
+
+/// Activity log entries kept (oldest dropped first)
+pub const MAX_ACTIVITY_ENTRIES: usize = 1000;
+
+/// Cloneable handle to the shared ledger
+#[derive(Debug, Clone)]
+pub struct LedgerService {
+    inner: Arc<RwLock<Ledger>>,
+    activity: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl LedgerService {
+    pub fn new(ledger: Ledger) -> Self {
+        Self { inner: Arc::new(RwLock::new(ledger)), activity: Arc::default() }
+    }
+
+    /// Shared access for multi-step reads
+    pub fn read(&self) -> RwLockReadGuard<'_, Ledger> {
+        self.inner.read().unwrap()
+    }
+
+    /// Exclusive access for multi-step updates
+    pub fn write(&self) -> RwLockWriteGuard<'_, Ledger> {
+        self.inner.write().unwrap()
+    }
+
+    // ========================================================================
+    // READS
+    // ========================================================================
+
+    pub fn resolve(&self, id: &str) -> Option<String> {
+        self.read().resolve(id)
+    }
+
+    pub fn balance(&self, id: &str) -> f64 {
+        self.read().balance(id)
+    }
+
+    pub fn confirmed_balance(&self, id: &str) -> f64 {
+        self.read().confirmed_balance(id)
+    }
+
+    pub fn pending(&self, id: &str) -> f64 {
+        self.read().pending(id)
+    }
+
+    pub fn locked(&self, id: &str) -> f64 {
+        self.read().locked(id)
+    }
+
+    pub fn balance_breakdown(&self, id: &str) -> Option<BalanceBreakdown> {
+        self.read().balance_breakdown(id)
+    }
+
+    pub fn stats(&self) -> LedgerStats {
+        self.read().stats()
+    }
+
+    /// Activity log, oldest first
+    pub fn activity(&self) -> Vec<String> {
+        self.activity.lock().unwrap().iter().cloned().collect()
+    }
+
+    // ========================================================================
+    // WRITES
+    // ========================================================================
+
+    pub fn register(&self, name: &str, address: &str, initial: f64) {
+        self.write().register(name, address, initial)
+    }
+
+    pub fn place_bet(&self, from: &str, market_id: &str, outcome: usize, amount: f64, sig: &str) -> Result<Transaction, String> {
+        self.write().place_bet(from, market_id, outcome, amount, sig)
+    }
+
+    pub fn transfer(&self, from: &str, to: &str, amount: f64, sig: &str) -> Result<Transaction, String> {
+        self.write().transfer(from, to, amount, sig)
+    }
+
+    pub fn credit(&self, id: &str, amount: f64) {
+        self.write().credit(id, amount)
+    }
+
+    pub fn debit(&self, id: &str, amount: f64) {
+        self.write().debit(id, amount)
+    }
+
+    pub fn record(&self, tx: Transaction) -> Transaction {
+        self.write().record(tx)
+    }
+
+    /// Print an activity line and append it to the log
+    pub fn log_activity(&self, emoji: &str, action: &str, details: &str) {
+        let timestamp = chrono::Local::now().format("%H:%M:%S");
+        let entry = format!("[{}] {} {} | {}", timestamp, emoji, action, details);
+        println!("{}", entry);
+        let mut activity = self.activity.lock().unwrap();
+        activity.push_back(entry);
+        if activity.len() > MAX_ACTIVITY_ENTRIES {
+            activity.pop_front();
+        }
+    }
+}
+
+impl Default for LedgerService {
+    fn default() -> Self {
+        Self::new(Ledger::new())
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_one_ledger() {
+        let ledger = LedgerService::default();
+        let actor_view = ledger.clone();
+
+        ledger.credit("alice", 100.0);
+        actor_view.debit("alice", 30.0);
+
+        assert_eq!(ledger.balance("alice"), 70.0);
+        assert_eq!(actor_view.read().balances.len(), 1);
+    }
+
+    #[test]
+    fn test_activity_log_is_bounded() {
+        let ledger = LedgerService::default();
+        for i in 0..MAX_ACTIVITY_ENTRIES + 5 {
+            ledger.clone().log_activity("💸", "TRANSFER", &format!("#{}", i));
+        }
+
+        let activity = ledger.activity();
+        assert_eq!(activity.len(), MAX_ACTIVITY_ENTRIES);
+        assert!(activity[0].ends_with("TRANSFER | #5"));
+    }
+}
@@ -4,10 +4,26 @@
 pub mod market_resolve;
 pub mod easteregg;
 pub mod bridge;
+pub mod bridge_proof;
+pub mod withdrawal_batch;
+pub mod state_root;
+pub mod blocks;
+pub mod forced_exit;
+pub mod session_receipt;
 pub mod auth;
 pub mod ledger;
+pub mod ledger_service;
 pub mod orderbook;
 pub mod shares;
+pub mod market_actor;
+pub mod config;
+pub mod api_error;
+pub mod pagination;
+pub mod market_search;
+pub mod idempotency;
+pub mod rate_limit;
+pub mod roles;
+pub mod audit_log;
 
 #[path = "../rss/mod.rs"]
 pub mod rss;
@@ -20,6 +36,8 @@ pub use market_resolve::{Ledger as MarketLedger, Transaction as MarketTransactio
 pub use market_resolve::{Market, MarketManager, Bet, MarketStatus, BetStatus};
 pub use market_resolve::{CPMMPool, SwapResult, EventStatus, PendingEvent, LP_FEE_RATE, MINIMUM_LAUNCH_LIQUIDITY, VIABILITY_THRESHOLD, VIABILITY_PERIOD_SECONDS};
 pub use market_resolve::escrow::*;
+pub use market_resolve::{DisputeManager, ResolutionProposal, ResolutionDispute, ProposalStatus, BondSettlement, DisputeError, DEFAULT_CHALLENGE_WINDOW_SECS, DEFAULT_RESOLUTION_BOND};
+pub use market_resolve::{CompiledRules, OutcomeRule, RuleExpr, RuleEvaluation, RuleError, CmpOp};
 
 // Re-export from orderbook (CLOB system)
 pub use orderbook::{
@@ -41,8 +59,32 @@ pub use shares::{
 };
 
 pub use easteregg::{GodMode, TestAccount, AccountInfo, SignedMessage, GodModeError};
-pub use ledger::{Ledger, L1Client, Balance, Transaction, TxType, LedgerStats};
+pub use easteregg::{OracleManager, DataFeed, DataFeedType, LocalFeed, PriceCondition, PriceRule, OracleResolution};
+pub use ledger::{Ledger, Balance, Transaction, TxType, LedgerStats};
+pub use ledger_service::LedgerService;
+pub use config::{Config, ConfigError, MarketParams, MarketOverrides, RateLimitConfig, ClassLimits, BucketLimit};
+pub use api_error::ApiError;
+pub use pagination::{Page, PageRequest, PageError, SortOrder, TimeRange, paginate, parse_filter, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+pub use idempotency::{IdempotencyStore, IdempotencyError, StoredResponse, idempotency_guard, DEFAULT_IDEMPOTENCY_TTL_SECS};
+pub use rate_limit::{RateLimiter, RateLimited, RouteClass, Scope, rate_limit_guard};
+pub use roles::{Role, RoleAction, RoleChange, RoleError, RoleRegistry, SignedRequest, Authorized, RequiredRole, AdminRole, OracleRole, DealerRole, ModeratorRole};
+pub use audit_log::{AuditLog, AuditEvent, AuditEntry, AuditReport, ChainBreak, verify_file, verify_ndjson, DEFAULT_AUDIT_PATH};
+pub use market_search::{MarketIndex, MarketDoc, SearchQuery, SearchSort, SearchHit, TRENDING_WINDOW_SECS};
+pub use market_actor::{MarketRegistry, MarketHandle, MarketSnapshot, CpmmFill, TradeError, fill_amounts, fill_parties, market_escrow, ORDERBOOK_ESCROW};
 pub use rpc::{SignedTransaction, SignedTxType, TransactionPayload, SignedTxError, TX_EXPIRY_SECS};
 pub use rpc::{L1BlackBookRpc, L1RpcConfig, L1HealthResponse, L1WalletLookupResponse, L1BalanceResponse, L1PoHStatus};
+pub use rpc::{L1VerifyRequest, L1VerifyResponse, L1SettlementRequest, L1SettlementResponse};
+pub use rpc::{L1WithdrawalBatchRequest, L1WithdrawalBatchResponse, L1WithdrawalClaimRequest, L1WithdrawalClaimResponse};
+pub use rpc::{L1StateRootRequest, L1StateRootResponse};
+pub use rpc::{MockL1, MockL1Session, FaultConfig, FaultAction};
+pub use rpc::{L1Backend, MockL1Backend, RecordingL1Backend, L1Call, l1_backend_from_config};
+pub use withdrawal_batch::{WithdrawalBatcher, WithdrawalBatch, WithdrawalLeaf, WithdrawalProof, BatchStatus, verify_withdrawal_proof};
+pub use state_root::{StateCommitter, StateCommitment, CommitmentStatus, StateLeaf, StateProof, SequencerKey, verify_state_proof, state_root_signing_bytes, compute_state_root};
+pub use blocks::{BlockProducer, Block, TxInclusionProof, tx_hash, block_hash, verify_tx_proof};
+pub use session_receipt::{SettlementReceipt, session_receipt_signing_bytes};
+pub use forced_exit::{ExitManager, ExitRequest, ExitStatus, ExitPayout, ExitPayoutKind, ExitPosition, FreezeState, DEFAULT_EXIT_DEADLINE_SECS};
+pub use bridge_proof::{DepositVerifier, DepositProof, DepositVerification, Attestation, deposit_leaf, deposit_signing_bytes, root_signing_bytes};
 pub use bridge::{BridgeManager, BridgeStatus, BridgeDirection, PendingBridge, BridgeError, BridgeRequest, BridgeResponse, BridgeCompleteRequest, BridgeCompleteResponse, BridgeStatusResponse, BridgeStats};
 pub use rss::{RssEvent, ResolutionRules, RssFeedManager, EventDates, write_rss_event_to_file, load_rss_events_from_folder};
+pub use rss::{FeedSource, FeedStatus, PollReport, parse_feed};
+pub use rss::{FeedPublisher, FeedItem, FeedItemKind, FeedItemDetail, FeedFilter};
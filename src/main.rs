@@ -2,32 +2,35 @@
 // Clean, modular architecture with cryptographic signature verification
 
 use axum::{
-    routing::{get, post, delete},
+    routing::{get, post, put, delete},
     Router,
 };
-use std::{net::SocketAddr, sync::{Arc, Mutex}};
 use tower_http::cors::{Any, CorsLayer};
 
+// Shared modules come from the library crate; only the server lives here
+use blackbook_prediction_market::{
+    market_resolve, auth, easteregg, bridge, bridge_proof, withdrawal_batch, state_root,
+    blocks, forced_exit, session_receipt, ledger, ledger_service, orderbook, shares,
+    market_actor, config, api_error, pagination, market_search, idempotency, rate_limit,
+    roles, audit_log, rss, rpc,
+};
+
 // Module declarations
-mod market_resolve;
-mod auth;
-mod easteregg;
-mod bridge;
 mod models;
-mod ledger;
 mod app_state;
 mod handlers;
 mod routes;
-mod orderbook;
-mod shares;
-
-#[path = "../rss/mod.rs"]
-mod rss;
-
-#[path = "../rpc/mod.rs"]
-mod rpc;
+mod openapi;
+mod price_resolver;
+mod bridge_relayer;
+mod state_publisher;
+mod block_producer;
+mod session_settlement;
+mod escape_hatch;
 
 use app_state::{AppState, SharedState};
+use idempotency::IdempotencyStore;
+use rate_limit::RateLimiter;
 use handlers::*;
 use routes::auth::connect_wallet;
 
@@ -37,17 +40,58 @@ async fn main() {
     println!("     🎲 BlackBook Layer 2 Prediction Market");
     println!("═══════════════════════════════════════════════\n");
 
+    // Load config (file layers + env overrides); refuse to start on bad values
+    let config = match config::Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+    let addr = config.listen_addr();
+
+    // Responses to Idempotency-Key requests, restored from the last run
+    let now = chrono::Utc::now().timestamp() as u64;
+    let idempotency = match IdempotencyStore::open(&config.idempotency.store_path, config.idempotency.ttl_secs, now) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("⚠️  Warning: Failed to load idempotency store: {}", e);
+            IdempotencyStore::new(config.idempotency.ttl_secs)
+        }
+    };
+    let idempotency = idempotency.with_forwarded_for(config.rate_limit.trust_forwarded_for);
+    if !idempotency.is_empty() {
+        println!("🔁 Restored {} idempotency key(s) from disk", idempotency.len());
+    }
+    let shutdown_idempotency = idempotency.clone();
+    let rate_limiter = RateLimiter::new(config.rate_limit.clone());
+    let mut feeds = config.feeds.feed_manager();
+    match feeds.load_processed() {
+        Ok(count) if count > 0 => println!("📡 Restored {} processed RSS item(s) from disk", count),
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️  Warning: Failed to load processed RSS items: {}", e),
+    }
+
     // Initialize application state
-    let state: SharedState = Arc::new(Mutex::new(AppState::new()));
+    let state = SharedState::new(AppState::new(config));
     
     // Clone state for shutdown handler before moving into router
     let shutdown_state = state.clone();
+    let finalizer_state = state.clone();
+    let resolver_state = state.clone();
+    let feed_state = state.clone();
+    let relayer_state = state.clone();
+    let publisher_state = state.clone();
+    let producer_state = state.clone();
+    let exit_state = state.clone();
+    let session_state = state.clone();
 
     // Build router with all endpoints
     let app = Router::new()
         // ===== CORE MARKET ENDPOINTS =====
         .route("/markets", get(get_markets))
         .route("/markets", post(create_market))
+        .route("/markets/search", get(search_markets))
         .route("/markets/initial-liquidity", post(initialize_all_market_liquidity))
         .route("/markets/initial-liquidity/:market_id", post(initialize_market_liquidity))
         .route("/markets/:id", get(get_market))
@@ -59,6 +103,24 @@ async fn main() {
         .route("/markets/:id/resolve", post(resolve_market))
         .route("/resolve/:market_id/:outcome", post(admin_resolve_market))  // SDK compatibility
         .route("/admin/resolve/:market_id/:outcome", post(admin_resolve_market))
+        .route("/markets/:id/dispute", post(dispute_resolution))
+        .route("/markets/:id/finalize", post(finalize_resolution))
+        .route("/admin/disputes/:market_id/arbitrate", post(arbitrate_dispute))
+        .route("/disputes", get(list_disputes))
+        .route("/resolver/status", get(get_resolver_status))
+        
+        // ===== PENDING EVENT INBOX =====
+        .route("/events/pending", get(list_pending_events))
+        .route("/events/pending", post(submit_pending_event))
+        .route("/events/pending/expire", post(expire_pending_events))
+        .route("/events/pending/:id", put(edit_pending_event))
+        .route("/events/pending/:id/reject", post(reject_pending_event))
+        .route("/events/launch", post(launch_pending_event))
+        // Outbound RSS/Atom feeds
+        .route("/feed", get(get_feed_items))
+        .route("/feed/rss", get(get_rss_feed))
+        .route("/feed/atom", get(get_atom_feed))
+        .route("/feeds/status", get(get_feed_status))
         
         // ===== DEALER / MARKET MAKER ENDPOINTS =====
         .route("/dealer/fund-all-markets", post(dealer_fund_all_markets))
@@ -101,12 +163,28 @@ async fn main() {
         .route("/settle/status", get(get_settlement_status)) // Get settlement status
         .route("/sync", post(sync_from_l1))                 // Sync balances from L1
         
+        // ===== L2 BLOCK EXPLORER =====
+        .route("/blocks", get(list_blocks))
+        .route("/blocks/latest", get(get_latest_block))
+        .route("/blocks/:height", get(get_block))
+        .route("/tx/:id", get(get_transaction))
+        
+        // ===== L2 STATE COMMITMENTS =====
+        .route("/state/root", get(get_state_root))
+        .route("/state/commitments", get(list_state_commitments))
+        .route("/state/proof/:account", get(get_state_proof))
+        .route("/state/commit", post(commit_state_root))
+        
         // ===== BRIDGE ENDPOINTS (L1↔L2 Token Movement) =====
         .route("/bridge/deposit", post(bridge_deposit))     // L1→L2 (receive from L1)
         .route("/bridge/withdraw", post(bridge_withdraw))   // L2→L1 (send to L1) - calls L1, refunds on failure
         .route("/bridge/status/:bridge_id", get(get_bridge_status))
         .route("/bridge/list/:wallet", get(list_wallet_bridges))
         .route("/bridge/stats", get(get_bridge_stats))
+        .route("/bridge/trust", get(get_bridge_trust))
+        .route("/bridge/batches", get(list_withdrawal_batches))
+        .route("/bridge/batches/:batch_id", get(get_withdrawal_batch))
+        .route("/bridge/l1-roots", post(add_trusted_l1_root))
         
         // ===== SESSION ENDPOINTS (Optimistic Execution) =====
         .route("/session/start", post(session_start))       // Start L2 session (mirrors L1 balance)
@@ -114,10 +192,21 @@ async fn main() {
         .route("/session/status/:wallet", get(session_status)) // Get session status
         .route("/session/list", get(session_list))          // List all active sessions
         
+        // ===== FORCED EXITS (ESCAPE HATCH) =====
+        .route("/exits", post(submit_exit))
+        .route("/exits", get(list_exits))
+        .route("/exits/overdue", get(list_overdue_exits))
+        .route("/exits/:exit_id", get(get_exit))
+        .route("/admin/freeze", post(freeze_and_exit))
+        
         // ===== ORACLE/ADMIN MANAGEMENT =====
         .route("/admin/oracles", post(add_oracle))
         .route("/admin/oracles", get(list_oracles))
         .route("/admin/oracles/:address", delete(remove_oracle))
+        .route("/admin/roles", get(list_roles))
+        .route("/admin/roles", post(change_role))
+        .route("/admin/audit/verify", get(verify_audit_log))
+        .route("/admin/audit/export", get(export_audit_log))
         
         // ===== RPC ENDPOINTS =====
         .route("/rpc/nonce/:address", get(get_nonce))
@@ -129,8 +218,13 @@ async fn main() {
         // ===== HEALTH CHECK =====
         .route("/", get(health_check))
         .route("/health", get(health_check))
+        .route("/openapi.json", get(openapi::openapi_json))
         
-        // Apply CORS and state
+        // Replay Idempotency-Key retries, reject writes once frozen, throttle,
+        // then apply CORS and state
+        .layer(axum::middleware::from_fn_with_state(idempotency, idempotency::idempotency_guard))
+        .layer(axum::middleware::from_fn_with_state(state.clone(), freeze_guard))
+        .layer(axum::middleware::from_fn_with_state(rate_limiter, rate_limit::rate_limit_guard))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
@@ -140,56 +234,103 @@ async fn main() {
         .with_state(state);
 
     // Start server
-    let addr = SocketAddr::from(([0, 0, 0, 0], 1234));
-    
+    let url = format!("http://{}", addr);
     println!("\n╔════════════════════════════════════════════╗");
     println!("║   🚀 SERVER RUNNING                        ║");
-    println!("║   📡 http://0.0.0.0:1234                   ║");
+    println!("║   📡 {:<38}║", url);
     println!("╚════════════════════════════════════════════╝\n");
     
-    println!("📋 Available Endpoints:");
+    println!("📋 Available Endpoints (full spec: GET /openapi.json):");
+    println!("   Send Idempotency-Key on POST/PUT/DELETE to make retries safe");
+    println!("   Rate limited per IP and wallet (429 + Retry-After); market makers send X-Api-Key");
     println!("   POST /auth/connect      - Connect wallet (creates & funds if new)");
     println!("   GET  /markets           - List all prediction markets");
     println!("   POST /markets           - Create new market");
+    println!("   GET  /markets/search    - Search markets (?q=, category, tags, sort=trending|liquidity|newest|closing_soon)");
     println!("   POST /markets/initial-liquidity - Init CPMM + L1 mint for all markets");
     println!("   GET  /markets/:id       - Get market details");
     println!("   GET  /markets/:id/odds  - Get dynamic odds (CLOB/CPMM hybrid)");
-    println!("");
+    println!();
     println!("   ═══ MARKET RESOLUTION ═══");
-    println!("   POST /markets/:id/resolve - Resolve market (oracle/admin only)");
-    println!("   POST /resolve/:id/:outcome - Admin shortcut to resolve");
+    println!("   POST /markets/:id/resolve - Propose resolution + bond (oracle/admin only)");
+    println!("   POST /resolve/:id/:outcome - Admin shortcut to propose resolution");
+    println!("   POST /markets/:id/dispute - Dispute proposal with matching bond");
+    println!("   POST /markets/:id/finalize - Finalize after challenge window");
+    println!("   POST /admin/disputes/:id/arbitrate - Arbitrate disputed resolution");
+    println!("   GET  /disputes          - List open proposals/disputes");
+    println!("   GET  /resolver/status   - Price resolver funding and last pass");
     println!("   GET  /markets/:id/resolution - Get resolution details");
     println!("   POST /shares/claim/:id  - Claim winnings after resolution");
-    println!("");
+    println!();
+    println!("   ═══ EVENT INBOX ═══");
+    println!("   GET  /events/pending    - Browse pending events");
+    println!("   POST /events/pending    - Submit event to inbox (moderator)");
+    println!("   PUT  /events/pending/:id - Edit pending event (moderator)");
+    println!("   POST /events/pending/:id/reject - Reject pending event (moderator)");
+    println!("   POST /events/pending/expire - Purge expired events (moderator)");
+    println!("   POST /events/launch     - Launch event with signed MarketLaunch");
+    println!("   GET  /feed/rss          - RSS feed (?category, ?tag, ?type)");
+    println!("   GET  /feed/atom         - Atom feed (?category, ?tag, ?type)");
+    println!("   GET  /feed              - Feed items as JSON");
+    println!("   GET  /feeds/status      - Inbound RSS feed polling health");
+    println!();
     println!("   ═══ CLOB ORDER BOOK ═══");
     println!("   POST /orders            - Submit limit order");
     println!("   DELETE /orders/:id      - Cancel order");
     println!("   GET  /orders/user/:wallet - Get user's open orders");
     println!("   GET  /orderbook/:market_id - Get order book depth");
     println!("   GET  /trades/:market_id - Get recent trades");
-    println!("");
+    println!();
     println!("   ═══ OUTCOME SHARES ═══");
     println!("   POST /shares/mint       - Mint YES+NO shares (1 BB → 1 YES + 1 NO)");
     println!("   POST /shares/redeem     - Redeem shares (1 YES + 1 NO → 1 BB)");
     println!("   GET  /positions/:wallet - Get all user positions");
-    println!("");
+    println!();
     println!("   ═══ L1↔L2 BRIDGE ═══");
-    println!("   POST /bridge/deposit    - L1→L2 deposit (receive from L1)");
+    println!("   POST /bridge/deposit    - L1→L2 deposit (requires L1 proof)");
     println!("   POST /bridge/withdraw   - L2→L1 withdraw (send to L1)");
-    println!("   GET  /bridge/status/:id - Get bridge status");
+    println!("   GET  /bridge/status/:id - Get bridge status (+ batch inclusion proof)");
+    println!("   GET  /bridge/batches    - Withdrawal batches");
+    println!("   GET  /bridge/batches/:id - Batch details");
     println!("   GET  /bridge/list/:wallet - List wallet bridges");
-    println!("   GET  /bridge/stats      - Bridge statistics");
-    println!("");
+    println!("   GET  /bridge/stats      - Bridge statistics (incl. stuck relays)");
+    println!("   GET  /bridge/trust      - Deposit trust anchors");
+    println!("   POST /bridge/l1-roots   - Trust an attested L1 root");
+    println!();
     println!("   ═══ L1 SETTLEMENT ═══");
     println!("   POST /settle            - Submit resolutions to L1");
     println!("   GET  /settle/pending    - View pending settlements");
     println!("   GET  /settle/status     - Get settlement status");
-    println!("");
+    println!();
+    println!("   ═══ L2 BLOCKS ═══");
+    println!("   GET  /blocks            - Recent blocks");
+    println!("   GET  /blocks/latest     - Latest block");
+    println!("   GET  /blocks/:height    - Block by height");
+    println!("   GET  /tx/:id            - Transaction + block inclusion proof");
+    println!();
+    println!("   ═══ L2 STATE COMMITMENTS ═══");
+    println!("   GET  /state/root        - Latest signed state root (+ latest posted to L1)");
+    println!("   GET  /state/commitments - State root history");
+    println!("   GET  /state/proof/:acct - Balance/position proofs against posted root");
+    println!("   POST /state/commit      - Commit and post a state root now (admin)");
+    println!();
+    println!("   ═══ FORCED EXITS ═══");
+    println!("   POST /exits             - Signed forced exit of all funds to L1");
+    println!("   GET  /exits             - Exits and freeze state");
+    println!("   GET  /exits/overdue     - Exits not processed by their deadline");
+    println!("   GET  /exits/:id         - Exit status and payouts");
+    println!("   POST /admin/freeze      - Freeze the L2 and exit every account (admin)");
+    println!();
     println!("   ═══ ADMIN/ORACLE ═══");
     println!("   POST /admin/oracles     - Add oracle to whitelist");
     println!("   GET  /admin/oracles     - List whitelisted oracles");
     println!("   DELETE /admin/oracles/:addr - Remove oracle");
-    println!("");
+    println!("   GET  /admin/roles       - List role holders and grant history");
+    println!("   POST /admin/roles       - Grant or revoke a role");
+    println!("   GET  /admin/audit/verify - Verify the audit log hash chain");
+    println!("   GET  /admin/audit/export - Export the audit log (NDJSON)");
+    println!("   (privileged routes need X-BlackBook-Key/-Timestamp/-Signature headers)");
+    println!();
     println!("   ═══ LEGACY ENDPOINTS ═══");
     println!("   POST /bet/signed        - Place bet (cryptographic signature)");
     println!("   GET  /balance/:account  - Get account balance");
@@ -201,6 +342,49 @@ async fn main() {
     // Setup graceful shutdown
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     
+    // Finalize undisputed resolutions once their challenge window closes
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+        loop {
+            interval.tick().await;
+            app_state::finalize_expired_resolutions(&finalizer_state).await;
+        }
+    });
+    
+    // Resolve price-threshold markets from oracle data at their resolution date
+    price_resolver::spawn(resolver_state);
+    
+    // Retry, reconcile and refund L2→L1 withdrawals
+    bridge_relayer::spawn(relayer_state);
+    
+    // Seal ledger transactions into L2 blocks
+    block_producer::spawn(producer_state);
+    
+    // Commit signed L2 state roots and post them to L1
+    state_publisher::spawn(publisher_state);
+    
+    // Settle expired L2 sessions to L1
+    session_settlement::spawn(session_state);
+    
+    // Pay out forced exits (and every account once frozen)
+    escape_hatch::spawn(exit_state);
+    
+    // Poll configured RSS/Atom feeds into the pending-event inbox
+    if !feeds.feed_urls.is_empty() {
+        println!("📡 Polling {} RSS feed(s) every {}s", feeds.feed_urls.len(), feeds.poll_interval_secs);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                let report = feeds.poll_due_feeds().await;
+                if let Ok(mut app_state) = feed_state.lock() {
+                    app_state.feed_sources = feeds.feed_status.clone();
+                    app_state.ingest_pending_events(report.new_events);
+                }
+            }
+        });
+    }
+    
     // Spawn shutdown handler
     tokio::spawn(async move {
         tokio::signal::ctrl_c()
@@ -209,6 +393,7 @@ async fn main() {
         
         println!("\n\n🛑 Shutdown signal received...");
         println!("💾 Saving state to disk...");
+        shutdown_idempotency.flush().await;
         
         if let Ok(app_state) = shutdown_state.lock() {
             if let Err(e) = app_state.save_to_disk() {
@@ -222,9 +407,18 @@ async fn main() {
         std::process::exit(0);
     });
 
-    axum::serve(listener, app).await.unwrap();
+    // Client addresses feed the per-IP rate limits
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>()).await.unwrap();
 }
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "Server is up", body = String, content_type = "text/plain"),
+    )
+)]
 async fn health_check() -> &'static str {
     "BlackBook Layer 2 Prediction Market - Online ✅"
 }
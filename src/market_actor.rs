@@ -0,0 +1,1012 @@
+//! Market Actors
+//!
+//! Every market runs as its own task that owns the market's matching engine,
+//! CPMM pool and share book. Commands arrive over a channel and are applied
+//! one at a time, so trading needs no lock and a busy market never stalls
+//! another. Fills, mints, redemptions and payouts settle against the
+//! separately locked [`LedgerService`].
+//!
+//! A new order escrows what it can spend before it matches: a bid's BB
+//! (limit price × size plus the taker fee) moves to [`ORDERBOOK_ESCROW`]
+//! and an ask's shares leave the seller's position. Fills settle out of the
+//! escrow, and whatever an order did not use goes back when it fills,
+//! cancels or stops resting.
+//!
+//! After each batch of commands the actor publishes an immutable
+//! [`MarketSnapshot`] on a `watch` channel, then answers the batch. Depth,
+//! odds, open orders and positions are read from the latest snapshot
+//! without messaging the actor, and a caller always sees its own write.
+//! Only the parts the batch touched are rebuilt; the rest are shared with
+//! the previous snapshot.
+//!
+//! Every fill and every order the market has seen go into its
+//! [`MarketHistory`] instead, which the actor appends to before replying
+//! and the paginated trade and order lists read.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+use tokio::sync::{mpsc, oneshot, watch};
+
+use crate::config::{Config, MarketParams};
+use crate::ledger_service::LedgerService;
+use crate::market_resolve::cpmm::CPMMPool;
+use crate::orderbook::{
+    Fill, LimitOrder, MarketOdds, OrderBookManager, OrderBookSnapshot, OrderBookStats,
+    OrderSubmitResult, Outcome, Side,
+};
+use crate::shares::{OutcomeIndex, SharePosition, SharesManager, SharesStats, SimplePosition};
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Price levels per side kept in a snapshot
+pub const SNAPSHOT_DEPTH: usize = 20;
+
+/// Commands applied before a snapshot is published under sustained load
+pub const MAX_BATCH: usize = 256;
+
+/// Ledger account holding resting bids' BB until they fill or end
+pub const ORDERBOOK_ESCROW: &str = "orderbook_escrow";
+
+/// Escrow left on an order below which nothing is refunded
+const DUST: f64 = 1e-9;
+
+/// Ledger account holding the BB behind a market's minted share pairs
+pub fn market_escrow(market_id: &str) -> String {
+    format!("escrow:{}", market_id)
+}
+
+/// (buyer pays, seller receives) for a fill, fees included
+pub fn fill_amounts(fill: &Fill) -> (f64, f64) {
+    (fill.value + fill.taker_fee, fill.value - fill.maker_fee)
+}
+
+/// (buyer, seller) of a fill
+pub fn fill_parties(fill: &Fill) -> (&str, &str) {
+    match fill.taker_side {
+        Side::Bid => (&fill.taker, &fill.maker),
+        Side::Ask => (&fill.maker, &fill.taker),
+    }
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TradeError {
+    /// An ask for more shares than the seller holds
+    InsufficientShares { available: f64, requested: f64 },
+    /// A bid or mint the wallet's BB balance cannot cover
+    InsufficientBalance { available: f64, required: f64 },
+    /// A redemption for more YES+NO pairs than the wallet holds
+    InsufficientPairs { yes_shares: f64, no_shares: f64, requested: f64 },
+    /// A claim from a wallet holding no winning shares
+    NoWinningShares { yes_shares: f64, no_shares: f64 },
+    /// The order book rejected the request
+    Rejected(String),
+    /// A ledger or share book update failed
+    Settlement(String),
+    /// The market's actor has stopped
+    Unavailable,
+}
+
+impl fmt::Display for TradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TradeError::InsufficientShares { available, requested } => {
+                write!(f, "Insufficient shares: have {}, need {}", available, requested)
+            }
+            TradeError::InsufficientBalance { available, required } => {
+                write!(f, "Insufficient balance: have {} BB, need {} BB", available, required)
+            }
+            TradeError::InsufficientPairs { yes_shares, no_shares, .. } => {
+                write!(f, "Insufficient share pairs: can redeem max {} pairs", yes_shares.min(*no_shares))
+            }
+            TradeError::NoWinningShares { .. } => write!(f, "No winning shares to claim"),
+            TradeError::Rejected(error) | TradeError::Settlement(error) => write!(f, "{}", error),
+            TradeError::Unavailable => write!(f, "Market is not accepting commands"),
+        }
+    }
+}
+
+// ============================================================================
+// SNAPSHOTS
+// ============================================================================
+
+/// Read-only view of a market, published after every batch of commands
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    pub market_id: String,
+    /// Commands applied so far
+    pub version: u64,
+    pub odds: MarketOdds,
+    pub yes_book: OrderBookSnapshot,
+    pub no_book: OrderBookSnapshot,
+    /// Active orders, oldest first
+    pub open_orders: Arc<Vec<LimitOrder>>,
+    /// Non-empty share positions by holder, then outcome (an ask's escrowed
+    /// shares excluded)
+    pub positions: Arc<Vec<SharePosition>>,
+    pub pool: Option<CPMMPool>,
+    pub orderbook_stats: OrderBookStats,
+    pub shares_stats: SharesStats,
+}
+
+impl MarketSnapshot {
+    /// Depth for one outcome, limited to `levels` per side
+    pub fn book(&self, outcome: Outcome, levels: usize) -> OrderBookSnapshot {
+        let mut book = if outcome == Outcome::NO { self.no_book.clone() } else { self.yes_book.clone() };
+        book.bids.truncate(levels);
+        book.asks.truncate(levels);
+        book
+    }
+
+    /// YES/NO shares held by `wallet`
+    pub fn position(&self, wallet: &str) -> SimplePosition {
+        let mut position = SimplePosition {
+            market_id: self.market_id.clone(),
+            yes_shares: 0.0,
+            no_shares: 0.0,
+        };
+        for p in self.positions.iter().filter(|p| p.holder == wallet) {
+            if p.outcome == OutcomeIndex::YES {
+                position.yes_shares += p.shares;
+            } else if p.outcome == OutcomeIndex::NO {
+                position.no_shares += p.shares;
+            }
+        }
+        position
+    }
+}
+
+// ============================================================================
+// HISTORY
+// ============================================================================
+
+/// Every fill and order a market has seen, shared by its actor and handles
+#[derive(Debug, Default)]
+pub struct MarketHistory {
+    /// Oldest first
+    pub fills: Vec<Arc<Fill>>,
+    /// Latest state of each order by id, filled and cancelled ones included
+    pub orders: HashMap<String, LimitOrder>,
+}
+
+// ============================================================================
+// COMMANDS
+// ============================================================================
+
+/// Outcome of a bet against the market's CPMM pool
+#[derive(Debug, Clone)]
+pub struct CpmmFill {
+    pub entry_price: f64,
+    pub shares: f64,
+    pub price_impact: f64,
+    pub new_price: f64,
+    pub fee: f64,
+}
+
+enum MarketCommand {
+    SubmitOrder {
+        order: LimitOrder,
+        reply: oneshot::Sender<Result<OrderSubmitResult, TradeError>>,
+    },
+    CancelOrder {
+        order_id: String,
+        wallet: String,
+        reply: oneshot::Sender<Result<LimitOrder, TradeError>>,
+    },
+    BuyCpmm {
+        outcome: usize,
+        amount: f64,
+        reply: oneshot::Sender<Option<CpmmFill>>,
+    },
+    Mint {
+        wallet: String,
+        amount: f64,
+        reply: oneshot::Sender<Result<SimplePosition, TradeError>>,
+    },
+    Redeem {
+        wallet: String,
+        amount: f64,
+        reply: oneshot::Sender<Result<SimplePosition, TradeError>>,
+    },
+    Claim {
+        wallet: String,
+        winning_outcome: usize,
+        reply: oneshot::Sender<Result<f64, TradeError>>,
+    },
+    Resolve {
+        winning_outcome: usize,
+        reply: oneshot::Sender<Vec<(String, f64)>>,
+    },
+    InstallPool {
+        pool: CPMMPool,
+    },
+}
+
+type Reply = Box<dyn FnOnce() + Send>;
+
+/// Parts of the snapshot a command may have changed
+#[derive(Debug, Clone, Copy, Default)]
+struct Changed {
+    /// Orders, depth, odds and order book stats
+    book: bool,
+    /// Positions and share stats
+    shares: bool,
+    /// CPMM pool (and the odds it prices)
+    pool: bool,
+}
+
+impl Changed {
+    const BOOK: Changed = Changed { book: true, shares: true, pool: false };
+    const SHARES: Changed = Changed { book: false, shares: true, pool: false };
+    const POOL: Changed = Changed { book: false, shares: false, pool: true };
+
+    fn merge(&mut self, other: Changed) {
+        self.book |= other.book;
+        self.shares |= other.shares;
+        self.pool |= other.pool;
+    }
+}
+
+/// BB or shares held back for an order until it fills or ends
+#[derive(Debug, Clone)]
+struct Escrow {
+    wallet: String,
+    /// Held for a bid
+    bb: f64,
+    /// Held for an ask
+    shares: f64,
+    outcome: OutcomeIndex,
+}
+
+fn reply<T: Send + 'static>(sender: oneshot::Sender<T>, value: T) -> Reply {
+    Box::new(move || {
+        let _ = sender.send(value);
+    })
+}
+
+// ============================================================================
+// ACTOR
+// ============================================================================
+
+struct MarketActor {
+    market_id: String,
+    orderbook: OrderBookManager,
+    shares: SharesManager,
+    ledger: LedgerService,
+    version: u64,
+    history: Arc<RwLock<MarketHistory>>,
+    /// Order id -> what it still holds in escrow
+    escrows: HashMap<String, Escrow>,
+}
+
+impl MarketActor {
+    fn new(market_id: &str, pool: Option<CPMMPool>, params: MarketParams, ledger: LedgerService) -> Self {
+        let mut orderbook = OrderBookManager::new();
+        if let Some(pool) = pool {
+            orderbook.cpmm_pools.insert(market_id.to_string(), pool);
+        }
+        orderbook.configure(&params);
+        {
+            // Fills and mints settle through these, so they must exist up front
+            let mut ledger = ledger.write();
+            for escrow in [ORDERBOOK_ESCROW.to_string(), market_escrow(market_id)] {
+                if ledger.resolve(&escrow).is_none() {
+                    ledger.credit(&escrow, 0.0);
+                }
+            }
+        }
+        Self {
+            market_id: market_id.to_string(),
+            orderbook,
+            shares: SharesManager::new(),
+            ledger,
+            version: 0,
+            history: Arc::new(RwLock::new(MarketHistory::default())),
+            escrows: HashMap::new(),
+        }
+    }
+
+    async fn run(
+        mut self,
+        mut commands: mpsc::UnboundedReceiver<MarketCommand>,
+        snapshots: watch::Sender<Arc<MarketSnapshot>>,
+    ) {
+        while let Some(command) = commands.recv().await {
+            let mut changed = Changed::default();
+            let mut replies = vec![self.apply(command, &mut changed)];
+            while replies.len() < MAX_BATCH {
+                match commands.try_recv() {
+                    Ok(command) => replies.push(self.apply(command, &mut changed)),
+                    Err(_) => break,
+                }
+            }
+            let previous = snapshots.borrow().clone();
+            snapshots.send_replace(Arc::new(self.snapshot(Some(&previous), changed)));
+            for reply in replies {
+                reply();
+            }
+        }
+    }
+
+    fn apply(&mut self, command: MarketCommand, changed: &mut Changed) -> Reply {
+        self.version += 1;
+        match command {
+            MarketCommand::SubmitOrder { order, reply: tx } => {
+                let result = self.submit_order(order);
+                changed.merge(Changed::BOOK);
+                reply(tx, result)
+            }
+            MarketCommand::CancelOrder { order_id, wallet, reply: tx } => {
+                let result = self.cancel_order(&order_id, &wallet);
+                changed.merge(Changed::BOOK);
+                reply(tx, result)
+            }
+            MarketCommand::BuyCpmm { outcome, amount, reply: tx } => {
+                changed.merge(Changed::POOL);
+                reply(tx, self.buy_cpmm(outcome, amount))
+            }
+            MarketCommand::Mint { wallet, amount, reply: tx } => {
+                changed.merge(Changed::SHARES);
+                reply(tx, self.mint(&wallet, amount))
+            }
+            MarketCommand::Redeem { wallet, amount, reply: tx } => {
+                changed.merge(Changed::SHARES);
+                reply(tx, self.redeem(&wallet, amount))
+            }
+            MarketCommand::Claim { wallet, winning_outcome, reply: tx } => {
+                changed.merge(Changed::SHARES);
+                reply(tx, self.claim(&wallet, winning_outcome))
+            }
+            MarketCommand::Resolve { winning_outcome, reply: tx } => {
+                changed.merge(Changed::SHARES);
+                reply(tx, self.resolve(winning_outcome))
+            }
+            MarketCommand::InstallPool { mut pool } => {
+                pool.fee_rate = self.orderbook.lp_fee_rate;
+                self.orderbook.cpmm_pools.insert(self.market_id.clone(), pool);
+                changed.merge(Changed::POOL);
+                Box::new(|| {})
+            }
+        }
+    }
+
+    /// Escrow what the order can spend, match it, then settle every fill
+    /// out of the escrow. An order that cannot be escrowed is rejected
+    /// before it reaches the book.
+    fn submit_order(&mut self, order: LimitOrder) -> Result<OrderSubmitResult, TradeError> {
+        let outcome = OutcomeIndex::from_usize(order.outcome.index());
+        let wallet = order.maker.clone();
+
+        let escrow = match order.side {
+            Side::Ask => {
+                let available = self.shares.get_shares(&wallet, &self.market_id, outcome);
+                if available < order.size {
+                    return Err(TradeError::InsufficientShares { available, requested: order.size });
+                }
+                self.shares.debit_shares_simple(&wallet, &self.market_id, outcome, order.size)
+                    .map_err(TradeError::Settlement)?;
+                Escrow { wallet, bb: 0.0, shares: order.size, outcome }
+            }
+            Side::Bid => {
+                // Covers every fill at or below the limit, taker fee included
+                let required = (order.price_bps as f64 / 100.0) * order.size
+                    * (1.0 + self.orderbook.engine.taker_fee_rate);
+                let available = self.ledger.balance(&wallet);
+                if available < required {
+                    return Err(TradeError::InsufficientBalance { available, required });
+                }
+                self.ledger.transfer(&wallet, ORDERBOOK_ESCROW, required, &order.id)
+                    .map_err(TradeError::Settlement)?;
+                Escrow { wallet, bb: required, shares: 0.0, outcome }
+            }
+        };
+        self.escrows.insert(order.id.clone(), escrow);
+
+        let result = self.orderbook.submit_order(order);
+
+        let mut failures = Vec::new();
+        {
+            let mut ledger = self.ledger.write();
+            let mut history = self.history.write().unwrap();
+            history.orders.insert(result.order.id.clone(), result.order.clone());
+            for fill in &result.fills {
+                let (buyer, seller) = fill_parties(fill);
+                let (buyer_cost, seller_receive) = fill_amounts(fill);
+                let (bid_id, ask_id) = match fill.taker_side {
+                    Side::Bid => (&fill.taker_order_id, &fill.maker_order_id),
+                    Side::Ask => (&fill.maker_order_id, &fill.taker_order_id),
+                };
+                if let Some(escrow) = self.escrows.get_mut(bid_id) {
+                    escrow.bb -= buyer_cost;
+                }
+                if let Some(escrow) = self.escrows.get_mut(ask_id) {
+                    escrow.shares -= fill.size;
+                }
+
+                if let Err(e) = ledger.transfer(ORDERBOOK_ESCROW, seller, seller_receive, &fill.id) {
+                    failures.push(format!("fill {}: {}", fill.id, e));
+                }
+                self.shares.credit_shares_simple(buyer, &self.market_id, outcome, fill.size);
+
+                // The resting side's fill counts changed too
+                if let Some(maker_order) = self.orderbook.engine.orders.get(&fill.maker_order_id) {
+                    history.orders.insert(maker_order.id.clone(), maker_order.clone());
+                }
+                history.fills.push(Arc::new(fill.clone()));
+            }
+        }
+
+        // Refund whatever orders that stopped resting did not use
+        let touched = std::iter::once(&result.order.id).chain(result.fills.iter().map(|f| &f.maker_order_id));
+        let ended: Vec<String> = touched
+            .filter(|id| self.orderbook.engine.orders.get(*id).is_none_or(|o| !o.status.is_active()))
+            .cloned()
+            .collect();
+        for order_id in ended {
+            if let Err(e) = self.release(&order_id) {
+                failures.push(e);
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(TradeError::Settlement(failures.join("; ")));
+        }
+        Ok(result)
+    }
+
+    /// Cancel a resting order and return its escrow
+    fn cancel_order(&mut self, order_id: &str, wallet: &str) -> Result<LimitOrder, TradeError> {
+        let order = self.orderbook.cancel_order(order_id, wallet)
+            .map_err(|e| TradeError::Rejected(format!("{:?}", e)))?;
+        self.history.write().unwrap().orders.insert(order.id.clone(), order.clone());
+        self.release(&order.id).map_err(TradeError::Settlement)?;
+        Ok(order)
+    }
+
+    /// Give an order's unused escrow back to its owner
+    fn release(&mut self, order_id: &str) -> Result<(), String> {
+        let Some(escrow) = self.escrows.remove(order_id) else {
+            return Ok(());
+        };
+        if escrow.shares > DUST {
+            self.shares.credit_shares_simple(&escrow.wallet, &self.market_id, escrow.outcome, escrow.shares);
+        }
+        if escrow.bb > DUST {
+            self.ledger.transfer(ORDERBOOK_ESCROW, &escrow.wallet, escrow.bb, order_id)
+                .map_err(|e| format!("refund of order {}: {}", order_id, e))?;
+        }
+        Ok(())
+    }
+
+    /// Spend `amount` BB on `outcome` against the CPMM pool. A failed swap
+    /// falls back to the pool's current price. None if the market has no pool.
+    fn buy_cpmm(&mut self, outcome: usize, amount: f64) -> Option<CpmmFill> {
+        let pool = self.orderbook.cpmm_pools.get_mut(&self.market_id)?;
+        let (entry_price, shares, price_impact, new_price, fee) = match pool.buy_with_amount(outcome, amount) {
+            Ok(buy) => (buy.entry_price, buy.shares_received, buy.price_impact, buy.new_price, buy.fee_paid),
+            Err(e) => {
+                println!("⚠️ CPMM buy failed: {} - falling back to static pricing", e);
+                let price = pool.calculate_prices().get(outcome).copied().unwrap_or(0.5);
+                (price, amount, 0.0, price, 0.0)
+            }
+        };
+        Some(CpmmFill { entry_price, shares, price_impact, new_price, fee })
+    }
+
+    /// 1 BB → 1 YES + 1 NO, the BB held in the market escrow
+    fn mint(&mut self, wallet: &str, amount: f64) -> Result<SimplePosition, TradeError> {
+        let available = self.ledger.balance(wallet);
+        if available < amount {
+            return Err(TradeError::InsufficientBalance { available, required: amount });
+        }
+        self.ledger.transfer(wallet, &market_escrow(&self.market_id), amount, "mint_shares")
+            .map_err(TradeError::Settlement)?;
+
+        self.shares.credit_shares_simple(wallet, &self.market_id, OutcomeIndex::YES, amount);
+        self.shares.credit_shares_simple(wallet, &self.market_id, OutcomeIndex::NO, amount);
+        Ok(self.shares.get_position(wallet, &self.market_id))
+    }
+
+    /// 1 YES + 1 NO → 1 BB from the market escrow
+    fn redeem(&mut self, wallet: &str, amount: f64) -> Result<SimplePosition, TradeError> {
+        let position = self.shares.get_position(wallet, &self.market_id);
+        if amount > position.yes_shares.min(position.no_shares) {
+            return Err(TradeError::InsufficientPairs {
+                yes_shares: position.yes_shares,
+                no_shares: position.no_shares,
+                requested: amount,
+            });
+        }
+
+        let _ = self.shares.debit_shares_simple(wallet, &self.market_id, OutcomeIndex::YES, amount);
+        let _ = self.shares.debit_shares_simple(wallet, &self.market_id, OutcomeIndex::NO, amount);
+
+        if let Err(e) = self.ledger.transfer(&market_escrow(&self.market_id), wallet, amount, "redeem_shares") {
+            // Give the shares back
+            self.shares.credit_shares_simple(wallet, &self.market_id, OutcomeIndex::YES, amount);
+            self.shares.credit_shares_simple(wallet, &self.market_id, OutcomeIndex::NO, amount);
+            return Err(TradeError::Settlement(e));
+        }
+        Ok(self.shares.get_position(wallet, &self.market_id))
+    }
+
+    /// Burn the wallet's winning shares for 1 BB each. Returns the payout.
+    fn claim(&mut self, wallet: &str, winning_outcome: usize) -> Result<f64, TradeError> {
+        let position = self.shares.get_position(wallet, &self.market_id);
+        let winning_shares = if winning_outcome == 0 { position.yes_shares } else { position.no_shares };
+        if winning_shares <= 0.0 {
+            return Err(TradeError::NoWinningShares {
+                yes_shares: position.yes_shares,
+                no_shares: position.no_shares,
+            });
+        }
+
+        let outcome = OutcomeIndex::from_usize(winning_outcome);
+        self.shares.debit_shares_simple(wallet, &self.market_id, outcome, winning_shares)
+            .map_err(TradeError::Settlement)?;
+        self.ledger.credit(wallet, winning_shares);
+        Ok(winning_shares)
+    }
+
+    /// Pay out every winning share and burn the rest
+    fn resolve(&mut self, winning_outcome: usize) -> Vec<(String, f64)> {
+        let payouts = self.shares.resolve_market(
+            &self.market_id,
+            OutcomeIndex::from_usize(winning_outcome),
+            2, // Binary market
+        );
+        let mut ledger = self.ledger.write();
+        for (wallet, amount) in &payouts {
+            ledger.credit(wallet, *amount);
+        }
+        payouts
+    }
+
+    /// The market as of now. Parts the batch did not touch (`changed`) are
+    /// shared with the `previous` snapshot instead of rebuilt.
+    fn snapshot(&self, previous: Option<&MarketSnapshot>, changed: Changed) -> MarketSnapshot {
+        let market_id = &self.market_id;
+        let keep = |touched: bool| previous.filter(|_| !touched);
+
+        let (yes_book, no_book, open_orders, orderbook_stats) = match keep(changed.book) {
+            Some(p) => (p.yes_book.clone(), p.no_book.clone(), p.open_orders.clone(), p.orderbook_stats.clone()),
+            None => {
+                // Walk the resting levels rather than every order the engine has seen
+                let engine = &self.orderbook.engine;
+                let mut open_orders: Vec<LimitOrder> = engine.books.values()
+                    .flat_map(|(bids, asks)| bids.levels.values().chain(asks.levels.values()))
+                    .flat_map(|level| level.orders.iter())
+                    .filter_map(|id| engine.orders.get(id))
+                    .filter(|o| o.status.is_active())
+                    .cloned()
+                    .collect();
+                open_orders.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+                (
+                    self.orderbook.get_orderbook(market_id, Outcome::YES, SNAPSHOT_DEPTH),
+                    self.orderbook.get_orderbook(market_id, Outcome::NO, SNAPSHOT_DEPTH),
+                    Arc::new(open_orders),
+                    self.orderbook.get_stats().clone(),
+                )
+            }
+        };
+        let odds = match keep(changed.book || changed.pool) {
+            Some(p) => p.odds.clone(),
+            None => self.orderbook.get_odds(market_id),
+        };
+        let (positions, shares_stats) = match keep(changed.shares) {
+            Some(p) => (p.positions.clone(), p.shares_stats.clone()),
+            None => {
+                let mut positions: Vec<SharePosition> = self.shares.balances.values()
+                    .flat_map(|holding| holding.positions.values())
+                    .filter(|position| position.has_shares())
+                    .cloned()
+                    .collect();
+                positions.sort_by(|a, b| a.holder.cmp(&b.holder).then(a.outcome.index().cmp(&b.outcome.index())));
+                (Arc::new(positions), self.shares.get_stats().clone())
+            }
+        };
+        let pool = match keep(changed.pool) {
+            Some(p) => p.pool.clone(),
+            None => self.orderbook.cpmm_pools.get(market_id).cloned(),
+        };
+
+        MarketSnapshot {
+            market_id: market_id.clone(),
+            version: self.version,
+            odds,
+            yes_book,
+            no_book,
+            open_orders,
+            positions,
+            pool,
+            orderbook_stats,
+            shares_stats,
+        }
+    }
+}
+
+// ============================================================================
+// HANDLES
+// ============================================================================
+
+/// Sends commands to one market's actor and reads its snapshots
+#[derive(Debug, Clone)]
+pub struct MarketHandle {
+    commands: mpsc::UnboundedSender<MarketCommand>,
+    snapshots: watch::Receiver<Arc<MarketSnapshot>>,
+    history: Arc<RwLock<MarketHistory>>,
+}
+
+impl fmt::Debug for MarketCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MarketCommand::SubmitOrder { .. } => "SubmitOrder",
+            MarketCommand::CancelOrder { .. } => "CancelOrder",
+            MarketCommand::BuyCpmm { .. } => "BuyCpmm",
+            MarketCommand::Mint { .. } => "Mint",
+            MarketCommand::Redeem { .. } => "Redeem",
+            MarketCommand::Claim { .. } => "Claim",
+            MarketCommand::Resolve { .. } => "Resolve",
+            MarketCommand::InstallPool { .. } => "InstallPool",
+        };
+        f.write_str(name)
+    }
+}
+
+impl MarketHandle {
+    /// Start the actor for `market_id` on the current tokio runtime
+    pub fn spawn(market_id: &str, pool: Option<CPMMPool>, params: MarketParams, ledger: LedgerService) -> Self {
+        let actor = MarketActor::new(market_id, pool, params, ledger);
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let (publisher, snapshots) = watch::channel(Arc::new(actor.snapshot(None, Changed::default())));
+        let history = actor.history.clone();
+        tokio::spawn(actor.run(receiver, publisher));
+        Self { commands, snapshots, history }
+    }
+
+    /// Latest published snapshot
+    pub fn snapshot(&self) -> Arc<MarketSnapshot> {
+        self.snapshots.borrow().clone()
+    }
+
+    /// Every fill and order so far. Hold the guard briefly: the actor
+    /// waits on it to record the next order.
+    pub fn history(&self) -> RwLockReadGuard<'_, MarketHistory> {
+        self.history.read().unwrap()
+    }
+
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> MarketCommand) -> Result<T, TradeError> {
+        let (tx, rx) = oneshot::channel();
+        self.commands.send(command(tx)).map_err(|_| TradeError::Unavailable)?;
+        rx.await.map_err(|_| TradeError::Unavailable)
+    }
+
+    pub async fn submit_order(&self, order: LimitOrder) -> Result<OrderSubmitResult, TradeError> {
+        self.request(|reply| MarketCommand::SubmitOrder { order, reply }).await?
+    }
+
+    pub async fn cancel_order(&self, order_id: &str, wallet: &str) -> Result<LimitOrder, TradeError> {
+        self.request(|reply| MarketCommand::CancelOrder {
+            order_id: order_id.to_string(),
+            wallet: wallet.to_string(),
+            reply,
+        }).await?
+    }
+
+    pub async fn buy_cpmm(&self, outcome: usize, amount: f64) -> Result<Option<CpmmFill>, TradeError> {
+        self.request(|reply| MarketCommand::BuyCpmm { outcome, amount, reply }).await
+    }
+
+    pub async fn mint(&self, wallet: &str, amount: f64) -> Result<SimplePosition, TradeError> {
+        self.request(|reply| MarketCommand::Mint { wallet: wallet.to_string(), amount, reply }).await?
+    }
+
+    pub async fn redeem(&self, wallet: &str, amount: f64) -> Result<SimplePosition, TradeError> {
+        self.request(|reply| MarketCommand::Redeem { wallet: wallet.to_string(), amount, reply }).await?
+    }
+
+    pub async fn claim(&self, wallet: &str, winning_outcome: usize) -> Result<f64, TradeError> {
+        self.request(|reply| MarketCommand::Claim { wallet: wallet.to_string(), winning_outcome, reply }).await?
+    }
+
+    /// Pay out winning shares (credited to the ledger). Returns (wallet, payout).
+    pub async fn resolve(&self, winning_outcome: usize) -> Result<Vec<(String, f64)>, TradeError> {
+        self.request(|reply| MarketCommand::Resolve { winning_outcome, reply }).await
+    }
+
+    /// Replace the market's CPMM pool
+    pub fn install_pool(&self, pool: CPMMPool) {
+        let _ = self.commands.send(MarketCommand::InstallPool { pool });
+    }
+}
+
+// ============================================================================
+// REGISTRY
+// ============================================================================
+
+/// Every market's actor, plus reads that span markets
+#[derive(Debug, Clone)]
+pub struct MarketRegistry {
+    ledger: LedgerService,
+    config: Arc<Config>,
+    markets: Arc<RwLock<HashMap<String, MarketHandle>>>,
+}
+
+impl MarketRegistry {
+    pub fn new(ledger: LedgerService) -> Self {
+        Self::with_config(ledger, Arc::new(Config::default()))
+    }
+
+    /// Registry whose markets take their fees and limits from `config`
+    pub fn with_config(ledger: LedgerService, config: Arc<Config>) -> Self {
+        Self { ledger, config, markets: Arc::new(RwLock::new(HashMap::new())) }
+    }
+
+    /// Handle for `market_id`, starting its actor if needed. A `pool`
+    /// replaces the market's CPMM pool.
+    pub fn open(&self, market_id: &str, pool: Option<CPMMPool>) -> MarketHandle {
+        if let Some(handle) = self.get(market_id) {
+            if let Some(pool) = pool {
+                handle.install_pool(pool);
+            }
+            return handle;
+        }
+        let mut markets = self.markets.write().unwrap();
+        match markets.get(market_id) {
+            Some(handle) => {
+                if let Some(pool) = pool {
+                    handle.install_pool(pool);
+                }
+                handle.clone()
+            }
+            None => {
+                let handle = MarketHandle::spawn(
+                    market_id,
+                    pool,
+                    self.config.market_params(market_id),
+                    self.ledger.clone(),
+                );
+                markets.insert(market_id.to_string(), handle.clone());
+                handle
+            }
+        }
+    }
+
+    pub fn get(&self, market_id: &str) -> Option<MarketHandle> {
+        self.markets.read().unwrap().get(market_id).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.markets.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Latest snapshot of every market
+    pub fn snapshots(&self) -> Vec<Arc<MarketSnapshot>> {
+        self.markets.read().unwrap().values().map(|h| h.snapshot()).collect()
+    }
+
+    /// Market holding the open order `order_id`
+    pub fn find_order(&self, order_id: &str) -> Option<MarketHandle> {
+        let markets = self.markets.read().unwrap();
+        markets.values()
+            .find(|h| h.snapshot().open_orders.iter().any(|o| o.id == order_id))
+            .cloned()
+    }
+
+    /// Every order `wallet` has placed across all markets, in any status
+    pub fn user_order_history(&self, wallet: &str) -> Vec<LimitOrder> {
+        let markets = self.markets.read().unwrap();
+        markets.values()
+            .flat_map(|h| h.history().orders.values().filter(|o| o.maker == wallet).cloned().collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Every non-empty share position across all markets
+    pub fn positions(&self) -> Vec<SharePosition> {
+        self.snapshots().iter().flat_map(|s| s.positions.iter().cloned().collect::<Vec<_>>()).collect()
+    }
+
+    /// `wallet`'s non-empty share positions across all markets
+    pub fn positions_of(&self, wallet: &str) -> Vec<SharePosition> {
+        self.positions().into_iter().filter(|p| p.holder == wallet).collect()
+    }
+
+    /// Order book statistics summed over all markets
+    pub fn orderbook_stats(&self) -> OrderBookStats {
+        self.snapshots().iter().fold(OrderBookStats::default(), |mut total, s| {
+            let stats = &s.orderbook_stats;
+            total.total_orders_submitted += stats.total_orders_submitted;
+            total.total_orders_filled += stats.total_orders_filled;
+            total.total_orders_cancelled += stats.total_orders_cancelled;
+            total.total_volume_traded += stats.total_volume_traded;
+            total.total_fees_collected += stats.total_fees_collected;
+            total.markets_with_clob += stats.markets_with_clob;
+            total.markets_with_cpmm_fallback += stats.markets_with_cpmm_fallback;
+            total
+        })
+    }
+
+    /// Share statistics summed over all markets (holders counted once)
+    pub fn shares_stats(&self) -> SharesStats {
+        let snapshots = self.snapshots();
+        let holders: HashSet<&str> = snapshots.iter()
+            .flat_map(|s| s.positions.iter().map(|p| p.holder.as_str()))
+            .collect();
+        let mut total = SharesStats { unique_holders: holders.len(), ..SharesStats::default() };
+        for s in &snapshots {
+            total.total_shares_minted += s.shares_stats.total_shares_minted;
+            total.total_shares_redeemed += s.shares_stats.total_shares_redeemed;
+            total.total_bb_locked += s.shares_stats.total_bb_locked;
+            total.total_transactions += s.shares_stats.total_transactions;
+        }
+        total
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::Ledger;
+    use crate::orderbook::{OrderStatus, OrderType};
+
+    const ALICE: &str = "L2_ALICE_00000000000000";
+    const BOB: &str = "L2_BOB_0000000000000000";
+    const MARKET: &str = "market_0000000001";
+
+    fn order(wallet: &str, side: Side, price_bps: u64, size: f64) -> LimitOrder {
+        LimitOrder::new(
+            MARKET.to_string(),
+            Outcome::YES,
+            side,
+            price_bps,
+            size,
+            OrderType::GTC,
+            wallet.to_string(),
+            String::new(),
+        ).unwrap()
+    }
+
+    fn registry() -> (MarketRegistry, LedgerService) {
+        let mut ledger = Ledger::new();
+        ledger.register("alice", ALICE, 1000.0);
+        ledger.register("bob", BOB, 1000.0);
+        let ledger = LedgerService::new(ledger);
+        (MarketRegistry::new(ledger.clone()), ledger)
+    }
+
+    #[tokio::test]
+    async fn test_fill_settles_ledger_and_shares() {
+        let (registry, ledger) = registry();
+        let market = registry.open(MARKET, None);
+
+        market.mint(ALICE, 100.0).await.unwrap();
+        market.submit_order(order(ALICE, Side::Ask, 60, 50.0)).await.unwrap();
+        let result = market.submit_order(order(BOB, Side::Bid, 60, 50.0)).await.unwrap();
+
+        assert_eq!(result.fills.len(), 1);
+        let (buyer_cost, seller_receive) = fill_amounts(&result.fills[0]);
+        assert!((ledger.balance(BOB) - (1000.0 - buyer_cost)).abs() < 1e-9);
+        assert!((ledger.balance(ALICE) - (900.0 + seller_receive)).abs() < 1e-9);
+
+        // The reply is only sent once the snapshot including it is published
+        let snapshot = market.snapshot();
+        assert_eq!(snapshot.position(BOB).yes_shares, 50.0);
+        assert_eq!(snapshot.position(ALICE).yes_shares, 50.0);
+        assert_eq!(snapshot.position(ALICE).no_shares, 100.0);
+        assert_eq!(market.history().fills.len(), 1);
+        assert!(snapshot.open_orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_orders_checked_against_holdings() {
+        let (registry, _) = registry();
+        let market = registry.open(MARKET, None);
+
+        let err = market.submit_order(order(ALICE, Side::Ask, 60, 10.0)).await.unwrap_err();
+        assert_eq!(err, TradeError::InsufficientShares { available: 0.0, requested: 10.0 });
+
+        let err = market.submit_order(order(BOB, Side::Bid, 50, 5000.0)).await.unwrap_err();
+        assert!(matches!(err, TradeError::InsufficientBalance { .. }));
+
+        market.submit_order(order(BOB, Side::Bid, 40, 10.0)).await.unwrap();
+        let resting = market.snapshot().open_orders.clone();
+        assert_eq!(resting.len(), 1);
+        assert!(registry.find_order(&resting[0].id).is_some());
+        market.cancel_order(&resting[0].id, BOB).await.unwrap();
+        assert!(market.snapshot().open_orders.is_empty());
+        assert!(registry.find_order(&resting[0].id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resting_orders_escrow_until_cancelled() {
+        let (registry, ledger) = registry();
+        let market = registry.open(MARKET, None);
+        market.mint(ALICE, 100.0).await.unwrap();
+        let before = market.snapshot();
+
+        // A resting bid holds its BB (taker fee included), a resting ask its shares
+        let bid = market.submit_order(order(BOB, Side::Bid, 40, 100.0)).await.unwrap().order;
+        let held = 40.0 * (1.0 + crate::orderbook::TAKER_FEE_RATE);
+        assert!((ledger.balance(BOB) - (1000.0 - held)).abs() < 1e-9);
+        let ask = market.submit_order(order(ALICE, Side::Ask, 70, 60.0)).await.unwrap().order;
+        assert_eq!(market.snapshot().position(ALICE).yes_shares, 40.0);
+
+        // BOB cannot spend the held BB twice
+        let err = market.submit_order(order(BOB, Side::Bid, 50, 1950.0)).await.unwrap_err();
+        assert!(matches!(err, TradeError::InsufficientBalance { .. }));
+
+        market.cancel_order(&bid.id, BOB).await.unwrap();
+        market.cancel_order(&ask.id, ALICE).await.unwrap();
+        assert!((ledger.balance(BOB) - 1000.0).abs() < 1e-9);
+        assert_eq!(market.snapshot().position(ALICE).yes_shares, 100.0);
+
+        // Pool-only batches reuse the order list instead of rebuilding it
+        let snapshot = market.snapshot();
+        assert!(!Arc::ptr_eq(&before.open_orders, &snapshot.open_orders));
+        market.buy_cpmm(0, 10.0).await.unwrap();
+        assert!(Arc::ptr_eq(&snapshot.open_orders, &market.snapshot().open_orders));
+    }
+
+    #[tokio::test]
+    async fn test_settlement_failure_is_reported() {
+        let (registry, ledger) = registry();
+        let market = registry.open(MARKET, None);
+        market.mint(ALICE, 100.0).await.unwrap();
+        market.submit_order(order(BOB, Side::Bid, 60, 50.0)).await.unwrap();
+
+        // Something drained the escrow behind the resting bid
+        ledger.debit(ORDERBOOK_ESCROW, ledger.balance(ORDERBOOK_ESCROW));
+        let err = market.submit_order(order(ALICE, Side::Ask, 60, 50.0)).await.unwrap_err();
+        assert!(matches!(err, TradeError::Settlement(_)));
+    }
+
+    #[tokio::test]
+    async fn test_history_keeps_filled_and_cancelled_orders() {
+        let (registry, _) = registry();
+        let market = registry.open(MARKET, None);
+        market.mint(ALICE, 100.0).await.unwrap();
+
+        let ask = market.submit_order(order(ALICE, Side::Ask, 60, 50.0)).await.unwrap().order;
+        market.submit_order(order(BOB, Side::Bid, 60, 50.0)).await.unwrap();
+        let bid = market.submit_order(order(BOB, Side::Bid, 40, 10.0)).await.unwrap().order;
+        market.cancel_order(&bid.id, BOB).await.unwrap();
+
+        let history = market.history();
+        assert_eq!(history.fills.len(), 1);
+        assert_eq!(history.orders[&ask.id].status, OrderStatus::Filled);
+        assert_eq!(history.orders[&bid.id].status, OrderStatus::Cancelled);
+        drop(history);
+
+        let bob_orders = registry.user_order_history(BOB);
+        assert_eq!(bob_orders.len(), 2);
+        assert!(bob_orders.iter().all(|o| !o.status.is_active()));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_pays_winning_shares() {
+        let (registry, ledger) = registry();
+        let market = registry.open(MARKET, None);
+        market.mint(ALICE, 100.0).await.unwrap();
+
+        let payouts = market.resolve(0).await.unwrap();
+        assert_eq!(payouts, vec![(ALICE.to_string(), 100.0)]);
+        assert_eq!(ledger.balance(ALICE), 1000.0);
+        assert!(registry.positions_of(ALICE).is_empty());
+        assert!(matches!(market.claim(ALICE, 0).await, Err(TradeError::NoWinningShares { .. })));
+    }
+}
@@ -81,6 +81,19 @@ impl EventStatus {
             EventStatus::Refunded => "💸",
         }
     }
+
+    /// Parse the lowercase name used by `Display` (query filters)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "pending" => Some(EventStatus::Pending),
+            "provisional" => Some(EventStatus::Provisional),
+            "active" => Some(EventStatus::Active),
+            "closed" => Some(EventStatus::Closed),
+            "resolved" => Some(EventStatus::Resolved),
+            "refunded" => Some(EventStatus::Refunded),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for EventStatus {
@@ -265,6 +278,14 @@ pub struct CPMMPool {
     
     /// Outcome labels for reference
     pub outcome_labels: Vec<String>,
+    
+    /// Fee rate charged on each trade (per-market, from config)
+    #[serde(default = "default_lp_fee_rate")]
+    pub fee_rate: f64,
+}
+
+fn default_lp_fee_rate() -> f64 {
+    LP_FEE_RATE
 }
 
 impl CPMMPool {
@@ -298,9 +319,16 @@ impl CPMMPool {
             lp_shares,
             total_lp_tokens: initial_liquidity, // LP tokens = initial liquidity
             outcome_labels,
+            fee_rate: LP_FEE_RATE,
         }
     }
     
+    /// Charge `fee_rate` on trades instead of the default LP_FEE_RATE
+    pub fn with_fee_rate(mut self, fee_rate: f64) -> Self {
+        self.fee_rate = fee_rate;
+        self
+    }
+    
     /// Calculate current price for each outcome
     /// 
     /// For binary market: Price(YES) = NO_reserve / (YES_reserve + NO_reserve)
@@ -380,7 +408,7 @@ impl CPMMPool {
             let old_other = self.reserves[other_index];
             let new_other = new_other_product; // For binary, other_product = other_reserve
             let cost_before_fee = new_other - old_other;
-            let fee = cost_before_fee * LP_FEE_RATE;
+            let fee = cost_before_fee * self.fee_rate;
             let total_cost = cost_before_fee + fee;
             
             Ok((cost_before_fee, fee, total_cost))
@@ -390,7 +418,7 @@ impl CPMMPool {
             let prices = self.calculate_prices();
             let price = prices[outcome_index];
             let cost_before_fee = amount * price / (1.0 - price).max(0.01);
-            let fee = cost_before_fee * LP_FEE_RATE;
+            let fee = cost_before_fee * self.fee_rate;
             let total_cost = cost_before_fee + fee;
             
             Ok((cost_before_fee, fee, total_cost))
@@ -496,7 +524,7 @@ impl CPMMPool {
             let other_index = 1 - outcome_index;
             
             // Fee is taken from the input amount
-            let fee = bb_amount * LP_FEE_RATE;
+            let fee = bb_amount * self.fee_rate;
             let amount_after_fee = bb_amount - fee;
             
             // Current reserves
@@ -546,7 +574,7 @@ impl CPMMPool {
         } else {
             // Multi-outcome market - use approximation
             // shares ≈ amount * (1 - fee) / price
-            let fee = bb_amount * LP_FEE_RATE;
+            let fee = bb_amount * self.fee_rate;
             let amount_after_fee = bb_amount - fee;
             let shares_out = amount_after_fee / entry_price.max(0.01);
             
@@ -0,0 +1,595 @@
+// ============================================================================
+// Dispute Module - Optimistic Resolution with Bonded Challenges
+// ============================================================================
+//
+// Market resolutions are no longer final the moment an oracle submits them.
+//
+// Flow:
+//   1. Oracle/admin PROPOSES a winning outcome and posts a BB bond
+//   2. A challenge window opens (default 24h)
+//   3. Any wallet may DISPUTE during the window by posting a matching bond
+//   4a. Undisputed  → proposal FINALIZES after the window, bond returned
+//   4b. Disputed    → escalates to the admin / multi-sig oracle set, who
+//                     ARBITRATE the final outcome
+//
+// Deciding an outcome only yields a `BondSettlement`; the proposal is marked
+// final by `finalize` once the market has actually been resolved with it, so
+// a failed resolution leaves the proposal (and its bonds) open for a retry.
+//
+// Bond outcomes:
+//   - Proposer right  → proposer gets bond back + challenger's bond
+//   - Challenger right → challenger gets bond back + proposer's bond
+//     (a challenger who claimed no outcome is right whenever the proposal
+//     is overturned)
+//   - Neither right   → arbiters picked an outcome nobody claimed; both
+//                       bonds are refunded
+//
+// Payouts and `/shares/claim` stay blocked until a proposal is finalized.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Default challenge window (24 hours)
+pub const DEFAULT_CHALLENGE_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// Default bond a proposer must post (in BB)
+pub const DEFAULT_RESOLUTION_BOND: f64 = 100.0;
+
+// ============================================================================
+// PROPOSAL STATUS
+// ============================================================================
+
+/// Lifecycle of a resolution proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalStatus {
+    /// Challenge window is open
+    Proposed,
+    /// Challenged - waiting for admin / oracle arbitration
+    Disputed,
+    /// Outcome is final, payouts released
+    Finalized,
+}
+
+impl ProposalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalStatus::Proposed => "proposed",
+            ProposalStatus::Disputed => "disputed",
+            ProposalStatus::Finalized => "finalized",
+        }
+    }
+}
+
+// ============================================================================
+// PROPOSAL & DISPUTE
+// ============================================================================
+
+/// A challenge against a proposed resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionDispute {
+    /// Wallet that posted the challenge bond
+    pub challenger: String,
+    /// Bond posted by the challenger (matches the proposer's bond)
+    pub bond: f64,
+    /// Outcome the challenger believes is correct (optional)
+    pub claimed_outcome: Option<usize>,
+    /// Reason/evidence for the dispute
+    pub reason: Option<String>,
+    /// Unix timestamp of the dispute
+    pub disputed_at: u64,
+    /// Arbitration votes: arbiter address -> outcome
+    pub votes: HashMap<String, usize>,
+}
+
+/// A proposed market resolution awaiting finalization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionProposal {
+    pub market_id: String,
+    /// Outcome the proposer claims won
+    pub proposed_outcome: usize,
+    /// Oracle/admin that proposed the resolution
+    pub proposer: String,
+    /// Bond posted by the proposer
+    pub proposer_bond: f64,
+    /// Optional reason/evidence for the resolution
+    pub reason: Option<String>,
+    pub proposed_at: u64,
+    /// End of the challenge window
+    pub challenge_deadline: u64,
+    pub status: ProposalStatus,
+    /// Set once a wallet challenges the proposal
+    pub dispute: Option<ResolutionDispute>,
+    /// Final outcome (set on finalization)
+    pub final_outcome: Option<usize>,
+    pub finalized_at: Option<u64>,
+    /// "challenge_window" or the arbiter address(es)
+    pub finalized_by: Option<String>,
+}
+
+impl ResolutionProposal {
+    /// Check if the challenge window is still open
+    pub fn window_open(&self, now: u64) -> bool {
+        now <= self.challenge_deadline
+    }
+
+    /// Seconds left in the challenge window
+    pub fn time_remaining_secs(&self, now: u64) -> u64 {
+        self.challenge_deadline.saturating_sub(now)
+    }
+
+    /// Undisputed and past the window - ready to finalize
+    pub fn is_finalizable(&self, now: u64) -> bool {
+        self.status == ProposalStatus::Proposed && !self.window_open(now)
+    }
+}
+
+/// How bonds are paid out when a proposal finalizes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BondSettlement {
+    /// Final winning outcome
+    pub winning_outcome: usize,
+    /// (wallet, amount) credits to apply to the ledger
+    pub payouts: Vec<(String, f64)>,
+    /// (wallet, amount) bonds that were slashed
+    pub slashed: Vec<(String, f64)>,
+    /// Whether the original proposal was upheld
+    pub proposal_upheld: bool,
+    /// "challenge_window", the deciding admin, or the agreeing oracles
+    #[serde(default)]
+    pub decided_by: String,
+}
+
+// ============================================================================
+// DISPUTE ERROR
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DisputeError {
+    ProposalNotFound(String),
+    AlreadyProposed(String),
+    AlreadyDisputed(String),
+    AlreadyFinalized(String),
+    WindowClosed { deadline: u64 },
+    WindowOpen { remaining_secs: u64 },
+    NotDisputed(String),
+    InsufficientBond { required: f64, provided: f64 },
+    InvalidOutcome { outcome: usize, num_outcomes: usize },
+    SelfDispute,
+    /// The proposer or challenger tried to arbitrate their own dispute
+    PartyArbiter(String),
+}
+
+impl std::fmt::Display for DisputeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisputeError::ProposalNotFound(id) => write!(f, "No resolution proposal for market {}", id),
+            DisputeError::AlreadyProposed(id) => write!(f, "Market {} already has a pending resolution proposal", id),
+            DisputeError::AlreadyDisputed(id) => write!(f, "Resolution for market {} is already disputed", id),
+            DisputeError::AlreadyFinalized(id) => write!(f, "Resolution for market {} is already finalized", id),
+            DisputeError::WindowClosed { deadline } => write!(f, "Challenge window closed at {}", deadline),
+            DisputeError::WindowOpen { remaining_secs } => {
+                write!(f, "Challenge window still open ({}s remaining)", remaining_secs)
+            }
+            DisputeError::NotDisputed(id) => write!(f, "Resolution for market {} is not disputed", id),
+            DisputeError::InsufficientBond { required, provided } => {
+                write!(f, "Insufficient bond: required {} BB, provided {} BB", required, provided)
+            }
+            DisputeError::InvalidOutcome { outcome, num_outcomes } => {
+                write!(f, "Invalid outcome {}. Market has {} outcomes.", outcome, num_outcomes)
+            }
+            DisputeError::SelfDispute => write!(f, "Proposer cannot dispute their own resolution"),
+            DisputeError::PartyArbiter(arbiter) => {
+                write!(f, "{} is a party to this dispute and cannot arbitrate it", arbiter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DisputeError {}
+
+// ============================================================================
+// DISPUTE MANAGER
+// ============================================================================
+
+/// Tracks resolution proposals, disputes and bond settlement
+#[derive(Debug)]
+pub struct DisputeManager {
+    /// market_id -> proposal
+    pub proposals: HashMap<String, ResolutionProposal>,
+    /// Length of the challenge window in seconds
+    pub challenge_window_secs: u64,
+    /// Bond required to propose (disputes must match the proposer's bond)
+    pub min_bond: f64,
+}
+
+impl DisputeManager {
+    pub fn new(challenge_window_secs: u64, min_bond: f64) -> Self {
+        Self {
+            proposals: HashMap::new(),
+            challenge_window_secs,
+            min_bond,
+        }
+    }
+
+    pub fn get(&self, market_id: &str) -> Option<&ResolutionProposal> {
+        self.proposals.get(market_id)
+    }
+
+    /// True while a proposal exists that has not been finalized
+    pub fn is_pending(&self, market_id: &str) -> bool {
+        self.proposals
+            .get(market_id)
+            .map(|p| p.status != ProposalStatus::Finalized)
+            .unwrap_or(false)
+    }
+
+    /// Open a resolution proposal. Caller must have debited `bond` already.
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose(
+        &mut self,
+        market_id: &str,
+        proposed_outcome: usize,
+        num_outcomes: usize,
+        proposer: &str,
+        bond: f64,
+        reason: Option<String>,
+        now: u64,
+    ) -> Result<ResolutionProposal, DisputeError> {
+        if let Some(existing) = self.proposals.get(market_id) {
+            return Err(match existing.status {
+                ProposalStatus::Finalized => DisputeError::AlreadyFinalized(market_id.to_string()),
+                _ => DisputeError::AlreadyProposed(market_id.to_string()),
+            });
+        }
+        if proposed_outcome >= num_outcomes {
+            return Err(DisputeError::InvalidOutcome { outcome: proposed_outcome, num_outcomes });
+        }
+        if bond < self.min_bond {
+            return Err(DisputeError::InsufficientBond { required: self.min_bond, provided: bond });
+        }
+
+        let proposal = ResolutionProposal {
+            market_id: market_id.to_string(),
+            proposed_outcome,
+            proposer: proposer.to_string(),
+            proposer_bond: bond,
+            reason,
+            proposed_at: now,
+            challenge_deadline: now + self.challenge_window_secs,
+            status: ProposalStatus::Proposed,
+            dispute: None,
+            final_outcome: None,
+            finalized_at: None,
+            finalized_by: None,
+        };
+        self.proposals.insert(market_id.to_string(), proposal.clone());
+        Ok(proposal)
+    }
+
+    /// Challenge a proposal inside its window. Caller must have debited `bond` already.
+    #[allow(clippy::too_many_arguments)]
+    pub fn dispute(
+        &mut self,
+        market_id: &str,
+        challenger: &str,
+        bond: f64,
+        claimed_outcome: Option<usize>,
+        num_outcomes: usize,
+        reason: Option<String>,
+        now: u64,
+    ) -> Result<ResolutionProposal, DisputeError> {
+        let proposal = self.proposals.get_mut(market_id)
+            .ok_or_else(|| DisputeError::ProposalNotFound(market_id.to_string()))?;
+
+        match proposal.status {
+            ProposalStatus::Disputed => return Err(DisputeError::AlreadyDisputed(market_id.to_string())),
+            ProposalStatus::Finalized => return Err(DisputeError::AlreadyFinalized(market_id.to_string())),
+            ProposalStatus::Proposed => {}
+        }
+        if !proposal.window_open(now) {
+            return Err(DisputeError::WindowClosed { deadline: proposal.challenge_deadline });
+        }
+        if challenger == proposal.proposer {
+            return Err(DisputeError::SelfDispute);
+        }
+        if bond < proposal.proposer_bond {
+            return Err(DisputeError::InsufficientBond { required: proposal.proposer_bond, provided: bond });
+        }
+        if let Some(outcome) = claimed_outcome {
+            if outcome >= num_outcomes {
+                return Err(DisputeError::InvalidOutcome { outcome, num_outcomes });
+            }
+        }
+
+        proposal.status = ProposalStatus::Disputed;
+        proposal.dispute = Some(ResolutionDispute {
+            challenger: challenger.to_string(),
+            bond,
+            claimed_outcome,
+            reason,
+            disputed_at: now,
+            votes: HashMap::new(),
+        });
+        Ok(proposal.clone())
+    }
+
+    /// Markets whose undisputed proposal has passed its challenge window
+    pub fn due_for_finalization(&self, now: u64) -> Vec<String> {
+        self.proposals.values()
+            .filter(|p| p.is_finalizable(now))
+            .map(|p| p.market_id.clone())
+            .collect()
+    }
+
+    /// Settlement of an undisputed proposal whose window has closed: the
+    /// proposer's bond back. `finalize` it once the market is resolved.
+    pub fn undisputed_settlement(&self, market_id: &str, now: u64) -> Result<BondSettlement, DisputeError> {
+        let proposal = self.proposals.get(market_id)
+            .ok_or_else(|| DisputeError::ProposalNotFound(market_id.to_string()))?;
+
+        match proposal.status {
+            ProposalStatus::Disputed => return Err(DisputeError::AlreadyDisputed(market_id.to_string())),
+            ProposalStatus::Finalized => return Err(DisputeError::AlreadyFinalized(market_id.to_string())),
+            ProposalStatus::Proposed => {}
+        }
+        if proposal.window_open(now) {
+            return Err(DisputeError::WindowOpen { remaining_secs: proposal.time_remaining_secs(now) });
+        }
+
+        Ok(BondSettlement {
+            winning_outcome: proposal.proposed_outcome,
+            payouts: vec![(proposal.proposer.clone(), proposal.proposer_bond)],
+            slashed: Vec::new(),
+            proposal_upheld: true,
+            decided_by: "challenge_window".to_string(),
+        })
+    }
+
+    /// Mark the proposal final with `settlement`'s outcome. Fails if it
+    /// already is, so a settlement is applied at most once.
+    pub fn finalize(&mut self, market_id: &str, settlement: &BondSettlement, now: u64) -> Result<(), DisputeError> {
+        let proposal = self.proposals.get_mut(market_id)
+            .ok_or_else(|| DisputeError::ProposalNotFound(market_id.to_string()))?;
+        if proposal.status == ProposalStatus::Finalized {
+            return Err(DisputeError::AlreadyFinalized(market_id.to_string()));
+        }
+        proposal.status = ProposalStatus::Finalized;
+        proposal.final_outcome = Some(settlement.winning_outcome);
+        proposal.finalized_at = Some(now);
+        proposal.finalized_by = Some(settlement.decided_by.clone());
+        Ok(())
+    }
+
+    /// Record an arbitration vote on a disputed proposal.
+    ///
+    /// `is_admin` votes decide immediately; oracle votes decide once
+    /// `threshold` oracles agree on the same outcome. Returns `Some` when the
+    /// dispute is settled, `None` while more votes are needed. The proposal
+    /// stays disputed until the settlement is passed to `finalize`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn arbitrate(
+        &mut self,
+        market_id: &str,
+        arbiter: &str,
+        outcome: usize,
+        num_outcomes: usize,
+        is_admin: bool,
+        threshold: u8,
+    ) -> Result<Option<BondSettlement>, DisputeError> {
+        if outcome >= num_outcomes {
+            return Err(DisputeError::InvalidOutcome { outcome, num_outcomes });
+        }
+
+        let proposal = self.proposals.get_mut(market_id)
+            .ok_or_else(|| DisputeError::ProposalNotFound(market_id.to_string()))?;
+
+        match proposal.status {
+            ProposalStatus::Proposed => return Err(DisputeError::NotDisputed(market_id.to_string())),
+            ProposalStatus::Finalized => return Err(DisputeError::AlreadyFinalized(market_id.to_string())),
+            ProposalStatus::Disputed => {}
+        }
+
+        let dispute = proposal.dispute.as_mut()
+            .ok_or_else(|| DisputeError::NotDisputed(market_id.to_string()))?;
+        if arbiter.eq_ignore_ascii_case(&proposal.proposer) || arbiter.eq_ignore_ascii_case(&dispute.challenger) {
+            return Err(DisputeError::PartyArbiter(arbiter.to_string()));
+        }
+        dispute.votes.insert(arbiter.to_string(), outcome);
+
+        let agreeing: Vec<String> = dispute.votes.iter()
+            .filter(|(_, &o)| o == outcome)
+            .map(|(addr, _)| addr.clone())
+            .collect();
+
+        if !is_admin && agreeing.len() < threshold.max(1) as usize {
+            return Ok(None);
+        }
+
+        let challenger = dispute.challenger.clone();
+        let challenger_bond = dispute.bond;
+        let upheld = outcome == proposal.proposed_outcome;
+        let challenger_right = dispute.claimed_outcome.is_none_or(|claimed| claimed == outcome);
+        let decided_by = if is_admin { arbiter.to_string() } else { agreeing.join(",") };
+
+        let settlement = if upheld {
+            BondSettlement {
+                winning_outcome: outcome,
+                payouts: vec![(proposal.proposer.clone(), proposal.proposer_bond + challenger_bond)],
+                slashed: vec![(challenger, challenger_bond)],
+                proposal_upheld: true,
+                decided_by,
+            }
+        } else if challenger_right {
+            BondSettlement {
+                winning_outcome: outcome,
+                payouts: vec![(challenger, challenger_bond + proposal.proposer_bond)],
+                slashed: vec![(proposal.proposer.clone(), proposal.proposer_bond)],
+                proposal_upheld: false,
+                decided_by,
+            }
+        } else {
+            // Both parties were wrong: neither earns the other's bond
+            BondSettlement {
+                winning_outcome: outcome,
+                payouts: vec![
+                    (proposal.proposer.clone(), proposal.proposer_bond),
+                    (challenger, challenger_bond),
+                ],
+                slashed: Vec::new(),
+                proposal_upheld: false,
+                decided_by,
+            }
+        };
+
+        Ok(Some(settlement))
+    }
+
+    /// All proposals that are not yet finalized
+    pub fn list_open(&self) -> Vec<&ResolutionProposal> {
+        self.proposals.values()
+            .filter(|p| p.status != ProposalStatus::Finalized)
+            .collect()
+    }
+}
+
+impl Default for DisputeManager {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHALLENGE_WINDOW_SECS, DEFAULT_RESOLUTION_BOND)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undisputed_finalizes_after_window() {
+        let mut manager = DisputeManager::new(100, 50.0);
+        manager.propose("m1", 0, 2, "oracle", 50.0, None, 1_000).unwrap();
+        assert!(manager.is_pending("m1"));
+
+        // Too early
+        assert!(matches!(
+            manager.undisputed_settlement("m1", 1_050),
+            Err(DisputeError::WindowOpen { .. })
+        ));
+        assert!(manager.due_for_finalization(1_050).is_empty());
+
+        // After window
+        assert_eq!(manager.due_for_finalization(1_101), vec!["m1".to_string()]);
+        let settlement = manager.undisputed_settlement("m1", 1_101).unwrap();
+        assert_eq!(settlement.winning_outcome, 0);
+        assert_eq!(settlement.payouts, vec![("oracle".to_string(), 50.0)]);
+        // Still open until the resolution is applied
+        assert!(manager.is_pending("m1"));
+
+        manager.finalize("m1", &settlement, 1_101).unwrap();
+        assert!(!manager.is_pending("m1"));
+        assert_eq!(manager.get("m1").unwrap().finalized_by.as_deref(), Some("challenge_window"));
+        assert!(matches!(
+            manager.finalize("m1", &settlement, 1_102),
+            Err(DisputeError::AlreadyFinalized(_))
+        ));
+    }
+
+    #[test]
+    fn test_dispute_requires_matching_bond_inside_window() {
+        let mut manager = DisputeManager::new(100, 50.0);
+        manager.propose("m1", 0, 2, "oracle", 75.0, None, 1_000).unwrap();
+
+        assert!(matches!(
+            manager.dispute("m1", "bob", 50.0, Some(1), 2, None, 1_010),
+            Err(DisputeError::InsufficientBond { .. })
+        ));
+        assert!(matches!(
+            manager.dispute("m1", "oracle", 75.0, Some(1), 2, None, 1_010),
+            Err(DisputeError::SelfDispute)
+        ));
+        assert!(matches!(
+            manager.dispute("m1", "bob", 75.0, Some(1), 2, None, 1_200),
+            Err(DisputeError::WindowClosed { .. })
+        ));
+
+        let proposal = manager.dispute("m1", "bob", 75.0, Some(1), 2, None, 1_010).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Disputed);
+        // Disputed proposals never auto-finalize
+        assert!(manager.due_for_finalization(5_000).is_empty());
+    }
+
+    #[test]
+    fn test_admin_overturns_and_slashes_proposer() {
+        let mut manager = DisputeManager::new(100, 50.0);
+        manager.propose("m1", 0, 2, "oracle", 50.0, None, 1_000).unwrap();
+        manager.dispute("m1", "bob", 50.0, Some(1), 2, None, 1_010).unwrap();
+
+        let settlement = manager.arbitrate("m1", "admin", 1, 2, true, 0).unwrap().unwrap();
+        assert!(!settlement.proposal_upheld);
+        assert_eq!(settlement.payouts, vec![("bob".to_string(), 100.0)]);
+        assert_eq!(settlement.slashed, vec![("oracle".to_string(), 50.0)]);
+        assert_eq!(manager.get("m1").unwrap().status, ProposalStatus::Disputed);
+        manager.finalize("m1", &settlement, 1_020).unwrap();
+        assert_eq!(manager.get("m1").unwrap().final_outcome, Some(1));
+        assert_eq!(manager.get("m1").unwrap().finalized_by.as_deref(), Some("admin"));
+    }
+
+    #[test]
+    fn test_third_outcome_refunds_both_bonds() {
+        let mut manager = DisputeManager::new(100, 50.0);
+        manager.propose("m1", 0, 3, "oracle", 50.0, None, 1_000).unwrap();
+        manager.dispute("m1", "bob", 50.0, Some(1), 3, None, 1_010).unwrap();
+
+        let settlement = manager.arbitrate("m1", "admin", 2, 3, true, 0).unwrap().unwrap();
+        assert!(!settlement.proposal_upheld);
+        assert_eq!(settlement.winning_outcome, 2);
+        assert_eq!(settlement.payouts, vec![
+            ("oracle".to_string(), 50.0),
+            ("bob".to_string(), 50.0),
+        ]);
+        assert!(settlement.slashed.is_empty());
+    }
+
+    #[test]
+    fn test_oracle_multisig_upholds_proposal() {
+        let mut manager = DisputeManager::new(100, 50.0);
+        manager.propose("m1", 0, 2, "oracle_a", 50.0, None, 1_000).unwrap();
+        manager.dispute("m1", "bob", 50.0, None, 2, None, 1_010).unwrap();
+
+        // First oracle vote is not enough with threshold 2
+        assert!(manager.arbitrate("m1", "oracle_b", 0, 2, false, 2).unwrap().is_none());
+        let settlement = manager.arbitrate("m1", "oracle_c", 0, 2, false, 2).unwrap().unwrap();
+
+        assert!(settlement.proposal_upheld);
+        assert_eq!(settlement.payouts, vec![("oracle_a".to_string(), 100.0)]);
+        assert_eq!(settlement.slashed, vec![("bob".to_string(), 50.0)]);
+    }
+
+    #[test]
+    fn test_parties_cannot_arbitrate() {
+        let mut manager = DisputeManager::new(100, 50.0);
+        manager.propose("m1", 0, 2, "L1_ORACLE", 50.0, None, 1_000).unwrap();
+        manager.dispute("m1", "L1_BOB", 50.0, Some(1), 2, None, 1_010).unwrap();
+
+        // Not even as admin, and not by changing the address case
+        assert!(matches!(
+            manager.arbitrate("m1", "L1_ORACLE", 0, 2, true, 1),
+            Err(DisputeError::PartyArbiter(_))
+        ));
+        assert!(matches!(
+            manager.arbitrate("m1", "l1_bob", 1, 2, true, 1),
+            Err(DisputeError::PartyArbiter(_))
+        ));
+        assert!(manager.get("m1").unwrap().dispute.as_ref().unwrap().votes.is_empty());
+
+        assert!(manager.arbitrate("m1", "L1_CAROL", 1, 2, true, 1).unwrap().is_some());
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use super::markets::{MarketManager, Bet};
 use super::escrow::EscrowManager;
 use crate::easteregg::GodMode;
+use crate::audit_log::{AuditEvent, AuditLog};
 
 /// Minimal blockchain ledger for BlackBook prediction market
 /// Each account is a real wallet with persistent balance
@@ -25,6 +26,9 @@ pub struct Ledger {
 
     /// Escrow manager for locking funds during active bets
     pub escrow_manager: EscrowManager,
+
+    /// Where admin mints and balance overrides are recorded
+    pub audit_log: Option<AuditLog>,
 }
 
 /// Simple transaction record (stores addresses in `from` and `to`)
@@ -100,6 +104,7 @@ impl Ledger {
             recipes: Vec::new(),
             market_manager: MarketManager::new(),
             escrow_manager: EscrowManager::new(),
+            audit_log: None,
         }
     }
 
@@ -613,6 +618,13 @@ impl Ledger {
         let current_balance = self.balances.get(&account_address).copied().unwrap_or(0.0);
         let new_balance = current_balance + amount;
 
+        self.audit_admin_action(
+            "ADMIN_MINT",
+            &format!("Minted {} BB to {}", amount, account_address),
+            &account_address,
+            current_balance,
+            new_balance,
+        )?;
         self.balances.insert(account_address.clone(), new_balance);
 
         // Record transaction
@@ -662,6 +674,13 @@ impl Ledger {
         let old_balance = self.balances.get(&account_address).copied().unwrap_or(0.0);
         let diff = new_balance - old_balance;
 
+        self.audit_admin_action(
+            "ADMIN_SET_BALANCE",
+            &format!("Set balance of {} to {} BB", account_address, new_balance),
+            &account_address,
+            old_balance,
+            new_balance,
+        )?;
         self.balances.insert(account_address.clone(), new_balance);
 
         // Record transaction
@@ -699,4 +718,25 @@ impl Ledger {
             account_name, account_address, new_balance, old_balance
         ))
     }
+
+    /// Record an admin balance change before it is applied; fails if it
+    /// cannot be written, so no admin change goes unrecorded
+    fn audit_admin_action(&self, action: &str, details: &str, address: &str, before: f64, after: f64) -> Result<(), String> {
+        let Some(log) = &self.audit_log else {
+            return Ok(());
+        };
+        let event = AuditEvent {
+            actor: "ADMIN".to_string(),
+            action: action.to_string(),
+            details: details.to_string(),
+            request: None,
+            before: serde_json::json!({ "address": address, "balance": before }),
+            after: serde_json::json!({ "address": address, "balance": after }),
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        log.append(event, now).map(|_| ())
+    }
 }
\ No newline at end of file
@@ -7,6 +7,8 @@
 //   - ledger: Transaction ledger and accounting
 //   - escrow: Funds locking and release for bets
 //   - markets: Market creation, betting, and resolution
+//   - dispute: Bonded challenge window before resolutions finalize
+//   - rules: Typed resolution-rules DSL evaluated against oracle data
 //
 // ============================================================================
 
@@ -14,8 +16,12 @@ pub mod cpmm;
 pub mod ledger;
 pub mod escrow;
 pub mod markets;
+pub mod dispute;
+pub mod rules;
 
 pub use cpmm::*;
 pub use ledger::*;
 pub use escrow::*;
 pub use markets::*;
+pub use dispute::*;
+pub use rules::*;
@@ -0,0 +1,589 @@
+// ============================================================================
+// Resolution Rules DSL - Typed outcome conditions over oracle data
+// ============================================================================
+//
+// Each market outcome maps to a boolean expression over named data points:
+//
+//   "Yes": "BTC >= 100000 AND date <= 2025-12-31"
+//   "No":  "otherwise"
+//
+// Grammar:
+//   expr       := or
+//   or         := and ( OR and )*
+//   and        := unary ( AND unary )*
+//   unary      := NOT unary | "(" expr ")" | TRUE | OTHERWISE | comparison
+//   comparison := ident op value | ident BETWEEN value AND value
+//   op         := > | >= | < | <= | == | !=
+//   value      := number ($ and , allowed) | date (YYYY-MM-DD or RFC 3339)
+//
+// `date` / `now` refer to the evaluation timestamp. Identifiers are
+// case-insensitive. `otherwise` only matches when no other outcome does.
+// Sources are capped at MAX_RULE_LEN bytes and MAX_RULE_DEPTH nesting levels.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ============================================================================
+// AST
+// ============================================================================
+
+/// Comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CmpOp {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+    Ne,
+}
+
+impl CmpOp {
+    fn apply(&self, left: f64, right: f64) -> bool {
+        match self {
+            CmpOp::Gt => left > right,
+            CmpOp::Gte => left >= right,
+            CmpOp::Lt => left < right,
+            CmpOp::Lte => left <= right,
+            CmpOp::Eq => (left - right).abs() < f64::EPSILON,
+            CmpOp::Ne => (left - right).abs() >= f64::EPSILON,
+        }
+    }
+}
+
+/// Parsed rule expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RuleExpr {
+    /// Always true
+    True,
+    /// Fallback - true only if no other outcome matched
+    Otherwise,
+    Not(Box<RuleExpr>),
+    And(Box<RuleExpr>, Box<RuleExpr>),
+    Or(Box<RuleExpr>, Box<RuleExpr>),
+    Compare { var: String, op: CmpOp, value: f64 },
+    Between { var: String, min: f64, max: f64 },
+}
+
+/// Names of the built-in evaluation-time variables
+const TIME_VARS: [&str; 2] = ["DATE", "NOW"];
+
+/// Longest accepted rule source, in bytes
+pub const MAX_RULE_LEN: usize = 4096;
+
+/// Deepest accepted nesting of `(` and `NOT`
+pub const MAX_RULE_DEPTH: usize = 32;
+
+fn is_time_var(var: &str) -> bool {
+    TIME_VARS.contains(&var)
+}
+
+impl RuleExpr {
+    /// Parse an expression from source text
+    pub fn parse(source: &str) -> Result<Self, String> {
+        if source.len() > MAX_RULE_LEN {
+            return Err(format!("expression longer than {} bytes", MAX_RULE_LEN));
+        }
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Err("empty expression".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0, depth: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(tok) = parser.peek() {
+            return Err(format!("unexpected token {:?}", tok));
+        }
+        Ok(expr)
+    }
+
+    /// Evaluate against data points (keys upper-cased) at time `now`
+    pub fn eval(&self, data: &HashMap<String, f64>, now: u64) -> Result<bool, RuleError> {
+        let lookup = |var: &str| -> Result<f64, RuleError> {
+            if is_time_var(var) {
+                return Ok(now as f64);
+            }
+            data.get(var).copied().ok_or_else(|| RuleError::MissingData(var.to_string()))
+        };
+
+        Ok(match self {
+            RuleExpr::True => true,
+            RuleExpr::Otherwise => false,
+            RuleExpr::Not(inner) => !inner.eval(data, now)?,
+            RuleExpr::And(a, b) => a.eval(data, now)? && b.eval(data, now)?,
+            RuleExpr::Or(a, b) => a.eval(data, now)? || b.eval(data, now)?,
+            RuleExpr::Compare { var, op, value } => op.apply(lookup(var)?, *value),
+            RuleExpr::Between { var, min, max } => {
+                let v = lookup(var)?;
+                v >= *min && v <= *max
+            }
+        })
+    }
+
+    /// Data point names referenced by this expression (excluding `date` / `now`)
+    pub fn variables(&self, out: &mut Vec<String>) {
+        match self {
+            RuleExpr::True | RuleExpr::Otherwise => {}
+            RuleExpr::Not(inner) => inner.variables(out),
+            RuleExpr::And(a, b) | RuleExpr::Or(a, b) => {
+                a.variables(out);
+                b.variables(out);
+            }
+            RuleExpr::Compare { var, .. } | RuleExpr::Between { var, .. } => {
+                if !is_time_var(var) && !out.contains(var) {
+                    out.push(var.clone());
+                }
+            }
+        }
+    }
+
+    fn is_otherwise(&self) -> bool {
+        matches!(self, RuleExpr::Otherwise)
+    }
+}
+
+// ============================================================================
+// TOKENIZER
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Op(CmpOp),
+    And,
+    Or,
+    Not,
+    Between,
+    True,
+    Otherwise,
+    LParen,
+    RParen,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::LParen } else { Token::RParen });
+            i += 1;
+            continue;
+        }
+        if matches!(c, '<' | '>' | '=' | '!') {
+            let next = chars.get(i + 1).copied();
+            let (op, len) = match (c, next) {
+                ('>', Some('=')) => (CmpOp::Gte, 2),
+                ('<', Some('=')) => (CmpOp::Lte, 2),
+                ('=', Some('=')) => (CmpOp::Eq, 2),
+                ('!', Some('=')) => (CmpOp::Ne, 2),
+                ('>', _) => (CmpOp::Gt, 1),
+                ('<', _) => (CmpOp::Lt, 1),
+                ('=', _) => (CmpOp::Eq, 1),
+                _ => return Err(format!("unexpected character '{}'", c)),
+            };
+            tokens.push(Token::Op(op));
+            i += len;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len()
+            && !chars[i].is_whitespace()
+            && !matches!(chars[i], '(' | ')' | '<' | '>' | '=' | '!')
+        {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        tokens.push(classify_word(&word)?);
+    }
+
+    Ok(tokens)
+}
+
+fn classify_word(word: &str) -> Result<Token, String> {
+    match word.to_uppercase().as_str() {
+        "AND" | "&&" => return Ok(Token::And),
+        "OR" | "||" => return Ok(Token::Or),
+        "NOT" => return Ok(Token::Not),
+        "BETWEEN" => return Ok(Token::Between),
+        "TRUE" => return Ok(Token::True),
+        "OTHERWISE" | "ELSE" => return Ok(Token::Otherwise),
+        _ => {}
+    }
+
+    if let Some(ts) = parse_date(word) {
+        return Ok(Token::Number(ts as f64));
+    }
+
+    let numeric = word.trim_start_matches('$').replace([',', '_'], "");
+    if let Ok(n) = numeric.parse::<f64>() {
+        if n.is_finite() {
+            return Ok(Token::Number(n));
+        }
+    }
+
+    let first = word.chars().next().unwrap_or(' ');
+    if (first.is_ascii_alphabetic() || first == '_')
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+    {
+        return Ok(Token::Ident(word.to_uppercase()));
+    }
+
+    Err(format!("invalid token '{}'", word))
+}
+
+/// Parse YYYY-MM-DD (start of day UTC) or RFC 3339 into a unix timestamp
+fn parse_date(word: &str) -> Option<u64> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(word) {
+        return Some(dt.timestamp().max(0) as u64);
+    }
+    chrono::NaiveDate::parse_from_str(word, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc().timestamp().max(0) as u64)
+}
+
+// ============================================================================
+// PARSER
+// ============================================================================
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Current `(` / `NOT` nesting level
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_or(&mut self) -> Result<RuleExpr, String> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = RuleExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<RuleExpr, String> {
+        let mut left = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            left = RuleExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_RULE_DEPTH {
+            return Err(format!("expression nested deeper than {} levels", MAX_RULE_DEPTH));
+        }
+        Ok(())
+    }
+
+    fn parse_unary(&mut self) -> Result<RuleExpr, String> {
+        match self.next() {
+            Some(Token::Not) => {
+                self.enter()?;
+                let inner = self.parse_unary()?;
+                self.depth -= 1;
+                Ok(RuleExpr::Not(Box::new(inner)))
+            }
+            Some(Token::LParen) => {
+                self.enter()?;
+                let expr = self.parse_or()?;
+                self.depth -= 1;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("missing closing ')'".to_string()),
+                }
+            }
+            Some(Token::True) => Ok(RuleExpr::True),
+            Some(Token::Otherwise) => Ok(RuleExpr::Otherwise),
+            Some(Token::Ident(var)) => self.parse_comparison(var),
+            Some(tok) => Err(format!("unexpected token {:?}", tok)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+
+    fn parse_comparison(&mut self, var: String) -> Result<RuleExpr, String> {
+        match self.next() {
+            Some(Token::Op(op)) => {
+                let value = self.parse_value()?;
+                Ok(RuleExpr::Compare { var, op, value })
+            }
+            Some(Token::Between) => {
+                let min = self.parse_value()?;
+                if self.next() != Some(Token::And) {
+                    return Err(format!("expected AND in BETWEEN for '{}'", var));
+                }
+                let max = self.parse_value()?;
+                if min > max {
+                    return Err(format!("BETWEEN range for '{}' is empty ({} > {})", var, min, max));
+                }
+                Ok(RuleExpr::Between { var, min, max })
+            }
+            _ => Err(format!("expected comparison after '{}'", var)),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(n),
+            Some(tok) => Err(format!("expected number or date, got {:?}", tok)),
+            None => Err("expected number or date".to_string()),
+        }
+    }
+}
+
+// ============================================================================
+// COMPILED RULES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RuleError {
+    /// No outcome rules were supplied
+    Empty,
+    /// Rule references an outcome the market does not have
+    UnknownOutcome(String),
+    /// Expression failed to parse
+    Parse { outcome: String, message: String },
+    /// Data point needed for evaluation is missing
+    MissingData(String),
+    /// No outcome condition was satisfied
+    NoOutcomeMatched,
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleError::Empty => write!(f, "No outcome rules provided"),
+            RuleError::UnknownOutcome(o) => write!(f, "Rule references unknown outcome '{}'", o),
+            RuleError::Parse { outcome, message } => {
+                write!(f, "Invalid rule for outcome '{}': {}", outcome, message)
+            }
+            RuleError::MissingData(name) => write!(f, "Missing data point '{}'", name),
+            RuleError::NoOutcomeMatched => write!(f, "No outcome condition was satisfied"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// A parsed rule for one outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutcomeRule {
+    pub outcome: usize,
+    pub label: String,
+    pub source: String,
+    pub expr: RuleExpr,
+}
+
+/// Result of evaluating a market's rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleEvaluation {
+    /// Outcome indices whose condition held
+    pub matched: Vec<usize>,
+    /// Single winner if exactly one outcome matched
+    pub winning_outcome: Option<usize>,
+    /// Payout fraction per outcome (split evenly across matches)
+    pub payouts: Vec<f64>,
+}
+
+/// Validated outcome rules for a market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledRules {
+    pub rules: Vec<OutcomeRule>,
+    pub num_outcomes: usize,
+}
+
+impl CompiledRules {
+    /// Parse and validate `outcome label -> expression` against market outcomes
+    pub fn compile(rules: &HashMap<String, String>, outcomes: &[String]) -> Result<Self, RuleError> {
+        if rules.is_empty() {
+            return Err(RuleError::Empty);
+        }
+
+        let mut compiled = Vec::with_capacity(rules.len());
+        for (label, source) in rules {
+            let outcome = outcomes.iter()
+                .position(|o| o.eq_ignore_ascii_case(label))
+                .ok_or_else(|| RuleError::UnknownOutcome(label.clone()))?;
+            let expr = RuleExpr::parse(source).map_err(|message| RuleError::Parse {
+                outcome: label.clone(),
+                message,
+            })?;
+            compiled.push(OutcomeRule {
+                outcome,
+                label: outcomes[outcome].clone(),
+                source: source.clone(),
+                expr,
+            });
+        }
+        compiled.sort_by_key(|r| r.outcome);
+
+        Ok(Self { rules: compiled, num_outcomes: outcomes.len() })
+    }
+
+    /// Data points the rules need (e.g. asset symbols)
+    pub fn data_points(&self) -> Vec<String> {
+        let mut vars = Vec::new();
+        for rule in &self.rules {
+            rule.expr.variables(&mut vars);
+        }
+        vars.sort();
+        vars
+    }
+
+    /// Evaluate the rules to a winning outcome / payout vector
+    pub fn evaluate(&self, data: &HashMap<String, f64>, now: u64) -> Result<RuleEvaluation, RuleError> {
+        let data: HashMap<String, f64> = data.iter()
+            .map(|(k, v)| (k.to_uppercase(), *v))
+            .collect();
+
+        let mut matched = Vec::new();
+        for rule in self.rules.iter().filter(|r| !r.expr.is_otherwise()) {
+            if rule.expr.eval(&data, now)? {
+                matched.push(rule.outcome);
+            }
+        }
+        if matched.is_empty() {
+            matched = self.rules.iter()
+                .filter(|r| r.expr.is_otherwise())
+                .map(|r| r.outcome)
+                .collect();
+        }
+        if matched.is_empty() {
+            return Err(RuleError::NoOutcomeMatched);
+        }
+
+        let share = 1.0 / matched.len() as f64;
+        let mut payouts = vec![0.0; self.num_outcomes];
+        for &i in &matched {
+            payouts[i] = share;
+        }
+
+        Ok(RuleEvaluation {
+            winning_outcome: if matched.len() == 1 { Some(matched[0]) } else { None },
+            matched,
+            payouts,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes() -> Vec<String> {
+        vec!["Yes".to_string(), "No".to_string()]
+    }
+
+    fn rules(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_parse_precedence_and_between() {
+        let expr = RuleExpr::parse("btc > 1 OR eth >= 2 AND NOT sol BETWEEN 10 AND 20").unwrap();
+        match expr {
+            RuleExpr::Or(_, right) => assert!(matches!(*right, RuleExpr::And(_, _))),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(RuleExpr::parse("btc >").is_err());
+        assert!(RuleExpr::parse("(btc > 1").is_err());
+        assert!(RuleExpr::parse("btc BETWEEN 5 AND 1").is_err());
+        assert!(RuleExpr::parse("").is_err());
+    }
+
+    #[test]
+    fn test_compile_rejects_unknown_outcome() {
+        let err = CompiledRules::compile(&rules(&[("Maybe", "btc > 1")]), &outcomes()).unwrap_err();
+        assert!(matches!(err, RuleError::UnknownOutcome(_)));
+        let err = CompiledRules::compile(&rules(&[("Yes", "btc >>> 1")]), &outcomes()).unwrap_err();
+        assert!(matches!(err, RuleError::Parse { .. }));
+    }
+
+    #[test]
+    fn test_evaluate_with_otherwise_and_dates() {
+        let compiled = CompiledRules::compile(
+            &rules(&[("Yes", "BTC >= $100,000 AND date <= 2025-12-31"), ("No", "otherwise")]),
+            &outcomes(),
+        ).unwrap();
+        assert_eq!(compiled.data_points(), vec!["BTC".to_string()]);
+
+        let jan_2025 = 1_735_689_600; // 2025-01-01
+        let mut data = HashMap::new();
+        data.insert("btc".to_string(), 105_000.0);
+        let eval = compiled.evaluate(&data, jan_2025).unwrap();
+        assert_eq!(eval.winning_outcome, Some(0));
+        assert_eq!(eval.payouts, vec![1.0, 0.0]);
+
+        data.insert("btc".to_string(), 90_000.0);
+        let eval = compiled.evaluate(&data, jan_2025).unwrap();
+        assert_eq!(eval.winning_outcome, Some(1));
+
+        assert!(matches!(
+            compiled.evaluate(&HashMap::new(), jan_2025),
+            Err(RuleError::MissingData(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_deep_nesting_and_long_sources() {
+        let nested = format!("{}x > 1{}", "(".repeat(50_000), ")".repeat(50_000));
+        assert!(RuleExpr::parse(&nested).unwrap_err().contains("longer than"));
+        let nots = format!("{}x > 1", "NOT ".repeat(MAX_RULE_DEPTH + 1));
+        assert!(RuleExpr::parse(&nots).unwrap_err().contains("nested deeper"));
+        let parens = format!("{}x > 1{}", "(".repeat(MAX_RULE_DEPTH), ")".repeat(MAX_RULE_DEPTH));
+        assert!(RuleExpr::parse(&parens).is_ok());
+    }
+
+    #[test]
+    fn test_now_is_evaluation_time() {
+        let compiled = CompiledRules::compile(
+            &rules(&[("Yes", "now >= 2025-01-01"), ("No", "otherwise")]),
+            &outcomes(),
+        ).unwrap();
+        assert!(compiled.data_points().is_empty());
+        let eval = compiled.evaluate(&HashMap::new(), 1_735_689_600).unwrap();
+        assert_eq!(eval.winning_outcome, Some(0));
+    }
+
+    #[test]
+    fn test_multiple_matches_split_payouts() {
+        let compiled = CompiledRules::compile(
+            &rules(&[("Yes", "x > 1"), ("No", "x < 10")]),
+            &outcomes(),
+        ).unwrap();
+        let mut data = HashMap::new();
+        data.insert("X".to_string(), 5.0);
+        let eval = compiled.evaluate(&data, 0).unwrap();
+        assert_eq!(eval.winning_outcome, None);
+        assert_eq!(eval.payouts, vec![0.5, 0.5]);
+    }
+}
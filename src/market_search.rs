@@ -0,0 +1,447 @@
+//! Market Search
+//!
+//! In-process index over market titles, descriptions and tags. Each market is
+//! a [`MarketDoc`] (the fields search and ranking need) whose words go into
+//! an inverted index weighted by field. The index is updated one market at a
+//! time as markets are created, traded and resolved; trades also feed a
+//! rolling volume window that drives the trending sort.
+//!
+//! Queries match every word (the last one as a prefix, for type-ahead), then
+//! filter by category, tag, status and close date.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::market_resolve::cpmm::EventStatus;
+use crate::pagination::SortOrder;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Window for trending: this many seconds of volume against the one before
+pub const TRENDING_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// Word weights by field
+const TITLE_WEIGHT: f64 = 3.0;
+const TAG_WEIGHT: f64 = 2.0;
+const DESCRIPTION_WEIGHT: f64 = 1.0;
+
+/// A prefix match scores this fraction of a whole-word match
+const PREFIX_FACTOR: f64 = 0.5;
+
+const STOP_WORDS: &[&str] = &["a", "an", "and", "at", "be", "by", "for", "in", "is", "of", "on", "or", "the", "to", "will"];
+
+// ============================================================================
+// DOCUMENTS & QUERIES
+// ============================================================================
+
+/// What the index keeps about a market
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketDoc {
+    pub market_id: String,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub status: EventStatus,
+    pub created_at: u64,
+    /// When betting closes, if known
+    pub closes_at: Option<u64>,
+    /// BB in the market's CPMM pool
+    pub liquidity: f64,
+    pub total_volume: f64,
+    pub unique_bettors: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchSort {
+    /// Best text match first (newest when there is no text)
+    #[default]
+    Relevance,
+    /// Largest rise in volume over the last 24h versus the 24h before
+    Trending,
+    /// Deepest CPMM pool first
+    Liquidity,
+    Newest,
+    /// Nearest upcoming close first; markets without one are left out
+    ClosingSoon,
+}
+
+impl SearchSort {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "relevance" => Some(SearchSort::Relevance),
+            "trending" => Some(SearchSort::Trending),
+            "liquidity" => Some(SearchSort::Liquidity),
+            "newest" => Some(SearchSort::Newest),
+            "closing_soon" | "closingsoon" => Some(SearchSort::ClosingSoon),
+            _ => None,
+        }
+    }
+
+    /// Direction of `SearchHit::sort_value` for this sort
+    pub fn order(&self) -> SortOrder {
+        match self {
+            SearchSort::ClosingSoon => SortOrder::Asc,
+            _ => SortOrder::Desc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    pub text: Option<String>,
+    /// Case-insensitive
+    pub category: Option<String>,
+    /// Markets must carry every one (case-insensitive)
+    pub tags: Vec<String>,
+    pub status: Option<EventStatus>,
+    pub closes_after: Option<u64>,
+    pub closes_before: Option<u64>,
+    pub sort: SearchSort,
+}
+
+/// A matching market, in the requested order
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub market_id: String,
+    /// Text relevance (0 without a text query)
+    pub score: f64,
+    pub volume_24h: f64,
+    /// Volume over the last 24h minus the 24h before
+    pub volume_change_24h: f64,
+    pub liquidity: f64,
+    pub created_at: u64,
+    pub closes_at: Option<u64>,
+    /// The value the hits are ordered by (ties broken by market id)
+    pub sort_value: f64,
+}
+
+// ============================================================================
+// INDEX
+// ============================================================================
+
+#[derive(Debug, Default)]
+pub struct MarketIndex {
+    docs: HashMap<String, MarketDoc>,
+    /// word → market → weight, ordered so prefixes are a range scan
+    terms: BTreeMap<String, HashMap<String, f64>>,
+    /// market → (timestamp, BB traded), oldest first, two windows deep
+    trades: HashMap<String, VecDeque<(u64, f64)>>,
+}
+
+impl MarketIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// Add a market or replace what the index holds for it
+    pub fn upsert(&mut self, doc: MarketDoc) {
+        if self.docs.get(&doc.market_id) == Some(&doc) {
+            return;
+        }
+        self.unindex(&doc.market_id);
+
+        let mut weights: HashMap<String, f64> = HashMap::new();
+        let fields = [
+            (doc.title.as_str(), TITLE_WEIGHT),
+            (doc.description.as_str(), DESCRIPTION_WEIGHT),
+        ];
+        for (text, weight) in fields {
+            for word in tokenize(text) {
+                *weights.entry(word).or_default() += weight;
+            }
+        }
+        for tag in &doc.tags {
+            for word in tokenize(tag) {
+                *weights.entry(word).or_default() += TAG_WEIGHT;
+            }
+        }
+        for (word, weight) in weights {
+            self.terms.entry(word).or_default().insert(doc.market_id.clone(), weight);
+        }
+        self.docs.insert(doc.market_id.clone(), doc);
+    }
+
+    pub fn remove(&mut self, market_id: &str) {
+        self.unindex(market_id);
+        self.trades.remove(market_id);
+    }
+
+    fn unindex(&mut self, market_id: &str) {
+        let Some(old) = self.docs.remove(market_id) else {
+            return;
+        };
+        let words: HashSet<String> = tokenize(&old.title)
+            .chain(tokenize(&old.description))
+            .chain(old.tags.iter().flat_map(|tag| tokenize(tag)))
+            .collect();
+        for word in words {
+            if let Some(postings) = self.terms.get_mut(&word) {
+                postings.remove(market_id);
+                if postings.is_empty() {
+                    self.terms.remove(&word);
+                }
+            }
+        }
+    }
+
+    /// Count `amount` BB traded on `market_id` at `timestamp` toward trending
+    pub fn record_trade(&mut self, market_id: &str, amount: f64, timestamp: u64) {
+        let trades = self.trades.entry(market_id.to_string()).or_default();
+        trades.push_back((timestamp, amount));
+        let horizon = timestamp.saturating_sub(2 * TRENDING_WINDOW_SECS);
+        while trades.front().is_some_and(|(ts, _)| *ts < horizon) {
+            trades.pop_front();
+        }
+    }
+
+    /// (volume in the last 24h, volume in the 24h before that)
+    pub fn volume_windows(&self, market_id: &str, now: u64) -> (f64, f64) {
+        let recent_from = now.saturating_sub(TRENDING_WINDOW_SECS);
+        let previous_from = now.saturating_sub(2 * TRENDING_WINDOW_SECS);
+        let mut windows = (0.0, 0.0);
+        for (ts, amount) in self.trades.get(market_id).into_iter().flatten() {
+            if *ts > now {
+                continue;
+            } else if *ts >= recent_from {
+                windows.0 += amount;
+            } else if *ts >= previous_from {
+                windows.1 += amount;
+            }
+        }
+        windows
+    }
+
+    /// Text relevance of every market matching all query words
+    fn text_scores(&self, text: &str) -> Option<HashMap<&str, f64>> {
+        let words: Vec<String> = tokenize(text).collect();
+        if words.is_empty() {
+            return None;
+        }
+        let mut scores: Option<HashMap<&str, f64>> = None;
+        for (i, word) in words.iter().enumerate() {
+            let mut word_scores: HashMap<&str, f64> = HashMap::new();
+            if let Some(postings) = self.terms.get(word) {
+                for (id, weight) in postings {
+                    *word_scores.entry(id.as_str()).or_default() += weight;
+                }
+            }
+            // The word being typed may be unfinished
+            if i == words.len() - 1 {
+                let longer = self.terms.range(word.clone()..)
+                    .skip_while(|(term, _)| *term == word)
+                    .take_while(|(term, _)| term.starts_with(word.as_str()));
+                for (_, postings) in longer {
+                    for (id, weight) in postings {
+                        *word_scores.entry(id.as_str()).or_default() += weight * PREFIX_FACTOR;
+                    }
+                }
+            }
+            scores = Some(match scores {
+                None => word_scores,
+                Some(previous) => previous.into_iter()
+                    .filter_map(|(id, score)| word_scores.get(id).map(|s| (id, score + s)))
+                    .collect(),
+            });
+        }
+        scores
+    }
+
+    /// Every market matching `query`, ordered by its sort
+    pub fn search(&self, query: &SearchQuery, now: u64) -> Vec<SearchHit> {
+        let scores = query.text.as_deref().and_then(|text| self.text_scores(text));
+        let tags: Vec<String> = query.tags.iter().map(|t| t.to_lowercase()).collect();
+
+        let mut hits: Vec<SearchHit> = self.docs.values()
+            .filter(|doc| scores.as_ref().is_none_or(|s| s.contains_key(doc.market_id.as_str())))
+            .filter(|doc| query.category.as_ref().is_none_or(|c| doc.category.eq_ignore_ascii_case(c)))
+            .filter(|doc| tags.iter().all(|t| doc.tags.iter().any(|tag| tag.to_lowercase() == *t)))
+            .filter(|doc| query.status.is_none_or(|s| doc.status == s))
+            .filter(|doc| match (query.closes_after, query.closes_before) {
+                (None, None) => true,
+                (after, before) => doc.closes_at.is_some_and(|c| {
+                    after.is_none_or(|a| c >= a) && before.is_none_or(|b| c <= b)
+                }),
+            })
+            .filter(|doc| query.sort != SearchSort::ClosingSoon || doc.closes_at.is_some_and(|c| c >= now))
+            .map(|doc| {
+                let score = scores.as_ref().and_then(|s| s.get(doc.market_id.as_str()).copied()).unwrap_or(0.0);
+                let (volume_24h, volume_previous) = self.volume_windows(&doc.market_id, now);
+                let volume_change_24h = volume_24h - volume_previous;
+                let sort_value = match query.sort {
+                    SearchSort::Relevance if scores.is_some() => score,
+                    SearchSort::Relevance | SearchSort::Newest => doc.created_at as f64,
+                    SearchSort::Trending => volume_change_24h,
+                    SearchSort::Liquidity => doc.liquidity,
+                    SearchSort::ClosingSoon => doc.closes_at.unwrap_or(u64::MAX) as f64,
+                };
+                SearchHit {
+                    market_id: doc.market_id.clone(),
+                    score,
+                    volume_24h,
+                    volume_change_24h,
+                    liquidity: doc.liquidity,
+                    created_at: doc.created_at,
+                    closes_at: doc.closes_at,
+                    sort_value,
+                }
+            })
+            .collect();
+
+        let descending = query.sort.order() == SortOrder::Desc;
+        hits.sort_by(|a, b| {
+            let ordering = a.sort_value.total_cmp(&b.sort_value).then_with(|| a.market_id.cmp(&b.market_id));
+            if descending { ordering.reverse() } else { ordering }
+        });
+        hits
+    }
+}
+
+/// Lowercase words of `text`, without stop words and single letters
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(str::to_lowercase)
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+}
+
+/// Unix seconds of an ISO 8601 date or datetime ("2026-11-03" or
+/// "2026-11-03T20:00:00Z"); None for anything else ("TBD")
+pub fn parse_iso_date(date: &str) -> Option<u64> {
+    let date = date.trim();
+    chrono::DateTime::parse_from_rfc3339(date)
+        .map(|dt| dt.timestamp())
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp())
+        })
+        .ok()
+        .and_then(|ts| u64::try_from(ts).ok())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_800_000_000;
+
+    fn doc(id: &str, title: &str, category: &str, tags: &[&str]) -> MarketDoc {
+        MarketDoc {
+            market_id: id.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            category: category.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            status: EventStatus::Active,
+            created_at: NOW - 1000,
+            closes_at: None,
+            liquidity: 0.0,
+            total_volume: 0.0,
+            unique_bettors: 0,
+        }
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.market_id.as_str()).collect()
+    }
+
+    fn index() -> MarketIndex {
+        let mut index = MarketIndex::new();
+        index.upsert(doc("btc", "Will Bitcoin hit 100k?", "crypto", &["bitcoin", "price"]));
+        index.upsert(doc("eth", "Ethereum ETF approved", "crypto", &["ethereum"]));
+        index.upsert(MarketDoc {
+            description: "Bitcoin mentioned in passing".into(),
+            ..doc("elect", "Election turnout record", "politics", &["election"])
+        });
+        index
+    }
+
+    #[test]
+    fn test_text_search_ranks_title_over_description() {
+        let index = index();
+        let query = SearchQuery { text: Some("bitcoin".into()), ..SearchQuery::default() };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["btc", "elect"]);
+
+        // Every word must match; the last one may be a prefix
+        let query = SearchQuery { text: Some("bitcoin pri".into()), ..SearchQuery::default() };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["btc"]);
+        let query = SearchQuery { text: Some("ether".into()), ..SearchQuery::default() };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["eth"]);
+    }
+
+    #[test]
+    fn test_upsert_replaces_old_words() {
+        let mut index = index();
+        index.upsert(doc("eth", "Solana flips Ethereum", "crypto", &[]));
+        let query = SearchQuery { text: Some("solana".into()), ..SearchQuery::default() };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["eth"]);
+
+        index.upsert(doc("eth", "Cardano news", "crypto", &[]));
+        assert!(index.search(&query, NOW).is_empty());
+        index.remove("eth");
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut index = index();
+        index.upsert(MarketDoc { status: EventStatus::Resolved, ..doc("eth", "Ethereum ETF approved", "crypto", &["ethereum"]) });
+
+        let query = SearchQuery { category: Some("Crypto".into()), ..SearchQuery::default() };
+        assert_eq!(index.search(&query, NOW).len(), 2);
+        let query = SearchQuery { status: Some(EventStatus::Active), category: Some("crypto".into()), ..SearchQuery::default() };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["btc"]);
+        let query = SearchQuery { tags: vec!["Election".into()], ..SearchQuery::default() };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["elect"]);
+    }
+
+    #[test]
+    fn test_trending_compares_volume_windows() {
+        let mut index = index();
+        // eth traded more in total, but less than the day before
+        index.record_trade("eth", 500.0, NOW - TRENDING_WINDOW_SECS - 60);
+        index.record_trade("eth", 300.0, NOW - 60);
+        index.record_trade("btc", 100.0, NOW - 60);
+
+        let query = SearchQuery { sort: SearchSort::Trending, ..SearchQuery::default() };
+        let hits = index.search(&query, NOW);
+        assert_eq!(ids(&hits)[0], "btc");
+        assert_eq!(hits[0].volume_24h, 100.0);
+        assert_eq!(hits.last().unwrap().volume_change_24h, -200.0);
+    }
+
+    #[test]
+    fn test_closing_soon_skips_closed_and_undated() {
+        let mut index = index();
+        index.upsert(MarketDoc { closes_at: Some(NOW + 500), ..doc("btc", "Will Bitcoin hit 100k?", "crypto", &[]) });
+        index.upsert(MarketDoc { closes_at: Some(NOW + 100), ..doc("eth", "Ethereum ETF approved", "crypto", &[]) });
+        index.upsert(MarketDoc { closes_at: Some(NOW - 100), ..doc("elect", "Election turnout", "politics", &[]) });
+
+        let query = SearchQuery { sort: SearchSort::ClosingSoon, ..SearchQuery::default() };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["eth", "btc"]);
+        let query = SearchQuery { closes_before: Some(NOW + 200), ..query };
+        assert_eq!(ids(&index.search(&query, NOW)), vec!["eth"]);
+    }
+
+    #[test]
+    fn test_parse_iso_date() {
+        assert_eq!(parse_iso_date("1970-01-02"), Some(86_400));
+        assert_eq!(parse_iso_date("1970-01-01T00:01:00Z"), Some(60));
+        assert_eq!(parse_iso_date("TBD"), None);
+    }
+}
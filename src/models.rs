@@ -1,8 +1,11 @@
 // Data models for the BlackBook prediction market
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 use crate::market_resolve::cpmm;
+use crate::market_search::{parse_iso_date, MarketDoc};
+use crate::rss::{RssEvent, EventDates, ResolutionRules as RssResolutionRules};
 
 // Individual bet record for tracking outcomes and payouts
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -214,15 +217,61 @@ impl PredictionMarket {
             .cloned()
             .collect()
     }
-}
 
-// Request/Response structs
-#[derive(Debug, Deserialize)]
-pub struct SignedBetRequest {
-    pub signed_tx: crate::rpc::SignedTransaction,
+    /// RSS metadata for this market, with current odds as probabilities
+    pub fn to_rss_event(&self) -> RssEvent {
+        RssEvent {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            source: self.source.clone(),
+            category: Some(self.category.clone()),
+            tags: self.tags.clone(),
+            market_type: self.market_type.clone().unwrap_or_else(|| "binary".to_string()),
+            outcomes: self.options.clone(),
+            initial_probabilities: Some(self.calculate_odds()),
+            source_url: self.source_url.clone().unwrap_or_default(),
+            image_url: self.image_url.clone(),
+            dates: EventDates {
+                published: self.dates.as_ref()
+                    .and_then(|d| d.published.clone())
+                    .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+                freeze: self.dates.as_ref().and_then(|d| d.freeze.clone()),
+                resolution: self.dates.as_ref().and_then(|d| d.resolution.clone()),
+            },
+            resolution_rules: self.resolution_rules.as_ref().map(|r| RssResolutionRules {
+                provider: r.provider.clone(),
+                data_source: r.data_source.clone(),
+                conditions: r.conditions.clone().unwrap_or_default(),
+                outcome_rules: r.outcome_rules.clone(),
+            }),
+            market_id: self.id.clone(),
+            added_to_ledger: true,
+        }
+    }
+
+    /// What the search index keeps about this market. Betting closes at
+    /// `betting_closes_at`, else at the freeze date if it parses.
+    pub fn search_doc(&self) -> MarketDoc {
+        MarketDoc {
+            market_id: self.id.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            category: self.category.clone(),
+            tags: self.tags.clone(),
+            status: self.market_status,
+            created_at: self.created_at,
+            closes_at: self.betting_closes_at.or_else(|| {
+                self.dates.as_ref().and_then(|d| d.freeze.as_deref()).and_then(parse_iso_date)
+            }),
+            liquidity: self.cpmm_pool.as_ref().map(|p| p.get_tvl()).unwrap_or(0.0),
+            total_volume: self.total_volume,
+            unique_bettors: self.unique_bettors.len(),
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
+// Request/Response structs
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SignedBetResponse {
     pub success: bool,
     pub bet_id: Option<String>,
@@ -246,29 +295,8 @@ pub struct SignedBetResponse {
     pub fee_paid: Option<f64>,
 }
 
-impl SignedBetResponse {
-    pub fn error(msg: &str) -> Self {
-        Self {
-            success: false,
-            bet_id: None,
-            transaction_id: None,
-            market_id: None,
-            outcome: None,
-            amount: None,
-            new_balance: None,
-            nonce_used: None,
-            error: Some(msg.to_string()),
-            entry_price: None,
-            shares_purchased: None,
-            price_impact: None,
-            new_price: None,
-            fee_paid: None,
-        }
-    }
-}
-
 /// Dates for market lifecycle
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, ToSchema)]
 pub struct MarketDates {
     #[serde(default)]
     pub published: Option<String>,
@@ -279,7 +307,7 @@ pub struct MarketDates {
 }
 
 /// Resolution rules configuration
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, ToSchema)]
 pub struct ResolutionRules {
     #[serde(default)]
     pub provider: Option<String>,
@@ -287,6 +315,9 @@ pub struct ResolutionRules {
     pub data_source: Option<String>,
     #[serde(default)]
     pub conditions: Option<std::collections::HashMap<String, String>>,
+    /// Outcome label → rule expression (see `market_resolve::rules`)
+    #[serde(default)]
+    pub outcome_rules: Option<std::collections::HashMap<String, String>>,
 }
 
 /// POST /markets request body
@@ -314,10 +345,13 @@ pub struct ResolutionRules {
 ///   "source_url": "https://...",
 ///   "image_url": "https://...",
 ///   "dates": { "published": "...", "freeze": null, "resolution": null },
-///   "resolution_rules": { "provider": "oracle_v1", "data_source": "...", "conditions": {...} }
+///   "resolution_rules": {
+///     "provider": "oracle_v1", "data_source": "...", "conditions": {...},
+///     "outcome_rules": { "Yes": "BTC >= 100000 AND date <= 2025-01-01", "No": "otherwise" }
+///   }
 /// }
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateMarketRequest {
     // === REQUIRED FIELDS ===
     pub title: String,
@@ -363,7 +397,7 @@ pub struct CreateMarketRequest {
     pub resolution_rules: Option<ResolutionRules>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TransferRequest {
     pub from: String,
     pub to: String,
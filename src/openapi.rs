@@ -0,0 +1,319 @@
+// ============================================================================
+// OpenAPI - Generated specification for the HTTP API
+// ============================================================================
+//
+// Built from the `#[utoipa::path]` annotation on every handler and the
+// `ToSchema` derives on their request types, served at GET /openapi.json.
+//
+// Adding a route to main.rs means annotating its handler and listing it in
+// `paths(...)` below; the tests fail for any route without a spec entry.
+// Routes registered twice for SDK compatibility are copied from their
+// canonical path by `RouteAliases`.
+// ============================================================================
+
+use axum::response::Json;
+use utoipa::openapi;
+use utoipa::{Modify, OpenApi};
+
+use crate::api_error::ApiError;
+use crate::bridge::{BridgeDirection, BridgeStats, BridgeStatus, PendingBridge};
+use crate::bridge_proof::{Attestation, DepositProof};
+use crate::handlers;
+use crate::handlers::{
+    AddOracleRequest, AdminResolveRequest, ArbitrateDisputeRequest, BetRequest, BridgeDepositRequest,
+    CancelOrderRequest, ClaimWinningsRequest, DealerFundAllRequest,
+    EditPendingEventRequest, FreezeRequest, InitLiquidityRequest,
+    MintSharesRequest, RedeemSharesRequest, RejectPendingEventRequest,
+    ResolveMarketRequest, RoleChangeRequest, SessionSettleRequest, SessionStartRequest, SettlementRequest,
+    SubmitOrderRequest, SubmitPendingEventRequest, TrustedRootRequest,
+};
+use crate::models::{CreateMarketRequest, MarketDates, ResolutionRules, SignedBetResponse, TransferRequest};
+use crate::roles::{Role, RoleAction, RoleChange, SignedRequest};
+use crate::orderbook::{Fill, LimitOrder, OrderStatus, OrderType, Outcome, Side};
+use crate::routes::auth::{self, ConnectWalletRequest};
+use crate::rpc::{SignedTransaction, SignedTxType, TransactionPayload};
+use crate::shares::{PositionInfo, SharesStats, SimplePosition, UserPositionsSummary};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "BlackBook L2 Prediction Market API",
+        description = "Prediction markets, CLOB and outcome shares on the BlackBook L2. \
+                       Errors share the `ApiError` envelope; branch on its `code`. \
+                       Privileged routes require `X-BlackBook-Key`, `X-BlackBook-Timestamp` and \
+                       `X-BlackBook-Signature` headers from a key holding the route's role.",
+    ),
+    paths(
+        crate::health_check,
+        openapi_json,
+        auth::connect_wallet,
+        // Markets
+        handlers::get_markets,
+        handlers::search_markets,
+        handlers::create_market,
+        handlers::initialize_all_market_liquidity,
+        handlers::initialize_market_liquidity,
+        handlers::get_market,
+        handlers::get_market_odds,
+        handlers::get_market_prices,
+        // Resolution
+        handlers::get_market_resolution,
+        handlers::resolve_market,
+        handlers::admin_resolve_market,
+        handlers::dispute_resolution,
+        handlers::finalize_resolution,
+        handlers::arbitrate_dispute,
+        handlers::list_disputes,
+        handlers::get_resolver_status,
+        // Event inbox and feeds
+        handlers::list_pending_events,
+        handlers::submit_pending_event,
+        handlers::expire_pending_events,
+        handlers::edit_pending_event,
+        handlers::reject_pending_event,
+        handlers::launch_pending_event,
+        handlers::get_feed_items,
+        handlers::get_rss_feed,
+        handlers::get_atom_feed,
+        handlers::get_feed_status,
+        // Dealer
+        handlers::dealer_fund_all_markets,
+        handlers::get_dealer_positions,
+        // CLOB
+        handlers::submit_order,
+        handlers::cancel_order,
+        handlers::get_user_orders,
+        handlers::get_orderbook,
+        handlers::get_orderbook_outcome,
+        handlers::get_recent_trades,
+        // Shares
+        handlers::mint_shares,
+        handlers::redeem_shares,
+        handlers::claim_market_winnings,
+        handlers::get_positions,
+        handlers::get_market_positions,
+        // Bets and nonces
+        handlers::place_signed_bet,
+        handlers::get_user_bets,
+        handlers::get_nonce,
+        // Ledger
+        handlers::get_balance,
+        handlers::get_balance_details,
+        handlers::transfer,
+        handlers::get_ledger_activity,
+        handlers::get_ledger_transactions,
+        handlers::get_unified_ledger,
+        // Settlement
+        handlers::settle_to_l1_real,
+        handlers::get_pending_settlements,
+        handlers::get_settlement_status,
+        handlers::sync_from_l1,
+        // Blocks and state roots
+        handlers::list_blocks,
+        handlers::get_latest_block,
+        handlers::get_block,
+        handlers::get_transaction,
+        handlers::get_state_root,
+        handlers::list_state_commitments,
+        handlers::get_state_proof,
+        handlers::commit_state_root,
+        // Bridge
+        handlers::bridge_deposit,
+        handlers::bridge_withdraw,
+        handlers::get_bridge_status,
+        handlers::list_wallet_bridges,
+        handlers::get_bridge_stats,
+        handlers::get_bridge_trust,
+        handlers::list_withdrawal_batches,
+        handlers::get_withdrawal_batch,
+        handlers::add_trusted_l1_root,
+        // Sessions
+        handlers::session_start,
+        handlers::session_settle,
+        handlers::session_status,
+        handlers::session_list,
+        // Forced exits
+        handlers::submit_exit,
+        handlers::list_exits,
+        handlers::list_overdue_exits,
+        handlers::get_exit,
+        handlers::freeze_and_exit,
+        // Oracles
+        handlers::add_oracle,
+        handlers::list_oracles,
+        handlers::remove_oracle,
+        handlers::list_roles,
+        handlers::change_role,
+        // Audit
+        handlers::verify_audit_log,
+        handlers::export_audit_log,
+        // Stats
+        handlers::get_orderbook_stats,
+        handlers::get_shares_stats,
+    ),
+    components(schemas(
+        ApiError,
+        // Requests
+        ConnectWalletRequest, CreateMarketRequest, MarketDates, ResolutionRules, InitLiquidityRequest,
+        ResolveMarketRequest, AdminResolveRequest, ArbitrateDisputeRequest,
+        SubmitPendingEventRequest, EditPendingEventRequest, RejectPendingEventRequest,
+        DealerFundAllRequest, SubmitOrderRequest, CancelOrderRequest,
+        MintSharesRequest, RedeemSharesRequest, ClaimWinningsRequest, BetRequest, TransferRequest,
+        SettlementRequest, BridgeDepositRequest, DepositProof, Attestation,
+        TrustedRootRequest, SessionStartRequest, SessionSettleRequest,
+        FreezeRequest, AddOracleRequest, RoleChangeRequest, SignedTransaction, SignedTxType, TransactionPayload,
+        // Responses and domain types
+        SignedBetResponse, LimitOrder, Fill, Side, OrderType, OrderStatus, Outcome,
+        PendingBridge, BridgeStatus, BridgeDirection, BridgeStats,
+        SharesStats, SimplePosition, UserPositionsSummary, PositionInfo,
+        Role, RoleAction, RoleChange, SignedRequest,
+    )),
+    tags(
+        (name = "health", description = "Liveness and this document"),
+        (name = "auth", description = "Wallet connection"),
+        (name = "markets", description = "Prediction markets and CPMM pools"),
+        (name = "resolution", description = "Bonded resolution proposals, disputes and arbitration"),
+        (name = "events", description = "Pending event inbox"),
+        (name = "feed", description = "RSS/Atom/JSON market feed"),
+        (name = "dealer", description = "Dealer liquidity"),
+        (name = "orders", description = "Central limit order book"),
+        (name = "shares", description = "Outcome shares and positions"),
+        (name = "bets", description = "Signed bets and nonces"),
+        (name = "ledger", description = "Balances, transfers and ledger history"),
+        (name = "settlement", description = "L1 settlement"),
+        (name = "blocks", description = "L2 block explorer"),
+        (name = "state", description = "State root commitments and proofs"),
+        (name = "bridge", description = "L1 ↔ L2 bridge"),
+        (name = "session", description = "Trading sessions"),
+        (name = "exits", description = "Forced exits and freeze-and-exit"),
+        (name = "oracles", description = "Oracle whitelist and role grants"),
+        (name = "audit", description = "Hash-chained log of privileged actions"),
+        (name = "stats", description = "System statistics"),
+    ),
+    modifiers(&RouteAliases),
+)]
+pub struct ApiDoc;
+
+/// Extra paths serving the same handler: (alias, canonical path)
+const ROUTE_ALIASES: &[(&str, &str)] = &[
+    ("/", "/health"),
+    ("/rpc/submit", "/bet/signed"),
+    ("/resolve/{market_id}/{outcome}", "/admin/resolve/{market_id}/{outcome}"),
+];
+
+/// Copies each canonical path item to its aliases (with distinct operation ids)
+struct RouteAliases;
+
+impl Modify for RouteAliases {
+    fn modify(&self, doc: &mut openapi::OpenApi) {
+        for (alias, canonical) in ROUTE_ALIASES {
+            let Some(mut item) = doc.paths.paths.get(*canonical).cloned() else {
+                continue;
+            };
+            for operation in item.operations.values_mut() {
+                operation.operation_id = operation.operation_id.take().map(|id| format!("{}_alias", id));
+            }
+            doc.paths.paths.insert(alias.to_string(), item);
+        }
+    }
+}
+
+/// GET /openapi.json - This API's OpenAPI 3 document
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "health",
+    responses(
+        (status = 200, description = "OpenAPI 3 document", body = Object),
+    )
+)]
+pub async fn openapi_json() -> Json<openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utoipa::openapi::PathItemType;
+
+    /// (method, OpenAPI path) for every `.route(...)` registered in main.rs
+    fn registered_routes() -> Vec<(String, String)> {
+        let route = regex::Regex::new(r#"\.route\("([^"]+)",\s*(.+)"#).unwrap();
+        let method = regex::Regex::new(r"\b(get|post|put|delete|patch)\(").unwrap();
+        let mut routes = Vec::new();
+        for caps in route.captures_iter(include_str!("main.rs")) {
+            let path = caps[1]
+                .split('/')
+                .map(|seg| match seg.strip_prefix(':') {
+                    Some(param) => format!("{{{}}}", param),
+                    None => seg.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            let path = if path.is_empty() { "/".to_string() } else { path };
+            for m in method.captures_iter(&caps[2]) {
+                routes.push((m[1].to_string(), path.clone()));
+            }
+        }
+        routes
+    }
+
+    fn item_type(method: &str) -> PathItemType {
+        match method {
+            "get" => PathItemType::Get,
+            "post" => PathItemType::Post,
+            "put" => PathItemType::Put,
+            "delete" => PathItemType::Delete,
+            "patch" => PathItemType::Patch,
+            other => panic!("unknown method {}", other),
+        }
+    }
+
+    #[test]
+    fn test_every_route_has_spec_entry() {
+        let doc = ApiDoc::openapi();
+        let routes = registered_routes();
+        assert!(routes.len() > 80, "route parser found only {} routes", routes.len());
+
+        let missing: Vec<String> = routes.iter()
+            .filter(|(method, path)| {
+                !doc.paths.paths.get(path)
+                    .is_some_and(|item| item.operations.contains_key(&item_type(method)))
+            })
+            .map(|(method, path)| format!("{} {}", method.to_uppercase(), path))
+            .collect();
+        assert!(missing.is_empty(), "routes without an OpenAPI entry: {:?}", missing);
+    }
+
+    #[test]
+    fn test_spec_has_no_unrouted_entries() {
+        let doc = ApiDoc::openapi();
+        let routes = registered_routes();
+
+        for (path, item) in &doc.paths.paths {
+            for op in item.operations.keys() {
+                assert!(
+                    routes.iter().any(|(m, p)| p == path && item_type(m) == *op),
+                    "spec documents {} {} which main.rs does not route",
+                    serde_json::to_value(op).unwrap(), path
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema_references_resolve() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+
+        let text = doc.to_string();
+        let refs = regex::Regex::new(r##""\$ref":"#/components/schemas/([^"]+)""##).unwrap();
+        for caps in refs.captures_iter(&text) {
+            assert!(schemas.contains_key(&caps[1]), "unregistered schema {}", &caps[1]);
+        }
+    }
+}
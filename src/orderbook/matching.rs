@@ -226,6 +226,10 @@ pub struct MatchingEngine {
     
     /// Total volume traded
     pub total_volume: f64,
+    
+    /// Fee rates applied to each fill
+    pub maker_fee_rate: f64,
+    pub taker_fee_rate: f64,
 }
 
 impl MatchingEngine {
@@ -237,6 +241,8 @@ impl MatchingEngine {
             user_orders: HashMap::new(),
             fee_pool: 0.0,
             total_volume: 0.0,
+            maker_fee_rate: MAKER_FEE_RATE,
+            taker_fee_rate: TAKER_FEE_RATE,
         }
     }
 
@@ -385,8 +391,8 @@ impl MatchingEngine {
             // Execute the fill at maker's price (price improvement for taker)
             let fill_price = maker_order.price_bps;
             let fill_value = (fill_price as f64 / 100.0) * fill_size;
-            let maker_fee = fill_value * MAKER_FEE_RATE;
-            let taker_fee = fill_value * TAKER_FEE_RATE;
+            let maker_fee = fill_value * self.maker_fee_rate;
+            let taker_fee = fill_value * self.taker_fee_rate;
 
             // Create fill record
             let fill = Fill::new(
@@ -396,7 +402,7 @@ impl MatchingEngine {
                 taker_order,
                 fill_price,
                 fill_size,
-            );
+            ).with_fees(maker_fee, taker_fee);
 
             // Update orders
             taker_order.fill(fill_size, fill_price as f64, taker_fee);
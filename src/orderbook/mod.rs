@@ -23,7 +23,8 @@ pub mod matching;
 pub use orders::*;
 pub use matching::*;
 
-use crate::market_resolve::cpmm::CPMMPool;
+use crate::config::MarketParams;
+use crate::market_resolve::cpmm::{CPMMPool, LP_FEE_RATE};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -62,6 +63,13 @@ pub struct OrderBookManager {
     
     /// Statistics
     pub stats: OrderBookStats,
+    
+    /// Depth (BB per side) and spread (bps) the CLOB needs to be used
+    pub min_clob_depth: f64,
+    pub max_spread_bps: u64,
+    
+    /// Fee rate given to CPMM pools created here
+    pub lp_fee_rate: f64,
 }
 
 /// Status of a market's order book
@@ -102,6 +110,21 @@ impl OrderBookManager {
             market_status: HashMap::new(),
             total_fees_collected: 0.0,
             stats: OrderBookStats::default(),
+            min_clob_depth: MIN_CLOB_DEPTH,
+            max_spread_bps: MAX_SPREAD_BPS,
+            lp_fee_rate: LP_FEE_RATE,
+        }
+    }
+
+    /// Apply a market's configured fees and liquidity thresholds
+    pub fn configure(&mut self, params: &MarketParams) {
+        self.engine.maker_fee_rate = params.maker_fee_rate;
+        self.engine.taker_fee_rate = params.taker_fee_rate;
+        self.min_clob_depth = params.min_clob_depth;
+        self.max_spread_bps = params.max_spread_bps;
+        self.lp_fee_rate = params.lp_fee_rate;
+        for pool in self.cpmm_pools.values_mut() {
+            pool.fee_rate = params.lp_fee_rate;
         }
     }
 
@@ -111,7 +134,8 @@ impl OrderBookManager {
         if let Some(liquidity) = initial_liquidity {
             if liquidity > 0.0 {
                 let outcomes = vec!["YES".to_string(), "NO".to_string()];
-                let pool = CPMMPool::new(liquidity, outcomes, "ORACLE");
+                let mut pool = CPMMPool::new(liquidity, outcomes, "ORACLE");
+                pool.fee_rate = self.lp_fee_rate;
                 self.cpmm_pools.insert(market_id.to_string(), pool);
             }
         }
@@ -319,10 +343,10 @@ impl OrderBookManager {
         let ask_depth: f64 = snapshot.asks.iter().map(|l| l.size).sum();
 
         // Check spread
-        let spread_ok = snapshot.spread.map(|s| s <= MAX_SPREAD_BPS).unwrap_or(false);
+        let spread_ok = snapshot.spread.map(|s| s <= self.max_spread_bps).unwrap_or(false);
 
         // Check depth
-        let depth_ok = bid_depth >= MIN_CLOB_DEPTH && ask_depth >= MIN_CLOB_DEPTH;
+        let depth_ok = bid_depth >= self.min_clob_depth && ask_depth >= self.min_clob_depth;
 
         // Check if we can fill the order
         let can_fill = match size {
@@ -340,8 +364,8 @@ impl OrderBookManager {
         let bid_depth: f64 = snapshot.bids.iter().map(|l| l.size).sum();
         let ask_depth: f64 = snapshot.asks.iter().map(|l| l.size).sum();
         
-        let clob_active = bid_depth >= MIN_CLOB_DEPTH && ask_depth >= MIN_CLOB_DEPTH
-            && snapshot.spread.map(|s| s <= MAX_SPREAD_BPS).unwrap_or(false);
+        let clob_active = bid_depth >= self.min_clob_depth && ask_depth >= self.min_clob_depth
+            && snapshot.spread.map(|s| s <= self.max_spread_bps).unwrap_or(false);
 
         let using_cpmm = !clob_active && self.cpmm_pools.contains_key(market_id);
 
@@ -451,8 +475,9 @@ impl OrderBookManager {
     /// Add CPMM liquidity (for market makers)
     pub fn add_cpmm_liquidity(&mut self, market_id: &str, amount: f64, provider: &str) -> Result<f64, String> {
         let outcomes = vec!["YES".to_string(), "NO".to_string()];
+        let lp_fee_rate = self.lp_fee_rate;
         let pool = self.cpmm_pools.entry(market_id.to_string())
-            .or_insert_with(|| CPMMPool::new(0.0, outcomes, "ORACLE"));
+            .or_insert_with(|| CPMMPool { fee_rate: lp_fee_rate, ..CPMMPool::new(0.0, outcomes, "ORACLE") });
 
         match pool.add_liquidity(provider, amount) {
             Ok(lp_tokens) => {
@@ -13,6 +13,7 @@
 // ============================================================================
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
@@ -46,7 +47,7 @@ pub const ORDER_EXPIRY_SECS: u64 = 7 * 24 * 60 * 60;
 // ============================================================================
 
 /// Order side - which outcome the order is for
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Side {
     /// Buying YES shares (betting event will happen)
@@ -72,7 +73,7 @@ impl Side {
 }
 
 /// Order type - execution behavior
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum OrderType {
     /// Good Till Cancelled - stays on book until filled or cancelled
@@ -92,7 +93,7 @@ impl Default for OrderType {
 }
 
 /// Order status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum OrderStatus {
     /// Order is active on the book
@@ -117,10 +118,23 @@ impl OrderStatus {
     pub fn is_terminal(&self) -> bool {
         matches!(self, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Expired | OrderStatus::Rejected)
     }
+
+    /// Parse the serialized name; `partially_filled` and `canceled` are accepted too
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace('_', "").as_str() {
+            "open" => Some(OrderStatus::Open),
+            "partiallyfilled" => Some(OrderStatus::PartiallyFilled),
+            "filled" => Some(OrderStatus::Filled),
+            "cancelled" | "canceled" => Some(OrderStatus::Cancelled),
+            "expired" => Some(OrderStatus::Expired),
+            "rejected" => Some(OrderStatus::Rejected),
+            _ => None,
+        }
+    }
 }
 
 /// Outcome for multi-outcome markets
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct Outcome(pub usize);
 
 impl Outcome {
@@ -141,7 +155,7 @@ impl Outcome {
 // ============================================================================
 
 /// A limit order on the order book
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LimitOrder {
     /// Unique order identifier
     pub id: String,
@@ -368,7 +382,7 @@ impl LimitOrder {
 // ============================================================================
 
 /// A record of a trade execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Fill {
     /// Unique fill identifier
     pub id: String,
@@ -447,6 +461,13 @@ impl Fill {
         }
     }
 
+    /// Replace the default-rate fees with the market's own
+    pub fn with_fees(mut self, maker_fee: f64, taker_fee: f64) -> Self {
+        self.maker_fee = maker_fee;
+        self.taker_fee = taker_fee;
+        self
+    }
+
     /// Price as decimal
     pub fn price(&self) -> f64 {
         self.price_bps as f64 / 100.0
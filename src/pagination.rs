@@ -0,0 +1,281 @@
+//! Cursor Pagination
+//!
+//! List endpoints return items in a stable order keyed by `(sort value, id)`,
+//! newest first unless asked otherwise. Each page carries an opaque cursor
+//! encoding the key of its last item; the next page starts strictly after
+//! that key, so items inserted between requests never shift a page or show
+//! up twice the way they do with offsets.
+//!
+//! Every list response shares the same `pagination` object:
+//!
+//! ```json
+//! { "limit": 100, "total": 2543, "has_more": true, "next_cursor": "5b3137..." }
+//! ```
+//!
+//! `total` counts every item matching the filters, across all pages.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Page size when the request gives none
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Largest page a request may ask for
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageError {
+    /// The cursor was not issued by this endpoint (or was altered)
+    InvalidCursor(String),
+    /// A filter value outside the accepted set
+    InvalidFilter { field: &'static str, value: String },
+}
+
+impl fmt::Display for PageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageError::InvalidCursor(cursor) => write!(f, "Invalid cursor: {}", cursor),
+            PageError::InvalidFilter { field, value } => write!(f, "Invalid {} filter: {}", field, value),
+        }
+    }
+}
+
+impl std::error::Error for PageError {}
+
+// ============================================================================
+// REQUEST
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "asc" => Some(SortOrder::Asc),
+            "desc" => Some(SortOrder::Desc),
+            _ => None,
+        }
+    }
+}
+
+/// Where a page starts, how long it is and which way it runs
+#[derive(Debug, Clone, Default)]
+pub struct PageRequest {
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+    pub order: SortOrder,
+}
+
+impl PageRequest {
+    pub fn new(cursor: Option<String>, limit: Option<usize>) -> Self {
+        Self { cursor, limit, order: SortOrder::Desc }
+    }
+
+    pub fn with_order(mut self, order: SortOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Requested page size, clamped to 1..=MAX_PAGE_LIMIT
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+    }
+}
+
+/// Inclusive unix-second window; open ends match everything
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+impl TimeRange {
+    pub fn new(from: Option<u64>, to: Option<u64>) -> Self {
+        Self { from, to }
+    }
+
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.from.is_none_or(|from| timestamp >= from) && self.to.is_none_or(|to| timestamp <= to)
+    }
+}
+
+/// Parse an optional filter value, rejecting anything `parse` doesn't know
+pub fn parse_filter<T>(
+    field: &'static str,
+    value: Option<&str>,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<T>, PageError> {
+    match value {
+        None => Ok(None),
+        Some(value) => parse(value)
+            .map(Some)
+            .ok_or_else(|| PageError::InvalidFilter { field, value: value.to_string() }),
+    }
+}
+
+// ============================================================================
+// PAGE
+// ============================================================================
+
+/// One page of a list
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor for the following page; None on the last page
+    pub next_cursor: Option<String>,
+    /// Items matching the filters across all pages
+    pub total: usize,
+    pub limit: usize,
+}
+
+impl<T> Page<T> {
+    pub fn has_more(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+
+    /// The `pagination` object of a list response
+    pub fn meta(&self) -> Value {
+        json!({
+            "limit": self.limit,
+            "total": self.total,
+            "has_more": self.has_more(),
+            "next_cursor": self.next_cursor,
+        })
+    }
+}
+
+pub fn encode_cursor<K: Serialize>(key: &K) -> String {
+    hex::encode(serde_json::to_vec(key).unwrap_or_default())
+}
+
+pub fn decode_cursor<K: DeserializeOwned>(cursor: &str) -> Result<K, PageError> {
+    hex::decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(|| PageError::InvalidCursor(cursor.to_string()))
+}
+
+/// Sort `items` by `key` in the requested order and cut the page after the
+/// request's cursor. Keys must be unique (end them with an id).
+pub fn paginate<T, K>(mut items: Vec<T>, key: impl Fn(&T) -> K, request: &PageRequest) -> Result<Page<T>, PageError>
+where
+    K: PartialOrd + Serialize + DeserializeOwned,
+{
+    let after: Option<K> = request.cursor.as_deref().map(decode_cursor).transpose()?;
+    let limit = request.limit();
+    let order = request.order;
+    let cmp = |a: &K, b: &K| {
+        let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        if order == SortOrder::Desc { ordering.reverse() } else { ordering }
+    };
+
+    items.sort_by(|a, b| cmp(&key(a), &key(b)));
+    let total = items.len();
+    if let Some(after) = after {
+        items.retain(|item| cmp(&key(item), &after) == Ordering::Greater);
+    }
+
+    let has_more = items.len() > limit;
+    items.truncate(limit);
+    let next_cursor = if has_more { items.last().map(|last| encode_cursor(&key(last))) } else { None };
+    Ok(Page { items, next_cursor, total, limit })
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<(u64, String)> {
+        // Two items share a timestamp; the id breaks the tie
+        vec![(10, "a".into()), (30, "c".into()), (20, "b".into()), (20, "bb".into()), (40, "d".into())]
+    }
+
+    fn key(item: &(u64, String)) -> (u64, String) {
+        item.clone()
+    }
+
+    fn ids(page: &Page<(u64, String)>) -> Vec<&str> {
+        page.items.iter().map(|(_, id)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_pages_walk_every_item_once() {
+        let mut request = PageRequest::new(None, Some(2));
+        let mut seen = Vec::new();
+        loop {
+            let page = paginate(items(), key, &request).unwrap();
+            assert_eq!(page.total, 5);
+            seen.extend(ids(&page).into_iter().map(String::from));
+            match page.next_cursor {
+                Some(cursor) => request.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(seen, vec!["d", "c", "bb", "b", "a"]);
+    }
+
+    #[test]
+    fn test_cursor_is_stable_across_inserts() {
+        let request = PageRequest::new(None, Some(2)).with_order(SortOrder::Asc);
+        let first = paginate(items(), key, &request).unwrap();
+        assert_eq!(ids(&first), vec!["a", "b"]);
+
+        // A newer item and one older than the cursor arrive between requests
+        let mut grown = items();
+        grown.push((50, "e".into()));
+        grown.push((5, "z".into()));
+        let next = PageRequest { cursor: first.next_cursor.clone(), ..request };
+        let second = paginate(grown, key, &next).unwrap();
+        assert_eq!(ids(&second), vec!["bb", "c"]);
+        assert!(second.has_more());
+    }
+
+    #[test]
+    fn test_limit_is_clamped() {
+        assert_eq!(PageRequest::new(None, None).limit(), DEFAULT_PAGE_LIMIT);
+        assert_eq!(PageRequest::new(None, Some(0)).limit(), 1);
+        assert_eq!(PageRequest::new(None, Some(50_000)).limit(), MAX_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn test_bad_cursor_and_filter_are_rejected() {
+        let request = PageRequest::new(Some("not-a-cursor".into()), None);
+        assert!(matches!(paginate(items(), key, &request), Err(PageError::InvalidCursor(_))));
+
+        assert_eq!(parse_filter("order", Some("asc"), SortOrder::parse), Ok(Some(SortOrder::Asc)));
+        assert_eq!(parse_filter("order", None, SortOrder::parse), Ok(None));
+        assert_eq!(
+            parse_filter("order", Some("sideways"), SortOrder::parse),
+            Err(PageError::InvalidFilter { field: "order", value: "sideways".into() })
+        );
+    }
+
+    #[test]
+    fn test_time_range() {
+        let range = TimeRange::new(Some(10), Some(20));
+        assert!(range.contains(10) && range.contains(20));
+        assert!(!range.contains(9) && !range.contains(21));
+        assert!(TimeRange::default().contains(0));
+    }
+}
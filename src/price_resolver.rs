@@ -0,0 +1,331 @@
+// ============================================================================
+// Price Resolver - Automatic resolution of oracle-backed markets
+// ============================================================================
+//
+// Background task that scans markets whose resolution date has passed and
+// resolves them from oracle data. Two rule shapes are supported:
+//
+//   outcome_rules: { "Yes": "BTC >= 100000", "No": "otherwise" }
+//   conditions:    { "asset": "BTC", "target_price": "100000", "condition": ">=" }
+//
+// The decided outcome is submitted as a bonded resolution proposal and goes
+// through the normal challenge window. The bond comes from
+// `resolution.resolver_address` (ORACLE by default): while it cannot cover
+// one, passes propose nothing and report the due markets in
+// `ResolverStatus` (GET /resolver/status) instead.
+// ============================================================================
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::app_state::{AppState, SharedState};
+use crate::easteregg::{OracleManager, PriceRule};
+use crate::handlers::open_resolution_proposal;
+use crate::market_resolve::CompiledRules;
+use crate::models::PredictionMarket;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Parse a market resolution date (RFC 3339 or YYYY-MM-DD, end of day UTC)
+fn parse_resolution_date(date: &str) -> Option<u64> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(date) {
+        return Some(dt.timestamp().max(0) as u64);
+    }
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(23, 59, 59))
+        .map(|dt| dt.and_utc().timestamp().max(0) as u64)
+}
+
+/// Resolver funding and what its last pass could not do
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResolverStatus {
+    /// Account bonding automatic proposals
+    pub address: String,
+    pub balance: f64,
+    /// Bond each proposal posts
+    pub required_bond: f64,
+    /// Whether `balance` covers a bond
+    pub funded: bool,
+    pub last_run: Option<u64>,
+    /// Proposals opened since startup
+    pub proposed: u64,
+    /// Due markets the last pass did not propose
+    pub failures: Vec<ResolverFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolverFailure {
+    pub market_id: String,
+    pub error: String,
+}
+
+impl ResolverStatus {
+    fn unfunded_reason(&self) -> String {
+        format!(
+            "resolver account {} holds {} BB but a proposal bonds {} BB",
+            self.address, self.balance, self.required_bond
+        )
+    }
+
+    fn fail(&mut self, market_id: &str, error: String) {
+        eprintln!("⚠️  Price resolver: {}: {}", market_id, error);
+        self.failures.push(ResolverFailure { market_id: market_id.to_string(), error });
+    }
+}
+
+/// Refresh the resolver's balance against the bond it must post
+pub fn check_funding(app: &mut AppState) -> bool {
+    let required_bond = app.disputes.min_bond;
+    let address = app.config.resolution.resolver_address.clone();
+    let balance = app.ledger.balance(&address);
+    let status = &mut app.resolver;
+    status.address = address;
+    status.balance = balance;
+    status.required_bond = required_bond;
+    status.funded = balance >= required_bond;
+    status.funded
+}
+
+/// How a due market is decided
+enum DueRule {
+    Expressions(CompiledRules),
+    Price(PriceRule),
+}
+
+/// Rule for a market that has reached its resolution date, or why its
+/// rules cannot decide it
+fn due_rule(market: &PredictionMarket, now: u64) -> Option<Result<DueRule, String>> {
+    if market.is_resolved {
+        return None;
+    }
+    let resolution_at = market.dates.as_ref()
+        .and_then(|d| d.resolution.as_deref())
+        .and_then(parse_resolution_date)?;
+    if resolution_at > now {
+        return None;
+    }
+    let rules = market.resolution_rules.as_ref()?;
+    if let Some(outcome_rules) = &rules.outcome_rules {
+        return Some(CompiledRules::compile(outcome_rules, &market.options)
+            .map(DueRule::Expressions)
+            .map_err(|e| format!("invalid outcome rules: {}", e)));
+    }
+    Some(PriceRule::from_conditions(rules.conditions.as_ref()?)
+        .map(DueRule::Price)
+        .map_err(|e| format!("invalid price conditions: {}", e)))
+}
+
+/// Decide a market's winning outcome from oracle data
+async fn decide(
+    market_id: &str,
+    rule: &DueRule,
+    oracle: &mut OracleManager,
+    now: u64,
+) -> Result<(usize, serde_json::Value), String> {
+    match rule {
+        DueRule::Price(rule) => {
+            let resolution = oracle
+                .resolve_price_market(market_id, &rule.asset, rule.target_price, rule.condition.clone())
+                .await?;
+            Ok((resolution.winning_outcome, resolution.resolution_data))
+        }
+        DueRule::Expressions(rules) => {
+            let mut data = HashMap::new();
+            for name in rules.data_points() {
+                let consensus = oracle.get_consensus_price(&name).await?;
+                data.insert(name, consensus.price);
+            }
+            let evaluation = rules.evaluate(&data, now).map_err(|e| e.to_string())?;
+            let winner = evaluation.winning_outcome.ok_or_else(|| {
+                format!("ambiguous rules: outcomes {:?} all matched", evaluation.matched)
+            })?;
+            Ok((winner, serde_json::json!({ "data": data, "evaluation": evaluation })))
+        }
+    }
+}
+
+/// Run one resolver pass. Returns the market IDs that were proposed.
+///
+/// Proposes nothing while the resolver cannot cover a bond; the due markets
+/// are listed as failures in its status instead.
+pub async fn run_once(state: &SharedState, oracle: &mut OracleManager) -> Vec<String> {
+    let due: Vec<(String, DueRule)> = {
+        let mut app = state.lock().unwrap();
+        let now = now();
+        let funded = check_funding(&mut app);
+        let due: Vec<(String, Result<DueRule, String>)> = app.markets.iter()
+            .filter(|(id, _)| app.disputes.get(id).is_none())
+            .filter_map(|(id, market)| due_rule(market, now).map(|rule| (id.clone(), rule)))
+            .collect();
+
+        let status = &mut app.resolver;
+        status.last_run = Some(now);
+        status.failures.clear();
+        let mut decidable = Vec::new();
+        for (market_id, rule) in due {
+            match rule {
+                Ok(rule) => decidable.push((market_id, rule)),
+                Err(e) => status.fail(&market_id, e),
+            }
+        }
+        if !funded {
+            let reason = status.unfunded_reason();
+            for (market_id, _) in &decidable {
+                status.fail(market_id, reason.clone());
+            }
+            return Vec::new();
+        }
+        decidable
+    };
+
+    let mut proposed = Vec::new();
+    for (market_id, rule) in due {
+        let (winning_outcome, resolution_data) = match decide(&market_id, &rule, oracle, now()).await {
+            Ok(decision) => decision,
+            Err(e) => {
+                state.lock().unwrap().resolver.fail(&market_id, e);
+                continue;
+            }
+        };
+
+        let mut app = state.lock().unwrap();
+        let resolver = app.config.resolution.resolver_address.clone();
+        match open_resolution_proposal(
+            &mut app,
+            &market_id,
+            winning_outcome,
+            &resolver,
+            None,
+            Some(resolution_data.to_string()),
+            now(),
+        ) {
+            Ok(_) => {
+                app.resolver.proposed += 1;
+                proposed.push(market_id);
+            }
+            Err(e) => app.resolver.fail(&market_id, format!("could not propose: {}", e)),
+        }
+        check_funding(&mut app);
+    }
+    proposed
+}
+
+/// Spawn the background price resolver (every `resolution.resolver_interval_secs`)
+pub fn spawn(state: SharedState) {
+    let (interval_secs, mut oracle) = {
+        let mut app = state.lock().unwrap();
+        if !check_funding(&mut app) {
+            eprintln!("🚨 Price resolver cannot bond proposals: {}. Automatic resolutions stay off until it is funded (GET /resolver/status).",
+                app.resolver.unfunded_reason()
+            );
+        }
+        (app.config.resolution.resolver_interval_secs, app.config.resolution.oracle())
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            run_once(&state, &mut oracle).await;
+        }
+    });
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::test_state;
+    use crate::easteregg::{DataFeedType, LocalFeed};
+    use crate::models::{MarketDates, ResolutionRules};
+
+    /// "Will BTC reach $100k?" whose resolution date has passed
+    fn due_btc_market(id: &str) -> PredictionMarket {
+        let mut market = PredictionMarket::new(
+            id.to_string(),
+            "Will BTC reach $100k?".to_string(),
+            "Resolves from spot price".to_string(),
+            "crypto".to_string(),
+            vec!["Yes".to_string(), "No".to_string()],
+        );
+        market.dates = Some(MarketDates { published: None, freeze: None, resolution: Some("2020-01-01".to_string()) });
+        let conditions = [("asset", "BTC"), ("target_price", "100000"), ("condition", ">=")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        market.resolution_rules = Some(ResolutionRules { conditions: Some(conditions), ..Default::default() });
+        market
+    }
+
+    #[tokio::test]
+    async fn test_unfunded_resolver_reports_due_markets() {
+        let state = test_state("resolver_unfunded");
+        {
+            let mut app = state.lock().unwrap();
+            app.config.resolution.resolver_address = "RESOLVER".to_string();
+            app.markets.insert("btc_100k".to_string(), due_btc_market("btc_100k"));
+        }
+        let prices = HashMap::from([("BTC".to_string(), 120_000.0)]);
+        let mut oracle = OracleManager::with_feeds(vec![DataFeedType::Local(LocalFeed::with_prices(prices))], 1);
+
+        // RESOLVER holds nothing: the due market is reported, not proposed
+        assert!(run_once(&state, &mut oracle).await.is_empty());
+        {
+            let app = state.lock().unwrap();
+            assert!(app.disputes.get("btc_100k").is_none());
+            let status = &app.resolver;
+            assert!(!status.funded);
+            assert_eq!(status.address, "RESOLVER");
+            assert_eq!(status.required_bond, app.disputes.min_bond);
+            assert_eq!(status.failures.len(), 1);
+            assert_eq!(status.failures[0].market_id, "btc_100k");
+            assert!(status.failures[0].error.contains("bonds"), "{}", status.failures[0].error);
+        }
+
+        // Once funded the next pass proposes it
+        let bond = state.lock().unwrap().disputes.min_bond;
+        state.ledger.credit("RESOLVER", bond);
+        assert_eq!(run_once(&state, &mut oracle).await, vec!["btc_100k".to_string()]);
+        let app = state.lock().unwrap();
+        let proposal = app.disputes.get("btc_100k").unwrap();
+        assert_eq!(proposal.proposed_outcome, 0);
+        assert_eq!(proposal.proposer, "RESOLVER");
+        assert_eq!(app.resolver.proposed, 1);
+        assert!(app.resolver.failures.is_empty());
+        assert!(!app.resolver.funded, "the bond used up the balance");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_rules_are_reported() {
+        let state = test_state("resolver_invalid_rules");
+        {
+            let mut app = state.lock().unwrap();
+            app.config.resolution.resolver_address = "RESOLVER".to_string();
+            let mut market = due_btc_market("btc_typo");
+            market.resolution_rules.as_mut().unwrap().conditions.as_mut().unwrap()
+                .insert("target_price".to_string(), "a lot".to_string());
+            app.markets.insert("btc_typo".to_string(), market);
+        }
+        let bond = state.lock().unwrap().disputes.min_bond;
+        state.ledger.credit("RESOLVER", bond);
+        let mut oracle = OracleManager::with_feeds(vec![DataFeedType::Local(LocalFeed::with_prices(HashMap::new()))], 1);
+
+        assert!(run_once(&state, &mut oracle).await.is_empty());
+        let app = state.lock().unwrap();
+        assert!(app.disputes.get("btc_typo").is_none());
+        assert_eq!(app.resolver.failures.len(), 1);
+        assert_eq!(app.resolver.failures[0].market_id, "btc_typo");
+        assert!(app.resolver.failures[0].error.contains("price conditions"), "{}", app.resolver.failures[0].error);
+    }
+}
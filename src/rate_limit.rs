@@ -0,0 +1,458 @@
+//! Rate Limiting
+//!
+//! Token buckets keyed by route class and client. Every request draws one
+//! token from its client IP's bucket, and a request whose signature checks
+//! out also draws from its signer's wallet bucket, so neither rotating
+//! wallets from one host nor spreading one wallet across hosts gets around
+//! the limits. The signer is the `X-BlackBook-Key` of a role-signed request,
+//! or the sender of a `SignedTransaction` body. Unsigned or badly signed
+//! requests are limited by IP only, so nobody can drain another wallet's
+//! bucket by naming it.
+//!
+//! Route classes have separate limits:
+//!
+//! - `admin` - resolution, oracle, dealer, settlement and L1 sync routes
+//! - `read`  - every other GET
+//! - `trade` - every other write (orders, bets, shares, `/auth/connect`)
+//!
+//! A drained bucket answers 429 `RATE_LIMITED` with `Retry-After`.
+//! Requests carrying a whitelisted `X-Api-Key` (market makers) skip every
+//! limit.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderValue, Method};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::http::request::Parts;
+
+use crate::api_error::ApiError;
+use crate::config::{BucketLimit, ClassLimits, RateLimitConfig};
+use crate::roles::{SignedRequest, MAX_SIGNED_BODY_BYTES};
+use crate::rpc::SignedTransaction;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Header carrying a market maker's exemption key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Largest body read to find the signer. A declared bigger one is limited
+/// by IP only; a chunked one that grows past it is refused.
+pub const MAX_WALLET_BODY_BYTES: usize = MAX_SIGNED_BODY_BYTES;
+
+/// Path prefixes of operator routes
+const ADMIN_PREFIXES: &[&str] = &[
+    "/admin", "/resolve/", "/dealer", "/markets/initial-liquidity", "/settle", "/sync",
+    "/state/commit", "/bridge/l1-roots",
+];
+
+/// Bucket count above which full (idle) buckets are dropped
+const PRUNE_THRESHOLD: usize = 10_000;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    Read,
+    Trade,
+    Admin,
+}
+
+impl RouteClass {
+    pub fn of(method: &Method, path: &str) -> Self {
+        if ADMIN_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+            RouteClass::Admin
+        } else if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            RouteClass::Read
+        } else {
+            RouteClass::Trade
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteClass::Read => "read",
+            RouteClass::Trade => "trade",
+            RouteClass::Admin => "admin",
+        }
+    }
+}
+
+/// Who a bucket belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Ip,
+    Wallet,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Ip => "ip",
+            Scope::Wallet => "wallet",
+        }
+    }
+}
+
+/// A request refused for an empty bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimited {
+    pub class: RouteClass,
+    pub scope: Scope,
+    /// Seconds until the bucket has a token again
+    pub retry_after: u64,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Rate limit exceeded for {} requests per {}; retry in {}s",
+            self.class.as_str(), self.scope.as_str(), self.retry_after
+        )
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Tokens after refilling up to `now`
+    fn level(&self, limit: BucketLimit, now: Instant) -> f64 {
+        let refill = now.saturating_duration_since(self.updated).as_secs_f64() * per_sec(limit);
+        (self.tokens + refill).min(limit.burst as f64)
+    }
+}
+
+fn per_sec(limit: BucketLimit) -> f64 {
+    limit.per_minute as f64 / 60.0
+}
+
+// ============================================================================
+// LIMITER
+// ============================================================================
+
+type BucketKey = (RouteClass, Scope, String);
+
+/// Buckets for every (class, scope, client), shared by all requests
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    config: Arc<RateLimitConfig>,
+    buckets: Arc<Mutex<HashMap<BucketKey, Bucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config: Arc::new(config), buckets: Arc::default() }
+    }
+
+    pub fn is_exempt(&self, api_key: &str) -> bool {
+        self.config.exempt_keys.iter().any(|key| key == api_key)
+    }
+
+    fn limit(&self, class: RouteClass, scope: Scope) -> BucketLimit {
+        let limits: &ClassLimits = match class {
+            RouteClass::Read => &self.config.read,
+            RouteClass::Trade => &self.config.trade,
+            RouteClass::Admin => &self.config.admin,
+        };
+        match scope {
+            Scope::Ip => limits.per_ip,
+            Scope::Wallet => limits.per_wallet,
+        }
+    }
+
+    /// Take a token from `client`'s bucket, or say how long until one is free
+    pub fn check(&self, class: RouteClass, scope: Scope, client: &str, now: Instant) -> Result<(), RateLimited> {
+        let limit = self.limit(class, scope);
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|(c, s, _), b| {
+                let limit = self.limit(*c, *s);
+                b.level(limit, now) < limit.burst as f64
+            });
+        }
+
+        let bucket = buckets
+            .entry((class, scope, client.to_string()))
+            .or_insert(Bucket { tokens: limit.burst as f64, updated: now });
+        bucket.tokens = bucket.level(limit, now);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        let retry_after = ((1.0 - bucket.tokens) / per_sec(limit)).ceil().max(1.0) as u64;
+        Err(RateLimited { class, scope, retry_after })
+    }
+}
+
+/// The wallet whose signature over this request verifies, if any
+pub fn verified_wallet(parts: &Parts, body: &[u8], now: u64) -> Option<String> {
+    let text = String::from_utf8(body.to_vec()).ok()?;
+    if let Ok(request) = SignedRequest::from_parts(parts, text) {
+        return request.verify_at(now).is_ok().then_some(request.signer);
+    }
+    let tx: SignedTransaction = serde_json::from_slice(body).ok()?;
+    let valid = matches!(tx.verify(), Ok(true)) && !tx.is_expired();
+    // sender_address is not signed: the wallet is the signing key's
+    valid.then(|| tx.signer_address())
+}
+
+/// The client's address: the last `X-Forwarded-For` hop when trusted,
+/// else the socket peer. Only the last hop was appended by our proxy;
+/// earlier entries come from the client and can be forged.
+pub fn client_ip(parts: &Parts, trust_forwarded_for: bool) -> String {
+    let forwarded = trust_forwarded_for
+        .then(|| parts.headers.get("x-forwarded-for"))
+        .flatten()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| !ip.is_empty());
+    forwarded
+        .or_else(|| parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|info| info.0.ip().to_string()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn too_many_requests(limited: RateLimited) -> Response {
+    let retry_after = limited.retry_after;
+    let mut response = ApiError::from(limited).into_response();
+    response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+    response
+}
+
+// ============================================================================
+// MIDDLEWARE
+// ============================================================================
+
+/// Middleware: throttle each request by client IP, then by signing wallet
+pub async fn rate_limit_guard(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !limiter.config.enabled {
+        return next.run(request).await;
+    }
+    let exempt = request.headers().get(API_KEY_HEADER)
+        .and_then(|key| key.to_str().ok())
+        .is_some_and(|key| limiter.is_exempt(key));
+    if exempt {
+        return next.run(request).await;
+    }
+
+    let class = RouteClass::of(request.method(), request.uri().path());
+    let now = Instant::now();
+    let (parts, body) = request.into_parts();
+    let ip = client_ip(&parts, limiter.config.trust_forwarded_for);
+    if let Err(limited) = limiter.check(class, Scope::Ip, &ip, now) {
+        return too_many_requests(limited);
+    }
+
+    // Chunked bodies are buffered too; only a declared oversize body, which
+    // the handler's extractor refuses anyway, skips the wallet bucket
+    let oversize = parts.headers.get(header::CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok()?.parse::<usize>().ok())
+        .is_some_and(|len| len > MAX_WALLET_BODY_BYTES);
+    if oversize {
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+
+    let Ok(body) = axum::body::to_bytes(body, MAX_WALLET_BODY_BYTES).await else {
+        return ApiError::BadRequest(format!("Request body over {} bytes", MAX_WALLET_BODY_BYTES)).into_response();
+    };
+    let unix_now = chrono::Utc::now().timestamp() as u64;
+    if let Some(wallet) = verified_wallet(&parts, &body, unix_now) {
+        if let Err(limited) = limiter.check(class, Scope::Wallet, &wallet, now) {
+            return too_many_requests(limited);
+        }
+    }
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::Router;
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::time::Duration;
+    use tower::Service;
+
+    use crate::roles::{SIGNATURE_HEADER, SIGNER_HEADER, TIMESTAMP_HEADER};
+
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(RateLimitConfig {
+            exempt_keys: vec!["mm-key".into()],
+            trade: ClassLimits { per_ip: BucketLimit::new(5, 60), per_wallet: BucketLimit::new(2, 60) },
+            ..RateLimitConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_bucket_drains_and_refills() {
+        let limiter = limiter();
+        let start = Instant::now();
+        for _ in 0..2 {
+            assert!(limiter.check(RouteClass::Trade, Scope::Wallet, "L1_A", start).is_ok());
+        }
+        let limited = limiter.check(RouteClass::Trade, Scope::Wallet, "L1_A", start).unwrap_err();
+        assert_eq!((limited.scope, limited.retry_after), (Scope::Wallet, 1));
+
+        // Other wallets and classes have their own buckets
+        assert!(limiter.check(RouteClass::Trade, Scope::Wallet, "L1_B", start).is_ok());
+        assert!(limiter.check(RouteClass::Read, Scope::Wallet, "L1_A", start).is_ok());
+
+        // 60/minute refills one token a second
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.check(RouteClass::Trade, Scope::Wallet, "L1_A", later).is_ok());
+        assert!(limiter.check(RouteClass::Trade, Scope::Wallet, "L1_A", later).is_err());
+    }
+
+    #[test]
+    fn test_route_classes() {
+        assert_eq!(RouteClass::of(&Method::GET, "/markets"), RouteClass::Read);
+        assert_eq!(RouteClass::of(&Method::POST, "/orders"), RouteClass::Trade);
+        assert_eq!(RouteClass::of(&Method::POST, "/auth/connect"), RouteClass::Trade);
+        assert_eq!(RouteClass::of(&Method::POST, "/admin/freeze"), RouteClass::Admin);
+        assert_eq!(RouteClass::of(&Method::GET, "/dealer/positions/L1_A"), RouteClass::Admin);
+    }
+
+    #[test]
+    fn test_signed_transaction_wallet_is_signing_key() {
+        use crate::easteregg::GodMode;
+        use crate::rpc::TransactionPayload;
+
+        let godmode = GodMode::new();
+        let alice = godmode.get_account("alice").unwrap().address.clone();
+        let mut tx = SignedTransaction::new(&godmode, "alice", 1, TransactionPayload::ForcedExit {
+            target_address: alice.clone(),
+        }).unwrap();
+        // Claiming someone else's address must not move the request into
+        // their rate bucket or idempotency scope
+        tx.sender_address = godmode.get_account("bob").unwrap().address.clone();
+        let body = serde_json::to_vec(&tx).unwrap();
+        let (parts, _) = Request::post("/exits").body(()).unwrap().into_parts();
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        assert_eq!(verified_wallet(&parts, &body, now), Some(alice.clone()));
+        assert_eq!(
+            crate::idempotency::scope(&parts, &body, false, now),
+            format!("signer:{} POST /exits", alice)
+        );
+    }
+
+    #[test]
+    fn test_client_ip_ignores_forged_forwarded_hops() {
+        let (parts, _) = Request::post("/orders")
+            .header("x-forwarded-for", "1.2.3.4, 203.0.113.9")
+            .body(())
+            .unwrap()
+            .into_parts();
+        // The client wrote 1.2.3.4; the proxy appended the address it saw
+        assert_eq!(client_ip(&parts, true), "203.0.113.9");
+        assert_eq!(client_ip(&parts, false), "unknown");
+        assert_eq!(
+            crate::idempotency::scope(&parts, b"{}", true, 0),
+            "ip:203.0.113.9 POST /orders"
+        );
+    }
+
+    enum Signature {
+        Unsigned,
+        Valid,
+        Forged,
+    }
+
+    struct Call {
+        body: &'static str,
+        signature: Signature,
+        chunked: bool,
+        api_key: Option<&'static str>,
+    }
+
+    impl Call {
+        fn new(body: &'static str) -> Self {
+            Call { body, signature: Signature::Unsigned, chunked: false, api_key: None }
+        }
+    }
+
+    async fn send(app: &Router, call: Call) -> Response {
+        let mut request = Request::post("/orders").header(header::CONTENT_TYPE, "application/json");
+        if !call.chunked {
+            request = request.header(header::CONTENT_LENGTH, call.body.len());
+        }
+        if let Some(key) = call.api_key {
+            request = request.header(API_KEY_HEADER, key);
+        }
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let timestamp = chrono::Utc::now().timestamp() as u64;
+        let signed_body = match call.signature {
+            Signature::Unsigned => None,
+            Signature::Valid => Some(call.body),
+            Signature::Forged => Some("{}"),
+        };
+        if let Some(signed_body) = signed_body {
+            let message = SignedRequest::signing_message("POST", "/orders", timestamp, signed_body.as_bytes());
+            request = request
+                .header(SIGNER_HEADER, hex::encode(key.verifying_key().as_bytes()))
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(SIGNATURE_HEADER, hex::encode(key.sign(message.as_bytes()).to_bytes()));
+        }
+        app.clone().call(request.body(Body::from(call.body)).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_middleware_limits_verified_signer_and_exempts_market_makers() {
+        let app = Router::new()
+            .route("/orders", post(|body: String| async move { body }))
+            .layer(axum::middleware::from_fn_with_state(limiter(), rate_limit_guard));
+
+        let order = r#"{"price":0.5}"#;
+        let signed = || Call { signature: Signature::Valid, ..Call::new(order) };
+        for _ in 0..2 {
+            assert_eq!(send(&app, signed()).await.status(), StatusCode::OK);
+        }
+        let limited = send(&app, signed()).await;
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[header::RETRY_AFTER], "1");
+
+        // A forged signature naming the same key only draws from the IP
+        // bucket, and the body still reaches the handler
+        let forged = send(&app, Call { signature: Signature::Forged, ..Call::new(order) }).await;
+        assert_eq!(forged.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(forged.into_body(), 1024).await.unwrap();
+        assert_eq!(&body[..], order.as_bytes());
+
+        // A chunked body is still read for its signer
+        let chunked = send(&app, Call { chunked: true, ..signed() }).await;
+        assert_eq!(chunked.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // The IP bucket (5) is drained, but market makers skip it
+        for _ in 0..10 {
+            assert_eq!(send(&app, Call { api_key: Some("mm-key"), ..signed() }).await.status(), StatusCode::OK);
+        }
+        assert_eq!(send(&app, Call::new(order)).await.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+}
@@ -0,0 +1,621 @@
+//! Roles
+//!
+//! Privileged routes are served only to requests signed by a key holding the
+//! route's role. Keys are Ed25519 public keys (hex, or the `L1_<PUBKEY>`
+//! address form). Admins hold every role implicitly.
+//!
+//! | role           | routes                                                   |
+//! |----------------|----------------------------------------------------------|
+//! | `admin`        | `/admin/*`, `/resolve/*`, settlement, state commits      |
+//! | `oracle`       | market resolution proposals, dispute arbitration         |
+//! | `dealer`       | `/dealer/*`, `/markets/initial-liquidity`                |
+//! | `moderator`    | pending event inbox curation                             |
+//! | `market_maker` | reserved for CLOB market-maker programs                  |
+//!
+//! A signed request carries three headers:
+//!
+//! ```text
+//! X-BlackBook-Key:       <public key hex>
+//! X-BlackBook-Timestamp: <unix seconds>
+//! X-BlackBook-Signature: <Ed25519 signature hex> over
+//!                        "<METHOD> <path?query>\n<timestamp>\n<sha256(body) hex>"
+//! ```
+//!
+//! Signatures older than `SIGNATURE_WINDOW_SECS` or seen before are refused.
+//! The [`Authorized`] extractor checks all of this and hands the handler the
+//! parsed body together with the [`SignedRequest`] as evidence.
+//!
+//! Roles start from the `[roles]` config section. Grants and revocations are
+//! signed admin requests; each is kept (with that request) in a log persisted
+//! to disk and re-verified when it is replayed on startup.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use axum::async_trait;
+use axum::extract::{FromRef, FromRequest, Request};
+use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use crate::api_error::ApiError;
+use crate::bridge_proof::verify_ed25519;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+pub const SIGNER_HEADER: &str = "x-blackbook-key";
+pub const TIMESTAMP_HEADER: &str = "x-blackbook-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-blackbook-signature";
+
+/// How far a signed request's timestamp may be from the server clock
+pub const SIGNATURE_WINDOW_SECS: u64 = 300;
+
+/// Where role grants and revocations are persisted
+pub const DEFAULT_ROLES_PATH: &str = "data/roles.json";
+
+/// Largest signed request body accepted
+pub const MAX_SIGNED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+// ============================================================================
+// ROLES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Oracle,
+    Dealer,
+    MarketMaker,
+    Moderator,
+}
+
+impl Role {
+    pub const ALL: [Role; 5] = [Role::Admin, Role::Oracle, Role::Dealer, Role::MarketMaker, Role::Moderator];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Oracle => "oracle",
+            Role::Dealer => "dealer",
+            Role::MarketMaker => "market_maker",
+            Role::Moderator => "moderator",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoleAction {
+    Grant,
+    Revoke,
+}
+
+/// Canonical form of a key: lowercase public key hex, or None if `key` is
+/// neither a public key nor an `L1_` address of one
+pub fn normalize_key(key: &str) -> Option<String> {
+    let hex_key = key.strip_prefix("L1_").or_else(|| key.strip_prefix("l1_")).unwrap_or(key);
+    let valid = hex_key.len() == 64 && hex_key.bytes().all(|b| b.is_ascii_hexdigit());
+    valid.then(|| hex_key.to_lowercase())
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoleError {
+    /// A signature header is missing
+    Unsigned(&'static str),
+    InvalidSignature,
+    /// Timestamp outside `SIGNATURE_WINDOW_SECS`
+    Expired { timestamp: u64, now: u64 },
+    /// The same signature was already used
+    Replayed,
+    /// The signer lacks the role
+    Forbidden { role: Role, signer: String },
+    /// Not an Ed25519 public key
+    InvalidKey(String),
+    /// Revoking the only admin would lock everyone out
+    LastAdmin,
+    /// The key already has (or already lacks) the role
+    Unchanged { role: Role, subject: String },
+}
+
+impl fmt::Display for RoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoleError::Unsigned(header) => write!(f, "Signed request required: missing {} header", header),
+            RoleError::InvalidSignature => write!(f, "Request signature does not match the signing key"),
+            RoleError::Expired { timestamp, now } => write!(
+                f,
+                "Signed request timestamp {} is more than {}s from server time {}",
+                timestamp, SIGNATURE_WINDOW_SECS, now
+            ),
+            RoleError::Replayed => write!(f, "Signed request was already used"),
+            RoleError::Forbidden { role, signer } => write!(f, "Key {} does not have the {} role", signer, role.as_str()),
+            RoleError::InvalidKey(key) => write!(f, "Not an Ed25519 public key: {}", key),
+            RoleError::LastAdmin => write!(f, "Cannot revoke the last admin"),
+            RoleError::Unchanged { role, subject } => write!(f, "Key {} already has that {} role state", subject, role.as_str()),
+        }
+    }
+}
+
+impl std::error::Error for RoleError {}
+
+// ============================================================================
+// SIGNED REQUESTS
+// ============================================================================
+
+/// A privileged request as signed by its caller, kept as evidence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SignedRequest {
+    /// Signer's public key (lowercase hex)
+    pub signer: String,
+    pub timestamp: u64,
+    pub signature: String,
+    pub method: String,
+    /// Path and query as requested
+    pub path: String,
+    /// Request body as sent
+    pub body: String,
+}
+
+impl SignedRequest {
+    /// The bytes a caller signs
+    pub fn signing_message(method: &str, path: &str, timestamp: u64, body: &[u8]) -> String {
+        format!("{} {}\n{}\n{}", method, path, timestamp, hex::encode(Sha256::digest(body)))
+    }
+
+    /// Read the signature headers of a request whose body has been buffered
+    pub fn from_parts(parts: &Parts, body: String) -> Result<Self, RoleError> {
+        let header = |name: &'static str| {
+            parts.headers.get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .ok_or(RoleError::Unsigned(name))
+        };
+        let raw_signer = header(SIGNER_HEADER)?;
+        let signer = normalize_key(raw_signer).ok_or_else(|| RoleError::InvalidKey(raw_signer.to_string()))?;
+        let timestamp = header(TIMESTAMP_HEADER)?.parse::<u64>().map_err(|_| RoleError::Unsigned(TIMESTAMP_HEADER))?;
+        let signature = header(SIGNATURE_HEADER)?.to_lowercase();
+        let path = parts.uri.path_and_query().map(|p| p.as_str()).unwrap_or(parts.uri.path());
+        Ok(Self {
+            signer,
+            timestamp,
+            signature,
+            method: parts.method.to_string(),
+            path: path.to_string(),
+            body,
+        })
+    }
+
+    /// Signature check only (no clock or replay checks)
+    pub fn verify(&self) -> bool {
+        let message = Self::signing_message(&self.method, &self.path, self.timestamp, self.body.as_bytes());
+        verify_ed25519(&self.signer, &self.signature, message.as_bytes())
+    }
+
+    /// Clock and signature checks (no replay check)
+    pub fn verify_at(&self, now: u64) -> Result<(), RoleError> {
+        if now.abs_diff(self.timestamp) > SIGNATURE_WINDOW_SECS {
+            return Err(RoleError::Expired { timestamp: self.timestamp, now });
+        }
+        if !self.verify() {
+            return Err(RoleError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// The signer's ledger address
+    pub fn address(&self) -> String {
+        format!("L1_{}", self.signer.to_uppercase())
+    }
+}
+
+/// A grant or revocation, with the admin request that made it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RoleChange {
+    pub action: RoleAction,
+    pub role: Role,
+    /// Public key (lowercase hex) gaining or losing the role
+    pub subject: String,
+    pub at: u64,
+    pub request: SignedRequest,
+}
+
+// ============================================================================
+// REGISTRY
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct Registry {
+    members: HashMap<String, BTreeSet<Role>>,
+    changes: Vec<RoleChange>,
+    /// Signatures used within the window, with their timestamps
+    seen: HashMap<String, u64>,
+}
+
+impl Registry {
+    fn has_role(&self, key: &str, role: Role) -> bool {
+        self.members.get(key).is_some_and(|roles| roles.contains(&role) || roles.contains(&Role::Admin))
+    }
+
+    fn admin_count(&self) -> usize {
+        self.members.values().filter(|roles| roles.contains(&Role::Admin)).count()
+    }
+
+    /// Check `change` against the current members and apply it
+    fn apply(&mut self, change: &RoleChange) -> Result<(), RoleError> {
+        if !self.has_role(&change.request.signer, Role::Admin) {
+            return Err(RoleError::Forbidden { role: Role::Admin, signer: change.request.signer.clone() });
+        }
+        let holds = self.members.get(&change.subject).is_some_and(|roles| roles.contains(&change.role));
+        let unchanged = || RoleError::Unchanged { role: change.role, subject: change.subject.clone() };
+        match change.action {
+            RoleAction::Grant if holds => return Err(unchanged()),
+            RoleAction::Revoke if !holds => return Err(unchanged()),
+            RoleAction::Revoke if change.role == Role::Admin && self.admin_count() == 1 => {
+                return Err(RoleError::LastAdmin);
+            }
+            RoleAction::Grant => {
+                self.members.entry(change.subject.clone()).or_default().insert(change.role);
+            }
+            RoleAction::Revoke => {
+                if let Some(roles) = self.members.get_mut(&change.subject) {
+                    roles.remove(&change.role);
+                }
+                self.members.retain(|_, roles| !roles.is_empty());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Who holds which role, shared by every request
+#[derive(Debug, Clone, Default)]
+pub struct RoleRegistry {
+    inner: Arc<RwLock<Registry>>,
+    /// Where the change log is persisted; None keeps it in memory
+    path: Option<PathBuf>,
+}
+
+impl RoleRegistry {
+    /// Registry holding the `bootstrap` roles (tests, or before any grants)
+    pub fn new(bootstrap: &[(Role, String)]) -> Self {
+        let mut registry = Registry::default();
+        for (role, key) in bootstrap {
+            if let Some(key) = normalize_key(key) {
+                registry.members.entry(key).or_default().insert(*role);
+            }
+        }
+        Self { inner: Arc::new(RwLock::new(registry)), path: None }
+    }
+
+    /// Registry persisted at `path`: the `bootstrap` roles plus every change
+    /// logged there that still verifies. Returns the number of changes
+    /// dropped because they no longer do.
+    pub fn open(path: impl Into<PathBuf>, bootstrap: &[(Role, String)]) -> Result<(Self, usize), String> {
+        let path = path.into();
+        let mut registry = Self { path: Some(path.clone()), ..Self::new(bootstrap) };
+        let changes: Vec<RoleChange> = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Failed to deserialize roles: {}", e))?,
+            Err(_) => return Ok((registry, 0)),
+        };
+
+        let mut dropped = 0;
+        {
+            let inner = Arc::get_mut(&mut registry.inner).expect("registry not shared yet");
+            let inner = inner.get_mut().unwrap();
+            for change in changes {
+                if change.request.verify() && inner.apply(&change).is_ok() {
+                    inner.changes.push(change);
+                } else {
+                    dropped += 1;
+                }
+            }
+        }
+        Ok((registry, dropped))
+    }
+
+    pub fn has_role(&self, key: &str, role: Role) -> bool {
+        normalize_key(key).is_some_and(|key| self.inner.read().unwrap().has_role(&key, role))
+    }
+
+    /// Keys holding `role` explicitly (admins are not listed under other roles)
+    pub fn holders(&self, role: Role) -> Vec<String> {
+        let inner = self.inner.read().unwrap();
+        let mut keys: Vec<String> = inner.members.iter()
+            .filter(|(_, roles)| roles.contains(&role))
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Every key with its roles
+    pub fn members(&self) -> BTreeMap<String, Vec<Role>> {
+        self.inner.read().unwrap().members.iter()
+            .map(|(key, roles)| (key.clone(), roles.iter().copied().collect()))
+            .collect()
+    }
+
+    /// Grants and revocations, oldest first
+    pub fn changes(&self) -> Vec<RoleChange> {
+        self.inner.read().unwrap().changes.clone()
+    }
+
+    /// Check a signed request's clock, signature, freshness and role
+    pub fn authorize(&self, request: &SignedRequest, role: Role, now: u64) -> Result<(), RoleError> {
+        request.verify_at(now)?;
+
+        let mut inner = self.inner.write().unwrap();
+        if !inner.has_role(&request.signer, role) {
+            return Err(RoleError::Forbidden { role, signer: request.signer.clone() });
+        }
+        inner.seen.retain(|_, timestamp| now.abs_diff(*timestamp) <= SIGNATURE_WINDOW_SECS);
+        if inner.seen.insert(request.signature.clone(), request.timestamp).is_some() {
+            return Err(RoleError::Replayed);
+        }
+        Ok(())
+    }
+
+    /// Grant or revoke `role` for `subject` on an (authorized) admin request
+    pub fn change(
+        &self,
+        action: RoleAction,
+        role: Role,
+        subject: &str,
+        request: SignedRequest,
+        now: u64,
+    ) -> Result<RoleChange, RoleError> {
+        let subject = normalize_key(subject).ok_or_else(|| RoleError::InvalidKey(subject.to_string()))?;
+        let change = RoleChange { action, role, subject, at: now, request };
+        let json = {
+            let mut inner = self.inner.write().unwrap();
+            inner.apply(&change)?;
+            inner.changes.push(change.clone());
+            self.path.as_ref().and_then(|_| serde_json::to_string_pretty(&inner.changes).ok())
+        };
+        if let (Some(path), Some(json)) = (&self.path, json) {
+            if let Err(e) = write_atomically(path, &json) {
+                eprintln!("⚠️  Failed to persist roles: {}", e);
+            }
+        }
+        Ok(change)
+    }
+}
+
+/// Write-then-rename so a crash mid-write keeps the previous file
+fn write_atomically(path: &PathBuf, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, contents).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// EXTRACTOR
+// ============================================================================
+
+/// The role a route requires, as a type for [`Authorized`]
+pub trait RequiredRole {
+    const ROLE: Role;
+}
+
+pub struct AdminRole;
+pub struct OracleRole;
+pub struct DealerRole;
+pub struct ModeratorRole;
+
+impl RequiredRole for AdminRole {
+    const ROLE: Role = Role::Admin;
+}
+impl RequiredRole for OracleRole {
+    const ROLE: Role = Role::Oracle;
+}
+impl RequiredRole for DealerRole {
+    const ROLE: Role = Role::Dealer;
+}
+impl RequiredRole for ModeratorRole {
+    const ROLE: Role = Role::Moderator;
+}
+
+/// Extractor: a request signed by a key holding `R`'s role, with its JSON
+/// body parsed as `T` (an empty body reads as `null`, or `{}` if `T` is a
+/// struct).
+/// Must be the last extractor of a handler since it reads the body.
+pub struct Authorized<R, T = ()> {
+    pub body: T,
+    pub request: SignedRequest,
+    role: PhantomData<fn() -> R>,
+}
+
+impl<R, T> Authorized<R, T> {
+    /// Signer's public key (lowercase hex)
+    pub fn signer(&self) -> &str {
+        &self.request.signer
+    }
+
+    /// Signer's ledger address
+    pub fn address(&self) -> String {
+        self.request.address()
+    }
+}
+
+#[async_trait]
+impl<S, R, T> FromRequest<S> for Authorized<R, T>
+where
+    S: Send + Sync,
+    RoleRegistry: FromRef<S>,
+    R: RequiredRole,
+    T: DeserializeOwned,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let (parts, body) = request.into_parts();
+        let bytes = axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES).await
+            .map_err(|_| ApiError::BadRequest(format!("Request body over {} bytes", MAX_SIGNED_BODY_BYTES)))?;
+        let body = String::from_utf8(bytes.to_vec())
+            .map_err(|_| ApiError::BadRequest("Request body is not UTF-8".to_string()))?;
+        let request = SignedRequest::from_parts(&parts, body)?;
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        RoleRegistry::from_ref(state).authorize(&request, R::ROLE, now)?;
+
+        let body = if request.body.trim().is_empty() {
+            serde_json::from_str("null").or_else(|_| serde_json::from_str("{}"))
+        } else {
+            serde_json::from_str(&request.body)
+        };
+        let body = body.map_err(|e| ApiError::BadRequest(format!("Invalid request body: {}", e)))?;
+        Ok(Self { body, request, role: PhantomData })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::Router;
+    use ed25519_dalek::{Signer, SigningKey};
+    use tower::Service;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn pubkey(key: &SigningKey) -> String {
+        hex::encode(key.verifying_key().as_bytes())
+    }
+
+    fn sign(key: &SigningKey, method: &str, path: &str, timestamp: u64, body: &str) -> SignedRequest {
+        let message = SignedRequest::signing_message(method, path, timestamp, body.as_bytes());
+        SignedRequest {
+            signer: pubkey(key),
+            timestamp,
+            signature: hex::encode(key.sign(message.as_bytes()).to_bytes()),
+            method: method.to_string(),
+            path: path.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_authorize_checks_signature_clock_replay_and_role() {
+        let (admin, dealer) = (key(1), key(2));
+        let registry = RoleRegistry::new(&[(Role::Admin, pubkey(&admin)), (Role::Dealer, pubkey(&dealer))]);
+
+        let request = sign(&dealer, "POST", "/dealer/fund-all-markets", 1000, "{}");
+        assert_eq!(registry.authorize(&request, Role::Dealer, 1010), Ok(()));
+        assert_eq!(registry.authorize(&request, Role::Dealer, 1010), Err(RoleError::Replayed));
+
+        let request = sign(&dealer, "POST", "/admin/freeze", 1000, "{}");
+        assert!(matches!(registry.authorize(&request, Role::Admin, 1000), Err(RoleError::Forbidden { .. })));
+
+        // Admins hold every role
+        let request = sign(&admin, "POST", "/dealer/fund-all-markets", 1000, "{}");
+        assert_eq!(registry.authorize(&request, Role::Dealer, 1000), Ok(()));
+
+        let stale = sign(&admin, "POST", "/admin/freeze", 1000, "{}");
+        assert!(matches!(registry.authorize(&stale, Role::Admin, 2000), Err(RoleError::Expired { .. })));
+
+        let mut tampered = sign(&admin, "POST", "/admin/freeze", 1000, "{}");
+        tampered.body = "{\"reason\":\"other\"}".to_string();
+        assert_eq!(registry.authorize(&tampered, Role::Admin, 1000), Err(RoleError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_changes_persist_and_replay() {
+        let path = std::env::temp_dir().join(format!("roles_{}.json", uuid::Uuid::new_v4().simple()));
+        let (admin, oracle) = (key(1), key(3));
+        let bootstrap = [(Role::Admin, format!("L1_{}", pubkey(&admin).to_uppercase()))];
+        let (registry, _) = RoleRegistry::open(&path, &bootstrap).unwrap();
+
+        let grant = sign(&admin, "POST", "/admin/roles", 1000, "{}");
+        registry.change(RoleAction::Grant, Role::Oracle, &pubkey(&oracle), grant.clone(), 1000).unwrap();
+        assert!(registry.has_role(&pubkey(&oracle), Role::Oracle));
+        assert!(matches!(
+            registry.change(RoleAction::Grant, Role::Oracle, &pubkey(&oracle), grant, 1001),
+            Err(RoleError::Unchanged { .. })
+        ));
+        let revoke_admin = sign(&admin, "POST", "/admin/roles", 1002, "{}");
+        assert_eq!(
+            registry.change(RoleAction::Revoke, Role::Admin, &pubkey(&admin), revoke_admin, 1002),
+            Err(RoleError::LastAdmin)
+        );
+
+        let (reopened, dropped) = RoleRegistry::open(&path, &bootstrap).unwrap();
+        assert_eq!(dropped, 0);
+        assert_eq!(reopened.holders(Role::Oracle), vec![pubkey(&oracle)]);
+        assert_eq!(reopened.changes().len(), 1);
+
+        // Without the admin that signed it, the grant no longer replays
+        let (untrusted, dropped) = RoleRegistry::open(&path, &[]).unwrap();
+        assert_eq!(dropped, 1);
+        assert!(!untrusted.has_role(&pubkey(&oracle), Role::Oracle));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_role_names_and_keys() {
+        assert_eq!(serde_json::to_string(&Role::MarketMaker).unwrap(), "\"market_maker\"");
+        assert!(Role::ALL.iter().all(|role| serde_json::to_value(role).unwrap() == role.as_str()));
+        let hex_key = "ab".repeat(32);
+        assert_eq!(normalize_key(&format!("L1_{}", hex_key.to_uppercase())), Some(hex_key));
+        assert_eq!(normalize_key("ORACLE"), None);
+    }
+
+    #[tokio::test]
+    async fn test_extractor_rejects_unsigned_and_wrong_role() {
+        #[derive(Deserialize)]
+        struct Freeze {
+            reason: String,
+        }
+        let admin = key(1);
+        let registry = RoleRegistry::new(&[(Role::Admin, pubkey(&admin)), (Role::Moderator, pubkey(&key(4)))]);
+        let mut app = Router::new()
+            .route("/admin/freeze", post(|auth: Authorized<AdminRole, Freeze>| async move {
+                format!("{} by {}", auth.body.reason, auth.address())
+            }))
+            .with_state(registry);
+
+        let body = r#"{"reason":"outage"}"#;
+        let now = chrono::Utc::now().timestamp() as u64;
+        let send = |signer: &SigningKey, signed_body: &str| {
+            let signed = sign(signer, "POST", "/admin/freeze", now, signed_body);
+            Request::post("/admin/freeze")
+                .header(SIGNER_HEADER, signed.signer)
+                .header(TIMESTAMP_HEADER, now)
+                .header(SIGNATURE_HEADER, signed.signature)
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let unsigned = Request::post("/admin/freeze").body(Body::from(body)).unwrap();
+        assert_eq!(app.call(unsigned).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(app.call(send(&key(4), body)).await.unwrap().status(), StatusCode::FORBIDDEN);
+        assert_eq!(app.call(send(&admin, "{}")).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.call(send(&admin, body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let text = axum::body::to_bytes(response.into_body(), 1024).await.unwrap();
+        assert!(text.starts_with(b"outage by L1_"));
+    }
+}
@@ -2,19 +2,19 @@
 // Simplified: No JWT - authentication via cryptographic signatures on transactions
 
 use axum::{
-    extract::{State, Path},
+    extract::State,
     response::Json,
 };
 use serde::Deserialize;
+use utoipa::ToSchema;
 use serde_json::{json, Value};
+use crate::api_error::ApiError;
 use crate::app_state::SharedState;
-
-// L1 endpoint for balance queries
-const L1_ENDPOINT: &str = "http://localhost:8080";
+use crate::rpc::L1Backend;
 
 // ===== REQUEST/RESPONSE TYPES =====
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ConnectWalletRequest {
     /// Wallet address (L1_ABC123...) - supports both 'wallet_address' and 'address' fields
     #[serde(alias = "address")]
@@ -27,33 +27,14 @@ pub struct ConnectWalletRequest {
     pub username: Option<String>,
 }
 
-/// Response from L1 balance endpoint
-#[derive(Debug, Deserialize)]
-struct L1BalanceResponse {
-    address: String,
-    balance: f64,
-    success: bool,
-}
-
 /// Fetch balance from L1 for a wallet address
-async fn fetch_l1_balance(address: &str) -> Option<f64> {
-    let url = format!("{}/balance/{}", L1_ENDPOINT, address);
-    
-    match reqwest::Client::new()
-        .get(&url)
-        .timeout(std::time::Duration::from_secs(5))
-        .send()
-        .await
-    {
-        Ok(response) => {
-            if let Ok(data) = response.json::<L1BalanceResponse>().await {
-                if data.success && data.balance > 0.0 {
-                    println!("📡 L1 balance for {}: {} BB", address, data.balance);
-                    return Some(data.balance);
-                }
-            }
-            None
+async fn fetch_l1_balance(l1: &dyn L1Backend, address: &str) -> Option<f64> {
+    match l1.get_balance(address).await {
+        Ok(data) if data.balance > 0.0 => {
+            println!("📡 L1 balance for {}: {} BB", address, data.balance);
+            Some(data.balance)
         }
+        Ok(_) => None,
         Err(e) => {
             println!("⚠️ Failed to fetch L1 balance: {}", e);
             None
@@ -68,10 +49,20 @@ async fn fetch_l1_balance(address: &str) -> Option<f64> {
 /// Creates account if new, returns balance if existing
 /// Now fetches real L1 balance for new accounts!
 /// Accepts: { address, public_key, timestamp } or { wallet_address, username }
+#[utoipa::path(
+    post,
+    path = "/auth/connect",
+    tag = "auth",
+    request_body = ConnectWalletRequest,
+    responses(
+        (status = 200, description = "Wallet connected", body = Object),
+        (status = 400, description = "No address given", body = ApiError),
+    )
+)]
 pub async fn connect_wallet(
     State(state): State<SharedState>,
     Json(payload): Json<ConnectWalletRequest>,
-) -> Json<Value> {
+) -> Result<Json<Value>, ApiError> {
     // Support both 'wallet_address' and 'address' fields, fallback to public_key
     let wallet_address = payload.wallet_address
         .clone()
@@ -79,22 +70,22 @@ pub async fn connect_wallet(
         .unwrap_or_default();
     
     if wallet_address.is_empty() {
-        return Json(json!({
-            "success": false,
-            "error": "No wallet address provided. Use 'address', 'wallet_address', or 'public_key' field."
-        }));
+        return Err(ApiError::BadRequest(
+            "No wallet address provided. Use 'address', 'wallet_address', or 'public_key' field.".to_string()
+        ));
     }
     
     println!("💳 Wallet connect: {}", wallet_address);
     
     // Check L1 balance BEFORE acquiring lock (async operation)
-    let l1_balance = fetch_l1_balance(&wallet_address).await;
+    let l1 = state.lock().unwrap().l1.clone();
+    let l1_balance = fetch_l1_balance(l1.as_ref(), &wallet_address).await;
 
     let mut app_state = state.lock().unwrap();
     
     // Check if wallet exists in ledger
     let balance_exists = app_state.ledger.balance(&wallet_address) > 0.0 
-        || app_state.ledger.accounts.values().any(|addr| addr == &wallet_address);
+        || app_state.ledger.read().accounts.values().any(|addr| addr == &wallet_address);
     
     if !balance_exists {
         println!("🆕 New wallet detected: {}", wallet_address);
@@ -125,7 +116,7 @@ pub async fn connect_wallet(
                 wallet_address, initial_balance, balance_source)
         );
         
-        Json(json!({
+        Ok(Json(json!({
             "success": true,
             "wallet_address": wallet_address,
             "username": username,
@@ -134,7 +125,7 @@ pub async fn connect_wallet(
             "balance_source": balance_source,
             "is_new_account": true,
             "message": format!("Account created and funded with {} BB from {}", initial_balance, balance_source)
-        }))
+        })))
     } else {
         // Existing account - return balance
         let balance = app_state.ledger.balance(&wallet_address);
@@ -148,28 +139,13 @@ pub async fn connect_wallet(
                 wallet_address, balance)
         );
         
-        Json(json!({
+        Ok(Json(json!({
             "success": true,
             "wallet_address": wallet_address,
             "username": payload.username,
             "balance": balance,
             "l1_balance": l1_balance,
             "is_new_account": false
-        }))
+        })))
     }
 }
-
-/// GET /balance/:wallet
-/// Get balance for any wallet address
-pub async fn get_wallet_balance(
-    State(state): State<SharedState>,
-    Path(wallet): Path<String>,
-) -> Json<Value> {
-    let app_state = state.lock().unwrap();
-    let balance = app_state.ledger.balance(&wallet);
-    
-    Json(json!({
-        "wallet_address": wallet,
-        "balance": balance
-    }))
-}
@@ -0,0 +1,129 @@
+//! Session Settlement Receipts
+//!
+//! When an L2 session is settled to L1 the sequencer signs a receipt over
+//! the settled amounts and the L1 transaction. The receipt is stored on the
+//! session so the user can prove what was settled even if the L2 later
+//! disappears.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bridge_proof::verify_ed25519;
+use crate::state_root::SequencerKey;
+
+/// Domain separator for settlement receipts
+pub const SESSION_RECEIPT_DOMAIN: &[u8] = b"BLACKBOOK_SESSION_RECEIPT_V1";
+
+/// Bytes the sequencer signs:
+/// domain || session_id || 0 || wallet || 0 || final_l2_balance || pnl (f64 BE)
+/// || bet_count (u32 BE) || settled_at (u64 BE) || l1_tx_hash
+pub fn session_receipt_signing_bytes(
+    session_id: &str,
+    wallet_address: &str,
+    final_l2_balance: f64,
+    pnl: f64,
+    bet_count: u32,
+    settled_at: u64,
+    l1_tx_hash: &str,
+) -> Vec<u8> {
+    let mut bytes = SESSION_RECEIPT_DOMAIN.to_vec();
+    bytes.extend_from_slice(session_id.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(wallet_address.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(&final_l2_balance.to_be_bytes());
+    bytes.extend_from_slice(&pnl.to_be_bytes());
+    bytes.extend_from_slice(&bet_count.to_be_bytes());
+    bytes.extend_from_slice(&settled_at.to_be_bytes());
+    bytes.extend_from_slice(l1_tx_hash.as_bytes());
+    bytes
+}
+
+/// Sequencer-signed record of a session settled on L1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementReceipt {
+    pub session_id: String,
+    pub wallet_address: String,
+    pub final_l2_balance: f64,
+    pub pnl: f64,
+    pub bet_count: u32,
+    pub settled_at: u64,
+    pub l1_tx_hash: String,
+    /// Settled by the expiry job rather than by the user
+    pub automatic: bool,
+    pub sequencer_pubkey: String,
+    pub signature: String,
+}
+
+impl SettlementReceipt {
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign(
+        key: &SequencerKey,
+        session_id: &str,
+        wallet_address: &str,
+        final_l2_balance: f64,
+        pnl: f64,
+        bet_count: u32,
+        settled_at: u64,
+        l1_tx_hash: &str,
+        automatic: bool,
+    ) -> Self {
+        let signature = key.sign_hex(&session_receipt_signing_bytes(
+            session_id, wallet_address, final_l2_balance, pnl, bet_count, settled_at, l1_tx_hash,
+        ));
+        SettlementReceipt {
+            session_id: session_id.to_string(),
+            wallet_address: wallet_address.to_string(),
+            final_l2_balance,
+            pnl,
+            bet_count,
+            settled_at,
+            l1_tx_hash: l1_tx_hash.to_string(),
+            automatic,
+            sequencer_pubkey: key.pubkey_hex(),
+            signature,
+        }
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        session_receipt_signing_bytes(
+            &self.session_id,
+            &self.wallet_address,
+            self.final_l2_balance,
+            self.pnl,
+            self.bet_count,
+            self.settled_at,
+            &self.l1_tx_hash,
+        )
+    }
+
+    /// Check the sequencer signature
+    pub fn verify(&self) -> bool {
+        verify_ed25519(&self.sequencer_pubkey, &self.signature, &self.signing_bytes())
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_receipt_signature_covers_amounts() {
+        let key = SequencerKey::new(SigningKey::from_bytes(&[9u8; 32]));
+        let receipt = SettlementReceipt::sign(&key, "session_1", "L1_ALICE", 120.0, 20.0, 3, 1000, "0xabc", true);
+        assert!(receipt.verify());
+        assert_eq!(receipt.sequencer_pubkey, key.pubkey_hex());
+
+        let mut forged = receipt.clone();
+        forged.pnl = 200.0;
+        assert!(!forged.verify());
+
+        let mut forged = receipt;
+        forged.l1_tx_hash = "0xdef".to_string();
+        assert!(!forged.verify());
+    }
+}
@@ -0,0 +1,361 @@
+// ============================================================================
+// Session Settlement - Writes an L2 session's final balance to L1
+// ============================================================================
+//
+// Shared by POST /session/settle, forced exits and the expiry job. The
+// session's tracked L2 balance and PnL (updated by every trade and claim)
+// are sent to L1; on success that balance is cleared on L2 (it now lives on
+// L1), the session is marked settled and the sequencer signs a settlement
+// receipt that is stored on the session.
+//
+// Sessions never outlive `expires_at`: once expired, trading against the
+// session is blocked and the background job settles it automatically.
+// Transport errors are retried with exponential backoff; an L1 rejection
+// marks the session "settle_failed" for an operator (or the user) to settle.
+// ============================================================================
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app_state::SharedState;
+use crate::rpc::L1SessionSettleRequest;
+use crate::session_receipt::{session_receipt_signing_bytes, SettlementReceipt};
+
+/// First retry delay after a failed automatic settlement (doubles per attempt)
+const RETRY_BASE_SECS: u64 = 15;
+
+/// Longest delay between automatic settlement attempts
+const RETRY_MAX_SECS: u64 = 3600;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// A session settled on L1
+#[derive(Debug, Clone)]
+pub struct SessionSettlement {
+    pub session_id: String,
+    pub bet_count: u32,
+    pub final_l2_balance: f64,
+    pub pnl: f64,
+    pub l1_tx_hash: Option<String>,
+    pub new_l1_balance: Option<f64>,
+    pub receipt: SettlementReceipt,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettleError {
+    /// The wallet has no session
+    NoSession,
+    /// The session exists but is not active (carries its status)
+    NotActive(String),
+    /// L1 rejected the settlement
+    Rejected(String),
+    /// L1 could not be reached; the session remains active
+    L1Unavailable(String),
+}
+
+impl fmt::Display for SettleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettleError::NoSession => write!(f, "No active session found"),
+            SettleError::NotActive(status) => write!(f, "Session is not active (status: {})", status),
+            SettleError::Rejected(error) => write!(f, "{}", error),
+            SettleError::L1Unavailable(error) => write!(f, "L1 settlement failed: {}", error),
+        }
+    }
+}
+
+/// Settle `wallet`'s session to L1. `automatic` settlements (expiry job,
+/// freeze-and-exit) have no user signature; the sequencer signs instead.
+pub async fn settle(
+    state: &SharedState,
+    wallet: &str,
+    signature: &str,
+    timestamp: u64,
+    automatic: bool,
+) -> Result<SessionSettlement, SettleError> {
+    let (session, sequencer, l1) = {
+        let app = state.lock().unwrap();
+        let session = app.sessions.get(wallet).ok_or(SettleError::NoSession)?.clone();
+        if !(session.status == "active" || session.awaiting_settlement()) {
+            return Err(SettleError::NotActive(session.status));
+        }
+        (session, app.sequencer.clone(), app.l1.clone())
+    };
+
+    let final_l2_balance = session.l2_balance.max(0.0);
+    let pnl = session.pnl;
+    let signature = if automatic {
+        sequencer.sign_hex(&session_receipt_signing_bytes(
+            &session.session_id, wallet, final_l2_balance, pnl, session.bet_count, timestamp, "",
+        ))
+    } else {
+        signature.to_string()
+    };
+
+    let l1_request = L1SessionSettleRequest {
+        wallet_address: wallet.to_string(),
+        session_id: session.session_id.clone(),
+        final_l2_balance,
+        pnl,
+        bet_count: session.bet_count,
+        signature,
+        timestamp,
+    };
+
+    let l1_response = l1.settle_session(&l1_request).await
+        .map_err(SettleError::L1Unavailable)?;
+    if !l1_response.success {
+        return Err(SettleError::Rejected(
+            l1_response.error.unwrap_or_else(|| "L1 rejected settlement".to_string())
+        ));
+    }
+
+    let settled_at = now();
+    let receipt = SettlementReceipt::sign(
+        &sequencer,
+        &session.session_id,
+        wallet,
+        final_l2_balance,
+        pnl,
+        session.bet_count,
+        settled_at,
+        l1_response.l1_tx_hash.as_deref().unwrap_or_default(),
+        automatic,
+    );
+
+    let mut app = state.lock().unwrap();
+
+    // Clear the session balance on L2 (settled to L1); funds outside the
+    // session (e.g. bridge deposits) stay on L2
+    let debit = final_l2_balance.min(app.ledger.balance(wallet));
+    app.ledger.debit(wallet, debit);
+
+    if let Some(s) = app.sessions.get_mut(wallet) {
+        s.status = "settled".to_string();
+        s.l1_settlement_hash = l1_response.l1_tx_hash.clone();
+        s.settlement_receipt = Some(receipt.clone());
+        s.next_settle_at = None;
+        s.last_settle_error = None;
+    }
+
+    app.log_activity("🎮", "SESSION_SETTLE", &format!(
+        "{} settled session{}: {} bets, PnL: {:.2} BB, new L1 balance: {:?}",
+        wallet, if automatic { " automatically" } else { "" }, session.bet_count, pnl, l1_response.new_l1_balance
+    ));
+
+    Ok(SessionSettlement {
+        session_id: session.session_id,
+        bet_count: session.bet_count,
+        final_l2_balance,
+        pnl,
+        l1_tx_hash: l1_response.l1_tx_hash,
+        new_l1_balance: l1_response.new_l1_balance,
+        receipt,
+    })
+}
+
+/// Delay before automatic attempt `attempts + 1`
+fn retry_delay(attempts: u32) -> u64 {
+    RETRY_BASE_SECS.saturating_mul(1u64 << attempts.min(16)).min(RETRY_MAX_SECS)
+}
+
+/// Expire sessions past `expires_at` and settle every expired session whose
+/// retry time has come. Returns (wallet, result) per attempt.
+pub async fn run_once(state: &SharedState) -> Vec<(String, Result<SessionSettlement, SettleError>)> {
+    let now = now();
+    let due: Vec<String> = {
+        let mut app = state.lock().unwrap();
+        let mut expired = Vec::new();
+        for session in app.sessions.values_mut() {
+            if session.status == "active" && session.is_expired() {
+                session.status = "expired".to_string();
+                expired.push(session.wallet_address.clone());
+            }
+        }
+        for wallet in &expired {
+            app.log_activity("⏰", "SESSION_EXPIRED", &format!(
+                "{}'s session expired; settling to L1", wallet
+            ));
+        }
+        app.sessions.values()
+            .filter(|s| s.status == "expired" && s.next_settle_at.is_none_or(|t| t <= now))
+            .map(|s| s.wallet_address.clone())
+            .collect()
+    };
+
+    let mut results = Vec::new();
+    for wallet in due {
+        let result = settle(state, &wallet, "", now, true).await;
+        if let Err(e) = &result {
+            let mut app = state.lock().unwrap();
+            let attempts = match app.sessions.get_mut(&wallet) {
+                Some(session) => {
+                    session.settle_attempts += 1;
+                    session.last_settle_error = Some(e.to_string());
+                    match e {
+                        SettleError::L1Unavailable(_) => {
+                            session.next_settle_at = Some(now + retry_delay(session.settle_attempts));
+                        }
+                        _ => {
+                            session.status = "settle_failed".to_string();
+                            session.next_settle_at = None;
+                        }
+                    }
+                    session.settle_attempts
+                }
+                None => 0,
+            };
+            app.log_activity("⚠️", "SESSION_SETTLE_RETRY", &format!(
+                "{}'s expired session not settled (attempt {}): {}", wallet, attempts, e
+            ));
+        }
+        results.push((wallet, result));
+    }
+    results
+}
+
+/// Spawn the expiry job (every `sessions.settle_interval_secs`)
+pub fn spawn(state: SharedState) {
+    let interval_secs = state.lock().unwrap().config.sessions.settle_interval_secs;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            run_once(&state).await;
+        }
+    });
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::app_state::{test_state, L2Session};
+    use crate::rpc::{L1SessionStartRequest, MockL1, MockL1Backend, RecordingL1Backend};
+
+    const WALLET: &str = "L1_ALICE";
+
+    /// 100 BB session out of a 500 BB L1 balance, up 30 BB after one trade,
+    /// plus a 25 BB bridge deposit held on L2 outside the session. Settlement
+    /// goes through a recorder wrapping the mock L1.
+    fn setup(name: &str) -> (SharedState, Arc<MockL1Backend>, Arc<RecordingL1Backend>) {
+        let state = test_state(name);
+        let l1 = Arc::new(MockL1Backend::new(MockL1::new(0.0)));
+        let recorder = Arc::new(RecordingL1Backend::new(l1.clone()));
+        l1.state().mint(WALLET, 500.0);
+        let started = l1.state().start_session(&L1SessionStartRequest {
+            wallet_address: WALLET.to_string(),
+            l2_session_id: "session_test".to_string(),
+            requested_amount: 100.0,
+            signature: String::new(),
+            timestamp: now(),
+            nonce: "1".to_string(),
+        });
+        assert!(started.success);
+
+        let mut session = L2Session::new(WALLET.to_string(), 500.0, 100.0, started.session_id.unwrap());
+        session.record_trade(20.0, 50.0);
+        state.ledger.credit(WALLET, 100.0 + 30.0 + 25.0);
+        {
+            let mut app = state.lock().unwrap();
+            app.l1 = recorder.clone();
+            app.sessions.insert(WALLET.to_string(), session);
+        }
+        (state, l1, recorder)
+    }
+
+    #[tokio::test]
+    async fn test_settle_moves_session_balance_to_l1() {
+        let (state, l1, recorder) = setup("settle_session");
+
+        let settlement = settle(&state, WALLET, "sig", now(), false).await.unwrap();
+        assert_eq!(settlement.final_l2_balance, 130.0);
+        assert_eq!(settlement.pnl, 30.0);
+        assert_eq!(settlement.bet_count, 1);
+        assert_eq!(settlement.new_l1_balance, Some(530.0));
+        assert!(settlement.receipt.verify());
+        assert!(!settlement.receipt.automatic);
+
+        // The session balance left L2; the bridge deposit did not
+        assert_eq!(state.ledger.balance(WALLET), 25.0);
+        assert_eq!(l1.state().balance(WALLET).balance, 530.0);
+        {
+            let app = state.lock().unwrap();
+            let session = &app.sessions[WALLET];
+            assert_eq!(session.status, "settled");
+            assert_eq!(session.l1_settlement_hash, settlement.l1_tx_hash);
+            assert_eq!(session.settlement_receipt.as_ref().unwrap().signature, settlement.receipt.signature);
+        }
+
+        assert_eq!(
+            settle(&state, WALLET, "sig", now(), false).await.unwrap_err(),
+            SettleError::NotActive("settled".to_string())
+        );
+        assert_eq!(settle(&state, "L1_BOB", "sig", now(), false).await.unwrap_err(), SettleError::NoSession);
+        assert_eq!(state.ledger.balance(WALLET), 25.0);
+
+        // Only the first settle reached L1
+        let calls = recorder.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].method, "settle_session");
+        assert_eq!(calls[0].request["wallet_address"], WALLET);
+        assert_eq!(calls[0].request["final_l2_balance"], 130.0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_session_retries_then_fails_on_rejection() {
+        let (state, l1, recorder) = setup("settle_expired");
+        state.lock().unwrap().sessions.get_mut(WALLET).unwrap().expires_at = now() - 1;
+        l1.faults().fail_next = 1;
+
+        // L1 unreachable: the session waits for a retry, balances untouched
+        let results = run_once(&state).await;
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0].1, Err(SettleError::L1Unavailable(_))), "{:?}", results[0].1);
+        {
+            let app = state.lock().unwrap();
+            let session = &app.sessions[WALLET];
+            assert_eq!(session.status, "expired");
+            assert_eq!(session.settle_attempts, 1);
+            assert!(session.next_settle_at.unwrap() > now());
+        }
+        assert_eq!(state.ledger.balance(WALLET), 155.0);
+        assert!(run_once(&state).await.is_empty());
+
+        // L1 already closed the session on its side and rejects the settlement
+        l1.state().sessions.get_mut(WALLET).unwrap().status = "settled".to_string();
+        state.lock().unwrap().sessions.get_mut(WALLET).unwrap().next_settle_at = Some(0);
+        let results = run_once(&state).await;
+        assert_eq!(results[0].1.as_ref().unwrap_err(), &SettleError::Rejected("Session already settled".to_string()));
+        {
+            let app = state.lock().unwrap();
+            let session = &app.sessions[WALLET];
+            assert_eq!(session.status, "settle_failed");
+            assert_eq!(session.settle_attempts, 2);
+            assert_eq!(session.next_settle_at, None);
+            assert_eq!(session.last_settle_error.as_deref(), Some("Session already settled"));
+            assert!(session.settlement_receipt.is_none());
+        }
+        assert_eq!(state.ledger.balance(WALLET), 155.0);
+        assert_eq!(l1.state().balance(WALLET).balance, 400.0);
+
+        // Left for an operator: the job does not pick it up again
+        assert!(run_once(&state).await.is_empty());
+
+        let calls = recorder.calls();
+        assert_eq!(calls.iter().map(|c| c.method).collect::<Vec<_>>(), vec!["settle_session", "settle_session"]);
+        assert_eq!(calls[0].response.as_ref().unwrap_err(), "injected fault on /session/settle");
+        assert_eq!(calls[1].response.as_ref().unwrap()["success"], false);
+    }
+}
@@ -29,6 +29,7 @@ pub use mint::*;
 pub use redeem::*;
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
@@ -319,7 +320,7 @@ pub enum ShareTxType {
 }
 
 /// Statistics for the share system
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct SharesStats {
     pub total_shares_minted: f64,
     pub total_shares_redeemed: f64,
@@ -624,7 +625,7 @@ impl SharesManager {
 }
 
 /// Simple position info for API responses
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SimplePosition {
     pub market_id: String,
     pub yes_shares: f64,
@@ -636,7 +637,7 @@ pub struct SimplePosition {
 // ============================================================================
 
 /// Summary of a user's positions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserPositionsSummary {
     pub wallet: String,
     pub positions: Vec<PositionInfo>,
@@ -658,7 +659,7 @@ impl UserPositionsSummary {
 }
 
 /// Info about a single position
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PositionInfo {
     pub market_id: String,
     pub outcome: usize,
@@ -0,0 +1,194 @@
+// ============================================================================
+// State Publisher - Commits L2 state roots and posts them to L1
+// ============================================================================
+//
+// Every epoch (`sequencer.state_commit_interval_secs`) the publisher
+// snapshots account balances, share positions and markets, signs the Merkle
+// root with the sequencer key and posts it to L1 (epoch is the idempotency
+// key). Roots are only committed when the state changed.
+//
+// Only the newest root is ever posted: it commits to the whole state, so an
+// older root that never reached L1 is superseded rather than retried.
+// Transport errors are retried with backoff; a rejection marks the root
+// failed and the next epoch tries again with a fresh one.
+// ============================================================================
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app_state::SharedState;
+use crate::rpc::L1StateRootRequest;
+use crate::state_root::StateCommitment;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn to_l1_request(commitment: &StateCommitment) -> L1StateRootRequest {
+    L1StateRootRequest {
+        epoch: commitment.epoch,
+        root: commitment.root.clone(),
+        leaf_count: commitment.leaf_count,
+        l2_block: commitment.l2_block,
+        created_at: commitment.created_at,
+        sequencer_pubkey: commitment.sequencer_pubkey.clone(),
+        signature: commitment.signature.clone(),
+    }
+}
+
+/// Commit the current state (if it changed) and post the newest pending
+/// root to L1. Returns the newest commitment after the attempt.
+pub async fn commit_and_post(state: &SharedState) -> Option<StateCommitment> {
+    commit_and_post_at(state, now()).await
+}
+
+/// [`commit_and_post`] as of `now`
+pub async fn commit_and_post_at(state: &SharedState, now: u64) -> Option<StateCommitment> {
+    let (l1, due) = {
+        let mut app = state.lock().unwrap();
+        let leaves = app.state_leaves();
+        let l2_block = app.ledger.read().block;
+        let sequencer = app.sequencer.clone();
+        if let Some(commitment) = app.state_commitments.commit(&sequencer, leaves, l2_block, now) {
+            app.log_activity("🌳", "STATE_ROOT", &format!(
+                "Epoch {} state root {} ({} leaves, block {})",
+                commitment.epoch, commitment.root, commitment.leaf_count, commitment.l2_block
+            ));
+        }
+        (app.l1.clone(), app.state_commitments.due_post(now))
+    };
+
+    if let Some(commitment) = due {
+        // Post to L1 (no lock held)
+        let result = l1.submit_state_root(&to_l1_request(&commitment)).await;
+
+        let mut app = state.lock().unwrap();
+        match result {
+            Ok(response) if response.success => {
+                let tx_hash = response.l1_tx_hash.unwrap_or_default();
+                app.state_commitments.mark_posted(commitment.epoch, Some(tx_hash.clone()), now);
+                app.log_activity("📤", "STATE_ROOT_POSTED", &format!(
+                    "Epoch {} state root posted to L1: {}", commitment.epoch, tx_hash
+                ));
+            }
+            Ok(response) => {
+                let error = response.error.unwrap_or_else(|| "L1 rejected state root".to_string());
+                app.log_activity("❌", "STATE_ROOT_REJECTED", &format!(
+                    "Epoch {} state root rejected by L1: {}", commitment.epoch, error
+                ));
+                app.state_commitments.mark_failed(commitment.epoch, error);
+            }
+            Err(e) => {
+                app.log_activity("⚠️", "STATE_ROOT_RETRY", &format!(
+                    "Epoch {} state root not posted: {}", commitment.epoch, e
+                ));
+                app.state_commitments.record_post_failure(commitment.epoch, e, now);
+            }
+        }
+        if let Err(e) = app.save_bridge_state() {
+            eprintln!("⚠️  Failed to persist state commitments: {}", e);
+        }
+    }
+
+    state.lock().unwrap().state_commitments.latest().cloned()
+}
+
+/// Spawn the background publisher (every `sequencer.state_commit_interval_secs`)
+pub fn spawn(state: SharedState) {
+    let interval_secs = state.lock().unwrap().state_commitments.interval_secs;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            commit_and_post(&state).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::app_state::test_state_with;
+    use crate::rpc::{MockL1, MockL1Backend, RecordingL1Backend};
+    use crate::state_root::{CommitmentStatus, SequencerKey};
+
+    const SEED: &str = "0707070707070707070707070707070707070707070707070707070707070707";
+
+    fn setup(name: &str) -> (SharedState, Arc<MockL1Backend>, Arc<RecordingL1Backend>) {
+        let state = test_state_with(name, |config| config.sequencer.signing_key = SEED.to_string());
+        let l1 = Arc::new(MockL1Backend::new(MockL1::new(0.0)));
+        let recorder = Arc::new(RecordingL1Backend::new(l1.clone()));
+        state.lock().unwrap().l1 = recorder.clone();
+        state.ledger.credit("L1_ALICE", 100.0);
+        (state, l1, recorder)
+    }
+
+    #[tokio::test]
+    async fn test_tick_signs_root_with_sequencer_key_and_posts_it() {
+        let (state, l1, recorder) = setup("publisher_post");
+        let pubkey = SequencerKey::from_seed(Some([7; 32])).pubkey_hex();
+
+        let posted = commit_and_post(&state).await.unwrap();
+        assert_eq!(posted.status, CommitmentStatus::Posted);
+        assert_eq!(posted.sequencer_pubkey, pubkey);
+        assert!(posted.verify_signature());
+        assert!(posted.l1_tx_hash.is_some());
+
+        {
+            let l1_state = l1.state();
+            let (request, response) = &l1_state.state_roots[&posted.epoch];
+            assert!(response.success);
+            assert_eq!(request.root, posted.root);
+            assert_eq!(request.signature, posted.signature);
+            assert_eq!(l1_state.sequencer_pubkey.as_deref(), Some(pubkey.as_str()));
+        }
+
+        // Nothing changed: no new root, nothing re-posted
+        let again = commit_and_post(&state).await.unwrap();
+        assert_eq!(again.epoch, posted.epoch);
+        assert_eq!(l1.state().state_roots.len(), 1);
+
+        let calls = recorder.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].method, "submit_state_root");
+        assert_eq!(calls[0].request["epoch"], posted.epoch);
+        assert_eq!(calls[0].request["root"], posted.root);
+    }
+
+    #[tokio::test]
+    async fn test_l1_error_is_retried_after_backoff() {
+        let (state, l1, recorder) = setup("publisher_retry");
+        let now = now();
+        l1.faults().fail_next = 1;
+
+        let failed = commit_and_post_at(&state, now).await.unwrap();
+        assert_eq!(failed.status, CommitmentStatus::Pending);
+        assert_eq!(failed.post_attempts, 1);
+        assert!(failed.last_error.is_some());
+        assert!(failed.next_attempt_at > now);
+        assert!(l1.state().state_roots.is_empty());
+
+        // Not due yet: the same root waits out its backoff
+        let waiting = commit_and_post_at(&state, now).await.unwrap();
+        assert_eq!((waiting.epoch, waiting.status), (failed.epoch, CommitmentStatus::Pending));
+        assert!(l1.state().state_roots.is_empty());
+
+        let posted = commit_and_post_at(&state, failed.next_attempt_at).await.unwrap();
+        assert_eq!(posted.epoch, failed.epoch);
+        assert_eq!(posted.status, CommitmentStatus::Posted);
+        assert_eq!(posted.last_error, None);
+        assert_eq!(l1.state().state_roots[&posted.epoch].0.root, failed.root);
+
+        // The waiting tick made no call; the retry re-sent the same root
+        let calls = recorder.calls_to("submit_state_root");
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].response.is_err());
+        assert_eq!(calls[1].request, calls[0].request);
+        assert_eq!(recorder.calls().len(), 2);
+    }
+}
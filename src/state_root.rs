@@ -0,0 +1,497 @@
+//! L2 State Commitments
+//!
+//! The sequencer periodically commits to the full L2 state — account
+//! balances, outcome-share positions and market states — as a single Merkle
+//! root. Leaves are keyed ("account:<addr>", "position:<holder>:<market>:<outcome>",
+//! "market:<id>") and sorted by key, so the same state always produces the
+//! same root regardless of hash-map iteration order.
+//!
+//! Each root is signed by the sequencer key and posted to L1. Users can then
+//! prove their balance or positions against the latest root L1 accepted.
+
+use ed25519_dalek::{Signer, SigningKey};
+use merkle::{MerkleTree, Proof};
+use ring::digest::SHA256;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::bridge::relay_backoff_secs;
+use crate::bridge_proof::verify_ed25519;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Domain separator for state leaves
+pub const STATE_LEAF_DOMAIN: &[u8] = b"BLACKBOOK_L2_STATE_LEAF_V1";
+
+/// Domain separator for signed state roots
+pub const STATE_ROOT_DOMAIN: &[u8] = b"BLACKBOOK_L2_STATE_ROOT_V1";
+
+/// Default seconds between state commitments
+pub const DEFAULT_STATE_COMMIT_INTERVAL_SECS: u64 = 60;
+
+/// Commitments kept in memory (older ones are dropped)
+pub const MAX_STATE_COMMITMENTS: usize = 256;
+
+// ============================================================================
+// LEAVES & PROOFS
+// ============================================================================
+
+/// One entry of the committed L2 state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StateLeaf {
+    /// Ledger balance of an account
+    Account { address: String, confirmed: f64, pending: f64, locked: f64 },
+    /// Outcome shares held by an account
+    Position { holder: String, market_id: String, outcome: usize, shares: f64 },
+    /// Market lifecycle and pool state
+    Market {
+        market_id: String,
+        status: String,
+        winning_option: Option<usize>,
+        reserves: Vec<f64>,
+        total_volume: f64,
+    },
+}
+
+impl StateLeaf {
+    /// Sort key; unique per leaf
+    pub fn key(&self) -> String {
+        match self {
+            StateLeaf::Account { address, .. } => format!("account:{}", address),
+            StateLeaf::Position { holder, market_id, outcome, .. } => {
+                format!("position:{}:{}:{}", holder, market_id, outcome)
+            }
+            StateLeaf::Market { market_id, .. } => format!("market:{}", market_id),
+        }
+    }
+
+    /// The account this leaf belongs to (None for markets)
+    pub fn owner(&self) -> Option<&str> {
+        match self {
+            StateLeaf::Account { address, .. } => Some(address),
+            StateLeaf::Position { holder, .. } => Some(holder),
+            StateLeaf::Market { .. } => None,
+        }
+    }
+
+    /// Canonical leaf bytes: domain || key || 0 || JSON(leaf)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = STATE_LEAF_DOMAIN.to_vec();
+        bytes.extend_from_slice(self.key().as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&serde_json::to_vec(self).expect("state leaf serializes"));
+        bytes
+    }
+}
+
+/// Check an inclusion proof for `leaf` against a committed state root
+pub fn verify_state_proof(root: &[u8], leaf: &StateLeaf, proof: &Proof<Vec<u8>>) -> bool {
+    *proof.algorithm == SHA256 && proof.value == leaf.to_bytes() && proof.validate(root)
+}
+
+/// Merkle root over `leaves` (in any order)
+pub fn compute_state_root(leaves: Vec<StateLeaf>) -> Vec<u8> {
+    StateSnapshot::new(leaves).tree().root_hash().clone()
+}
+
+/// Bytes the sequencer signs for a state root:
+/// domain || epoch || l2_block || leaf_count || created_at (u64 BE) || root
+pub fn state_root_signing_bytes(epoch: u64, l2_block: u64, leaf_count: usize, created_at: u64, root: &[u8]) -> Vec<u8> {
+    let mut bytes = STATE_ROOT_DOMAIN.to_vec();
+    bytes.extend_from_slice(&epoch.to_be_bytes());
+    bytes.extend_from_slice(&l2_block.to_be_bytes());
+    bytes.extend_from_slice(&(leaf_count as u64).to_be_bytes());
+    bytes.extend_from_slice(&created_at.to_be_bytes());
+    bytes.extend_from_slice(root);
+    bytes
+}
+
+/// Proof that a leaf is part of a posted state root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateProof {
+    pub epoch: u64,
+    /// State root (hex)
+    pub root: String,
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    pub leaf: StateLeaf,
+    pub proof: Proof<Vec<u8>>,
+}
+
+// ============================================================================
+// SEQUENCER KEY
+// ============================================================================
+
+/// Key the sequencer signs state roots with
+#[derive(Clone)]
+pub struct SequencerKey {
+    signing_key: SigningKey,
+}
+
+impl SequencerKey {
+    pub fn new(signing_key: SigningKey) -> Self {
+        SequencerKey { signing_key }
+    }
+
+    /// Key from an Ed25519 seed, or a throwaway random key when none is
+    /// configured (mock L1 only; `Config::validate` requires a seed otherwise)
+    pub fn from_seed(seed: Option<[u8; 32]>) -> Self {
+        let seed = seed.unwrap_or_else(rand::random);
+        Self::new(SigningKey::from_bytes(&seed))
+    }
+
+    pub fn pubkey_hex(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().as_bytes())
+    }
+
+    pub fn sign_hex(&self, message: &[u8]) -> String {
+        hex::encode(self.signing_key.sign(message).to_bytes())
+    }
+}
+
+impl std::fmt::Debug for SequencerKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SequencerKey").field("pubkey", &self.pubkey_hex()).finish()
+    }
+}
+
+// ============================================================================
+// COMMITMENTS
+// ============================================================================
+
+/// Where a state root is in its trip to L1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitmentStatus {
+    /// Signed, not yet accepted by L1
+    Pending,
+    /// Accepted by L1
+    Posted,
+    /// A newer root was computed before this one reached L1
+    Superseded,
+    /// L1 rejected the root
+    Failed,
+}
+
+/// A signed state root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateCommitment {
+    pub epoch: u64,
+    /// Merkle root over the sorted state leaves (hex)
+    pub root: String,
+    pub leaf_count: usize,
+    /// Ledger block height the root was taken at
+    pub l2_block: u64,
+    pub created_at: u64,
+    pub sequencer_pubkey: String,
+    pub signature: String,
+    pub status: CommitmentStatus,
+    pub l1_tx_hash: Option<String>,
+    pub posted_at: Option<u64>,
+    /// Failed attempts to reach L1
+    pub post_attempts: u32,
+    pub last_error: Option<String>,
+    /// Earliest time the root should be posted again
+    pub next_attempt_at: u64,
+}
+
+impl StateCommitment {
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let root = hex::decode(&self.root).unwrap_or_default();
+        state_root_signing_bytes(self.epoch, self.l2_block, self.leaf_count, self.created_at, &root)
+    }
+
+    /// Check the sequencer signature over this root
+    pub fn verify_signature(&self) -> bool {
+        verify_ed25519(&self.sequencer_pubkey, &self.signature, &self.signing_bytes())
+    }
+}
+
+/// The leaves behind one commitment, kept so proofs can be served
+#[derive(Debug, Clone, Default)]
+struct StateSnapshot {
+    leaves: Vec<StateLeaf>,
+}
+
+impl StateSnapshot {
+    fn new(mut leaves: Vec<StateLeaf>) -> Self {
+        leaves.sort_by_key(|l| l.key());
+        StateSnapshot { leaves }
+    }
+
+    fn tree(&self) -> MerkleTree<Vec<u8>> {
+        MerkleTree::from_vec(&SHA256, self.leaves.iter().map(|l| l.to_bytes()).collect())
+    }
+}
+
+/// Computes, signs and tracks L2 state commitments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateCommitter {
+    #[serde(skip)]
+    pub interval_secs: u64,
+    commitments: BTreeMap<u64, StateCommitment>,
+    /// Leaves for the newest commitment and the newest posted one
+    #[serde(skip)]
+    snapshots: BTreeMap<u64, StateSnapshot>,
+    next_epoch: u64,
+}
+
+impl Default for StateCommitter {
+    fn default() -> Self {
+        Self::new(DEFAULT_STATE_COMMIT_INTERVAL_SECS)
+    }
+}
+
+impl StateCommitter {
+    pub fn new(interval_secs: u64) -> Self {
+        StateCommitter {
+            interval_secs,
+            commitments: BTreeMap::new(),
+            snapshots: BTreeMap::new(),
+            next_epoch: 1,
+        }
+    }
+
+    /// Take over persisted commitments (leaves are not persisted, so proofs
+    /// become available again after the next commitment is posted)
+    pub fn restore(&mut self, persisted: StateCommitter) {
+        self.commitments = persisted.commitments;
+        self.next_epoch = persisted.next_epoch.max(1);
+    }
+
+    /// Commit to `leaves`. Returns None when the root hasn't changed since
+    /// the last commitment. Older roots still waiting for L1 are superseded.
+    pub fn commit(&mut self, key: &SequencerKey, leaves: Vec<StateLeaf>, l2_block: u64, now: u64) -> Option<StateCommitment> {
+        let snapshot = StateSnapshot::new(leaves);
+        let root = snapshot.tree().root_hash().clone();
+        let root_hex = hex::encode(&root);
+        if self.latest().is_some_and(|c| c.root == root_hex) {
+            return None;
+        }
+
+        let epoch = self.next_epoch;
+        self.next_epoch += 1;
+        let leaf_count = snapshot.leaves.len();
+        let signature = key.sign_hex(&state_root_signing_bytes(epoch, l2_block, leaf_count, now, &root));
+
+        for older in self.commitments.values_mut() {
+            if older.status == CommitmentStatus::Pending {
+                older.status = CommitmentStatus::Superseded;
+            }
+        }
+        let commitment = StateCommitment {
+            epoch,
+            root: root_hex,
+            leaf_count,
+            l2_block,
+            created_at: now,
+            sequencer_pubkey: key.pubkey_hex(),
+            signature,
+            status: CommitmentStatus::Pending,
+            l1_tx_hash: None,
+            posted_at: None,
+            post_attempts: 0,
+            last_error: None,
+            next_attempt_at: now,
+        };
+        self.commitments.insert(epoch, commitment.clone());
+        self.snapshots.insert(epoch, snapshot);
+        self.prune();
+        Some(commitment)
+    }
+
+    /// The newest root, if it still has to be posted at `now`
+    pub fn due_post(&self, now: u64) -> Option<StateCommitment> {
+        self.latest()
+            .filter(|c| c.status == CommitmentStatus::Pending && c.next_attempt_at <= now)
+            .cloned()
+    }
+
+    /// L1 accepted the root
+    pub fn mark_posted(&mut self, epoch: u64, l1_tx_hash: Option<String>, now: u64) -> Option<StateCommitment> {
+        let commitment = self.commitments.get_mut(&epoch)?;
+        if commitment.status != CommitmentStatus::Pending {
+            return None;
+        }
+        commitment.status = CommitmentStatus::Posted;
+        commitment.l1_tx_hash = l1_tx_hash;
+        commitment.posted_at = Some(now);
+        commitment.last_error = None;
+        let posted = commitment.clone();
+        self.prune();
+        Some(posted)
+    }
+
+    /// L1 definitively rejected the root
+    pub fn mark_failed(&mut self, epoch: u64, error: String) -> Option<StateCommitment> {
+        let commitment = self.commitments.get_mut(&epoch)?;
+        if commitment.status != CommitmentStatus::Pending {
+            return None;
+        }
+        commitment.status = CommitmentStatus::Failed;
+        commitment.last_error = Some(error);
+        Some(commitment.clone())
+    }
+
+    /// Failed attempt to reach L1; schedule a retry with backoff
+    pub fn record_post_failure(&mut self, epoch: u64, error: String, now: u64) -> Option<StateCommitment> {
+        let commitment = self.commitments.get_mut(&epoch)?;
+        commitment.post_attempts += 1;
+        commitment.last_error = Some(error);
+        commitment.next_attempt_at = now + relay_backoff_secs(commitment.post_attempts);
+        Some(commitment.clone())
+    }
+
+    /// Drop leaves nobody can ask proofs for, and cap the history
+    fn prune(&mut self) {
+        let newest = self.latest().map(|c| c.epoch);
+        let posted = self.latest_posted().map(|c| c.epoch);
+        self.snapshots.retain(|epoch, _| Some(*epoch) == newest || Some(*epoch) == posted);
+        while self.commitments.len() > MAX_STATE_COMMITMENTS {
+            self.commitments.pop_first();
+        }
+    }
+
+    pub fn commitment(&self, epoch: u64) -> Option<&StateCommitment> {
+        self.commitments.get(&epoch)
+    }
+
+    pub fn latest(&self) -> Option<&StateCommitment> {
+        self.commitments.values().next_back()
+    }
+
+    /// Newest root L1 has accepted
+    pub fn latest_posted(&self) -> Option<&StateCommitment> {
+        self.commitments.values().rev().find(|c| c.status == CommitmentStatus::Posted)
+    }
+
+    /// All commitments, newest first
+    pub fn commitments(&self) -> Vec<&StateCommitment> {
+        self.commitments.values().rev().collect()
+    }
+
+    /// Proofs for every leaf owned by `address` (account and positions)
+    /// against the latest posted root
+    pub fn proofs_for_account(&self, address: &str) -> Option<(StateCommitment, Vec<StateProof>)> {
+        let commitment = self.latest_posted()?;
+        let snapshot = self.snapshots.get(&commitment.epoch)?;
+        let tree = snapshot.tree();
+        let proofs = snapshot.leaves.iter()
+            .enumerate()
+            .filter(|(_, leaf)| leaf.owner() == Some(address))
+            .filter_map(|(index, leaf)| {
+                Some(StateProof {
+                    epoch: commitment.epoch,
+                    root: commitment.root.clone(),
+                    leaf_index: index,
+                    leaf_count: snapshot.leaves.len(),
+                    leaf: leaf.clone(),
+                    proof: tree.gen_nth_proof(index)?,
+                })
+            })
+            .collect();
+        Some((commitment.clone(), proofs))
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> SequencerKey {
+        SequencerKey::new(SigningKey::from_bytes(&[9u8; 32]))
+    }
+
+    fn account(address: &str, confirmed: f64) -> StateLeaf {
+        StateLeaf::Account { address: address.into(), confirmed, pending: 0.0, locked: 0.0 }
+    }
+
+    fn state() -> Vec<StateLeaf> {
+        vec![
+            account("L1_BOB", 50.0),
+            StateLeaf::Position { holder: "L1_ALICE".into(), market_id: "m1".into(), outcome: 0, shares: 12.5 },
+            StateLeaf::Market {
+                market_id: "m1".into(),
+                status: "Active".into(),
+                winning_option: None,
+                reserves: vec![100.0, 80.0],
+                total_volume: 20.0,
+            },
+            account("L1_ALICE", 100.0),
+        ]
+    }
+
+    #[test]
+    fn test_root_is_order_independent_and_signed() {
+        let mut a = StateCommitter::new(60);
+        let mut b = StateCommitter::new(60);
+        let first = a.commit(&key(), state(), 7, 100).unwrap();
+        let mut reversed = state();
+        reversed.reverse();
+        let second = b.commit(&key(), reversed, 7, 100).unwrap();
+        assert_eq!(first.root, second.root);
+        assert!(first.verify_signature());
+
+        // Unchanged state is not committed again
+        assert!(a.commit(&key(), state(), 8, 160).is_none());
+
+        let mut tampered = first.clone();
+        tampered.l2_block = 8;
+        assert!(!tampered.verify_signature());
+    }
+
+    #[test]
+    fn test_account_proofs_against_posted_root() {
+        let mut committer = StateCommitter::new(60);
+        let first = committer.commit(&key(), state(), 1, 100).unwrap();
+        assert!(committer.proofs_for_account("L1_ALICE").is_none()); // nothing posted yet
+
+        committer.mark_posted(first.epoch, Some("0xroot".into()), 110).unwrap();
+        let (posted, proofs) = committer.proofs_for_account("L1_ALICE").unwrap();
+        assert_eq!(proofs.len(), 2); // balance + position
+        let root = hex::decode(&posted.root).unwrap();
+        for p in &proofs {
+            assert!(verify_state_proof(&root, &p.leaf, &p.proof));
+        }
+
+        // Proofs keep pointing at the posted root until a newer one is accepted
+        let mut changed = state();
+        changed[3] = account("L1_ALICE", 40.0);
+        let second = committer.commit(&key(), changed, 2, 160).unwrap();
+        let (still, _) = committer.proofs_for_account("L1_ALICE").unwrap();
+        assert_eq!(still.epoch, first.epoch);
+
+        committer.mark_posted(second.epoch, None, 170).unwrap();
+        let (latest, proofs) = committer.proofs_for_account("L1_ALICE").unwrap();
+        assert_eq!(latest.epoch, second.epoch);
+        let balance = proofs.iter().find(|p| matches!(p.leaf, StateLeaf::Account { .. })).unwrap();
+        assert_eq!(balance.leaf, account("L1_ALICE", 40.0));
+        assert!(!verify_state_proof(&root, &balance.leaf, &balance.proof));
+    }
+
+    #[test]
+    fn test_newer_root_supersedes_unposted() {
+        let mut committer = StateCommitter::new(60);
+        let first = committer.commit(&key(), state(), 1, 100).unwrap();
+        committer.record_post_failure(first.epoch, "L1 down".into(), 100).unwrap();
+        assert!(committer.due_post(100).is_none());
+
+        let second = committer.commit(&key(), vec![account("L1_CAROL", 1.0)], 2, 110).unwrap();
+        assert_eq!(committer.commitment(first.epoch).unwrap().status, CommitmentStatus::Superseded);
+        assert_eq!(committer.due_post(110).unwrap().epoch, second.epoch);
+        assert!(committer.mark_posted(first.epoch, None, 120).is_none());
+
+        let json = serde_json::to_string(&committer).unwrap();
+        let mut restored = StateCommitter::new(60);
+        restored.restore(serde_json::from_str(&json).unwrap());
+        let third = restored.commit(&key(), state(), 3, 200).unwrap();
+        assert_eq!(third.epoch, second.epoch + 1);
+    }
+}
@@ -0,0 +1,400 @@
+//! Batched L2 → L1 Withdrawals
+//!
+//! Instead of one L1 call per withdrawal, L2→L1 withdrawals are queued into
+//! batches. A batch is sealed once it holds `max_size` withdrawals or its
+//! oldest entry is `max_age_secs` old, and is then committed to L1 as a
+//! single Merkle root over (address, amount, nonce) leaves.
+//!
+//! Every withdrawal gets a unique, monotonically increasing nonce from the
+//! batcher, so leaves never collide and L1 can dedupe claims by nonce. Users
+//! claim on L1 independently with the inclusion proof for their leaf.
+
+use merkle::{MerkleTree, Proof};
+use ring::digest::SHA256;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::bridge::relay_backoff_secs;
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/// Domain separator for withdrawal leaves
+pub const WITHDRAWAL_LEAF_DOMAIN: &[u8] = b"BLACKBOOK_L2_WITHDRAWAL_V1";
+
+/// Default maximum withdrawals per batch
+pub const DEFAULT_BATCH_MAX_SIZE: usize = 100;
+
+/// Default maximum time a batch stays open (seconds)
+pub const DEFAULT_BATCH_MAX_AGE_SECS: u64 = 30;
+
+// ============================================================================
+// LEAVES & PROOFS
+// ============================================================================
+
+/// One withdrawal in a batch: what the user claims on L1
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithdrawalLeaf {
+    /// L1 recipient address
+    pub address: String,
+    pub amount: f64,
+    /// Batcher-assigned withdrawal nonce (unique across all batches)
+    pub nonce: u64,
+}
+
+impl WithdrawalLeaf {
+    /// Canonical leaf bytes: domain || address || 0 || amount (f64 BE) || nonce (u64 BE)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(WITHDRAWAL_LEAF_DOMAIN.len() + self.address.len() + 17);
+        bytes.extend_from_slice(WITHDRAWAL_LEAF_DOMAIN);
+        bytes.extend_from_slice(self.address.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&self.amount.to_be_bytes());
+        bytes.extend_from_slice(&self.nonce.to_be_bytes());
+        bytes
+    }
+}
+
+/// Check an inclusion proof for `leaf` against a committed batch root
+pub fn verify_withdrawal_proof(root: &[u8], leaf: &WithdrawalLeaf, proof: &Proof<Vec<u8>>) -> bool {
+    *proof.algorithm == SHA256 && proof.value == leaf.to_bytes() && proof.validate(root)
+}
+
+/// Everything a user needs to claim a batched withdrawal on L1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalProof {
+    pub batch_id: u64,
+    pub batch_status: BatchStatus,
+    /// Merkle root (hex)
+    pub root: String,
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    pub leaf: WithdrawalLeaf,
+    pub proof: Proof<Vec<u8>>,
+    /// L1 tx that committed the root (once committed)
+    pub l1_tx_hash: Option<String>,
+}
+
+// ============================================================================
+// BATCH
+// ============================================================================
+
+/// Lifecycle of a withdrawal batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchStatus {
+    /// Accepting withdrawals
+    Open,
+    /// Root fixed, waiting for L1 to accept the commitment
+    Sealed,
+    /// Root committed on L1; withdrawals are claimable
+    Committed,
+    /// L1 rejected the commitment; withdrawals were refunded
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalBatch {
+    pub batch_id: u64,
+    pub status: BatchStatus,
+    pub leaves: Vec<WithdrawalLeaf>,
+    /// Bridge id for each leaf (same order as `leaves`)
+    pub bridge_ids: Vec<String>,
+    /// Merkle root (hex), set when sealed
+    pub root: Option<String>,
+    pub created_at: u64,
+    pub sealed_at: Option<u64>,
+    pub committed_at: Option<u64>,
+    pub l1_tx_hash: Option<String>,
+    /// Failed commit attempts (L1 unreachable or errored)
+    pub relay_attempts: u32,
+    pub last_relay_error: Option<String>,
+    /// Earliest time the relayer should try to commit again
+    pub next_attempt_at: u64,
+    pub error: Option<String>,
+}
+
+impl WithdrawalBatch {
+    fn new(batch_id: u64, now: u64) -> Self {
+        WithdrawalBatch {
+            batch_id,
+            status: BatchStatus::Open,
+            leaves: Vec::new(),
+            bridge_ids: Vec::new(),
+            root: None,
+            created_at: now,
+            sealed_at: None,
+            committed_at: None,
+            l1_tx_hash: None,
+            relay_attempts: 0,
+            last_relay_error: None,
+            next_attempt_at: 0,
+            error: None,
+        }
+    }
+
+    fn tree(&self) -> MerkleTree<Vec<u8>> {
+        MerkleTree::from_vec(&SHA256, self.leaves.iter().map(|l| l.to_bytes()).collect())
+    }
+
+    /// Merkle root over the current leaves
+    pub fn compute_root(&self) -> Vec<u8> {
+        self.tree().root_hash().clone()
+    }
+
+    pub fn total_amount(&self) -> f64 {
+        self.leaves.iter().map(|l| l.amount).sum()
+    }
+}
+
+// ============================================================================
+// BATCHER
+// ============================================================================
+
+/// Queues withdrawals into batches and tracks their L1 commitments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalBatcher {
+    /// Batch withdrawals (false: relay each withdrawal individually)
+    #[serde(skip)]
+    pub enabled: bool,
+    #[serde(skip)]
+    pub max_size: usize,
+    #[serde(skip)]
+    pub max_age_secs: u64,
+    batches: BTreeMap<u64, WithdrawalBatch>,
+    /// bridge_id → (batch_id, leaf index)
+    by_bridge: HashMap<String, (u64, usize)>,
+    open_batch: Option<u64>,
+    next_batch_id: u64,
+    next_nonce: u64,
+}
+
+impl Default for WithdrawalBatcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_BATCH_MAX_SIZE, DEFAULT_BATCH_MAX_AGE_SECS)
+    }
+}
+
+impl WithdrawalBatcher {
+    pub fn new(max_size: usize, max_age_secs: u64) -> Self {
+        WithdrawalBatcher {
+            enabled: true,
+            max_size: max_size.max(1),
+            max_age_secs,
+            batches: BTreeMap::new(),
+            by_bridge: HashMap::new(),
+            open_batch: None,
+            next_batch_id: 1,
+            next_nonce: 1,
+        }
+    }
+
+    /// Take over persisted batches, keeping this batcher's configuration
+    pub fn restore(&mut self, persisted: WithdrawalBatcher) {
+        self.batches = persisted.batches;
+        self.by_bridge = persisted.by_bridge;
+        self.open_batch = persisted.open_batch;
+        self.next_batch_id = persisted.next_batch_id.max(1);
+        self.next_nonce = persisted.next_nonce.max(1);
+    }
+
+    /// Queue a withdrawal; returns (batch_id, leaf). Seals the batch when full.
+    pub fn enqueue(&mut self, bridge_id: &str, address: &str, amount: f64, now: u64) -> (u64, WithdrawalLeaf) {
+        let batch_id = match self.open_batch {
+            Some(id) => id,
+            None => {
+                let id = self.next_batch_id;
+                self.next_batch_id += 1;
+                self.batches.insert(id, WithdrawalBatch::new(id, now));
+                self.open_batch = Some(id);
+                id
+            }
+        };
+
+        let leaf = WithdrawalLeaf {
+            address: address.to_string(),
+            amount,
+            nonce: self.next_nonce,
+        };
+        self.next_nonce += 1;
+
+        let batch = self.batches.get_mut(&batch_id).expect("open batch exists");
+        batch.leaves.push(leaf.clone());
+        batch.bridge_ids.push(bridge_id.to_string());
+        self.by_bridge.insert(bridge_id.to_string(), (batch_id, batch.leaves.len() - 1));
+
+        if batch.leaves.len() >= self.max_size {
+            self.seal(batch_id, now);
+        }
+        (batch_id, leaf)
+    }
+
+    /// Seal the open batch if it has reached its maximum age
+    pub fn seal_due(&mut self, now: u64) -> Option<u64> {
+        let batch_id = self.open_batch?;
+        let batch = self.batches.get(&batch_id)?;
+        if now.saturating_sub(batch.created_at) >= self.max_age_secs {
+            self.seal(batch_id, now);
+            return Some(batch_id);
+        }
+        None
+    }
+
+    fn seal(&mut self, batch_id: u64, now: u64) {
+        if let Some(batch) = self.batches.get_mut(&batch_id) {
+            batch.root = Some(hex::encode(batch.compute_root()));
+            batch.status = BatchStatus::Sealed;
+            batch.sealed_at = Some(now);
+            batch.next_attempt_at = now;
+        }
+        if self.open_batch == Some(batch_id) {
+            self.open_batch = None;
+        }
+    }
+
+    /// Sealed batches whose commitment should be (re)sent at `now`
+    pub fn due_commits(&self, now: u64) -> Vec<WithdrawalBatch> {
+        self.batches
+            .values()
+            .filter(|b| b.status == BatchStatus::Sealed && b.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// L1 accepted the batch root
+    pub fn mark_committed(&mut self, batch_id: u64, l1_tx_hash: Option<String>, now: u64) -> Option<WithdrawalBatch> {
+        let batch = self.batches.get_mut(&batch_id)?;
+        if batch.status != BatchStatus::Sealed {
+            return None;
+        }
+        batch.status = BatchStatus::Committed;
+        batch.committed_at = Some(now);
+        batch.l1_tx_hash = l1_tx_hash;
+        batch.last_relay_error = None;
+        Some(batch.clone())
+    }
+
+    /// L1 definitively rejected the batch root
+    pub fn mark_failed(&mut self, batch_id: u64, error: String) -> Option<WithdrawalBatch> {
+        let batch = self.batches.get_mut(&batch_id)?;
+        if batch.status != BatchStatus::Sealed {
+            return None;
+        }
+        batch.status = BatchStatus::Failed;
+        batch.error = Some(error);
+        Some(batch.clone())
+    }
+
+    /// Failed attempt to reach L1; schedule a retry with backoff
+    pub fn record_relay_failure(&mut self, batch_id: u64, error: String, now: u64) -> Option<WithdrawalBatch> {
+        let batch = self.batches.get_mut(&batch_id)?;
+        batch.relay_attempts += 1;
+        batch.last_relay_error = Some(error);
+        batch.next_attempt_at = now + relay_backoff_secs(batch.relay_attempts);
+        Some(batch.clone())
+    }
+
+    pub fn batch(&self, batch_id: u64) -> Option<&WithdrawalBatch> {
+        self.batches.get(&batch_id)
+    }
+
+    /// All batches, newest first
+    pub fn batches(&self) -> Vec<&WithdrawalBatch> {
+        self.batches.values().rev().collect()
+    }
+
+    /// Batch and leaf index for a bridge
+    pub fn locate(&self, bridge_id: &str) -> Option<(u64, usize)> {
+        self.by_bridge.get(bridge_id).copied()
+    }
+
+    /// Inclusion proof for a bridge's withdrawal (once its batch is sealed)
+    pub fn proof_for(&self, bridge_id: &str) -> Option<WithdrawalProof> {
+        let (batch_id, index) = self.locate(bridge_id)?;
+        let batch = self.batches.get(&batch_id)?;
+        let root = batch.root.clone()?;
+        let proof = batch.tree().gen_nth_proof(index)?;
+        Some(WithdrawalProof {
+            batch_id,
+            batch_status: batch.status,
+            root,
+            leaf_index: index,
+            leaf_count: batch.leaves.len(),
+            leaf: batch.leaves[index].clone(),
+            proof,
+            l1_tx_hash: batch.l1_tx_hash.clone(),
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batches_seal_by_count_and_age() {
+        let mut batcher = WithdrawalBatcher::new(2, 30);
+        let (b1, first) = batcher.enqueue("w1", "L1_alice", 10.0, 100);
+        let (b1_again, second) = batcher.enqueue("w2", "L1_bob", 5.0, 101);
+        assert_eq!(b1, b1_again);
+        assert_ne!(first.nonce, second.nonce);
+        assert_eq!(batcher.batch(b1).unwrap().status, BatchStatus::Sealed);
+
+        // Next withdrawal opens a new batch that seals on age
+        let (b2, _) = batcher.enqueue("w3", "L1_carol", 1.0, 102);
+        assert_ne!(b1, b2);
+        assert_eq!(batcher.seal_due(110), None);
+        assert_eq!(batcher.seal_due(132), Some(b2));
+        assert_eq!(batcher.due_commits(132).len(), 2);
+    }
+
+    #[test]
+    fn test_proofs_verify_against_root() {
+        let mut batcher = WithdrawalBatcher::new(10, 30);
+        for i in 0..5 {
+            batcher.enqueue(&format!("w{}", i), "L1_same", 10.0, 100);
+        }
+        assert!(batcher.proof_for("w3").is_none()); // still open
+        let batch_id = batcher.seal_due(200).unwrap();
+
+        let root = hex::decode(batcher.batch(batch_id).unwrap().root.clone().unwrap()).unwrap();
+        for i in 0..5 {
+            let claim = batcher.proof_for(&format!("w{}", i)).unwrap();
+            assert_eq!(claim.leaf_index, i);
+            assert_eq!(claim.proof.index(claim.leaf_count), i);
+            assert!(verify_withdrawal_proof(&root, &claim.leaf, &claim.proof));
+        }
+
+        // A proof doesn't verify for a different amount
+        let claim = batcher.proof_for("w0").unwrap();
+        let inflated = WithdrawalLeaf { amount: 1000.0, ..claim.leaf.clone() };
+        assert!(!verify_withdrawal_proof(&root, &inflated, &claim.proof));
+    }
+
+    #[test]
+    fn test_commit_lifecycle_and_restore() {
+        let mut batcher = WithdrawalBatcher::new(1, 30);
+        let (batch_id, _) = batcher.enqueue("w1", "L1_alice", 10.0, 100);
+
+        let retried = batcher.record_relay_failure(batch_id, "timeout".into(), 100).unwrap();
+        assert!(batcher.due_commits(100).is_empty());
+        assert_eq!(batcher.due_commits(retried.next_attempt_at).len(), 1);
+
+        batcher.mark_committed(batch_id, Some("0xbatch".into()), 120).unwrap();
+        assert!(batcher.due_commits(u64::MAX).is_empty());
+        assert!(batcher.mark_failed(batch_id, "late".into()).is_none());
+
+        let json = serde_json::to_string(&batcher).unwrap();
+        let mut restored = WithdrawalBatcher::new(1, 30);
+        restored.restore(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.proof_for("w1").unwrap().l1_tx_hash, Some("0xbatch".into()));
+        let (next_batch, leaf) = restored.enqueue("w2", "L1_bob", 1.0, 130);
+        assert_eq!(next_batch, batch_id + 1);
+        assert_eq!(leaf.nonce, 2);
+    }
+}